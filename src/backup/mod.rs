@@ -0,0 +1,325 @@
+//! Backup bundles: every day file, the config and optionally the running timer in one zip
+//!
+//! A bundle holds `days/YYYY-MM-DD.json`, `config.toml` and `running_timer.json`.
+//! Restoring checks every entry before writing anything, so a bad bundle or a
+//! refused overwrite leaves the data directory as it was.
+
+use crate::models::DayData;
+use crate::storage::Storage;
+use crate::timer::TimerState;
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use time::Date;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const DAYS_DIR: &str = "days/";
+const CONFIG_ENTRY: &str = "config.toml";
+const TIMER_ENTRY: &str = "running_timer.json";
+
+/// What a bundle holds, once read
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub days: Vec<DayData>,
+    pub config: Option<String>,
+    pub timer: Option<TimerState>,
+}
+
+/// Write every stored day, the config at `config_path` (if there is one) and, with
+/// `include_timer`, the running timer to a zip at `output`
+pub fn write_bundle(
+    storage: &Storage,
+    config_path: &Path,
+    include_timer: bool,
+    output: &Path,
+) -> Result<Bundle> {
+    let bundle = Bundle {
+        days: storage.load_all_days()?,
+        config: match config_path.exists() {
+            true => Some(
+                fs::read_to_string(config_path)
+                    .with_context(|| format!("Failed to read config: {:?}", config_path))?,
+            ),
+            false => None,
+        },
+        timer: match include_timer {
+            true => storage.load_active_timer()?,
+            false => None,
+        },
+    };
+
+    let file = fs::File::create(output)
+        .with_context(|| format!("Failed to create bundle: {:?}", output))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut add = |name: String, contents: String| -> Result<()> {
+        zip.start_file(name, options)?;
+        zip.write_all(contents.as_bytes())?;
+        Ok(())
+    };
+    for day in &bundle.days {
+        let json = serde_json::to_string_pretty(day).context("Failed to serialize day")?;
+        add(format!("{}{}.json", DAYS_DIR, day.date), json)?;
+    }
+    if let Some(config) = &bundle.config {
+        add(CONFIG_ENTRY.to_string(), config.clone())?;
+    }
+    if let Some(timer) = &bundle.timer {
+        let json = serde_json::to_string_pretty(timer).context("Failed to serialize timer")?;
+        add(TIMER_ENTRY.to_string(), json)?;
+    }
+    zip.finish()
+        .with_context(|| format!("Failed to write bundle: {:?}", output))?;
+    Ok(bundle)
+}
+
+/// Read and check a bundle written by `write_bundle`
+///
+/// # Errors
+/// Returns an error for unreadable zips, entries that don't parse, or entries a
+/// bundle never contains (so nothing outside the data directory can be written)
+pub fn read_bundle(path: &Path) -> Result<Bundle> {
+    let file =
+        fs::File::open(path).with_context(|| format!("Failed to open bundle: {:?}", path))?;
+    let mut archive = ZipArchive::new(file).context("Not a zip file")?;
+    let format = time::macros::format_description!("[year]-[month]-[day]");
+
+    let mut bundle = Bundle {
+        days: Vec::new(),
+        config: None,
+        timer: None,
+    };
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let name = entry.name().to_string();
+        if entry.is_dir() {
+            continue;
+        }
+        let mut contents = String::new();
+        entry
+            .read_to_string(&mut contents)
+            .with_context(|| format!("Failed to read {} from bundle", name))?;
+
+        if name == CONFIG_ENTRY {
+            toml::from_str::<toml::Table>(&contents)
+                .with_context(|| format!("Invalid {} in bundle", name))?;
+            bundle.config = Some(contents);
+        } else if name == TIMER_ENTRY {
+            let timer = serde_json::from_str(&contents)
+                .with_context(|| format!("Invalid {} in bundle", name))?;
+            bundle.timer = Some(timer);
+        } else if let Some(date) = name
+            .strip_prefix(DAYS_DIR)
+            .and_then(|file| file.strip_suffix(".json"))
+            .and_then(|stem| Date::parse(stem, &format).ok())
+        {
+            let day: DayData = serde_json::from_str(&contents)
+                .with_context(|| format!("Invalid {} in bundle", name))?;
+            if day.date != date {
+                return Err(anyhow!("{} in bundle holds {}", name, day.date));
+            }
+            bundle.days.push(day);
+        } else {
+            return Err(anyhow!("Unexpected file in bundle: {}", name));
+        }
+    }
+    bundle.days.sort_by_key(|day| day.date);
+    Ok(bundle)
+}
+
+/// Write `bundle` into `storage` and `config_path`
+///
+/// # Errors
+/// Without `force`, returns an error naming what already exists (day files, the
+/// config, a running timer) before writing anything
+pub fn restore_bundle(
+    bundle: &Bundle,
+    storage: &Storage,
+    config_path: &Path,
+    force: bool,
+) -> Result<()> {
+    if !force {
+        let existing = existing_data(bundle, storage, config_path)?;
+        if !existing.is_empty() {
+            return Err(anyhow!(
+                "Would overwrite {}; use --force to replace it",
+                existing.join(", ")
+            ));
+        }
+    }
+
+    for day in &bundle.days {
+        storage.save(day)?;
+    }
+    if let Some(config) = &bundle.config {
+        if let Some(dir) = config_path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create config directory: {:?}", dir))?;
+        }
+        fs::write(config_path, config)
+            .with_context(|| format!("Failed to write config: {:?}", config_path))?;
+    }
+    if let Some(timer) = &bundle.timer {
+        storage.save_active_timer(timer)?;
+    }
+    Ok(())
+}
+
+/// Data in place that restoring `bundle` would replace, described for an error
+fn existing_data(bundle: &Bundle, storage: &Storage, config_path: &Path) -> Result<Vec<String>> {
+    let stored = storage.list_day_files()?;
+    let days = bundle
+        .days
+        .iter()
+        .filter(|day| stored.contains(&day.date))
+        .count();
+
+    let mut existing = Vec::new();
+    if days > 0 {
+        existing.push(format!("{} day file(s)", days));
+    }
+    if bundle.config.is_some() && config_path.exists() {
+        existing.push("the config".to_string());
+    }
+    if bundle.timer.is_some() && storage.load_active_timer()?.is_some() {
+        existing.push("the running timer".to_string());
+    }
+    Ok(existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use tempfile::TempDir;
+    use time::macros::date;
+
+    fn day_with(date: Date, name: &str) -> DayData {
+        let mut day = DayData::new(date);
+        day.add_record(WorkRecord::new(
+            1,
+            name.to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 30).unwrap(),
+        ));
+        day
+    }
+
+    /// A data directory with two days, a config and a running timer
+    fn populated() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().join("data")).unwrap();
+        storage
+            .save(&day_with(date!(2025 - 11 - 05), "Code review"))
+            .unwrap();
+        storage
+            .save(&day_with(date!(2025 - 11 - 06), "Feature dev"))
+            .unwrap();
+        fs::write(
+            temp_dir.path().join("config.toml"),
+            "[ui]\nshow_ids = true\n",
+        )
+        .unwrap();
+        crate::timer::TimerManager::new(storage.clone())
+            .start("Deploy".to_string(), None, None, None)
+            .unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_round_trip_into_fresh_directory() {
+        let (source_dir, source) = populated();
+        let output = source_dir.path().join("backup.zip");
+        let written = write_bundle(
+            &source,
+            &source_dir.path().join("config.toml"),
+            true,
+            &output,
+        )
+        .unwrap();
+        assert_eq!(written.days.len(), 2);
+
+        let target_dir = TempDir::new().unwrap();
+        let target = Storage::new_with_dir(target_dir.path().join("data")).unwrap();
+        let config_path = target_dir.path().join("cfg").join("config.toml");
+        let bundle = read_bundle(&output).unwrap();
+        assert_eq!(bundle, written);
+        restore_bundle(&bundle, &target, &config_path, false).unwrap();
+
+        assert_eq!(
+            target.load_all_days().unwrap(),
+            source.load_all_days().unwrap()
+        );
+        assert_eq!(
+            fs::read_to_string(&config_path).unwrap(),
+            "[ui]\nshow_ids = true\n"
+        );
+        assert_eq!(
+            target.load_active_timer().unwrap(),
+            source.load_active_timer().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_timer_is_left_out_unless_asked_for() {
+        let (source_dir, source) = populated();
+        let output = source_dir.path().join("backup.zip");
+        write_bundle(
+            &source,
+            Path::new("/nonexistent/config.toml"),
+            false,
+            &output,
+        )
+        .unwrap();
+
+        let bundle = read_bundle(&output).unwrap();
+        assert!(bundle.timer.is_none());
+        assert!(bundle.config.is_none());
+        assert_eq!(bundle.days.len(), 2);
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite_without_force() {
+        let (source_dir, source) = populated();
+        let config_path = source_dir.path().join("config.toml");
+        let output = source_dir.path().join("backup.zip");
+        write_bundle(&source, &config_path, true, &output).unwrap();
+        let mut bundle = read_bundle(&output).unwrap();
+
+        // Restoring over the same data names everything it would replace
+        source
+            .save(&day_with(date!(2025 - 11 - 06), "Edited since"))
+            .unwrap();
+        let err = restore_bundle(&bundle, &source, &config_path, false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Would overwrite 2 day file(s), the config, the running timer; use --force to replace it"
+        );
+        let kept = source.load(&date!(2025 - 11 - 06)).unwrap();
+        assert_eq!(kept.work_records[&1].name, "Edited since");
+
+        bundle.days.truncate(1);
+        restore_bundle(&bundle, &source, &config_path, true).unwrap();
+        let restored = source.load(&date!(2025 - 11 - 05)).unwrap();
+        assert_eq!(restored.work_records[&1].name, "Code review");
+    }
+
+    #[test]
+    fn test_unexpected_entries_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("evil.zip");
+        let mut zip = ZipWriter::new(fs::File::create(&path).unwrap());
+        zip.start_file("../outside.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.finish().unwrap();
+
+        let err = read_bundle(&path).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unexpected file in bundle: ../outside.json"
+        );
+    }
+}
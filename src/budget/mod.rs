@@ -0,0 +1,340 @@
+//! Per-ticket time budgets tracked across all days
+//!
+//! Budgets are configured as `[budgets]` entries mapping a ticket id (`"ACME-77"`)
+//! or a prefix (`"ACME-"`) to a total number of minutes. Spent time is the sum of
+//! every record whose ticket matches, over every day file, plus a running timer.
+
+mod period;
+
+pub use period::{BudgetPeriod, period_statuses};
+
+use crate::integrations::extract_ticket_from_name;
+use crate::models::DayData;
+use anyhow::Result;
+use std::collections::HashMap;
+use time::Date;
+
+/// Share of the budget at which a warning is shown
+const WARNING_PERCENT: u32 = 80;
+
+/// How close a ticket is to its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLevel {
+    Ok,
+    Warning,
+    Exceeded,
+}
+
+/// Spent vs. budgeted minutes for one budget entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetStatus {
+    /// Ticket id or prefix as written in the config
+    pub key: String,
+    pub budget_minutes: u32,
+    pub spent_minutes: u32,
+}
+
+impl BudgetStatus {
+    pub fn percent(&self) -> u32 {
+        if self.budget_minutes == 0 {
+            return if self.spent_minutes > 0 { u32::MAX } else { 0 };
+        }
+        ((self.spent_minutes as u64 * 100) / self.budget_minutes as u64).min(u32::MAX as u64) as u32
+    }
+
+    pub fn remaining_minutes(&self) -> u32 {
+        self.budget_minutes.saturating_sub(self.spent_minutes)
+    }
+
+    pub fn level(&self) -> BudgetLevel {
+        if self.spent_minutes > self.budget_minutes {
+            BudgetLevel::Exceeded
+        } else if self.percent() >= WARNING_PERCENT {
+            BudgetLevel::Warning
+        } else {
+            BudgetLevel::Ok
+        }
+    }
+}
+
+/// Whether a task name belongs to the budget `key` (exact ticket id or ticket prefix)
+///
+/// A key ending in a digit ("ACME-7") is an id and must match exactly; any other
+/// ("ACME-") is a prefix.
+pub fn matches_budget(key: &str, name: &str) -> bool {
+    let is_prefix = !key.ends_with(|c: char| c.is_ascii_digit());
+    extract_ticket_from_name(name)
+        .is_some_and(|ticket| ticket == key || (is_prefix && ticket.starts_with(key)))
+}
+
+/// Find the budget entry a task name falls under, preferring the longest (most specific) key
+pub fn budget_key_for<'a>(budgets: &'a HashMap<String, u32>, name: &str) -> Option<&'a str> {
+    budgets
+        .keys()
+        .filter(|key| matches_budget(key, name))
+        .max_by_key(|key| key.len())
+        .map(String::as_str)
+}
+
+/// Minutes recorded against `key` in one day
+pub fn spent_in_day(day: &DayData, key: &str, skip_record: Option<u32>) -> u32 {
+    let minutes: i64 = day
+        .work_records
+        .values()
+        .filter(|record| Some(record.id) != skip_record && matches_budget(key, &record.name))
+        .map(|record| record.effective_minutes())
+        .sum();
+    minutes.clamp(0, u32::MAX as i64) as u32
+}
+
+/// Minutes recorded against `key` across all given days
+pub fn spent_across_days<'a>(days: impl IntoIterator<Item = &'a DayData>, key: &str) -> u32 {
+    days.into_iter()
+        .map(|day| spent_in_day(day, key, None))
+        .sum()
+}
+
+/// Statuses for every configured budget, sorted by key
+pub fn budget_statuses(budgets: &HashMap<String, u32>, days: &[DayData]) -> Vec<BudgetStatus> {
+    let mut statuses: Vec<BudgetStatus> = budgets
+        .iter()
+        .map(|(key, &budget_minutes)| BudgetStatus {
+            key: key.clone(),
+            budget_minutes,
+            spent_minutes: spent_across_days(days, key),
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.key.cmp(&b.key));
+    statuses
+}
+
+/// A running timer's contribution to a budget
+pub struct RunningTime<'a> {
+    pub task_name: &'a str,
+    pub elapsed_minutes: u32,
+    /// Record on the current day the timer will update when stopped
+    pub source_record_id: Option<u32>,
+}
+
+/// Cumulative budget tracking for the TUI
+///
+/// Totals from days other than the one on screen are cached per budget key; the
+/// current day is always summed from memory so edits show up immediately.
+/// The cache must be invalidated whenever day files may have changed on disk.
+#[derive(Debug, Default)]
+pub struct BudgetTracker {
+    other_days: HashMap<String, u32>,
+    cached_for: Option<Date>,
+}
+
+impl BudgetTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget cached totals (call after saves or external file changes)
+    pub fn invalidate(&mut self) {
+        self.other_days.clear();
+        self.cached_for = None;
+    }
+
+    /// Compute statuses for all budgets
+    ///
+    /// `load_days` is only called when the cache is cold; it should return every
+    /// stored day (the current day's entry is ignored in favour of `current`).
+    pub fn statuses(
+        &mut self,
+        budgets: &HashMap<String, u32>,
+        current: &DayData,
+        running: Option<RunningTime>,
+        load_days: impl FnOnce() -> Result<Vec<DayData>>,
+    ) -> Result<Vec<BudgetStatus>> {
+        if budgets.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if self.cached_for != Some(current.date)
+            || !budgets.keys().all(|k| self.other_days.contains_key(k))
+        {
+            let days: Vec<DayData> = load_days()?
+                .into_iter()
+                .filter(|day| day.date != current.date)
+                .collect();
+            self.other_days = budgets
+                .keys()
+                .map(|key| (key.clone(), spent_across_days(&days, key)))
+                .collect();
+            self.cached_for = Some(current.date);
+        }
+
+        let mut statuses: Vec<BudgetStatus> = budgets
+            .iter()
+            .map(|(key, &budget_minutes)| {
+                let running_for_key = running
+                    .as_ref()
+                    .filter(|timer| matches_budget(key, timer.task_name));
+                let skip_record = running_for_key.and_then(|timer| timer.source_record_id);
+                let running_minutes = running_for_key.map_or(0, |timer| timer.elapsed_minutes);
+
+                BudgetStatus {
+                    key: key.clone(),
+                    budget_minutes,
+                    spent_minutes: self.other_days.get(key).copied().unwrap_or(0)
+                        + spent_in_day(current, key, skip_record)
+                        + running_minutes,
+                }
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(statuses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use crate::storage::Storage;
+    use tempfile::TempDir;
+    use time::macros::date;
+
+    fn day_with(date: Date, records: &[(u32, &str, u8, u8)]) -> DayData {
+        let mut day = DayData::new(date);
+        for &(id, name, start, end) in records {
+            day.add_record(WorkRecord::new(
+                id,
+                name.to_string(),
+                TimePoint::new(start, 0).unwrap(),
+                TimePoint::new(end, 0).unwrap(),
+            ));
+        }
+        day
+    }
+
+    fn budgets(entries: &[(&str, u32)]) -> HashMap<String, u32> {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_matches_exact_ticket_and_prefix() {
+        assert!(matches_budget("ACME-77", "ACME-77 fix login"));
+        assert!(!matches_budget("ACME-77", "ACME-78 other"));
+        assert!(matches_budget("ACME-", "ACME-78 other"));
+        assert!(!matches_budget("ACME-77", "Team meeting"));
+        // An id is not a prefix of longer ids
+        assert!(!matches_budget("ACME-7", "ACME-77 fix login"));
+        assert!(!matches_budget("ACME-7", "ACME-700 other"));
+        assert!(matches_budget("ACME-7", "ACME-7 review"));
+    }
+
+    #[test]
+    fn test_cumulative_minutes_across_day_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        storage
+            .save(&day_with(
+                date!(2025 - 11 - 03),
+                &[(1, "ACME-77 fix", 9, 11), (2, "Meeting", 11, 12)],
+            ))
+            .unwrap();
+        storage
+            .save(&day_with(
+                date!(2025 - 11 - 04),
+                &[(1, "ACME-77 review", 13, 14), (2, "ACME-80 spike", 14, 16)],
+            ))
+            .unwrap();
+
+        let days = storage.load_all_days().unwrap();
+        let statuses = budget_statuses(&budgets(&[("ACME-77", 360), ("ACME-", 300)]), &days);
+
+        assert_eq!(statuses[0].key, "ACME-");
+        assert_eq!(statuses[0].spent_minutes, 300);
+        assert_eq!(statuses[1].key, "ACME-77");
+        assert_eq!(statuses[1].spent_minutes, 180);
+        assert_eq!(statuses[1].remaining_minutes(), 180);
+    }
+
+    #[test]
+    fn test_threshold_levels() {
+        let status = |spent| BudgetStatus {
+            key: "ACME-77".to_string(),
+            budget_minutes: 100,
+            spent_minutes: spent,
+        };
+        assert_eq!(status(79).level(), BudgetLevel::Ok);
+        assert_eq!(status(80).level(), BudgetLevel::Warning);
+        assert_eq!(status(100).level(), BudgetLevel::Warning);
+        assert_eq!(status(101).level(), BudgetLevel::Exceeded);
+        assert_eq!(status(101).remaining_minutes(), 0);
+    }
+
+    #[test]
+    fn test_tracker_uses_current_day_and_running_timer() {
+        let other = day_with(date!(2025 - 11 - 03), &[(1, "ACME-77 fix", 9, 13)]);
+        let mut current = day_with(date!(2025 - 11 - 04), &[(1, "ACME-77 fix", 9, 10)]);
+        let budgets = budgets(&[("ACME-77", 360)]);
+        let mut tracker = BudgetTracker::new();
+
+        let statuses = tracker
+            .statuses(&budgets, &current, None, || {
+                Ok(vec![other.clone(), current.clone()])
+            })
+            .unwrap();
+        assert_eq!(statuses[0].spent_minutes, 300);
+        assert_eq!(statuses[0].level(), BudgetLevel::Warning);
+
+        // Edits to the current day count without reloading other days
+        current.add_record(WorkRecord::new(
+            2,
+            "ACME-77 more".to_string(),
+            TimePoint::new(10, 0).unwrap(),
+            TimePoint::new(10, 30).unwrap(),
+        ));
+        let running = RunningFixture::new("ACME-77 more", 40, Some(2));
+        let statuses = tracker
+            .statuses(&budgets, &current, Some(running.as_running()), || {
+                panic!("cache should be warm")
+            })
+            .unwrap();
+        // Record 2 is replaced by the timer's elapsed time
+        assert_eq!(statuses[0].spent_minutes, 240 + 60 + 40);
+
+        let running = RunningFixture::new("ACME-77 more", 61, Some(2));
+        let statuses = tracker
+            .statuses(&budgets, &current, Some(running.as_running()), || {
+                panic!("cache should be warm")
+            })
+            .unwrap();
+        assert_eq!(statuses[0].level(), BudgetLevel::Exceeded);
+
+        tracker.invalidate();
+        let statuses = tracker
+            .statuses(&budgets, &current, None, || Ok(Vec::new()))
+            .unwrap();
+        assert_eq!(statuses[0].spent_minutes, 90);
+    }
+
+    struct RunningFixture {
+        name: String,
+        elapsed: u32,
+        source: Option<u32>,
+    }
+
+    impl RunningFixture {
+        fn new(name: &str, elapsed: u32, source: Option<u32>) -> Self {
+            Self {
+                name: name.to_string(),
+                elapsed,
+                source,
+            }
+        }
+
+        fn as_running(&self) -> RunningTime<'_> {
+            RunningTime {
+                task_name: &self.name,
+                elapsed_minutes: self.elapsed,
+                source_record_id: self.source,
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Weekly and monthly hour caps per task or project (`[[task_budgets]]`)
+//!
+//! Unlike the all-time ticket budgets, these only count the days of one week or
+//! month, so the same cap applies again once the period is over.
+
+use super::BudgetStatus;
+use crate::config::TaskBudget;
+use crate::models::{DayData, WorkRecord};
+use crate::report::week_containing;
+use crate::timefmt::days_in_month;
+use time::{Date, Weekday};
+
+/// Span a `[[task_budgets]]` cap applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BudgetPeriod {
+    Week,
+    Month,
+}
+
+impl BudgetPeriod {
+    pub fn label(self) -> &'static str {
+        match self {
+            BudgetPeriod::Week => "week",
+            BudgetPeriod::Month => "month",
+        }
+    }
+
+    /// First and last day of the week or month containing `date`
+    pub fn range(self, date: Date, week_start: Weekday) -> (Date, Date) {
+        match self {
+            BudgetPeriod::Week => {
+                let first = week_containing(date, week_start);
+                (first, first + time::Duration::days(6))
+            }
+            BudgetPeriod::Month => {
+                let first = date.replace_day(1).expect("day 1 exists");
+                let last = date
+                    .replace_day(days_in_month(date.month(), date.year()))
+                    .expect("last day exists");
+                (first, last)
+            }
+        }
+    }
+
+    /// Budgeted minutes of `budget` for this period, None when it has no cap for it
+    fn minutes(self, budget: &TaskBudget) -> Option<u32> {
+        let hours = match self {
+            BudgetPeriod::Week => budget.weekly_hours,
+            BudgetPeriod::Month => budget.monthly_hours,
+        }?;
+        // Negative or NaN hours are ignored rather than read as zero
+        (hours >= 0.0).then(|| (hours * 60.0).round().min(u32::MAX as f64) as u32)
+    }
+}
+
+/// How a budget is listed: its task, its project, or "task (project)"
+fn task_budget_label(budget: &TaskBudget) -> Option<String> {
+    match (&budget.task, &budget.project) {
+        (Some(task), Some(project)) => Some(format!("{} ({})", task, project)),
+        (Some(task), None) => Some(task.clone()),
+        (None, Some(project)) => Some(project.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Whether `record` counts against `budget`
+fn matches_task_budget(budget: &TaskBudget, record: &WorkRecord) -> bool {
+    if budget.task.is_none() && budget.project.is_none() {
+        return false;
+    }
+    let task_matches = budget
+        .task
+        .as_deref()
+        .is_none_or(|task| normalize(task) == normalize(&record.name));
+    let project_matches = budget.project.as_deref().is_none_or(|project| {
+        record
+            .project
+            .as_deref()
+            .is_some_and(|own| normalize(own) == normalize(project))
+    });
+    task_matches && project_matches
+}
+
+fn normalize(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Spent vs. budgeted minutes for every budget with a cap for `period`
+///
+/// `days` should be the days of one period; statuses follow the config order.
+pub fn period_statuses(
+    budgets: &[TaskBudget],
+    period: BudgetPeriod,
+    days: &[DayData],
+) -> Vec<BudgetStatus> {
+    budgets
+        .iter()
+        .filter_map(|budget| {
+            let key = task_budget_label(budget)?;
+            let budget_minutes = period.minutes(budget)?;
+            let spent: i64 = days
+                .iter()
+                .flat_map(|day| day.work_records.values())
+                .filter(|record| matches_task_budget(budget, record))
+                .map(|record| record.effective_minutes())
+                .sum();
+            Some(BudgetStatus {
+                key,
+                budget_minutes,
+                spent_minutes: spent.clamp(0, u32::MAX as i64) as u32,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::BudgetLevel;
+    use crate::models::TimePoint;
+    use time::macros::date;
+
+    fn day_with(date: Date, records: &[(&str, Option<&str>, u8, u8)]) -> DayData {
+        let mut day = DayData::new(date);
+        for (id, &(name, project, start, end)) in records.iter().enumerate() {
+            let mut record = WorkRecord::new(
+                id as u32 + 1,
+                name.to_string(),
+                TimePoint::new(start, 0).unwrap(),
+                TimePoint::new(end, 0).unwrap(),
+            );
+            record.project = project.map(str::to_string);
+            day.add_record(record);
+        }
+        day
+    }
+
+    fn budget(task: Option<&str>, project: Option<&str>, weekly: Option<f64>) -> TaskBudget {
+        TaskBudget {
+            task: task.map(str::to_string),
+            project: project.map(str::to_string),
+            weekly_hours: weekly,
+            monthly_hours: None,
+        }
+    }
+
+    fn week() -> Vec<DayData> {
+        vec![
+            day_with(
+                date!(2025 - 11 - 03),
+                &[
+                    ("Meetings", None, 9, 12),
+                    ("Code review", Some("Platform"), 13, 15),
+                ],
+            ),
+            day_with(
+                date!(2025 - 11 - 04),
+                &[
+                    ("meetings ", None, 9, 11),
+                    ("Ticket triage", Some("Support"), 11, 12),
+                    ("Meetings", Some("Support"), 14, 15),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_over_budget_across_days() {
+        let statuses = period_statuses(
+            &[budget(Some("Meetings"), None, Some(5.0))],
+            BudgetPeriod::Week,
+            &week(),
+        );
+        // 3h + 2h + 1h, the name compared ignoring case and spaces
+        assert_eq!(statuses[0].key, "Meetings");
+        assert_eq!(statuses[0].budget_minutes, 300);
+        assert_eq!(statuses[0].spent_minutes, 360);
+        assert_eq!(statuses[0].level(), BudgetLevel::Exceeded);
+    }
+
+    #[test]
+    fn test_under_budget_by_project_and_by_both() {
+        let statuses = period_statuses(
+            &[
+                budget(None, Some("support"), Some(4.0)),
+                budget(Some("Meetings"), Some("Support"), Some(1.5)),
+            ],
+            BudgetPeriod::Week,
+            &week(),
+        );
+        assert_eq!(statuses[0].key, "support");
+        assert_eq!(statuses[0].spent_minutes, 120);
+        assert_eq!(statuses[0].level(), BudgetLevel::Ok);
+        assert_eq!(statuses[1].key, "Meetings (Support)");
+        assert_eq!(statuses[1].budget_minutes, 90);
+        assert_eq!(statuses[1].spent_minutes, 60);
+        assert_eq!(statuses[1].level(), BudgetLevel::Ok);
+    }
+
+    #[test]
+    fn test_budgets_without_a_cap_for_the_period_are_left_out() {
+        let mut monthly = budget(Some("Meetings"), None, None);
+        monthly.monthly_hours = Some(20.0);
+        let budgets = [
+            monthly,
+            budget(None, None, Some(1.0)),
+            budget(Some("Code review"), None, Some(-1.0)),
+            budget(Some("Code review"), None, Some(0.0)),
+        ];
+
+        let weekly = period_statuses(&budgets, BudgetPeriod::Week, &week());
+        assert_eq!(weekly.len(), 1);
+        // A zero cap means any time at all is too much
+        assert_eq!(weekly[0].key, "Code review");
+        assert_eq!(weekly[0].level(), BudgetLevel::Exceeded);
+
+        let monthly = period_statuses(&budgets, BudgetPeriod::Month, &week());
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].budget_minutes, 1200);
+        assert_eq!(monthly[0].level(), BudgetLevel::Ok);
+    }
+
+    #[test]
+    fn test_period_ranges() {
+        let thursday = date!(2025 - 11 - 06);
+        assert_eq!(
+            BudgetPeriod::Week.range(thursday, Weekday::Monday),
+            (date!(2025 - 11 - 03), date!(2025 - 11 - 09))
+        );
+        assert_eq!(
+            BudgetPeriod::Week.range(thursday, Weekday::Sunday),
+            (date!(2025 - 11 - 02), date!(2025 - 11 - 08))
+        );
+        assert_eq!(
+            BudgetPeriod::Month.range(date!(2024 - 02 - 10), Weekday::Monday),
+            (date!(2024 - 02 - 01), date!(2024 - 02 - 29))
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Date arguments: `2025-11-06`, `today`, `yesterday`, `-3`, `mon`, `next:fri`
+//!
+//! Every `--date` flag goes through `parse`, with today passed in so the rules can
+//! be tested against a fixed day. A bare weekday is the most recent one, today
+//! included; `next:` picks the upcoming one instead.
+
+use anyhow::{Result, anyhow, bail};
+use time::{Date, Duration, Weekday};
+
+const USAGE: &str = "use YYYY-MM-DD, today, yesterday, tomorrow, +N/-N days or a weekday";
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Monday),
+    ("tuesday", Weekday::Tuesday),
+    ("wednesday", Weekday::Wednesday),
+    ("thursday", Weekday::Thursday),
+    ("friday", Weekday::Friday),
+    ("saturday", Weekday::Saturday),
+    ("sunday", Weekday::Sunday),
+];
+
+/// A resolved date argument, with a note on how a weekday name was read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub date: Date,
+    /// "'mon' resolved to …; use next:mon for the upcoming Monday"
+    pub hint: Option<String>,
+}
+
+/// Resolve a date expression relative to `today`
+pub fn parse(value: &str, today: Date) -> Result<Resolved> {
+    let expr = value.trim().to_ascii_lowercase();
+    let invalid = || anyhow!("Invalid date '{}' ({})", value, USAGE);
+    let plain = |date: Date| Resolved { date, hint: None };
+
+    match expr.as_str() {
+        "today" => return Ok(plain(today)),
+        "yesterday" => return offset(today, -1, value).map(plain),
+        "tomorrow" => return offset(today, 1, value).map(plain),
+        _ => {}
+    }
+
+    if let Some(days) = expr.strip_prefix(['+', '-']) {
+        if days.is_empty() || !days.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let days: i64 = days.parse().map_err(|_| invalid())?;
+        let days = if expr.starts_with('-') { -days } else { days };
+        return offset(today, days, value).map(plain);
+    }
+
+    if let Some(name) = expr.strip_prefix("next:") {
+        let weekday = weekday(name).ok_or_else(invalid)?;
+        let ahead = (weekday.number_days_from_monday() as i64
+            - today.weekday().number_days_from_monday() as i64)
+            .rem_euclid(7);
+        let days = if ahead == 0 { 7 } else { ahead };
+        return offset(today, days, value).map(plain);
+    }
+
+    if let Some(weekday) = weekday(&expr) {
+        let back = (today.weekday().number_days_from_monday() as i64
+            - weekday.number_days_from_monday() as i64)
+            .rem_euclid(7);
+        let date = offset(today, -back, value)?;
+        let hint = format!(
+            "'{}' resolved to {}; use next:{} for the upcoming {}",
+            value.trim(),
+            date,
+            expr,
+            weekday
+        );
+        return Ok(Resolved {
+            date,
+            hint: Some(hint),
+        });
+    }
+
+    if expr.chars().all(|c| c.is_ascii_digit()) && !expr.is_empty() && expr.len() < 4 {
+        bail!(
+            "Invalid date '{}' (a day offset needs a sign: +{} or -{})",
+            value,
+            expr,
+            expr
+        );
+    }
+
+    let format = time::macros::format_description!("[year]-[month]-[day]");
+    Date::parse(&expr, &format)
+        .map(plain)
+        .map_err(|_| invalid())
+}
+
+/// Monday of an ISO week ("2025-W45"), the week containing `today` when None
+pub fn parse_week(value: Option<&str>, today: Date) -> Result<Date> {
+    let Some(value) = value else {
+        let (year, week, _) = today.to_iso_week_date();
+        return Ok(Date::from_iso_week_date(year, week, Weekday::Monday)?);
+    };
+    let invalid = || anyhow!("Invalid week '{}' (use YYYY-Www, e.g. 2025-W45)", value);
+    let (year, week) = value
+        .trim()
+        .to_ascii_uppercase()
+        .split_once("-W")
+        .and_then(|(year, week)| Some((year.parse::<i32>().ok()?, week.parse::<u8>().ok()?)))
+        .ok_or_else(invalid)?;
+    Date::from_iso_week_date(year, week, Weekday::Monday).map_err(|_| invalid())
+}
+
+/// Weekday for a full name or an abbreviation of at least three letters
+fn weekday(name: &str) -> Option<Weekday> {
+    if name.len() < 3 {
+        return None;
+    }
+    WEEKDAYS
+        .iter()
+        .find(|(full, _)| full.starts_with(name))
+        .map(|&(_, weekday)| weekday)
+}
+
+fn offset(today: Date, days: i64, value: &str) -> Result<Date> {
+    // Well past the calendar's range, and small enough not to overflow a Duration
+    const MAX_DAYS: u64 = 10_000 * 366;
+    if days.unsigned_abs() > MAX_DAYS {
+        bail!("Date '{}' is out of range", value);
+    }
+    today
+        .checked_add(Duration::days(days))
+        .ok_or_else(|| anyhow!("Date '{}' is out of range", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    // A Thursday
+    const TODAY: Date = date!(2025 - 11 - 06);
+
+    fn date_of(value: &str) -> Date {
+        parse(value, TODAY).unwrap().date
+    }
+
+    fn error_of(value: &str) -> String {
+        parse(value, TODAY).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_iso_weeks() {
+        assert_eq!(parse_week(None, TODAY).unwrap(), date!(2025 - 11 - 03));
+        assert_eq!(
+            parse_week(Some("2025-W45"), TODAY).unwrap(),
+            date!(2025 - 11 - 03)
+        );
+        // Week 1 of 2026 starts in December 2025
+        assert_eq!(
+            parse_week(Some("2026-w01"), TODAY).unwrap(),
+            date!(2025 - 12 - 29)
+        );
+        assert_eq!(
+            parse_week(Some("2025-W54"), TODAY).unwrap_err().to_string(),
+            "Invalid week '2025-W54' (use YYYY-Www, e.g. 2025-W45)"
+        );
+        assert!(parse_week(Some("2025-45"), TODAY).is_err());
+    }
+
+    #[test]
+    fn test_iso_dates() {
+        assert_eq!(date_of("2025-11-06"), TODAY);
+        assert_eq!(date_of(" 2024-02-29 "), date!(2024 - 02 - 29));
+        assert_eq!(
+            error_of("2025-02-30"),
+            format!("Invalid date '2025-02-30' ({})", USAGE)
+        );
+        assert!(parse("06/11/2025", TODAY).is_err());
+    }
+
+    #[test]
+    fn test_named_days() {
+        assert_eq!(date_of("today"), TODAY);
+        assert_eq!(date_of("Yesterday"), date!(2025 - 11 - 05));
+        assert_eq!(date_of("TOMORROW"), date!(2025 - 11 - 07));
+        assert_eq!(
+            parse("2025-12-31", date!(2025 - 12 - 30)).unwrap().date,
+            parse("tomorrow", date!(2025 - 12 - 30)).unwrap().date
+        );
+    }
+
+    #[test]
+    fn test_signed_offsets() {
+        assert_eq!(date_of("-1"), date!(2025 - 11 - 05));
+        assert_eq!(date_of("-3"), date!(2025 - 11 - 03));
+        assert_eq!(date_of("+1"), date!(2025 - 11 - 07));
+        assert_eq!(date_of("+0"), TODAY);
+        assert_eq!(date_of("-30"), date!(2025 - 10 - 07));
+        assert_eq!(date_of("+60"), date!(2026 - 01 - 05));
+        assert_eq!(
+            error_of("3"),
+            "Invalid date '3' (a day offset needs a sign: +3 or -3)"
+        );
+        assert!(parse("+x", TODAY).is_err());
+        assert!(parse("-", TODAY).is_err());
+        assert!(parse("--1", TODAY).is_err());
+        assert!(parse("++1", TODAY).is_err());
+        assert_eq!(error_of("+9999999"), "Date '+9999999' is out of range");
+        assert!(parse("-99999999999999999", TODAY).is_err());
+    }
+
+    #[test]
+    fn test_weekdays_resolve_to_the_most_recent_one() {
+        assert_eq!(date_of("mon"), date!(2025 - 11 - 03));
+        assert_eq!(date_of("Monday"), date!(2025 - 11 - 03));
+        assert_eq!(date_of("wed"), date!(2025 - 11 - 05));
+        // Today's weekday is today
+        assert_eq!(date_of("thu"), TODAY);
+        assert_eq!(date_of("thurs"), TODAY);
+        assert_eq!(date_of("fri"), date!(2025 - 10 - 31));
+        assert_eq!(date_of("sun"), date!(2025 - 11 - 02));
+        assert!(parse("mo", TODAY).is_err());
+        assert!(parse("mondays", TODAY).is_err());
+    }
+
+    #[test]
+    fn test_next_weekday_is_upcoming() {
+        assert_eq!(date_of("next:fri"), date!(2025 - 11 - 07));
+        assert_eq!(date_of("next:mon"), date!(2025 - 11 - 10));
+        // Never today
+        assert_eq!(date_of("next:thu"), date!(2025 - 11 - 13));
+        assert_eq!(date_of("NEXT:Wednesday"), date!(2025 - 11 - 12));
+        assert_eq!(
+            error_of("next:week"),
+            format!("Invalid date 'next:week' ({})", USAGE)
+        );
+    }
+
+    #[test]
+    fn test_weekday_hint_names_the_alternative() {
+        assert_eq!(
+            parse("mon", TODAY).unwrap().hint.as_deref(),
+            Some("'mon' resolved to 2025-11-03; use next:mon for the upcoming Monday")
+        );
+        assert_eq!(parse("next:mon", TODAY).unwrap().hint, None);
+        assert_eq!(parse("-1", TODAY).unwrap().hint, None);
+        assert_eq!(parse("2025-11-03", TODAY).unwrap().hint, None);
+    }
+
+    #[test]
+    fn test_empty_and_unknown_input() {
+        assert!(parse("", TODAY).is_err());
+        assert!(parse("someday", TODAY).is_err());
+        assert!(parse("next:", TODAY).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Exit codes and `--error-format json` for failed commands
+//!
+//! Handlers return `anyhow::Result` as everywhere else and mark the errors whose
+//! class they know with `Classify` (a bad date is a usage error, a missing session
+//! a state error). `CliError::classify` sorts out the rest when the command has
+//! failed: anything caused by I/O or an unreadable data file is a storage error.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use std::fmt;
+
+/// How a failed command reports its error on stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// "Error: ..." with the chain of causes
+    #[default]
+    Text,
+    /// `{"error": {"code": "...", "message": "..."}}` on one line
+    Json,
+}
+
+impl ErrorFormat {
+    /// The format asked for on a command line clap could not parse
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let value = match arg.strip_prefix("--error-format") {
+                Some("") => args.next(),
+                Some(value) => value.strip_prefix('=').map(str::to_string),
+                None => continue,
+            };
+            if let Some(format) = value.and_then(|value| Self::from_str(&value, true).ok()) {
+                return format;
+            }
+        }
+        Self::Text
+    }
+}
+
+/// Why a command failed, deciding its exit code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// Bad arguments or values: an unknown option, an invalid date or time (exit 2)
+    Usage(String),
+    /// Valid arguments the data can't satisfy: no session running, one already
+    /// running, no such record (exit 3)
+    State(String),
+    /// Reading or writing the data or config files failed (exit 4)
+    Storage(String),
+    /// Anything not covered above, such as a failed tracker request (exit 1)
+    Other(String),
+}
+
+impl CliError {
+    /// Class of `err`, with its whole chain of causes as the message
+    pub fn classify(err: &anyhow::Error) -> Self {
+        let message = format!("{:#}", err);
+        let marked = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<CliError>());
+        match marked {
+            Some(CliError::Usage(_)) => CliError::Usage(message),
+            Some(CliError::State(_)) => CliError::State(message),
+            Some(CliError::Storage(_)) => CliError::Storage(message),
+            Some(CliError::Other(_)) => CliError::Other(message),
+            None if is_storage(err) => CliError::Storage(message),
+            None => CliError::Other(message),
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Other(_) => 1,
+            CliError::Usage(_) => 2,
+            CliError::State(_) => 3,
+            CliError::Storage(_) => 4,
+        }
+    }
+
+    /// Stable name of the class for scripts, the `code` of the JSON error
+    pub fn code(&self) -> &'static str {
+        match self {
+            CliError::Usage(_) => "usage",
+            CliError::State(_) => "state",
+            CliError::Storage(_) => "storage",
+            CliError::Other(_) => "other",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            CliError::Usage(message)
+            | CliError::State(message)
+            | CliError::Storage(message)
+            | CliError::Other(message) => message,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "error": {
+                "code": self.code(),
+                "message": self.message(),
+            }
+        })
+        .to_string()
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Errors caused by the file system or by a data file that doesn't parse
+fn is_storage(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause.is::<std::io::Error>()
+            || cause.is::<serde_json::Error>()
+            || cause.is::<toml::de::Error>()
+    })
+}
+
+/// Mark the class of a failed call, the way `anyhow::Context` adds context
+pub trait Classify<T> {
+    /// The call only looked at the arguments, so whatever failed is their fault;
+    /// an import file that doesn't parse is a usage error, not a storage one
+    fn usage_error(self) -> Result<T>;
+
+    /// The call failed on the state of the data, unless storage failed underneath:
+    /// a timer that can't be stopped because its file is unreadable is a storage error
+    fn state_error(self) -> Result<T>;
+}
+
+impl<T> Classify<T> for Result<T> {
+    fn usage_error(self) -> Result<T> {
+        self.map_err(|err| CliError::Usage(format!("{:#}", err)).into())
+    }
+
+    fn state_error(self) -> Result<T> {
+        self.map_err(|err| match is_storage(&err) {
+            true => err,
+            false => CliError::State(format!("{:#}", err)).into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn classify(result: Result<()>) -> CliError {
+        CliError::classify(&result.unwrap_err())
+    }
+
+    #[test]
+    fn test_marked_errors_keep_their_class_under_context() {
+        let usage = classify(
+            Err(anyhow::anyhow!("Invalid date 'someday'"))
+                .usage_error()
+                .context("Report failed"),
+        );
+        assert_eq!(
+            usage,
+            CliError::Usage("Report failed: Invalid date 'someday'".to_string())
+        );
+        assert_eq!(usage.exit_code(), 2);
+
+        let state = classify(Err(
+            CliError::State("No session is running".to_string()).into()
+        ));
+        assert_eq!(state.code(), "state");
+        assert_eq!(state.exit_code(), 3);
+    }
+
+    #[test]
+    fn test_io_and_parse_failures_are_storage_errors() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        let storage = classify(Err(
+            anyhow::Error::from(io).context("Failed to read day file")
+        ));
+        assert_eq!(storage.exit_code(), 4);
+        assert_eq!(storage.message(), "Failed to read day file: denied");
+
+        // A state mark doesn't hide a storage failure underneath
+        let corrupt = || anyhow::Error::from(serde_json::from_str::<u32>("{").unwrap_err());
+        let storage = classify(Err(corrupt()).state_error());
+        assert_eq!(storage.code(), "storage");
+        // An argument that doesn't parse is the caller's to fix
+        let usage = classify(Err(corrupt()).usage_error());
+        assert_eq!(usage.code(), "usage");
+    }
+
+    #[test]
+    fn test_unmarked_errors_are_other() {
+        let other = classify(Err(anyhow::anyhow!("Tracker returned 500")));
+        assert_eq!(other, CliError::Other("Tracker returned 500".to_string()));
+        assert_eq!(other.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let error = CliError::State("No session is running".to_string());
+        let json: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "error": {"code": "state", "message": "No session is running"}
+            })
+        );
+        assert!(!error.to_json().contains('\n'));
+    }
+
+    #[test]
+    fn test_error_format_from_unparsed_args() {
+        let args = |line: &str| line.split(' ').map(str::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            ErrorFormat::from_args(args("work-tuimer --error-format json session bogus")),
+            ErrorFormat::Json
+        );
+        assert_eq!(
+            ErrorFormat::from_args(args("work-tuimer session --error-format=JSON")),
+            ErrorFormat::Json
+        );
+        assert_eq!(
+            ErrorFormat::from_args(args("work-tuimer session bogus")),
+            ErrorFormat::Text
+        );
+        assert_eq!(
+            ErrorFormat::from_args(args("work-tuimer --error-format")),
+            ErrorFormat::Text
+        );
+    }
+}
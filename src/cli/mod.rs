@@ -1,8 +1,27 @@
-use crate::storage::Storage;
-use crate::timer::TimerManager;
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::time::Duration;
+use crate::backup;
+use crate::budget::{BudgetLevel, BudgetPeriod, BudgetStatus, budget_statuses, period_statuses};
+use crate::config::{Config, TimerConfig};
+use crate::import::{
+    ImportOptions, ImportedEntry, batch, import_entries, parse_clockify, parse_toggl,
+};
+use crate::integrations::credentials::{KEYRING_SERVICE, Keyring, Secret, SystemKeyring};
+use crate::integrations::worklog;
+use crate::models::{DayData, TimePoint, shift};
+use crate::profile::Profile;
+use crate::report;
+use crate::serve::{self, StatusServer};
+use crate::storage::{self, CommitOutcome, Storage, SystemGit};
+use crate::timefmt::{self, format_duration_human, format_minutes};
+use crate::timer::{TimerManager, find_drift};
+use crate::validation;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+mod date_expr;
+mod error;
+mod plain;
+
+pub use error::{Classify, CliError, ErrorFormat};
 
 /// WorkTimer CLI - Automatic time tracking
 #[derive(Parser)]
@@ -10,18 +29,420 @@ use std::time::Duration;
 #[command(about = "Automatic time tracking with CLI commands and TUI", long_about = None)]
 #[command(version)]
 pub struct Cli {
+    /// Profile to use (defaults to $WORK_TUIMER_PROFILE, then the default profile)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Ignore config.toml and custom themes, using built-in defaults (for troubleshooting)
+    #[arg(long, global = true)]
+    pub safe: bool,
+
+    /// How a failed command reports its error: prose, or one JSON object on stderr
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
+
+    /// Plain text lines instead of tables and modals, for screen readers
+    #[arg(long)]
+    pub plain: bool,
+
+    /// Open the TUI on this day (YYYY-MM-DD, today, -1, mon, next:fri)
+    #[arg(long, allow_hyphen_values = true)]
+    pub date: Option<String>,
+
+    /// Open the TUI with the calendar up
+    #[arg(long)]
+    pub calendar: bool,
+
+    /// Command to run; without one the TUI is started
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
+}
+
+/// How the TUI opens when no subcommand is given
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TuiOptions {
+    /// Day shown first instead of today
+    pub date: Option<time::Date>,
+    /// Calendar modal open before the first draw
+    pub calendar: bool,
+}
+
+/// What a command line asks for: a subcommand, or the TUI
+pub enum Mode {
+    Cli(Commands),
+    Tui(TuiOptions),
+}
+
+impl Cli {
+    /// Route to a subcommand or the TUI, parsing `--date` before the screen is taken over
+    pub fn into_mode(self) -> Result<Mode> {
+        match self.command {
+            Some(_) if self.date.is_some() || self.calendar => Err(CliError::Usage(
+                "--date and --calendar open the TUI; give subcommands their own options"
+                    .to_string(),
+            )
+            .into()),
+            Some(command) => Ok(Mode::Cli(command)),
+            None => Ok(Mode::Tui(TuiOptions {
+                date: self.date.as_deref().map(parse_date).transpose()?,
+                calendar: self.calendar,
+            })),
+        }
+    }
+
+    /// Load the profile's config, or the built-in defaults in safe mode
+    pub fn load_config(&self, profile: &Profile) -> Result<Config> {
+        self.load_config_from(&profile.config_path_in(&Config::get_config_dir()))
+    }
+
+    fn load_config_from(&self, config_path: &std::path::Path) -> Result<Config> {
+        if self.safe {
+            return Ok(Config::default());
+        }
+        Config::load_from(config_path)
+    }
 }
 
 /// Available CLI commands
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Manage timer sessions (start/stop/pause/resume/status)
+    /// Manage timer sessions (start/stop/pause/resume/status/list)
     Session {
         #[command(subcommand)]
         command: SessionCommands,
     },
+
+    /// Manage profiles with separate data and config (list/create)
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+
+    /// Work with issue tracker tickets (open)
+    Ticket {
+        #[command(subcommand)]
+        command: TicketCommands,
+    },
+
+    /// Check the issue tracker config (test)
+    Integrations {
+        #[command(subcommand)]
+        command: IntegrationCommands,
+    },
+
+    /// Store tracker API tokens in the OS keyring (set/remove)
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommands,
+    },
+
+    /// Print reports over all recorded days (budgets/day/html/estimates/audit/check)
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Package all data into a single file (bundle)
+    Export {
+        #[command(subcommand)]
+        command: ExportCommands,
+    },
+
+    /// Import time entries exported from another tracker, or restore a bundle (toggl/clockify/bundle)
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+
+    /// Add or remove records without opening the TUI (import-batch/clear)
+    Record {
+        #[command(subcommand)]
+        command: RecordCommands,
+    },
+
+    /// Change a whole day at once (shift)
+    Day {
+        #[command(subcommand)]
+        command: DayCommands,
+    },
+
+    /// Work with the data directory itself (commit)
+    Storage {
+        #[command(subcommand)]
+        command: StorageCommands,
+    },
+
+    /// Answer /status and /today as JSON over HTTP until interrupted
+    Serve {
+        /// Address to listen on; `[serve] http` or 127.0.0.1:4877 without a value
+        #[arg(long)]
+        http: Option<Option<String>>,
+    },
+}
+
+/// Storage commands
+#[derive(Subcommand)]
+pub enum StorageCommands {
+    /// Commit the data directory to git now, if it is inside a git repository
+    Commit,
+}
+
+/// Record commands
+#[derive(Subcommand)]
+pub enum RecordCommands {
+    /// Add every `[[entry]]` of a TOML or JSON file (date, task, start, end, description)
+    ImportBatch {
+        /// Path to the batch file; `.json` is read as JSON, anything else as TOML
+        #[arg(long)]
+        file: std::path::PathBuf,
+
+        /// Validate and show per-day counts without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Delete every record on a date (the running session is left alone)
+    Clear {
+        /// Day to clear (YYYY-MM-DD, today, -1, mon, next:fri)
+        #[arg(long, allow_hyphen_values = true)]
+        date: String,
+
+        /// Confirm the deletion; without it nothing is removed
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// Day commands
+#[derive(Subcommand)]
+pub enum DayCommands {
+    /// Move every record of a day earlier or later, e.g. `--by +1h` or `--by -30m`
+    Shift {
+        /// Day to shift (YYYY-MM-DD, today, -1, mon, next:fri)
+        #[arg(long, allow_hyphen_values = true)]
+        date: String,
+
+        /// Signed offset: +1h, -30m, 1h30m or plain minutes
+        #[arg(long, allow_hyphen_values = true)]
+        by: String,
+
+        /// Keep records squeezed to nothing at 00:00 or 23:59 instead of refusing
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Export commands
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    /// Zip every day file and the config (and optionally the running timer) for backup
+    Bundle {
+        /// Where to write the zip
+        #[arg(long, default_value = "work-tuimer-backup.zip")]
+        output: std::path::PathBuf,
+
+        /// Also include the running timer
+        #[arg(long)]
+        include_timer: bool,
+    },
+}
+
+/// Import commands, one per export format
+#[derive(Subcommand)]
+pub enum ImportCommands {
+    /// Import a Toggl Track JSON export
+    Toggl(ImportArgs),
+
+    /// Import a Clockify JSON export
+    Clockify(ImportArgs),
+
+    /// Restore a bundle written by `export bundle` into this profile
+    Bundle {
+        /// Path to the zip
+        file: std::path::PathBuf,
+
+        /// Replace day files, the config or the running timer that already exist
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Options shared by every import format
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Path to the JSON export
+    pub file: std::path::PathBuf,
+
+    /// Name records "Project: description" instead of just the description
+    #[arg(long)]
+    pub project_as_prefix: bool,
+
+    /// Show what would be imported without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// UTC offset to place entries in (e.g. +02:00), defaults to the local offset
+    #[arg(long, allow_hyphen_values = true)]
+    pub utc_offset: Option<String>,
+}
+
+/// Ticket commands
+#[derive(Subcommand)]
+pub enum TicketCommands {
+    /// Open a ticket using `[integrations] open_mode`
+    Open {
+        /// Ticket ID, e.g. PROJ-123
+        ticket: String,
+
+        /// Only print the URL (for scripts)
+        #[arg(long)]
+        print_only: bool,
+
+        /// Open the worklog page instead of the ticket
+        #[arg(long)]
+        worklog: bool,
+    },
+}
+
+/// Integration commands
+#[derive(Subcommand)]
+pub enum IntegrationCommands {
+    /// Show how a task name or ticket resolves to a tracker and its URLs
+    Test {
+        /// Task name ("PROJ-123 Fix login") or ticket ID
+        input: String,
+    },
+}
+
+/// Keyring commands, for trackers with `credential = "keyring"`
+#[derive(Subcommand)]
+pub enum AuthCommands {
+    /// Prompt for a tracker's API token and store it in the keyring
+    Set {
+        /// Tracker name as in `[integrations.trackers.<name>]`
+        tracker: String,
+    },
+
+    /// Remove a tracker's API token from the keyring
+    Remove {
+        /// Tracker name as in `[integrations.trackers.<name>]`
+        tracker: String,
+    },
+}
+
+/// Report commands
+#[derive(Subcommand)]
+pub enum ReportCommands {
+    /// Show spent and remaining time for each `[budgets]` entry
+    Budgets {
+        /// Check the `[[task_budgets]]` caps of the current week or month instead
+        #[arg(long, value_enum)]
+        period: Option<BudgetPeriod>,
+
+        /// Tab-separated rows for scripts: key, spent, budget, remaining, percent (minutes)
+        #[arg(long)]
+        plain: bool,
+    },
+
+    /// Show worked time, break time and the span between first start and last end
+    Day {
+        /// Day to report (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+
+        /// One tab-separated row for scripts: date, worked, breaks, span (minutes)
+        #[arg(long)]
+        plain: bool,
+    },
+
+    /// Write a day as a self-contained HTML page (timeline, totals, notes)
+    Html {
+        /// Day to report (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+
+        /// File to write, e.g. report.html
+        #[arg(long)]
+        output: std::path::PathBuf,
+    },
+
+    /// Compare estimated with actual time for records that have an estimate
+    Estimates {
+        /// Group by project instead of task name
+        #[arg(long)]
+        by_project: bool,
+
+        /// Tab-separated rows for scripts: key, records, estimated, actual, percent (minutes)
+        #[arg(long)]
+        plain: bool,
+    },
+
+    /// Worked time per task and weekday for one week, with row and day totals
+    Grid {
+        /// ISO week (YYYY-Www, e.g. 2025-W45), defaults to the current week
+        #[arg(long)]
+        week: Option<String>,
+
+        /// Aligned text, or CSV with minutes in each cell
+        #[arg(long, value_enum, default_value_t = GridFormat::Text)]
+        format: GridFormat,
+    },
+
+    /// Average worked minutes per hour of the day, as a grid of weekdays by hours
+    Heatmap {
+        /// First day (YYYY-MM-DD, today, -1, mon, next:fri), defaults to 4 weeks before --to
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+
+        /// Last day (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+    },
+
+    /// Timer session habits: sessions per day, length, pauses and false starts
+    Focus {
+        /// First day (YYYY-MM-DD, today, -1, mon, next:fri), defaults to 4 weeks before --to
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+
+        /// Last day (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+    },
+
+    /// List records whose times were edited away from the timer sessions that wrote them
+    Audit {
+        /// Day to check (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+    },
+
+    /// List records missing what `[validation]` requires (description, ticket, duration)
+    Check {
+        /// Day to check (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+    },
+}
+
+/// Output of `report grid`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GridFormat {
+    Text,
+    Csv,
+}
+
+/// Profile management commands
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// List available profiles
+    List,
+
+    /// Create a new profile with an empty config
+    Create {
+        /// Profile name (letters, digits, '-' and '_')
+        name: String,
+    },
 }
 
 /// Session management commands
@@ -35,11 +456,39 @@ pub enum SessionCommands {
         /// Optional task description
         #[arg(short, long)]
         description: Option<String>,
+
+        /// Continue today's record with this name instead of creating a new one
+        #[arg(long = "continue")]
+        continue_record: bool,
+
+        /// With --continue, only consider records with this description
+        #[arg(long, requires = "continue_record")]
+        match_description: Option<String>,
+
+        /// Continue today's record with this ID
+        #[arg(long, conflicts_with = "continue_record")]
+        continue_id: Option<u32>,
     },
 
     /// Stop the running timer session
     Stop,
 
+    /// Stop the running session (if any) and start a new one on another task
+    Switch {
+        /// Task name
+        task: String,
+
+        /// Optional task description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Add a timestamped note to the running session, kept in its record's description
+    Note {
+        /// Note text
+        text: String,
+    },
+
     /// Pause the running timer session
     Pause,
 
@@ -48,34 +497,227 @@ pub enum SessionCommands {
 
     /// Show status of running timer session
     Status,
+
+    /// List the timer sessions stopped on a day
+    List {
+        /// Day to list (YYYY-MM-DD, today, -1, mon, next:fri), defaults to today
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+
+        /// Tab-separated rows for scripts: date, start, end, minutes, record ID, task
+        #[arg(long)]
+        plain: bool,
+    },
 }
 
 /// Handle CLI command execution
-pub fn handle_command(cmd: Commands, storage: Storage) -> Result<()> {
+pub fn handle_command(
+    cmd: Commands,
+    profile: &Profile,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
     match cmd {
-        Commands::Session { command } => match command {
-            SessionCommands::Start { task, description } => {
-                handle_start(task, description, storage)
-            }
-            SessionCommands::Stop => handle_stop(storage),
-            SessionCommands::Pause => handle_pause(storage),
-            SessionCommands::Resume => handle_resume(storage),
-            SessionCommands::Status => handle_status(storage),
+        Commands::Session { command } => {
+            auto_stop_overdue(&storage, &config.timer)?;
+
+            match command {
+                SessionCommands::Start {
+                    task,
+                    description,
+                    continue_record,
+                    match_description,
+                    continue_id,
+                } => {
+                    let continuation = match (continue_id, continue_record) {
+                        (Some(id), _) => Some(Continuation::Id(id)),
+                        (None, true) => Some(Continuation::Name { match_description }),
+                        (None, false) => None,
+                    };
+                    handle_start(task, description, continuation, storage, &config.timer)
+                }
+                SessionCommands::Switch { task, description } => {
+                    handle_switch(task, description, storage, &config.timer)
+                }
+                SessionCommands::Stop => handle_stop(storage, config),
+                SessionCommands::Note { text } => handle_note(&text, storage),
+                SessionCommands::Pause => handle_pause(storage),
+                SessionCommands::Resume => handle_resume(storage),
+                SessionCommands::Status => handle_status(storage),
+                SessionCommands::List { date, plain } => {
+                    handle_session_list(date.as_deref(), plain, storage, config)
+                }
+            }
+        }
+        Commands::Profile { command } => match command {
+            ProfileCommands::List => handle_profile_list(profile),
+            ProfileCommands::Create { name } => handle_profile_create(&name),
+        },
+        Commands::Ticket { command } => match command {
+            TicketCommands::Open {
+                ticket,
+                print_only,
+                worklog,
+            } => handle_ticket_open(&ticket, print_only, worklog, config),
         },
+        Commands::Integrations { command } => match command {
+            IntegrationCommands::Test { input } => {
+                for line in integration_test_lines(&input, config) {
+                    println!("{}", line);
+                }
+                Ok(())
+            }
+        },
+        Commands::Auth { command } => match command {
+            AuthCommands::Set { tracker } => handle_auth_set(&tracker, config),
+            AuthCommands::Remove { tracker } => handle_auth_remove(&tracker, config),
+        },
+        Commands::Report { command } => match command {
+            ReportCommands::Budgets { period, plain } => {
+                handle_report_budgets(period, plain, storage, config)
+            }
+            ReportCommands::Day { date, plain } => {
+                handle_report_day(date.as_deref(), plain, storage, config)
+            }
+            ReportCommands::Estimates { by_project, plain } => {
+                handle_report_estimates(by_project, plain, storage)
+            }
+            ReportCommands::Grid { week, format } => {
+                handle_report_grid(week.as_deref(), format, storage, config)
+            }
+            ReportCommands::Heatmap { from, to } => {
+                handle_report_heatmap(from.as_deref(), to.as_deref(), storage, config)
+            }
+            ReportCommands::Focus { from, to } => {
+                handle_report_focus(from.as_deref(), to.as_deref(), storage, config)
+            }
+            ReportCommands::Audit { date } => handle_report_audit(date.as_deref(), storage, config),
+            ReportCommands::Check { date } => handle_report_check(date.as_deref(), storage, config),
+            ReportCommands::Html { date, output } => {
+                handle_report_html(date.as_deref(), &output, storage, config)
+            }
+        },
+        Commands::Export { command } => match command {
+            ExportCommands::Bundle {
+                output,
+                include_timer,
+            } => handle_export_bundle(&output, include_timer, profile, storage),
+        },
+        Commands::Import { command } => match command {
+            ImportCommands::Toggl(args) => handle_import(args, parse_toggl, storage, config),
+            ImportCommands::Clockify(args) => handle_import(args, parse_clockify, storage, config),
+            ImportCommands::Bundle { file, force } => {
+                handle_import_bundle(&file, force, profile, storage)
+            }
+        },
+        Commands::Record { command } => match command {
+            RecordCommands::ImportBatch { file, dry_run } => {
+                handle_import_batch(&file, dry_run, storage)
+            }
+            RecordCommands::Clear { date, yes } => handle_record_clear(&date, yes, storage),
+        },
+        Commands::Day { command } => match command {
+            DayCommands::Shift { date, by, force } => handle_day_shift(&date, &by, force, storage),
+        },
+        Commands::Storage { command } => match command {
+            StorageCommands::Commit => handle_storage_commit(&storage),
+        },
+        Commands::Serve { http } => handle_serve(http.flatten(), storage, config),
     }
 }
 
-/// Start a new session
-fn handle_start(task: String, description: Option<String>, storage: Storage) -> Result<()> {
-    let timer_manager = TimerManager::new(storage);
+/// Stop a session past its `[timer] auto_stop_at` deadline, if one is set
+fn auto_stop_overdue(storage: &Storage, timer_config: &TimerConfig) -> Result<()> {
+    let Some(cutoff) = timer_config.auto_stop_cutoff() else {
+        return Ok(());
+    };
+
+    let now = timefmt::now_local();
+    let timer_manager = TimerManager::new(storage.clone())
+        .create_record_on_start(timer_config.create_record_on_start);
+    if let Some(record) =
+        timer_manager.auto_stop_if_overdue(cutoff, timer_config.restart_after_auto_stop, now)?
+    {
+        println!(
+            "⏹ Session auto-stopped at {} ([timer] auto_stop_at)",
+            record.end
+        );
+        println!("  Task: {}", record.name);
+        println!("  Logged: {} - {}", record.start, record.end);
+        if timer_manager.status()?.is_some() {
+            println!("  A new session was started for today");
+        }
+    }
+
+    Ok(())
+}
+
+/// Stop the running session, if any, and start one on `task`
+fn handle_switch(
+    task: String,
+    description: Option<String>,
+    storage: Storage,
+    timer_config: &TimerConfig,
+) -> Result<()> {
+    let timer_manager = TimerManager::new(storage.clone())
+        .create_record_on_start(timer_config.create_record_on_start);
+    if timer_manager.status()?.is_none() {
+        fill_gap_before_start(&storage, timer_config)?;
+    }
+    let (stopped, timer) = timer_manager.switch(task, description).state_error()?;
+
+    if let Some(record) = stopped {
+        println!(
+            "✓ Stopped {} ({} - {}, {})",
+            record.name,
+            record.start,
+            record.end,
+            format_minutes(record.total_minutes)
+        );
+    }
+    println!("✓ Session started");
+    println!("  Task: {}", timer.task_name);
+    if let Some(desc) = &timer.description {
+        println!("  Description: {}", desc);
+    }
+    println!("  Started at: {}", format_time(timer.start_time));
 
+    Ok(())
+}
+
+/// Start a new session
+fn handle_start(
+    task: String,
+    description: Option<String>,
+    continuation: Option<Continuation>,
+    storage: Storage,
+    timer_config: &TimerConfig,
+) -> Result<()> {
     // Trim task name
     let task = task.trim().to_string();
     if task.is_empty() {
-        return Err(anyhow::anyhow!("Task name cannot be empty"));
+        return Err(CliError::Usage("Task name cannot be empty".to_string()).into());
     }
 
-    let timer = timer_manager.start(task, description, None, None)?;
+    let (source_record_id, source_record_date) = match continuation {
+        Some(continuation) => {
+            let today = timefmt::now_local().date();
+            let day_data = storage.load(&today)?;
+            let id = resolve_continuation(&day_data, &task, &continuation).state_error()?;
+            (Some(id), Some(today))
+        }
+        None => (None, None),
+    };
+
+    let timer_manager = TimerManager::new(storage.clone())
+        .create_record_on_start(timer_config.create_record_on_start);
+    // A continued record is extended over the gap when the session stops
+    if source_record_id.is_none() && timer_manager.status()?.is_none() {
+        fill_gap_before_start(&storage, timer_config)?;
+    }
+    let timer = timer_manager
+        .start(task, description, source_record_id, source_record_date)
+        .state_error()?;
 
     let start_time = format_time(timer.start_time);
     println!("✓ Session started");
@@ -83,27 +725,117 @@ fn handle_start(task: String, description: Option<String>, storage: Storage) ->
     if let Some(desc) = &timer.description {
         println!("  Description: {}", desc);
     }
+    match timer.source_record_id {
+        Some(id) if source_record_id.is_some() => println!("  Continuing record: {}", id),
+        Some(id) => println!("  Added record: {}", id),
+        None => {}
+    }
     println!("  Started at: {}", start_time);
 
     Ok(())
 }
 
+/// Add a break over the gap since today's last record, if `[timer] fill_gaps_with_break` is set
+fn fill_gap_before_start(storage: &Storage, timer_config: &TimerConfig) -> Result<()> {
+    let Some((min, max)) = timer_config.break_gap_bounds() else {
+        return Ok(());
+    };
+    let now = timefmt::now_local();
+    let now_time = TimePoint::new(now.hour(), now.minute()).map_err(|e| anyhow::anyhow!(e))?;
+    let mut day_data = storage.load(&now.date())?;
+    if let Some(id) = day_data.fill_gap_with_break(now_time, min, max) {
+        storage.save(&day_data)?;
+        let record = &day_data.work_records[&id];
+        println!("✓ Break added ({} - {})", record.start, record.end);
+    }
+    Ok(())
+}
+
+/// Which of today's records a new session continues
+enum Continuation {
+    /// The record with this name (and description, if given)
+    Name { match_description: Option<String> },
+    /// The record with this ID
+    Id(u32),
+}
+
+/// Find the record a continued session updates
+///
+/// # Errors
+/// Returns an error if no record matches, or if several do (listing their IDs
+/// so one can be picked with `--continue-id`)
+fn resolve_continuation(
+    day_data: &DayData,
+    task: &str,
+    continuation: &Continuation,
+) -> Result<u32> {
+    let match_description = match continuation {
+        Continuation::Id(id) => {
+            return match day_data.work_records.get(id) {
+                Some(record) if record.name == task => Ok(*id),
+                Some(record) => Err(anyhow::anyhow!(
+                    "Record {} is '{}', not '{}'",
+                    id,
+                    record.name,
+                    task
+                )),
+                None => Err(anyhow::anyhow!(
+                    "No record with ID {} on {}",
+                    id,
+                    day_data.date
+                )),
+            };
+        }
+        Continuation::Name { match_description } => match_description.as_deref(),
+    };
+
+    let candidates: Vec<u32> = day_data
+        .get_sorted_records()
+        .into_iter()
+        .filter(|record| record.name == task)
+        .filter(|record| match_description.is_none_or(|desc| record.description == desc.trim()))
+        .map(|record| record.id)
+        .collect();
+
+    match candidates.as_slice() {
+        [id] => Ok(*id),
+        [] => Err(anyhow::anyhow!(
+            "No record named '{}'{} on {}",
+            task,
+            match_description
+                .map(|desc| format!(" with description '{}'", desc))
+                .unwrap_or_default(),
+            day_data.date
+        )),
+        ids => Err(anyhow::anyhow!(
+            "Several records named '{}' on {} (IDs {}); use --match-description or --continue-id",
+            task,
+            day_data.date,
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
 /// Stop the running session
-fn handle_stop(storage: Storage) -> Result<()> {
+fn handle_stop(storage: Storage, config: &Config) -> Result<()> {
     let timer_manager = TimerManager::new(storage);
 
     // Load and validate timer exists
     let timer = timer_manager
         .status()?
-        .ok_or_else(|| anyhow::anyhow!("No session is running"))?;
+        .ok_or_else(|| CliError::State("No session is running".to_string()))?;
 
     let elapsed = timer_manager.get_elapsed_duration(&timer);
-    let formatted_duration = format_duration(elapsed);
+    let formatted_duration = format_duration_human(elapsed);
 
     let start_time = format_time(timer.start_time);
 
     // Stop the timer and get the work record
-    let record = timer_manager.stop()?;
+    let stopped = timer_manager.stop().state_error()?;
+    let record = &stopped.session;
 
     // Format end time from the work record (HH:MM format)
     let end_time = format!("{:02}:{:02}:{:02}", record.end.hour, record.end.minute, 0);
@@ -113,6 +845,21 @@ fn handle_stop(storage: Storage) -> Result<()> {
     println!("  Duration: {}", formatted_duration);
     println!("  Started at: {}", start_time);
     println!("  Ended at: {}", end_time);
+    println!("  Record: {} ({})", stopped.summary(), stopped.date);
+    if let Some(worklog) = worklog::auto_worklog(&stopped, config, &worklog::CurlClient) {
+        println!("  Worklog: {}", worklog);
+    }
+
+    Ok(())
+}
+
+/// Add a note to the running session
+fn handle_note(text: &str, storage: Storage) -> Result<()> {
+    let timer = TimerManager::new(storage).add_note(text).state_error()?;
+
+    println!("✓ Note added");
+    println!("  Task: {}", timer.task_name);
+    println!("  Notes: {}", timer.notes.len());
 
     Ok(())
 }
@@ -123,11 +870,11 @@ fn handle_pause(storage: Storage) -> Result<()> {
 
     let timer = timer_manager
         .status()?
-        .ok_or_else(|| anyhow::anyhow!("No session is running"))?;
+        .ok_or_else(|| CliError::State("No session is running".to_string()))?;
 
-    let _paused_timer = timer_manager.pause()?;
+    let _paused_timer = timer_manager.pause().state_error()?;
     let elapsed = timer_manager.get_elapsed_duration(&timer);
-    let formatted_duration = format_duration(elapsed);
+    let formatted_duration = format_duration_human(elapsed);
 
     println!("⏸ Session paused");
     println!("  Task: {}", timer.task_name);
@@ -142,11 +889,11 @@ fn handle_resume(storage: Storage) -> Result<()> {
 
     let timer = timer_manager
         .status()?
-        .ok_or_else(|| anyhow::anyhow!("No session is running"))?;
+        .ok_or_else(|| CliError::State("No session is running".to_string()))?;
 
-    let _resumed_timer = timer_manager.resume()?;
+    let _resumed_timer = timer_manager.resume().state_error()?;
     let elapsed = timer_manager.get_elapsed_duration(&timer);
-    let formatted_duration = format_duration(elapsed);
+    let formatted_duration = format_duration_human(elapsed);
 
     println!("▶ Session resumed");
     println!("  Task: {}", timer.task_name);
@@ -162,7 +909,7 @@ fn handle_status(storage: Storage) -> Result<()> {
     match timer_manager.status()? {
         Some(timer) => {
             let elapsed = timer_manager.get_elapsed_duration(&timer);
-            let formatted_duration = format_duration(elapsed);
+            let formatted_duration = format_duration_human(elapsed);
             let start_time = format_time(timer.start_time);
 
             println!("⏱ Session Status");
@@ -189,60 +936,1731 @@ fn handle_status(storage: Storage) -> Result<()> {
     Ok(())
 }
 
-/// Format time::OffsetDateTime for display (HH:MM:SS)
-fn format_time(dt: time::OffsetDateTime) -> String {
-    format!("{:02}:{:02}:{:02}", dt.hour(), dt.minute(), dt.second())
-}
+/// List the sessions journaled for a day, with the task of the record each one wrote
+fn handle_session_list(
+    date: Option<&str>,
+    plain: bool,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let date = date_or_today(date)?;
+    let day_data = storage.load(&date)?;
+    let sessions = storage.load_session_log(&date)?;
 
-/// Format Duration for display (h:mm:ss or mm:ss)
-fn format_duration(duration: Duration) -> String {
-    let total_secs = duration.as_secs();
-    let hours = total_secs / 3600;
-    let minutes = (total_secs % 3600) / 60;
-    let seconds = total_secs % 60;
-
-    if hours > 0 {
-        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
-    } else {
-        format!("{}m {:02}s", minutes, seconds)
+    if plain {
+        for line in plain::session_rows(&day_data, &sessions) {
+            println!("{}", line);
+        }
+        return Ok(());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    if sessions.is_empty() {
+        println!(
+            "No timer sessions stopped on {}",
+            config.ui.format_date(date)
+        );
+        return Ok(());
+    }
 
-    #[test]
-    fn test_format_duration_hours_minutes_seconds() {
-        let duration = Duration::from_secs(3661); // 1h 1m 1s
-        assert_eq!(format_duration(duration), "1h 01m 01s");
+    println!("⏱ Sessions on {}", config.ui.format_date(date));
+    for session in &sessions {
+        let minutes = crate::models::WorkRecord::calculate_duration(&session.start, &session.end);
+        let task = day_data
+            .work_records
+            .get(&session.record_id)
+            .map_or("(record deleted)", |record| record.name.as_str());
+        println!(
+            "  {} - {}  {}  {} (record {})",
+            session.start,
+            session.end,
+            format_minutes(minutes),
+            task,
+            session.record_id
+        );
     }
 
-    #[test]
-    fn test_format_duration_minutes_seconds() {
-        let duration = Duration::from_secs(125); // 2m 5s
-        assert_eq!(format_duration(duration), "2m 05s");
+    Ok(())
+}
+
+/// List profiles, marking the active one
+fn handle_profile_list(active: &Profile) -> Result<()> {
+    for name in Profile::list_in(&Config::get_config_dir()) {
+        let marker = if name == active.name() { "*" } else { " " };
+        println!("{} {}", marker, name);
     }
 
-    #[test]
-    fn test_format_duration_seconds_only() {
-        let duration = Duration::from_secs(45);
-        assert_eq!(format_duration(duration), "0m 45s");
+    Ok(())
+}
+
+/// Create a profile's config and data directories
+fn handle_profile_create(name: &str) -> Result<()> {
+    let profile = Profile::named(name).usage_error()?;
+    if profile.is_default() {
+        return Err(CliError::State("The default profile always exists".to_string()).into());
     }
 
-    #[test]
-    fn test_format_duration_zero() {
-        let duration = Duration::from_secs(0);
-        assert_eq!(format_duration(duration), "0m 00s");
+    let config_path = profile.config_path_in(&Config::get_config_dir());
+    if config_path.exists() {
+        return Err(CliError::State(format!("Profile '{}' already exists", profile.name())).into());
     }
 
-    #[test]
-    fn test_format_time() {
+    let config_dir = config_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid config path: {:?}", config_path))?;
+    std::fs::create_dir_all(config_dir)
+        .with_context(|| format!("Failed to create profile directory: {:?}", config_dir))?;
+    std::fs::write(&config_path, profile_config_template(&profile))
+        .with_context(|| format!("Failed to write profile config: {:?}", config_path))?;
+    Storage::new_for(&profile)?;
+
+    println!("✓ Profile created");
+    println!("  Name: {}", profile.name());
+    println!("  Config: {}", config_path.display());
+    println!(
+        "  Use it with: work-tuimer --profile {} (or {}={})",
+        profile.name(),
+        crate::profile::PROFILE_ENV_VAR,
+        profile.name()
+    );
+
+    Ok(())
+}
+
+/// Store a tracker's token in the keyring, read without echo
+fn handle_auth_set(tracker: &str, config: &Config) -> Result<()> {
+    check_tracker_name(tracker, config)?;
+    let secret = read_secret(&format!("API token for {}: ", tracker))?;
+    if secret.expose().is_empty() {
+        anyhow::bail!("No token entered; nothing stored");
+    }
+    SystemKeyring.set(KEYRING_SERVICE, tracker, &secret)?;
+    println!("✓ Stored the token for {} in the keyring", tracker);
+    if config.integrations.trackers[tracker].credential.trim() != "keyring" {
+        println!(
+            "  Set credential = \"keyring\" under [integrations.trackers.{}] to use it",
+            tracker
+        );
+    }
+    Ok(())
+}
+
+fn handle_auth_remove(tracker: &str, config: &Config) -> Result<()> {
+    check_tracker_name(tracker, config)?;
+    if SystemKeyring.delete(KEYRING_SERVICE, tracker)? {
+        println!("✓ Removed the token for {} from the keyring", tracker);
+    } else {
+        println!("No token for {} in the keyring", tracker);
+    }
+    Ok(())
+}
+
+fn check_tracker_name(tracker: &str, config: &Config) -> Result<()> {
+    if !config.integrations.trackers.contains_key(tracker) {
+        return Err(CliError::Usage(format!(
+            "Unknown tracker '{}' (add [integrations.trackers.{}] to the config)",
+            tracker, tracker
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Read a line from the terminal without echoing it; piped input is read as is
+fn read_secret(prompt: &str) -> Result<Secret> {
+    use std::io::{IsTerminal, Write};
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        let mut line = String::new();
+        stdin.read_line(&mut line)?;
+        return Ok(Secret::new(line.trim_end_matches(['\r', '\n'])));
+    }
+
+    eprint!("{}", prompt);
+    std::io::stderr().flush()?;
+    crossterm::terminal::enable_raw_mode()?;
+    let typed = read_hidden_keys();
+    crossterm::terminal::disable_raw_mode()?;
+    eprintln!();
+    typed
+}
+
+fn read_hidden_keys() -> Result<Secret> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    let mut typed = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(Secret::new(typed)),
+            KeyCode::Esc => anyhow::bail!("Cancelled"),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                anyhow::bail!("Cancelled")
+            }
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Char(c) => typed.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Open (or print) a ticket's URL
+fn handle_ticket_open(
+    ticket: &str,
+    print_only: bool,
+    worklog: bool,
+    config: &Config,
+) -> Result<()> {
+    use crate::integrations::{
+        OpenOutcome, copy_to_clipboard, open_url_in_browser, open_with_fallback, ticket_url,
+    };
+
+    let url = ticket_url(ticket.trim(), config, worklog).usage_error()?;
+    if print_only {
+        println!("{}", url);
+        return Ok(());
+    }
+
+    match open_with_fallback(
+        &url,
+        config.integrations.open_mode,
+        open_url_in_browser,
+        copy_to_clipboard,
+    ) {
+        OpenOutcome::Opened => println!("✓ Opened {}", url),
+        OpenOutcome::Copied => println!("✓ Copied {}", url),
+        OpenOutcome::Shown { reason } => {
+            if let Some(reason) = reason {
+                eprintln!("{}", reason);
+            }
+            println!("{}", url);
+        }
+    }
+
+    Ok(())
+}
+
+/// Trace ticket extraction, tracker choice and URLs for `integrations test`
+///
+/// Uses the same functions as `T`/`L` in the TUI and `ticket open`.
+fn integration_test_lines(input: &str, config: &Config) -> Vec<String> {
+    use crate::integrations::{build_url, detect_tracker_candidates, extract_ticket_from_name};
+
+    let mut lines = Vec::new();
+    // The TUI extracts the ticket from a task name; `ticket open` takes it as given
+    let ticket = match extract_ticket_from_name(input) {
+        Some(ticket) => {
+            lines.push(format!("Ticket: {}", ticket));
+            ticket
+        }
+        None => {
+            let ticket = input.trim().to_string();
+            lines.push(format!(
+                "Ticket: {} (no ticket found in a task name, using the input as is)",
+                ticket
+            ));
+            ticket
+        }
+    };
+
+    let detection = detect_tracker_candidates(&ticket, config);
+    if detection.candidates.is_empty() {
+        lines.push("Matching trackers: none".to_string());
+    } else {
+        lines.push("Matching trackers (highest priority first):".to_string());
+        for candidate in &detection.candidates {
+            lines.push(format!(
+                "  {} (priority {})",
+                candidate.name, candidate.priority
+            ));
+        }
+    }
+
+    let Some(tracker) = detection.chosen else {
+        lines.push("Chosen: none (no pattern matched and no default_tracker set)".to_string());
+        return lines;
+    };
+    if detection.candidates.is_empty() {
+        lines.push(format!("Chosen: {} (default_tracker)", tracker));
+    } else {
+        lines.push(format!("Chosen: {}", tracker));
+    }
+
+    for (label, for_worklog) in [("Browse URL", false), ("Worklog URL", true)] {
+        match build_url(&ticket, &tracker, config, for_worklog) {
+            Ok(url) => lines.push(format!("{}: {}", label, url)),
+            Err(e) => lines.push(format!("{}: error: {}", label, e)),
+        }
+    }
+    lines
+}
+
+/// Print spent/remaining time per budget, from all stored day files
+fn handle_report_budgets(
+    period: Option<BudgetPeriod>,
+    plain: bool,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    if let Some(period) = period {
+        return handle_report_period_budgets(period, plain, storage, config);
+    }
+
+    if config.budgets.is_empty() {
+        if !plain {
+            println!("No budgets configured (add a [budgets] section to config.toml)");
+        }
+        return Ok(());
+    }
+
+    let days = storage.load_all_days()?;
+    let statuses = budget_statuses(&config.budgets, &days);
+
+    if plain {
+        for line in plain::budget_rows(&statuses) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    println!("💰 Budgets");
+    for line in format_budget_report(&statuses) {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// `[[task_budgets]]` caps of the week or month containing today
+fn handle_report_period_budgets(
+    period: BudgetPeriod,
+    plain: bool,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let (first, last) = period.range(timefmt::now_local().date(), config.ui.week_start.weekday());
+    let statuses = period_statuses(
+        &config.task_budgets,
+        period,
+        &storage.load_days_between(first, last)?,
+    );
+
+    if plain {
+        for line in plain::budget_rows(&statuses) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!(
+            "No {}ly budgets configured (add [[task_budgets]] with {}ly_hours to config.toml)",
+            period.label(),
+            period.label()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "💰 Budgets this {}, {} to {}",
+        period.label(),
+        config.ui.format_date(first),
+        config.ui.format_date(last)
+    );
+    for line in format_budget_report(&statuses) {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+fn handle_report_estimates(by_project: bool, plain: bool, storage: Storage) -> Result<()> {
+    let accuracy = report::estimate_accuracy(&storage.load_all_days()?, by_project);
+
+    if plain {
+        for line in plain::estimate_rows(&accuracy) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if accuracy.is_empty() {
+        println!("No records have an estimate (set one with E in the TUI)");
+        return Ok(());
+    }
+
+    println!("⏳ Estimates");
+    for line in format_estimate_report(&accuracy) {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+fn handle_report_grid(
+    week: Option<&str>,
+    format: GridFormat,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let monday = date_expr::parse_week(week, timefmt::now_local().date()).usage_error()?;
+    let days: [DayData; 7] = storage
+        .load_days_between(monday, monday + time::Duration::days(6))?
+        .try_into()
+        .expect("seven days");
+    let grid = report::WeekGrid::build(&days, config.ui.group_by);
+
+    if format == GridFormat::Csv {
+        print!("{}", grid.csv());
+        return Ok(());
+    }
+
+    let (year, week, _) = monday.to_iso_week_date();
+    println!(
+        "🗓 Week {}-W{:02}, {} to {}",
+        year,
+        week,
+        config.ui.format_date(grid.dates[0]),
+        config.ui.format_date(grid.dates[6])
+    );
+    if grid.rows.is_empty() {
+        println!("  No work recorded");
+        return Ok(());
+    }
+    for line in grid.lines(config.ui.locale) {
+        println!("  {}", line);
+    }
+    Ok(())
+}
+
+/// Days `report heatmap` and `report focus` cover when --from is left out
+const REPORT_DEFAULT_DAYS: i64 = 28;
+
+fn handle_report_heatmap(
+    from: Option<&str>,
+    to: Option<&str>,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let (from, to) = report_range(from, to)?;
+
+    let mut grid = report::HeatGrid::new();
+    for day in storage.load_days_between(from, to)? {
+        grid.add_day(&day);
+    }
+
+    println!(
+        "🔥 Minutes worked per hour, {} to {} (average per day)",
+        config.ui.format_date(from),
+        config.ui.format_date(to)
+    );
+    for line in grid.lines(config.ui.week_start.weekday()) {
+        println!("  {}", line);
+    }
+    println!(
+        "  {} none  {} a quarter  {} half  {} a full hour",
+        report::shade(0),
+        report::shade(15),
+        report::shade(30),
+        report::shade(60)
+    );
+    Ok(())
+}
+
+/// Print focus statistics over the journaled timer sessions of a range of days
+fn handle_report_focus(
+    from: Option<&str>,
+    to: Option<&str>,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let (from, to) = report_range(from, to)?;
+    let days = (to - from).whole_days() as u32 + 1;
+    let sessions = storage.load_session_log_between(from, to)?;
+    let stats = report::FocusStats::from_sessions(days, &sessions);
+
+    println!(
+        "🎯 Focus, {} to {}",
+        config.ui.format_date(from),
+        config.ui.format_date(to)
+    );
+    for line in stats.lines() {
+        println!("  {}", line);
+    }
+    Ok(())
+}
+
+/// `--from`/`--to` of a multi-day report, `--from` defaulting to 4 weeks before `--to`
+fn report_range(from: Option<&str>, to: Option<&str>) -> Result<(time::Date, time::Date)> {
+    let to = date_or_today(to)?;
+    let from = match from {
+        Some(from) => parse_date(from)?,
+        None => to.saturating_sub(time::Duration::days(REPORT_DEFAULT_DAYS - 1)),
+    };
+    if from > to {
+        return Err(CliError::Usage(format!("--from {} is after --to {}", from, to)).into());
+    }
+    Ok((from, to))
+}
+
+/// Print drift between a day's records and its journaled timer sessions
+fn handle_report_day(
+    date: Option<&str>,
+    plain: bool,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let date = date_or_today(date)?;
+    let day_data = storage.load(&date)?;
+    let breakdown = day_data.breakdown();
+
+    if plain {
+        println!("{}", plain::day_row(date, &breakdown));
+        return Ok(());
+    }
+
+    println!("📅 Day report for {}", config.ui.format_date(date));
+    println!("  Worked: {}", format_minutes(breakdown.worked_minutes));
+    println!("  Breaks: {}", format_minutes(breakdown.break_minutes));
+    println!("  Span:   {}", format_minutes(breakdown.span_minutes));
+    let billable_default = config.defaults.billable;
+    if day_data.has_non_billable(billable_default) {
+        println!(
+            "  Billable: {}",
+            format_minutes(day_data.billable_minutes(billable_default))
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_report_html(
+    date: Option<&str>,
+    output: &std::path::Path,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let date = date_or_today(date)?;
+    let html = report::day_html(&storage.load(&date)?);
+    std::fs::write(output, html)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!(
+        "✓ Wrote report for {} to {}",
+        config.ui.format_date(date),
+        output.display()
+    );
+    Ok(())
+}
+
+fn handle_report_audit(date: Option<&str>, storage: Storage, config: &Config) -> Result<()> {
+    let date = date_or_today(date)?;
+
+    let day_data = storage.load(&date)?;
+    let sessions = storage.load_session_log(&date)?;
+    let findings = find_drift(
+        &day_data.get_sorted_records(),
+        &sessions,
+        config.timer.audit_tolerance(),
+    );
+
+    if findings.is_empty() {
+        println!(
+            "✓ Records for {} match their timer sessions",
+            config.ui.format_date(date)
+        );
+        return Ok(());
+    }
+
+    println!("⚠ Audit for {}", config.ui.format_date(date));
+    for finding in &findings {
+        println!("  {}", finding);
+    }
+
+    Ok(())
+}
+
+fn handle_report_check(date: Option<&str>, storage: Storage, config: &Config) -> Result<()> {
+    let date = date_or_today(date)?;
+    let day_data = storage.load(&date)?;
+    let required = config.required_fields();
+
+    let incomplete: Vec<_> = day_data
+        .get_sorted_records()
+        .into_iter()
+        .map(|record| (record, validation::record_issues(record, required)))
+        .filter(|(_, issues)| !issues.is_empty())
+        .collect();
+    if incomplete.is_empty() {
+        println!("✓ Records for {} are complete", config.ui.format_date(date));
+        return Ok(());
+    }
+
+    println!(
+        "⚠ {} record(s) on {} need attention",
+        incomplete.len(),
+        config.ui.format_date(date)
+    );
+    for (record, issues) in &incomplete {
+        println!(
+            "  #{} {}-{} {}: {}",
+            record.id,
+            record.start,
+            record.end,
+            record.name,
+            validation::describe_issues(issues)
+        );
+    }
+    Ok(())
+}
+
+fn handle_storage_commit(storage: &Storage) -> Result<()> {
+    let today = date_or_today(None)?;
+    let message = storage::commit_message(&std::collections::BTreeSet::from([today]));
+    match storage::commit_data_dir(&SystemGit, storage.data_dir(), &message)? {
+        CommitOutcome::Committed => println!("✓ Committed: {}", message),
+        CommitOutcome::NothingToCommit => println!("Nothing to commit"),
+        CommitOutcome::NotARepository => {
+            return Err(CliError::State(format!(
+                "{} is not inside a git repository (run `git init` there first)",
+                storage.data_dir().display()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn handle_serve(http: Option<String>, storage: Storage, config: &Config) -> Result<()> {
+    let addr = http
+        .or_else(|| config.serve.http.clone())
+        .unwrap_or_else(|| serve::DEFAULT_ADDR.to_string());
+    let server = StatusServer::start(&addr, config.serve.allow_remote, storage, config.clone())?;
+    println!(
+        "Serving /status and /today on http://{} (Ctrl+C to stop)",
+        server.local_addr()
+    );
+    server.wait();
+    Ok(())
+}
+
+fn handle_import(
+    args: ImportArgs,
+    parse: fn(&str) -> Result<Vec<ImportedEntry>>,
+    storage: Storage,
+    config: &Config,
+) -> Result<()> {
+    let contents = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let entries = parse(&contents).usage_error()?;
+    // A given offset is used as is; the local one follows the listed clock changes
+    let (now, offset_changes) = match args.utc_offset.as_deref() {
+        Some(value) => (
+            time::OffsetDateTime::now_utc().to_offset(parse_utc_offset(value)?),
+            &[][..],
+        ),
+        None => (timefmt::now_local(), &config.time.offset_changes[..]),
+    };
+    let options = ImportOptions {
+        now,
+        offset_changes,
+        project_as_prefix: args.project_as_prefix,
+    };
+
+    let summaries = import_entries(&storage, &entries, options, args.dry_run)?;
+    if args.dry_run {
+        println!("Dry run: nothing was written");
+    }
+    for day in &summaries {
+        println!(
+            "  {}: {} created, {} skipped (already imported)",
+            day.date, day.created, day.skipped
+        );
+    }
+    let created: usize = summaries.iter().map(|day| day.created).sum();
+    let skipped: usize = summaries.iter().map(|day| day.skipped).sum();
+    let action = if args.dry_run {
+        "Would import"
+    } else {
+        "Imported"
+    };
+    println!(
+        "✓ {} {} records ({} skipped) from {} entries",
+        action,
+        created,
+        skipped,
+        entries.len()
+    );
+
+    Ok(())
+}
+
+fn handle_export_bundle(
+    output: &std::path::Path,
+    include_timer: bool,
+    profile: &Profile,
+    storage: Storage,
+) -> Result<()> {
+    let config_path = profile.config_path_in(&Config::get_config_dir());
+    let bundle = backup::write_bundle(&storage, &config_path, include_timer, output)?;
+    println!("{}", bundle_summary("Exported", &bundle));
+    println!("  to {}", output.display());
+    Ok(())
+}
+
+fn handle_import_bundle(
+    file: &std::path::Path,
+    force: bool,
+    profile: &Profile,
+    storage: Storage,
+) -> Result<()> {
+    let config_path = profile.config_path_in(&Config::get_config_dir());
+    let bundle = backup::read_bundle(file)?;
+    backup::restore_bundle(&bundle, &storage, &config_path, force)?;
+    println!("{}", bundle_summary("Restored", &bundle));
+    Ok(())
+}
+
+/// "✓ Exported 12 days, the config and the running timer"
+fn bundle_summary(action: &str, bundle: &backup::Bundle) -> String {
+    let mut parts = vec![format!("{} days", bundle.days.len())];
+    if bundle.config.is_some() {
+        parts.push("the config".to_string());
+    }
+    if bundle.timer.is_some() {
+        parts.push("the running timer".to_string());
+    }
+    let last = parts.pop().unwrap_or_default();
+    match parts.is_empty() {
+        true => format!("✓ {} {}", action, last),
+        false => format!("✓ {} {} and {}", action, parts.join(", "), last),
+    }
+}
+
+fn handle_import_batch(file: &std::path::Path, dry_run: bool, storage: Storage) -> Result<()> {
+    let contents = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let entries = batch::parse_batch(file, &contents).usage_error()?;
+    let (records, invalid) = batch::validate_entries(&entries);
+
+    let summaries = batch::import_batch(&storage, records, dry_run)?;
+    if dry_run {
+        println!("Dry run: nothing was written");
+    }
+    for day in &summaries {
+        println!(
+            "  {}: {} created, {} skipped (already present)",
+            day.date, day.created, day.skipped
+        );
+    }
+    for row in &invalid {
+        println!("  ✗ entry {}: {}", row.row, row.reason);
+    }
+    let created: usize = summaries.iter().map(|day| day.created).sum();
+    let skipped: usize = summaries.iter().map(|day| day.skipped).sum();
+    let action = if dry_run { "Would add" } else { "Added" };
+    println!(
+        "✓ {} {} records from {} entries ({} already present, {} invalid)",
+        action,
+        created,
+        entries.len(),
+        skipped,
+        invalid.len()
+    );
+
+    Ok(())
+}
+
+fn handle_record_clear(date: &str, yes: bool, storage: Storage) -> Result<()> {
+    let date = parse_date(date)?;
+    let mut day = storage.load(&date)?;
+    let count = day.work_records.len();
+    if count == 0 {
+        println!("No records on {}", date);
+        return Ok(());
+    }
+    if !yes {
+        return Err(CliError::Usage(format!(
+            "This deletes {} record(s) on {}; run again with --yes to confirm",
+            count, date
+        ))
+        .into());
+    }
+
+    day.clear_records();
+    storage.save(&day)?;
+    println!("✓ Cleared {} record(s) on {}", count, date);
+    Ok(())
+}
+
+fn handle_day_shift(date: &str, by: &str, force: bool, storage: Storage) -> Result<()> {
+    let date = parse_date(date)?;
+    let minutes = shift::parse_offset(by)
+        .map_err(anyhow::Error::msg)
+        .usage_error()?;
+    let mut day = storage.load(&date)?;
+    let count = day.work_records.len();
+    if count == 0 {
+        println!("No records on {}", date);
+        return Ok(());
+    }
+
+    let report = shift::shift_day(&mut day, minutes, force)
+        .map_err(|e| anyhow::anyhow!("{}; nothing was changed (--force truncates)", e))
+        .state_error()?;
+    storage.save(&day)?;
+    println!(
+        "✓ Shifted {} record(s) on {} by {}",
+        count,
+        date,
+        shift::format_offset(minutes)
+    );
+    if let Some(summary) = report.summary() {
+        println!("  {}", summary);
+    }
+    Ok(())
+}
+
+/// Parse a "+HH:MM" / "-HH:MM" offset argument
+fn parse_utc_offset(value: &str) -> Result<time::UtcOffset> {
+    let format = time::macros::format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+    time::UtcOffset::parse(value.trim(), &format)
+        .with_context(|| format!("Invalid UTC offset '{}' (use +HH:MM)", value))
+        .usage_error()
+}
+
+/// Parse an optional date argument, defaulting to today
+fn date_or_today(value: Option<&str>) -> Result<time::Date> {
+    match value {
+        Some(value) => parse_date(value),
+        None => Ok(timefmt::now_local().date()),
+    }
+}
+
+/// Parse a date argument (see `date_expr`), noting on stderr how a weekday was read
+fn parse_date(value: &str) -> Result<time::Date> {
+    let resolved = date_expr::parse(value, timefmt::now_local().date()).usage_error()?;
+    if let Some(hint) = resolved.hint {
+        eprintln!("{}", hint);
+    }
+    Ok(resolved.date)
+}
+
+/// One line per budget: key, spent / budget, percentage, remaining
+fn format_budget_report(statuses: &[BudgetStatus]) -> Vec<String> {
+    let key_width = statuses
+        .iter()
+        .map(|status| status.key.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    statuses
+        .iter()
+        .map(|status| {
+            let marker = match status.level() {
+                BudgetLevel::Ok => " ",
+                BudgetLevel::Warning => "⚠",
+                BudgetLevel::Exceeded => "⛔",
+            };
+            let remaining = if status.level() == BudgetLevel::Exceeded {
+                format!(
+                    "over by {}",
+                    format_minutes(status.spent_minutes - status.budget_minutes)
+                )
+            } else {
+                format!("{} left", format_minutes(status.remaining_minutes()))
+            };
+            format!(
+                "{} {:<width$}  {} / {} ({}%), {}",
+                marker,
+                status.key,
+                format_minutes(status.spent_minutes),
+                format_minutes(status.budget_minutes),
+                status.percent(),
+                remaining,
+                width = key_width
+            )
+        })
+        .collect()
+}
+
+fn format_estimate_report(accuracy: &[report::EstimateAccuracy]) -> Vec<String> {
+    let key_width = accuracy
+        .iter()
+        .map(|group| group.key.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    accuracy
+        .iter()
+        .map(|group| {
+            let marker = if group.actual_minutes > group.estimated_minutes {
+                "⚠"
+            } else {
+                " "
+            };
+            format!(
+                "{} {:<width$}  {} / {} estimated ({}%), {} record(s)",
+                marker,
+                group.key,
+                format_minutes(group.actual_minutes),
+                format_minutes(group.estimated_minutes),
+                group.percent(),
+                group.records,
+                width = key_width
+            )
+        })
+        .collect()
+}
+
+/// Starting config for a new profile; every section is optional
+fn profile_config_template(profile: &Profile) -> String {
+    format!(
+        "# work-tuimer config for profile '{}'\n\
+         # All sections are optional; see the README for available settings.\n\
+         \n\
+         # [theme]\n\
+         # active = \"default\"\n",
+        profile.name()
+    )
+}
+
+/// Format time::OffsetDateTime for display (HH:MM:SS)
+fn format_time(dt: time::OffsetDateTime) -> String {
+    format!("{:02}:{:02}:{:02}", dt.hour(), dt.minute(), dt.second())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_duration_hours_minutes_seconds() {
+        let duration = Duration::from_secs(3661); // 1h 1m 1s
+        assert_eq!(format_duration_human(duration), "1h 01m 01s");
+    }
+
+    #[test]
+    fn test_format_duration_minutes_seconds() {
+        let duration = Duration::from_secs(125); // 2m 5s
+        assert_eq!(format_duration_human(duration), "2m 05s");
+    }
+
+    #[test]
+    fn test_format_duration_seconds_only() {
+        let duration = Duration::from_secs(45);
+        assert_eq!(format_duration_human(duration), "0m 45s");
+    }
+
+    #[test]
+    fn test_format_duration_zero() {
+        let duration = Duration::from_secs(0);
+        assert_eq!(format_duration_human(duration), "0m 00s");
+    }
+
+    #[test]
+    fn test_format_time() {
         use time::macros::datetime;
         let dt = datetime!(2025-01-15 14:30:45 UTC);
         assert_eq!(format_time(dt), "14:30:45");
     }
 
+    #[test]
+    fn test_profile_config_template_parses() {
+        let profile = Profile::named("acme").unwrap();
+        let template = profile_config_template(&profile);
+        assert!(template.contains("profile 'acme'"));
+        let config: Config = toml::from_str(&template).unwrap();
+        assert_eq!(config.theme.active, "default");
+    }
+
+    #[test]
+    fn test_cli_parses_global_profile_flag() {
+        let cli =
+            Cli::try_parse_from(["work-tuimer", "session", "status", "--profile", "acme"]).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("acme"));
+        assert!(matches!(cli.command, Some(Commands::Session { .. })));
+
+        let cli = Cli::try_parse_from(["work-tuimer", "--profile", "acme"]).unwrap();
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn test_format_budget_report() {
+        let statuses = vec![
+            BudgetStatus {
+                key: "ACME-".to_string(),
+                budget_minutes: 600,
+                spent_minutes: 90,
+            },
+            BudgetStatus {
+                key: "ACME-77".to_string(),
+                budget_minutes: 120,
+                spent_minutes: 100,
+            },
+            BudgetStatus {
+                key: "OPS-1".to_string(),
+                budget_minutes: 60,
+                spent_minutes: 75,
+            },
+        ];
+
+        assert_eq!(
+            format_budget_report(&statuses),
+            vec![
+                "  ACME-    1h 30m / 10h 00m (15%), 8h 30m left",
+                "⚠ ACME-77  1h 40m / 2h 00m (83%), 0h 20m left",
+                "⛔ OPS-1    1h 15m / 1h 00m (125%), over by 0h 15m",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_estimate_report() {
+        let accuracy = vec![
+            report::EstimateAccuracy {
+                key: "Docs".to_string(),
+                records: 1,
+                estimated_minutes: 60,
+                actual_minutes: 45,
+            },
+            report::EstimateAccuracy {
+                key: "Review".to_string(),
+                records: 2,
+                estimated_minutes: 90,
+                actual_minutes: 120,
+            },
+        ];
+
+        assert_eq!(
+            format_estimate_report(&accuracy),
+            vec![
+                "  Docs    0h 45m / 1h 00m estimated (75%), 1 record(s)",
+                "⚠ Review  2h 00m / 1h 30m estimated (133%), 2 record(s)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_report_budgets() {
+        let cli = Cli::try_parse_from(["work-tuimer", "report", "budgets"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report {
+                command: ReportCommands::Budgets {
+                    period: None,
+                    plain: false
+                }
+            })
+        ));
+
+        let cli =
+            Cli::try_parse_from(["work-tuimer", "report", "budgets", "--period", "week"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report {
+                command: ReportCommands::Budgets {
+                    period: Some(BudgetPeriod::Week),
+                    plain: false
+                }
+            })
+        ));
+        assert!(
+            Cli::try_parse_from(["work-tuimer", "report", "budgets", "--period", "year"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_accepts_negative_day_offsets() {
+        let cli = Cli::try_parse_from(["work-tuimer", "report", "day", "--date", "-1"]).unwrap();
+        match cli.command {
+            Some(Commands::Report {
+                command: ReportCommands::Day { date, .. },
+            }) => assert_eq!(date.as_deref(), Some("-1")),
+            _ => panic!("expected report day"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_report_heatmap_range() {
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "report",
+            "heatmap",
+            "--from",
+            "2025-11-01",
+            "--to",
+            "-1",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Report {
+                command: ReportCommands::Heatmap { from, to },
+            }) => {
+                assert_eq!(from.as_deref(), Some("2025-11-01"));
+                assert_eq!(to.as_deref(), Some("-1"));
+            }
+            _ => panic!("expected report heatmap"),
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_auth() {
+        let cli = Cli::try_parse_from(["work-tuimer", "auth", "set", "jira"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Auth {
+                command: AuthCommands::Set { tracker }
+            }) if tracker == "jira"
+        ));
+        let cli = Cli::try_parse_from(["work-tuimer", "auth", "remove", "jira"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Auth {
+                command: AuthCommands::Remove { tracker }
+            }) if tracker == "jira"
+        ));
+    }
+
+    #[test]
+    fn test_auth_needs_a_configured_tracker() {
+        let error = check_tracker_name("jira", &Config::default()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown tracker 'jira' (add [integrations.trackers.jira] to the config)"
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_report_grid() {
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "report",
+            "grid",
+            "--week",
+            "2025-W45",
+            "--format",
+            "csv",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Report {
+                command: ReportCommands::Grid { week, format },
+            }) => {
+                assert_eq!(week.as_deref(), Some("2025-W45"));
+                assert_eq!(format, GridFormat::Csv);
+            }
+            _ => panic!("expected report grid"),
+        }
+
+        let cli = Cli::try_parse_from(["work-tuimer", "report", "grid"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report {
+                command: ReportCommands::Grid {
+                    week: None,
+                    format: GridFormat::Text
+                }
+            })
+        ));
+    }
+
+    fn mode(args: &[&str]) -> Result<Mode> {
+        Cli::try_parse_from(args).unwrap().into_mode()
+    }
+
+    #[test]
+    fn test_no_arguments_open_the_tui_on_today() {
+        assert!(matches!(
+            mode(&["work-tuimer"]).unwrap(),
+            Mode::Tui(TuiOptions {
+                date: None,
+                calendar: false
+            })
+        ));
+    }
+
+    #[test]
+    fn test_flags_only_open_the_tui_where_asked() {
+        match mode(&["work-tuimer", "--date", "2025-11-03", "--calendar"]).unwrap() {
+            Mode::Tui(options) => {
+                assert_eq!(options.date, Some(time::macros::date!(2025 - 11 - 03)));
+                assert!(options.calendar);
+            }
+            Mode::Cli(_) => panic!("expected the TUI"),
+        }
+        let error = mode(&["work-tuimer", "--date", "2025-13-40"])
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("2025-13-40"), "{}", error);
+    }
+
+    #[test]
+    fn test_subcommands_run_in_cli_mode() {
+        assert!(matches!(
+            mode(&["work-tuimer", "report", "day", "--date", "-1"]).unwrap(),
+            Mode::Cli(Commands::Report {
+                command: ReportCommands::Day { .. }
+            })
+        ));
+        let error = mode(&["work-tuimer", "--calendar", "report", "budgets"])
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .starts_with("--date and --calendar open the TUI")
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_plain_listings() {
+        let cli = Cli::try_parse_from(["work-tuimer", "report", "budgets", "--plain"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Report {
+                command: ReportCommands::Budgets {
+                    period: None,
+                    plain: true
+                }
+            })
+        ));
+        // The subcommand flag is not the TUI's screen reader mode
+        assert!(!cli.plain);
+
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "session",
+            "list",
+            "--date",
+            "2025-11-06",
+            "--plain",
+        ])
+        .unwrap();
+        let Some(Commands::Session {
+            command: SessionCommands::List { date, plain },
+        }) = cli.command
+        else {
+            panic!("expected session list");
+        };
+        assert_eq!(date.as_deref(), Some("2025-11-06"));
+        assert!(plain);
+    }
+
+    #[test]
+    fn test_cli_parses_session_switch() {
+        let cli = Cli::try_parse_from(["work-tuimer", "session", "switch", "Deploy", "-d", "v2"])
+            .unwrap();
+        let Some(Commands::Session {
+            command: SessionCommands::Switch { task, description },
+        }) = cli.command
+        else {
+            panic!("expected session switch");
+        };
+        assert_eq!(task, "Deploy");
+        assert_eq!(description.as_deref(), Some("v2"));
+    }
+
+    #[test]
+    fn test_handle_switch_leaves_one_record_and_one_timer() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        TimerManager::new(storage.clone())
+            .start("Review".to_string(), None, None, None)
+            .unwrap();
+
+        handle_switch(
+            "Deploy".to_string(),
+            None,
+            storage.clone(),
+            &TimerConfig::default(),
+        )
+        .unwrap();
+
+        let dates = storage.list_day_files().unwrap();
+        let records: usize = dates
+            .iter()
+            .map(|date| storage.load(date).unwrap().work_records.len())
+            .sum();
+        assert_eq!(records, 1);
+        let active = storage.load_active_timer().unwrap().unwrap();
+        assert_eq!(active.task_name, "Deploy");
+    }
+
+    #[test]
+    fn test_cli_parses_record_import_batch() {
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "record",
+            "import-batch",
+            "--file",
+            "entries.toml",
+            "--dry-run",
+        ])
+        .unwrap();
+        let Some(Commands::Record {
+            command: RecordCommands::ImportBatch { file, dry_run },
+        }) = cli.command
+        else {
+            panic!("expected record import-batch");
+        };
+        assert_eq!(file, std::path::PathBuf::from("entries.toml"));
+        assert!(dry_run);
+    }
+
+    #[test]
+    fn test_cli_parses_day_shift_with_negative_offset() {
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "day",
+            "shift",
+            "--date",
+            "2025-11-06",
+            "--by",
+            "-30m",
+        ])
+        .unwrap();
+        let Some(Commands::Day {
+            command: DayCommands::Shift { date, by, force },
+        }) = cli.command
+        else {
+            panic!("expected day shift");
+        };
+        assert_eq!(
+            (date.as_str(), by.as_str(), force),
+            ("2025-11-06", "-30m", false)
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_error_format_anywhere() {
+        let cli = Cli::try_parse_from(["work-tuimer", "session", "stop"]).unwrap();
+        assert_eq!(cli.error_format, ErrorFormat::Text);
+        let cli = Cli::try_parse_from(["work-tuimer", "session", "stop", "--error-format", "json"])
+            .unwrap();
+        assert_eq!(cli.error_format, ErrorFormat::Json);
+        let cli = Cli::try_parse_from(["work-tuimer", "--error-format=json"]).unwrap();
+        assert_eq!(cli.error_format, ErrorFormat::Json);
+        assert!(Cli::try_parse_from(["work-tuimer", "--error-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_handler_errors_map_to_exit_codes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let config = Config::default();
+        let class = |result: Result<()>| {
+            let error = CliError::classify(&result.unwrap_err());
+            (error.code(), error.exit_code())
+        };
+
+        assert_eq!(
+            class(handle_record_clear("someday", true, storage.clone())),
+            ("usage", 2)
+        );
+        assert_eq!(
+            class(handle_start(
+                " ".to_string(),
+                None,
+                None,
+                storage.clone(),
+                &config.timer
+            )),
+            ("usage", 2)
+        );
+        assert_eq!(class(handle_stop(storage.clone(), &config)), ("state", 3));
+        assert_eq!(class(handle_pause(storage.clone())), ("state", 3));
+        TimerManager::new(storage.clone())
+            .start("Review".to_string(), None, None, None)
+            .unwrap();
+        assert_eq!(
+            class(handle_start(
+                "Deploy".to_string(),
+                None,
+                None,
+                storage.clone(),
+                &config.timer
+            )),
+            ("state", 3)
+        );
+        assert_eq!(
+            class(handle_start(
+                "Deploy".to_string(),
+                None,
+                Some(Continuation::Id(42)),
+                storage.clone(),
+                &config.timer
+            )),
+            ("state", 3)
+        );
+
+        std::fs::write(storage.data_dir().join("2025-11-06.json"), "{").unwrap();
+        assert_eq!(
+            class(handle_report_day(
+                Some("2025-11-06"),
+                true,
+                storage.clone(),
+                &config
+            )),
+            ("storage", 4)
+        );
+    }
+
+    #[test]
+    fn test_handle_day_shift_refuses_then_forces() {
+        use crate::models::{TimePoint, WorkRecord};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let date = time::macros::date!(2025 - 11 - 06);
+        let mut day = DayData::new(date);
+        for (id, start, end) in [(1, (9, 0), (10, 0)), (2, (23, 0), (23, 30))] {
+            day.add_record(WorkRecord::new(
+                id,
+                format!("Task {}", id),
+                TimePoint::new(start.0, start.1).unwrap(),
+                TimePoint::new(end.0, end.1).unwrap(),
+            ));
+        }
+        storage.save(&day).unwrap();
+
+        assert!(handle_day_shift("2025-11-06", "+1h", false, storage.clone()).is_err());
+        let day = storage.load(&date).unwrap();
+        assert_eq!(day.work_records[&1].start, TimePoint::new(9, 0).unwrap());
+
+        handle_day_shift("2025-11-06", "+1h", true, storage.clone()).unwrap();
+        let day = storage.load(&date).unwrap();
+        assert_eq!(day.work_records[&1].start, TimePoint::new(10, 0).unwrap());
+        assert_eq!(day.work_records[&2].start, TimePoint::new(23, 59).unwrap());
+    }
+
+    #[test]
+    fn test_handle_record_clear_needs_yes_and_spares_other_days() {
+        use crate::models::{TimePoint, WorkRecord};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        for date in [
+            time::macros::date!(2025 - 11 - 06),
+            time::macros::date!(2025 - 11 - 07),
+        ] {
+            let mut day = DayData::new(date);
+            day.add_record(WorkRecord::new(
+                1,
+                "Review".to_string(),
+                TimePoint::new(9, 0).unwrap(),
+                TimePoint::new(10, 0).unwrap(),
+            ));
+            storage.save(&day).unwrap();
+        }
+        TimerManager::new(storage.clone())
+            .start("Deploy".to_string(), None, None, None)
+            .unwrap();
+
+        assert!(handle_record_clear("2025-11-06", false, storage.clone()).is_err());
+        let day = storage.load(&time::macros::date!(2025 - 11 - 06)).unwrap();
+        assert_eq!(day.work_records.len(), 1);
+
+        handle_record_clear("2025-11-06", true, storage.clone()).unwrap();
+        let day = storage.load(&time::macros::date!(2025 - 11 - 06)).unwrap();
+        assert!(day.work_records.is_empty());
+        let other = storage.load(&time::macros::date!(2025 - 11 - 07)).unwrap();
+        assert_eq!(other.work_records.len(), 1);
+        assert!(storage.load_active_timer().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_integration_test_lines_trace_the_choice() {
+        let config: Config = toml::from_str(
+            r#"
+[integrations.trackers.jira]
+enabled = true
+base_url = "https://jira.example.com"
+ticket_patterns = ["^[A-Z]+-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+worklog_url = "{base_url}/browse/{ticket}?worklog"
+
+[integrations.trackers.linear]
+enabled = true
+priority = 3
+base_url = "https://linear.app/team"
+ticket_patterns = ["^LIN-\\d+$"]
+browse_url = "{base_url}/issue/{ticket}"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            integration_test_lines("LIN-42 Fix login", &config),
+            vec![
+                "Ticket: LIN-42",
+                "Matching trackers (highest priority first):",
+                "  linear (priority 3)",
+                "  jira (priority 0)",
+                "Chosen: linear",
+                "Browse URL: https://linear.app/team/issue/LIN-42",
+                "Worklog URL: https://linear.app/team/issue/LIN-42",
+            ]
+        );
+
+        let lines = integration_test_lines("#12", &config);
+        assert_eq!(lines[1], "Matching trackers: none");
+        assert_eq!(
+            lines[2],
+            "Chosen: none (no pattern matched and no default_tracker set)"
+        );
+    }
+
+    #[test]
+    fn test_safe_mode_ignores_config_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[theme]\nactive = \"broken\"\n[ui]\nshow_ids = \"not a bool\"\n",
+        )
+        .unwrap();
+
+        let cli = Cli::try_parse_from(["work-tuimer"]).unwrap();
+        assert!(cli.load_config_from(&config_path).is_err());
+
+        let cli = Cli::try_parse_from(["work-tuimer", "--safe"]).unwrap();
+        let config = cli.load_config_from(&config_path).unwrap();
+        assert_eq!(config.theme.active, "default");
+        assert!(!config.ui.show_ids);
+
+        // The flag is global, so it also works after a subcommand
+        let cli = Cli::try_parse_from(["work-tuimer", "session", "status", "--safe"]).unwrap();
+        assert!(cli.safe);
+    }
+
+    #[test]
+    fn test_cli_parses_plain() {
+        assert!(!Cli::try_parse_from(["work-tuimer"]).unwrap().plain);
+        assert!(
+            Cli::try_parse_from(["work-tuimer", "--plain"])
+                .unwrap()
+                .plain
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_import() {
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "import",
+            "toggl",
+            "export.json",
+            "--project-as-prefix",
+            "--dry-run",
+            "--utc-offset",
+            "-05:00",
+        ])
+        .unwrap();
+        let Some(Commands::Import {
+            command: ImportCommands::Toggl(args),
+        }) = cli.command
+        else {
+            panic!("expected import toggl");
+        };
+        assert_eq!(args.file, std::path::PathBuf::from("export.json"));
+        assert!(args.project_as_prefix);
+        assert!(args.dry_run);
+        assert_eq!(
+            parse_utc_offset(args.utc_offset.as_deref().unwrap()).unwrap(),
+            time::macros::offset!(-5)
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_bundle_export_and_import() {
+        let cli =
+            Cli::try_parse_from(["work-tuimer", "export", "bundle", "--include-timer"]).unwrap();
+        let Some(Commands::Export {
+            command:
+                ExportCommands::Bundle {
+                    output,
+                    include_timer,
+                },
+        }) = cli.command
+        else {
+            panic!("expected export bundle");
+        };
+        assert_eq!(output, std::path::PathBuf::from("work-tuimer-backup.zip"));
+        assert!(include_timer);
+
+        let cli = Cli::try_parse_from(["work-tuimer", "import", "bundle", "backup.zip", "--force"])
+            .unwrap();
+        let Some(Commands::Import {
+            command: ImportCommands::Bundle { file, force },
+        }) = cli.command
+        else {
+            panic!("expected import bundle");
+        };
+        assert_eq!(file, std::path::PathBuf::from("backup.zip"));
+        assert!(force);
+    }
+
+    #[test]
+    fn test_bundle_summary() {
+        let mut bundle = backup::Bundle {
+            days: vec![DayData::new(time::macros::date!(2025 - 11 - 06))],
+            config: None,
+            timer: None,
+        };
+        assert_eq!(bundle_summary("Exported", &bundle), "✓ Exported 1 days");
+        bundle.config = Some(String::new());
+        assert_eq!(
+            bundle_summary("Restored", &bundle),
+            "✓ Restored 1 days and the config"
+        );
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(
+            parse_utc_offset("+02:00").unwrap(),
+            time::macros::offset!(+2)
+        );
+        assert_eq!(
+            parse_utc_offset("+05:30").unwrap(),
+            time::macros::offset!(+5:30)
+        );
+        assert!(parse_utc_offset("2").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_argument() {
+        use time::macros::date;
+        assert_eq!(parse_date("2025-11-06").unwrap(), date!(2025 - 11 - 06));
+        assert_eq!(parse_date("today").unwrap(), timefmt::now_local().date());
+        assert!(parse_date("06/11/2025").is_err());
+    }
+
+    fn day_with_duplicates() -> DayData {
+        use crate::models::{TimePoint, WorkRecord};
+        use time::macros::date;
+
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        for (id, name, description, hour) in [
+            (1, "Standup", "", 9),
+            (2, "Review", "backend", 10),
+            (3, "Review", "frontend", 13),
+        ] {
+            let mut record = WorkRecord::new(
+                id,
+                name.to_string(),
+                TimePoint::new(hour, 0).unwrap(),
+                TimePoint::new(hour + 1, 0).unwrap(),
+            );
+            record.description = description.to_string();
+            day.add_record(record);
+        }
+        day
+    }
+
+    #[test]
+    fn test_continue_single_match() {
+        let day = day_with_duplicates();
+        let by_name = Continuation::Name {
+            match_description: None,
+        };
+        assert_eq!(resolve_continuation(&day, "Standup", &by_name).unwrap(), 1);
+        assert!(resolve_continuation(&day, "Lunch", &by_name).is_err());
+    }
+
+    #[test]
+    fn test_continue_ambiguous_lists_candidates() {
+        let day = day_with_duplicates();
+        let err = resolve_continuation(
+            &day,
+            "Review",
+            &Continuation::Name {
+                match_description: None,
+            },
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("IDs 2, 3"), "{}", err);
+
+        let by_description = Continuation::Name {
+            match_description: Some("frontend".to_string()),
+        };
+        assert_eq!(
+            resolve_continuation(&day, "Review", &by_description).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_continue_id_targets_exactly() {
+        let day = day_with_duplicates();
+        assert_eq!(
+            resolve_continuation(&day, "Review", &Continuation::Id(2)).unwrap(),
+            2
+        );
+        assert!(resolve_continuation(&day, "Standup", &Continuation::Id(2)).is_err());
+        assert!(resolve_continuation(&day, "Review", &Continuation::Id(9)).is_err());
+    }
+
+    #[test]
+    fn test_cli_continue_flags() {
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "session",
+            "start",
+            "Review",
+            "--continue",
+            "--match-description",
+            "frontend",
+        ]);
+        assert!(cli.is_ok());
+
+        // --match-description only makes sense with --continue
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "session",
+            "start",
+            "Review",
+            "--match-description",
+            "frontend",
+        ]);
+        assert!(cli.is_err());
+
+        let cli = Cli::try_parse_from([
+            "work-tuimer",
+            "session",
+            "start",
+            "Review",
+            "--continue",
+            "--continue-id",
+            "2",
+        ]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn test_cli_parses_ticket_open() {
+        let cli = Cli::try_parse_from(["work-tuimer", "ticket", "open", "PROJ-1", "--print-only"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Ticket {
+                command: TicketCommands::Open {
+                    print_only: true,
+                    worklog: false,
+                    ..
+                }
+            })
+        ));
+    }
+
     #[test]
     fn test_cli_has_version() {
         use clap::CommandFactory;
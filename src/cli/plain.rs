@@ -0,0 +1,191 @@
+//! Tab-separated output for scripts (`--plain`)
+//!
+//! One row per line and no header, so the output pipes straight into `cut` or
+//! `awk`. Durations are whole minutes, ready to be summed.
+
+use crate::budget::BudgetStatus;
+use crate::models::{DayBreakdown, DayData, WorkRecord};
+use crate::report::EstimateAccuracy;
+use crate::timer::SessionLogEntry;
+use time::Date;
+
+/// Join `fields` with tabs, turning tabs and line breaks inside a field into spaces
+pub fn row<I>(fields: I) -> String
+where
+    I: IntoIterator,
+    I::Item: ToString,
+{
+    fields
+        .into_iter()
+        .map(|field| field.to_string().replace(['\t', '\n', '\r'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// `key  spent  budget  remaining  percent`, remaining negative once over budget
+pub fn budget_rows(statuses: &[BudgetStatus]) -> Vec<String> {
+    statuses
+        .iter()
+        .map(|status| {
+            let remaining = status.budget_minutes as i64 - status.spent_minutes as i64;
+            row([
+                status.key.clone(),
+                status.spent_minutes.to_string(),
+                status.budget_minutes.to_string(),
+                remaining.to_string(),
+                status.percent().to_string(),
+            ])
+        })
+        .collect()
+}
+
+/// `key  records  estimated  actual  percent`
+pub fn estimate_rows(accuracy: &[EstimateAccuracy]) -> Vec<String> {
+    accuracy
+        .iter()
+        .map(|group| {
+            row([
+                group.key.clone(),
+                group.records.to_string(),
+                group.estimated_minutes.to_string(),
+                group.actual_minutes.to_string(),
+                group.percent().to_string(),
+            ])
+        })
+        .collect()
+}
+
+/// `date  worked  breaks  span`
+pub fn day_row(date: Date, breakdown: &DayBreakdown) -> String {
+    row([
+        date.to_string(),
+        breakdown.worked_minutes.to_string(),
+        breakdown.break_minutes.to_string(),
+        breakdown.span_minutes.to_string(),
+    ])
+}
+
+/// `date  start  end  minutes  record_id  task`, the task empty if the record is gone
+pub fn session_rows(day: &DayData, sessions: &[SessionLogEntry]) -> Vec<String> {
+    sessions
+        .iter()
+        .map(|session| {
+            let task = day
+                .work_records
+                .get(&session.record_id)
+                .map(|record| record.name.clone())
+                .unwrap_or_default();
+            row([
+                session.date.to_string(),
+                session.start.to_string(),
+                session.end.to_string(),
+                WorkRecord::calculate_duration(&session.start, &session.end).to_string(),
+                session.record_id.to_string(),
+                task,
+            ])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+    use time::macros::date;
+
+    /// Fail unless every line has `columns` fields of plain ASCII
+    fn assert_tsv(lines: &[String], columns: usize) {
+        for line in lines {
+            assert_eq!(line.split('\t').count(), columns, "{:?}", line);
+            assert!(line.is_ascii(), "{:?}", line);
+            assert!(!line.contains(['│', '─', '|']), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_row_keeps_fields_on_one_line() {
+        assert_eq!(row(["a\tb", "c\nd", "e"]), "a b\tc d\te");
+        assert_eq!(row([1, 2]), "1\t2");
+    }
+
+    #[test]
+    fn test_budget_rows() {
+        let statuses = [
+            BudgetStatus {
+                key: "PROJ-101".to_string(),
+                budget_minutes: 120,
+                spent_minutes: 90,
+            },
+            BudgetStatus {
+                key: "OPS".to_string(),
+                budget_minutes: 60,
+                spent_minutes: 75,
+            },
+        ];
+        let lines = budget_rows(&statuses);
+        assert_tsv(&lines, 5);
+        assert_eq!(
+            lines,
+            ["PROJ-101\t90\t120\t30\t75", "OPS\t75\t60\t-15\t125"]
+        );
+    }
+
+    #[test]
+    fn test_estimate_rows() {
+        let accuracy = [EstimateAccuracy {
+            key: "Review".to_string(),
+            records: 2,
+            estimated_minutes: 90,
+            actual_minutes: 120,
+        }];
+        let lines = estimate_rows(&accuracy);
+        assert_tsv(&lines, 5);
+        assert_eq!(lines, ["Review\t2\t90\t120\t133"]);
+    }
+
+    #[test]
+    fn test_day_row() {
+        let breakdown = DayBreakdown {
+            worked_minutes: 405,
+            break_minutes: 30,
+            span_minutes: 480,
+        };
+        let line = day_row(date!(2025 - 11 - 06), &breakdown);
+        assert_tsv(std::slice::from_ref(&line), 4);
+        assert_eq!(line, "2025-11-06\t405\t30\t480");
+    }
+
+    #[test]
+    fn test_session_rows() {
+        let time = |h, m| TimePoint::new(h, m).unwrap();
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(WorkRecord::new(
+            4,
+            "PROJ-101 Code review".to_string(),
+            time(9, 0),
+            time(10, 30),
+        ));
+        let session = |record_id, start, end| SessionLogEntry {
+            date: day.date,
+            record_id,
+            start,
+            end,
+            extended: false,
+            focus: crate::timer::SessionFocus::default(),
+        };
+        let sessions = [
+            session(4, time(9, 0), time(10, 30)),
+            session(9, time(11, 0), time(11, 20)),
+        ];
+
+        let lines = session_rows(&day, &sessions);
+        assert_tsv(&lines, 6);
+        assert_eq!(
+            lines,
+            [
+                "2025-11-06\t09:00\t10:30\t90\t4\tPROJ-101 Code review",
+                "2025-11-06\t11:00\t11:20\t20\t9\t",
+            ]
+        );
+    }
+}
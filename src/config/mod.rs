@@ -1,9 +1,13 @@
+use crate::models::{GroupBy, OffsetChange, TimePoint};
+use crate::profile::Profile;
+use crate::validation::RequiredFields;
 use anyhow::{Context, Result};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use time::{Date, Weekday};
 
 /// Configuration for issue tracker integrations (JIRA, Linear, GitHub, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -13,9 +17,57 @@ pub struct Config {
 
     #[serde(default)]
     pub theme: ThemeConfig,
+
+    #[serde(default)]
+    pub timer: TimerConfig,
+
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    #[serde(default)]
+    pub serve: ServeConfig,
+
+    #[serde(default)]
+    pub ui: UiConfig,
+
+    #[serde(default)]
+    pub time: TimeConfig,
+
+    #[serde(default)]
+    pub upcoming: UpcomingConfig,
+
+    #[serde(default)]
+    pub validation: ValidationConfig,
+
+    #[serde(default)]
+    pub defaults: DefaultsConfig,
+
+    #[serde(default)]
+    pub edit: EditConfig,
+
+    /// Total-minute budgets keyed by ticket id ("ACME-77") or ticket prefix ("ACME-")
+    #[serde(default)]
+    pub budgets: HashMap<String, u32>,
+
+    /// Custom per-record fields ("cost_center"), edited with `F`
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldDef>,
+
+    /// Browse-mode key overrides by action name (`quit = "Ctrl+c"`)
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+
+    /// Tasks started with one Browse key (`[[quick_tasks]] key = "1" name = "Standup"`)
+    #[serde(default)]
+    pub quick_tasks: Vec<QuickTask>,
+
+    /// Weekly or monthly hour caps per task or project
+    /// (`[[task_budgets]] task = "Meetings" weekly_hours = 5`)
+    #[serde(default)]
+    pub task_budgets: Vec<TaskBudget>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct IntegrationConfig {
     /// Default tracker name when auto-detection is ambiguous
     #[serde(default)]
@@ -24,9 +76,29 @@ pub struct IntegrationConfig {
     /// Map of tracker name to tracker configuration
     #[serde(default)]
     pub trackers: HashMap<String, TrackerConfig>,
+
+    /// What `T`/`L` and `ticket open` do with a URL
+    #[serde(default)]
+    pub open_mode: OpenMode,
+    /// Log each stopped session to its ticket through the tracker's API
+    #[serde(default)]
+    pub auto_worklog: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// How ticket and record URLs are opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenMode {
+    /// Launch the system browser, falling back to showing (and copying) the URL
+    #[default]
+    Browser,
+    /// Copy the URL to the clipboard (OSC 52 terminal escape)
+    Copy,
+    /// Only show the URL so it can be selected and copied by hand
+    Print,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct TrackerConfig {
     #[serde(default)]
     pub enabled: bool,
@@ -41,15 +113,615 @@ pub struct TrackerConfig {
     /// URL template for worklog page: {base_url}, {ticket}
     #[serde(default)]
     pub worklog_url: String,
+    /// When several trackers match a ticket, the highest priority wins (ties by name)
+    #[serde(default)]
+    pub priority: u32,
+    /// Account for JIRA worklogs (`auto_worklog`), usually an email address
+    #[serde(default)]
+    pub api_user: String,
+    /// Environment variable holding the API token for `api_user`
+    #[serde(default)]
+    pub api_token_env: String,
+    /// Where the API token comes from: `env:VAR`, `keyring` or `file:PATH`;
+    /// takes precedence over `api_token_env`
+    #[serde(default)]
+    pub credential: String,
+    /// Icon in front of this tracker's ticket badge, instead of 🎫
+    #[serde(default)]
+    pub badge_icon: Option<String>,
+    /// Color of this tracker's ticket badge (a theme color: name, hex or r,g,b)
+    #[serde(default)]
+    pub badge_color: Option<String>,
+}
+
+impl TrackerConfig {
+    /// `badge_color` parsed, None when unset
+    pub fn badge_color(&self) -> Option<Color> {
+        self.badge_color.as_deref().map(parse_color)
+    }
+}
+
+/// Wall-clock handling settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TimeConfig {
+    /// Correct durations on days where the local clock changes (DST)
+    #[serde(default)]
+    pub dst_aware: bool,
+
+    /// Known clock changes; without a timezone database these must be listed by hand
+    #[serde(default)]
+    pub offset_changes: Vec<OffsetChange>,
+}
+
+impl TimeConfig {
+    /// Clock changes to apply to durations, empty unless `dst_aware` is on
+    pub fn active_offset_changes(&self) -> &[OffsetChange] {
+        if self.dst_aware {
+            &self.offset_changes
+        } else {
+            &[]
+        }
+    }
+}
+
+/// Display settings for the TUI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Show each record's id in the records table
+    #[serde(default)]
+    pub show_ids: bool,
+
+    /// Start with Start/End shown relative to now ("7h ago"); toggled with `R`
+    #[serde(default)]
+    pub relative_times: bool,
+
+    /// Keep records flagged with `m` in the day file instead of for this session only
+    #[serde(default)]
+    pub persist_flags: bool,
+
+    /// Show worked, break and span time in the header instead of a single total
+    #[serde(default)]
+    pub show_day_breakdown: bool,
+
+    /// Animate a spinner in the timer bar while a session is running
+    #[serde(default = "default_timer_spinner")]
+    pub timer_spinner: bool,
+
+    /// Elapsed time in the timer bar to the second ("MM:SS") or minute ("HH:MM")
+    #[serde(default)]
+    pub timer_precision: TimerPrecision,
+
+    /// Plain text lines instead of tables and modals, for screen readers (`--plain`)
+    #[serde(default)]
+    pub screen_reader: bool,
+
+    /// Period totalled in the header at startup; cycled with `Tab`
+    #[serde(default)]
+    pub header_totals: TotalsWindow,
+
+    /// Language of month and weekday names
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// How dates are shown in the header and CLI output (`%Y-%m-%d`, `%a %e %B`, ...)
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+
+    /// First column of the calendar
+    #[serde(default)]
+    pub week_start: WeekStart,
+
+    /// Summary rows per task name, ticket id or project (`name`, `ticket`, `project`)
+    #[serde(default)]
+    pub group_by: GroupBy,
+
+    /// Total only the records shown while the table is filtered by a summary row
+    #[serde(default)]
+    pub filtered_totals: bool,
+
+    /// Show Start/End snapped to this many minutes (0 = exact); stored times are untouched
+    #[serde(default)]
+    pub snap_display_minutes: u8,
+
+    /// Show the day's worked minutes per hour as a strip under the summary
+    #[serde(default)]
+    pub heat_strip: bool,
+
+    /// Start/End on a "24h" or "12h" clock; unset follows the system locale
+    #[serde(default)]
+    pub time_format: Option<TimeFormat>,
+
+    /// Cut task names in the records table to this many columns (0 = no limit)
+    #[serde(default)]
+    pub max_name_chars: usize,
+}
+
+impl UiConfig {
+    /// `date` in the configured format and language
+    pub fn format_date(&self, date: Date) -> String {
+        crate::timefmt::format_date(date, &self.date_format, self.locale)
+    }
+
+    /// The configured clock, or the system locale's when `time_format` is unset
+    pub fn clock(&self) -> TimeFormat {
+        self.time_format
+            .unwrap_or_else(crate::timefmt::detect_time_format)
+    }
+}
+
+/// Built-in languages for month and weekday names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+/// Day a calendar week starts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Monday => Weekday::Monday,
+            WeekStart::Sunday => Weekday::Sunday,
+        }
+    }
 }
 
+/// Days summed in the header's totals panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TotalsWindow {
+    /// The day on screen
+    #[default]
+    Today,
+    /// Today and the 6 days before it
+    Week,
+    /// Today and the 29 days before it
+    Month,
+}
+
+impl TotalsWindow {
+    /// Number of days in the window
+    pub fn days(self) -> u32 {
+        match self {
+            TotalsWindow::Today => 1,
+            TotalsWindow::Week => 7,
+            TotalsWindow::Month => 30,
+        }
+    }
+
+    /// Today, then the last 7 days, then the last 30, then back to today
+    pub fn next(self) -> Self {
+        match self {
+            TotalsWindow::Today => TotalsWindow::Week,
+            TotalsWindow::Week => TotalsWindow::Month,
+            TotalsWindow::Month => TotalsWindow::Today,
+        }
+    }
+
+    /// Label shown in front of the window's total
+    pub fn label(self) -> &'static str {
+        match self {
+            TotalsWindow::Today => "Today",
+            TotalsWindow::Week => "Last 7 days",
+            TotalsWindow::Month => "Last 30 days",
+        }
+    }
+}
+
+/// 24 hour ("13:05") or 12 hour ("1:05 PM") clock times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TimeFormat {
+    #[default]
+    #[serde(rename = "24h")]
+    H24,
+    #[serde(rename = "12h")]
+    H12,
+}
+
+/// How precisely the timer bar shows elapsed time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerPrecision {
+    /// Stopwatch style, "MM:SS" or "H:MM:SS"
+    #[default]
+    Seconds,
+    /// Ambient style, "HH:MM", changing once a minute
+    Minutes,
+}
+
+fn default_timer_spinner() -> bool {
+    true
+}
+
+fn default_date_format() -> String {
+    "%Y-%m-%d".to_string()
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            show_ids: false,
+            relative_times: false,
+            persist_flags: false,
+            show_day_breakdown: false,
+            timer_spinner: default_timer_spinner(),
+            timer_precision: TimerPrecision::default(),
+            screen_reader: false,
+            header_totals: TotalsWindow::default(),
+            locale: Locale::default(),
+            date_format: default_date_format(),
+            week_start: WeekStart::default(),
+            group_by: GroupBy::default(),
+            filtered_totals: false,
+            snap_display_minutes: 0,
+            heat_strip: false,
+            time_format: None,
+            max_name_chars: 0,
+        }
+    }
+}
+
+/// Countdown to the next record that hasn't started yet (meetings entered in advance)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UpcomingConfig {
+    /// Show "next: ... in 23m" in the header
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Count breaks as upcoming records
+    #[serde(default)]
+    pub include_breaks: bool,
+
+    /// Ring the terminal bell this many minutes before the next record starts
+    #[serde(default)]
+    pub alert_lead_minutes: Option<u32>,
+}
+
+/// What to do on save with a started record whose end is still in the future
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FutureEndRule {
+    /// Save records as they are
+    #[default]
+    Off,
+    /// List the records in the status line
+    Warn,
+    /// Move their end back to now
+    Clamp,
+}
+
+/// Save-time checks on today's records
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ValidationConfig {
+    /// Handling of end times later than now (placeholders left in by mistake)
+    #[serde(default)]
+    pub clamp_future_end: FutureEndRule,
+
+    /// Minutes an end may be ahead of now before the rule applies
+    #[serde(default)]
+    pub future_end_grace_minutes: Option<u32>,
+
+    /// Flag work records without a description
+    #[serde(default)]
+    pub require_description: bool,
+
+    /// Flag work records without a ticket in the name (only with a tracker configured)
+    #[serde(default)]
+    pub require_ticket: bool,
+
+    /// Don't flag records whose start and end are the same
+    #[serde(default)]
+    pub allow_zero_duration: bool,
+}
+
+impl ValidationConfig {
+    /// Grace period in minutes (5 unless configured)
+    pub fn future_end_grace(&self) -> u32 {
+        self.future_end_grace_minutes
+            .unwrap_or(DEFAULT_FUTURE_END_GRACE_MINUTES)
+    }
+}
+
+const DEFAULT_FUTURE_END_GRACE_MINUTES: u32 = 5;
+
+/// Defaults for records added in the TUI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefaultsConfig {
+    /// Where `n` starts a new record
+    #[serde(default)]
+    pub new_record_start: NewRecordStart,
+
+    /// Whether records count as billable unless marked otherwise with `$`
+    #[serde(default = "default_billable")]
+    pub billable: bool,
+
+    /// Pre-fills an empty description of a record with a ticket, e.g. "{ticket}: "
+    #[serde(default)]
+    pub description_template: Option<String>,
+
+    /// Have `n` end the record before the new one at its start, if that end was
+    /// only the placeholder `n` gave it
+    #[serde(default)]
+    pub close_previous: bool,
+}
+
+fn default_billable() -> bool {
+    true
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            new_record_start: NewRecordStart::default(),
+            billable: default_billable(),
+            description_template: None,
+            close_previous: false,
+        }
+    }
+}
+
+/// How editing one record may change its neighbours
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EditConfig {
+    /// Move the next record's start along with an end it was touching
+    #[serde(default)]
+    pub cascade: Cascade,
+}
+
+/// Whether an edited end carries over to the record that started there
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Cascade {
+    /// Leave the next record alone
+    #[default]
+    Off,
+    /// Move the next record's start with the end
+    Contiguous,
+    /// Ask each time whether to move it
+    Ask,
+}
+
+/// Start time for a record added with `n`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NewRecordStart {
+    /// End of the selected record
+    #[default]
+    AfterSelected,
+    /// The current time when viewing today, else as `after_selected`
+    Now,
+    /// Latest end of any record on the day
+    AfterLast,
+}
+
+/// Type of a custom record field declared under `[fields]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FieldDef {
+    /// Free text
+    Text,
+    /// One value out of `options`
+    Choice { options: Vec<String> },
+}
+
+impl FieldDef {
+    /// Check a value for this field; an empty value clears the field and is always allowed
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            FieldDef::Choice { options }
+                if !value.is_empty() && !options.iter().any(|o| o == value) =>
+            {
+                Err(format!("'{}' is not one of: {}", value, options.join(", ")))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A task started by a single Browse key, declared under `[[quick_tasks]]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickTask {
+    /// Key as written in `[keys]`: "1", "Ctrl+s"
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// An hour cap for a task or project over a week or month, under `[[task_budgets]]`
+///
+/// With both `task` and `project` set, a record has to match both.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct TaskBudget {
+    /// Task name, compared ignoring case and extra spaces
+    #[serde(default)]
+    pub task: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub weekly_hours: Option<f64>,
+    #[serde(default)]
+    pub monthly_hours: Option<f64>,
+}
+
+/// Timer behaviour settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TimerConfig {
+    /// Time of day ("HH:MM" or "midnight") at which a running timer is stopped
+    /// automatically, with its record ending at exactly that time
+    #[serde(default)]
+    pub auto_stop_at: Option<String>,
+
+    /// Start a fresh timer for the same task after an auto-stop
+    #[serde(default)]
+    pub restart_after_auto_stop: bool,
+
+    /// Minutes a record may differ from its timer session before the audit reports it
+    #[serde(default)]
+    pub audit_tolerance_minutes: Option<u32>,
+
+    /// Add the day's record as soon as a timer starts, finishing it on stop
+    #[serde(default)]
+    pub create_record_on_start: bool,
+
+    /// Add a "Break" record covering the gap since the last record when a new
+    /// (unlinked) timer starts
+    #[serde(default)]
+    pub fill_gaps_with_break: bool,
+
+    /// Shortest gap filled with a break, in minutes (5 unless configured)
+    #[serde(default)]
+    pub break_gap_min_minutes: Option<u32>,
+
+    /// Longest gap filled with a break, in minutes (120 unless configured)
+    #[serde(default)]
+    pub break_gap_max_minutes: Option<u32>,
+
+    /// Pause the running timer while the session is locked or asleep (needs
+    /// the `logind` feature, Linux only)
+    #[serde(default)]
+    pub pause_on_lock: bool,
+
+    /// What the TUI does when the local date changes while it is open
+    #[serde(default)]
+    pub on_midnight: MidnightRollover,
+
+    /// Ring the terminal bell when the TUI starts, stops, pauses or resumes a timer
+    #[serde(default)]
+    pub bell: bool,
+}
+
+/// TUI behavior when midnight passes (`[timer] on_midnight`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MidnightRollover {
+    /// Keep showing the day; a running session stays on the day it started
+    #[default]
+    Stay,
+    /// Move the view from yesterday to the new day
+    Follow,
+    /// Follow, and end a running session at midnight, continuing it on the new day
+    Split,
+}
+
+/// Read-only HTTP status endpoint for dashboards, off unless `http` is set
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ServeConfig {
+    /// Address to listen on while the TUI runs, e.g. "127.0.0.1:4877"
+    #[serde(default)]
+    pub http: Option<String>,
+
+    /// Allow listening on an address other than loopback
+    #[serde(default)]
+    pub allow_remote: bool,
+}
+
+/// Data directory settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Commit the data directory to git after saves, if it is a git repository
+    #[serde(default)]
+    pub git_autocommit: bool,
+
+    /// Seconds without a save before the commit is made (30 unless configured)
+    #[serde(default)]
+    pub git_autocommit_debounce_secs: Option<u64>,
+
+    /// Records loaded from one day file at most; a day with more is shown in part
+    /// and not saved
+    #[serde(default = "default_max_records")]
+    pub max_records: usize,
+}
+
+fn default_max_records() -> usize {
+    crate::storage::DEFAULT_MAX_RECORDS
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            git_autocommit: false,
+            git_autocommit_debounce_secs: None,
+            max_records: default_max_records(),
+        }
+    }
+}
+
+impl StorageConfig {
+    /// Debounce before an autocommit, None unless `git_autocommit` is on
+    pub fn git_autocommit_debounce(&self) -> Option<std::time::Duration> {
+        self.git_autocommit.then(|| {
+            std::time::Duration::from_secs(
+                self.git_autocommit_debounce_secs
+                    .unwrap_or(DEFAULT_GIT_AUTOCOMMIT_DEBOUNCE_SECS),
+            )
+        })
+    }
+}
+
+const DEFAULT_GIT_AUTOCOMMIT_DEBOUNCE_SECS: u64 = 30;
+
+impl TimerConfig {
+    /// Parse `auto_stop_at` into a cutoff time, None if unset or invalid
+    pub fn auto_stop_cutoff(&self) -> Option<TimePoint> {
+        let value = self.auto_stop_at.as_deref()?.trim();
+        if value.eq_ignore_ascii_case("midnight") {
+            // Records can't end at 24:00, so the last minute of the day stands in for it
+            return TimePoint::new(23, 59).ok();
+        }
+        TimePoint::parse(value).ok()
+    }
+
+    /// Audit tolerance in minutes (5 unless configured)
+    pub fn audit_tolerance(&self) -> u32 {
+        self.audit_tolerance_minutes
+            .unwrap_or(DEFAULT_AUDIT_TOLERANCE_MINUTES)
+    }
+
+    /// Gap lengths (min, max) filled with a break, None unless `fill_gaps_with_break`
+    pub fn break_gap_bounds(&self) -> Option<(u32, u32)> {
+        self.fill_gaps_with_break.then(|| {
+            (
+                self.break_gap_min_minutes
+                    .unwrap_or(DEFAULT_BREAK_GAP_MIN_MINUTES),
+                self.break_gap_max_minutes
+                    .unwrap_or(DEFAULT_BREAK_GAP_MAX_MINUTES),
+            )
+        })
+    }
+}
+
+const DEFAULT_AUDIT_TOLERANCE_MINUTES: u32 = 5;
+const DEFAULT_BREAK_GAP_MIN_MINUTES: u32 = 5;
+const DEFAULT_BREAK_GAP_MAX_MINUTES: u32 = 120;
+
 impl Config {
     /// Load config from file, or return defaults if file doesn't exist
     pub fn load() -> Result<Self> {
-        let config_path = Self::get_config_path();
+        Self::load_for(&Profile::default())
+    }
+
+    /// Load the config of the given profile, or return defaults if it doesn't exist
+    pub fn load_for(profile: &Profile) -> Result<Self> {
+        Self::load_from(&profile.config_path_in(&Self::get_config_dir()))
+    }
 
+    /// Load config from a specific file, or the defaults if it doesn't exist
+    pub fn load_from(config_path: &Path) -> Result<Self> {
         if config_path.exists() {
-            let contents = fs::read_to_string(&config_path)
+            let contents = fs::read_to_string(config_path)
                 .context(format!("Failed to read config file: {:?}", config_path))?;
             let config: Config =
                 toml::from_str(&contents).context("Failed to parse config TOML")?;
@@ -59,23 +731,18 @@ impl Config {
         }
     }
 
-    /// Get config file path (~/.config/work-tuimer/config.toml)
+    /// Get config directory (~/.config/work-tuimer)
     /// Respects XDG_CONFIG_HOME environment variable on Unix systems
-    fn get_config_path() -> PathBuf {
+    pub fn get_config_dir() -> PathBuf {
         // On Unix systems (Linux/macOS), respect XDG_CONFIG_HOME
         #[cfg(unix)]
         {
             if let Ok(xdg_config) = std::env::var("XDG_CONFIG_HOME") {
-                return PathBuf::from(xdg_config)
-                    .join("work-tuimer")
-                    .join("config.toml");
+                return PathBuf::from(xdg_config).join("work-tuimer");
             }
             // Fall back to ~/.config if XDG_CONFIG_HOME is not set
             if let Some(home) = std::env::var_os("HOME") {
-                return PathBuf::from(home)
-                    .join(".config")
-                    .join("work-tuimer")
-                    .join("config.toml");
+                return PathBuf::from(home).join(".config").join("work-tuimer");
             }
         }
 
@@ -83,12 +750,12 @@ impl Config {
         #[cfg(windows)]
         {
             if let Some(config_dir) = dirs::config_dir() {
-                return config_dir.join("work-tuimer").join("config.toml");
+                return config_dir.join("work-tuimer");
             }
         }
 
         // Final fallback for any platform
-        PathBuf::from("./config.toml")
+        PathBuf::from(".")
     }
 
     /// Check if any tracker integration is properly configured
@@ -99,6 +766,15 @@ impl Config {
             .any(|tracker| tracker.enabled && !tracker.base_url.is_empty())
     }
 
+    /// Record checks from `[validation]`; tickets are only required with a tracker set up
+    pub fn required_fields(&self) -> RequiredFields {
+        RequiredFields {
+            description: self.validation.require_description,
+            ticket: self.validation.require_ticket && self.has_integrations(),
+            duration: !self.validation.allow_zero_duration,
+        }
+    }
+
     /// Get the active theme (either pre-defined or custom)
     pub fn get_theme(&self) -> Theme {
         self.theme.get_active_theme()
@@ -108,29 +784,78 @@ impl Config {
 /// Theme configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
-    /// Name of active theme: "default", "kanagawa", "catppuccin", "gruvbox", "monokai", "dracula", "everforest", "terminal"
+    /// Name of active theme: "default", "kanagawa", "catppuccin", "gruvbox", "monokai", "dracula", "everforest", "terminal",
+    /// or one of the light ones, "default-light" and "gruvbox-light"
     #[serde(default = "default_theme_name")]
     pub active: String,
 
     /// Custom theme definitions
     #[serde(default)]
     pub custom: HashMap<String, CustomThemeColors>,
+
+    /// Ask the terminal for its background color at startup and pick
+    /// `active_light` or `active_dark` to match
+    #[serde(default)]
+    pub auto_detect: bool,
+
+    /// Theme for a light background when `auto_detect` is on
+    #[serde(default = "default_light_theme_name")]
+    pub active_light: String,
+
+    /// Theme for a dark background when `auto_detect` is on (`active` unless set)
+    #[serde(default)]
+    pub active_dark: Option<String>,
+
+    /// Background to assume when the terminal doesn't answer the query
+    #[serde(default)]
+    pub background: Option<Background>,
+}
+
+/// Terminal background brightness, for `[theme] auto_detect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Background {
+    Light,
+    Dark,
 }
 
 fn default_theme_name() -> String {
     "default".to_string()
 }
 
+fn default_light_theme_name() -> String {
+    "default-light".to_string()
+}
+
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
             active: default_theme_name(),
             custom: HashMap::new(),
+            auto_detect: false,
+            active_light: default_light_theme_name(),
+            active_dark: None,
+            background: None,
         }
     }
 }
 
 impl ThemeConfig {
+    /// Theme name to use on a terminal whose background was `detected` as
+    ///
+    /// Without `auto_detect` this is always `active`. An undetected background
+    /// falls back to `background`, then to `active`.
+    pub fn active_for(&self, detected: Option<Background>) -> &str {
+        if !self.auto_detect {
+            return &self.active;
+        }
+        match detected.or(self.background) {
+            Some(Background::Light) => &self.active_light,
+            Some(Background::Dark) => self.active_dark.as_deref().unwrap_or(&self.active),
+            None => &self.active,
+        }
+    }
+
     /// Get the active theme based on config
     pub fn get_active_theme(&self) -> Theme {
         // Check custom themes first (allows overriding predefined themes)
@@ -148,6 +873,8 @@ impl ThemeConfig {
             "dracula" => Theme::dracula(),
             "everforest" => Theme::everforest(),
             "terminal" => Theme::terminal(),
+            "default-light" => Theme::default_light(),
+            "gruvbox-light" => Theme::gruvbox_light(),
             _ => {
                 // Fallback to default if theme not found
                 Theme::default_theme()
@@ -426,6 +1153,56 @@ impl Theme {
         }
     }
 
+    /// Default theme for light terminal backgrounds
+    pub fn default_light() -> Self {
+        Self {
+            active_border: Color::Rgb(0, 110, 150),
+            inactive_border: Color::Rgb(170, 170, 180),
+            searching_border: Color::Rgb(180, 120, 0),
+            selected_bg: Color::Rgb(205, 220, 240),
+            selected_inactive_bg: Color::Rgb(225, 230, 238),
+            visual_bg: Color::Rgb(150, 190, 230),
+            timer_active_bg: Color::Rgb(190, 230, 190),
+            row_alternate_bg: Color::Rgb(242, 242, 246),
+            edit_bg: Color::Rgb(200, 230, 235),
+            focus_bg: Color::Rgb(220, 205, 240), // pale purple focus indicator
+            primary_text: Color::Rgb(30, 30, 40),
+            secondary_text: Color::Rgb(100, 100, 110),
+            highlight_text: Color::Rgb(0, 110, 150),
+            success: Color::Rgb(30, 130, 50),
+            warning: Color::Rgb(170, 110, 0),
+            error: Color::Rgb(190, 30, 40),
+            info: Color::Rgb(0, 110, 150),
+            timer_text: Color::Rgb(170, 90, 0),
+            badge: Color::Rgb(140, 60, 160),
+        }
+    }
+
+    /// Gruvbox light theme (the retro palette on a cream background)
+    pub fn gruvbox_light() -> Self {
+        Self {
+            active_border: Color::Rgb(66, 123, 88),          // aqua
+            inactive_border: Color::Rgb(213, 196, 161),      // bg2
+            searching_border: Color::Rgb(181, 118, 20),      // yellow
+            selected_bg: Color::Rgb(213, 196, 161),          // bg2
+            selected_inactive_bg: Color::Rgb(235, 219, 178), // bg1
+            visual_bg: Color::Rgb(131, 165, 152),            // light aqua
+            timer_active_bg: Color::Rgb(184, 187, 38),       // light green
+            row_alternate_bg: Color::Rgb(242, 229, 188),     // bg0_s
+            edit_bg: Color::Rgb(189, 174, 147),              // bg3
+            focus_bg: Color::Rgb(211, 134, 155),             // light purple
+            primary_text: Color::Rgb(60, 56, 54),            // fg
+            secondary_text: Color::Rgb(124, 111, 100),       // fg4
+            highlight_text: Color::Rgb(7, 102, 120),         // blue
+            success: Color::Rgb(121, 116, 14),               // green
+            warning: Color::Rgb(181, 118, 20),               // yellow
+            error: Color::Rgb(157, 0, 6),                    // red
+            info: Color::Rgb(7, 102, 120),                   // blue
+            timer_text: Color::Rgb(175, 58, 3),              // orange
+            badge: Color::Rgb(143, 63, 113),                 // purple
+        }
+    }
+
     /// Create theme from custom color definitions
     pub fn from_custom(colors: &CustomThemeColors) -> Self {
         Self {
@@ -590,6 +1367,7 @@ worklog_url = "{base_url}/browse/{ticket}?focusedWorklogId=-1"
         let theme_config = ThemeConfig {
             active: "kanagawa".to_string(),
             custom: HashMap::new(),
+            ..ThemeConfig::default()
         };
         let theme = theme_config.get_active_theme();
         // Verify it returns kanagawa theme (check one specific color)
@@ -607,18 +1385,87 @@ worklog_url = "{base_url}/browse/{ticket}?focusedWorklogId=-1"
             "dracula",
             "everforest",
             "terminal",
+            "default-light",
+            "gruvbox-light",
         ];
 
         for name in theme_names {
             let theme_config = ThemeConfig {
                 active: name.to_string(),
                 custom: HashMap::new(),
+                ..ThemeConfig::default()
             };
             let _theme = theme_config.get_active_theme();
             // Just verify it doesn't panic and returns a theme
         }
     }
 
+    #[test]
+    fn test_light_themes_are_light() {
+        for name in ["default-light", "gruvbox-light"] {
+            let theme_config = ThemeConfig {
+                active: name.to_string(),
+                ..ThemeConfig::default()
+            };
+            let theme = theme_config.get_active_theme();
+            assert!(
+                matches!(theme.row_alternate_bg, Color::Rgb(r, _, _) if r > 200),
+                "{name}"
+            );
+            assert!(
+                matches!(theme.primary_text, Color::Rgb(r, _, _) if r < 100),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_active_for_picks_theme_by_background() {
+        let mut theme_config = ThemeConfig {
+            active: "kanagawa".to_string(),
+            ..ThemeConfig::default()
+        };
+        // Detection is off unless asked for
+        assert_eq!(theme_config.active_for(Some(Background::Light)), "kanagawa");
+
+        theme_config.auto_detect = true;
+        assert_eq!(
+            theme_config.active_for(Some(Background::Light)),
+            "default-light"
+        );
+        assert_eq!(theme_config.active_for(Some(Background::Dark)), "kanagawa");
+        assert_eq!(theme_config.active_for(None), "kanagawa");
+
+        theme_config.active_dark = Some("dracula".to_string());
+        theme_config.active_light = "gruvbox-light".to_string();
+        assert_eq!(theme_config.active_for(Some(Background::Dark)), "dracula");
+        assert_eq!(
+            theme_config.active_for(Some(Background::Light)),
+            "gruvbox-light"
+        );
+
+        // A terminal that doesn't answer falls back to the configured background
+        theme_config.background = Some(Background::Light);
+        assert_eq!(theme_config.active_for(None), "gruvbox-light");
+        assert_eq!(theme_config.active_for(Some(Background::Dark)), "dracula");
+    }
+
+    #[test]
+    fn test_theme_auto_detect_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[theme]
+auto_detect = true
+active_dark = "everforest"
+background = "dark"
+"#,
+        )
+        .unwrap();
+        assert!(config.theme.auto_detect);
+        assert_eq!(config.theme.active_light, "default-light");
+        assert_eq!(config.theme.active_for(None), "everforest");
+    }
+
     #[test]
     fn test_parse_color_hex_6_digit() {
         let color = parse_color("#7e9cd8");
@@ -760,6 +1607,7 @@ badge = "lightmagenta"
         let theme_config = ThemeConfig {
             active: "test-theme".to_string(),
             custom,
+            ..ThemeConfig::default()
         };
 
         let theme = theme_config.get_active_theme();
@@ -772,6 +1620,7 @@ badge = "lightmagenta"
         let theme_config = ThemeConfig {
             active: "non-existent-theme".to_string(),
             custom: HashMap::new(),
+            ..ThemeConfig::default()
         };
 
         let theme = theme_config.get_active_theme();
@@ -846,7 +1695,22 @@ badge = "lightmagenta"
             theme: ThemeConfig {
                 active: "custom1".to_string(),
                 custom,
+                ..ThemeConfig::default()
             },
+            timer: TimerConfig::default(),
+            storage: StorageConfig::default(),
+            serve: ServeConfig::default(),
+            ui: UiConfig::default(),
+            time: TimeConfig::default(),
+            upcoming: UpcomingConfig::default(),
+            validation: ValidationConfig::default(),
+            defaults: DefaultsConfig::default(),
+            edit: EditConfig::default(),
+            budgets: HashMap::new(),
+            fields: BTreeMap::new(),
+            keys: BTreeMap::new(),
+            quick_tasks: Vec::new(),
+            task_budgets: Vec::new(),
         };
 
         let theme = config.get_theme();
@@ -1132,6 +1996,7 @@ badge = "magenta"
         let theme_config = ThemeConfig {
             active: "KANAGAWA".to_string(), // uppercase (not found)
             custom: HashMap::new(),
+            ..ThemeConfig::default()
         };
 
         let theme = theme_config.get_active_theme();
@@ -1171,6 +2036,7 @@ badge = "magenta"
         let theme_config = ThemeConfig {
             active: "default".to_string(),
             custom,
+            ..ThemeConfig::default()
         };
 
         let theme = theme_config.get_active_theme();
@@ -1201,6 +2067,7 @@ badge = "magenta"
         let theme_config = ThemeConfig {
             active: "gruvbox".to_string(),
             custom: HashMap::new(),
+            ..ThemeConfig::default()
         };
 
         let serialized = toml::to_string(&theme_config).expect("Failed to serialize");
@@ -1211,4 +2078,261 @@ badge = "magenta"
         assert_eq!(deserialized.active, "gruvbox");
         assert!(deserialized.custom.is_empty());
     }
+
+    #[test]
+    fn test_timer_config_defaults_to_no_auto_stop() {
+        let config = Config::default();
+        assert_eq!(config.timer.auto_stop_cutoff(), None);
+        assert!(!config.timer.restart_after_auto_stop);
+    }
+
+    #[test]
+    fn test_timer_config_parses_auto_stop_time() {
+        let toml_str = r#"
+[timer]
+auto_stop_at = "18:30"
+restart_after_auto_stop = true
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.timer.auto_stop_cutoff(),
+            Some(TimePoint::new(18, 30).unwrap())
+        );
+        assert!(config.timer.restart_after_auto_stop);
+    }
+
+    #[test]
+    fn test_timer_config_on_midnight() {
+        assert_eq!(Config::default().timer.on_midnight, MidnightRollover::Stay);
+        let config: Config = toml::from_str("[timer]\non_midnight = \"split\"\n").unwrap();
+        assert_eq!(config.timer.on_midnight, MidnightRollover::Split);
+        assert!(toml::from_str::<Config>("[timer]\non_midnight = \"later\"\n").is_err());
+    }
+
+    #[test]
+    fn test_timer_config_midnight_maps_to_last_minute() {
+        let timer = TimerConfig {
+            auto_stop_at: Some("Midnight".to_string()),
+            restart_after_auto_stop: false,
+            ..TimerConfig::default()
+        };
+        assert_eq!(
+            timer.auto_stop_cutoff(),
+            Some(TimePoint::new(23, 59).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_timer_config_invalid_auto_stop_is_ignored() {
+        let timer = TimerConfig {
+            auto_stop_at: Some("25:00".to_string()),
+            restart_after_auto_stop: false,
+            ..TimerConfig::default()
+        };
+        assert_eq!(timer.auto_stop_cutoff(), None);
+    }
+
+    #[test]
+    fn test_ui_config_show_ids() {
+        let config = Config::default();
+        assert!(!config.ui.show_ids);
+
+        let config: Config = toml::from_str("[ui]\nshow_ids = true\n").unwrap();
+        assert!(config.ui.show_ids);
+    }
+
+    #[test]
+    fn test_ui_config_timer_precision() {
+        assert_eq!(
+            Config::default().ui.timer_precision,
+            TimerPrecision::Seconds
+        );
+
+        let config: Config = toml::from_str("[ui]\ntimer_precision = \"minutes\"\n").unwrap();
+        assert_eq!(config.ui.timer_precision, TimerPrecision::Minutes);
+
+        assert!(toml::from_str::<Config>("[ui]\ntimer_precision = \"hours\"\n").is_err());
+    }
+
+    #[test]
+    fn test_ui_config_locale_and_week_start() {
+        let ui = Config::default().ui;
+        assert_eq!(
+            ui.format_date(time::macros::date!(2025 - 11 - 06)),
+            "2025-11-06"
+        );
+        assert_eq!(ui.week_start, WeekStart::Monday);
+
+        let config: Config = toml::from_str(
+            "[ui]\nlocale = \"de\"\ndate_format = \"%A, %e. %B %Y\"\nweek_start = \"sunday\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.ui.format_date(time::macros::date!(2025 - 11 - 06)),
+            "Donnerstag, 6. November 2025"
+        );
+        assert_eq!(config.ui.week_start.weekday(), time::Weekday::Sunday);
+
+        assert!(toml::from_str::<Config>("[ui]\nlocale = \"xx\"\n").is_err());
+    }
+
+    #[test]
+    fn test_fields_config_parses_text_and_choice() {
+        let toml = r#"
+[fields]
+po_number = { type = "text" }
+cost_center = { type = "choice", options = ["R&D", "Ops"] }
+"#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.fields.get("po_number"), Some(&FieldDef::Text));
+        assert_eq!(
+            config.fields.get("cost_center"),
+            Some(&FieldDef::Choice {
+                options: vec!["R&D".to_string(), "Ops".to_string()]
+            })
+        );
+        // Declared names come back sorted, the order the editor lists them in
+        let names: Vec<&String> = config.fields.keys().collect();
+        assert_eq!(names, ["cost_center", "po_number"]);
+    }
+
+    #[test]
+    fn test_field_def_check_rejects_unknown_choice() {
+        let choice = FieldDef::Choice {
+            options: vec!["R&D".to_string(), "Ops".to_string()],
+        };
+        assert!(choice.check("Ops").is_ok());
+        assert!(choice.check("").is_ok());
+        assert_eq!(
+            choice.check("Sales").unwrap_err(),
+            "'Sales' is not one of: R&D, Ops"
+        );
+        assert!(FieldDef::Text.check("anything").is_ok());
+    }
+
+    #[test]
+    fn test_defaults_config_new_record_start() {
+        assert_eq!(
+            Config::default().defaults.new_record_start,
+            NewRecordStart::AfterSelected
+        );
+        let config: Config =
+            toml::from_str("[defaults]\nnew_record_start = \"after_last\"\n").unwrap();
+        assert_eq!(config.defaults.new_record_start, NewRecordStart::AfterLast);
+        let config: Config = toml::from_str("[defaults]\nnew_record_start = \"now\"\n").unwrap();
+        assert_eq!(config.defaults.new_record_start, NewRecordStart::Now);
+        assert!(toml::from_str::<Config>("[defaults]\nnew_record_start = \"later\"\n").is_err());
+    }
+
+    #[test]
+    fn test_defaults_config_billable() {
+        assert!(Config::default().defaults.billable);
+        let config: Config = toml::from_str("[defaults]\nnew_record_start = \"now\"\n").unwrap();
+        assert!(config.defaults.billable);
+        let config: Config = toml::from_str("[defaults]\nbillable = false\n").unwrap();
+        assert!(!config.defaults.billable);
+    }
+
+    #[test]
+    fn test_defaults_config_description_template() {
+        assert_eq!(Config::default().defaults.description_template, None);
+        let config: Config =
+            toml::from_str("[defaults]\ndescription_template = \"{ticket}: \"\n").unwrap();
+        assert_eq!(
+            config.defaults.description_template.as_deref(),
+            Some("{ticket}: ")
+        );
+    }
+
+    #[test]
+    fn test_defaults_config_close_previous() {
+        assert!(!Config::default().defaults.close_previous);
+        let config: Config = toml::from_str("[defaults]\nclose_previous = true\n").unwrap();
+        assert!(config.defaults.close_previous);
+    }
+
+    #[test]
+    fn test_storage_config_max_records() {
+        assert_eq!(Config::default().storage.max_records, 5000);
+        let config: Config = toml::from_str("[storage]\ngit_autocommit = true\n").unwrap();
+        assert_eq!(config.storage.max_records, 5000);
+        let config: Config = toml::from_str("[storage]\nmax_records = 200\n").unwrap();
+        assert_eq!(config.storage.max_records, 200);
+    }
+
+    #[test]
+    fn test_ui_config_timer_spinner_defaults_on() {
+        assert!(Config::default().ui.timer_spinner);
+
+        let config: Config = toml::from_str("[ui]\nshow_ids = true\n").unwrap();
+        assert!(config.ui.timer_spinner);
+
+        let config: Config = toml::from_str("[ui]\ntimer_spinner = false\n").unwrap();
+        assert!(!config.ui.timer_spinner);
+    }
+
+    #[test]
+    fn test_time_config_offset_changes_require_dst_aware() {
+        let toml_str = r#"
+[time]
+dst_aware = false
+
+[[time.offset_changes]]
+date = "2025-03-30"
+at = "02:00"
+shift_minutes = 60
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.time.offset_changes.len(), 1);
+        assert!(config.time.active_offset_changes().is_empty());
+
+        config.time.dst_aware = true;
+        assert_eq!(config.time.active_offset_changes().len(), 1);
+    }
+
+    #[test]
+    fn test_timer_config_audit_tolerance() {
+        assert_eq!(Config::default().timer.audit_tolerance(), 5);
+
+        let config: Config = toml::from_str("[timer]\naudit_tolerance_minutes = 15\n").unwrap();
+        assert_eq!(config.timer.audit_tolerance(), 15);
+    }
+
+    #[test]
+    fn test_integrations_open_mode() {
+        assert_eq!(Config::default().integrations.open_mode, OpenMode::Browser);
+
+        let config: Config = toml::from_str("[integrations]\nopen_mode = \"print\"\n").unwrap();
+        assert_eq!(config.integrations.open_mode, OpenMode::Print);
+        assert!(toml::from_str::<Config>("[integrations]\nopen_mode = \"fax\"\n").is_err());
+    }
+
+    #[test]
+    fn test_budgets_config() {
+        assert!(Config::default().budgets.is_empty());
+
+        let config: Config =
+            toml::from_str("[budgets]\n\"ACME-77\" = 600\n\"ACME-\" = 2400\n").unwrap();
+        assert_eq!(config.budgets.get("ACME-77"), Some(&600));
+        assert_eq!(config.budgets.get("ACME-"), Some(&2400));
+    }
+
+    #[test]
+    fn test_task_budgets_config() {
+        assert!(Config::default().task_budgets.is_empty());
+
+        let config: Config = toml::from_str(
+            "[budgets]\n\"ACME-\" = 2400\n\n\
+             [[task_budgets]]\ntask = \"Meetings\"\nweekly_hours = 5\n\n\
+             [[task_budgets]]\nproject = \"Support\"\nmonthly_hours = 12.5\n",
+        )
+        .unwrap();
+        assert_eq!(config.budgets.get("ACME-"), Some(&2400));
+        assert_eq!(config.task_budgets.len(), 2);
+        assert_eq!(config.task_budgets[0].task.as_deref(), Some("Meetings"));
+        assert_eq!(config.task_budgets[0].weekly_hours, Some(5.0));
+        assert_eq!(config.task_budgets[0].monthly_hours, None);
+        assert_eq!(config.task_budgets[1].project.as_deref(), Some("Support"));
+        assert_eq!(config.task_budgets[1].monthly_hours, Some(12.5));
+    }
 }
@@ -0,0 +1,300 @@
+//! Batch entry of records from a TOML or JSON file (`record import-batch`)
+//!
+//! Both formats share one schema, a list of `entry` tables:
+//!
+//! ```toml
+//! [[entry]]
+//! date = "2025-11-06"
+//! task = "Code review"
+//! start = "09:00"
+//! end = "10:30"
+//! description = "Auth changes"   # optional
+//! ```
+//!
+//! Rows are validated up front. Invalid ones are reported and skipped, and the
+//! rest are written with one load and one save per day.
+
+use super::DaySummary;
+use crate::models::{TimePoint, WorkRecord};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use time::Date;
+
+#[derive(Debug, Deserialize)]
+struct BatchFile {
+    #[serde(default)]
+    entry: Vec<BatchEntry>,
+}
+
+/// One row of a batch file, as written
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BatchEntry {
+    pub date: String,
+    pub task: String,
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A row that was left out, with its 1-based position in the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedRow {
+    pub row: usize,
+    pub reason: String,
+}
+
+/// Parse a batch file, as JSON when the path ends in `.json` and as TOML otherwise
+pub fn parse_batch(path: &Path, contents: &str) -> Result<Vec<BatchEntry>> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let file: BatchFile = if is_json {
+        serde_json::from_str(contents).context("Invalid JSON batch file")?
+    } else {
+        toml::from_str(contents).context("Invalid TOML batch file")?
+    };
+    Ok(file.entry)
+}
+
+/// Check every row, returning the records to write and the rows that were skipped
+pub fn validate_entries(entries: &[BatchEntry]) -> (Vec<(Date, WorkRecord)>, Vec<SkippedRow>) {
+    let mut records = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        match entry_record(entry) {
+            Ok(dated) => records.push(dated),
+            Err(reason) => skipped.push(SkippedRow {
+                row: index + 1,
+                reason,
+            }),
+        }
+    }
+    (records, skipped)
+}
+
+fn entry_record(entry: &BatchEntry) -> Result<(Date, WorkRecord), String> {
+    let format = time::macros::format_description!("[year]-[month]-[day]");
+    let date = Date::parse(entry.date.trim(), &format)
+        .map_err(|_| format!("invalid date '{}' (use YYYY-MM-DD)", entry.date))?;
+    let task = entry.task.trim();
+    if task.is_empty() {
+        return Err("task is empty".to_string());
+    }
+    let start = TimePoint::parse(&entry.start)?;
+    let end = TimePoint::parse(&entry.end)?;
+    if end < start {
+        return Err(format!("end {} is before start {}", end, start));
+    }
+
+    let mut record = WorkRecord::new(0, task.to_string(), start, end);
+    record.description = entry.description.trim().to_string();
+    Ok((date, record))
+}
+
+/// Write validated records, grouped so each day file is loaded and saved once
+///
+/// A record with the same task, start and end as one already on its day counts as
+/// skipped, so running the same batch twice doesn't duplicate it.
+pub fn import_batch(
+    storage: &Storage,
+    records: Vec<(Date, WorkRecord)>,
+    dry_run: bool,
+) -> Result<Vec<DaySummary>> {
+    let mut by_date: BTreeMap<Date, Vec<WorkRecord>> = BTreeMap::new();
+    for (date, record) in records {
+        by_date.entry(date).or_default().push(record);
+    }
+
+    let mut summaries = Vec::new();
+    for (date, records) in by_date {
+        let mut day = storage.load(&date)?;
+        let mut summary = DaySummary {
+            date,
+            created: 0,
+            skipped: 0,
+        };
+        for mut record in records {
+            let exists = day.work_records.values().any(|existing| {
+                existing.name == record.name
+                    && existing.start == record.start
+                    && existing.end == record.end
+            });
+            if exists {
+                summary.skipped += 1;
+                continue;
+            }
+            record.id = day.next_id();
+            day.add_record(record);
+            summary.created += 1;
+        }
+        if summary.created > 0 && !dry_run {
+            storage.save(&day)?;
+        }
+        summaries.push(summary);
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use time::macros::date;
+
+    const BATCH_TOML: &str = r#"
+[[entry]]
+date = "2025-11-06"
+task = "Code review"
+start = "09:00"
+end = "10:30"
+description = "Auth changes"
+
+[[entry]]
+date = "2025-11-06"
+task = "Standup"
+start = "10:30"
+end = "10:45"
+
+[[entry]]
+date = "2025-11-07"
+task = "Planning"
+start = "9.15"
+end = "10.00"
+"#;
+
+    fn storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_parse_batch_toml() {
+        let entries = parse_batch(Path::new("entries.toml"), BATCH_TOML).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].task, "Code review");
+        assert_eq!(entries[0].description, "Auth changes");
+        assert_eq!(entries[1].description, "");
+
+        let (records, skipped) = validate_entries(&entries);
+        assert!(skipped.is_empty());
+        assert_eq!(records[0].0, date!(2025 - 11 - 06));
+        assert_eq!(records[0].1.total_minutes, 90);
+        assert_eq!(records[2].1.start, TimePoint::new(9, 15).unwrap());
+    }
+
+    #[test]
+    fn test_parse_batch_json() {
+        let json = r#"{"entry": [
+            {"date": "2025-11-06", "task": "Code review", "start": "09:00", "end": "10:30"}
+        ]}"#;
+        let entries = parse_batch(Path::new("entries.JSON"), json).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].end, "10:30");
+    }
+
+    #[test]
+    fn test_parse_batch_rejects_missing_fields() {
+        let toml = "[[entry]]\ndate = \"2025-11-06\"\ntask = \"No times\"\n";
+        assert!(parse_batch(Path::new("entries.toml"), toml).is_err());
+    }
+
+    #[test]
+    fn test_validate_entries_reports_end_before_start() {
+        let toml = r#"
+[[entry]]
+date = "2025-11-06"
+task = "Fine"
+start = "09:00"
+end = "10:00"
+
+[[entry]]
+date = "2025-11-06"
+task = "Backwards"
+start = "14:00"
+end = "13:00"
+
+[[entry]]
+date = "2025-13-01"
+task = "Bad date"
+start = "09:00"
+end = "10:00"
+
+[[entry]]
+date = "2025-11-06"
+task = "  "
+start = "09:00"
+end = "10:00"
+"#;
+        let entries = parse_batch(Path::new("entries.toml"), toml).unwrap();
+        let (records, skipped) = validate_entries(&entries);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            skipped,
+            vec![
+                SkippedRow {
+                    row: 2,
+                    reason: "end 13:00 is before start 14:00".to_string()
+                },
+                SkippedRow {
+                    row: 3,
+                    reason: "invalid date '2025-13-01' (use YYYY-MM-DD)".to_string()
+                },
+                SkippedRow {
+                    row: 4,
+                    reason: "task is empty".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_batch_groups_by_day_and_skips_duplicates() {
+        let (_temp_dir, storage) = storage();
+        let entries = parse_batch(Path::new("entries.toml"), BATCH_TOML).unwrap();
+
+        let (records, _) = validate_entries(&entries);
+        let summaries = import_batch(&storage, records, false).unwrap();
+        assert_eq!(
+            summaries,
+            vec![
+                DaySummary {
+                    date: date!(2025 - 11 - 06),
+                    created: 2,
+                    skipped: 0
+                },
+                DaySummary {
+                    date: date!(2025 - 11 - 07),
+                    created: 1,
+                    skipped: 0
+                },
+            ]
+        );
+        let day = storage.load(&date!(2025 - 11 - 06)).unwrap();
+        assert_eq!(day.work_records.len(), 2);
+        assert_eq!(day.last_id, 2);
+
+        // Running the same batch again changes nothing
+        let (records, _) = validate_entries(&entries);
+        let summaries = import_batch(&storage, records, false).unwrap();
+        assert!(summaries.iter().all(|day| day.created == 0));
+        let day = storage.load(&date!(2025 - 11 - 06)).unwrap();
+        assert_eq!(day.work_records.len(), 2);
+    }
+
+    #[test]
+    fn test_import_batch_dry_run_writes_nothing() {
+        let (_temp_dir, storage) = storage();
+        let entries = parse_batch(Path::new("entries.toml"), BATCH_TOML).unwrap();
+        let (records, _) = validate_entries(&entries);
+
+        let summaries = import_batch(&storage, records, true).unwrap();
+        assert_eq!(summaries.iter().map(|day| day.created).sum::<usize>(), 3);
+        assert!(storage.list_day_files().unwrap().is_empty());
+    }
+}
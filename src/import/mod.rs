@@ -0,0 +1,507 @@
+//! Import time entries exported from other trackers (Toggl, Clockify)
+//!
+//! Each export format is parsed into `ImportedEntry`, the shared intermediate
+//! form. Entries are then converted to local time, split at midnight and
+//! written as records on their day. Every record keeps the entry's id in
+//! `external_id`, so importing the same export again skips what's already there.
+
+pub mod batch;
+
+use crate::models::{DayData, OffsetChange, TimePoint, WorkRecord, offset_at};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Duration, OffsetDateTime};
+
+/// Name given to entries exported without a description
+const NO_DESCRIPTION: &str = "(no description)";
+
+/// One time entry from an export, independent of the tracker it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedEntry {
+    /// Stable id, prefixed with the source ("toggl:123")
+    pub external_id: String,
+    pub description: String,
+    pub project: Option<String>,
+    pub start: OffsetDateTime,
+    /// None for an entry that was still running when exported
+    pub stop: Option<OffsetDateTime>,
+}
+
+/// How entries are turned into records
+#[derive(Debug, Clone, Copy)]
+pub struct ImportOptions<'a> {
+    /// The time of the import, in the offset in effect now
+    pub now: OffsetDateTime,
+    /// Clock changes (`[time] offset_changes`) to look up the offset each entry was
+    /// recorded in; without any, every entry is converted to the offset of `now`
+    pub offset_changes: &'a [OffsetChange],
+    /// Name records "Project: description" instead of just the description
+    pub project_as_prefix: bool,
+}
+
+/// What an import did (or would do, on a dry run) to one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySummary {
+    pub date: Date,
+    pub created: usize,
+    /// Pieces already present from an earlier import
+    pub skipped: usize,
+}
+
+#[derive(Deserialize)]
+struct TogglEntry {
+    id: serde_json::Value,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default, alias = "project_name")]
+    project: Option<String>,
+    start: String,
+    #[serde(default, alias = "end")]
+    stop: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClockifyEntry {
+    id: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    project_name: Option<String>,
+    #[serde(default)]
+    project: Option<ClockifyProject>,
+    time_interval: ClockifyInterval,
+}
+
+#[derive(Deserialize)]
+struct ClockifyProject {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ClockifyInterval {
+    start: String,
+    #[serde(default)]
+    end: Option<String>,
+}
+
+/// Parse a Toggl Track JSON export (an array of time entries)
+pub fn parse_toggl(json: &str) -> Result<Vec<ImportedEntry>> {
+    let entries: Vec<TogglEntry> =
+        serde_json::from_str(json).context("Failed to parse Toggl export")?;
+    entries
+        .into_iter()
+        .map(|entry| {
+            let id = match entry.id {
+                serde_json::Value::String(id) => id,
+                other => other.to_string(),
+            };
+            Ok(ImportedEntry {
+                external_id: format!("toggl:{}", id),
+                description: entry.description.unwrap_or_default(),
+                project: entry.project,
+                start: parse_timestamp(&entry.start)?,
+                stop: entry.stop.as_deref().map(parse_timestamp).transpose()?,
+            })
+        })
+        .collect()
+}
+
+/// Parse a Clockify JSON export (an array of time entries)
+pub fn parse_clockify(json: &str) -> Result<Vec<ImportedEntry>> {
+    let entries: Vec<ClockifyEntry> =
+        serde_json::from_str(json).context("Failed to parse Clockify export")?;
+    entries
+        .into_iter()
+        .map(|entry| {
+            Ok(ImportedEntry {
+                external_id: format!("clockify:{}", entry.id),
+                description: entry.description.unwrap_or_default(),
+                project: entry
+                    .project_name
+                    .or(entry.project.map(|project| project.name)),
+                start: parse_timestamp(&entry.time_interval.start)?,
+                stop: entry
+                    .time_interval
+                    .end
+                    .as_deref()
+                    .map(parse_timestamp)
+                    .transpose()?,
+            })
+        })
+        .collect()
+}
+
+fn parse_timestamp(value: &str) -> Result<OffsetDateTime> {
+    OffsetDateTime::parse(value, &Rfc3339)
+        .with_context(|| format!("Invalid timestamp '{}' (expected RFC 3339)", value))
+}
+
+/// Records an entry becomes, keyed by local date; entries crossing midnight are split
+///
+/// A piece ending at midnight ends at 23:59 (records can't end at 24:00). Running
+/// entries and pieces shorter than a minute produce nothing.
+pub fn entry_records(entry: &ImportedEntry, options: ImportOptions) -> Vec<(Date, WorkRecord)> {
+    let Some(stop) = entry.stop else {
+        return Vec::new();
+    };
+    let local = |instant: OffsetDateTime| {
+        instant.to_offset(offset_at(options.offset_changes, options.now, instant))
+    };
+    let mut start = local(entry.start);
+    let stop = local(stop);
+
+    let name = record_name(entry, options.project_as_prefix);
+    let mut pieces = Vec::new();
+    while start < stop {
+        let next_midnight = start.replace_time(time::Time::MIDNIGHT) + Duration::days(1);
+        let piece_end = stop.min(next_midnight);
+        let end_point = if piece_end == next_midnight {
+            TimePoint::new(23, 59).unwrap()
+        } else {
+            TimePoint::new(piece_end.hour(), piece_end.minute()).unwrap()
+        };
+        let start_point = TimePoint::new(start.hour(), start.minute()).unwrap();
+
+        if start_point < end_point {
+            let mut record = WorkRecord::new(0, name.clone(), start_point, end_point);
+            record.description = entry.description.clone();
+            record.project = entry.project.clone();
+            record.external_id = Some(if pieces.is_empty() {
+                entry.external_id.clone()
+            } else {
+                format!("{}/{}", entry.external_id, pieces.len())
+            });
+            pieces.push((start.date(), record));
+        }
+        start = next_midnight;
+    }
+    pieces
+}
+
+fn record_name(entry: &ImportedEntry, project_as_prefix: bool) -> String {
+    let description = entry.description.trim();
+    let description = if description.is_empty() {
+        NO_DESCRIPTION
+    } else {
+        description
+    };
+    match (&entry.project, project_as_prefix) {
+        (Some(project), true) => format!("{}: {}", project, description),
+        _ => description.to_string(),
+    }
+}
+
+/// Add the records from `entries` to their days, skipping ones already imported
+///
+/// With `dry_run` nothing is written; the summary shows what would change.
+pub fn import_entries(
+    storage: &Storage,
+    entries: &[ImportedEntry],
+    options: ImportOptions,
+    dry_run: bool,
+) -> Result<Vec<DaySummary>> {
+    let mut by_date: BTreeMap<Date, Vec<WorkRecord>> = BTreeMap::new();
+    for entry in entries {
+        for (date, record) in entry_records(entry, options) {
+            by_date.entry(date).or_default().push(record);
+        }
+    }
+
+    let mut summaries = Vec::new();
+    for (date, records) in by_date {
+        let mut day = storage.load(&date)?;
+        let summary = add_new_records(&mut day, records);
+        if summary.created > 0 && !dry_run {
+            storage.save(&day)?;
+        }
+        summaries.push(summary);
+    }
+    Ok(summaries)
+}
+
+fn add_new_records(day: &mut DayData, records: Vec<WorkRecord>) -> DaySummary {
+    let mut summary = DaySummary {
+        date: day.date,
+        created: 0,
+        skipped: 0,
+    };
+    for mut record in records {
+        let exists = day
+            .work_records
+            .values()
+            .any(|existing| existing.external_id == record.external_id);
+        if exists {
+            summary.skipped += 1;
+            continue;
+        }
+        record.id = day.next_id();
+        day.add_record(record);
+        summary.created += 1;
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    const TOGGL_EXPORT: &str = r#"[
+        {
+            "id": 3001,
+            "description": "Code review",
+            "project": "Acme",
+            "start": "2025-11-06T08:00:00+00:00",
+            "stop": "2025-11-06T09:30:00+00:00"
+        },
+        {
+            "id": 3002,
+            "description": "Deploy",
+            "start": "2025-11-06T22:30:00Z",
+            "stop": "2025-11-07T00:15:00Z"
+        },
+        {
+            "id": 3003,
+            "description": "Still running",
+            "start": "2025-11-07T08:00:00Z",
+            "stop": null
+        }
+    ]"#;
+
+    const CLOCKIFY_EXPORT: &str = r#"[
+        {
+            "id": "65a1",
+            "description": "",
+            "projectName": "Internal",
+            "timeInterval": {
+                "start": "2025-11-06T13:00:00Z",
+                "end": "2025-11-06T14:00:00Z"
+            }
+        },
+        {
+            "id": "65a2",
+            "description": "Planning",
+            "project": { "name": "Acme" },
+            "timeInterval": {
+                "start": "2025-11-06T15:00:00Z",
+                "end": "2025-11-06T15:45:00Z"
+            }
+        }
+    ]"#;
+
+    fn utc() -> ImportOptions<'static> {
+        ImportOptions {
+            now: datetime!(2025-12-01 12:00 UTC),
+            offset_changes: &[],
+            project_as_prefix: false,
+        }
+    }
+
+    fn t(hour: u8, minute: u8) -> TimePoint {
+        TimePoint::new(hour, minute).unwrap()
+    }
+
+    #[test]
+    fn test_parse_toggl() {
+        let entries = parse_toggl(TOGGL_EXPORT).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].external_id, "toggl:3001");
+        assert_eq!(entries[0].project.as_deref(), Some("Acme"));
+        assert_eq!(entries[0].start, datetime!(2025-11-06 08:00 UTC));
+        assert_eq!(entries[1].project, None);
+        assert_eq!(entries[2].stop, None);
+    }
+
+    #[test]
+    fn test_parse_clockify_shares_the_intermediate_form() {
+        let entries = parse_clockify(CLOCKIFY_EXPORT).unwrap();
+        assert_eq!(
+            entries[1],
+            ImportedEntry {
+                external_id: "clockify:65a2".to_string(),
+                description: "Planning".to_string(),
+                project: Some("Acme".to_string()),
+                start: datetime!(2025-11-06 15:00 UTC),
+                stop: Some(datetime!(2025-11-06 15:45 UTC)),
+            }
+        );
+        assert_eq!(entries[0].project.as_deref(), Some("Internal"));
+    }
+
+    #[test]
+    fn test_invalid_timestamp_is_an_error() {
+        let json = r#"[{"id": 1, "start": "yesterday", "stop": null}]"#;
+        assert!(parse_toggl(json).is_err());
+    }
+
+    #[test]
+    fn test_timezone_conversion_moves_entry_to_local_day() {
+        let entries = parse_toggl(TOGGL_EXPORT).unwrap();
+        let options = ImportOptions {
+            now: datetime!(2025-12-01 12:00 +2),
+            ..utc()
+        };
+
+        let pieces = entry_records(&entries[0], options);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].0, date!(2025 - 11 - 06));
+        assert_eq!((pieces[0].1.start, pieces[0].1.end), (t(10, 0), t(11, 30)));
+
+        // 22:30-00:15 UTC is 00:30-02:15 on the next local day
+        let pieces = entry_records(&entries[1], options);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].0, date!(2025 - 11 - 07));
+        assert_eq!((pieces[0].1.start, pieces[0].1.end), (t(0, 30), t(2, 15)));
+    }
+
+    #[test]
+    fn test_entries_across_a_dst_change_keep_their_own_offset() {
+        // Summer time (+02:00) ended 2025-10-26 at 03:00, back to +01:00
+        let changes = [
+            OffsetChange {
+                date: "2025-03-30".to_string(),
+                at: "02:00".to_string(),
+                shift_minutes: 60,
+            },
+            OffsetChange {
+                date: "2025-10-26".to_string(),
+                at: "03:00".to_string(),
+                shift_minutes: -60,
+            },
+        ];
+        let json = r#"[
+            {"id": 1, "start": "2025-10-24T07:00:00Z", "stop": "2025-10-24T08:00:00Z"},
+            {"id": 2, "start": "2025-10-27T08:00:00Z", "stop": "2025-10-27T09:00:00Z"}
+        ]"#;
+        let entries = parse_toggl(json).unwrap();
+        let options = ImportOptions {
+            now: datetime!(2025-11-10 12:00 +1),
+            offset_changes: &changes,
+            ..utc()
+        };
+
+        // Both were logged as 09:00-10:00 local time
+        let (day, before) = &entry_records(&entries[0], options)[0];
+        assert_eq!(*day, date!(2025 - 10 - 24));
+        assert_eq!((before.start, before.end), (t(9, 0), t(10, 0)));
+        let (day, after) = &entry_records(&entries[1], options)[0];
+        assert_eq!(*day, date!(2025 - 10 - 27));
+        assert_eq!((after.start, after.end), (t(9, 0), t(10, 0)));
+    }
+
+    #[test]
+    fn test_midnight_split() {
+        let entries = parse_toggl(TOGGL_EXPORT).unwrap();
+        let pieces = entry_records(&entries[1], utc());
+
+        assert_eq!(pieces.len(), 2);
+        let (first_day, first) = &pieces[0];
+        let (second_day, second) = &pieces[1];
+        assert_eq!(*first_day, date!(2025 - 11 - 06));
+        assert_eq!((first.start, first.end), (t(22, 30), t(23, 59)));
+        assert_eq!(first.external_id.as_deref(), Some("toggl:3002"));
+        assert_eq!(*second_day, date!(2025 - 11 - 07));
+        assert_eq!((second.start, second.end), (t(0, 0), t(0, 15)));
+        assert_eq!(second.external_id.as_deref(), Some("toggl:3002/1"));
+    }
+
+    #[test]
+    fn test_running_entry_is_not_imported() {
+        let entries = parse_toggl(TOGGL_EXPORT).unwrap();
+        assert!(entry_records(&entries[2], utc()).is_empty());
+    }
+
+    #[test]
+    fn test_names_and_project_prefix() {
+        let entries = parse_clockify(CLOCKIFY_EXPORT).unwrap();
+        let (_, plain) = &entry_records(&entries[1], utc())[0];
+        assert_eq!(plain.name, "Planning");
+        assert_eq!(plain.project.as_deref(), Some("Acme"));
+
+        let options = ImportOptions {
+            project_as_prefix: true,
+            ..utc()
+        };
+        let (_, prefixed) = &entry_records(&entries[1], options)[0];
+        assert_eq!(prefixed.name, "Acme: Planning");
+        let (_, untitled) = &entry_records(&entries[0], options)[0];
+        assert_eq!(untitled.name, "Internal: (no description)");
+    }
+
+    #[test]
+    fn test_reimport_is_idempotent() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let entries = parse_toggl(TOGGL_EXPORT).unwrap();
+
+        let first = import_entries(&storage, &entries, utc(), false).unwrap();
+        assert_eq!(
+            first,
+            vec![
+                DaySummary {
+                    date: date!(2025 - 11 - 06),
+                    created: 2,
+                    skipped: 0
+                },
+                DaySummary {
+                    date: date!(2025 - 11 - 07),
+                    created: 1,
+                    skipped: 0
+                },
+            ]
+        );
+
+        let second = import_entries(&storage, &entries, utc(), false).unwrap();
+        assert!(second.iter().all(|day| day.created == 0));
+        assert_eq!(second.iter().map(|day| day.skipped).sum::<usize>(), 3);
+        assert_eq!(
+            storage
+                .load(&date!(2025 - 11 - 06))
+                .unwrap()
+                .work_records
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_import_keeps_existing_records() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(WorkRecord::new(1, "Manual".to_string(), t(7, 0), t(8, 0)));
+        storage.save(&day).unwrap();
+
+        let entries = parse_toggl(TOGGL_EXPORT).unwrap();
+        import_entries(&storage, &entries[..1], utc(), false).unwrap();
+
+        let day = storage.load(&date!(2025 - 11 - 06)).unwrap();
+        assert_eq!(day.work_records.len(), 2);
+        assert_eq!(day.work_records[&1].name, "Manual");
+        assert_eq!(
+            day.work_records[&2].external_id.as_deref(),
+            Some("toggl:3001")
+        );
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let entries = parse_clockify(CLOCKIFY_EXPORT).unwrap();
+
+        let summary = import_entries(&storage, &entries, utc(), true).unwrap();
+        assert_eq!(summary[0].created, 2);
+        assert!(
+            storage
+                .load(&date!(2025 - 11 - 06))
+                .unwrap()
+                .work_records
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Tracker API tokens, fetched when they are used (`credential` in a tracker)
+//!
+//! `env:VAR` reads a variable, `file:PATH` a file only its owner can read, and
+//! `keyring` the OS keychain under service "work-tuimer" with the tracker name
+//! as the account. Trackers without `credential` fall back to `api_token_env`.
+//! Tokens are held in [`Secret`], whose Debug output is masked, and no error
+//! message quotes one.
+
+use crate::config::TrackerConfig;
+use anyhow::{Context, Result, anyhow, bail};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Keychain service every tracker token is stored under
+pub const KEYRING_SERVICE: &str = "work-tuimer";
+
+/// A token or password; Debug prints a mask instead
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(****)")
+    }
+}
+
+/// Where a tracker's token comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    Env(String),
+    Keyring,
+    File(PathBuf),
+}
+
+impl CredentialSource {
+    /// Parse `env:VAR`, `keyring` or `file:PATH` (`~/` is the home directory)
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value == "keyring" {
+            return Ok(Self::Keyring);
+        }
+        if let Some(name) = value.strip_prefix("env:")
+            && !name.is_empty()
+        {
+            return Ok(Self::Env(name.to_string()));
+        }
+        if let Some(path) = value.strip_prefix("file:")
+            && !path.is_empty()
+        {
+            return Ok(Self::File(expand_home(path)));
+        }
+        bail!(
+            "Invalid credential '{}' (use env:VAR, keyring or file:PATH)",
+            value
+        )
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// OS keychain access
+pub trait Keyring {
+    /// The stored secret, None when there is none
+    fn get(&self, service: &str, account: &str) -> Result<Option<Secret>>;
+    fn set(&self, service: &str, account: &str, secret: &Secret) -> Result<()>;
+    /// Whether there was a secret to remove
+    fn delete(&self, service: &str, account: &str) -> Result<bool>;
+}
+
+/// `Keyring` backed by the platform's store: the macOS Keychain, the Windows
+/// Credential Manager, or the Secret Service (GNOME Keyring, KWallet) elsewhere
+pub struct SystemKeyring;
+
+impl SystemKeyring {
+    fn entry(service: &str, account: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(service, account).context("Could not open the system keyring")
+    }
+}
+
+impl Keyring for SystemKeyring {
+    fn get(&self, service: &str, account: &str) -> Result<Option<Secret>> {
+        match Self::entry(service, account)?.get_password() {
+            Ok(secret) if !secret.is_empty() => Ok(Some(Secret(secret))),
+            Ok(_) | Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).context("Could not read the system keyring"),
+        }
+    }
+
+    fn set(&self, service: &str, account: &str, secret: &Secret) -> Result<()> {
+        Self::entry(service, account)?
+            .set_password(secret.expose())
+            .context("Could not store the secret")
+    }
+
+    fn delete(&self, service: &str, account: &str) -> Result<bool> {
+        match Self::entry(service, account)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(e).context("Could not remove the secret"),
+        }
+    }
+}
+
+/// The token for tracker `name`: its `credential`, else `api_token_env`
+pub fn resolve_token(
+    name: &str,
+    tracker: &TrackerConfig,
+    env: impl Fn(&str) -> Option<String>,
+    keyring: &dyn Keyring,
+) -> Result<Secret> {
+    let source = if !tracker.credential.trim().is_empty() {
+        CredentialSource::parse(&tracker.credential)?
+    } else if !tracker.api_token_env.is_empty() {
+        CredentialSource::Env(tracker.api_token_env.clone())
+    } else {
+        bail!("no credential set for tracker '{}'", name);
+    };
+
+    match source {
+        CredentialSource::Env(var) => env(&var)
+            .filter(|token| !token.is_empty())
+            .map(Secret)
+            .ok_or_else(|| anyhow!("${} is not set", var)),
+        CredentialSource::Keyring => keyring
+            .get(KEYRING_SERVICE, name)?
+            .ok_or_else(|| anyhow!("no keyring entry for '{}' (run: auth set {})", name, name)),
+        CredentialSource::File(path) => read_secret_file(&path),
+    }
+}
+
+/// First line of `path`, refusing files others can read
+pub fn read_secret_file(path: &Path) -> Result<Secret> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)
+            .with_context(|| format!("Could not read {}", path.display()))?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            bail!(
+                "{} can be read by group or others (mode {:o}); run chmod 600 on it",
+                path.display(),
+                mode & 0o777
+            );
+        }
+    }
+    // The error mentions the path only; the contents are the secret
+    let contents =
+        std::fs::read_to_string(path).map_err(|_| anyhow!("Could not read {}", path.display()))?;
+    let token = contents.lines().next().unwrap_or("").trim();
+    if token.is_empty() {
+        bail!("{} is empty", path.display());
+    }
+    Ok(Secret(token.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// In-memory keychain keyed by (service, account)
+    #[derive(Default)]
+    struct MockKeyring {
+        entries: RefCell<HashMap<(String, String), Secret>>,
+    }
+
+    impl Keyring for MockKeyring {
+        fn get(&self, service: &str, account: &str) -> Result<Option<Secret>> {
+            let key = (service.to_string(), account.to_string());
+            Ok(self.entries.borrow().get(&key).cloned())
+        }
+
+        fn set(&self, service: &str, account: &str, secret: &Secret) -> Result<()> {
+            let key = (service.to_string(), account.to_string());
+            self.entries.borrow_mut().insert(key, secret.clone());
+            Ok(())
+        }
+
+        fn delete(&self, service: &str, account: &str) -> Result<bool> {
+            let key = (service.to_string(), account.to_string());
+            Ok(self.entries.borrow_mut().remove(&key).is_some())
+        }
+    }
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "JIRA_TOKEN" => Some("from-env".to_string()),
+            "OLD_TOKEN" => Some("from-old-env".to_string()),
+            _ => None,
+        }
+    }
+
+    fn tracker(credential: &str, api_token_env: &str) -> TrackerConfig {
+        TrackerConfig {
+            credential: credential.to_string(),
+            api_token_env: api_token_env.to_string(),
+            ..TrackerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_sources() {
+        assert_eq!(
+            CredentialSource::parse("env:JIRA_TOKEN").unwrap(),
+            CredentialSource::Env("JIRA_TOKEN".to_string())
+        );
+        assert_eq!(
+            CredentialSource::parse(" keyring ").unwrap(),
+            CredentialSource::Keyring
+        );
+        assert_eq!(
+            CredentialSource::parse("file:/run/secrets/jira").unwrap(),
+            CredentialSource::File(PathBuf::from("/run/secrets/jira"))
+        );
+        for invalid in ["env:", "file:", "vault:jira", "hunter2"] {
+            assert!(CredentialSource::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_credential_takes_precedence_over_api_token_env() {
+        let keyring = MockKeyring::default();
+        keyring
+            .set(KEYRING_SERVICE, "jira", &Secret::new("from-keyring"))
+            .unwrap();
+
+        let token = |tracker: &TrackerConfig| {
+            resolve_token("jira", tracker, env, &keyring).map(|secret| secret.expose().to_string())
+        };
+        assert_eq!(
+            token(&tracker("env:JIRA_TOKEN", "OLD_TOKEN")).unwrap(),
+            "from-env"
+        );
+        assert_eq!(
+            token(&tracker("keyring", "OLD_TOKEN")).unwrap(),
+            "from-keyring"
+        );
+        assert_eq!(token(&tracker("", "OLD_TOKEN")).unwrap(), "from-old-env");
+        assert_eq!(
+            token(&tracker("", "")).unwrap_err().to_string(),
+            "no credential set for tracker 'jira'"
+        );
+        assert_eq!(
+            token(&tracker("env:MISSING", "")).unwrap_err().to_string(),
+            "$MISSING is not set"
+        );
+    }
+
+    #[test]
+    fn test_keyring_entry_is_per_tracker() {
+        let keyring = MockKeyring::default();
+        keyring
+            .set(KEYRING_SERVICE, "jira", &Secret::new("from-keyring"))
+            .unwrap();
+        let error = resolve_token("linear", &tracker("keyring", ""), env, &keyring).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "no keyring entry for 'linear' (run: auth set linear)"
+        );
+        assert!(keyring.delete(KEYRING_SERVICE, "jira").unwrap());
+        assert!(!keyring.delete(KEYRING_SERVICE, "jira").unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_file_must_be_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("jira.token");
+        std::fs::write(&path, "file-secret\n").unwrap();
+
+        for mode in [0o644, 0o640, 0o604] {
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+            let error = read_secret_file(&path).unwrap_err().to_string();
+            assert!(error.contains("chmod 600"), "{}", error);
+            assert!(!error.contains("file-secret"));
+        }
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let credential = format!("file:{}", path.display());
+        let secret = resolve_token(
+            "jira",
+            &tracker(&credential, ""),
+            env,
+            &MockKeyring::default(),
+        )
+        .unwrap();
+        assert_eq!(secret.expose(), "file-secret");
+    }
+
+    #[test]
+    fn test_secrets_are_masked_in_debug_output() {
+        let secret = Secret::new("hunter2");
+        assert_eq!(format!("{:?}", secret), "Secret(****)");
+        assert!(!format!("{:?}", Some(secret)).contains("hunter2"));
+    }
+}
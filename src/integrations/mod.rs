@@ -1,6 +1,13 @@
-use crate::config::Config;
+use crate::config::{Config, IntegrationConfig, OpenMode};
 use anyhow::Result;
+use ratatui::style::Color;
 use regex::Regex;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+pub mod credentials;
+pub mod worklog;
 
 /// Extract ticket ID from task name using regex pattern: "PROJ-123 - Task name" -> "PROJ-123"
 pub fn extract_ticket_from_name(name: &str) -> Option<String> {
@@ -12,18 +19,70 @@ pub fn extract_ticket_from_name(name: &str) -> Option<String> {
         .map(|m| m.as_str().to_string())
 }
 
+/// Fill a `[defaults] description_template` for a record named `name`
+///
+/// `{ticket}` becomes the ticket found in the name and `{name}` the name itself.
+/// None when the name has no ticket, so records without one start empty.
+pub fn fill_description_template(template: &str, name: &str) -> Option<String> {
+    let ticket = extract_ticket_from_name(name)?;
+    Some(
+        template
+            .replace("{ticket}", &ticket)
+            .replace("{name}", name),
+    )
+}
+
+/// An enabled tracker whose patterns match a ticket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackerCandidate {
+    pub name: String,
+    pub priority: u32,
+}
+
+/// How a ticket was matched to a tracker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackerDetection {
+    /// Matching trackers, highest priority first, ties in name order
+    pub candidates: Vec<TrackerCandidate>,
+    /// First candidate, else `default_tracker`
+    pub chosen: Option<String>,
+}
+
+/// Find every tracker matching `ticket` and pick one
+///
+/// Trackers are tried by descending `priority`, then by name, so the result doesn't
+/// depend on the order of the config's tracker table.
+pub fn detect_tracker_candidates(ticket: &str, config: &Config) -> TrackerDetection {
+    let mut candidates: Vec<TrackerCandidate> = config
+        .integrations
+        .trackers
+        .iter()
+        .filter(|(_, tracker)| {
+            tracker.enabled && matches_patterns(ticket, &tracker.ticket_patterns)
+        })
+        .map(|(name, tracker)| TrackerCandidate {
+            name: name.clone(),
+            priority: tracker.priority,
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let chosen = candidates
+        .first()
+        .map(|candidate| candidate.name.clone())
+        // Fallback to default tracker if configured
+        .or_else(|| config.integrations.default_tracker.clone());
+    TrackerDetection { candidates, chosen }
+}
+
 /// Detect which tracker a ticket belongs to based on config patterns
 /// Returns the tracker name if a match is found
 pub fn detect_tracker(ticket: &str, config: &Config) -> Option<String> {
-    // Try each enabled tracker's patterns
-    for (name, tracker_config) in &config.integrations.trackers {
-        if tracker_config.enabled && matches_patterns(ticket, &tracker_config.ticket_patterns) {
-            return Some(name.clone());
-        }
-    }
-
-    // Fallback to default tracker if configured
-    config.integrations.default_tracker.clone()
+    detect_tracker_candidates(ticket, config).chosen
 }
 
 /// Check if ticket matches any of the provided patterns
@@ -36,6 +95,33 @@ fn matches_patterns(ticket: &str, patterns: &[String]) -> bool {
     })
 }
 
+/// Icon of a ticket badge whose tracker sets no `badge_icon`
+pub const DEFAULT_BADGE_ICON: &str = "🎫";
+
+/// How the badge of a record with a ticket looks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TicketBadge {
+    pub icon: String,
+    /// None keeps the row's own color
+    pub color: Option<Color>,
+}
+
+/// Badge for tickets routed to `tracker`, the default one without a tracker
+pub fn tracker_badge(tracker: Option<&str>, config: &Config) -> TicketBadge {
+    let tracker = tracker.and_then(|name| config.integrations.trackers.get(name));
+    TicketBadge {
+        icon: tracker
+            .and_then(|tracker| tracker.badge_icon.clone())
+            .unwrap_or_else(|| DEFAULT_BADGE_ICON.to_string()),
+        color: tracker.and_then(|tracker| tracker.badge_color()),
+    }
+}
+
+/// Badge for `ticket`, from the tracker `detect_tracker` routes it to
+pub fn ticket_badge(ticket: &str, config: &Config) -> TicketBadge {
+    tracker_badge(detect_tracker(ticket, config).as_deref(), config)
+}
+
 /// Build a URL for the given ticket and tracker name
 pub fn build_url(
     ticket: &str,
@@ -66,10 +152,233 @@ pub fn build_url(
     Ok(url)
 }
 
+/// URL of a ticket on its detected tracker
+pub fn ticket_url(ticket: &str, config: &Config, for_worklog: bool) -> Result<String> {
+    let tracker_name = detect_tracker(ticket, config)
+        .ok_or_else(|| anyhow::anyhow!("Could not detect tracker for ticket"))?;
+    build_url(ticket, &tracker_name, config, for_worklog)
+}
+
+/// The ticket in a task name and where `T` would open it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTicket {
+    pub ticket: String,
+    /// Detected tracker, `None` when no pattern matches and there is no default
+    pub tracker: Option<String>,
+    /// Browse URL, `None` without a tracker or when it isn't enabled
+    pub browse_url: Option<String>,
+}
+
+/// Extract the ticket from `name` and route it to a tracker
+pub fn resolve_ticket(name: &str, config: &Config) -> Option<ResolvedTicket> {
+    let ticket = extract_ticket_from_name(name)?;
+    let tracker = detect_tracker(&ticket, config);
+    let browse_url = tracker
+        .as_deref()
+        .and_then(|tracker| build_url(&ticket, tracker, config, false).ok());
+    Some(ResolvedTicket {
+        ticket,
+        tracker,
+        browse_url,
+    })
+}
+
+/// `resolve_ticket` results per record id, so drawing a frame doesn't rerun the regexes
+///
+/// An entry is only reused while the record keeps the name it was resolved for and
+/// the integrations config is unchanged.
+#[derive(Debug, Default)]
+pub struct TicketCache {
+    entries: RefCell<HashMap<u32, (String, Option<ResolvedTicket>)>>,
+    integrations: RefCell<Option<IntegrationConfig>>,
+    /// `Config::has_integrations` for the cached integrations config
+    enabled: std::cell::Cell<bool>,
+    #[cfg(test)]
+    misses: std::cell::Cell<usize>,
+}
+
+impl TicketCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a tracker is set up, worked out again only when the config changes
+    pub fn has_integrations(&self, config: &Config) -> bool {
+        self.sync(config);
+        self.enabled.get()
+    }
+
+    pub fn resolve(&self, id: u32, name: &str, config: &Config) -> Option<ResolvedTicket> {
+        self.sync(config);
+
+        if let Some((cached_name, resolved)) = self.entries.borrow().get(&id)
+            && cached_name == name
+        {
+            return resolved.clone();
+        }
+
+        #[cfg(test)]
+        self.misses.set(self.misses.get() + 1);
+        let resolved = resolve_ticket(name, config);
+        self.entries
+            .borrow_mut()
+            .insert(id, (name.to_string(), resolved.clone()));
+        resolved
+    }
+
+    /// Drop every entry once the integrations config differs from the cached one
+    fn sync(&self, config: &Config) {
+        let mut integrations = self.integrations.borrow_mut();
+        if integrations.as_ref() != Some(&config.integrations) {
+            self.entries.borrow_mut().clear();
+            self.enabled.set(config.has_integrations());
+            *integrations = Some(config.integrations.clone());
+        }
+    }
+}
+
+/// What happened when a URL was opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenOutcome {
+    /// The browser was launched
+    Opened,
+    /// The URL was put on the clipboard
+    Copied,
+    /// The URL has to be shown to the user; `reason` says why opening it failed
+    Shown { reason: Option<String> },
+}
+
+/// Open a URL according to `mode`, degrading gracefully when that fails
+///
+/// A browser that can't be launched (SSH, containers, WSL) falls back to copying
+/// the URL and showing it; a failed copy falls back to showing it.
+pub fn open_with_fallback(
+    url: &str,
+    mode: OpenMode,
+    open_browser: impl FnOnce(&str) -> io::Result<()>,
+    copy: impl FnOnce(&str) -> io::Result<()>,
+) -> OpenOutcome {
+    match mode {
+        OpenMode::Browser => match open_browser(url) {
+            Ok(()) => OpenOutcome::Opened,
+            Err(e) => {
+                let copied = copy(url).is_ok();
+                OpenOutcome::Shown {
+                    reason: Some(if copied {
+                        format!("Could not open browser ({}); URL copied to clipboard", e)
+                    } else {
+                        format!("Could not open browser ({})", e)
+                    }),
+                }
+            }
+        },
+        OpenMode::Copy => match copy(url) {
+            Ok(()) => OpenOutcome::Copied,
+            Err(e) => OpenOutcome::Shown {
+                reason: Some(format!("Could not copy to clipboard ({})", e)),
+            },
+        },
+        OpenMode::Print => OpenOutcome::Shown { reason: None },
+    }
+}
+
+/// Open a URL in the default browser using platform-specific commands.
+///
+/// On Windows, special care is taken to handle URLs with query parameters
+/// containing `&` characters. The `start` command requires an empty string
+/// as the window title argument before the URL, otherwise `&` is interpreted
+/// as a command separator by cmd.exe.
+pub fn open_url_in_browser(url: &str) -> io::Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open").arg(url).spawn()?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // Windows cmd.exe treats & as a command separator.
+        // We use raw_arg to pass the complete command string with proper quoting.
+        // Format: cmd /C start "" "url" - empty quotes for title, quoted URL.
+        use std::os::windows::process::CommandExt;
+        std::process::Command::new("cmd")
+            .raw_arg(format!("/C start \"\" \"{}\"", url))
+            .spawn()?;
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        // Linux/Unix
+        std::process::Command::new("xdg-open").arg(url).spawn()?;
+    }
+
+    Ok(())
+}
+
+/// Put text on the clipboard with the OSC 52 terminal escape
+///
+/// Works over SSH and in containers because the terminal, not the host, owns the
+/// clipboard; terminals without OSC 52 support silently ignore it.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()
+}
+
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Standard base64 with padding (enough for OSC 52, no extra dependency)
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fill_description_template_with_ticket() {
+        assert_eq!(
+            fill_description_template("{ticket}: ", "PROJ-123 Fix login bug").as_deref(),
+            Some("PROJ-123: ")
+        );
+        assert_eq!(
+            fill_description_template("[{ticket}] {name} - ", "WL-1 Standup").as_deref(),
+            Some("[WL-1] WL-1 Standup - ")
+        );
+    }
+
+    #[test]
+    fn test_fill_description_template_without_ticket() {
+        assert_eq!(
+            fill_description_template("{ticket}: ", "Team meeting"),
+            None
+        );
+        assert_eq!(
+            fill_description_template("Notes: ", "proj-12 lowercase"),
+            None
+        );
+    }
+
     #[test]
     fn test_extract_ticket_simple() {
         let name = "PROJ-123 Fix login bug";
@@ -186,6 +495,52 @@ browse_url = "{base_url}/issues/{ticket}"
         assert_eq!(tracker, Some("github".to_string()));
     }
 
+    #[test]
+    fn test_ticket_badge_follows_the_matching_tracker() {
+        let toml_str = r##"
+[integrations.trackers.jira]
+enabled = true
+ticket_patterns = ["^PROJ-\\d+$"]
+badge_icon = "J"
+badge_color = "#0052cc"
+
+[integrations.trackers.linear]
+enabled = true
+ticket_patterns = ["^ENG-\\d+$"]
+badge_color = "magenta"
+
+[integrations.trackers.github]
+enabled = true
+ticket_patterns = ["^GH-\\d+$"]
+        "##;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(
+            ticket_badge("PROJ-12", &config),
+            TicketBadge {
+                icon: "J".to_string(),
+                color: Some(Color::Rgb(0x00, 0x52, 0xcc)),
+            }
+        );
+        assert_eq!(
+            ticket_badge("ENG-4", &config),
+            TicketBadge {
+                icon: DEFAULT_BADGE_ICON.to_string(),
+                color: Some(Color::Magenta),
+            }
+        );
+        assert_eq!(
+            ticket_badge("GH-9", &config),
+            TicketBadge {
+                icon: DEFAULT_BADGE_ICON.to_string(),
+                color: None,
+            }
+        );
+        // No tracker matches and there is no default
+        assert_eq!(ticket_badge("OPS-1", &config), tracker_badge(None, &config));
+        assert_eq!(tracker_badge(None, &config).icon, DEFAULT_BADGE_ICON);
+    }
+
     #[test]
     fn test_detect_tracker_overlapping_patterns_first_wins() {
         // Test that when multiple trackers match, the first one in iteration order wins
@@ -207,18 +562,72 @@ browse_url = "{base_url}/issue/{ticket}"
         "#;
         let config: Config = toml::from_str(toml_str).unwrap();
 
-        // PROJ-123 matches both patterns - should use whichever tracker appears first
+        // PROJ-123 matches both patterns at the same priority, so the first name wins
         let tracker = detect_tracker("PROJ-123", &config);
-        assert!(tracker.is_some());
+        assert_eq!(tracker, Some("jira".to_string()));
+    }
+
+    #[test]
+    fn test_detect_tracker_highest_priority_wins() {
+        let toml_str = r#"
+[integrations]
+default_tracker = "fallback"
+
+[integrations.trackers.jira]
+enabled = true
+ticket_patterns = ["^[A-Z]+-\\d+$"]
+browse_url = "https://jira.example.com/browse/{ticket}"
+
+[integrations.trackers.linear]
+enabled = true
+priority = 10
+ticket_patterns = ["^LIN-\\d+$"]
+browse_url = "https://linear.app/team/issue/{ticket}"
+
+[integrations.trackers.youtrack]
+enabled = true
+priority = 5
+ticket_patterns = ["^[A-Z]+-\\d+$"]
+browse_url = "https://youtrack.example.com/issue/{ticket}"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        let detection = detect_tracker_candidates("LIN-7", &config);
+        let order: Vec<(&str, u32)> = detection
+            .candidates
+            .iter()
+            .map(|c| (c.name.as_str(), c.priority))
+            .collect();
+        assert_eq!(order, vec![("linear", 10), ("youtrack", 5), ("jira", 0)]);
+        assert_eq!(detection.chosen, Some("linear".to_string()));
+
+        let detection = detect_tracker_candidates("PROJ-1", &config);
+        assert_eq!(detection.chosen, Some("youtrack".to_string()));
+        assert_eq!(detection.candidates.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_tracker_candidates_skips_disabled_and_falls_back() {
+        let toml_str = r#"
+[integrations]
+default_tracker = "jira"
 
-        // The result should be deterministic (either "jira" or "linear")
-        // Note: HashMap iteration order is not guaranteed in Rust, but it should be consistent
-        let tracker_name = tracker.unwrap();
-        assert!(tracker_name == "jira" || tracker_name == "linear");
+[integrations.trackers.jira]
+enabled = true
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "https://jira.example.com/browse/{ticket}"
+
+[integrations.trackers.github]
+enabled = false
+priority = 99
+ticket_patterns = ["^[A-Z]+-\\d+$"]
+browse_url = "https://github.com/issues/{ticket}"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
 
-        // Verify the same ticket always resolves to the same tracker
-        let tracker2 = detect_tracker("PROJ-123", &config);
-        assert_eq!(tracker2, Some(tracker_name));
+        let detection = detect_tracker_candidates("OPS-4", &config);
+        assert!(detection.candidates.is_empty());
+        assert_eq!(detection.chosen, Some("jira".to_string()));
     }
 
     #[test]
@@ -407,4 +816,186 @@ worklog_url = ""
         assert!(url_str.contains("tab=details"));
         assert!(url_str.contains("expand=true"));
     }
+
+    fn failing(_: &str) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "xdg-open not found",
+        ))
+    }
+
+    fn succeeding(_: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_browser_success() {
+        assert_eq!(
+            open_with_fallback("https://x", OpenMode::Browser, succeeding, failing),
+            OpenOutcome::Opened
+        );
+    }
+
+    #[test]
+    fn test_open_browser_failure_falls_back_to_copy_and_show() {
+        let outcome = open_with_fallback("https://x", OpenMode::Browser, failing, succeeding);
+        assert_eq!(
+            outcome,
+            OpenOutcome::Shown {
+                reason: Some(
+                    "Could not open browser (xdg-open not found); URL copied to clipboard"
+                        .to_string()
+                )
+            }
+        );
+
+        let outcome = open_with_fallback("https://x", OpenMode::Browser, failing, failing);
+        assert_eq!(
+            outcome,
+            OpenOutcome::Shown {
+                reason: Some("Could not open browser (xdg-open not found)".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn test_copy_and_print_modes_never_launch_browser() {
+        let panics = |_: &str| -> io::Result<()> { panic!("browser must not be launched") };
+        assert_eq!(
+            open_with_fallback("https://x", OpenMode::Copy, panics, succeeding),
+            OpenOutcome::Copied
+        );
+        assert!(matches!(
+            open_with_fallback("https://x", OpenMode::Copy, panics, failing),
+            OpenOutcome::Shown { reason: Some(_) }
+        ));
+        assert_eq!(
+            open_with_fallback("https://x", OpenMode::Print, panics, failing),
+            OpenOutcome::Shown { reason: None }
+        );
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(
+            base64_encode(b"https://x/?a=1&b"),
+            "aHR0cHM6Ly94Lz9hPTEmYg=="
+        );
+        assert_eq!(osc52_sequence("foo"), "\x1b]52;c;Zm9v\x07");
+    }
+
+    #[test]
+    fn test_ticket_url_reuses_tracker_detection() {
+        let toml_str = r#"
+[integrations.trackers.my-jira]
+enabled = true
+base_url = "https://test.atlassian.net"
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+worklog_url = "{base_url}/browse/{ticket}?focusedWorklogId=-1"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            ticket_url("PROJ-123", &config, true).unwrap(),
+            "https://test.atlassian.net/browse/PROJ-123?focusedWorklogId=-1"
+        );
+        assert!(ticket_url("OTHER-1", &config, false).is_err());
+    }
+
+    fn two_tracker_config() -> Config {
+        toml::from_str(
+            r#"
+[integrations.trackers.jira]
+enabled = true
+base_url = "https://test.atlassian.net"
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+
+[integrations.trackers.linear]
+enabled = true
+base_url = "https://linear.app/acme"
+ticket_patterns = ["^LIN-\\d+$"]
+browse_url = "{base_url}/issue/{ticket}"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_ticket_combines_extraction_and_detection() {
+        let config = two_tracker_config();
+        assert_eq!(
+            resolve_ticket("LIN-7 Sync", &config),
+            Some(ResolvedTicket {
+                ticket: "LIN-7".to_string(),
+                tracker: Some("linear".to_string()),
+                browse_url: Some("https://linear.app/acme/issue/LIN-7".to_string()),
+            })
+        );
+        let unrouted = resolve_ticket("OPS-3 Pager", &config).unwrap();
+        assert_eq!(unrouted.tracker, None);
+        assert_eq!(unrouted.browse_url, None);
+        assert_eq!(resolve_ticket("Standup", &config), None);
+    }
+
+    #[test]
+    fn test_ticket_cache_reuses_entries_per_record() {
+        let config = two_tracker_config();
+        let cache = TicketCache::new();
+
+        let first = cache.resolve(1, "PROJ-1 Login", &config);
+        assert_eq!(first.unwrap().tracker.as_deref(), Some("jira"));
+        cache.resolve(1, "PROJ-1 Login", &config);
+        cache.resolve(2, "Standup", &config);
+        cache.resolve(2, "Standup", &config);
+        assert_eq!(cache.misses.get(), 2);
+    }
+
+    #[test]
+    fn test_ticket_cache_invalidates_on_rename() {
+        let config = two_tracker_config();
+        let cache = TicketCache::new();
+        cache.resolve(1, "PROJ-1 Login", &config);
+
+        let renamed = cache.resolve(1, "LIN-9 Login", &config).unwrap();
+        assert_eq!(renamed.ticket, "LIN-9");
+        assert_eq!(renamed.tracker.as_deref(), Some("linear"));
+        assert_eq!(cache.misses.get(), 2);
+    }
+
+    #[test]
+    fn test_ticket_cache_follows_trackers_being_enabled() {
+        let mut config = two_tracker_config();
+        let cache = TicketCache::new();
+        assert!(cache.has_integrations(&config));
+
+        for tracker in config.integrations.trackers.values_mut() {
+            tracker.enabled = false;
+        }
+        assert!(!cache.has_integrations(&config));
+        assert!(!cache.has_integrations(&Config::default()));
+    }
+
+    #[test]
+    fn test_ticket_cache_invalidates_on_config_change() {
+        let mut config = two_tracker_config();
+        let cache = TicketCache::new();
+        cache.resolve(1, "PROJ-1 Login", &config);
+
+        // PROJ tickets now also match linear, which outranks jira
+        let linear = config.integrations.trackers.get_mut("linear").unwrap();
+        linear.ticket_patterns.push("^PROJ-\\d+$".to_string());
+        linear.priority = 1;
+        let resolved = cache.resolve(1, "PROJ-1 Login", &config).unwrap();
+        assert_eq!(resolved.tracker.as_deref(), Some("linear"));
+        assert_eq!(
+            resolved.browse_url.as_deref(),
+            Some("https://linear.app/acme/issue/PROJ-1")
+        );
+        assert_eq!(cache.misses.get(), 2);
+    }
 }
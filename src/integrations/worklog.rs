@@ -0,0 +1,358 @@
+//! JIRA worklogs submitted when a session stops (`[integrations] auto_worklog`)
+//!
+//! Only the stopped session is logged, so continuing a record adds the new time
+//! rather than the record's whole duration. Requests go through [`HttpClient`];
+//! [`CurlClient`] runs the system `curl`, fed on stdin so the token never shows
+//! up in the process list.
+
+use super::credentials::{Secret, SystemKeyring, resolve_token};
+use super::{detect_tracker, extract_ticket_from_name};
+use crate::config::{Config, TrackerConfig};
+use crate::timefmt;
+use crate::timer::StoppedTimer;
+use anyhow::{Context, Result, anyhow, bail};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use time::OffsetDateTime;
+use time::macros::format_description;
+
+/// Seconds curl may take before the stop goes on without a worklog
+const REQUEST_TIMEOUT_SECS: u32 = 10;
+
+/// A worklog ready to send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorklogRequest {
+    pub ticket: String,
+    /// `{base_url}/rest/api/2/issue/{ticket}/worklog`
+    pub url: String,
+    /// `user:token` for basic auth
+    pub credentials: Secret,
+    /// JSON with `timeSpentSeconds`, `started` and `comment`
+    pub body: String,
+    pub minutes: u32,
+}
+
+/// Sends a JSON body, returning the HTTP status
+pub trait HttpClient {
+    fn post_json(&self, url: &str, credentials: &Secret, body: &str) -> Result<u16>;
+}
+
+/// `HttpClient` running the system `curl`
+pub struct CurlClient;
+
+impl HttpClient for CurlClient {
+    fn post_json(&self, url: &str, credentials: &Secret, body: &str) -> Result<u16> {
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--config", "-"])
+            .args(["--output", "/dev/null", "--write-out", "%{http_code}"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Could not run curl")?;
+
+        let config = [
+            ("url", url),
+            ("user", credentials.expose()),
+            ("header", "Content-Type: application/json"),
+            ("data-binary", body),
+            ("max-time", &REQUEST_TIMEOUT_SECS.to_string()),
+        ]
+        .iter()
+        .map(|(option, value)| format!("{} = \"{}\"\n", option, curl_quote(value)))
+        .collect::<String>();
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("Could not write to curl"))?
+            .write_all(config.as_bytes())?;
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .context("curl gave no HTTP status")
+    }
+}
+
+/// Escape a value for a double-quoted curl config string
+fn curl_quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The worklog for a stopped session, None when there is nothing to log
+///
+/// Sessions without a ticket, on a tracker without `api_user`, or under a minute
+/// long are left alone. `token` fetches the tracker's API token (see
+/// `credentials`); a missing token is an error, since logging was asked for.
+pub fn worklog_request(
+    stopped: &StoppedTimer,
+    config: &Config,
+    token: impl Fn(&str, &TrackerConfig) -> Result<Secret>,
+) -> Result<Option<WorklogRequest>> {
+    let session = &stopped.session;
+    let Some(ticket) = extract_ticket_from_name(&session.name) else {
+        return Ok(None);
+    };
+    let Some((name, tracker)) = detect_tracker(&ticket, config)
+        .and_then(|name| config.integrations.trackers.get_key_value(&name))
+        .filter(|(_, tracker)| tracker.enabled && !tracker.api_user.is_empty())
+    else {
+        return Ok(None);
+    };
+    if session.total_minutes == 0 {
+        return Ok(None);
+    }
+
+    let token = token(name, tracker)?;
+    let comment = if session.description.trim().is_empty() {
+        &session.name
+    } else {
+        &session.description
+    };
+    let body = serde_json::json!({
+        "timeSpentSeconds": session.total_minutes * 60,
+        "started": jira_timestamp(stopped.started_at)?,
+        "comment": comment,
+    });
+
+    Ok(Some(WorklogRequest {
+        url: format!(
+            "{}/rest/api/2/issue/{}/worklog",
+            tracker.base_url.trim_end_matches('/'),
+            ticket
+        ),
+        ticket,
+        credentials: Secret::new(format!("{}:{}", tracker.api_user, token.expose())),
+        body: body.to_string(),
+        minutes: session.total_minutes,
+    }))
+}
+
+/// "2025-11-06T09:00:00.000+0100", the only form JIRA takes for `started`
+fn jira_timestamp(at: OffsetDateTime) -> Result<String> {
+    Ok(at.format(format_description!(
+        "[year]-[month]-[day]T[hour]:[minute]:[second].000[offset_hour sign:mandatory][offset_minute]"
+    ))?)
+}
+
+/// Log a stopped session if `auto_worklog` is on, describing what happened
+///
+/// None when the setting is off or the session has nothing to log.
+pub fn auto_worklog(
+    stopped: &StoppedTimer,
+    config: &Config,
+    client: &dyn HttpClient,
+) -> Option<String> {
+    send_worklog(stopped, config, client, |name, tracker| {
+        resolve_token(name, tracker, |var| std::env::var(var).ok(), &SystemKeyring)
+    })
+}
+
+fn send_worklog(
+    stopped: &StoppedTimer,
+    config: &Config,
+    client: &dyn HttpClient,
+    token: impl Fn(&str, &TrackerConfig) -> Result<Secret>,
+) -> Option<String> {
+    if !config.integrations.auto_worklog {
+        return None;
+    }
+    let request = match worklog_request(stopped, config, token) {
+        Ok(request) => request?,
+        Err(e) => return Some(format!("worklog not sent: {}", e)),
+    };
+    let sent = client
+        .post_json(&request.url, &request.credentials, &request.body)
+        .and_then(|status| match status {
+            200..=299 => Ok(()),
+            status => Err(anyhow!("HTTP {}", status)),
+        });
+    Some(match sent {
+        Ok(()) => format!(
+            "logged {} to {}",
+            timefmt::format_minutes(request.minutes),
+            request.ticket
+        ),
+        Err(e) => format!("worklog to {} failed: {}", request.ticket, e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use crate::timer::StopAction;
+    use std::cell::RefCell;
+    use time::macros::{date, datetime};
+
+    /// Records requests and answers with a fixed status
+    struct MockClient {
+        status: u16,
+        sent: RefCell<Vec<(String, String, String)>>,
+    }
+
+    impl MockClient {
+        fn answering(status: u16) -> Self {
+            Self {
+                status,
+                sent: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl HttpClient for MockClient {
+        fn post_json(&self, url: &str, credentials: &Secret, body: &str) -> Result<u16> {
+            self.sent.borrow_mut().push((
+                url.to_string(),
+                credentials.expose().to_string(),
+                body.to_string(),
+            ));
+            Ok(self.status)
+        }
+    }
+
+    /// Token lookup from an environment with only the test tracker's variable
+    fn env(name: &str, tracker: &TrackerConfig) -> Result<Secret> {
+        let env = |var: &str| (var == "JIRA_TOKEN").then(|| "secret".to_string());
+        resolve_token(name, tracker, env, &SystemKeyring)
+    }
+
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+[integrations]
+auto_worklog = true
+
+[integrations.trackers.jira]
+enabled = true
+base_url = "https://test.atlassian.net/"
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+api_user = "me@example.com"
+api_token_env = "JIRA_TOKEN"
+"#,
+        )
+        .unwrap()
+    }
+
+    /// A 9:00 session that continued a record which already ran 8:00–8:30
+    fn continued(name: &str, end: (u8, u8)) -> StoppedTimer {
+        let session = WorkRecord::new(
+            4,
+            name.to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(end.0, end.1).unwrap(),
+        );
+        let mut record = session.clone();
+        record.start = TimePoint::new(8, 0).unwrap();
+        record.update_duration();
+        StoppedTimer {
+            session,
+            record,
+            date: date!(2025 - 11 - 06),
+            started_at: datetime!(2025-11-06 09:00:00 +01:00),
+            action: StopAction::Updated {
+                previous_end: TimePoint::new(8, 30).unwrap(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_payload_logs_only_the_session() {
+        let request = worklog_request(&continued("PROJ-12 Review", (9, 45)), &config(), env)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            request.url,
+            "https://test.atlassian.net/rest/api/2/issue/PROJ-12/worklog"
+        );
+        assert_eq!(request.credentials.expose(), "me@example.com:secret");
+        assert!(!format!("{:?}", request).contains("secret"));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["timeSpentSeconds"], 45 * 60);
+        assert_eq!(body["started"], "2025-11-06T09:00:00.000+0100");
+        assert_eq!(body["comment"], "PROJ-12 Review");
+    }
+
+    #[test]
+    fn test_description_becomes_the_comment() {
+        let mut stopped = continued("PROJ-12 Review", (9, 45));
+        stopped.session.description = "second pass".to_string();
+        let request = worklog_request(&stopped, &config(), env).unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["comment"], "second pass");
+    }
+
+    #[test]
+    fn test_nothing_to_log() {
+        let config = config();
+        for stopped in [
+            continued("Review", (9, 45)),
+            continued("OTHER-1 Review", (9, 45)),
+            continued("PROJ-12 Review", (9, 0)),
+        ] {
+            assert_eq!(worklog_request(&stopped, &config, env).unwrap(), None);
+        }
+
+        let mut no_user = config.clone();
+        no_user
+            .integrations
+            .trackers
+            .get_mut("jira")
+            .unwrap()
+            .api_user
+            .clear();
+        let stopped = continued("PROJ-12 Review", (9, 45));
+        assert_eq!(worklog_request(&stopped, &no_user, env).unwrap(), None);
+    }
+
+    #[test]
+    fn test_auto_worklog_reports_success_and_failure() {
+        let stopped = continued("PROJ-12 Review", (10, 5));
+        let client = MockClient::answering(201);
+        assert_eq!(
+            send_worklog(&stopped, &config(), &client, env),
+            Some("logged 1h 05m to PROJ-12".to_string())
+        );
+        assert_eq!(client.sent.borrow().len(), 1);
+
+        let client = MockClient::answering(401);
+        assert_eq!(
+            send_worklog(&stopped, &config(), &client, env),
+            Some("worklog to PROJ-12 failed: HTTP 401".to_string())
+        );
+    }
+
+    #[test]
+    fn test_auto_worklog_off_or_without_token() {
+        let stopped = continued("PROJ-12 Review", (9, 45));
+        let client = MockClient::answering(201);
+
+        let mut off = config();
+        off.integrations.auto_worklog = false;
+        assert_eq!(send_worklog(&stopped, &off, &client, env), None);
+
+        let mut unset = config();
+        unset
+            .integrations
+            .trackers
+            .get_mut("jira")
+            .unwrap()
+            .api_token_env = "OTHER_TOKEN".to_string();
+        assert_eq!(
+            send_worklog(&stopped, &unset, &client, env),
+            Some("worklog not sent: $OTHER_TOKEN is not set".to_string())
+        );
+        assert!(client.sent.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_curl_config_quoting() {
+        assert_eq!(curl_quote(r#"{"a":"b\n"}"#), r#"{\"a\":\"b\\n\"}"#);
+    }
+}
@@ -1,10 +1,18 @@
 // Library exports for work-tuimer
 // This allows integration tests to access internal modules
 
+pub mod backup;
+pub mod budget;
 pub mod cli;
 pub mod config;
+pub mod import;
 pub mod integrations;
 pub mod models;
+pub mod profile;
+pub mod report;
+pub mod serve;
 pub mod storage;
+pub mod timefmt;
 pub mod timer;
 pub mod ui;
+pub mod validation;
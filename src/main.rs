@@ -1,76 +1,194 @@
+mod backup;
+mod budget;
 mod cli;
 mod config;
+mod import;
 mod integrations;
 mod models;
+mod profile;
+mod report;
+mod serve;
 mod storage;
+mod timefmt;
 mod timer;
 mod ui;
+mod validation;
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use cli::Classify;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io;
+use std::process::ExitCode;
 use time::OffsetDateTime;
 use ui::AppState;
+use ui::app_state::CommandAction;
+use ui::keymap::{Key, KeyBinding, Resolved};
 
-fn main() -> Result<()> {
-    // Try to parse CLI arguments
-    let args: Vec<String> = std::env::args().collect();
+fn main() -> ExitCode {
+    let cli = match cli::Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(err) => {
+            // Help and version go to stdout and exit 0; errors exit 2 like usage errors
+            if err.use_stderr()
+                && cli::ErrorFormat::from_args(std::env::args()) == cli::ErrorFormat::Json
+            {
+                let message = err.to_string();
+                let first_line = message.lines().next().unwrap_or_default();
+                let message = first_line.strip_prefix("error: ").unwrap_or(first_line);
+                eprintln!("{}", cli::CliError::Usage(message.to_string()).to_json());
+                return ExitCode::from(2);
+            }
+            err.exit();
+        }
+    };
 
-    // If there are CLI arguments (beyond program name), run in CLI mode
-    if args.len() > 1 {
-        return run_cli();
+    let error_format = cli.error_format;
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let error = cli::CliError::classify(&err);
+            match error_format {
+                cli::ErrorFormat::Text => eprintln!("Error: {:?}", err),
+                cli::ErrorFormat::Json => eprintln!("{}", error.to_json()),
+            }
+            ExitCode::from(error.exit_code())
+        }
     }
+}
 
-    // Otherwise, run TUI
-    run_tui()
+fn run(cli: cli::Cli) -> Result<()> {
+    // A bad --profile or $WORK_TUIMER_PROFILE name
+    let profile = profile::Profile::resolve(cli.profile.as_deref()).usage_error()?;
+
+    if cli.safe {
+        eprintln!("Safe mode: ignoring config.toml and custom themes");
+    }
+    let mut config = cli.load_config(&profile).unwrap_or_default();
+    // Applies in safe mode too, which resets the config but not the display needs
+    if cli.plain {
+        config.ui.screen_reader = true;
+    }
+
+    let safe_mode = cli.safe;
+    // A bad --date is reported here, before the TUI takes over the screen
+    match cli.into_mode()? {
+        // A subcommand runs in CLI mode
+        cli::Mode::Cli(command) => run_cli(command, &profile, config),
+        // Otherwise, run TUI
+        cli::Mode::Tui(options) => run_tui(&profile, config, safe_mode, options),
+    }
 }
 
 /// Run in CLI mode
-fn run_cli() -> Result<()> {
-    let cli = cli::Cli::parse();
-    let storage = storage::Storage::new()?;
-    cli::handle_command(cli.command, storage)
+fn run_cli(
+    command: cli::Commands,
+    profile: &profile::Profile,
+    config: config::Config,
+) -> Result<()> {
+    let storage = storage::Storage::new_for(profile)?;
+    cli::handle_command(command, profile, storage, &config)
 }
 
-/// Run in TUI mode
-fn run_tui() -> Result<()> {
-    let today = OffsetDateTime::now_local()
-        .context("Failed to get local time")?
-        .date();
-    let mut storage = storage::StorageManager::new()?;
-    let day_data = storage.load_with_tracking(today)?;
+/// Run in TUI mode, on `options.date` or today
+fn run_tui(
+    profile: &profile::Profile,
+    mut config: config::Config,
+    safe_mode: bool,
+    options: cli::TuiOptions,
+) -> Result<()> {
+    let date = match options.date {
+        Some(date) => date,
+        None => OffsetDateTime::now_local()
+            .context("Failed to get local time")?
+            .date(),
+    };
+    let mut storage = storage::StorageManager::new_for(profile)?
+        .create_record_on_start(config.timer.create_record_on_start)
+        .git_autocommit(config.storage.git_autocommit_debounce())
+        .max_records(config.storage.max_records);
+    let day_data = storage.load_with_tracking(date)?;
 
     enable_raw_mode()?;
+    // Asked before the alternate screen, so the terminal answers about the real one
+    if config.theme.auto_detect {
+        let background = ui::background::detect(ui::background::QUERY_TIMEOUT);
+        config.theme.active = config.theme.active_for(background).to_string();
+    }
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = AppState::new(day_data);
+    let mut app = AppState::with_config(day_data, config);
+    app.profile = profile.clone();
+    app.safe_mode = safe_mode;
+    if app.config.timer.pause_on_lock {
+        app.lock_events = timer::listen_for_lock();
+    }
 
     // Load active timer if one exists
     if let Ok(Some(timer)) = storage.load_active_timer() {
         app.active_timer = Some(timer);
     }
+    app.flag_orphaned_records();
+    if options.calendar {
+        app.open_calendar();
+    }
 
     // Initialize last_file_modified with tracked time
-    app.last_file_modified = storage.get_last_modified(&today);
+    app.last_file_modified = storage.get_last_modified(&date);
+
+    // Dashboard endpoint, stopped when the TUI exits
+    let status_server = match app.config.serve.http.clone() {
+        Some(addr) => {
+            let started = storage::Storage::new_for(profile).and_then(|server_storage| {
+                serve::StatusServer::start(
+                    &addr,
+                    app.config.serve.allow_remote,
+                    server_storage,
+                    app.config.clone(),
+                )
+            });
+            match started {
+                Ok(server) => Some(server),
+                Err(e) => {
+                    app.last_error_message = Some(format!("Status endpoint not started: {:#}", e));
+                    None
+                }
+            }
+        }
+        None => None,
+    };
 
     let result = run_app(&mut terminal, &mut app, &mut storage);
+    if let Some(server) = status_server {
+        server.shutdown();
+    }
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
 
     if let Err(err) = result {
         eprintln!("Error: {}", err);
     }
+    // Saves still waiting out the debounce are committed before exiting
+    if let Err(err) = storage.finish_git_autocommit() {
+        eprintln!("Git autocommit failed: {}", err);
+    }
 
     Ok(())
 }
@@ -81,63 +199,183 @@ fn run_app<B: ratatui::backend::Backend>(
     storage: &mut storage::StorageManager,
 ) -> Result<()> {
     loop {
+        if let Err(e) = app.refresh_budgets(storage) {
+            app.last_error_message = Some(e);
+        }
+        if let Err(e) = app.refresh_window_totals(storage) {
+            app.last_error_message = Some(e);
+        }
+        if let Err(e) = app.refresh_focus_stats(storage) {
+            app.last_error_message = Some(e);
+        }
+        // Rung between frames, so the BEL never lands inside a drawn frame
+        if app.take_bell() {
+            let _ = ui::bell::ring(&mut io::stdout());
+        }
         terminal.draw(|f| ui::render::render(f, app))?;
+        app.frame = app.frame.wrapping_add(1);
 
         if app.should_quit {
+            app.apply_future_end_rule();
             storage.save(&app.day_data)?;
             app.last_file_modified = storage.get_last_modified(&app.current_date);
             break;
         }
 
         if app.date_changed {
-            storage.save(&app.day_data)?;
-            let new_day_data = storage.load_with_tracking(app.current_date)?;
-            app.load_new_day_data(new_day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            // Keep consuming a held [ or ] until input settles, so only the day it
+            // stops on gets loaded; anything else is handled after the load
+            let mut pending = None;
+            let browsing = matches!(app.mode, ui::AppMode::Browse);
+            app.change_day(storage, || {
+                if pending.is_some() || !event::poll(DAY_NAVIGATION_SETTLE)? {
+                    return Ok(None);
+                }
+                match event::read()? {
+                    Event::Key(key) if browsing && key.kind == KeyEventKind::Press => {
+                        match key.code {
+                            KeyCode::Char('[') => return Ok(Some(-1)),
+                            KeyCode::Char(']') => return Ok(Some(1)),
+                            _ => pending = Some(Event::Key(key)),
+                        }
+                    }
+                    event => pending = Some(event),
+                }
+                Ok(None)
+            })?;
+            if let Some(event) = pending {
+                handle_event(app, event, storage);
+            }
             continue; // Force redraw with new data before waiting for next event
         }
 
         // Poll for events with timeout to update timer display
-        if event::poll(std::time::Duration::from_millis(500))?
-            && let Event::Key(key) = event::read()?
-            && key.kind == KeyEventKind::Press
-        {
-            handle_key_event(app, key, storage);
+        if event::poll(std::time::Duration::from_millis(500))? {
+            let event = event::read()?;
+            handle_event(app, event, storage);
         }
         // If no event (timeout), check for external file changes and redraw with updated timer
         else {
-            // Check if the file has been modified externally (e.g., by CLI)
-            app.check_and_reload_if_modified(storage);
+            handle_tick(app, storage);
         }
     }
 
     Ok(())
 }
 
+/// How long day navigation waits for another [ or ] before loading the day
+const DAY_NAVIGATION_SETTLE: std::time::Duration = std::time::Duration::from_millis(150);
+
+fn handle_event(app: &mut AppState, event: Event, storage: &mut storage::StorageManager) {
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => {
+            handle_key_event(app, key, storage);
+        }
+        // Bracketed paste delivers the whole clipboard text at once
+        Event::Paste(text) => handle_paste_event(app, &text, storage),
+        _ => handle_tick(app, storage),
+    }
+}
+
+fn handle_tick(app: &mut AppState, storage: &mut storage::StorageManager) {
+    // Follow the date past midnight, splitting the running session if configured
+    if let Err(e) = app.handle_midnight(storage) {
+        app.last_error_message = Some(e);
+    }
+    // Stop a timer that was left running overnight
+    if let Err(e) = app.auto_stop_overdue_timer(storage) {
+        app.last_error_message = Some(e);
+    }
+    // Pause for a locked screen or sleep, resume on return
+    if let Err(e) = app.poll_lock_events(storage) {
+        app.last_error_message = Some(e);
+    }
+    // Check if the file has been modified externally (e.g., by CLI)
+    app.check_and_reload_if_modified(storage);
+    // Commit saved days to git once saves pause, reporting a failed commit
+    if let Some(message) = storage.poll_git_autocommit(std::time::Instant::now()) {
+        app.status_message = Some(message);
+    }
+    // Remind of a meeting entered in advance with the terminal bell
+    if app.take_upcoming_alert() {
+        let _ = ui::bell::ring(&mut io::stdout());
+    }
+}
+
+fn handle_paste_event(app: &mut AppState, text: &str, storage: &mut storage::StorageManager) {
+    match app.handle_paste(text) {
+        Ok(true) => {
+            app.save_day(storage);
+        }
+        Ok(false) => {}
+        Err(e) => app.last_error_message = Some(e),
+    }
+}
+
 fn handle_key_event(app: &mut AppState, key: KeyEvent, storage: &mut storage::StorageManager) {
     // Clear any previous error messages on new key press
     app.clear_error();
+    // A shown URL is dismissed by the next key
+    if app.url_notice.take().is_some() {
+        return;
+    }
+
+    // `[keys]` overrides take precedence over the built-in Browse keys below
+    if matches!(app.mode, ui::AppMode::Browse)
+        && let Some(binding) = key_binding(&key)
+    {
+        match app.keymap.resolve(binding) {
+            Resolved::Action(CommandAction::StartTimer) => return toggle_session(app, storage),
+            Resolved::Action(action) => return execute_command_action(app, action, storage),
+            Resolved::QuickTask(index) => {
+                if let Err(e) = app.start_quick_task(index, storage) {
+                    app.last_error_message = Some(format!("Failed to start timer: {}", e));
+                }
+                return;
+            }
+            Resolved::Unbound => return,
+            Resolved::BuiltIn => {}
+        }
+    }
 
     match app.mode {
         ui::AppMode::Browse => match key.code {
             KeyCode::Char('q') => app.should_quit = true,
             KeyCode::Char('?') => app.open_command_palette(),
             KeyCode::Char('C') => app.open_calendar(),
-            KeyCode::Char('T') if app.config.has_integrations() => app.open_ticket_in_browser(),
+            KeyCode::Char('G') => {
+                if let Err(e) = app.open_recent_days(storage) {
+                    app.last_error_message = Some(format!("Failed to list days: {}", e));
+                }
+            }
+            KeyCode::Char('T')
+                if app.config.has_integrations() || app.selected_record_has_url() =>
+            {
+                app.open_ticket_in_browser()
+            }
+            KeyCode::Char('U') => app.open_link_prompt(),
+            KeyCode::Char('N') => app.open_annotation_prompt(),
+            KeyCode::Char('E') => app.open_estimate_prompt(),
+            KeyCode::Char('K') => app.open_adjustment_prompt(),
+            KeyCode::Char('F') => app.open_fields_editor(),
+            KeyCode::Char('X') => app.open_clear_day(),
+            KeyCode::Char('D') => app.open_shift_prompt(),
+            KeyCode::Char('Y') => app.open_save_template(),
+            KeyCode::Char('I') => {
+                execute_command_action(app, CommandAction::InsertTemplate, storage)
+            }
             KeyCode::Char('L') if app.config.has_integrations() => app.open_worklog_in_browser(),
             // Timer keybindings
-            KeyCode::Char('S') => {
-                // Start/Stop toggle - Start if no timer active, Stop if timer is running
-                if let Some(timer) = app.get_timer_status() {
-                    use crate::timer::TimerStatus;
-                    if matches!(timer.status, TimerStatus::Running | TimerStatus::Paused) {
-                        if let Err(e) = app.stop_active_timer(storage) {
-                            app.last_error_message = Some(e);
-                        }
-                    } else if let Err(e) = app.start_timer_for_selected(storage) {
-                        app.last_error_message = Some(e);
-                    }
-                } else if let Err(e) = app.start_timer_for_selected(storage) {
+            KeyCode::Char('S') => toggle_session(app, storage),
+            KeyCode::Char('w') => app.open_switch_session(),
+            KeyCode::Char('J') => app.open_session_note_prompt(),
+            KeyCode::Char('A') => {
+                if let Err(e) = app.start_timer_for_last_task(storage) {
+                    app.last_error_message = Some(e);
+                }
+            }
+            KeyCode::Char('B') => {
+                if let Err(e) = app.backfill_timer_for_selected(storage) {
                     app.last_error_message = Some(e);
                 }
             }
@@ -168,38 +406,48 @@ fn handle_key_event(app: &mut AppState, key: KeyEvent, storage: &mut storage::St
             KeyCode::Char('c') => app.change_task_name(),
             KeyCode::Char('n') => {
                 app.add_new_record();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('b') => {
                 app.add_break();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('d') => {
                 app.delete_selected_record();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('v') => app.enter_visual_mode(),
+            KeyCode::Char('g') => app.open_goto_id(),
+            KeyCode::Char('R') => app.toggle_relative_times(),
+            KeyCode::Char('M') => app.mark_day_planned(),
+            KeyCode::Char('O') => app.toggle_plan_audit(),
+            KeyCode::Tab => app.cycle_totals_window(),
+            KeyCode::Char('H') => app.open_revert_field(),
+            KeyCode::Char('z') => app.toggle_selected_project_collapsed(),
+            KeyCode::Char('f') => app.focus_summary(),
+            KeyCode::Esc if app.record_filter.is_some() => app.clear_record_filter(),
+            KeyCode::Char('m') => execute_command_action(app, CommandAction::ToggleFlag, storage),
+            KeyCode::Char('$') => {
+                execute_command_action(app, CommandAction::ToggleBillable, storage)
+            }
+            KeyCode::Char('}') => app.jump_to_flag(true),
+            KeyCode::Char('{') => app.jump_to_flag(false),
+            KeyCode::Char(')') => app.jump_to_longest(true),
+            KeyCode::Char('(') => app.jump_to_longest(false),
             KeyCode::Char('t') => {
                 app.set_current_time_on_field();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('u') => {
                 app.undo();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('r') => {
                 app.redo();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('s') => {
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Char('[') => app.navigate_to_previous_day(),
             KeyCode::Char(']') => app.navigate_to_next_day(),
@@ -208,11 +456,10 @@ fn handle_key_event(app: &mut AppState, key: KeyEvent, storage: &mut storage::St
         ui::AppMode::Edit => match key.code {
             KeyCode::Esc => app.exit_edit_mode(),
             KeyCode::Tab => app.next_field(),
-            KeyCode::Enter => {
-                let _ = app.save_edit();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
-            }
+            KeyCode::Enter => match app.save_edit() {
+                Ok(()) => app.save_day(storage),
+                Err(e) => app.last_error_message = Some(e),
+            },
             KeyCode::Backspace => app.handle_backspace(),
             KeyCode::Char(c) => app.handle_char_input(c),
             _ => {}
@@ -223,9 +470,33 @@ fn handle_key_event(app: &mut AppState, key: KeyEvent, storage: &mut storage::St
             KeyCode::Down | KeyCode::Char('j') => app.move_selection_down(),
             KeyCode::Char('d') => {
                 app.delete_visual_selection();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
+            }
+            KeyCode::Char('$') => {
+                app.toggle_billable();
+                app.save_day(storage);
+            }
+            KeyCode::Char('J') => app.open_join_prompt(),
+            _ => {}
+        },
+        ui::AppMode::JoinPrompt => match key.code {
+            KeyCode::Esc => app.close_join_prompt(),
+            KeyCode::Enter => {
+                app.confirm_join_prompt();
+                app.save_day(storage);
             }
+            KeyCode::Backspace => app.handle_join_prompt_backspace(),
+            KeyCode::Char(c) => app.handle_join_prompt_char(c),
+            _ => {}
+        },
+        ui::AppMode::ShiftPrompt => match key.code {
+            KeyCode::Esc => app.close_shift_prompt(),
+            KeyCode::Enter => {
+                app.confirm_shift_prompt();
+                app.save_day(storage);
+            }
+            KeyCode::Backspace => app.handle_shift_prompt_backspace(),
+            KeyCode::Char(c) => app.handle_shift_prompt_char(c),
             _ => {}
         },
         ui::AppMode::CommandPalette => match key.code {
@@ -257,6 +528,17 @@ fn handle_key_event(app: &mut AppState, key: KeyEvent, storage: &mut storage::St
             KeyCode::Char('>') | KeyCode::Char('.') | KeyCode::Char(']') => {
                 app.calendar_next_month()
             }
+            KeyCode::Char('w') => {
+                if let Err(e) = app.open_week_grid(storage) {
+                    app.last_error_message = Some(format!("Failed to load the week: {}", e));
+                }
+            }
+            _ => {}
+        },
+        ui::AppMode::WeekGrid => match key.code {
+            KeyCode::Esc | KeyCode::Char('w') => app.close_week_grid(),
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_week_grid_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_week_grid_down(),
             _ => {}
         },
         ui::AppMode::TaskPicker => match key.code {
@@ -266,25 +548,230 @@ fn handle_key_event(app: &mut AppState, key: KeyEvent, storage: &mut storage::St
                 let filtered_tasks = app.get_filtered_task_names();
                 app.move_task_picker_down(filtered_tasks.len());
             }
+            KeyCode::Enter if app.task_picker_switch => {
+                if let Err(e) = app.confirm_switch_session(storage) {
+                    app.last_error_message = Some(format!("Failed to switch session: {}", e));
+                }
+            }
             KeyCode::Enter => {
                 app.select_task_from_picker();
-                let _ = storage.save(&app.day_data);
-                app.last_file_modified = storage.get_last_modified(&app.current_date);
+                app.save_day(storage);
             }
             KeyCode::Backspace => app.handle_task_picker_backspace(),
             KeyCode::Char(c) => app.handle_task_picker_char(c),
             _ => {}
         },
+        ui::AppMode::TemplatePicker => match key.code {
+            KeyCode::Esc => app.close_template_picker(),
+            KeyCode::Up => app.move_template_picker_up(),
+            KeyCode::Down => app.move_template_picker_down(),
+            KeyCode::Enter => {
+                app.insert_selected_template();
+                app.save_day(storage);
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.rename_selected_template()
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Err(e) = app.delete_selected_template(storage) {
+                    app.last_error_message = Some(format!("Failed to delete template: {}", e));
+                }
+            }
+            KeyCode::Backspace => app.handle_template_picker_backspace(),
+            KeyCode::Char(c) => app.handle_template_picker_char(c),
+            _ => {}
+        },
+        ui::AppMode::TemplateNamePrompt => match key.code {
+            KeyCode::Esc => app.close_template_name_prompt(),
+            KeyCode::Enter => {
+                if let Err(e) = app.confirm_template_name(storage) {
+                    app.last_error_message = Some(e);
+                }
+            }
+            KeyCode::Backspace => app.handle_template_name_backspace(),
+            KeyCode::Char(c) => app.handle_template_name_char(c),
+            _ => {}
+        },
+        ui::AppMode::GotoId => match key.code {
+            KeyCode::Esc => app.close_goto_id(),
+            KeyCode::Enter => {
+                if let Err(e) = app.confirm_goto_id() {
+                    app.last_error_message = Some(e);
+                }
+            }
+            KeyCode::Backspace => app.handle_goto_id_backspace(),
+            // `g a`: jump to the record the running session is attached to
+            KeyCode::Char('a') => {
+                if let Err(e) = app.goto_timer_record() {
+                    app.last_error_message = Some(e);
+                }
+            }
+            KeyCode::Char(c) => app.handle_goto_id_char(c),
+            _ => {}
+        },
+        ui::AppMode::Summary => match key.code {
+            KeyCode::Esc | KeyCode::Char('f') => app.close_summary(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_summary_selection_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.move_summary_selection_down(),
+            KeyCode::Enter => app.apply_summary_filter(),
+            _ => {}
+        },
+        ui::AppMode::RecentDays => match key.code {
+            KeyCode::Esc => app.close_recent_days(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_recent_days_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.move_recent_days_down(),
+            KeyCode::Enter => app.confirm_recent_day(),
+            _ => {}
+        },
+        ui::AppMode::RevertField => match key.code {
+            KeyCode::Esc => app.close_revert_field(),
+            KeyCode::Up | KeyCode::Char('k') => app.move_revert_selection_up(),
+            KeyCode::Down | KeyCode::Char('j') => app.move_revert_selection_down(),
+            KeyCode::Enter => match app.confirm_revert_field() {
+                Ok(()) => app.save_day(storage),
+                Err(e) => app.last_error_message = Some(e),
+            },
+            _ => {}
+        },
+        ui::AppMode::Fields => match key.code {
+            KeyCode::Esc => app.close_fields_editor(),
+            KeyCode::Enter => match app.confirm_fields_editor() {
+                Ok(()) => app.save_day(storage),
+                Err(e) => app.last_error_message = Some(e),
+            },
+            KeyCode::Up => app.move_field_selection_up(),
+            KeyCode::Down | KeyCode::Tab => app.move_field_selection_down(),
+            KeyCode::Left => app.cycle_field_choice(false),
+            KeyCode::Right => app.cycle_field_choice(true),
+            KeyCode::Backspace => app.handle_fields_backspace(),
+            KeyCode::Char(c) => app.handle_fields_char(c),
+            _ => {}
+        },
+        // A record changed here and on disk; other keys wait for an answer
+        ui::AppMode::MergeConflict => {
+            use models::Resolution;
+
+            let resolution = match key.code {
+                KeyCode::Char('m') => Resolution::KeepMine,
+                KeyCode::Char('d') => Resolution::TakeDisk,
+                KeyCode::Char('b') => Resolution::KeepBoth,
+                _ => return,
+            };
+            app.resolve_merge_conflict(resolution);
+            if app.merge_conflicts.is_empty() {
+                app.save_day(storage);
+            }
+        }
+        // Only `y` moves the next record; any other key leaves it
+        ui::AppMode::ConfirmCascade => {
+            app.confirm_cascade(key.code == KeyCode::Char('y'));
+            app.save_day(storage);
+        }
+        // Only `y` clears; any other key backs out
+        ui::AppMode::ConfirmClear => match key.code {
+            KeyCode::Char('y') => {
+                app.confirm_clear_day();
+                app.save_day(storage);
+            }
+            _ => app.close_clear_day(),
+        },
+        ui::AppMode::LinkPrompt => match key.code {
+            KeyCode::Esc => app.close_link_prompt(),
+            KeyCode::Enter => {
+                app.confirm_link_prompt();
+                app.save_day(storage);
+            }
+            KeyCode::Backspace => app.handle_link_prompt_backspace(),
+            KeyCode::Char(c) => app.handle_link_prompt_char(c),
+            _ => {}
+        },
+        ui::AppMode::EstimatePrompt => match key.code {
+            KeyCode::Esc => app.close_estimate_prompt(),
+            KeyCode::Enter => {
+                app.confirm_estimate_prompt();
+                app.save_day(storage);
+            }
+            KeyCode::Backspace => app.handle_estimate_prompt_backspace(),
+            KeyCode::Char(c) => app.handle_estimate_prompt_char(c),
+            _ => {}
+        },
+        ui::AppMode::AdjustmentPrompt => match key.code {
+            KeyCode::Esc => app.close_adjustment_prompt(),
+            KeyCode::Enter => {
+                app.confirm_adjustment_prompt();
+                app.save_day(storage);
+            }
+            KeyCode::Backspace => app.handle_adjustment_prompt_backspace(),
+            KeyCode::Char(c) => app.handle_adjustment_prompt_char(c),
+            _ => {}
+        },
+        ui::AppMode::AnnotationPrompt => match key.code {
+            KeyCode::Esc => app.close_annotation_prompt(),
+            KeyCode::Enter => {
+                app.confirm_annotation_prompt();
+                app.save_day(storage);
+            }
+            KeyCode::Backspace => app.handle_annotation_prompt_backspace(),
+            KeyCode::Char(c) => app.handle_annotation_prompt_char(c),
+            _ => {}
+        },
+        ui::AppMode::SessionNotePrompt => match key.code {
+            KeyCode::Esc => app.close_session_note_prompt(),
+            KeyCode::Enter => {
+                if let Err(e) = app.confirm_session_note(storage) {
+                    app.last_error_message = Some(format!("Failed to add note: {}", e));
+                }
+            }
+            KeyCode::Backspace => app.handle_session_note_backspace(),
+            KeyCode::Char(c) => app.handle_session_note_char(c),
+            _ => {}
+        },
+    }
+}
+
+/// The key press as `[keys]` would write it; Shift is carried by the character itself
+fn key_binding(key: &KeyEvent) -> Option<KeyBinding> {
+    let key_code = match key.code {
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        _ => return None,
+    };
+    Some(KeyBinding {
+        ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+        key: key_code,
+    })
+}
+
+/// Start a session on the selected record, or stop the running or paused one
+fn toggle_session(app: &mut AppState, storage: &mut storage::StorageManager) {
+    if let Err(e) = app.check_timer_day() {
+        app.last_error_message = Some(e);
+    } else if let Some(timer) = app.get_timer_status() {
+        use crate::timer::TimerStatus;
+        if matches!(timer.status, TimerStatus::Running | TimerStatus::Paused) {
+            if let Err(e) = app.stop_active_timer(storage) {
+                app.last_error_message = Some(e);
+            }
+        } else if let Err(e) = app.start_timer_for_selected(storage) {
+            app.last_error_message = Some(e);
+        }
+    } else if let Err(e) = app.start_timer_for_selected(storage) {
+        app.last_error_message = Some(e);
     }
 }
 
 fn execute_command_action(
     app: &mut AppState,
-    action: ui::app_state::CommandAction,
+    action: CommandAction,
     storage: &mut storage::StorageManager,
 ) {
-    use ui::app_state::CommandAction;
-
     match action {
         CommandAction::MoveUp => app.move_selection_up(),
         CommandAction::MoveDown => app.move_selection_down(),
@@ -294,41 +781,93 @@ fn execute_command_action(
         CommandAction::Change => app.change_task_name(),
         CommandAction::New => {
             app.add_new_record();
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::Break => {
             app.add_break();
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::Delete => {
             app.delete_selected_record();
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::Visual => app.enter_visual_mode(),
+        CommandAction::GotoId => app.open_goto_id(),
+        CommandAction::GotoTimerRecord => {
+            if let Err(e) = app.goto_timer_record() {
+                app.last_error_message = Some(e);
+            }
+        }
+        CommandAction::SetLink => app.open_link_prompt(),
+        CommandAction::Annotate => app.open_annotation_prompt(),
+        CommandAction::SetEstimate => app.open_estimate_prompt(),
+        CommandAction::SetAdjustment => app.open_adjustment_prompt(),
+        CommandAction::EditFields => app.open_fields_editor(),
+        CommandAction::RevertField => app.open_revert_field(),
+        CommandAction::ToggleRelativeTimes => app.toggle_relative_times(),
+        CommandAction::MarkPlanned => app.mark_day_planned(),
+        CommandAction::TogglePlanAudit => app.toggle_plan_audit(),
+        CommandAction::CycleTotalsWindow => app.cycle_totals_window(),
+        CommandAction::ToggleProject => app.toggle_selected_project_collapsed(),
+        CommandAction::FocusSummary => app.focus_summary(),
+        CommandAction::ToggleFlag => {
+            app.toggle_flag();
+            if app.config.ui.persist_flags {
+                app.save_day(storage);
+            }
+        }
+        CommandAction::ToggleBillable => {
+            app.toggle_billable();
+            app.save_day(storage);
+        }
+        CommandAction::NextFlag => app.jump_to_flag(true),
+        CommandAction::JumpLongest => app.jump_to_longest(true),
+        CommandAction::JumpShortest => app.jump_to_longest(false),
+        CommandAction::PreviousFlag => app.jump_to_flag(false),
         CommandAction::SetNow => {
             app.set_current_time_on_field();
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::Undo => {
             app.undo();
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::Redo => {
             app.redo();
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::Save => {
-            let _ = storage.save(&app.day_data);
-            app.last_file_modified = storage.get_last_modified(&app.current_date);
+            app.save_day(storage);
         }
         CommandAction::StartTimer => {
-            if let Err(e) = app.start_timer_for_selected(storage) {
+            if let Err(e) = app.check_timer_day() {
+                app.last_error_message = Some(e);
+            } else if let Err(e) = app.start_timer_for_selected(storage) {
+                app.last_error_message = Some(format!("Failed to start timer: {}", e));
+            }
+        }
+        CommandAction::SwitchSession => app.open_switch_session(),
+        CommandAction::NoteSession => app.open_session_note_prompt(),
+        CommandAction::RecentDays => {
+            if let Err(e) = app.open_recent_days(storage) {
+                app.last_error_message = Some(format!("Failed to list days: {}", e));
+            }
+        }
+        CommandAction::ClearDay => app.open_clear_day(),
+        CommandAction::ShiftDay => app.open_shift_prompt(),
+        CommandAction::SaveTemplate => app.open_save_template(),
+        CommandAction::InsertTemplate => {
+            if let Err(e) = app.open_template_picker(storage) {
+                app.last_error_message = Some(format!("Failed to load templates: {}", e));
+            }
+        }
+        CommandAction::ResumeLastTask => {
+            if let Err(e) = app.start_timer_for_last_task(storage) {
+                app.last_error_message = Some(format!("Failed to start timer: {}", e));
+            }
+        }
+        CommandAction::BackfillTimer => {
+            if let Err(e) = app.backfill_timer_for_selected(storage) {
                 app.last_error_message = Some(format!("Failed to start timer: {}", e));
             }
         }
@@ -1,13 +1,109 @@
-use super::WorkRecord;
+use super::plan::{PlanAudit, audit_plan};
+use super::{GroupBy, OffsetChange, TimePoint, WorkRecord};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Mutex, MutexGuard};
 use time::Date;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Label used for records without a project in project rollups
+pub const NO_PROJECT_LABEL: &str = "No project";
+
+/// Summary totals for one project, with the task totals it is made of
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectTotals {
+    pub project: Option<String>,
+    pub total_minutes: u32,
+    pub tasks: Vec<(String, u32)>,
+}
+
+impl ProjectTotals {
+    /// Display name, also the key for collapse state
+    pub fn label(&self) -> &str {
+        self.project.as_deref().unwrap_or(NO_PROJECT_LABEL)
+    }
+}
+
+/// How a day's time splits into work, breaks and the span they cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct DayBreakdown {
+    /// Effective minutes of every non-break record, clamped at zero
+    pub worked_minutes: u32,
+    /// Minutes of break records
+    pub break_minutes: u32,
+    /// Latest end minus earliest start over timed records
+    pub span_minutes: u32,
+}
+
+/// Records of a day file left out on load, past `[storage] max_records`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Truncated {
+    pub kept: usize,
+    pub total: usize,
+}
+
+/// Record ids in (start, id) order, kept up by `add_record` and `remove_record`
+///
+/// Records changed straight through `work_records` are caught when the order is
+/// read: it is only used while it names every record once, still in order.
+#[derive(Default)]
+struct SortedIds(Mutex<SortedState>);
+
+#[derive(Debug, Clone, Default)]
+struct SortedState {
+    ids: Vec<u32>,
+    /// Full sorts done, to tell reuse from re-sorting
+    sorts: usize,
+}
+
+impl SortedIds {
+    fn lock(&self) -> MutexGuard<'_, SortedState> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clone for SortedIds {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.lock().clone()))
+    }
+}
+
+impl PartialEq for SortedIds {
+    /// A cache; two days with the same records are equal whatever it holds
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Debug for SortedIds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SortedIds")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DayData {
     pub date: Date,
     pub last_id: u32,
     pub work_records: HashMap<u32, WorkRecord>,
+    /// Short notes pinned to task groups in the summary, keyed by task name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+    /// Records as they stood when the day was marked as planned, to audit against
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub planned: Vec<WorkRecord>,
+    /// Records flagged with `m`, kept here only with `[ui] persist_flags`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub flagged: BTreeSet<u32>,
+    /// Fields this version doesn't know, written back as they were read
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
+    /// Set when only part of the file's records were loaded; such a day isn't saved
+    #[serde(skip)]
+    pub truncated: Option<Truncated>,
+    #[serde(skip)]
+    sorted: SortedIds,
 }
 
 impl DayData {
@@ -16,6 +112,12 @@ impl DayData {
             date,
             last_id: 0,
             work_records: HashMap::new(),
+            annotations: BTreeMap::new(),
+            planned: Vec::new(),
+            flagged: BTreeSet::new(),
+            unknown: serde_json::Map::new(),
+            truncated: None,
+            sorted: SortedIds::default(),
         }
     }
 
@@ -23,32 +125,412 @@ impl DayData {
         if record.id > self.last_id {
             self.last_id = record.id;
         }
+        let key = (record.start, record.id);
         self.work_records.insert(record.id, record);
+
+        let records = &self.work_records;
+        let mut sorted = self.sorted.lock();
+        sorted.ids.retain(|&id| id != key.1);
+        let at = sorted.ids.partition_point(|id| {
+            records
+                .get(id)
+                .is_none_or(|record| (record.start, record.id) < key)
+        });
+        sorted.ids.insert(at, key.1);
     }
 
     pub fn remove_record(&mut self, id: u32) -> Option<WorkRecord> {
+        self.flagged.remove(&id);
+        self.sorted.lock().ids.retain(|&sorted| sorted != id);
         self.work_records.remove(&id)
     }
 
+    /// Remove every record and annotation, keeping the date and `last_id`
+    ///
+    /// Ids are not reused, so a session still linked to a cleared record can't end
+    /// up updating a new one.
+    pub fn clear_records(&mut self) {
+        self.work_records.clear();
+        self.sorted.lock().ids.clear();
+        self.annotations.clear();
+        self.flagged.clear();
+    }
+
+    /// Note pinned to the task group named `task`
+    pub fn annotation(&self, task: &str) -> Option<&str> {
+        self.annotations.get(task).map(String::as_str)
+    }
+
+    /// Pin `note` to the task group named `task`; a blank note removes it
+    pub fn set_annotation(&mut self, task: &str, note: &str) {
+        let note = note.trim();
+        if note.is_empty() {
+            self.annotations.remove(task);
+        } else {
+            self.annotations.insert(task.to_string(), note.to_string());
+        }
+    }
+
+    /// Keep the current records as the day's plan, replacing any earlier one
+    pub fn mark_planned(&mut self) {
+        self.planned = self.get_sorted_records().into_iter().cloned().collect();
+    }
+
+    /// Records compared with the plan, if the day has one
+    pub fn plan_audit(&self) -> Option<PlanAudit> {
+        if self.planned.is_empty() {
+            return None;
+        }
+        Some(audit_plan(&self.planned, &self.get_sorted_records()))
+    }
+
     pub fn next_id(&mut self) -> u32 {
         self.last_id += 1;
         self.last_id
     }
 
+    /// Recompute durations for a day with clock changes listed in `changes`
+    ///
+    /// Days without a matching change are left untouched, so hand-edited totals survive.
+    pub fn apply_offset_changes(&mut self, changes: &[OffsetChange]) {
+        if !changes.iter().any(|change| change.applies_to(self.date)) {
+            return;
+        }
+        for record in self.work_records.values_mut() {
+            record.update_duration_on(self.date, changes);
+        }
+    }
+
+    /// Records by start time (then id), from the kept order while it still holds
     pub fn get_sorted_records(&self) -> Vec<&WorkRecord> {
+        let mut sorted = self.sorted.lock();
+        if let Some(records) = self.in_order(&sorted.ids) {
+            return records;
+        }
+
         let mut records: Vec<&WorkRecord> = self.work_records.values().collect();
-        records.sort_by_key(|r| r.start);
+        records.sort_by_key(|r| (r.start, r.id));
+        sorted.ids = records.iter().map(|r| r.id).collect();
+        sorted.sorts += 1;
+        records
+    }
+
+    /// The records `ids` name, if that is every record once and in order
+    fn in_order(&self, ids: &[u32]) -> Option<Vec<&WorkRecord>> {
+        if ids.len() != self.work_records.len() {
+            return None;
+        }
+        let records = ids
+            .iter()
+            .map(|id| self.work_records.get(id))
+            .collect::<Option<Vec<&WorkRecord>>>()?;
         records
+            .windows(2)
+            .all(|pair| (pair[0].start, pair[0].id) < (pair[1].start, pair[1].id))
+            .then_some(records)
+    }
+
+    /// Full sorts `get_sorted_records` has done so far
+    #[cfg(test)]
+    fn sorts(&self) -> usize {
+        self.sorted.lock().sorts
+    }
+
+    /// Sum of effective minutes (tracked time plus adjustments), possibly negative
+    fn raw_total_minutes(&self) -> i64 {
+        self.work_records
+            .values()
+            .map(|record| record.effective_minutes())
+            .sum()
+    }
+
+    /// Billable part of the day's total among records matching `filter`, clamped at zero
+    pub fn billable_minutes_where(
+        &self,
+        default: bool,
+        filter: impl Fn(&WorkRecord) -> bool,
+    ) -> u32 {
+        let minutes: i64 = self
+            .work_records
+            .values()
+            .filter(|record| record.is_billable(default) && filter(record))
+            .map(|record| record.effective_minutes())
+            .sum();
+        minutes.clamp(0, u32::MAX as i64) as u32
+    }
+
+    /// Billable part of `total_minutes`
+    pub fn billable_minutes(&self, default: bool) -> u32 {
+        self.billable_minutes_where(default, |_| true)
+    }
+
+    /// Whether any record is non-billable, i.e. the day's totals need splitting
+    pub fn has_non_billable(&self, default: bool) -> bool {
+        self.work_records
+            .values()
+            .any(|record| !record.is_billable(default))
+    }
+
+    /// Grand total for the day, clamped at zero when adjustments outweigh tracked time
+    pub fn total_minutes(&self) -> u32 {
+        self.raw_total_minutes().clamp(0, u32::MAX as i64) as u32
+    }
+
+    /// Whether `total_minutes` had to be clamped because adjustments went below zero
+    pub fn is_total_clamped(&self) -> bool {
+        self.raw_total_minutes() < 0
+    }
+
+    /// Net worked time, break time and the day's span
+    ///
+    /// Adjustment-only records (start equal to end) count towards worked time but
+    /// do not widen the span.
+    pub fn breakdown(&self) -> DayBreakdown {
+        let mut worked: i64 = 0;
+        let mut break_minutes = 0;
+        for record in self.work_records.values() {
+            if record.is_break() {
+                break_minutes += record.total_minutes;
+            } else {
+                worked += record.effective_minutes();
+            }
+        }
+
+        let timed = self
+            .work_records
+            .values()
+            .filter(|record| record.start != record.end);
+        let earliest = timed.clone().map(|record| record.start).min();
+        let latest = timed.map(|record| record.end).max();
+        let span_minutes = match (earliest, latest) {
+            (Some(start), Some(end)) => WorkRecord::calculate_duration(&start, &end),
+            _ => 0,
+        };
+
+        DayBreakdown {
+            worked_minutes: worked.clamp(0, u32::MAX as i64) as u32,
+            break_minutes,
+            span_minutes,
+        }
+    }
+
+    /// First record other than `id` that starts at `end`, i.e. ran on right after it
+    pub fn contiguous_after(&self, id: u32, end: TimePoint) -> Option<u32> {
+        self.get_sorted_records()
+            .into_iter()
+            .find(|record| record.id != id && record.start == end)
+            .map(|record| record.id)
+    }
+
+    /// The work record that ended last, for resuming the most recent task
+    ///
+    /// Breaks and adjustment-only records are skipped. Records ending at the same
+    /// time go to the higher ID, the one added later.
+    pub fn most_recent_record(&self) -> Option<&WorkRecord> {
+        self.work_records
+            .values()
+            .filter(|record| !record.is_break() && record.start != record.end)
+            .max_by_key(|record| (record.end, record.id))
+    }
+
+    /// ID of the record with the longest duration; ties go to the earlier start
+    pub fn longest_record(&self) -> Option<u32> {
+        self.work_records
+            .values()
+            .min_by_key(|record| {
+                (
+                    std::cmp::Reverse(record.total_minutes),
+                    record.start,
+                    record.id,
+                )
+            })
+            .map(|record| record.id)
+    }
+
+    /// ID of the record with the shortest duration; ties go to the earlier start
+    pub fn shortest_record(&self) -> Option<u32> {
+        self.work_records
+            .values()
+            .min_by_key(|record| (record.total_minutes, record.start, record.id))
+            .map(|record| record.id)
+    }
+
+    /// Start of the untracked gap ending at `now`, if it lasts `min_minutes` to `max_minutes`
+    ///
+    /// The gap starts where the last record ending by `now` ends. There is none while a
+    /// record is still running at `now` or before any record. In-progress records
+    /// (with no real end yet) and records running past midnight are ignored.
+    pub fn gap_before(
+        &self,
+        now: TimePoint,
+        min_minutes: u32,
+        max_minutes: u32,
+    ) -> Option<TimePoint> {
+        let finished = || {
+            self.work_records
+                .values()
+                .filter(|record| !record.in_progress && record.start <= record.end)
+        };
+        if finished().any(|record| record.start < now && now < record.end) {
+            return None;
+        }
+        let last_end = finished()
+            .map(|record| record.end)
+            .filter(|end| *end <= now)
+            .max()?;
+        let gap = WorkRecord::calculate_duration(&last_end, &now);
+        (gap > 0 && (min_minutes..=max_minutes).contains(&gap)).then_some(last_end)
+    }
+
+    /// Add a "Break" record over `gap_before(now, ..)`, returning its id if one was added
+    pub fn fill_gap_with_break(
+        &mut self,
+        now: TimePoint,
+        min_minutes: u32,
+        max_minutes: u32,
+    ) -> Option<u32> {
+        let start = self.gap_before(now, min_minutes, max_minutes)?;
+        let id = self.next_id();
+        self.add_record(WorkRecord::new(id, "Break".to_string(), start, now));
+        Some(id)
+    }
+
+    /// Start and end of one record covering all of `ids`
+    ///
+    /// The records must follow one another without a gap; overlaps are fine. Fewer
+    /// than two records, unknown ids and records running past midnight are refused.
+    pub fn merged_span(&self, ids: &[u32]) -> Result<(TimePoint, TimePoint), String> {
+        let records = self.records_in_time_order(ids)?;
+        let start = records[0].start;
+        let mut end = records[0].end;
+        for record in &records[1..] {
+            if record.start > end {
+                return Err(format!(
+                    "Records must be contiguous, nothing between {} and {}",
+                    end, record.start
+                ));
+            }
+            end = end.max(record.end);
+        }
+        Ok((start, end))
+    }
+
+    /// Replace `ids` with one record named `name` over their `merged_span`, returning its id
+    ///
+    /// The earliest record is kept, with its other fields, and the rest removed.
+    /// Descriptions are joined one per line in time order, skipping empty ones.
+    pub fn merge_records(&mut self, ids: &[u32], name: &str) -> Result<u32, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Task name cannot be empty".to_string());
+        }
+        let (start, end) = self.merged_span(ids)?;
+        let records = self.records_in_time_order(ids)?;
+        let id = records[0].id;
+        let description = records
+            .iter()
+            .map(|record| record.description.trim())
+            .filter(|description| !description.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let others: Vec<u32> = records[1..].iter().map(|record| record.id).collect();
+
+        for other in others {
+            self.remove_record(other);
+        }
+        let record = self
+            .work_records
+            .get_mut(&id)
+            .expect("the earliest record is kept");
+        record.name = name.to_string();
+        record.start = start;
+        record.end = end;
+        record.description = description;
+        record.update_duration();
+        Ok(id)
+    }
+
+    fn records_in_time_order(&self, ids: &[u32]) -> Result<Vec<&WorkRecord>, String> {
+        if ids.len() < 2 {
+            return Err("Select at least two records to join".to_string());
+        }
+        let mut records = ids
+            .iter()
+            .map(|id| {
+                self.work_records
+                    .get(id)
+                    .ok_or_else(|| format!("No record #{}", id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if records.iter().any(|record| record.end < record.start) {
+            return Err("Records running past midnight can't be joined".to_string());
+        }
+        records.sort_by_key(|record| (record.start, record.id));
+        Ok(records)
+    }
+
+    /// Whether any record on this day is assigned to a project
+    pub fn has_projects(&self) -> bool {
+        self.work_records
+            .values()
+            .any(|record| record.project.is_some())
+    }
+
+    /// Task totals grouped under their project; a project's total is the sum of its tasks
+    ///
+    /// Projects are ordered by total (descending) then name, with unassigned tasks last.
+    pub fn get_project_totals(&self) -> Vec<ProjectTotals> {
+        let mut by_project: HashMap<Option<String>, HashMap<String, i64>> = HashMap::new();
+        for record in self.work_records.values() {
+            *by_project
+                .entry(record.project.clone())
+                .or_default()
+                .entry(record.name.clone())
+                .or_insert(0) += record.effective_minutes();
+        }
+
+        let mut result: Vec<ProjectTotals> = by_project
+            .into_iter()
+            .map(|(project, tasks)| {
+                let mut tasks: Vec<(String, u32)> = tasks
+                    .into_iter()
+                    .map(|(name, minutes)| (name, minutes.clamp(0, u32::MAX as i64) as u32))
+                    .collect();
+                tasks.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                ProjectTotals {
+                    project,
+                    total_minutes: tasks.iter().map(|(_, minutes)| minutes).sum(),
+                    tasks,
+                }
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            a.project
+                .is_none()
+                .cmp(&b.project.is_none())
+                .then_with(|| b.total_minutes.cmp(&a.total_minutes))
+                .then_with(|| a.project.cmp(&b.project))
+        });
+        result
     }
 
     pub fn get_grouped_totals(&self) -> Vec<(String, u32)> {
-        let mut totals: HashMap<String, u32> = HashMap::new();
+        self.get_grouped_totals_by(GroupBy::Name)
+    }
+
+    /// Totals per `WorkRecord::group_key`, so name variants of one ticket add up
+    pub fn get_grouped_totals_by(&self, by: GroupBy) -> Vec<(String, u32)> {
+        let mut totals: HashMap<String, i64> = HashMap::new();
 
         for record in self.work_records.values() {
-            *totals.entry(record.name.clone()).or_insert(0) += record.total_minutes;
+            *totals.entry(record.group_key(by)).or_insert(0) += record.effective_minutes();
         }
 
-        let mut result: Vec<(String, u32)> = totals.into_iter().collect();
+        // A task whose adjustments exceed its tracked time counts as zero
+        let mut result: Vec<(String, u32)> = totals
+            .into_iter()
+            .map(|(name, minutes)| (name, minutes.clamp(0, u32::MAX as i64) as u32))
+            .collect();
         // Sort by duration (descending), then by task name (ascending) for stable ordering
         result.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
         result
@@ -70,6 +552,330 @@ mod tests {
         WorkRecord::new(id, name.to_string(), start, end)
     }
 
+    /// A day of `count` one-minute records from 00:01, ids out of start order
+    fn large_day(count: u32) -> DayData {
+        let mut day = DayData::new(create_test_date());
+        for id in 1..=count {
+            let minute = 1 + (id * 7919) % 1438;
+            day.add_record(WorkRecord::new(
+                id,
+                format!("Import {}", id),
+                TimePoint::from_minutes_since_midnight(minute).unwrap(),
+                TimePoint::from_minutes_since_midnight(minute + 1).unwrap(),
+            ));
+        }
+        day
+    }
+
+    fn assert_sorted(day: &DayData) {
+        let records = day.get_sorted_records();
+        assert_eq!(records.len(), day.work_records.len());
+        assert!(
+            records
+                .windows(2)
+                .all(|pair| (pair[0].start, pair[0].id) < (pair[1].start, pair[1].id))
+        );
+    }
+
+    #[test]
+    fn test_sorted_records_are_reused_on_a_large_day() {
+        let day = large_day(5000);
+        assert_sorted(&day);
+        for _ in 0..200 {
+            assert_eq!(day.get_sorted_records().len(), 5000);
+        }
+        // Records added one by one were kept in order; nothing was sorted
+        assert_eq!(day.sorts(), 0);
+
+        // A loaded day is sorted once, then reused
+        let loaded: DayData = serde_json::from_str(&serde_json::to_string(&day).unwrap()).unwrap();
+        assert_sorted(&loaded);
+        loaded.get_sorted_records();
+        assert_eq!(loaded.sorts(), 1);
+    }
+
+    #[test]
+    fn test_sorted_records_follow_add_and_remove() {
+        let mut day = large_day(5000);
+        day.get_sorted_records();
+
+        day.add_record(create_test_record(6000, "Early", 0, 1));
+        let records = day.get_sorted_records();
+        assert_eq!(records[0].id, 6000);
+        assert_eq!(records.len(), 5001);
+        // Replacing a record moves it
+        let mut moved = day.work_records[&6000].clone();
+        moved.start = TimePoint::new(23, 59).unwrap();
+        moved.end = moved.start;
+        day.add_record(moved);
+        assert_eq!(day.get_sorted_records().last().unwrap().id, 6000);
+
+        day.remove_record(6000);
+        assert_sorted(&day);
+        assert!(day.get_sorted_records().iter().all(|r| r.id != 6000));
+        assert_eq!(day.sorts(), 0);
+
+        day.clear_records();
+        assert!(day.get_sorted_records().is_empty());
+    }
+
+    #[test]
+    fn test_sorted_records_notice_changes_made_in_place() {
+        let mut day = large_day(50);
+        let first = day.get_sorted_records()[0].id;
+        day.work_records.get_mut(&first).unwrap().start = TimePoint::new(23, 59).unwrap();
+        assert_eq!(day.get_sorted_records().last().unwrap().id, first);
+        assert_eq!(day.sorts(), 1);
+
+        day.work_records
+            .insert(999, create_test_record(999, "Direct", 0, 1));
+        assert_eq!(day.get_sorted_records()[0].id, 999);
+        assert_eq!(day.sorts(), 2);
+    }
+
+    #[test]
+    fn test_annotations_round_trip_through_json() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Code review", 9, 11));
+        day.set_annotation("Code review", "  over estimate ");
+
+        let json = serde_json::to_string(&day).unwrap();
+        let loaded: DayData = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, day);
+        assert_eq!(loaded.annotation("Code review"), Some("over estimate"));
+    }
+
+    #[test]
+    fn test_day_without_annotations_omits_and_defaults_them() {
+        let day = DayData::new(create_test_date());
+        let json = serde_json::to_string(&day).unwrap();
+        assert!(!json.contains("annotations"));
+
+        // Files written before annotations existed load with none
+        let loaded: DayData = serde_json::from_str(&json).unwrap();
+        assert!(loaded.annotations.is_empty());
+    }
+
+    #[test]
+    fn test_mark_planned_snapshots_records() {
+        let mut day = DayData::new(create_test_date());
+        assert!(day.plan_audit().is_none());
+        day.add_record(create_test_record(1, "Standup", 9, 10));
+        day.add_record(create_test_record(2, "Design review", 10, 11));
+        day.mark_planned();
+        assert!(
+            !serde_json::to_string(&DayData::new(create_test_date()))
+                .unwrap()
+                .contains("planned")
+        );
+
+        // Reality: standup ran over, the review never happened
+        day.work_records.get_mut(&1).unwrap().end = TimePoint::new(10, 30).unwrap();
+        day.work_records.get_mut(&1).unwrap().update_duration();
+        day.remove_record(2);
+        day.add_record(create_test_record(3, "Support", 11, 12));
+
+        let json = serde_json::to_string(&day).unwrap();
+        let loaded: DayData = serde_json::from_str(&json).unwrap();
+        let audit = loaded.plan_audit().unwrap();
+        assert_eq!(audit.describe(1), "ran 30m over");
+        assert_eq!(audit.describe(3), "unplanned");
+        assert_eq!(audit.missed[0].name, "Design review");
+        assert_eq!((audit.planned_minutes, audit.actual_minutes), (120, 150));
+    }
+
+    #[test]
+    fn test_annotation_lookup_by_task_name() {
+        let mut day = DayData::new(create_test_date());
+        day.set_annotation("Code review", "over estimate");
+        day.set_annotation("Meeting", "ran long");
+
+        assert_eq!(day.annotation("Code review"), Some("over estimate"));
+        assert_eq!(day.annotation("Meeting"), Some("ran long"));
+        assert_eq!(day.annotation("code review"), None);
+
+        day.set_annotation("Meeting", "   ");
+        assert_eq!(day.annotation("Meeting"), None);
+        assert_eq!(day.annotations.len(), 1);
+    }
+
+    #[test]
+    fn test_longest_and_shortest_record() {
+        let mut day = DayData::new(create_test_date());
+        assert_eq!(day.longest_record(), None);
+        assert_eq!(day.shortest_record(), None);
+
+        day.add_record(create_test_record(1, "Standup", 9, 10));
+        day.add_record(create_test_record(2, "Deep work", 10, 14));
+        day.add_record(create_test_record(3, "Review", 14, 16));
+        assert_eq!(day.longest_record(), Some(2));
+        assert_eq!(day.shortest_record(), Some(1));
+    }
+
+    #[test]
+    fn test_longest_and_shortest_ties_go_to_earlier_start() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Afternoon", 15, 17));
+        day.add_record(create_test_record(2, "Morning", 9, 11));
+        day.add_record(create_test_record(3, "Call", 12, 13));
+        day.add_record(create_test_record(4, "Sync", 11, 12));
+        assert_eq!(day.longest_record(), Some(2));
+        assert_eq!(day.shortest_record(), Some(4));
+    }
+
+    #[test]
+    fn test_most_recent_record_empty() {
+        let day = DayData::new(create_test_date());
+        assert!(day.most_recent_record().is_none());
+    }
+
+    #[test]
+    fn test_most_recent_record_by_latest_end() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Late start", 13, 14));
+        day.add_record(create_test_record(2, "Long meeting", 9, 16));
+        day.add_record(create_test_record(3, "Morning", 8, 9));
+
+        assert_eq!(day.most_recent_record().unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_most_recent_record_tie_goes_to_higher_id() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(4, "Later", 11, 12));
+        day.add_record(create_test_record(2, "Earlier", 10, 12));
+
+        assert_eq!(day.most_recent_record().unwrap().name, "Later");
+    }
+
+    #[test]
+    fn test_most_recent_record_skips_breaks_and_adjustments() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 12));
+        day.add_record(create_test_record(2, "Lunch Break", 12, 13));
+        day.add_record(create_test_record(3, "Adjustment", 15, 15));
+
+        assert_eq!(day.most_recent_record().unwrap().name, "Coding");
+    }
+
+    #[test]
+    fn test_gap_fill_boundaries() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 10));
+
+        // Below the minimum, at both bounds, and past the maximum
+        assert_eq!(day.gap_before(at(10, 4), 5, 120), None);
+        assert_eq!(day.gap_before(at(10, 5), 5, 120), Some(at(10, 0)));
+        assert_eq!(day.gap_before(at(11, 0), 5, 120), Some(at(10, 0)));
+        assert_eq!(day.gap_before(at(12, 0), 5, 120), Some(at(10, 0)));
+        assert_eq!(day.gap_before(at(12, 1), 5, 120), None);
+        // Nothing to follow yet, and no zero-length breaks
+        assert_eq!(day.gap_before(at(8, 30), 0, 120), None);
+        assert_eq!(day.gap_before(at(10, 0), 0, 120), None);
+    }
+
+    #[test]
+    fn test_gap_follows_latest_finished_record() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 10));
+        day.add_record(create_test_record(2, "Review", 10, 11));
+        // Planned for later, not part of the gap
+        day.add_record(create_test_record(3, "Meeting", 14, 15));
+        assert_eq!(day.gap_before(at(11, 30), 5, 120), Some(at(11, 0)));
+
+        // A record still running at now leaves no gap
+        assert_eq!(day.gap_before(at(14, 30), 5, 120), None);
+
+        // An in-progress record has no real end to measure from
+        let mut running = create_test_record(4, "Docs", 11, 11);
+        running.in_progress = true;
+        day.add_record(running);
+        assert_eq!(day.gap_before(at(11, 30), 5, 120), Some(at(11, 0)));
+    }
+
+    #[test]
+    fn test_fill_gap_with_break() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 10));
+
+        assert_eq!(day.fill_gap_with_break(at(10, 3), 5, 120), None);
+        assert_eq!(day.work_records.len(), 1);
+
+        let id = day.fill_gap_with_break(at(10, 40), 5, 120).unwrap();
+        let record = &day.work_records[&id];
+        assert!(record.is_break());
+        assert_eq!((record.start, record.end), (at(10, 0), at(10, 40)));
+        assert_eq!(record.total_minutes, 40);
+
+        // The gap is filled now
+        assert_eq!(day.fill_gap_with_break(at(10, 40), 5, 120), None);
+    }
+
+    #[test]
+    fn test_merged_span_covers_contiguous_records() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 10, 11));
+        day.add_record(create_test_record(2, "Review", 9, 10));
+        day.add_record(create_test_record(3, "Coding", 10, 12));
+        day.add_record(create_test_record(4, "Lunch", 13, 14));
+
+        // Touching and overlapping records join, in any order
+        assert_eq!(day.merged_span(&[3, 1, 2]), Ok((at(9, 0), at(12, 0))));
+        assert_eq!(
+            day.merged_span(&[2, 4]),
+            Err("Records must be contiguous, nothing between 10:00 and 13:00".to_string())
+        );
+        assert!(day.merged_span(&[1]).is_err());
+        assert!(day.merged_span(&[1, 9]).is_err());
+
+        day.add_record(create_test_record(5, "Night", 23, 1));
+        assert!(day.merged_span(&[4, 5]).is_err());
+    }
+
+    #[test]
+    fn test_merge_records_joins_descriptions() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        let mut day = DayData::new(create_test_date());
+        let mut second = create_test_record(1, "Fix bug", 10, 11);
+        second.description = "wrote the test".to_string();
+        let mut first = create_test_record(2, "Investigate", 9, 10);
+        first.description = "found the cause ".to_string();
+        first.project = Some("Core".to_string());
+        day.add_record(second);
+        day.add_record(first);
+        day.add_record(create_test_record(3, "Fix bug", 11, 12));
+
+        let id = day.merge_records(&[1, 2, 3], " Bug 42 ").unwrap();
+        // The earliest record is the one kept
+        assert_eq!(id, 2);
+        assert_eq!(day.work_records.len(), 1);
+        let record = &day.work_records[&2];
+        assert_eq!(record.name, "Bug 42");
+        assert_eq!((record.start, record.end), (at(9, 0), at(12, 0)));
+        assert_eq!(record.total_minutes, 180);
+        assert_eq!(record.description, "found the cause\nwrote the test");
+        assert_eq!(record.project.as_deref(), Some("Core"));
+    }
+
+    #[test]
+    fn test_merge_records_refuses_without_changes() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "A", 9, 10));
+        day.add_record(create_test_record(2, "B", 11, 12));
+        let before = day.clone();
+
+        assert!(day.merge_records(&[1, 2], "A").is_err());
+        assert_eq!(
+            day.merge_records(&[1, 2], "  "),
+            Err("Task name cannot be empty".to_string())
+        );
+        assert_eq!(day.work_records, before.work_records);
+    }
+
     #[test]
     fn test_new_day_data() {
         let date = create_test_date();
@@ -274,6 +1080,45 @@ mod tests {
         assert_eq!(totals[1].1, 60);
     }
 
+    #[test]
+    fn test_grouped_totals_by_ticket_collapse_name_variants() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "PROJ-1 fix", 9, 10));
+        day.add_record(create_test_record(2, "PROJ-1 fix bug", 10, 12));
+        day.add_record(create_test_record(3, "Fixing PROJ-1", 13, 14));
+        day.add_record(create_test_record(4, "PROJ-2 review", 14, 15));
+        day.add_record(create_test_record(5, "Standup", 15, 16));
+
+        assert_eq!(
+            day.get_grouped_totals_by(GroupBy::Ticket),
+            vec![
+                ("PROJ-1".to_string(), 240),
+                ("PROJ-2".to_string(), 60),
+                ("Standup".to_string(), 60),
+            ]
+        );
+        assert_eq!(day.get_grouped_totals_by(GroupBy::Name).len(), 5);
+    }
+
+    #[test]
+    fn test_grouped_totals_by_project_fall_back_to_name() {
+        let mut day = DayData::new(create_test_date());
+        for (id, name, project, start, end) in [
+            (1, "Fix login", Some("Web"), 9, 10),
+            (2, "Fix signup", Some("Web"), 10, 12),
+            (3, "Standup", None, 12, 13),
+        ] {
+            let mut record = create_test_record(id, name, start, end);
+            record.project = project.map(str::to_string);
+            day.add_record(record);
+        }
+
+        assert_eq!(
+            day.get_grouped_totals_by(GroupBy::Project),
+            vec![("Web".to_string(), 180), ("Standup".to_string(), 60)]
+        );
+    }
+
     #[test]
     fn test_get_grouped_totals_sorted_by_duration() {
         let mut day = DayData::new(create_test_date());
@@ -328,4 +1173,210 @@ mod tests {
         assert_eq!(day1.last_id, day2.last_id);
         assert_eq!(day1.work_records.len(), day2.work_records.len());
     }
+
+    #[test]
+    fn test_apply_offset_changes_on_transition_day() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Night", 1, 3));
+        day.add_record(create_test_record(2, "Day", 9, 17));
+
+        let changes = vec![OffsetChange {
+            date: "2025-11-06".to_string(),
+            at: "02:00".to_string(),
+            shift_minutes: 60,
+        }];
+        day.apply_offset_changes(&changes);
+
+        assert_eq!(day.work_records[&1].total_minutes, 60);
+        assert_eq!(day.work_records[&2].total_minutes, 480);
+    }
+
+    #[test]
+    fn test_apply_offset_changes_leaves_other_days_untouched() {
+        let mut day = DayData::new(create_test_date());
+        let mut record = create_test_record(1, "Hand edited", 1, 3);
+        record.total_minutes = 100;
+        day.add_record(record);
+
+        let changes = vec![OffsetChange {
+            date: "2025-03-30".to_string(),
+            at: "02:00".to_string(),
+            shift_minutes: 60,
+        }];
+        day.apply_offset_changes(&changes);
+
+        assert_eq!(day.work_records[&1].total_minutes, 100);
+    }
+
+    #[test]
+    fn test_negative_adjustment_reduces_totals() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 12));
+        let mut correction = create_test_record(2, "Coding", 12, 12);
+        correction.adjustment_minutes = Some(-60);
+        day.add_record(correction);
+
+        assert_eq!(day.total_minutes(), 120);
+        assert!(!day.is_total_clamped());
+        assert_eq!(day.get_grouped_totals(), vec![("Coding".to_string(), 120)]);
+    }
+
+    #[test]
+    fn test_total_clamps_at_zero() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 10));
+        let mut correction = create_test_record(2, "Correction", 10, 10);
+        correction.adjustment_minutes = Some(-180);
+        day.add_record(correction);
+
+        assert_eq!(day.total_minutes(), 0);
+        assert!(day.is_total_clamped());
+
+        let totals = day.get_grouped_totals();
+        assert!(totals.contains(&("Coding".to_string(), 60)));
+        assert!(totals.contains(&("Correction".to_string(), 0)));
+    }
+
+    fn create_project_record(
+        id: u32,
+        name: &str,
+        project: Option<&str>,
+        start_hour: u8,
+        end_hour: u8,
+    ) -> WorkRecord {
+        let mut record = create_test_record(id, name, start_hour, end_hour);
+        record.project = project.map(str::to_string);
+        record
+    }
+
+    #[test]
+    fn test_project_totals_sum_their_tasks() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_project_record(1, "API", Some("Acme"), 8, 10));
+        day.add_record(create_project_record(2, "Review", Some("Acme"), 10, 11));
+        day.add_record(create_project_record(3, "API", Some("Acme"), 11, 12));
+        day.add_record(create_project_record(4, "Design", Some("Globex"), 13, 18));
+        day.add_record(create_project_record(5, "Break", None, 12, 13));
+
+        assert!(day.has_projects());
+        let totals = day.get_project_totals();
+
+        let labels: Vec<&str> = totals.iter().map(|p| p.label()).collect();
+        assert_eq!(labels, vec!["Globex", "Acme", "No project"]);
+
+        for project in &totals {
+            let task_sum: u32 = project.tasks.iter().map(|(_, m)| m).sum();
+            assert_eq!(project.total_minutes, task_sum);
+        }
+
+        let acme = &totals[1];
+        assert_eq!(acme.total_minutes, 240);
+        assert_eq!(
+            acme.tasks,
+            vec![("API".to_string(), 180), ("Review".to_string(), 60)]
+        );
+
+        // The project rollup accounts for the whole day
+        let grand: u32 = totals.iter().map(|p| p.total_minutes).sum();
+        assert_eq!(grand, day.total_minutes());
+    }
+
+    #[test]
+    fn test_project_totals_without_projects() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 12));
+
+        assert!(!day.has_projects());
+        let totals = day.get_project_totals();
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].label(), NO_PROJECT_LABEL);
+        assert_eq!(totals[0].total_minutes, 180);
+    }
+
+    #[test]
+    fn test_breakdown_with_interleaved_breaks() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 8, 10));
+        day.add_record(WorkRecord::new(
+            2,
+            "Break".to_string(),
+            TimePoint::new(10, 0).unwrap(),
+            TimePoint::new(10, 15).unwrap(),
+        ));
+        day.add_record(WorkRecord::new(
+            3,
+            "Review".to_string(),
+            TimePoint::new(10, 15).unwrap(),
+            TimePoint::new(12, 0).unwrap(),
+        ));
+        day.add_record(create_test_record(4, "Lunch break", 12, 13));
+        day.add_record(create_test_record(5, "Coding", 13, 16));
+
+        let breakdown = day.breakdown();
+        assert_eq!(breakdown.worked_minutes, 120 + 105 + 180);
+        assert_eq!(breakdown.break_minutes, 15 + 60);
+        assert_eq!(breakdown.span_minutes, 8 * 60);
+        assert_eq!(
+            breakdown.span_minutes,
+            breakdown.worked_minutes + breakdown.break_minutes
+        );
+    }
+
+    #[test]
+    fn test_breakdown_span_includes_untracked_gaps() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 10));
+        day.add_record(create_test_record(2, "Break", 10, 11));
+        day.add_record(create_test_record(3, "Coding", 14, 15));
+
+        let breakdown = day.breakdown();
+        assert_eq!(breakdown.worked_minutes, 120);
+        assert_eq!(breakdown.break_minutes, 60);
+        assert_eq!(breakdown.span_minutes, 6 * 60);
+    }
+
+    #[test]
+    fn test_breakdown_adjustments_count_as_work_but_not_span() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 12));
+        let evening = TimePoint::new(20, 0).unwrap();
+        let mut credit = WorkRecord::new(2, "Correction".to_string(), evening, evening);
+        credit.adjustment_minutes = Some(-30);
+        day.add_record(credit);
+
+        let breakdown = day.breakdown();
+        assert_eq!(breakdown.worked_minutes, 150);
+        assert_eq!(breakdown.span_minutes, 180);
+        assert_eq!(
+            DayData::new(create_test_date()).breakdown(),
+            DayBreakdown::default()
+        );
+    }
+
+    #[test]
+    fn test_billable_minutes_split_the_total() {
+        let mut day = DayData::new(create_test_date());
+        day.add_record(create_test_record(1, "Coding", 9, 12));
+        let mut review = create_test_record(2, "Review", 12, 13);
+        review.billable = Some(false);
+        day.add_record(review);
+        let mut fix = create_test_record(3, "Coding", 14, 15);
+        fix.adjustment_minutes = Some(-15);
+        day.add_record(fix);
+
+        assert_eq!(day.total_minutes(), 285);
+        assert!(day.has_non_billable(true));
+        assert_eq!(day.billable_minutes(true), 225);
+        assert_eq!(
+            day.billable_minutes_where(true, |record| record.name == "Review"),
+            0
+        );
+        // Only the record marked billable counts when the default is off
+        day.work_records.get_mut(&1).unwrap().billable = Some(true);
+        assert_eq!(day.billable_minutes(false), 180);
+
+        day.work_records.get_mut(&2).unwrap().billable = None;
+        assert!(!day.has_non_billable(true));
+        assert_eq!(day.billable_minutes(true), day.total_minutes());
+    }
 }
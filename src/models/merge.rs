@@ -0,0 +1,263 @@
+//! Three-way merge of a day edited in the TUI and changed on disk meanwhile
+//!
+//! The last saved day is the common base. A record changed on one side only takes
+//! that side's version; a record changed differently on both sides is a conflict
+//! for the user to settle.
+
+use super::{DayData, WorkRecord};
+use std::collections::BTreeSet;
+
+/// How to settle a record changed both in memory and on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    KeepMine,
+    TakeDisk,
+    /// Keep the disk record and add the in-memory one under a new id
+    KeepBoth,
+}
+
+/// A record changed on both sides since the last save; `None` where it was deleted
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordConflict {
+    pub id: u32,
+    pub mine: Option<WorkRecord>,
+    pub disk: Option<WorkRecord>,
+}
+
+impl RecordConflict {
+    /// Settle the conflict in `day`, which holds the disk side of the record
+    pub fn resolve(&self, day: &mut DayData, resolution: Resolution) {
+        match (resolution, &self.mine) {
+            (Resolution::TakeDisk, _) | (Resolution::KeepBoth, None) => {}
+            (Resolution::KeepMine, None) => {
+                day.remove_record(self.id);
+            }
+            (Resolution::KeepMine, Some(mine)) => day.add_record(mine.clone()),
+            (Resolution::KeepBoth, Some(mine)) => {
+                let mut record = mine.clone();
+                if self.disk.is_some() {
+                    record.id = day.next_id();
+                }
+                day.add_record(record);
+            }
+        }
+    }
+}
+
+/// Result of `merge_days`: every one-sided change applied, conflicts on the disk side
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayMerge {
+    pub merged: DayData,
+    pub conflicts: Vec<RecordConflict>,
+}
+
+/// Merge in-memory edits (`mine`) and disk changes (`disk`) made since `base`
+///
+/// Records are matched by id. Annotations are merged the same way, except that a
+/// note changed on both sides keeps the in-memory one rather than asking; so does
+/// the plan.
+pub fn merge_days(base: &DayData, mine: &DayData, disk: &DayData) -> DayMerge {
+    let mut merged = disk.clone();
+    merged.last_id = mine.last_id.max(disk.last_id);
+
+    let ids: BTreeSet<u32> = base
+        .work_records
+        .keys()
+        .chain(mine.work_records.keys())
+        .chain(disk.work_records.keys())
+        .copied()
+        .collect();
+    let mut conflicts = Vec::new();
+    for id in ids {
+        let base_record = base.work_records.get(&id);
+        let mine_record = mine.work_records.get(&id);
+        let disk_record = disk.work_records.get(&id);
+        if mine_record == disk_record || mine_record == base_record {
+            continue;
+        }
+        if disk_record != base_record {
+            conflicts.push(RecordConflict {
+                id,
+                mine: mine_record.cloned(),
+                disk: disk_record.cloned(),
+            });
+            continue;
+        }
+        match mine_record {
+            Some(record) => merged.add_record(record.clone()),
+            None => {
+                merged.remove_record(id);
+            }
+        }
+    }
+
+    let tasks: BTreeSet<&String> = base
+        .annotations
+        .keys()
+        .chain(mine.annotations.keys())
+        .collect();
+    for task in tasks {
+        let mine_note = mine.annotation(task);
+        if mine_note != base.annotation(task) {
+            merged.set_annotation(task, mine_note.unwrap_or_default());
+        }
+    }
+
+    if mine.planned != base.planned {
+        merged.planned = mine.planned.clone();
+    }
+    if mine.flagged != base.flagged {
+        merged.flagged = mine.flagged.clone();
+    }
+
+    DayMerge { merged, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+    use time::macros::date;
+
+    fn record(id: u32, name: &str, start_hour: u8, end_hour: u8) -> WorkRecord {
+        WorkRecord::new(
+            id,
+            name.to_string(),
+            TimePoint::new(start_hour, 0).unwrap(),
+            TimePoint::new(end_hour, 0).unwrap(),
+        )
+    }
+
+    fn base_day() -> DayData {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(record(1, "Code review", 9, 10));
+        day.add_record(record(2, "Meeting", 10, 11));
+        day.add_record(record(3, "Feature dev", 11, 13));
+        day
+    }
+
+    /// Both sides edited record 1, each differently
+    fn conflicting() -> (DayData, DayMerge) {
+        let base = base_day();
+        let mut mine = base.clone();
+        mine.work_records.get_mut(&1).unwrap().description = "mine".to_string();
+        let mut disk = base.clone();
+        disk.work_records.get_mut(&1).unwrap().description = "disk".to_string();
+        let merge = merge_days(&base, &mine, &disk);
+        (mine, merge)
+    }
+
+    #[test]
+    fn test_disjoint_edits_merge_without_conflicts() {
+        let base = base_day();
+
+        let mut mine = base.clone();
+        mine.work_records.get_mut(&1).unwrap().description = "mine".to_string();
+        mine.remove_record(2);
+        let id = mine.next_id();
+        mine.add_record(record(id, "Lunch", 12, 13));
+        mine.set_annotation("Code review", "over estimate");
+
+        let mut disk = base.clone();
+        disk.work_records.get_mut(&3).unwrap().name = "Feature dev (CLI)".to_string();
+        disk.set_annotation("Meeting", "ran long");
+
+        let merge = merge_days(&base, &mine, &disk);
+        assert!(merge.conflicts.is_empty());
+        let merged = merge.merged;
+        assert_eq!(merged.work_records[&1].description, "mine");
+        assert!(!merged.work_records.contains_key(&2));
+        assert_eq!(merged.work_records[&3].name, "Feature dev (CLI)");
+        assert_eq!(merged.work_records[&4].name, "Lunch");
+        assert_eq!(merged.last_id, 4);
+        assert_eq!(merged.annotation("Code review"), Some("over estimate"));
+        assert_eq!(merged.annotation("Meeting"), Some("ran long"));
+    }
+
+    #[test]
+    fn test_same_edit_on_both_sides_is_not_a_conflict() {
+        let base = base_day();
+        let mut mine = base.clone();
+        mine.remove_record(3);
+        let disk = mine.clone();
+
+        let merge = merge_days(&base, &mine, &disk);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.merged, disk);
+    }
+
+    #[test]
+    fn test_record_changed_on_both_sides_conflicts() {
+        let (_, merge) = conflicting();
+        assert_eq!(merge.conflicts.len(), 1);
+        let conflict = &merge.conflicts[0];
+        assert_eq!(conflict.id, 1);
+        assert_eq!(conflict.mine.as_ref().unwrap().description, "mine");
+        assert_eq!(conflict.disk.as_ref().unwrap().description, "disk");
+        // Unresolved, the merged day holds the disk side
+        assert_eq!(merge.merged.work_records[&1].description, "disk");
+    }
+
+    #[test]
+    fn test_resolve_keep_mine() {
+        let (_, mut merge) = conflicting();
+        merge.conflicts[0].resolve(&mut merge.merged, Resolution::KeepMine);
+        assert_eq!(merge.merged.work_records[&1].description, "mine");
+        assert_eq!(merge.merged.work_records.len(), 3);
+    }
+
+    #[test]
+    fn test_resolve_take_disk() {
+        let (_, mut merge) = conflicting();
+        merge.conflicts[0].resolve(&mut merge.merged, Resolution::TakeDisk);
+        assert_eq!(merge.merged.work_records[&1].description, "disk");
+        assert_eq!(merge.merged.work_records.len(), 3);
+    }
+
+    #[test]
+    fn test_resolve_keep_both_adds_mine_under_a_new_id() {
+        let (_, mut merge) = conflicting();
+        merge.conflicts[0].resolve(&mut merge.merged, Resolution::KeepBoth);
+        let merged = &merge.merged;
+        assert_eq!(merged.work_records.len(), 4);
+        assert_eq!(merged.work_records[&1].description, "disk");
+        assert_eq!(merged.work_records[&4].description, "mine");
+        assert_eq!(merged.last_id, 4);
+    }
+
+    #[test]
+    fn test_edit_against_delete_conflicts() {
+        let base = base_day();
+        let mut mine = base.clone();
+        mine.work_records.get_mut(&2).unwrap().description = "agenda".to_string();
+        let mut disk = base.clone();
+        disk.remove_record(2);
+
+        let mut merge = merge_days(&base, &mine, &disk);
+        assert_eq!(merge.conflicts.len(), 1);
+        assert!(merge.conflicts[0].disk.is_none());
+
+        let conflict = merge.conflicts[0].clone();
+        let mut kept = merge.merged.clone();
+        conflict.resolve(&mut kept, Resolution::KeepBoth);
+        assert_eq!(kept.work_records[&2].description, "agenda");
+
+        conflict.resolve(&mut merge.merged, Resolution::TakeDisk);
+        assert!(!merge.merged.work_records.contains_key(&2));
+    }
+
+    #[test]
+    fn test_records_added_under_the_same_id_conflict() {
+        let base = base_day();
+        let mut mine = base.clone();
+        mine.add_record(record(4, "Lunch", 12, 13));
+        let mut disk = base.clone();
+        disk.add_record(record(4, "Support call", 13, 14));
+
+        let mut merge = merge_days(&base, &mine, &disk);
+        assert_eq!(merge.conflicts.len(), 1);
+        merge.conflicts[0].resolve(&mut merge.merged, Resolution::KeepBoth);
+        assert_eq!(merge.merged.work_records[&4].name, "Support call");
+        assert_eq!(merge.merged.work_records[&5].name, "Lunch");
+    }
+}
@@ -1,7 +1,16 @@
 mod day_data;
+mod merge;
+mod offset_change;
+mod plan;
+pub mod shift;
+mod template;
 mod time_point;
 mod work_record;
 
-pub use day_data::DayData;
-pub use time_point::TimePoint;
-pub use work_record::WorkRecord;
+pub use day_data::{DayBreakdown, DayData, NO_PROJECT_LABEL, Truncated};
+pub use merge::{RecordConflict, Resolution, merge_days};
+pub use offset_change::{OffsetChange, offset_at};
+pub use plan::PlanAudit;
+pub use template::RecordTemplate;
+pub use time_point::{TimePoint, humanize_minutes, parse_minutes};
+pub use work_record::{EstimateStatus, GroupBy, WorkRecord};
@@ -0,0 +1,179 @@
+use super::TimePoint;
+use serde::{Deserialize, Serialize};
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+/// A local clock change (e.g. a DST transition) on a given date
+///
+/// `time` has no timezone database, so transitions are listed in config:
+///
+/// ```toml
+/// [[time.offset_changes]]
+/// date = "2025-03-30"
+/// at = "02:00"
+/// shift_minutes = 60
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OffsetChange {
+    /// Date of the transition ("YYYY-MM-DD")
+    pub date: String,
+    /// Wall-clock time at which the clocks move ("HH:MM")
+    pub at: String,
+    /// How far the clocks move: 60 springs forward an hour, -60 falls back
+    pub shift_minutes: i32,
+}
+
+impl OffsetChange {
+    /// Parse the date and time, None if either is malformed
+    fn parsed(&self) -> Option<(Date, TimePoint)> {
+        let date = Date::parse(&self.date, format_description!("[year]-[month]-[day]")).ok()?;
+        let at = TimePoint::parse(&self.at).ok()?;
+        Some((date, at))
+    }
+
+    /// Whether this change happens on `date`
+    pub fn applies_to(&self, date: Date) -> bool {
+        self.parsed().is_some_and(|(d, _)| d == date)
+    }
+}
+
+/// Total clock shift in minutes that has happened on `date` by wall-clock `time`
+pub fn shift_minutes_at(changes: &[OffsetChange], date: Date, time: TimePoint) -> i32 {
+    changes
+        .iter()
+        .filter_map(|change| change.parsed().map(|parsed| (parsed, change.shift_minutes)))
+        .filter(|((d, at), _)| *d == date && *at <= time)
+        .map(|(_, shift)| shift)
+        .sum()
+}
+
+/// UTC offset in effect at `instant`, going back from the one in effect at `now`
+///
+/// Every change that took place after `instant` and by `now` is undone, so a
+/// summer instant read in winter gets the summer offset back.
+pub fn offset_at(
+    changes: &[OffsetChange],
+    now: OffsetDateTime,
+    instant: OffsetDateTime,
+) -> UtcOffset {
+    let mut parsed: Vec<_> = changes
+        .iter()
+        .filter_map(|change| change.parsed().map(|parsed| (parsed, change.shift_minutes)))
+        .collect();
+    parsed.sort_by_key(|(parsed, _)| std::cmp::Reverse(*parsed));
+
+    let mut offset = now.offset();
+    for ((date, at), shift) in parsed {
+        // The change happens at `at` on the clock before it
+        let Ok(before) = UtcOffset::from_whole_seconds(offset.whole_seconds() - shift * 60) else {
+            continue;
+        };
+        let Ok(at) = Time::from_hms(at.hour, at.minute, 0) else {
+            continue;
+        };
+        let happened = PrimitiveDateTime::new(date, at).assume_offset(before);
+        if happened > now {
+            continue;
+        }
+        if happened <= instant {
+            break;
+        }
+        offset = before;
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn change(date: &str, at: &str, shift_minutes: i32) -> OffsetChange {
+        OffsetChange {
+            date: date.to_string(),
+            at: at.to_string(),
+            shift_minutes,
+        }
+    }
+
+    #[test]
+    fn test_shift_before_and_after_change() {
+        let changes = vec![change("2025-03-30", "02:00", 60)];
+        let day = date!(2025 - 03 - 30);
+
+        assert_eq!(
+            shift_minutes_at(&changes, day, TimePoint::new(1, 59).unwrap()),
+            0
+        );
+        assert_eq!(
+            shift_minutes_at(&changes, day, TimePoint::new(3, 0).unwrap()),
+            60
+        );
+    }
+
+    #[test]
+    fn test_shift_ignores_other_dates() {
+        let changes = vec![change("2025-03-30", "02:00", 60)];
+        assert_eq!(
+            shift_minutes_at(
+                &changes,
+                date!(2025 - 03 - 31),
+                TimePoint::new(12, 0).unwrap()
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn test_offset_at_undoes_changes_since_the_instant() {
+        use time::macros::{datetime, offset};
+
+        // Summer time from 2025-03-30 02:00 to 2025-10-26 03:00 (+01:00 / +02:00)
+        let changes = vec![
+            change("2025-03-30", "02:00", 60),
+            change("2025-10-26", "03:00", -60),
+            change("2026-03-29", "02:00", 60),
+        ];
+        let now = datetime!(2025-12-01 12:00 +1);
+
+        assert_eq!(
+            offset_at(&changes, now, datetime!(2025-11-15 09:00 UTC)),
+            offset!(+1)
+        );
+        assert_eq!(
+            offset_at(&changes, now, datetime!(2025-10-26 00:59 UTC)),
+            offset!(+2)
+        );
+        assert_eq!(
+            offset_at(&changes, now, datetime!(2025-10-26 01:00 UTC)),
+            offset!(+1)
+        );
+        assert_eq!(
+            offset_at(&changes, now, datetime!(2025-03-30 00:59 UTC)),
+            offset!(+1)
+        );
+        assert_eq!(
+            offset_at(&changes, now, datetime!(2025-03-30 01:00 UTC)),
+            offset!(+2)
+        );
+        // Without listed changes the current offset is all there is
+        assert_eq!(
+            offset_at(&[], now, datetime!(2025-07-01 09:00 UTC)),
+            offset!(+1)
+        );
+    }
+
+    #[test]
+    fn test_malformed_change_is_ignored() {
+        let changes = vec![
+            change("30/03/2025", "02:00", 60),
+            change("2025-03-30", "2am", 60),
+        ];
+        let day = date!(2025 - 03 - 30);
+        assert_eq!(
+            shift_minutes_at(&changes, day, TimePoint::new(12, 0).unwrap()),
+            0
+        );
+        assert!(!changes[0].applies_to(day));
+    }
+}
@@ -0,0 +1,196 @@
+//! Planned against actual: how a day went compared with the records marked as its plan
+//!
+//! Actual records are matched to planned ones by exact name first, then by the
+//! ticket in the name, each planned record used at most once and earlier starts
+//! matched first. Whatever is left over is "missed" (planned) or "unplanned" (actual).
+
+use super::WorkRecord;
+use crate::integrations::extract_ticket_from_name;
+use std::collections::HashMap;
+
+/// How far an actual record drifted from the planned one it matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlanDelta {
+    /// Positive when it started late
+    pub start_minutes: i32,
+    /// Positive when it ran longer than planned
+    pub duration_minutes: i32,
+}
+
+impl PlanDelta {
+    fn between(planned: &WorkRecord, actual: &WorkRecord) -> Self {
+        PlanDelta {
+            start_minutes: actual.start.to_minutes_since_midnight() as i32
+                - planned.start.to_minutes_since_midnight() as i32,
+            duration_minutes: actual.effective_minutes() as i32
+                - planned.effective_minutes() as i32,
+        }
+    }
+
+    /// Started on time and took as long as planned
+    pub fn is_on_plan(&self) -> bool {
+        self.start_minutes == 0 && self.duration_minutes == 0
+    }
+
+    /// "+15m late, ran 20m over", "5m early", "on plan"
+    pub fn describe(&self) -> String {
+        let start = match self.start_minutes {
+            0 => None,
+            late if late > 0 => Some(format!("+{}m late", late)),
+            early => Some(format!("{}m early", -early)),
+        };
+        let duration = match self.duration_minutes {
+            0 => None,
+            over if over > 0 => Some(format!("ran {}m over", over)),
+            short => Some(format!("ran {}m short", -short)),
+        };
+        match (start, duration) {
+            (None, None) => "on plan".to_string(),
+            (Some(text), None) | (None, Some(text)) => text,
+            (Some(start), Some(duration)) => format!("{}, {}", start, duration),
+        }
+    }
+}
+
+/// Outcome of comparing a day's records with its plan
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlanAudit {
+    /// Drift of each matched actual record, keyed by its id; ids missing here are unplanned
+    pub matched: HashMap<u32, PlanDelta>,
+    /// Planned records nothing matched, by start time
+    pub missed: Vec<WorkRecord>,
+    pub planned_minutes: u32,
+    pub actual_minutes: u32,
+}
+
+impl PlanAudit {
+    /// "+15m late, ran 20m over" for a matched record, "unplanned" otherwise
+    pub fn describe(&self, id: u32) -> String {
+        self.matched
+            .get(&id)
+            .map_or_else(|| "unplanned".to_string(), PlanDelta::describe)
+    }
+}
+
+fn same_name(planned: &WorkRecord, actual: &WorkRecord) -> bool {
+    planned.name == actual.name
+}
+
+fn same_ticket(planned: &WorkRecord, actual: &WorkRecord) -> bool {
+    extract_ticket_from_name(&planned.name)
+        .is_some_and(|ticket| extract_ticket_from_name(&actual.name) == Some(ticket))
+}
+
+/// Compare `actual` records with `planned` ones
+pub fn audit_plan(planned: &[WorkRecord], actual: &[&WorkRecord]) -> PlanAudit {
+    let mut planned: Vec<&WorkRecord> = planned.iter().collect();
+    planned.sort_by_key(|record| (record.start, record.id));
+    let mut actual: Vec<&WorkRecord> = actual.to_vec();
+    actual.sort_by_key(|record| (record.start, record.id));
+
+    let mut used = vec![false; planned.len()];
+    let mut matched = HashMap::new();
+    let rules: [fn(&WorkRecord, &WorkRecord) -> bool; 2] = [same_name, same_ticket];
+    for rule in rules {
+        for record in &actual {
+            if matched.contains_key(&record.id) {
+                continue;
+            }
+            let found = planned
+                .iter()
+                .enumerate()
+                .find(|(i, plan)| !used[*i] && rule(plan, record));
+            if let Some((i, plan)) = found {
+                used[i] = true;
+                matched.insert(record.id, PlanDelta::between(plan, record));
+            }
+        }
+    }
+
+    let total = |records: &[&WorkRecord]| -> u32 {
+        records
+            .iter()
+            .map(|record| record.effective_minutes())
+            .sum::<i64>()
+            .max(0) as u32
+    };
+    PlanAudit {
+        matched,
+        missed: planned
+            .iter()
+            .zip(&used)
+            .filter(|(_, used)| !**used)
+            .map(|(record, _)| (*record).clone())
+            .collect(),
+        planned_minutes: total(&planned),
+        actual_minutes: total(&actual),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+
+    fn record(id: u32, name: &str, start: (u8, u8), end: (u8, u8)) -> WorkRecord {
+        WorkRecord::new(
+            id,
+            name.to_string(),
+            TimePoint::new(start.0, start.1).unwrap(),
+            TimePoint::new(end.0, end.1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_describe_delta() {
+        let delta = |start_minutes, duration_minutes| PlanDelta {
+            start_minutes,
+            duration_minutes,
+        };
+        assert_eq!(delta(15, 20).describe(), "+15m late, ran 20m over");
+        assert_eq!(delta(-5, 0).describe(), "5m early");
+        assert_eq!(delta(0, -10).describe(), "ran 10m short");
+        assert_eq!(delta(0, 0).describe(), "on plan");
+        assert!(delta(0, 0).is_on_plan());
+        assert!(!delta(-5, 0).is_on_plan());
+    }
+
+    #[test]
+    fn test_matches_by_name_then_ticket() {
+        let planned = [
+            record(1, "Standup", (9, 0), (9, 15)),
+            record(2, "PROJ-7 Design review", (10, 0), (11, 0)),
+            record(3, "1:1 with Sam", (14, 0), (14, 30)),
+        ];
+        let standup = record(10, "Standup", (9, 15), (9, 50));
+        let review = record(11, "PROJ-7 review (moved)", (10, 0), (10, 45));
+        let support = record(12, "Support", (13, 0), (14, 0));
+
+        let audit = audit_plan(&planned, &[&support, &review, &standup]);
+        assert_eq!(audit.describe(10), "+15m late, ran 20m over");
+        assert_eq!(audit.describe(11), "ran 15m short");
+        assert_eq!(audit.describe(12), "unplanned");
+        let missed: Vec<&str> = audit.missed.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(missed, ["1:1 with Sam"]);
+        assert_eq!(audit.planned_minutes, 15 + 60 + 30);
+        assert_eq!(audit.actual_minutes, 35 + 45 + 60);
+    }
+
+    #[test]
+    fn test_exact_names_win_over_tickets_and_each_plan_matches_once() {
+        let planned = [
+            record(1, "PROJ-7 Build", (9, 0), (10, 0)),
+            record(2, "PROJ-7 Review", (11, 0), (12, 0)),
+        ];
+        // The first actual record shares only the ticket; the second has the exact name
+        let build_again = record(10, "PROJ-7 Fixups", (9, 0), (9, 30));
+        let review = record(11, "PROJ-7 Review", (11, 0), (12, 0));
+        let extra = record(12, "PROJ-7 Review", (15, 0), (15, 30));
+
+        let audit = audit_plan(&planned, &[&build_again, &review, &extra]);
+        assert_eq!(audit.describe(11), "on plan");
+        assert_eq!(audit.describe(10), "ran 30m short");
+        assert_eq!(audit.describe(12), "unplanned");
+        assert!(audit.missed.is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+//! Moving a whole day's records earlier or later (`day shift`, `D` in the TUI)
+//!
+//! Every record moves by the same signed offset. Times can't leave the day, so
+//! they stop at 00:00 and 23:59; a record squeezed to nothing (or inverted) by
+//! that is refused unless truncation is forced.
+
+use super::{DayData, TimePoint, parse_minutes};
+
+const LAST_MINUTE: i32 = 23 * 60 + 59;
+
+/// Parse a signed offset in minutes: "+1h", "-30m", "1h30m", "-1:30" or "90"
+///
+/// A bare number is minutes. The sign is optional, so "1h" moves later too.
+pub fn parse_offset(input: &str) -> Result<i32, String> {
+    let invalid = || format!("Invalid offset '{}' (use e.g. +1h, -30m or 90)", input);
+    let trimmed = input.trim();
+    let (sign, rest) = match trimmed.as_bytes().first() {
+        Some(b'-') => (-1, &trimmed[1..]),
+        Some(b'+') => (1, &trimmed[1..]),
+        _ => (1, trimmed),
+    };
+    let minutes = parse_minutes(rest).ok_or_else(invalid)?;
+    if minutes > LAST_MINUTE as u32 {
+        return Err(format!("Offset '{}' is longer than a day", input));
+    }
+    Ok(sign * minutes as i32)
+}
+
+/// "+1h", "-30m", "+1h 30m"
+pub fn format_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let (hours, rest) = (minutes.unsigned_abs() / 60, minutes.unsigned_abs() % 60);
+    match (hours, rest) {
+        (0, rest) => format!("{}{}m", sign, rest),
+        (hours, 0) => format!("{}{}h", sign, hours),
+        (hours, rest) => format!("{}{}h {}m", sign, hours, rest),
+    }
+}
+
+/// Records a shift had to bend to keep inside the day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShiftReport {
+    /// Ids of records stopped at 00:00 or 23:59, in start order
+    pub clamped: Vec<u32>,
+    /// Ids of clamped records left empty, only with `force`
+    pub truncated: Vec<u32>,
+}
+
+/// Move every record of `day` by `minutes`, clamping at the day's edges
+///
+/// If any record with a duration would be left empty or inverted, nothing is
+/// changed and its id is named in the error, unless `force` keeps such records
+/// truncated at the edge. Records running past midnight are always refused.
+pub fn shift_day(day: &mut DayData, minutes: i32, force: bool) -> Result<ShiftReport, String> {
+    let mut report = ShiftReport::default();
+    let mut shifted = Vec::new();
+    let mut squeezed = Vec::new();
+    for record in day.get_sorted_records() {
+        if record.end < record.start {
+            return Err(format!(
+                "Record #{} runs past midnight; shift it by hand",
+                record.id
+            ));
+        }
+        let start = shift_time(record.start, minutes);
+        let end = shift_time(record.end, minutes);
+        let moved = |time: TimePoint, to: TimePoint| {
+            time.to_minutes_since_midnight() as i32 + minutes
+                != to.to_minutes_since_midnight() as i32
+        };
+        if moved(record.start, start) || moved(record.end, end) {
+            report.clamped.push(record.id);
+            if record.start < record.end && start >= end {
+                squeezed.push(record.id);
+            }
+        }
+        shifted.push((record.id, start, end));
+    }
+
+    if !squeezed.is_empty() && !force {
+        let ids: Vec<String> = squeezed.iter().map(|id| format!("#{}", id)).collect();
+        return Err(format!(
+            "Shifting by {} would leave {} empty at the edge of the day",
+            format_offset(minutes),
+            ids.join(", ")
+        ));
+    }
+    report.truncated = squeezed;
+
+    for (id, start, end) in shifted {
+        if let Some(record) = day.work_records.get_mut(&id) {
+            record.start = start;
+            record.end = end;
+            record.update_duration();
+        }
+    }
+    Ok(report)
+}
+
+fn shift_time(time: TimePoint, minutes: i32) -> TimePoint {
+    let shifted = (time.to_minutes_since_midnight() as i32 + minutes).clamp(0, LAST_MINUTE);
+    TimePoint::from_minutes_since_midnight(shifted as u32)
+        .expect("clamped to the day, so always a valid time")
+}
+
+impl ShiftReport {
+    /// "clamped #3, #5" (with ", truncated #5"), or None when nothing was bent
+    pub fn summary(&self) -> Option<String> {
+        let list = |ids: &[u32]| {
+            ids.iter()
+                .map(|id| format!("#{}", id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if self.clamped.is_empty() {
+            return None;
+        }
+        let mut text = format!("clamped {}", list(&self.clamped));
+        if !self.truncated.is_empty() {
+            text.push_str(&format!(", truncated {}", list(&self.truncated)));
+        }
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WorkRecord;
+    use time::macros::date;
+
+    fn at(hour: u8, minute: u8) -> TimePoint {
+        TimePoint::new(hour, minute).unwrap()
+    }
+
+    fn day_with(records: &[(u32, TimePoint, TimePoint)]) -> DayData {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        for &(id, start, end) in records {
+            day.add_record(WorkRecord::new(id, format!("Task {}", id), start, end));
+        }
+        day
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("+1h"), Ok(60));
+        assert_eq!(parse_offset("-30m"), Ok(-30));
+        assert_eq!(parse_offset("90"), Ok(90));
+        assert_eq!(parse_offset("-90"), Ok(-90));
+        assert_eq!(parse_offset("1h30m"), Ok(90));
+        assert_eq!(parse_offset("+1h 15m"), Ok(75));
+        assert_eq!(parse_offset("-1:30"), Ok(-90));
+        assert_eq!(parse_offset(" 2h "), Ok(120));
+    }
+
+    #[test]
+    fn test_parse_offset_rejects_junk() {
+        for input in [
+            "",
+            "+",
+            "h",
+            "1x",
+            "1h30",
+            "1:75",
+            "--1h",
+            "24h",
+            "+71582789h",
+        ] {
+            assert!(parse_offset(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(60), "+1h");
+        assert_eq!(format_offset(-30), "-30m");
+        assert_eq!(format_offset(90), "+1h 30m");
+        assert_eq!(format_offset(0), "+0m");
+    }
+
+    #[test]
+    fn test_shift_moves_every_record() {
+        let mut day = day_with(&[(1, at(9, 0), at(10, 0)), (2, at(10, 0), at(11, 30))]);
+        let report = shift_day(&mut day, 60, false).unwrap();
+        assert_eq!(report, ShiftReport::default());
+        assert_eq!(
+            (day.work_records[&1].start, day.work_records[&1].end),
+            (at(10, 0), at(11, 0))
+        );
+        assert_eq!(
+            (day.work_records[&2].start, day.work_records[&2].end),
+            (at(11, 0), at(12, 30))
+        );
+        assert_eq!(day.work_records[&2].total_minutes, 90);
+    }
+
+    #[test]
+    fn test_shift_clamps_at_the_day_edges() {
+        let mut day = day_with(&[(1, at(0, 10), at(1, 0)), (2, at(23, 0), at(23, 45))]);
+        let report = shift_day(&mut day, -30, false).unwrap();
+        assert_eq!(report.clamped, vec![1]);
+        assert_eq!(
+            (day.work_records[&1].start, day.work_records[&1].end),
+            (at(0, 0), at(0, 30))
+        );
+
+        let mut day = day_with(&[(2, at(23, 15), at(23, 45))]);
+        let report = shift_day(&mut day, 30, false).unwrap();
+        assert_eq!(report.clamped, vec![2]);
+        assert_eq!(
+            (day.work_records[&2].start, day.work_records[&2].end),
+            (at(23, 45), at(23, 59))
+        );
+        assert_eq!(report.summary().as_deref(), Some("clamped #2"));
+    }
+
+    #[test]
+    fn test_shift_refuses_empty_records_unless_forced() {
+        let mut day = day_with(&[(1, at(9, 0), at(10, 0)), (2, at(23, 0), at(23, 30))]);
+        let before = day.clone();
+
+        let refused = shift_day(&mut day, 60, false);
+        assert_eq!(
+            refused,
+            Err("Shifting by +1h would leave #2 empty at the edge of the day".to_string())
+        );
+        assert_eq!(day.work_records, before.work_records);
+
+        let report = shift_day(&mut day, 60, true).unwrap();
+        assert_eq!(report.truncated, vec![2]);
+        assert_eq!(
+            report.summary().as_deref(),
+            Some("clamped #2, truncated #2")
+        );
+        assert_eq!(
+            (day.work_records[&2].start, day.work_records[&2].end),
+            (at(23, 59), at(23, 59))
+        );
+        assert_eq!(day.work_records[&1].start, at(10, 0));
+    }
+
+    #[test]
+    fn test_shift_keeps_zero_length_records_and_refuses_overnight_ones() {
+        let mut day = day_with(&[(1, at(23, 59), at(23, 59))]);
+        assert!(shift_day(&mut day, 10, false).is_ok());
+
+        let mut day = day_with(&[(1, at(23, 0), at(1, 0))]);
+        assert!(shift_day(&mut day, 10, true).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use super::{TimePoint, WorkRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A record saved for reuse (`Y`), kept in `templates.json` in the data directory
+///
+/// Only what describes the work is kept: the duration instead of the start and end,
+/// and no link or import id, which belong to the one record they came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordTemplate {
+    /// Name the template is listed under in the picker
+    pub name: String,
+    /// Task name of the inserted record
+    pub task: String,
+    pub duration_minutes: u32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Custom field values, as in `WorkRecord::extra`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub billable: Option<bool>,
+}
+
+impl RecordTemplate {
+    /// Capture `record` as a template called `name`
+    pub fn from_record(name: &str, record: &WorkRecord) -> Self {
+        Self {
+            name: name.to_string(),
+            task: record.name.clone(),
+            duration_minutes: WorkRecord::calculate_duration(&record.start, &record.end),
+            description: record.description.clone(),
+            project: record.project.clone(),
+            extra: record.extra.clone(),
+            estimate_minutes: record.estimate_minutes,
+            billable: record.billable,
+        }
+    }
+
+    /// A record with id `id` starting at `start` for the template's duration
+    ///
+    /// The end stops at 23:59 rather than running into the next day.
+    pub fn instantiate(&self, id: u32, start: TimePoint) -> WorkRecord {
+        let end_minutes =
+            (start.to_minutes_since_midnight() + self.duration_minutes).min(24 * 60 - 1);
+        let end = TimePoint::from_minutes_since_midnight(end_minutes).unwrap();
+        let mut record = WorkRecord::new(id, self.task.clone(), start, end);
+        record.description = self.description.clone();
+        record.project = self.project.clone();
+        record.extra = self.extra.clone();
+        record.estimate_minutes = self.estimate_minutes;
+        record.billable = self.billable;
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> WorkRecord {
+        let mut record = WorkRecord::new(
+            7,
+            "PROJ-12 Review".to_string(),
+            TimePoint::new(9, 15).unwrap(),
+            TimePoint::new(10, 45).unwrap(),
+        );
+        record.description = "Weekly review".to_string();
+        record.project = Some("Platform".to_string());
+        record.url = Some("https://example.com/pr/1".to_string());
+        record
+            .extra
+            .insert("client".to_string(), "Acme".to_string());
+        record.billable = Some(true);
+        record
+    }
+
+    #[test]
+    fn test_from_record_keeps_duration_not_times() {
+        let template = RecordTemplate::from_record("Review", &record());
+        assert_eq!(template.name, "Review");
+        assert_eq!(template.task, "PROJ-12 Review");
+        assert_eq!(template.duration_minutes, 90);
+        assert_eq!(template.project.as_deref(), Some("Platform"));
+        assert_eq!(template.extra["client"], "Acme");
+    }
+
+    #[test]
+    fn test_from_overnight_record() {
+        let mut night = record();
+        night.start = TimePoint::new(23, 0).unwrap();
+        night.end = TimePoint::new(1, 0).unwrap();
+        assert_eq!(
+            RecordTemplate::from_record("Night", &night).duration_minutes,
+            120
+        );
+    }
+
+    #[test]
+    fn test_instantiate_places_duration_after_start() {
+        let template = RecordTemplate::from_record("Review", &record());
+        let inserted = template.instantiate(3, TimePoint::new(14, 0).unwrap());
+        assert_eq!(inserted.id, 3);
+        assert_eq!(inserted.start, TimePoint::new(14, 0).unwrap());
+        assert_eq!(inserted.end, TimePoint::new(15, 30).unwrap());
+        assert_eq!(inserted.total_minutes, 90);
+        assert_eq!(inserted.description, "Weekly review");
+        assert_eq!(inserted.billable, Some(true));
+        // The link belonged to the original record only
+        assert_eq!(inserted.url, None);
+    }
+
+    #[test]
+    fn test_instantiate_stops_at_end_of_day() {
+        let template = RecordTemplate::from_record("Review", &record());
+        let inserted = template.instantiate(1, TimePoint::new(23, 0).unwrap());
+        assert_eq!(inserted.end, TimePoint::new(23, 59).unwrap());
+    }
+
+    #[test]
+    fn test_minimal_template_json() {
+        let template: RecordTemplate = serde_json::from_str(
+            r#"{"name": "Standup", "task": "Standup", "duration_minutes": 15}"#,
+        )
+        .unwrap();
+        assert_eq!(template.duration_minutes, 15);
+        assert!(template.description.is_empty());
+        assert_eq!(
+            serde_json::to_string(&template).unwrap(),
+            r#"{"name":"Standup","task":"Standup","duration_minutes":15}"#
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
+use time::{Date, OffsetDateTime};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TimePoint {
@@ -33,23 +34,144 @@ impl TimePoint {
         (self.hour as u32) * 60 + (self.minute as u32)
     }
 
+    /// Parse a typed time: "09:15", "9:15", "0915", "915" or "9.15"
+    ///
+    /// Surrounding whitespace is ignored. Errors name the input and what is wrong
+    /// with it, so they can be shown as they are.
     pub fn parse(s: &str) -> Result<Self, String> {
-        let parts: Vec<&str> = s.split(':').collect();
-        if parts.len() != 2 {
-            return Err(format!("Invalid time format: {}", s));
+        let input = s.trim();
+        if input.is_empty() {
+            return Err("Time is empty (use HH:MM)".to_string());
         }
+        let invalid = || format!("Invalid time '{}' (use HH:MM, H:MM, HHMM or H.MM)", input);
+        let digits = |part: &str, max_len: usize| {
+            (!part.is_empty() && part.len() <= max_len && part.bytes().all(|b| b.is_ascii_digit()))
+                .then(|| part.parse::<u8>().ok())
+                .flatten()
+        };
+
+        let (hour, minute) = match input.split_once([':', '.']) {
+            Some((hour, minute)) => (digits(hour, 2), digits(minute, 2)),
+            // "HHMM" or "HMM": the last two digits are the minutes
+            None if matches!(input.len(), 3 | 4) && input.bytes().all(|b| b.is_ascii_digit()) => {
+                let (hour, minute) = input.split_at(input.len() - 2);
+                (digits(hour, 2), digits(minute, 2))
+            }
+            None => (None, None),
+        };
+        let (Some(hour), Some(minute)) = (hour, minute) else {
+            return Err(invalid());
+        };
 
-        let hour = parts[0]
-            .parse::<u8>()
-            .map_err(|_| format!("Invalid hour: {}", parts[0]))?;
-        let minute = parts[1]
-            .parse::<u8>()
-            .map_err(|_| format!("Invalid minute: {}", parts[1]))?;
+        if hour >= 24 {
+            return Err(format!("Invalid time '{}': hour must be 0-23", input));
+        }
+        if minute >= 60 {
+            return Err(format!("Invalid time '{}': minute must be 0-59", input));
+        }
+        Ok(TimePoint { hour, minute })
+    }
 
-        Self::new(hour, minute)
+    /// Parse a pasted time range such as "09:15 - 10:45", "9.15-10.45" or "9:15 to 10:45"
+    ///
+    /// Accepts `-`, `–`, `—` or "to" between the two times, and `:` or `.` inside each time.
+    pub fn parse_range(s: &str) -> Option<(Self, Self)> {
+        let re = regex::Regex::new(
+            r"(?i)^\s*(\d{1,2}[:.]\d{2})\s*(?:-|–|—|\bto\b)\s*(\d{1,2}[:.]\d{2})\s*$",
+        )
+        .ok()?;
+        let caps = re.captures(s)?;
+        let start = Self::parse(&caps[1]).ok()?;
+        let end = Self::parse(&caps[2]).ok()?;
+        Some((start, end))
     }
 }
 
+impl TimePoint {
+    /// Nearest multiple of `step` minutes, for display only (`[ui] snap_display_minutes`)
+    ///
+    /// Halfway rounds up; a time that would snap to midnight keeps the last step of
+    /// the day. A step of 0 or 1 leaves the time as it is.
+    pub fn snapped(self, step: u8) -> Self {
+        let step = step as u32;
+        if step <= 1 {
+            return self;
+        }
+        let minutes = self.to_minutes_since_midnight();
+        let nearest = (minutes + step / 2) / step * step;
+        let snapped = if nearest < 24 * 60 {
+            nearest
+        } else {
+            minutes / step * step
+        };
+        TimePoint::from_minutes_since_midnight(snapped).unwrap_or(self)
+    }
+
+    /// Describe this time on `date` relative to `now` ("7h ago", "in 20m")
+    ///
+    /// Returns None when `date` isn't today, so callers fall back to the absolute time.
+    pub fn relative_to(self, date: Date, now: OffsetDateTime) -> Option<String> {
+        if date != now.date() {
+            return None;
+        }
+
+        let now_minutes = now.hour() as i64 * 60 + now.minute() as i64;
+        let diff = now_minutes - self.to_minutes_since_midnight() as i64;
+        Some(match diff {
+            0 => "now".to_string(),
+            d if d > 0 => format!("{} ago", humanize_minutes(d as u32)),
+            d => format!("in {}", humanize_minutes(d.unsigned_abs() as u32)),
+        })
+    }
+}
+
+/// Compact human-readable span: minutes under an hour ("20m"), hours and minutes above ("7h", "7h 05m")
+pub fn humanize_minutes(minutes: u32) -> String {
+    let hours = minutes / 60;
+    let mins = minutes % 60;
+    match (hours, mins) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}
+
+/// Parse a typed span in minutes: "90", "1:30", "1h", "45m", "1h30m" or "1h 30m"
+///
+/// None when the input is none of these, or too long to count in minutes.
+pub fn parse_minutes(input: &str) -> Option<u32> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+    let number = |part: &str| -> Option<u32> {
+        let part = part.trim();
+        (!part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| part.parse().ok())
+            .flatten()
+    };
+
+    let (hours, minutes) = if let Some((hours, minutes)) = input.split_once(':') {
+        (
+            number(hours)?,
+            number(minutes).filter(|minutes| *minutes < 60)?,
+        )
+    } else if let Some(minutes) = number(input) {
+        (0, minutes)
+    } else {
+        let (hours, rest) = match input.split_once('h') {
+            Some((hours, rest)) => (number(hours)?, rest.trim()),
+            None => (0, input),
+        };
+        let minutes = match rest {
+            "" => 0,
+            rest => number(rest.strip_suffix('m')?)?,
+        };
+        (hours, minutes)
+    };
+    hours.checked_mul(60)?.checked_add(minutes)
+}
+
 impl fmt::Display for TimePoint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:02}:{:02}", self.hour, self.minute)
@@ -122,6 +244,91 @@ mod tests {
         assert!(TimePoint::parse("").is_err());
     }
 
+    #[test]
+    fn test_parse_accepted_formats() {
+        let nine_fifteen = TimePoint::new(9, 15).unwrap();
+        for input in [
+            "09:15",
+            "9:15",
+            "0915",
+            "915",
+            "9.15",
+            "09.15",
+            "9:15 ",
+            " 09:15\t\n",
+        ] {
+            assert_eq!(TimePoint::parse(input), Ok(nine_fifteen), "{:?}", input);
+        }
+        assert_eq!(
+            TimePoint::parse("2359").unwrap(),
+            TimePoint::new(23, 59).unwrap()
+        );
+        assert_eq!(
+            TimePoint::parse("0000").unwrap(),
+            TimePoint::new(0, 0).unwrap()
+        );
+        // Written back in the one canonical form
+        assert_eq!(TimePoint::parse("915").unwrap().to_string(), "09:15");
+    }
+
+    #[test]
+    fn test_parse_rejects_with_descriptive_errors() {
+        assert_eq!(
+            TimePoint::parse("25:00").unwrap_err(),
+            "Invalid time '25:00': hour must be 0-23"
+        );
+        assert_eq!(
+            TimePoint::parse("12:60").unwrap_err(),
+            "Invalid time '12:60': minute must be 0-59"
+        );
+        assert_eq!(
+            TimePoint::parse("2500").unwrap_err(),
+            "Invalid time '2500': hour must be 0-23"
+        );
+        assert_eq!(
+            TimePoint::parse("").unwrap_err(),
+            "Time is empty (use HH:MM)"
+        );
+        assert_eq!(
+            TimePoint::parse("   ").unwrap_err(),
+            "Time is empty (use HH:MM)"
+        );
+        assert_eq!(
+            TimePoint::parse("9,15").unwrap_err(),
+            "Invalid time '9,15' (use HH:MM, H:MM, HHMM or H.MM)"
+        );
+        for input in [
+            "9", "09150", "9:", ":15", "9:150", "1 5:00", "+9:15", "9h15", "1é2", "€1",
+        ] {
+            assert!(TimePoint::parse(input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_range_formats() {
+        let expected = Some((
+            TimePoint::new(9, 15).unwrap(),
+            TimePoint::new(10, 45).unwrap(),
+        ));
+
+        assert_eq!(TimePoint::parse_range("09:15 - 10:45"), expected);
+        assert_eq!(TimePoint::parse_range("9.15-10.45"), expected);
+        assert_eq!(TimePoint::parse_range("9:15–10:45"), expected);
+        assert_eq!(TimePoint::parse_range("09:15 — 10.45"), expected);
+        assert_eq!(TimePoint::parse_range("9:15 to 10:45"), expected);
+        assert_eq!(TimePoint::parse_range("  9:15 TO 10:45\n"), expected);
+    }
+
+    #[test]
+    fn test_parse_range_rejects_other_text() {
+        assert_eq!(TimePoint::parse_range("09:15"), None);
+        assert_eq!(TimePoint::parse_range("09:15 - "), None);
+        assert_eq!(TimePoint::parse_range("9:15 until 10:45"), None);
+        assert_eq!(TimePoint::parse_range("9:15-10:45 standup"), None);
+        assert_eq!(TimePoint::parse_range("9:15to10:45"), None);
+        assert_eq!(TimePoint::parse_range("25:00 - 26:00"), None);
+    }
+
     #[test]
     fn test_parse_invalid_values() {
         assert!(TimePoint::parse("24:00").is_err());
@@ -207,4 +414,83 @@ mod tests {
         let time2 = time1;
         assert_eq!(time1, time2);
     }
+
+    #[test]
+    fn test_parse_minutes() {
+        assert_eq!(parse_minutes("90"), Some(90));
+        assert_eq!(parse_minutes("1:30"), Some(90));
+        assert_eq!(parse_minutes("2h"), Some(120));
+        assert_eq!(parse_minutes("45m"), Some(45));
+        assert_eq!(parse_minutes("1h30m"), Some(90));
+        assert_eq!(parse_minutes(" 1h 15m "), Some(75));
+        for input in ["", "h", "m", "1x", "1h30", "1:75", "1:+5", "-1h", "1:2:3"] {
+            assert_eq!(parse_minutes(input), None, "{input}");
+        }
+        // Too long to count in minutes
+        assert_eq!(parse_minutes("71582789h"), None);
+        assert_eq!(parse_minutes("71582789:00"), None);
+        assert_eq!(parse_minutes("99999999999"), None);
+    }
+
+    #[test]
+    fn test_humanize_minutes_boundaries() {
+        assert_eq!(humanize_minutes(0), "0m");
+        assert_eq!(humanize_minutes(59), "59m");
+        assert_eq!(humanize_minutes(60), "1h");
+        assert_eq!(humanize_minutes(61), "1h 01m");
+        assert_eq!(humanize_minutes(23 * 60 + 59), "23h 59m");
+    }
+
+    #[test]
+    fn test_relative_to_past_and_future() {
+        use time::macros::{date, datetime};
+        let now = datetime!(2025-11-06 16:00 UTC);
+        let today = date!(2025 - 11 - 06);
+
+        assert_eq!(
+            TimePoint::new(9, 0).unwrap().relative_to(today, now),
+            Some("7h ago".to_string())
+        );
+        assert_eq!(
+            TimePoint::new(15, 45).unwrap().relative_to(today, now),
+            Some("15m ago".to_string())
+        );
+        assert_eq!(
+            TimePoint::new(16, 20).unwrap().relative_to(today, now),
+            Some("in 20m".to_string())
+        );
+        assert_eq!(
+            TimePoint::new(14, 59).unwrap().relative_to(today, now),
+            Some("1h 01m ago".to_string())
+        );
+        assert_eq!(
+            TimePoint::new(16, 0).unwrap().relative_to(today, now),
+            Some("now".to_string())
+        );
+    }
+
+    #[test]
+    fn test_snapped_to_nearest_step() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        assert_eq!(at(9, 3).snapped(5), at(9, 5));
+        assert_eq!(at(9, 2).snapped(5), at(9, 0));
+        assert_eq!(at(9, 58).snapped(5), at(10, 0));
+        assert_eq!(at(9, 7).snapped(15), at(9, 0));
+        assert_eq!(at(9, 8).snapped(15), at(9, 15));
+        assert_eq!(at(23, 58).snapped(5), at(23, 55));
+        assert_eq!(at(9, 3).snapped(0), at(9, 3));
+        assert_eq!(at(9, 3).snapped(1), at(9, 3));
+    }
+
+    #[test]
+    fn test_relative_to_other_day_is_none() {
+        use time::macros::{date, datetime};
+        let now = datetime!(2025-11-06 00:10 UTC);
+        assert_eq!(
+            TimePoint::new(23, 50)
+                .unwrap()
+                .relative_to(date!(2025 - 11 - 05), now),
+            None
+        );
+    }
 }
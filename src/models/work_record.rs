@@ -1,7 +1,12 @@
 use super::TimePoint;
+use super::offset_change::{OffsetChange, shift_minutes_at};
+use crate::integrations::extract_ticket_from_name;
+use crate::timefmt::format_minutes;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use time::{Date, UtcOffset};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WorkRecord {
     pub id: u32,
     pub name: String,
@@ -10,6 +15,56 @@ pub struct WorkRecord {
     pub total_minutes: u32,
     #[serde(default)]
     pub description: String,
+    /// Signed correction added to the tracked duration (negative to credit time back)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adjustment_minutes: Option<i32>,
+    /// Optional project the task belongs to, used to roll up the summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Explicit link (PR, doc, design) opened instead of the detected ticket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Id of the entry this record was imported from ("toggl:123"), to skip it on re-import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Values of custom fields declared in `[fields]`, keyed by field name
+    ///
+    /// Keys no longer declared are kept as they are.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, String>,
+    /// Created by a running timer and not finished yet; the timer's stop sets the end
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub in_progress: bool,
+    /// Minutes the task was expected to take, shown against the actual duration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    /// Marked billable or not with `$`; unmarked records follow `[defaults] billable`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub billable: Option<bool>,
+    /// Fields this version doesn't know, written back as they were read
+    #[serde(flatten)]
+    pub unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+/// How a record's duration compares with its estimate
+/// What the summary totals records by (`[ui] group_by`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// The full task name
+    #[default]
+    Name,
+    /// The ticket id in the name ("PROJ-1"), else the name
+    Ticket,
+    /// The record's project, else the name
+    Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstimateStatus {
+    /// Done in the estimated time or less
+    Within,
+    Over,
 }
 
 impl WorkRecord {
@@ -22,9 +77,23 @@ impl WorkRecord {
             end,
             total_minutes,
             description: String::new(),
+            adjustment_minutes: None,
+            project: None,
+            url: None,
+            external_id: None,
+            extra: HashMap::new(),
+            in_progress: false,
+            estimate_minutes: None,
+            billable: None,
+            unknown: serde_json::Map::new(),
         }
     }
 
+    /// Whether the record counts towards billable time, given `[defaults] billable`
+    pub fn is_billable(&self, default: bool) -> bool {
+        self.billable.unwrap_or(default)
+    }
+
     pub fn calculate_duration(start: &TimePoint, end: &TimePoint) -> u32 {
         let start_mins = start.to_minutes_since_midnight();
         let end_mins = end.to_minutes_since_midnight();
@@ -36,14 +105,112 @@ impl WorkRecord {
         }
     }
 
+    /// Calculate the real elapsed minutes between two wall-clock times whose UTC offsets
+    /// are known (e.g. both ends of a timer session).
+    ///
+    /// On DST transition days the wall-clock span differs from the real one: on
+    /// spring-forward 01:30 (+01:00) → 03:30 (+02:00) is one hour, on fall-back
+    /// 01:30 (+02:00) → 02:30 (+01:00) is two. The offset change is subtracted from the
+    /// naive wall-clock difference, so same-offset spans match `calculate_duration`.
+    pub fn calculate_duration_with_offsets(
+        start: &TimePoint,
+        start_offset: UtcOffset,
+        end: &TimePoint,
+        end_offset: UtcOffset,
+    ) -> u32 {
+        let wall_minutes = Self::calculate_duration(start, end) as i64;
+        let offset_change_minutes =
+            (end_offset.whole_seconds() as i64 - start_offset.whole_seconds() as i64) / 60;
+
+        (wall_minutes - offset_change_minutes).max(0) as u32
+    }
+
+    /// Calculate the real elapsed minutes for a record on `date`, applying any clock
+    /// changes listed for that date. Without matching changes this is `calculate_duration`.
+    pub fn calculate_duration_on(
+        date: Date,
+        start: &TimePoint,
+        end: &TimePoint,
+        changes: &[OffsetChange],
+    ) -> u32 {
+        let start_shift = shift_minutes_at(changes, date, *start);
+        // An overnight record ends on the next day, after every change on `date`
+        let end_point = if end >= start {
+            *end
+        } else {
+            TimePoint {
+                hour: 23,
+                minute: 59,
+            }
+        };
+        let end_shift = shift_minutes_at(changes, date, end_point);
+
+        let to_offset =
+            |shift: i32| UtcOffset::from_whole_seconds(shift * 60).unwrap_or(UtcOffset::UTC);
+        Self::calculate_duration_with_offsets(
+            start,
+            to_offset(start_shift),
+            end,
+            to_offset(end_shift),
+        )
+    }
+
+    #[allow(dead_code)]
     pub fn update_duration(&mut self) {
         self.total_minutes = Self::calculate_duration(&self.start, &self.end);
     }
 
+    /// Recompute `total_minutes` taking the UTC offsets at start and end into account
+    pub fn update_duration_with_offsets(&mut self, start_offset: UtcOffset, end_offset: UtcOffset) {
+        self.total_minutes =
+            Self::calculate_duration_with_offsets(&self.start, start_offset, &self.end, end_offset);
+    }
+
+    /// Recompute `total_minutes` for a record on `date` using the configured clock changes
+    pub fn update_duration_on(&mut self, date: Date, changes: &[OffsetChange]) {
+        self.total_minutes = Self::calculate_duration_on(date, &self.start, &self.end, changes);
+    }
+
+    /// Whether this record carries a non-zero time adjustment
+    pub fn is_adjustment(&self) -> bool {
+        self.adjustment_minutes.is_some_and(|minutes| minutes != 0)
+    }
+
+    /// Key this record is totalled under in the summary
+    pub fn group_key(&self, by: GroupBy) -> String {
+        let key = match by {
+            GroupBy::Name => None,
+            GroupBy::Ticket => extract_ticket_from_name(&self.name),
+            GroupBy::Project => self.project.clone(),
+        };
+        key.unwrap_or_else(|| self.name.clone())
+    }
+
+    /// Whether this record is a break (named like the records `b` adds)
+    pub fn is_break(&self) -> bool {
+        self.name.to_lowercase().contains("break")
+    }
+
+    /// Tracked minutes plus any adjustment; negative for a net credit
+    pub fn effective_minutes(&self) -> i64 {
+        self.total_minutes as i64 + self.adjustment_minutes.unwrap_or(0) as i64
+    }
+
+    /// Over or within the estimate, going by tracked minutes plus adjustment
+    pub fn estimate_status(&self) -> Option<EstimateStatus> {
+        let estimate = self.estimate_minutes?;
+        if self.effective_minutes() > estimate as i64 {
+            Some(EstimateStatus::Over)
+        } else {
+            Some(EstimateStatus::Within)
+        }
+    }
+
     pub fn format_duration(&self) -> String {
-        let hours = self.total_minutes / 60;
-        let minutes = self.total_minutes % 60;
-        format!("{}h {:02}m", hours, minutes)
+        let effective = self.effective_minutes();
+        let sign = if effective < 0 { "-" } else { "" };
+        let minutes = effective.unsigned_abs().min(u32::MAX as u64) as u32;
+        format!("{}{}", sign, format_minutes(minutes))
     }
 }
 
@@ -133,6 +300,83 @@ mod tests {
         assert_eq!(record.total_minutes, 150); // 2h 30m
     }
 
+    #[test]
+    fn test_calculate_duration_with_same_offsets_matches_naive() {
+        let start = TimePoint::new(9, 0).unwrap();
+        let end = TimePoint::new(17, 30).unwrap();
+        let offset = UtcOffset::from_hms(1, 0, 0).unwrap();
+        let duration = WorkRecord::calculate_duration_with_offsets(&start, offset, &end, offset);
+        assert_eq!(duration, WorkRecord::calculate_duration(&start, &end));
+    }
+
+    #[test]
+    fn test_calculate_duration_spring_forward() {
+        // Clocks jump from 02:00 (+01:00) to 03:00 (+02:00): 01:30 → 03:30 is one real hour
+        let start = TimePoint::new(1, 30).unwrap();
+        let end = TimePoint::new(3, 30).unwrap();
+        let duration = WorkRecord::calculate_duration_with_offsets(
+            &start,
+            UtcOffset::from_hms(1, 0, 0).unwrap(),
+            &end,
+            UtcOffset::from_hms(2, 0, 0).unwrap(),
+        );
+        assert_eq!(duration, 60);
+    }
+
+    #[test]
+    fn test_calculate_duration_fall_back() {
+        // Clocks fall back from 03:00 (+02:00) to 02:00 (+01:00): 01:30 → 02:30 is two real hours
+        let start = TimePoint::new(1, 30).unwrap();
+        let end = TimePoint::new(2, 30).unwrap();
+        let duration = WorkRecord::calculate_duration_with_offsets(
+            &start,
+            UtcOffset::from_hms(2, 0, 0).unwrap(),
+            &end,
+            UtcOffset::from_hms(1, 0, 0).unwrap(),
+        );
+        assert_eq!(duration, 120);
+    }
+
+    #[test]
+    fn test_calculate_duration_with_offsets_overnight() {
+        // Overnight session ending after a fall-back transition
+        let start = TimePoint::new(22, 0).unwrap();
+        let end = TimePoint::new(4, 0).unwrap();
+        let duration = WorkRecord::calculate_duration_with_offsets(
+            &start,
+            UtcOffset::from_hms(2, 0, 0).unwrap(),
+            &end,
+            UtcOffset::from_hms(1, 0, 0).unwrap(),
+        );
+        assert_eq!(duration, 7 * 60);
+    }
+
+    #[test]
+    fn test_calculate_duration_with_offsets_never_negative() {
+        let time = TimePoint::new(3, 0).unwrap();
+        let duration = WorkRecord::calculate_duration_with_offsets(
+            &time,
+            UtcOffset::from_hms(1, 0, 0).unwrap(),
+            &time,
+            UtcOffset::from_hms(2, 0, 0).unwrap(),
+        );
+        assert_eq!(duration, 0);
+    }
+
+    #[test]
+    fn test_update_duration_with_offsets() {
+        let start = TimePoint::new(1, 30).unwrap();
+        let end = TimePoint::new(3, 30).unwrap();
+        let mut record = WorkRecord::new(1, "Night shift".to_string(), start, end);
+        assert_eq!(record.total_minutes, 120);
+
+        record.update_duration_with_offsets(
+            UtcOffset::from_hms(1, 0, 0).unwrap(),
+            UtcOffset::from_hms(2, 0, 0).unwrap(),
+        );
+        assert_eq!(record.total_minutes, 60);
+    }
+
     #[test]
     fn test_format_duration_zero() {
         let start = TimePoint::new(9, 0).unwrap();
@@ -197,4 +441,176 @@ mod tests {
         assert_eq!(record1.end, record2.end);
         assert_eq!(record1.total_minutes, record2.total_minutes);
     }
+
+    fn dst_changes() -> Vec<OffsetChange> {
+        vec![
+            OffsetChange {
+                date: "2025-03-30".to_string(),
+                at: "02:00".to_string(),
+                shift_minutes: 60,
+            },
+            OffsetChange {
+                date: "2025-10-26".to_string(),
+                at: "03:00".to_string(),
+                shift_minutes: -60,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_calculate_duration_on_spring_forward_day() {
+        use time::macros::date;
+        let start = TimePoint::new(1, 30).unwrap();
+        let end = TimePoint::new(3, 30).unwrap();
+        let minutes =
+            WorkRecord::calculate_duration_on(date!(2025 - 03 - 30), &start, &end, &dst_changes());
+        assert_eq!(minutes, 60);
+    }
+
+    #[test]
+    fn test_calculate_duration_on_fall_back_day() {
+        use time::macros::date;
+        let start = TimePoint::new(1, 30).unwrap();
+        let end = TimePoint::new(3, 30).unwrap();
+        let minutes =
+            WorkRecord::calculate_duration_on(date!(2025 - 10 - 26), &start, &end, &dst_changes());
+        assert_eq!(minutes, 180);
+    }
+
+    #[test]
+    fn test_calculate_duration_on_ordinary_day_matches_wall_clock() {
+        use time::macros::date;
+        let start = TimePoint::new(1, 30).unwrap();
+        let end = TimePoint::new(3, 30).unwrap();
+        let minutes =
+            WorkRecord::calculate_duration_on(date!(2025 - 03 - 29), &start, &end, &dst_changes());
+        assert_eq!(minutes, WorkRecord::calculate_duration(&start, &end));
+    }
+
+    #[test]
+    fn test_calculate_duration_on_record_after_change_is_unaffected() {
+        use time::macros::date;
+        let start = TimePoint::new(9, 0).unwrap();
+        let end = TimePoint::new(17, 0).unwrap();
+        let minutes =
+            WorkRecord::calculate_duration_on(date!(2025 - 03 - 30), &start, &end, &dst_changes());
+        assert_eq!(minutes, 480);
+    }
+
+    #[test]
+    fn test_update_duration_on_overnight_record() {
+        use time::macros::date;
+        let mut record = WorkRecord::new(
+            1,
+            "Night shift".to_string(),
+            TimePoint::new(22, 0).unwrap(),
+            TimePoint::new(6, 0).unwrap(),
+        );
+        record.update_duration_on(date!(2025 - 10 - 26), &dst_changes());
+        // The fall-back on this date happened before 22:00, so the night is 8 hours
+        assert_eq!(record.total_minutes, 480);
+    }
+
+    #[test]
+    fn test_effective_minutes_with_adjustment() {
+        let mut record = WorkRecord::new(
+            1,
+            "Correction".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 0).unwrap(),
+        );
+        assert!(!record.is_adjustment());
+        assert_eq!(record.effective_minutes(), 60);
+
+        record.adjustment_minutes = Some(-90);
+        assert!(record.is_adjustment());
+        assert_eq!(record.effective_minutes(), -30);
+        assert_eq!(record.format_duration(), "-0h 30m");
+    }
+
+    #[test]
+    fn test_adjustment_is_optional_in_json() {
+        let json = r#"{"id":1,"name":"Task","start":{"hour":9,"minute":0},"end":{"hour":10,"minute":0},"total_minutes":60}"#;
+        let record: WorkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.adjustment_minutes, None);
+
+        let serialized = serde_json::to_string(&record).unwrap();
+        assert!(!serialized.contains("adjustment_minutes"));
+    }
+
+    #[test]
+    fn test_estimate_is_optional_in_json() {
+        let json = r#"{"id":1,"name":"Task","start":{"hour":9,"minute":0},"end":{"hour":10,"minute":0},"total_minutes":60}"#;
+        let mut record: WorkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.estimate_minutes, None);
+        assert!(!serde_json::to_string(&record).unwrap().contains("estimate"));
+
+        record.estimate_minutes = Some(90);
+        let serialized = serde_json::to_string(&record).unwrap();
+        assert!(serialized.contains(r#""estimate_minutes":90"#));
+        let reloaded: WorkRecord = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(reloaded.estimate_minutes, Some(90));
+    }
+
+    #[test]
+    fn test_estimate_status() {
+        let mut record = WorkRecord::new(
+            1,
+            "Task".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(11, 0).unwrap(),
+        );
+        assert_eq!(record.estimate_status(), None);
+
+        record.estimate_minutes = Some(90);
+        assert_eq!(record.estimate_status(), Some(EstimateStatus::Over));
+        record.estimate_minutes = Some(120);
+        assert_eq!(record.estimate_status(), Some(EstimateStatus::Within));
+
+        // A credit back brings it under
+        record.estimate_minutes = Some(90);
+        record.adjustment_minutes = Some(-30);
+        assert_eq!(record.estimate_status(), Some(EstimateStatus::Within));
+    }
+
+    #[test]
+    fn test_extra_fields_round_trip() {
+        let json = r#"{"id":1,"name":"Task","start":{"hour":9,"minute":0},"end":{"hour":10,"minute":0},"total_minutes":60,"extra":{"cost_center":"R&D","legacy":"kept"}}"#;
+        let record: WorkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.extra.get("cost_center").unwrap(), "R&D");
+
+        let serialized = serde_json::to_string(&record).unwrap();
+        let reloaded: WorkRecord = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(reloaded.extra, record.extra);
+        assert_eq!(reloaded.extra.get("legacy").unwrap(), "kept");
+    }
+
+    #[test]
+    fn test_extra_fields_omitted_when_empty() {
+        let record = WorkRecord::new(
+            1,
+            "Task".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 0).unwrap(),
+        );
+        let serialized = serde_json::to_string(&record).unwrap();
+        assert!(!serialized.contains("extra"));
+    }
+
+    #[test]
+    fn test_billable_follows_the_default_unless_marked() {
+        // Files written before the flag existed have none
+        let json = r#"{"id":1,"name":"Task","start":{"hour":9,"minute":0},"end":{"hour":10,"minute":0},"total_minutes":60}"#;
+        let mut record: WorkRecord = serde_json::from_str(json).unwrap();
+        assert_eq!(record.billable, None);
+        assert!(record.is_billable(true));
+        assert!(!record.is_billable(false));
+        assert!(!serde_json::to_string(&record).unwrap().contains("billable"));
+
+        record.billable = Some(false);
+        let serialized = serde_json::to_string(&record).unwrap();
+        assert!(serialized.contains(r#""billable":false"#));
+        let reloaded: WorkRecord = serde_json::from_str(&serialized).unwrap();
+        assert!(!reloaded.is_billable(true));
+    }
 }
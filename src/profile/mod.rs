@@ -0,0 +1,225 @@
+//! Named profiles for keeping separate sets of time data
+//!
+//! The default profile uses the original locations (`<config>/work-tuimer/config.toml`
+//! and `<data>/work-tuimer/`). A named profile lives under `profiles/NAME/` in both
+//! the config and data directories, so each has its own config, day files and
+//! running timer.
+
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name shown for (and accepted as) the default profile
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable selecting a profile when `--profile` isn't given
+pub const PROFILE_ENV_VAR: &str = "WORK_TUIMER_PROFILE";
+
+/// Directory holding named profiles, inside both config and data directories
+const PROFILES_DIR: &str = "profiles";
+
+/// The active profile
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Profile {
+    /// None for the default profile
+    name: Option<String>,
+}
+
+impl Profile {
+    /// Profile with the given name ("default" selects the default profile)
+    ///
+    /// # Errors
+    /// Returns an error if the name is empty or contains characters other than
+    /// letters, digits, `-` and `_`
+    pub fn named(name: &str) -> Result<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Profile name cannot be empty"));
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow!(
+                "Invalid profile name '{}': use letters, digits, '-' and '_'",
+                name
+            ));
+        }
+        if name == DEFAULT_PROFILE {
+            return Ok(Profile::default());
+        }
+        Ok(Profile {
+            name: Some(name.to_string()),
+        })
+    }
+
+    /// Resolve the active profile from the `--profile` flag, then `WORK_TUIMER_PROFILE`
+    pub fn resolve(flag: Option<&str>) -> Result<Self> {
+        let env = std::env::var(PROFILE_ENV_VAR).ok();
+        Self::resolve_from(flag, env.as_deref())
+    }
+
+    /// Resolve with an explicit environment value (the flag wins over the env var)
+    pub fn resolve_from(flag: Option<&str>, env: Option<&str>) -> Result<Self> {
+        match flag.or(env.filter(|value| !value.trim().is_empty())) {
+            Some(name) => Self::named(name),
+            None => Ok(Profile::default()),
+        }
+    }
+
+    /// Profile name, "default" for the default profile
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name.is_none()
+    }
+
+    /// Directory holding this profile's config, given the app's config directory
+    pub fn config_dir_in(&self, config_root: &Path) -> PathBuf {
+        match &self.name {
+            Some(name) => config_root.join(PROFILES_DIR).join(name),
+            None => config_root.to_path_buf(),
+        }
+    }
+
+    /// Path of this profile's config.toml, given the app's config directory
+    pub fn config_path_in(&self, config_root: &Path) -> PathBuf {
+        self.config_dir_in(config_root).join("config.toml")
+    }
+
+    /// Directory holding this profile's day files and timer, given the app's data directory
+    pub fn data_dir_in(&self, data_root: &Path) -> PathBuf {
+        match &self.name {
+            Some(name) => data_root.join(PROFILES_DIR).join(name),
+            None => data_root.to_path_buf(),
+        }
+    }
+
+    /// Names of all profiles found under the config directory, default first
+    pub fn list_in(config_root: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(config_root.join(PROFILES_DIR))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| Profile::named(name).is_ok_and(|p| !p.is_default()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+    use crate::timer::TimerManager;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_default_profile_keeps_original_paths() {
+        let profile = Profile::default();
+        let root = Path::new("/cfg/work-tuimer");
+
+        assert_eq!(profile.name(), "default");
+        assert_eq!(
+            profile.config_path_in(root),
+            PathBuf::from("/cfg/work-tuimer/config.toml")
+        );
+        assert_eq!(
+            profile.data_dir_in(Path::new("/data/work-tuimer")),
+            PathBuf::from("/data/work-tuimer")
+        );
+    }
+
+    #[test]
+    fn test_named_profile_paths() {
+        let profile = Profile::named("acme").unwrap();
+
+        assert_eq!(
+            profile.config_path_in(Path::new("/cfg/work-tuimer")),
+            PathBuf::from("/cfg/work-tuimer/profiles/acme/config.toml")
+        );
+        assert_eq!(
+            profile.data_dir_in(Path::new("/data/work-tuimer")),
+            PathBuf::from("/data/work-tuimer/profiles/acme")
+        );
+    }
+
+    #[test]
+    fn test_named_default_is_default_profile() {
+        assert!(Profile::named("default").unwrap().is_default());
+    }
+
+    #[test]
+    fn test_invalid_profile_names() {
+        assert!(Profile::named("").is_err());
+        assert!(Profile::named("../escape").is_err());
+        assert!(Profile::named("has space").is_err());
+    }
+
+    #[test]
+    fn test_resolve_flag_wins_over_env() {
+        let profile = Profile::resolve_from(Some("acme"), Some("globex")).unwrap();
+        assert_eq!(profile.name(), "acme");
+
+        let profile = Profile::resolve_from(None, Some("globex")).unwrap();
+        assert_eq!(profile.name(), "globex");
+
+        let profile = Profile::resolve_from(None, Some("")).unwrap();
+        assert!(profile.is_default());
+
+        let profile = Profile::resolve_from(None, None).unwrap();
+        assert!(profile.is_default());
+    }
+
+    #[test]
+    fn test_list_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("profiles/globex")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("profiles/acme")).unwrap();
+
+        assert_eq!(
+            Profile::list_in(temp_dir.path()),
+            vec!["default", "acme", "globex"]
+        );
+    }
+
+    #[test]
+    fn test_timers_are_isolated_between_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let acme = Profile::named("acme").unwrap();
+        let globex = Profile::named("globex").unwrap();
+
+        let acme_timers =
+            TimerManager::new(Storage::new_with_dir(acme.data_dir_in(temp_dir.path())).unwrap());
+        let globex_timers =
+            TimerManager::new(Storage::new_with_dir(globex.data_dir_in(temp_dir.path())).unwrap());
+        let default_timers = TimerManager::new(
+            Storage::new_with_dir(Profile::default().data_dir_in(temp_dir.path())).unwrap(),
+        );
+
+        acme_timers
+            .start("Acme task".to_string(), None, None, None)
+            .unwrap();
+
+        assert!(acme_timers.status().unwrap().is_some());
+        assert!(globex_timers.status().unwrap().is_none());
+        assert!(default_timers.status().unwrap().is_none());
+
+        // Each profile can run its own timer at the same time
+        globex_timers
+            .start("Globex task".to_string(), None, None, None)
+            .unwrap();
+        assert_eq!(
+            acme_timers.status().unwrap().unwrap().task_name,
+            "Acme task"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Estimated against actual time, summed per task or project (`report estimates`)
+//!
+//! Only records with an estimate count, so the totals compare like with like.
+
+use crate::models::{DayData, NO_PROJECT_LABEL};
+use std::collections::BTreeMap;
+
+/// Estimated and actual minutes of every estimated record sharing a task or project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EstimateAccuracy {
+    pub key: String,
+    pub records: usize,
+    pub estimated_minutes: u32,
+    pub actual_minutes: u32,
+}
+
+impl EstimateAccuracy {
+    /// Actual time as a percentage of the estimate (100 is spot on)
+    pub fn percent(&self) -> u32 {
+        if self.estimated_minutes == 0 {
+            return 0;
+        }
+        (self.actual_minutes as u64 * 100 / self.estimated_minutes as u64) as u32
+    }
+}
+
+/// Estimation accuracy over `days`, per task name or per project, sorted by key
+pub fn estimate_accuracy(days: &[DayData], by_project: bool) -> Vec<EstimateAccuracy> {
+    let mut groups: BTreeMap<String, EstimateAccuracy> = BTreeMap::new();
+    for record in days.iter().flat_map(|day| day.work_records.values()) {
+        let Some(estimate) = record.estimate_minutes else {
+            continue;
+        };
+        let key = if by_project {
+            record.project.as_deref().unwrap_or(NO_PROJECT_LABEL)
+        } else {
+            record.name.as_str()
+        };
+        let group = groups
+            .entry(key.to_string())
+            .or_insert_with(|| EstimateAccuracy {
+                key: key.to_string(),
+                records: 0,
+                estimated_minutes: 0,
+                actual_minutes: 0,
+            });
+        group.records += 1;
+        group.estimated_minutes += estimate;
+        group.actual_minutes += record.effective_minutes().max(0) as u32;
+    }
+    groups.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use time::macros::date;
+
+    fn record(id: u32, name: &str, hours: (u8, u8), estimate: Option<u32>) -> WorkRecord {
+        let mut record = WorkRecord::new(
+            id,
+            name.to_string(),
+            TimePoint::new(hours.0, 0).unwrap(),
+            TimePoint::new(hours.1, 0).unwrap(),
+        );
+        record.estimate_minutes = estimate;
+        record
+    }
+
+    fn days() -> Vec<DayData> {
+        let mut monday = DayData::new(date!(2025 - 11 - 03));
+        let mut review = record(1, "Review", (9, 11), Some(90));
+        review.project = Some("Acme".to_string());
+        monday.add_record(review);
+        monday.add_record(record(2, "Docs", (11, 12), Some(60)));
+        monday.add_record(record(3, "Email", (12, 13), None));
+
+        let mut tuesday = DayData::new(date!(2025 - 11 - 04));
+        let mut review = record(1, "Review", (9, 10), Some(90));
+        review.project = Some("Acme".to_string());
+        tuesday.add_record(review);
+        vec![monday, tuesday]
+    }
+
+    #[test]
+    fn test_accuracy_per_task() {
+        let accuracy = estimate_accuracy(&days(), false);
+        assert_eq!(
+            accuracy,
+            [
+                EstimateAccuracy {
+                    key: "Docs".to_string(),
+                    records: 1,
+                    estimated_minutes: 60,
+                    actual_minutes: 60,
+                },
+                EstimateAccuracy {
+                    key: "Review".to_string(),
+                    records: 2,
+                    estimated_minutes: 180,
+                    actual_minutes: 180,
+                },
+            ]
+        );
+        assert_eq!(accuracy[1].percent(), 100);
+    }
+
+    #[test]
+    fn test_accuracy_per_project_skips_unestimated_records() {
+        let accuracy = estimate_accuracy(&days(), true);
+        let keys: Vec<&str> = accuracy.iter().map(|group| group.key.as_str()).collect();
+        assert_eq!(keys, ["Acme", NO_PROJECT_LABEL]);
+        // Email has no estimate, so only Docs counts for records without a project
+        assert_eq!(accuracy[1].records, 1);
+        assert_eq!(accuracy[1].actual_minutes, 60);
+    }
+
+    #[test]
+    fn test_percent_over_estimate() {
+        let group = EstimateAccuracy {
+            key: "Review".to_string(),
+            records: 1,
+            estimated_minutes: 90,
+            actual_minutes: 120,
+        };
+        assert_eq!(group.percent(), 133);
+    }
+}
@@ -0,0 +1,262 @@
+//! How timer sessions went: length, pauses and false starts (`report focus`)
+//!
+//! Built from the session journal rather than the day files, since records are
+//! merged and hand-edited while sessions keep what the timer measured. Sessions
+//! journaled before pauses were tracked count with their wall-clock span and no
+//! pauses.
+
+use crate::models::WorkRecord;
+use crate::timefmt::format_minutes;
+use crate::timer::SessionLogEntry;
+
+/// Sessions shorter than this count as false starts
+pub const FALSE_START_SECS: u64 = 2 * 60;
+
+/// Focus figures over a run of days
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FocusStats {
+    /// Days in the range, including those without sessions
+    pub days: u32,
+    /// Running seconds of each session
+    lengths: Vec<u64>,
+    /// Pauses across all sessions
+    pub pauses: u32,
+    /// Seconds spent paused across all sessions
+    pub paused_secs: u64,
+    /// Sessions shorter than `FALSE_START_SECS`
+    pub false_starts: usize,
+    /// Longest session that was never paused, in seconds
+    pub longest_unbroken: Option<u64>,
+}
+
+/// Running seconds of `session`, from the journal or its start and end times
+pub fn session_secs(session: &SessionLogEntry) -> u64 {
+    session.focus.active_secs.unwrap_or_else(|| {
+        u64::from(WorkRecord::calculate_duration(&session.start, &session.end)) * 60
+    })
+}
+
+impl FocusStats {
+    /// Statistics over `days` days holding `sessions`
+    pub fn from_sessions<'a>(
+        days: u32,
+        sessions: impl IntoIterator<Item = &'a SessionLogEntry>,
+    ) -> Self {
+        let mut stats = FocusStats {
+            days,
+            ..Self::default()
+        };
+        for session in sessions {
+            stats.add(session);
+        }
+        stats
+    }
+
+    fn add(&mut self, session: &SessionLogEntry) {
+        let secs = session_secs(session);
+        self.lengths.push(secs);
+        self.pauses += session.focus.pauses;
+        self.paused_secs += session.focus.paused_secs;
+        if secs < FALSE_START_SECS {
+            self.false_starts += 1;
+        }
+        if session.focus.pauses == 0 {
+            self.longest_unbroken = self.longest_unbroken.max(Some(secs));
+        }
+    }
+
+    pub fn sessions(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Sessions per day over the whole range, empty days included
+    pub fn sessions_per_day(&self) -> f64 {
+        if self.days == 0 {
+            return 0.0;
+        }
+        self.sessions() as f64 / f64::from(self.days)
+    }
+
+    /// Mean session length in seconds, None without sessions
+    pub fn mean_secs(&self) -> Option<u64> {
+        (!self.lengths.is_empty())
+            .then(|| self.lengths.iter().sum::<u64>() / self.lengths.len() as u64)
+    }
+
+    /// Median session length in seconds, None without sessions
+    pub fn median_secs(&self) -> Option<u64> {
+        let mut lengths = self.lengths.clone();
+        lengths.sort_unstable();
+        let middle = lengths.len() / 2;
+        match lengths.len() {
+            0 => None,
+            len if len % 2 == 0 => Some((lengths[middle - 1] + lengths[middle]) / 2),
+            _ => Some(lengths[middle]),
+        }
+    }
+
+    /// Mean pauses per session, None without sessions
+    pub fn mean_pauses(&self) -> Option<f64> {
+        (!self.lengths.is_empty()).then(|| f64::from(self.pauses) / self.sessions() as f64)
+    }
+
+    /// Report lines, one figure each
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Sessions:         {} over {} {} ({:.1} per day)",
+            self.sessions(),
+            self.days,
+            if self.days == 1 { "day" } else { "days" },
+            self.sessions_per_day()
+        )];
+        let (Some(mean), Some(median), Some(pauses)) =
+            (self.mean_secs(), self.median_secs(), self.mean_pauses())
+        else {
+            return lines;
+        };
+        lines.push(format!(
+            "Length:           mean {}, median {}",
+            format_secs(mean),
+            format_secs(median)
+        ));
+        lines.push(format!(
+            "Pauses:           {:.1} per session ({} paused)",
+            pauses,
+            format_secs(self.paused_secs)
+        ));
+        lines.push(format!(
+            "False starts:     {} (under {} min)",
+            self.false_starts,
+            FALSE_START_SECS / 60
+        ));
+        lines.push(format!(
+            "Longest unbroken: {}",
+            self.longest_unbroken
+                .map_or("none".to_string(), format_secs)
+        ));
+        lines
+    }
+
+    /// Short form for the header: "3 sessions, 2 pauses, 1 false start"
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, word: &str| {
+            format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+        };
+        let mut text = format!(
+            "{}, {}",
+            plural(self.sessions(), "session"),
+            plural(self.pauses as usize, "pause")
+        );
+        if self.false_starts > 0 {
+            text.push_str(&format!(", {}", plural(self.false_starts, "false start")));
+        }
+        text
+    }
+}
+
+fn format_secs(secs: u64) -> String {
+    format_minutes((secs / 60) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+    use crate::timer::SessionFocus;
+    use time::{Date, Month};
+
+    fn session(active_mins: Option<u64>, pauses: u32) -> SessionLogEntry {
+        SessionLogEntry {
+            date: Date::from_calendar_date(2025, Month::November, 6).unwrap(),
+            record_id: 1,
+            start: TimePoint::new(9, 0).unwrap(),
+            end: TimePoint::new(10, 30).unwrap(),
+            extended: false,
+            focus: SessionFocus {
+                active_secs: active_mins.map(|mins| mins * 60),
+                pauses,
+                paused_secs: u64::from(pauses) * 300,
+            },
+        }
+    }
+
+    #[test]
+    fn test_no_sessions_over_empty_days() {
+        let stats = FocusStats::from_sessions(7, &[]);
+        assert_eq!(stats.sessions(), 0);
+        assert_eq!(stats.sessions_per_day(), 0.0);
+        assert_eq!(stats.mean_secs(), None);
+        assert_eq!(stats.median_secs(), None);
+        assert_eq!(stats.mean_pauses(), None);
+        assert_eq!(
+            stats.lines(),
+            vec!["Sessions:         0 over 7 days (0.0 per day)"]
+        );
+    }
+
+    #[test]
+    fn test_empty_days_lower_sessions_per_day() {
+        let sessions = [session(Some(30), 0), session(Some(60), 1)];
+        let stats = FocusStats::from_sessions(4, &sessions);
+        assert_eq!(stats.sessions_per_day(), 0.5);
+    }
+
+    #[test]
+    fn test_mean_and_median_lengths() {
+        let sessions = [
+            session(Some(10), 0),
+            session(Some(20), 0),
+            session(Some(90), 0),
+        ];
+        let stats = FocusStats::from_sessions(1, &sessions);
+        assert_eq!(stats.mean_secs(), Some(40 * 60));
+        assert_eq!(stats.median_secs(), Some(20 * 60));
+
+        let even = [session(Some(10), 0), session(Some(20), 0)];
+        assert_eq!(
+            FocusStats::from_sessions(1, &even).median_secs(),
+            Some(15 * 60)
+        );
+    }
+
+    #[test]
+    fn test_pauses_false_starts_and_longest_unbroken() {
+        let sessions = [
+            session(Some(1), 0),
+            session(Some(45), 0),
+            session(Some(120), 3),
+            session(Some(30), 1),
+        ];
+        let stats = FocusStats::from_sessions(1, &sessions);
+        assert_eq!(stats.pauses, 4);
+        assert_eq!(stats.paused_secs, 4 * 300);
+        assert_eq!(stats.mean_pauses(), Some(1.0));
+        assert_eq!(stats.false_starts, 1);
+        // The two-hour session was paused, so it isn't unbroken
+        assert_eq!(stats.longest_unbroken, Some(45 * 60));
+        assert_eq!(stats.summary(), "4 sessions, 4 pauses, 1 false start");
+    }
+
+    #[test]
+    fn test_old_entries_use_wall_clock_span() {
+        let stats = FocusStats::from_sessions(1, &[session(None, 0)]);
+        assert_eq!(stats.mean_secs(), Some(90 * 60));
+        assert_eq!(stats.longest_unbroken, Some(90 * 60));
+    }
+
+    #[test]
+    fn test_lines() {
+        let sessions = [session(Some(30), 0), session(Some(60), 2)];
+        let stats = FocusStats::from_sessions(1, &sessions);
+        assert_eq!(
+            stats.lines(),
+            vec![
+                "Sessions:         2 over 1 day (2.0 per day)",
+                "Length:           mean 0h 45m, median 0h 45m",
+                "Pauses:           1.0 per session (0h 10m paused)",
+                "False starts:     0 (under 2 min)",
+                "Longest unbroken: 0h 30m",
+            ]
+        );
+    }
+}
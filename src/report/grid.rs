@@ -0,0 +1,367 @@
+//! Tasks × weekdays totals for one week (`report grid`, `w` in the calendar)
+//!
+//! Rows are keyed like the summary (`[ui] group_by`), with runs of whitespace
+//! collapsed so "Fix  login" and "Fix login " share a row. Breaks are left out.
+
+use crate::config::Locale;
+use crate::models::{DayData, GroupBy};
+use crate::timefmt::{format_minutes, short_weekday_name};
+use crate::ui::text_width::{display_width, truncate_to_width};
+use time::{Date, Duration, Weekday};
+
+/// Widest task column in the text grid before names are cut with "…"
+pub const MAX_NAME_WIDTH: usize = 28;
+
+/// Width of a day or total column ("12h 30m")
+pub const CELL_WIDTH: usize = 7;
+
+/// The day on or before `date` that falls on `first`, where its week begins
+pub fn week_containing(date: Date, first: Weekday) -> Date {
+    let back = (date.weekday().number_days_from_monday() + 7 - first.number_days_from_monday()) % 7;
+    date - Duration::days(back as i64)
+}
+
+/// One task's minutes on each day of the week
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridRow {
+    pub key: String,
+    pub minutes: [u32; 7],
+}
+
+impl GridRow {
+    pub fn total(&self) -> u32 {
+        self.minutes.iter().sum()
+    }
+}
+
+/// Worked minutes per task and day over seven consecutive days
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeekGrid {
+    pub dates: [Date; 7],
+    /// Largest weekly total first, ties by key
+    pub rows: Vec<GridRow>,
+}
+
+impl WeekGrid {
+    /// Sum `days` into rows keyed by `by`
+    ///
+    /// A task's adjustments are netted per day, and a day where they exceed the
+    /// tracked time counts as zero, as in the summary.
+    pub fn build(days: &[DayData; 7], by: GroupBy) -> Self {
+        let mut rows: Vec<GridRow> = Vec::new();
+        for (column, day) in days.iter().enumerate() {
+            for (key, minutes) in day_totals(day, by) {
+                match rows.iter_mut().find(|row| row.key == key) {
+                    Some(row) => row.minutes[column] += minutes,
+                    None => {
+                        let mut row = GridRow {
+                            key,
+                            minutes: [0; 7],
+                        };
+                        row.minutes[column] = minutes;
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+        rows.retain(|row| row.total() > 0);
+        rows.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.key.cmp(&b.key)));
+
+        Self {
+            dates: days.each_ref().map(|day| day.date),
+            rows,
+        }
+    }
+
+    pub fn column_totals(&self) -> [u32; 7] {
+        let mut totals = [0; 7];
+        for row in &self.rows {
+            for (total, minutes) in totals.iter_mut().zip(row.minutes) {
+                *total += minutes;
+            }
+        }
+        totals
+    }
+
+    pub fn grand_total(&self) -> u32 {
+        self.rows.iter().map(GridRow::total).sum()
+    }
+
+    /// "Mon 03" for each column
+    pub fn day_labels(&self, locale: Locale) -> [String; 7] {
+        self.dates.map(|date| {
+            format!(
+                "{} {:02}",
+                short_weekday_name(date.weekday(), locale),
+                date.day()
+            )
+        })
+    }
+
+    /// Aligned text: a header, a row per task, then the day totals
+    pub fn lines(&self, locale: Locale) -> Vec<String> {
+        let name_width = self
+            .rows
+            .iter()
+            .map(|row| display_width(&row.key))
+            .max()
+            .unwrap_or(0)
+            .clamp("Total".len(), MAX_NAME_WIDTH);
+        let line = |name: &str, cells: Vec<String>| {
+            let mut line = pad(&truncate_to_width(name, name_width), name_width);
+            for cell in cells {
+                line.push_str(&format!("  {:>width$}", cell, width = CELL_WIDTH));
+            }
+            line
+        };
+
+        let mut header: Vec<String> = self.day_labels(locale).into();
+        header.push("Total".to_string());
+        let mut lines = vec![line("Task", header)];
+        for row in &self.rows {
+            lines.push(line(&row.key, cells(row.minutes, row.total())));
+        }
+        lines.push(line(
+            "Total",
+            cells(self.column_totals(), self.grand_total()),
+        ));
+        lines
+    }
+
+    /// CSV with a header of ISO dates, minutes in each cell and a final totals row
+    pub fn csv(&self) -> String {
+        let mut header = vec!["task".to_string()];
+        header.extend(self.dates.iter().map(Date::to_string));
+        header.push("total".to_string());
+
+        let mut csv = csv_line(&header);
+        for row in &self.rows {
+            csv.push_str(&csv_line(&minutes_fields(
+                &row.key,
+                row.minutes,
+                row.total(),
+            )));
+        }
+        csv.push_str(&csv_line(&minutes_fields(
+            "total",
+            self.column_totals(),
+            self.grand_total(),
+        )));
+        csv
+    }
+}
+
+/// Worked minutes per normalized key on `day`
+fn day_totals(day: &DayData, by: GroupBy) -> Vec<(String, u32)> {
+    let mut totals: Vec<(String, i64)> = Vec::new();
+    for record in day
+        .work_records
+        .values()
+        .filter(|record| !record.is_break())
+    {
+        let key = normalize(&record.group_key(by));
+        match totals.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, minutes)) => *minutes += record.effective_minutes(),
+            None => totals.push((key, record.effective_minutes())),
+        }
+    }
+    totals
+        .into_iter()
+        .map(|(key, minutes)| (key, minutes.clamp(0, u32::MAX as i64) as u32))
+        .collect()
+}
+
+/// Trim and collapse runs of whitespace
+fn normalize(key: &str) -> String {
+    key.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A grid cell: "1h 30m", or "·" for a day without time on the task
+pub fn cell_text(minutes: u32) -> String {
+    match minutes {
+        0 => "·".to_string(),
+        minutes => format_minutes(minutes),
+    }
+}
+
+/// Day cells then the total
+fn cells(minutes: [u32; 7], total: u32) -> Vec<String> {
+    minutes
+        .iter()
+        .chain([&total])
+        .map(|&minutes| cell_text(minutes))
+        .collect()
+}
+
+fn minutes_fields(name: &str, minutes: [u32; 7], total: u32) -> Vec<String> {
+    let mut fields = vec![name.to_string()];
+    fields.extend(minutes.iter().chain([&total]).map(u32::to_string));
+    fields
+}
+
+/// `text` followed by spaces up to `width` columns
+fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+fn csv_line(fields: &[String]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+/// Quote a field holding a comma, quote or line break, doubling inner quotes
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use time::macros::date;
+
+    /// Day offset from Monday, name, start and end
+    type Entry<'a> = (i64, &'a str, (u8, u8), (u8, u8));
+
+    /// The week of Monday 2025-11-03, with records on the given day offsets
+    fn week(records: &[Entry]) -> [DayData; 7] {
+        let monday = date!(2025 - 11 - 03);
+        let mut days = std::array::from_fn(|i| DayData::new(monday + Duration::days(i as i64)));
+        for (id, &(offset, name, start, end)) in records.iter().enumerate() {
+            days[offset as usize].add_record(WorkRecord::new(
+                id as u32 + 1,
+                name.to_string(),
+                TimePoint::new(start.0, start.1).unwrap(),
+                TimePoint::new(end.0, end.1).unwrap(),
+            ));
+        }
+        days
+    }
+
+    #[test]
+    fn test_week_containing() {
+        let thursday = date!(2025 - 11 - 06);
+        assert_eq!(
+            week_containing(thursday, Weekday::Monday),
+            date!(2025 - 11 - 03)
+        );
+        assert_eq!(
+            week_containing(thursday, Weekday::Sunday),
+            date!(2025 - 11 - 02)
+        );
+        assert_eq!(
+            week_containing(date!(2025 - 11 - 03), Weekday::Monday),
+            date!(2025 - 11 - 03)
+        );
+    }
+
+    #[test]
+    fn test_rows_ordered_by_weekly_total() {
+        let grid = WeekGrid::build(
+            &week(&[
+                (0, "Review", (9, 0), (10, 0)),
+                (0, "Coding", (10, 0), (11, 0)),
+                (2, "Coding", (9, 0), (11, 0)),
+                (4, "Admin", (9, 0), (10, 0)),
+                (4, "Lunch break", (12, 0), (13, 0)),
+            ]),
+            GroupBy::Name,
+        );
+
+        let keys: Vec<&str> = grid.rows.iter().map(|row| row.key.as_str()).collect();
+        assert_eq!(keys, vec!["Coding", "Admin", "Review"]);
+        assert_eq!(grid.rows[0].minutes, [60, 0, 120, 0, 0, 0, 0]);
+        assert_eq!(grid.rows[0].total(), 180);
+    }
+
+    #[test]
+    fn test_totals_agree() {
+        let grid = WeekGrid::build(
+            &week(&[
+                (0, "PROJ-1 Review", (9, 0), (10, 30)),
+                (1, "PROJ-1  Review ", (9, 0), (9, 45)),
+                (1, "PROJ-2 Fix", (10, 0), (12, 0)),
+                (6, "PROJ-2 Deploy", (20, 0), (20, 30)),
+            ]),
+            GroupBy::Ticket,
+        );
+
+        assert_eq!(grid.rows.len(), 2);
+        assert_eq!(grid.rows[0].key, "PROJ-2");
+        assert_eq!(grid.column_totals(), [90, 165, 0, 0, 0, 0, 30]);
+        let row_sum: u32 = grid.rows.iter().map(GridRow::total).sum();
+        let column_sum: u32 = grid.column_totals().iter().sum();
+        assert_eq!(row_sum, grid.grand_total());
+        assert_eq!(column_sum, grid.grand_total());
+        assert_eq!(grid.grand_total(), 285);
+    }
+
+    #[test]
+    fn test_whitespace_variants_share_a_row() {
+        let grid = WeekGrid::build(
+            &week(&[
+                (0, "Fix  login", (9, 0), (10, 0)),
+                (3, " Fix login", (9, 0), (10, 0)),
+            ]),
+            GroupBy::Name,
+        );
+        assert_eq!(grid.rows.len(), 1);
+        assert_eq!(grid.rows[0].key, "Fix login");
+        assert_eq!(grid.rows[0].minutes, [60, 0, 0, 60, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_text_lines_truncate_long_names() {
+        let long = "A task name far too long for the grid's task column";
+        let grid = WeekGrid::build(&week(&[(0, long, (9, 0), (10, 0))]), GroupBy::Name);
+        let lines = grid.lines(Locale::En);
+
+        assert!(lines[0].starts_with("Task "));
+        assert!(lines[0].contains("Mon 03"));
+        assert!(lines[0].ends_with("Total"));
+        assert!(lines[1].starts_with("A task name far too long fo…"));
+        assert!(lines[1].contains("1h 00m        ·"));
+        assert!(lines[2].starts_with("Total"));
+        assert!(
+            lines
+                .iter()
+                .all(|line| display_width(line) == display_width(&lines[0]))
+        );
+    }
+
+    #[test]
+    fn test_csv_escaping() {
+        assert_eq!(csv_field("Review"), "Review");
+        assert_eq!(csv_field("Review, part 2"), "\"Review, part 2\"");
+        assert_eq!(csv_field("The \"big\" one"), "\"The \"\"big\"\" one\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_csv_has_dates_minutes_and_totals() {
+        let grid = WeekGrid::build(
+            &week(&[
+                (0, "Review, part 2", (9, 0), (10, 0)),
+                (2, "Coding", (9, 0), (11, 0)),
+            ]),
+            GroupBy::Name,
+        );
+        assert_eq!(
+            grid.csv(),
+            "task,2025-11-03,2025-11-04,2025-11-05,2025-11-06,2025-11-07,2025-11-08,2025-11-09,total\n\
+             Coding,0,0,120,0,0,0,0,120\n\
+             \"Review, part 2\",60,0,0,0,0,0,0,60\n\
+             total,60,0,120,0,0,0,0,180\n"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! When in the day work happens, in minutes per hour (`report heatmap`, `[ui] heat_strip`)
+//!
+//! Each record's span is sliced at hour boundaries. A record running past midnight
+//! carries on in the early hours of the same strip, since only the time of day
+//! matters here. Breaks are left out.
+
+use crate::models::{DayData, WorkRecord};
+use time::Weekday;
+
+/// Blocks of increasing density, from no work in the hour to a full hour
+const LEVELS: [char; 9] = ['·', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Minutes of `record` falling in each hour of the day
+pub fn hourly_minutes(record: &WorkRecord) -> [u32; 24] {
+    let mut hours = [0; 24];
+    let mut minute = record.start.to_minutes_since_midnight();
+    let mut left = WorkRecord::calculate_duration(&record.start, &record.end);
+    while left > 0 {
+        let in_hour = (60 - minute % 60).min(left);
+        hours[(minute / 60) as usize] += in_hour;
+        left -= in_hour;
+        minute = (minute + in_hour) % (24 * 60);
+    }
+    hours
+}
+
+/// Worked minutes in each hour of `day`
+pub fn day_hours(day: &DayData) -> [u32; 24] {
+    let mut hours = [0; 24];
+    for record in day
+        .work_records
+        .values()
+        .filter(|record| !record.is_break())
+    {
+        for (total, minutes) in hours.iter_mut().zip(hourly_minutes(record)) {
+            *total += minutes;
+        }
+    }
+    hours
+}
+
+/// Block for `minutes` worked in an hour: "·" for none up to "█" for all of it
+pub fn shade(minutes: u32) -> char {
+    LEVELS[(minutes * 8).div_ceil(60).min(8) as usize]
+}
+
+/// One character per hour of `hours`, midnight first
+pub fn strip(hours: &[u32; 24]) -> String {
+    hours.iter().map(|&minutes| shade(minutes)).collect()
+}
+
+/// Worked minutes per hour and weekday, summed over the days added
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeatGrid {
+    /// Indexed by days from Monday, then hour
+    minutes: [[u32; 24]; 7],
+    days: [u32; 7],
+}
+
+impl HeatGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count `day`, even an empty one, so averages are per calendar day
+    pub fn add_day(&mut self, day: &DayData) {
+        let weekday = day.date.weekday().number_days_from_monday() as usize;
+        self.days[weekday] += 1;
+        for (total, minutes) in self.minutes[weekday].iter_mut().zip(day_hours(day)) {
+            *total += minutes;
+        }
+    }
+
+    /// Average minutes worked in `hour` on `weekday`, 0 when no such day was added
+    pub fn average(&self, weekday: Weekday, hour: usize) -> u32 {
+        let weekday = weekday.number_days_from_monday() as usize;
+        match self.days[weekday] {
+            0 => 0,
+            days => (self.minutes[weekday][hour] as f64 / days as f64).round() as u32,
+        }
+    }
+
+    /// Text grid: an hour ruler, then a row per weekday starting at `first`
+    pub fn lines(&self, first: Weekday) -> Vec<String> {
+        let mut ruler = "    ".to_string();
+        for hour in (0..24).step_by(3) {
+            ruler.push_str(&format!("{:<6}", hour));
+        }
+        let mut lines = vec![ruler.trim_end().to_string()];
+
+        let mut weekday = first;
+        for _ in 0..7 {
+            let mut line = format!("{:<4}", &weekday.to_string()[..3]);
+            for hour in 0..24 {
+                let block = shade(self.average(weekday, hour));
+                line.push(block);
+                line.push(block);
+            }
+            lines.push(line);
+            weekday = weekday.next();
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+    use time::macros::date;
+
+    fn record(name: &str, start: (u8, u8), end: (u8, u8)) -> WorkRecord {
+        WorkRecord::new(
+            1,
+            name.to_string(),
+            TimePoint::new(start.0, start.1).unwrap(),
+            TimePoint::new(end.0, end.1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_record_within_one_hour() {
+        let hours = hourly_minutes(&record("Review", (9, 10), (9, 40)));
+        assert_eq!(hours[9], 30);
+        assert_eq!(hours.iter().sum::<u32>(), 30);
+    }
+
+    #[test]
+    fn test_record_crossing_hour_boundaries() {
+        let hours = hourly_minutes(&record("Review", (9, 45), (11, 15)));
+        assert_eq!(&hours[9..12], &[15, 60, 15]);
+        assert_eq!(hours.iter().sum::<u32>(), 90);
+    }
+
+    #[test]
+    fn test_record_crossing_midnight_wraps_to_early_hours() {
+        let hours = hourly_minutes(&record("Deploy", (23, 30), (1, 10)));
+        assert_eq!(hours[23], 30);
+        assert_eq!(hours[0], 60);
+        assert_eq!(hours[1], 10);
+        assert_eq!(hours.iter().sum::<u32>(), 100);
+    }
+
+    #[test]
+    fn test_empty_record_has_no_minutes() {
+        let hours = hourly_minutes(&record("Review", (9, 0), (9, 0)));
+        assert_eq!(hours, [0; 24]);
+    }
+
+    #[test]
+    fn test_day_hours_leave_out_breaks() {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(record("Review", (9, 0), (10, 30)));
+        let mut lunch = record("Break", (10, 30), (11, 0));
+        lunch.id = 2;
+        day.add_record(lunch);
+
+        let hours = day_hours(&day);
+        assert_eq!(&hours[9..11], &[60, 30]);
+        assert_eq!(strip(&hours).chars().nth(10), Some('▄'));
+    }
+
+    #[test]
+    fn test_shades_grow_with_minutes() {
+        assert_eq!(shade(0), '·');
+        assert_eq!(shade(1), '▁');
+        assert_eq!(shade(30), '▄');
+        assert_eq!(shade(60), '█');
+        // Overlapping records can log more than an hour in one
+        assert_eq!(shade(90), '█');
+    }
+
+    #[test]
+    fn test_grid_averages_per_weekday() {
+        let mut grid = HeatGrid::new();
+        // Two Thursdays, one with an hour at 9:00
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(record("Review", (9, 0), (10, 0)));
+        grid.add_day(&day);
+        grid.add_day(&DayData::new(date!(2025 - 11 - 13)));
+
+        assert_eq!(grid.average(Weekday::Thursday, 9), 30);
+        assert_eq!(grid.average(Weekday::Friday, 9), 0);
+
+        let lines = grid.lines(Weekday::Monday);
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with("    0     3     6"));
+        assert!(lines[4].starts_with("Thu ··"));
+        assert_eq!(lines[4].chars().nth(4 + 18), Some('▄'));
+    }
+}
@@ -0,0 +1,314 @@
+//! Self-contained HTML report for one day (`report html`)
+//!
+//! The page is built from a template compiled into the binary, with `{{name}}`
+//! placeholders filled in a single pass. Styles are inline and nothing is fetched,
+//! so the file can be mailed or dropped into a chat as is.
+//!
+//! Totals over several days live in `window`, estimation accuracy in `estimates`,
+//! minutes per hour of the day in `heatmap`, a week's tasks by day in `grid` and
+//! timer session habits in `focus`.
+
+mod estimates;
+mod focus;
+mod grid;
+mod heatmap;
+mod window;
+
+pub use estimates::{EstimateAccuracy, estimate_accuracy};
+pub use focus::FocusStats;
+pub use grid::{CELL_WIDTH, WeekGrid, cell_text, week_containing};
+pub use heatmap::{HeatGrid, day_hours, shade, strip};
+pub use window::WindowTotals;
+
+use crate::integrations::extract_ticket_from_name;
+use crate::models::{DayData, WorkRecord};
+use crate::timefmt::format_minutes;
+
+const DAY_TEMPLATE: &str = include_str!("day_template.html");
+
+/// Render `day` as a complete HTML page
+pub fn day_html(day: &DayData) -> String {
+    let records = day.get_sorted_records();
+    let title = format!("Work report for {}", day.date);
+
+    fill_template(
+        DAY_TEMPLATE,
+        &[
+            ("title", escape_html(&title)),
+            ("stats", stats_html(day, records.len())),
+            ("timeline", timeline_html(&records)),
+            ("totals", totals_html(day)),
+            ("notes", notes_html(&records)),
+        ],
+    )
+}
+
+/// Escape text for use in element content and quoted attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Replace each `{{name}}` in `template` with its value
+///
+/// Done in one pass, so a value that happens to contain `{{...}}` is left alone.
+fn fill_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        output.push_str(&rest[..open]);
+        let after = &rest[open + 2..];
+        let Some(close) = after.find("}}") else {
+            output.push_str(&rest[open..]);
+            return output;
+        };
+        let name = &after[..close];
+        match values.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => output.push_str(value),
+            None => output.push_str(&rest[open..open + close + 4]),
+        }
+        rest = &after[close + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Stable color for a record: its ticket when it has one, else its task name
+fn record_color(record: &WorkRecord) -> String {
+    let key = extract_ticket_from_name(&record.name).unwrap_or_else(|| record.name.clone());
+    // FNV-1a, so colors don't change between runs or builds
+    let hash = key.bytes().fold(0x811c9dc5u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    });
+    format!("hsl({}, 55%, 45%)", hash % 360)
+}
+
+fn stats_html(day: &DayData, record_count: usize) -> String {
+    let breakdown = day.breakdown();
+    [
+        ("Total", format_minutes(day.total_minutes())),
+        ("Worked", format_minutes(breakdown.worked_minutes)),
+        ("Breaks", format_minutes(breakdown.break_minutes)),
+        ("Span", format_minutes(breakdown.span_minutes)),
+        ("Records", record_count.to_string()),
+    ]
+    .iter()
+    .map(|(label, value)| format!("<div class=\"stat\">{}<b>{}</b></div>", label, value))
+    .collect()
+}
+
+/// One lane per record, bars placed on an axis covering the whole hours worked
+fn timeline_html(records: &[&WorkRecord]) -> String {
+    if records.is_empty() {
+        return "<p class=\"muted\">No records.</p>".to_string();
+    }
+
+    // Overnight records run to the end of the axis
+    let span = |record: &WorkRecord| {
+        let start = record.start.to_minutes_since_midnight();
+        let end = record.end.to_minutes_since_midnight();
+        (start, if end < start { 24 * 60 } else { end })
+    };
+    let first_hour = records.iter().map(|r| span(r).0).min().unwrap_or(0) / 60;
+    let last_hour = records
+        .iter()
+        .map(|r| span(r).1.div_ceil(60))
+        .max()
+        .unwrap_or(24)
+        .max(first_hour + 1);
+    let axis_start = first_hour * 60;
+    let axis_minutes = ((last_hour - first_hour) * 60) as f64;
+    let percent = |minutes: u32| (minutes - axis_start) as f64 / axis_minutes * 100.0;
+
+    let step = if last_hour - first_hour > 12 { 2 } else { 1 };
+    let mut html = String::from("<div class=\"axis\">");
+    for hour in (first_hour..=last_hour).step_by(step) {
+        html.push_str(&format!(
+            "<span style=\"left: {:.2}%\">{:02}:00</span>",
+            percent(hour * 60),
+            hour
+        ));
+    }
+    html.push_str("</div>\n");
+
+    for record in records {
+        let (start, end) = span(record);
+        let label = format!("{} ({} - {})", record.name, record.start, record.end);
+        html.push_str(&format!(
+            "<div class=\"lane\"><div class=\"bar\" data-record=\"{}\" title=\"{}\" style=\"left: {:.2}%; width: {:.2}%; background: {}\">{}</div></div>\n",
+            record.id,
+            escape_html(&label),
+            percent(start),
+            percent(end) - percent(start),
+            record_color(record),
+            escape_html(&label)
+        ));
+    }
+    html
+}
+
+fn totals_html(day: &DayData) -> String {
+    let totals = day.get_grouped_totals();
+    if totals.is_empty() {
+        return "<p class=\"muted\">Nothing tracked.</p>".to_string();
+    }
+
+    let mut html = String::from("<table>\n<tr><th>Task</th><th>Total</th></tr>\n");
+    for (name, minutes) in &totals {
+        let note = day
+            .annotation(name)
+            .map(|note| format!(" <span class=\"muted\">{}</span>", escape_html(note)))
+            .unwrap_or_default();
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}{}</td></tr>\n",
+            escape_html(name),
+            format_minutes(*minutes),
+            note
+        ));
+    }
+    html.push_str("</table>");
+    html
+}
+
+/// Record descriptions, in the order the records happened
+fn notes_html(records: &[&WorkRecord]) -> String {
+    let notes: Vec<String> = records
+        .iter()
+        .filter(|record| !record.description.trim().is_empty())
+        .map(|record| {
+            format!(
+                "<li><b>{}</b> {}: {}</li>",
+                record.start,
+                escape_html(&record.name),
+                escape_html(record.description.trim())
+            )
+        })
+        .collect();
+    if notes.is_empty() {
+        return "<p class=\"muted\">No notes.</p>".to_string();
+    }
+    format!("<ul class=\"notes\">\n{}\n</ul>", notes.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+    use time::macros::date;
+
+    fn record(id: u32, name: &str, start: (u8, u8), end: (u8, u8)) -> WorkRecord {
+        WorkRecord::new(
+            id,
+            name.to_string(),
+            TimePoint::new(start.0, start.1).unwrap(),
+            TimePoint::new(end.0, end.1).unwrap(),
+        )
+    }
+
+    fn fixture_day() -> DayData {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        let mut review = record(1, "PROJ-101 <script>alert(1)</script>", (9, 0), (10, 30));
+        review.description = "Checked \"auth\" & 'session' code".to_string();
+        day.add_record(review);
+        day.add_record(record(2, "Team meeting", (10, 30), (11, 0)));
+        day.add_record(record(3, "Break", (12, 0), (12, 30)));
+        day.add_record(record(4, "Team meeting", (14, 0), (14, 15)));
+        day
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("plain"), "plain");
+    }
+
+    #[test]
+    fn test_day_html_escapes_names_and_descriptions() {
+        let html = day_html(&fixture_day());
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("PROJ-101 &lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("Checked &quot;auth&quot; &amp; &#39;session&#39; code"));
+    }
+
+    #[test]
+    fn test_day_html_has_every_record_once() {
+        let day = fixture_day();
+        let html = day_html(&day);
+        for id in day.work_records.keys() {
+            let marker = format!("data-record=\"{}\"", id);
+            assert_eq!(html.matches(&marker).count(), 1, "record {}", id);
+        }
+        assert_eq!(
+            html.matches("class=\"bar\"").count(),
+            day.work_records.len()
+        );
+    }
+
+    #[test]
+    fn test_day_html_fills_every_placeholder() {
+        let html = day_html(&fixture_day());
+        assert!(!html.contains("{{"));
+        assert!(html.contains("<title>Work report for 2025-11-06</title>"));
+        // Totals group both meetings
+        assert!(html.contains("<td>Team meeting</td><td class=\"num\">0h 45m</td>"));
+        assert!(html.contains("Worked<b>2h 15m</b>"));
+    }
+
+    #[test]
+    fn test_day_html_shows_task_annotations() {
+        let mut day = fixture_day();
+        day.set_annotation("Team meeting", "ran <long>");
+        let html = day_html(&day);
+        assert!(html.contains(
+            "<td>Team meeting</td><td class=\"num\">0h 45m <span class=\"muted\">ran &lt;long&gt;</span></td>"
+        ));
+    }
+
+    #[test]
+    fn test_day_html_leaves_braces_in_content_alone() {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(record(1, "Fix {{notes}} template", (9, 0), (10, 0)));
+        let html = day_html(&day);
+        assert!(html.contains("Fix {{notes}} template"));
+        assert_eq!(html.matches("No notes.").count(), 1);
+    }
+
+    #[test]
+    fn test_day_html_empty_day() {
+        let html = day_html(&DayData::new(date!(2025 - 11 - 06)));
+        assert!(html.contains("No records."));
+        assert!(html.contains("Nothing tracked."));
+    }
+
+    #[test]
+    fn test_timeline_positions_bars_on_whole_hours() {
+        let day = fixture_day();
+        let html = timeline_html(&day.get_sorted_records());
+        // Axis runs 09:00 to 15:00, so 10:30 is a quarter of the way
+        assert!(html.contains("<span style=\"left: 0.00%\">09:00</span>"));
+        assert!(html.contains("<span style=\"left: 100.00%\">15:00</span>"));
+        assert!(html.contains("left: 25.00%; width: 8.33%"));
+    }
+
+    #[test]
+    fn test_record_color_follows_ticket() {
+        let a = record(1, "PROJ-7 design", (9, 0), (10, 0));
+        let b = record(2, "PROJ-7 review", (10, 0), (11, 0));
+        let c = record(3, "PROJ-8 design", (11, 0), (12, 0));
+        assert_eq!(record_color(&a), record_color(&b));
+        assert_ne!(record_color(&a), record_color(&c));
+    }
+}
@@ -0,0 +1,225 @@
+//! Worked time over a rolling window of days ending today
+//!
+//! The header can show the last 7 or 30 days instead of the viewed day. Other
+//! days' totals are cached so the files are read once per window, not per frame.
+
+use crate::config::TotalsWindow;
+use crate::models::DayData;
+use anyhow::Result;
+use time::{Date, Duration};
+
+/// First and last day of `window` ending on `today`
+pub fn window_range(window: TotalsWindow, today: Date) -> (Date, Date) {
+    let first = today.saturating_sub(Duration::days(window.days() as i64 - 1));
+    (first, today)
+}
+
+/// Worked minutes in `days`, breaks excluded as in the header's "Worked" figure
+pub fn worked_minutes<'a>(days: impl IntoIterator<Item = &'a DayData>) -> u32 {
+    days.into_iter()
+        .map(|day| day.breakdown().worked_minutes)
+        .sum()
+}
+
+/// Cached worked time of every day in the window but the one on screen
+///
+/// The day on screen is always summed from memory, so edits show up at once.
+/// The cache is rebuilt when the window, today's date or the viewed day changes,
+/// and must be invalidated whenever day files may have changed on disk.
+#[derive(Debug, Default)]
+pub struct WindowTotals {
+    other_days: u32,
+    cached_for: Option<(TotalsWindow, Date, Date)>,
+}
+
+impl WindowTotals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the cached total (call after external file changes or stopped timers)
+    pub fn invalidate(&mut self) {
+        self.cached_for = None;
+    }
+
+    /// Worked minutes over `window` ending `today`, reading days with `load_day`
+    ///
+    /// `load_day` is only called on a cold cache, once per day in the window
+    /// other than `current`'s; a day without a file should load as empty.
+    pub fn worked_minutes(
+        &mut self,
+        window: TotalsWindow,
+        today: Date,
+        current: &DayData,
+        mut load_day: impl FnMut(Date) -> Result<DayData>,
+    ) -> Result<u32> {
+        let (first, last) = window_range(window, today);
+        let key = (window, today, current.date);
+        if self.cached_for != Some(key) {
+            let mut days = Vec::new();
+            let mut date = first;
+            while date <= last {
+                if date != current.date {
+                    days.push(load_day(date)?);
+                }
+                date = date.saturating_add(Duration::days(1));
+            }
+            self.other_days = worked_minutes(&days);
+            self.cached_for = Some(key);
+        }
+
+        let in_window = (first..=last).contains(&current.date);
+        let current_minutes = if in_window {
+            worked_minutes([current])
+        } else {
+            0
+        };
+        Ok(self.other_days + current_minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use crate::storage::Storage;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+    use time::macros::date;
+
+    fn day_with(date: Date, records: &[(&str, u8, u8)]) -> DayData {
+        let mut day = DayData::new(date);
+        for (id, &(name, start, end)) in records.iter().enumerate() {
+            day.add_record(WorkRecord::new(
+                id as u32 + 1,
+                name.to_string(),
+                TimePoint::new(start, 0).unwrap(),
+                TimePoint::new(end, 0).unwrap(),
+            ));
+        }
+        day
+    }
+
+    /// Days around the end of November, with gaps where nothing was tracked
+    fn storage_across_month_end() -> (Storage, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let days = [
+            day_with(date!(2025 - 11 - 20), &[("Old work", 9, 17)]),
+            day_with(
+                date!(2025 - 11 - 27),
+                &[("Coding", 9, 12), ("Break", 12, 13)],
+            ),
+            day_with(date!(2025 - 11 - 30), &[("Review", 10, 11)]),
+            day_with(date!(2025 - 12 - 02), &[("Coding", 9, 11)]),
+        ];
+        for day in &days {
+            storage.save(day).unwrap();
+        }
+        (storage, temp_dir)
+    }
+
+    #[test]
+    fn test_window_range_spans_month_boundary() {
+        let today = date!(2025 - 12 - 03);
+        assert_eq!(window_range(TotalsWindow::Today, today), (today, today));
+        assert_eq!(
+            window_range(TotalsWindow::Week, today),
+            (date!(2025 - 11 - 27), today)
+        );
+        assert_eq!(
+            window_range(TotalsWindow::Month, today),
+            (date!(2025 - 11 - 04), today)
+        );
+    }
+
+    #[test]
+    fn test_week_total_skips_breaks_and_missing_days() {
+        let (storage, _temp) = storage_across_month_end();
+        let today = day_with(date!(2025 - 12 - 03), &[("Coding", 9, 10)]);
+        let mut totals = WindowTotals::new();
+
+        // 11-27 (3h, break excluded), 11-30 (1h), 12-02 (2h) and today (1h);
+        // 11-20 falls outside the week
+        let minutes = totals
+            .worked_minutes(TotalsWindow::Week, today.date, &today, |date| {
+                storage.load(&date)
+            })
+            .unwrap();
+        assert_eq!(minutes, 7 * 60);
+
+        let minutes = totals
+            .worked_minutes(TotalsWindow::Month, today.date, &today, |date| {
+                storage.load(&date)
+            })
+            .unwrap();
+        assert_eq!(minutes, 15 * 60);
+    }
+
+    #[test]
+    fn test_cache_rules() {
+        let (storage, _temp) = storage_across_month_end();
+        let mut current = day_with(date!(2025 - 12 - 03), &[("Coding", 9, 10)]);
+        let today = current.date;
+        let loads = Cell::new(0);
+        let load = |date: Date| {
+            loads.set(loads.get() + 1);
+            storage.load(&date)
+        };
+        let mut totals = WindowTotals::new();
+
+        // Six other days are read once; edits to the day on screen need no reload
+        totals
+            .worked_minutes(TotalsWindow::Week, today, &current, load)
+            .unwrap();
+        assert_eq!(loads.get(), 6);
+        current.add_record(WorkRecord::new(
+            2,
+            "Docs".to_string(),
+            TimePoint::new(10, 0).unwrap(),
+            TimePoint::new(11, 0).unwrap(),
+        ));
+        let minutes = totals
+            .worked_minutes(TotalsWindow::Week, today, &current, load)
+            .unwrap();
+        assert_eq!(minutes, 8 * 60);
+        assert_eq!(loads.get(), 6);
+
+        // A new window reloads
+        totals
+            .worked_minutes(TotalsWindow::Month, today, &current, load)
+            .unwrap();
+        assert_eq!(loads.get(), 6 + 29);
+
+        // So does the day rolling over, even with the same day on screen
+        loads.set(0);
+        let minutes = totals
+            .worked_minutes(TotalsWindow::Week, date!(2025 - 12 - 04), &current, load)
+            .unwrap();
+        assert_eq!(loads.get(), 6);
+        // 11-27 dropped out of the week
+        assert_eq!(minutes, 5 * 60);
+
+        // Viewing a day outside the window reads the whole window from disk
+        loads.set(0);
+        let old = day_with(date!(2025 - 11 - 20), &[("Old work", 9, 17)]);
+        let minutes = totals
+            .worked_minutes(TotalsWindow::Week, today, &old, load)
+            .unwrap();
+        assert_eq!(loads.get(), 7);
+        // Today's file was never saved, so only the stored days count
+        assert_eq!(minutes, 6 * 60);
+
+        // Invalidating picks up files changed on disk
+        storage
+            .save(&day_with(date!(2025 - 11 - 28), &[("Support", 9, 10)]))
+            .unwrap();
+        loads.set(0);
+        totals.invalidate();
+        let minutes = totals
+            .worked_minutes(TotalsWindow::Week, today, &old, load)
+            .unwrap();
+        assert_eq!(loads.get(), 7);
+        assert_eq!(minutes, 7 * 60);
+    }
+}
@@ -0,0 +1,319 @@
+//! Read-only JSON status over HTTP for home dashboards (`[serve] http`)
+//!
+//! Two endpoints: `/status` for the running session and `/today` for the day's
+//! breakdown. Requests are answered one at a time on a background thread, reading
+//! straight from storage, so the TUI never waits on a client. Only loopback
+//! addresses are accepted unless `[serve] allow_remote` is set.
+
+use crate::config::Config;
+use crate::integrations::extract_ticket_from_name;
+use crate::models::DayBreakdown;
+use crate::storage::Storage;
+use crate::timefmt;
+use crate::timer::TimerStatus;
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Address `serve --http` listens on when neither it nor `[serve] http` names one
+pub const DEFAULT_ADDR: &str = "127.0.0.1:4877";
+
+/// How often the listener checks for a shutdown while no client is connecting
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// A client gets this long to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+/// Longest request head read; the request line is all that's used
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Status code and JSON body of an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Response { status: 200, body },
+            Err(e) => Self::error(500, &e.to_string()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        let body = serde_json::json!({ "error": message }).to_string();
+        Response { status, body }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            400 => "Bad Request",
+            _ => "Internal Server Error",
+        }
+    }
+
+    /// The full HTTP/1.1 response, closing the connection afterwards
+    pub fn to_http(&self) -> String {
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+/// `/status` body; only `active` is set while no session runs
+#[derive(Debug, Serialize)]
+struct StatusBody {
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticket: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<TimerStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed_seconds: Option<u64>,
+}
+
+/// `/today` body: the day's breakdown as `report day` prints it
+#[derive(Debug, Serialize)]
+struct TodayBody {
+    date: String,
+    #[serde(flatten)]
+    breakdown: DayBreakdown,
+    billable_minutes: u32,
+    records: usize,
+}
+
+/// Answer a raw request (`GET /status HTTP/1.1 …`) as of `now`
+pub fn respond(request: &str, storage: &Storage, config: &Config, now: OffsetDateTime) -> Response {
+    let mut parts = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Response::error(400, "Malformed request");
+    };
+    let path = target.split('?').next().unwrap_or_default();
+    if !matches!(path, "/status" | "/today") {
+        return Response::error(404, &format!("No such endpoint: {}", path));
+    }
+    if method != "GET" {
+        return Response::error(405, "Only GET is supported");
+    }
+
+    let answer = match path {
+        "/status" => status(storage, now).map(|body| Response::json(&body)),
+        _ => today(storage, config, now).map(|body| Response::json(&body)),
+    };
+    answer.unwrap_or_else(|e| Response::error(500, &e.to_string()))
+}
+
+fn status(storage: &Storage, now: OffsetDateTime) -> Result<StatusBody> {
+    let Some(timer) = storage.load_active_timer()? else {
+        return Ok(StatusBody {
+            active: false,
+            task: None,
+            ticket: None,
+            status: None,
+            elapsed_seconds: None,
+        });
+    };
+    Ok(StatusBody {
+        active: true,
+        ticket: extract_ticket_from_name(&timer.task_name),
+        elapsed_seconds: Some(timefmt::elapsed(&timer, now).as_secs()),
+        status: Some(timer.status),
+        task: Some(timer.task_name),
+    })
+}
+
+fn today(storage: &Storage, config: &Config, now: OffsetDateTime) -> Result<TodayBody> {
+    let date = now.date();
+    let day = storage.load(&date)?;
+    Ok(TodayBody {
+        date: date.to_string(),
+        breakdown: day.breakdown(),
+        billable_minutes: day.billable_minutes(config.defaults.billable),
+        records: day.work_records.len(),
+    })
+}
+
+/// The listener thread; stopped and joined on `shutdown` or drop
+pub struct StatusServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StatusServer {
+    /// Listen on `addr`, refusing a non-loopback address unless `allow_remote`
+    pub fn start(addr: &str, allow_remote: bool, storage: Storage, config: Config) -> Result<Self> {
+        let requested: SocketAddr = addr
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid HTTP address '{}' (use 127.0.0.1:4877)", addr))?;
+        if !requested.ip().is_loopback() && !allow_remote {
+            bail!(
+                "Refusing to serve on {}, which isn't loopback; set [serve] allow_remote = true to allow it",
+                requested
+            );
+        }
+
+        let listener = TcpListener::bind(requested)
+            .with_context(|| format!("Failed to listen on {}", requested))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let thread = std::thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        // A client that goes away mid-request only loses its answer
+                        let _ = answer(stream, &storage, &config);
+                    }
+                    // Nobody waiting, or a failed accept worth retrying later
+                    Err(_) => std::thread::sleep(POLL_INTERVAL),
+                }
+            }
+        });
+
+        Ok(StatusServer {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address actually listened on (the port is filled in when 0 was asked for)
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Block until the listener stops; it only does when the process ends
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    /// Stop listening and wait for the thread to finish its current client
+    pub fn shutdown(mut self) {
+        self.stop_thread();
+    }
+
+    fn stop_thread(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for StatusServer {
+    fn drop(&mut self) {
+        self.stop_thread();
+    }
+}
+
+/// Read one request head from `stream` and write the answer
+fn answer(mut stream: TcpStream, storage: &Storage, config: &Config) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        match stream.read(&mut buf)? {
+            0 => break,
+            n => request.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let response = respond(&request, storage, config, timefmt::now_local());
+    stream.write_all(response.to_http().as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage() -> (tempfile::TempDir, Storage) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_non_loopback_needs_allow_remote() {
+        let (_dir, storage) = storage();
+        let error = StatusServer::start("0.0.0.0:0", false, storage, Config::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Refusing to serve on 0.0.0.0:0, which isn't loopback; set [serve] allow_remote = true to allow it"
+        );
+    }
+
+    #[test]
+    fn test_invalid_address_is_reported() {
+        let (_dir, storage) = storage();
+        let error = StatusServer::start("localhost", false, storage, Config::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Invalid HTTP address 'localhost' (use 127.0.0.1:4877)"
+        );
+    }
+
+    #[test]
+    fn test_server_answers_over_loopback_and_shuts_down() {
+        let (_dir, storage) = storage();
+        let server = StatusServer::start("127.0.0.1:0", false, storage, Config::default()).unwrap();
+
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream
+            .write_all(b"GET /status HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 200 OK\r\n"), "{}", reply);
+        assert!(reply.ends_with("\r\n\r\n{\"active\":false}"), "{}", reply);
+
+        let addr = server.local_addr();
+        server.shutdown();
+        assert!(TcpStream::connect(addr).is_err());
+    }
+
+    #[test]
+    fn test_unknown_paths_and_methods_are_refused() {
+        let (_dir, storage) = storage();
+        let config = Config::default();
+        let now = timefmt::now_local();
+        let respond = |request: &str| respond(request, &storage, &config, now);
+
+        assert_eq!(respond("GET /nope HTTP/1.1\r\n\r\n").status, 404);
+        assert_eq!(respond("POST /status HTTP/1.1\r\n\r\n").status, 405);
+        assert_eq!(respond("").status, 400);
+        assert_eq!(respond("GET /status?x=1 HTTP/1.1\r\n\r\n").status, 200);
+    }
+}
@@ -0,0 +1,176 @@
+//! Reading day files without holding more than `[storage] max_records` records
+//!
+//! The file is parsed straight from the reader. Records past the limit are
+//! skipped as they stream by, and the day is marked `truncated` so it isn't saved
+//! over the records left out.
+
+use crate::models::{DayData, Truncated, WorkRecord};
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+/// Records kept from a day unless `[storage] max_records` says otherwise
+pub const DEFAULT_MAX_RECORDS: usize = 5000;
+
+/// Parse a day file from `reader`, keeping its first `max_records` records
+pub fn parse(reader: impl Read, max_records: usize) -> serde_json::Result<DayData> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let day = DaySeed { max_records }.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(day)
+}
+
+struct DaySeed {
+    max_records: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for DaySeed {
+    type Value = DayData;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<DayData, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DaySeed {
+    type Value = DayData;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a day file")
+    }
+
+    /// `work_records` goes through the limit; the rest of the day is small and is
+    /// handed to `DayData`'s own deserializer, unknown fields included
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<DayData, A::Error> {
+        let mut records = None;
+        let mut rest = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "work_records" {
+                records = Some(map.next_value_seed(RecordsSeed {
+                    max_records: self.max_records,
+                })?);
+            } else {
+                rest.insert(key, map.next_value()?);
+            }
+        }
+        let (records, total) = records.ok_or_else(|| de::Error::missing_field("work_records"))?;
+
+        rest.insert(
+            "work_records".to_string(),
+            serde_json::Value::Object(serde_json::Map::new()),
+        );
+        let mut day: DayData =
+            serde_json::from_value(serde_json::Value::Object(rest)).map_err(de::Error::custom)?;
+        if total > records.len() {
+            day.truncated = Some(Truncated {
+                kept: records.len(),
+                total,
+            });
+        }
+        day.work_records = records;
+        Ok(day)
+    }
+}
+
+/// The first `max_records` records of `work_records`, and how many there were
+struct RecordsSeed {
+    max_records: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for RecordsSeed {
+    type Value = (HashMap<u32, WorkRecord>, usize);
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for RecordsSeed {
+    type Value = (HashMap<u32, WorkRecord>, usize);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("records keyed by id")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut records = HashMap::new();
+        let mut total = 0;
+        while let Some(id) = map.next_key::<u32>()? {
+            total += 1;
+            if records.len() < self.max_records {
+                records.insert(id, map.next_value::<WorkRecord>()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok((records, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+    use time::macros::date;
+
+    fn day_with(records: u32) -> DayData {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        for id in 1..=records {
+            let start = TimePoint::from_minutes_since_midnight(id % 1400).unwrap();
+            let end = TimePoint::from_minutes_since_midnight(id % 1400 + 1).unwrap();
+            day.add_record(WorkRecord::new(id, format!("Task {}", id), start, end));
+        }
+        day.set_annotation("Task 1", "first");
+        day
+    }
+
+    #[test]
+    fn test_parse_matches_serde_under_the_limit() {
+        let day = day_with(20);
+        let json = serde_json::to_string_pretty(&day).unwrap();
+        let parsed = parse(json.as_bytes(), DEFAULT_MAX_RECORDS).unwrap();
+        assert_eq!(parsed, day);
+        assert_eq!(parsed.truncated, None);
+        assert_eq!(parsed.get_sorted_records().len(), 20);
+    }
+
+    #[test]
+    fn test_parse_keeps_the_first_records_past_the_limit() {
+        let json = serde_json::to_string(&day_with(30)).unwrap();
+        let parsed = parse(json.as_bytes(), 10).unwrap();
+        assert_eq!(parsed.work_records.len(), 10);
+        assert_eq!(
+            parsed.truncated,
+            Some(Truncated {
+                kept: 10,
+                total: 30
+            })
+        );
+        // The rest of the day is read in full
+        assert_eq!(parsed.last_id, 30);
+        assert_eq!(parsed.annotation("Task 1"), Some("first"));
+    }
+
+    #[test]
+    fn test_parse_keeps_unknown_fields_and_rejects_bad_files() {
+        let mut value = serde_json::to_value(DayData::new(date!(2025 - 11 - 06))).unwrap();
+        value["synced"] = serde_json::Value::Bool(true);
+        let parsed = parse(value.to_string().as_bytes(), DEFAULT_MAX_RECORDS).unwrap();
+        assert_eq!(parsed.unknown["synced"], true);
+
+        let without_records = value
+            .as_object()
+            .unwrap()
+            .iter()
+            .filter(|(key, _)| *key != "work_records")
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<serde_json::Map<_, _>>();
+        let without_records = serde_json::Value::Object(without_records).to_string();
+        assert!(parse(without_records.as_bytes(), 10).is_err());
+        assert!(parse(format!("{} x", value).as_bytes(), 10).is_err());
+    }
+}
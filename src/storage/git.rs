@@ -0,0 +1,224 @@
+//! Optional git commits of the data directory after saves (`[storage] git_autocommit`)
+//!
+//! A save only marks its day as pending. Once no save has come in for the debounce
+//! interval, one commit covers every pending day. `StorageManager` runs the commit
+//! on a background thread and picks up its outcome on a later tick, so the UI
+//! never waits on git.
+
+use anyhow::{Result, anyhow};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use time::Date;
+
+/// Runs git; the real one in the app, a fake in tests
+pub trait CommandRunner: Send + Sync {
+    /// Run `git <args>` in `dir`, returning stdout, or stderr as the error
+    fn git(&self, dir: &Path, args: &[&str]) -> Result<String>;
+}
+
+/// The `git` on PATH
+pub struct SystemGit;
+
+impl CommandRunner for SystemGit {
+    fn git(&self, dir: &Path, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow!("{}", stderr.lines().next().unwrap_or("git failed")))
+        }
+    }
+}
+
+/// What `commit_data_dir` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitOutcome {
+    Committed,
+    NothingToCommit,
+    NotARepository,
+}
+
+/// "work-tuimer: 2025-11-06 update", listing every day the commit covers
+pub fn commit_message(dates: &BTreeSet<Date>) -> String {
+    let days = dates
+        .iter()
+        .map(Date::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("work-tuimer: {} update", days)
+}
+
+/// Stage and commit everything under `dir` (and nothing else in an enclosing repo)
+pub fn commit_data_dir(
+    runner: &dyn CommandRunner,
+    dir: &Path,
+    message: &str,
+) -> Result<CommitOutcome> {
+    if runner
+        .git(dir, &["rev-parse", "--is-inside-work-tree"])
+        .is_err()
+    {
+        return Ok(CommitOutcome::NotARepository);
+    }
+    runner.git(dir, &["add", "-A", "--", "."])?;
+    if runner
+        .git(dir, &["status", "--porcelain", "--", "."])?
+        .trim()
+        .is_empty()
+    {
+        return Ok(CommitOutcome::NothingToCommit);
+    }
+    runner.git(dir, &["commit", "-q", "-m", message, "--", "."])?;
+    Ok(CommitOutcome::Committed)
+}
+
+/// Coalesces saves into one commit once they stop for `debounce`
+#[derive(Debug, Clone)]
+pub struct AutoCommit {
+    debounce: Duration,
+    pending: BTreeSet<Date>,
+    due: Option<Instant>,
+}
+
+impl AutoCommit {
+    pub fn new(debounce: Duration) -> Self {
+        AutoCommit {
+            debounce,
+            pending: BTreeSet::new(),
+            due: None,
+        }
+    }
+
+    /// Note a save of `date`; every save pushes the commit back by the debounce
+    pub fn saved(&mut self, date: Date, now: Instant) {
+        self.pending.insert(date);
+        self.due = Some(now + self.debounce);
+    }
+
+    /// The commit message once the debounce has passed, clearing what was pending
+    pub fn take_due(&mut self, now: Instant) -> Option<String> {
+        if self.due.is_none_or(|due| now < due) {
+            return None;
+        }
+        self.due = None;
+        Some(commit_message(&std::mem::take(&mut self.pending)))
+    }
+
+    /// The commit message for anything pending, due or not (on exit)
+    pub fn take_pending(&mut self) -> Option<String> {
+        self.due = None;
+        (!self.pending.is_empty()).then(|| commit_message(&std::mem::take(&mut self.pending)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use time::macros::date;
+
+    /// Records every git call; `repo` and `changes` decide the answers
+    struct FakeGit {
+        repo: bool,
+        changes: bool,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl FakeGit {
+        fn new(repo: bool, changes: bool) -> Self {
+            FakeGit {
+                repo,
+                changes,
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl CommandRunner for FakeGit {
+        fn git(&self, _dir: &Path, args: &[&str]) -> Result<String> {
+            self.calls.lock().unwrap().push(args.join(" "));
+            match args[0] {
+                "rev-parse" if !self.repo => Err(anyhow!("not a git repository")),
+                "status" if self.changes => Ok(" M 2025-11-06.json\n".to_string()),
+                _ => Ok(String::new()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_commit_message_lists_days() {
+        let one = BTreeSet::from([date!(2025 - 11 - 06)]);
+        assert_eq!(commit_message(&one), "work-tuimer: 2025-11-06 update");
+        let two = BTreeSet::from([date!(2025 - 11 - 06), date!(2025 - 11 - 05)]);
+        assert_eq!(
+            commit_message(&two),
+            "work-tuimer: 2025-11-05, 2025-11-06 update"
+        );
+    }
+
+    #[test]
+    fn test_rapid_saves_coalesce_into_one_commit() {
+        let start = Instant::now();
+        let secs = |n| start + Duration::from_secs(n);
+        let mut auto = AutoCommit::new(Duration::from_secs(30));
+        assert_eq!(auto.take_due(start), None);
+
+        auto.saved(date!(2025 - 11 - 06), start);
+        auto.saved(date!(2025 - 11 - 05), secs(20));
+        // 30s after the first save but only 10s after the second
+        assert_eq!(auto.take_due(secs(30)), None);
+        assert_eq!(
+            auto.take_due(secs(50)).as_deref(),
+            Some("work-tuimer: 2025-11-05, 2025-11-06 update")
+        );
+        assert_eq!(auto.take_due(secs(100)), None);
+
+        auto.saved(date!(2025 - 11 - 07), secs(101));
+        assert_eq!(
+            auto.take_pending().as_deref(),
+            Some("work-tuimer: 2025-11-07 update")
+        );
+        assert_eq!(auto.take_due(secs(200)), None);
+    }
+
+    #[test]
+    fn test_commit_only_in_a_repository_with_changes() {
+        let dir = Path::new("/data");
+        let git = FakeGit::new(true, true);
+        let outcome = commit_data_dir(&git, dir, "work-tuimer: 2025-11-06 update").unwrap();
+        assert_eq!(outcome, CommitOutcome::Committed);
+        assert_eq!(
+            git.calls(),
+            [
+                "rev-parse --is-inside-work-tree",
+                "add -A -- .",
+                "status --porcelain -- .",
+                "commit -q -m work-tuimer: 2025-11-06 update -- .",
+            ]
+        );
+
+        let clean = FakeGit::new(true, false);
+        assert_eq!(
+            commit_data_dir(&clean, dir, "m").unwrap(),
+            CommitOutcome::NothingToCommit
+        );
+
+        let plain_dir = FakeGit::new(false, true);
+        assert_eq!(
+            commit_data_dir(&plain_dir, dir, "m").unwrap(),
+            CommitOutcome::NotARepository
+        );
+        assert_eq!(plain_dir.calls().len(), 1);
+    }
+}
@@ -1,29 +1,65 @@
-use crate::models::{DayData, WorkRecord};
-use crate::timer::TimerState;
+mod day_file;
+mod git;
+
+pub use day_file::DEFAULT_MAX_RECORDS;
+
+pub use git::{
+    AutoCommit, CommandRunner, CommitOutcome, SystemGit, commit_data_dir, commit_message,
+};
+
+use crate::models::{DayData, RecordTemplate, WorkRecord};
+use crate::profile::Profile;
+use crate::timer::{SessionLogEntry, TimerState};
 use anyhow::{Context, Result};
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::PathBuf;
-use std::time::SystemTime;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, Instant, SystemTime};
 use time::Date;
 
 #[derive(Clone)]
 pub struct Storage {
     data_dir: PathBuf,
+    /// Records loaded per day at most (`[storage] max_records`)
+    max_records: usize,
 }
 
 /// High-level storage manager that provides transactional operations
 /// and automatic file modification tracking
 pub struct StorageManager {
     storage: Storage,
+    /// Records kept per day in the loads the TUI shows and edits (`[storage] max_records`)
+    max_records: usize,
     file_modified_times: std::collections::HashMap<Date, Option<SystemTime>>,
+    create_record_on_start: bool,
+    git: Option<GitAutoCommit>,
+}
+
+/// Pending and running autocommits (`[storage] git_autocommit`)
+struct GitAutoCommit {
+    schedule: AutoCommit,
+    runner: Arc<dyn CommandRunner>,
+    /// Outcome of the commit running in the background, if one is
+    running: Option<mpsc::Receiver<Result<CommitOutcome>>>,
 }
 
 impl StorageManager {
-    /// Create a new StorageManager
+    /// Create a new StorageManager for the default profile
+    #[allow(dead_code)]
     pub fn new() -> Result<Self> {
+        Self::new_for(&Profile::default())
+    }
+
+    /// Create a new StorageManager for the given profile's data directory
+    pub fn new_for(profile: &Profile) -> Result<Self> {
         Ok(StorageManager {
-            storage: Storage::new()?,
+            storage: Storage::new_for(profile)?,
+            max_records: usize::MAX,
             file_modified_times: std::collections::HashMap::new(),
+            create_record_on_start: false,
+            git: None,
         })
     }
 
@@ -33,14 +69,106 @@ impl StorageManager {
     pub fn new_with_dir(data_dir: PathBuf) -> Result<Self> {
         Ok(StorageManager {
             storage: Storage::new_with_dir(data_dir)?,
+            max_records: usize::MAX,
             file_modified_times: std::collections::HashMap::new(),
+            create_record_on_start: false,
+            git: None,
         })
     }
 
+    /// Load at most `max` records per day (`[storage] max_records`)
+    ///
+    /// Only the tracked loads of the viewed day are capped; totals across days
+    /// and record updates still read every record.
+    pub fn max_records(mut self, max: usize) -> Self {
+        self.max_records = max;
+        self
+    }
+
+    /// Have timers started without a source record add theirs right away (`[timer]`)
+    pub fn create_record_on_start(mut self, enabled: bool) -> Self {
+        self.create_record_on_start = enabled;
+        self
+    }
+
+    /// Commit the data directory to git once saves pause for `debounce` (`[storage]`)
+    pub fn git_autocommit(self, debounce: Option<Duration>) -> Self {
+        self.git_autocommit_with(debounce, Arc::new(SystemGit))
+    }
+
+    /// `git_autocommit` with another way of running git
+    pub fn git_autocommit_with(
+        mut self,
+        debounce: Option<Duration>,
+        runner: Arc<dyn CommandRunner>,
+    ) -> Self {
+        self.git = debounce.map(|debounce| GitAutoCommit {
+            schedule: AutoCommit::new(debounce),
+            runner,
+            running: None,
+        });
+        self
+    }
+
+    /// Start a due autocommit in the background and collect a finished one
+    ///
+    /// Returns a one-line message when a commit failed. Only one commit runs at a
+    /// time; saves made meanwhile go into the next one.
+    pub fn poll_git_autocommit(&mut self, now: Instant) -> Option<String> {
+        let data_dir = self.storage.data_dir.clone();
+        let git = self.git.as_mut()?;
+        if let Some(running) = &git.running {
+            match running.try_recv() {
+                Ok(result) => {
+                    git.running = None;
+                    if let Err(e) = result {
+                        return Some(format!("Git autocommit failed: {}", e));
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => return None,
+                Err(mpsc::TryRecvError::Disconnected) => git.running = None,
+            }
+        }
+
+        let message = git.schedule.take_due(now)?;
+        let runner = git.runner.clone();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(commit_data_dir(runner.as_ref(), &data_dir, &message));
+        });
+        git.running = Some(receiver);
+        None
+    }
+
+    /// Wait for a running autocommit and commit whatever is still pending (on exit)
+    pub fn finish_git_autocommit(&mut self) -> Result<()> {
+        let Some(git) = self.git.as_mut() else {
+            return Ok(());
+        };
+        if let Some(running) = git.running.take() {
+            running
+                .recv()
+                .unwrap_or(Ok(CommitOutcome::NothingToCommit))?;
+        }
+        if let Some(message) = git.schedule.take_pending() {
+            commit_data_dir(git.runner.as_ref(), &self.storage.data_dir, &message)?;
+        }
+        Ok(())
+    }
+
+    /// Remember a written day file's modification time and schedule its autocommit
+    fn track_save(&mut self, date: Date) {
+        let modified_time = self.storage.get_file_modified_time(&date);
+        self.file_modified_times.insert(date, modified_time);
+        if let Some(git) = self.git.as_mut() {
+            git.schedule.saved(date, Instant::now());
+        }
+    }
+
     /// Load day data with automatic file modification tracking
     /// Returns the loaded data and updates internal tracking
     pub fn load_with_tracking(&mut self, date: Date) -> Result<DayData> {
-        let data = self.storage.load(&date)?;
+        let data = self.storage.load_at_most(&date, self.max_records)?;
         let modified_time = self.storage.get_file_modified_time(&date);
         self.file_modified_times.insert(date, modified_time);
         Ok(data)
@@ -56,7 +184,7 @@ impl StorageManager {
 
         if !is_tracked {
             // First time checking this date - load it and start tracking
-            let data = self.storage.load(&date)?;
+            let data = self.storage.load_at_most(&date, self.max_records)?;
             self.file_modified_times.insert(date, current_modified);
             Ok(Some(data))
         } else {
@@ -64,7 +192,7 @@ impl StorageManager {
 
             // If modification times differ, reload the file
             if current_modified != last_known {
-                let data = self.storage.load(&date)?;
+                let data = self.storage.load_at_most(&date, self.max_records)?;
                 self.file_modified_times.insert(date, current_modified);
                 Ok(Some(data))
             } else {
@@ -79,27 +207,28 @@ impl StorageManager {
         let mut day_data = self.storage.load(&date)?;
         day_data.add_record(record);
         self.storage.save(&day_data)?;
-
-        // Update tracking after successful save
-        let modified_time = self.storage.get_file_modified_time(&date);
-        self.file_modified_times.insert(date, modified_time);
+        self.track_save(date);
 
         Ok(())
     }
 
     /// Update an existing work record (transactional: load → update → save → track)
+    ///
+    /// A record built from scratch keeps the fields on disk this version doesn't know.
     #[allow(dead_code)]
-    pub fn update_record(&mut self, date: Date, record: WorkRecord) -> Result<()> {
+    pub fn update_record(&mut self, date: Date, mut record: WorkRecord) -> Result<()> {
         let mut day_data = self.storage.load(&date)?;
 
+        if record.unknown.is_empty()
+            && let Some(existing) = day_data.work_records.get(&record.id)
+        {
+            record.unknown = existing.unknown.clone();
+        }
         // Update the record (will replace if ID exists)
         day_data.add_record(record);
 
         self.storage.save(&day_data)?;
-
-        // Update tracking after successful save
-        let modified_time = self.storage.get_file_modified_time(&date);
-        self.file_modified_times.insert(date, modified_time);
+        self.track_save(date);
 
         Ok(())
     }
@@ -116,10 +245,7 @@ impl StorageManager {
             .context(format!("Record with ID {} not found", id))?;
 
         self.storage.save(&day_data)?;
-
-        // Update tracking after successful save
-        let modified_time = self.storage.get_file_modified_time(&date);
-        self.file_modified_times.insert(date, modified_time);
+        self.track_save(date);
 
         Ok(record)
     }
@@ -127,11 +253,7 @@ impl StorageManager {
     /// Save day data and update tracking
     pub fn save(&mut self, day_data: &DayData) -> Result<()> {
         self.storage.save(day_data)?;
-
-        // Update tracking after successful save
-        let modified_time = self.storage.get_file_modified_time(&day_data.date);
-        self.file_modified_times
-            .insert(day_data.date, modified_time);
+        self.track_save(day_data.date);
 
         Ok(())
     }
@@ -141,6 +263,40 @@ impl StorageManager {
         self.file_modified_times.get(date).copied().flatten()
     }
 
+    /// Load one day file without tracking it (used for cross-day totals)
+    pub fn load_day(&self, date: Date) -> Result<DayData> {
+        self.storage.load(&date)
+    }
+
+    /// Load every stored day file (not tracked; used for cross-day totals)
+    pub fn load_all_days(&self) -> Result<Vec<DayData>> {
+        self.storage.load_all_days()
+    }
+
+    /// Every day from `first` to `last`, empty where there is no file (not tracked)
+    pub fn load_days_between(&self, first: Date, last: Date) -> Result<Vec<DayData>> {
+        self.storage.load_days_between(first, last)
+    }
+
+    /// Journaled timer sessions of `date` (not tracked)
+    pub fn load_session_log(&self, date: Date) -> Result<Vec<SessionLogEntry>> {
+        self.storage.load_session_log(&date)
+    }
+
+    /// Latest days with records up to `until`, newest first (not tracked)
+    pub fn recent_days(&self, until: Date, limit: usize) -> Result<Vec<DayData>> {
+        self.storage.recent_days(until, limit)
+    }
+
+    /// Saved record templates (not tracked)
+    pub fn load_templates(&self) -> Result<Vec<RecordTemplate>> {
+        self.storage.load_templates()
+    }
+
+    pub fn save_templates(&self, templates: &[RecordTemplate]) -> Result<()> {
+        self.storage.save_templates(templates)
+    }
+
     /// Pass-through methods for timer operations (these don't need tracking)
     #[allow(dead_code)]
     pub fn save_active_timer(&self, timer: &TimerState) -> Result<()> {
@@ -162,6 +318,7 @@ impl StorageManager {
         // Clone the storage for timer operations
         // This is safe because timer operations are independent
         crate::timer::TimerManager::new(self.storage.clone())
+            .create_record_on_start(self.create_record_on_start)
     }
 
     /// Start a new timer with the given task name and optional description
@@ -176,18 +333,69 @@ impl StorageManager {
         timer_manager.start(task_name, description, source_record_id, source_record_date)
     }
 
-    /// Stop the active timer and return the work record
-    pub fn stop_timer(&self) -> Result<crate::models::WorkRecord> {
+    /// Start a timer on today's `record` that counts the time since it ended
+    pub fn backfill_timer(
+        &self,
+        record: &crate::models::WorkRecord,
+        date: time::Date,
+    ) -> Result<TimerState> {
+        self.create_timer_manager().backfill(record, date)
+    }
+
+    /// Stop the running timer, if any, and start an unlinked one on `task_name`
+    pub fn switch_timer(
+        &self,
+        task_name: String,
+        description: Option<String>,
+    ) -> Result<(Option<crate::models::WorkRecord>, TimerState)> {
+        let timer_manager = self.create_timer_manager();
+        timer_manager.switch(task_name, description)
+    }
+
+    /// Stop the active timer and return the record it was written to
+    pub fn stop_timer(&self) -> Result<crate::timer::StoppedTimer> {
         let timer_manager = self.create_timer_manager();
         timer_manager.stop()
     }
 
+    /// Stop a timer left running past its start day at the configured cutoff
+    pub fn auto_stop_timer(
+        &self,
+        cutoff: crate::models::TimePoint,
+        restart: bool,
+        now: time::OffsetDateTime,
+    ) -> Result<Option<crate::models::WorkRecord>> {
+        let timer_manager = self.create_timer_manager();
+        timer_manager.auto_stop_if_overdue(cutoff, restart, now)
+    }
+
+    /// Split a session running since an earlier day at the midnight after it began
+    pub fn split_timer_at_midnight(
+        &self,
+        now: time::OffsetDateTime,
+    ) -> Result<Option<(crate::timer::StoppedTimer, TimerState)>> {
+        let timer_manager = self.create_timer_manager();
+        timer_manager.split_at_midnight(now)
+    }
+
+    /// Add a timestamped note to the active session
+    pub fn add_timer_note(&self, text: &str) -> Result<TimerState> {
+        let timer_manager = self.create_timer_manager();
+        timer_manager.add_note(text)
+    }
+
     /// Pause the active timer
     pub fn pause_timer(&self) -> Result<TimerState> {
         let timer_manager = self.create_timer_manager();
         timer_manager.pause()
     }
 
+    /// Pause the active timer as of `at`
+    pub fn pause_timer_at(&self, at: time::OffsetDateTime) -> Result<TimerState> {
+        let timer_manager = self.create_timer_manager();
+        timer_manager.pause_at(at)
+    }
+
     /// Resume a paused timer
     pub fn resume_timer(&self) -> Result<TimerState> {
         let timer_manager = self.create_timer_manager();
@@ -203,11 +411,20 @@ impl StorageManager {
 }
 
 impl Storage {
+    #[allow(dead_code)]
     pub fn new() -> Result<Self> {
-        let data_dir = Self::get_data_directory()?;
+        Self::new_for(&Profile::default())
+    }
+
+    /// Create a Storage in the given profile's data directory
+    pub fn new_for(profile: &Profile) -> Result<Self> {
+        let data_dir = profile.data_dir_in(&Self::get_data_directory()?);
         fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
 
-        Ok(Storage { data_dir })
+        Ok(Storage {
+            data_dir,
+            max_records: usize::MAX,
+        })
     }
 
     /// Create a new Storage with a custom directory (for testing)
@@ -215,7 +432,22 @@ impl Storage {
     #[allow(dead_code)]
     pub fn new_with_dir(data_dir: PathBuf) -> Result<Self> {
         fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
-        Ok(Storage { data_dir })
+        Ok(Storage {
+            data_dir,
+            max_records: usize::MAX,
+        })
+    }
+
+    /// Load at most `max` records per day, as the TUI's `StorageManager` does
+    #[cfg(test)]
+    pub fn max_records(mut self, max: usize) -> Self {
+        self.max_records = max;
+        self
+    }
+
+    /// Directory holding the day files
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
     }
 
     fn get_data_directory() -> Result<PathBuf> {
@@ -246,21 +478,34 @@ impl Storage {
     }
 
     pub fn load(&self, date: &Date) -> Result<DayData> {
+        self.load_at_most(date, self.max_records)
+    }
+
+    /// Load the day keeping at most `max_records` of its records
+    fn load_at_most(&self, date: &Date, max_records: usize) -> Result<DayData> {
         let path = self.get_file_path(date);
 
         if !path.exists() {
             return Ok(DayData::new(*date));
         }
 
-        let contents =
-            fs::read_to_string(&path).context(format!("Failed to read file: {:?}", path))?;
-
-        let day_data: DayData = serde_json::from_str(&contents).context("Failed to parse JSON")?;
-
-        Ok(day_data)
+        let file = fs::File::open(&path).context(format!("Failed to read file: {:?}", path))?;
+        day_file::parse(std::io::BufReader::new(file), max_records).context("Failed to parse JSON")
     }
 
+    /// Write the day to its file
+    ///
+    /// A day loaded only in part (`DayData::truncated`) is refused, since writing it
+    /// would drop the records that were left out.
     pub fn save(&self, day_data: &DayData) -> Result<()> {
+        if let Some(truncated) = day_data.truncated {
+            anyhow::bail!(
+                "{} has {} records but only {} were loaded ([storage] max_records); not saving",
+                day_data.date,
+                truncated.total,
+                truncated.kept
+            );
+        }
         let path = self.get_file_path(&day_data.date);
 
         let json = serde_json::to_string_pretty(day_data).context("Failed to serialize data")?;
@@ -270,6 +515,93 @@ impl Storage {
         Ok(())
     }
 
+    /// Dates of all stored day files, oldest first
+    ///
+    /// Only `YYYY-MM-DD.json` names count, so the timer file, backups and anything
+    /// else in the directory are skipped.
+    pub fn list_day_files(&self) -> Result<Vec<Date>> {
+        let format = time::macros::format_description!("[year]-[month]-[day]");
+        let mut dates: Vec<Date> = fs::read_dir(&self.data_dir)
+            .context(format!(
+                "Failed to read data directory: {:?}",
+                self.data_dir
+            ))?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let stem = name.strip_suffix(".json")?;
+                Date::parse(stem, &format).ok()
+            })
+            .collect();
+        dates.sort();
+        Ok(dates)
+    }
+
+    /// Every day from `first` to `last`, oldest first, empty where there is no file
+    ///
+    /// Only dates `list_day_files` found are read, rather than probing each day.
+    pub fn load_days_between(&self, first: Date, last: Date) -> Result<Vec<DayData>> {
+        let stored: BTreeSet<Date> = self
+            .list_day_files()?
+            .into_iter()
+            .filter(|date| (first..=last).contains(date))
+            .collect();
+        let mut days = Vec::new();
+        let mut date = first;
+        while date <= last {
+            if stored.contains(&date) {
+                days.push(self.load(&date)?);
+            } else {
+                days.push(DayData::new(date));
+            }
+            let Some(next) = date.next_day() else {
+                break;
+            };
+            date = next;
+        }
+        Ok(days)
+    }
+
+    /// Up to `limit` days on or before `until` that have records, newest first
+    pub fn recent_days(&self, until: Date, limit: usize) -> Result<Vec<DayData>> {
+        let mut days = Vec::new();
+        for date in self.list_day_files()?.into_iter().rev() {
+            if days.len() == limit {
+                break;
+            }
+            if date > until {
+                continue;
+            }
+            let day = self.load(&date)?;
+            if !day.work_records.is_empty() {
+                days.push(day);
+            }
+        }
+        Ok(days)
+    }
+
+    /// Load every stored day file, oldest first
+    ///
+    /// Fails on a day loaded only in part, as `save` does, so an export never
+    /// drops records silently.
+    pub fn load_all_days(&self) -> Result<Vec<DayData>> {
+        self.list_day_files()?
+            .iter()
+            .map(|date| {
+                let day = self.load(date)?;
+                if let Some(truncated) = day.truncated {
+                    anyhow::bail!(
+                        "{} has {} records but only {} were loaded ([storage] max_records)",
+                        day.date,
+                        truncated.total,
+                        truncated.kept
+                    );
+                }
+                Ok(day)
+            })
+            .collect()
+    }
+
     /// Get the modification time of a day data file
     /// Returns None if the file doesn't exist
     pub fn get_file_modified_time(&self, date: &Date) -> Option<std::time::SystemTime> {
@@ -294,6 +626,18 @@ impl Storage {
         Ok(())
     }
 
+    /// Write running_timer.json only if no timer file exists yet
+    ///
+    /// The existence check and the create are one hard link of the written file,
+    /// which fails if the target exists, so of several racing starts exactly one
+    /// wins and no reader sees the file empty. Returns false, writing nothing, when
+    /// a timer file is already there.
+    pub fn create_active_timer(&self, timer: &TimerState) -> Result<bool> {
+        let path = self.get_timer_file_path();
+        let json = serde_json::to_string_pretty(timer).context("Failed to serialize timer")?;
+        create_exclusive(&path, &json).context(format!("Failed to write timer file: {:?}", path))
+    }
+
     /// Load the active timer from running_timer.json
     ///
     /// Returns None if no timer file exists (no active timer)
@@ -312,6 +656,103 @@ impl Storage {
         Ok(Some(timer))
     }
 
+    /// Get the path to the journal of stopped timer sessions
+    fn get_session_log_path(&self) -> PathBuf {
+        self.data_dir.join("session_log.jsonl")
+    }
+
+    /// Append a stopped session to session_log.jsonl (one JSON object per line)
+    pub fn append_session_log(&self, entry: &SessionLogEntry) -> Result<()> {
+        use std::io::Write;
+
+        let path = self.get_session_log_path();
+        let line = serde_json::to_string(entry).context("Failed to serialize session")?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .context(format!("Failed to open session log: {:?}", path))?;
+        writeln!(file, "{}", line).context(format!("Failed to write session log: {:?}", path))?;
+        Ok(())
+    }
+
+    /// Length in bytes of session_log.jsonl, 0 if there is no log yet
+    pub fn session_log_len(&self) -> Result<u64> {
+        let path = self.get_session_log_path();
+        if !path.exists() {
+            return Ok(0);
+        }
+        let metadata =
+            fs::metadata(&path).context(format!("Failed to read session log: {:?}", path))?;
+        Ok(metadata.len())
+    }
+
+    /// Cut session_log.jsonl back to `len` bytes, dropping sessions appended since
+    pub fn truncate_session_log(&self, len: u64) -> Result<()> {
+        let path = self.get_session_log_path();
+        if !path.exists() {
+            return Ok(());
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_len(len))
+            .context(format!("Failed to write session log: {:?}", path))
+    }
+
+    /// Load the journaled sessions whose records live in `date`'s file, oldest first
+    ///
+    /// Unreadable lines are skipped so one bad entry doesn't hide the rest.
+    pub fn load_session_log(&self, date: &Date) -> Result<Vec<SessionLogEntry>> {
+        self.load_session_log_between(*date, *date)
+    }
+
+    /// Load the journaled sessions of every day from `first` to `last`, oldest first
+    pub fn load_session_log_between(
+        &self,
+        first: Date,
+        last: Date,
+    ) -> Result<Vec<SessionLogEntry>> {
+        let path = self.get_session_log_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents =
+            fs::read_to_string(&path).context(format!("Failed to read session log: {:?}", path))?;
+        Ok(contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<SessionLogEntry>(line).ok())
+            .filter(|entry| (first..=last).contains(&entry.date))
+            .collect())
+    }
+
+    /// Get the path to the saved record templates
+    fn get_templates_file_path(&self) -> PathBuf {
+        self.data_dir.join("templates.json")
+    }
+
+    /// Load the record templates from templates.json, none if there is no file yet
+    pub fn load_templates(&self) -> Result<Vec<RecordTemplate>> {
+        let path = self.get_templates_file_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .context(format!("Failed to read templates file: {:?}", path))?;
+        serde_json::from_str(&contents).context("Failed to parse templates JSON")
+    }
+
+    /// Save the record templates to templates.json
+    pub fn save_templates(&self, templates: &[RecordTemplate]) -> Result<()> {
+        let path = self.get_templates_file_path();
+        let json =
+            serde_json::to_string_pretty(templates).context("Failed to serialize templates")?;
+        fs::write(&path, json).context(format!("Failed to write templates file: {:?}", path))?;
+        Ok(())
+    }
+
     /// Clear the active timer by deleting running_timer.json
     pub fn clear_active_timer(&self) -> Result<()> {
         let path = self.get_timer_file_path();
@@ -324,6 +765,51 @@ impl Storage {
     }
 }
 
+/// Create `path` with `contents`, or return false if it already exists
+///
+/// Anything that must be claimed by one process at a time (the running timer, a
+/// lock file) goes through here rather than checking for the file and then writing it.
+/// The contents are written to a temporary file first and hard-linked into place,
+/// so `path` never exists empty or half written.
+fn create_exclusive(path: &Path, contents: &str) -> std::io::Result<bool> {
+    static TEMP_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(
+        ".{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    ));
+    let linked = write_new(&temp, contents).and_then(|()| fs::hard_link(&temp, path));
+    let _ = fs::remove_file(&temp);
+    match linked {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        // A file system without hard links: claim the file directly instead
+        Err(e) if e.kind() == std::io::ErrorKind::Unsupported => match write_new(path, contents) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/// Write a file that must not exist yet, removing it again if the write fails
+fn write_new(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?;
+    if let Err(e) = file.write_all(contents.as_bytes()) {
+        drop(file);
+        let _ = fs::remove_file(path);
+        return Err(e);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -501,6 +987,202 @@ mod tests {
         assert_eq!(loaded_day2.work_records.get(&1).unwrap().name, "Day2Task");
     }
 
+    #[test]
+    fn test_load_all_days_skips_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let later = Date::from_calendar_date(2025, time::Month::November, 7).unwrap();
+        let mut day = DayData::new(later);
+        day.add_record(create_test_record(1, "Later"));
+        storage.save(&day).unwrap();
+        storage.save(&DayData::new(create_test_date())).unwrap();
+        fs::write(temp_dir.path().join("running_timer.json"), "{}").unwrap();
+        fs::write(temp_dir.path().join("notes.json"), "{}").unwrap();
+
+        assert_eq!(
+            storage.list_day_files().unwrap(),
+            vec![create_test_date(), later]
+        );
+        let days = storage.load_all_days().unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[1].work_records.len(), 1);
+    }
+
+    #[test]
+    fn test_list_day_files_only_valid_dates_sorted() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let day = |d| Date::from_calendar_date(2025, time::Month::November, d).unwrap();
+
+        for d in [12, 3, 7] {
+            storage.save(&DayData::new(day(d))).unwrap();
+        }
+        for junk in [
+            "running_timer.json",
+            "2025-11-05.json.bak",
+            "2025-11-31.json",
+            "archive-2025-10.json",
+            "2025-11-04.txt",
+            "README",
+        ] {
+            fs::write(temp_dir.path().join(junk), "{}").unwrap();
+        }
+        fs::create_dir(temp_dir.path().join("2025-11-08")).unwrap();
+
+        assert_eq!(
+            storage.list_day_files().unwrap(),
+            vec![day(3), day(7), day(12)]
+        );
+    }
+
+    #[test]
+    fn test_large_day_is_loaded_in_part_and_not_saved() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let date = create_test_date();
+        let mut day = DayData::new(date);
+        for id in 1..=12 {
+            day.add_record(create_test_record(id, "Import"));
+        }
+        storage.save(&day).unwrap();
+
+        assert_eq!(storage.load(&date).unwrap().truncated, None);
+
+        let capped = storage.clone().max_records(5);
+        let loaded = capped.load(&date).unwrap();
+        assert_eq!(loaded.work_records.len(), 5);
+        assert_eq!(
+            loaded.truncated,
+            Some(crate::models::Truncated { kept: 5, total: 12 })
+        );
+        let err = capped.save(&loaded).unwrap_err();
+        assert!(err.to_string().contains("only 5 were loaded"));
+        // The file still has every record
+        assert_eq!(storage.load(&date).unwrap().work_records.len(), 12);
+
+        let err = capped.load_all_days().unwrap_err();
+        assert!(err.to_string().contains("only 5 were loaded"));
+        assert_eq!(storage.load_all_days().unwrap()[0].work_records.len(), 12);
+    }
+
+    #[test]
+    fn test_templates_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        assert!(storage.load_templates().unwrap().is_empty());
+
+        let mut record = create_test_record(4, "Standup");
+        record.description = "Daily sync".to_string();
+        record
+            .extra
+            .insert("client".to_string(), "Acme".to_string());
+        let templates = vec![
+            RecordTemplate::from_record("Standup", &record),
+            RecordTemplate::from_record("Review", &create_test_record(5, "Review")),
+        ];
+        storage.save_templates(&templates).unwrap();
+
+        assert!(temp_dir.path().join("templates.json").exists());
+        assert_eq!(storage.load_templates().unwrap(), templates);
+        // Not mistaken for a day file
+        assert!(storage.list_day_files().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_days_between_fills_gaps() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let day = |d| Date::from_calendar_date(2025, time::Month::November, d).unwrap();
+
+        for d in [2, 4, 9] {
+            let mut day_data = DayData::new(day(d));
+            day_data.add_record(create_test_record(1, "Coding"));
+            storage.save(&day_data).unwrap();
+        }
+
+        let days = storage.load_days_between(day(3), day(6)).unwrap();
+        let dates: Vec<Date> = days.iter().map(|day| day.date).collect();
+        assert_eq!(dates, vec![day(3), day(4), day(5), day(6)]);
+        let records: Vec<usize> = days.iter().map(|day| day.work_records.len()).collect();
+        assert_eq!(records, vec![0, 1, 0, 0]);
+
+        assert!(
+            storage
+                .load_days_between(day(6), day(3))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_recent_days_newest_first_with_records_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let day = |d| Date::from_calendar_date(2025, time::Month::November, d).unwrap();
+
+        for d in [3, 4, 6, 10, 12] {
+            let mut data = DayData::new(day(d));
+            data.add_record(create_test_record(1, "Work"));
+            storage.save(&data).unwrap();
+        }
+        // Emptied day, and files that aren't days
+        storage.save(&DayData::new(day(7))).unwrap();
+        fs::write(temp_dir.path().join("running_timer.json"), "{}").unwrap();
+        fs::write(temp_dir.path().join("2025-11-08.json.bak"), "{}").unwrap();
+
+        let dates = |days: Vec<DayData>| days.iter().map(|day| day.date).collect::<Vec<_>>();
+        assert_eq!(
+            dates(storage.recent_days(day(10), 3).unwrap()),
+            vec![day(10), day(6), day(4)]
+        );
+        assert_eq!(
+            dates(storage.recent_days(day(9), 10).unwrap()),
+            vec![day(6), day(4), day(3)]
+        );
+        assert!(storage.recent_days(day(2), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_session_log_filters_by_date_and_skips_bad_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let other_date = Date::from_calendar_date(2025, time::Month::November, 7).unwrap();
+
+        let entry = |date, record_id| SessionLogEntry {
+            date,
+            record_id,
+            start: TimePoint::new(9, 0).unwrap(),
+            end: TimePoint::new(10, 0).unwrap(),
+            extended: false,
+            focus: crate::timer::SessionFocus::default(),
+        };
+        storage
+            .append_session_log(&entry(create_test_date(), 1))
+            .unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(temp_dir.path().join("session_log.jsonl"))
+            .and_then(|mut file| std::io::Write::write_all(&mut file, b"not json\n"))
+            .unwrap();
+        storage.append_session_log(&entry(other_date, 2)).unwrap();
+        storage
+            .append_session_log(&entry(create_test_date(), 3))
+            .unwrap();
+
+        let sessions = storage.load_session_log(&create_test_date()).unwrap();
+        assert_eq!(
+            sessions.iter().map(|s| s.record_id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(
+            storage
+                .load_session_log(&Date::from_calendar_date(2024, time::Month::January, 1).unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_json_format_is_pretty() {
         let temp_dir = TempDir::new().unwrap();
@@ -547,11 +1229,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id: None,
             source_record_date: None,
+            notes: Vec::new(),
         };
 
         // Save
@@ -585,11 +1269,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id: None,
             source_record_date: None,
+            notes: Vec::new(),
         };
 
         // Save
@@ -603,6 +1289,23 @@ mod tests {
         assert!(storage.load_active_timer().unwrap().is_none());
     }
 
+    #[test]
+    fn test_create_exclusive_writes_whole_file_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("running_timer.json");
+
+        assert!(create_exclusive(&path, "first").unwrap());
+        assert!(!create_exclusive(&path, "second").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        // No temporary file is left behind
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+
+        // A failed create leaves nothing that would block the next one
+        let missing = temp_dir.path().join("missing").join("running_timer.json");
+        assert!(create_exclusive(&missing, "first").is_err());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_clear_active_timer_when_none_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -833,11 +1536,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Paused,
             paused_duration_secs: 120,
+            pause_count: 0,
             paused_at: Some(now),
             created_at: now,
             updated_at: now,
             source_record_id: None,
             source_record_date: None,
+            notes: Vec::new(),
         };
 
         storage.save_active_timer(&timer).unwrap();
@@ -867,11 +1572,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id: Some(5),
             source_record_date: Some(source_date),
+            notes: Vec::new(),
         };
 
         storage.save_active_timer(&timer).unwrap();
@@ -921,11 +1628,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id: None,
             source_record_date: None,
+            notes: Vec::new(),
         };
 
         // Test passthrough methods
@@ -1099,4 +1808,76 @@ mod tests {
         assert!(loaded.work_records.contains_key(&2));
         assert!(!loaded.work_records.contains_key(&1));
     }
+
+    /// Keeps each commit message; fails the commit itself when `fail` is set
+    struct CommitLog {
+        fail: bool,
+        messages: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl CommandRunner for CommitLog {
+        fn git(&self, _dir: &Path, args: &[&str]) -> Result<String> {
+            match args[0] {
+                "commit" if self.fail => Err(anyhow::anyhow!("index.lock exists")),
+                "commit" => {
+                    self.messages.lock().unwrap().push(args[3].to_string());
+                    Ok(String::new())
+                }
+                "status" => Ok(" M 2025-11-06.json\n".to_string()),
+                _ => Ok(String::new()),
+            }
+        }
+    }
+
+    fn git_manager(temp_dir: &TempDir, fail: bool) -> (StorageManager, Arc<CommitLog>) {
+        let log = Arc::new(CommitLog {
+            fail,
+            messages: std::sync::Mutex::new(Vec::new()),
+        });
+        let manager = StorageManager::new_with_dir(temp_dir.path().to_path_buf())
+            .unwrap()
+            .git_autocommit_with(Some(Duration::ZERO), log.clone());
+        (manager, log)
+    }
+
+    #[test]
+    fn test_saves_are_committed_together() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, log) = git_manager(&temp_dir, false);
+        let date = create_test_date();
+
+        manager
+            .add_record(date, create_test_record(1, "Task"))
+            .unwrap();
+        manager
+            .add_record(date, create_test_record(2, "Other"))
+            .unwrap();
+        assert_eq!(manager.poll_git_autocommit(Instant::now()), None);
+        manager.finish_git_autocommit().unwrap();
+
+        assert_eq!(
+            *log.messages.lock().unwrap(),
+            ["work-tuimer: 2025-11-06 update"]
+        );
+    }
+
+    #[test]
+    fn test_failed_autocommit_is_reported_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut manager, _log) = git_manager(&temp_dir, true);
+
+        manager
+            .add_record(create_test_date(), create_test_record(1, "Task"))
+            .unwrap();
+        let mut reported = Vec::new();
+        for _ in 0..200 {
+            reported.extend(manager.poll_git_autocommit(Instant::now()));
+            if !reported.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(reported, ["Git autocommit failed: index.lock exists"]);
+        assert_eq!(manager.poll_git_autocommit(Instant::now()), None);
+    }
 }
@@ -0,0 +1,113 @@
+//! 12 or 24 hour clock times, following the system locale unless `[ui] time_format` is set
+//!
+//! Only the locale environment variables are read (`LC_ALL`, `LC_TIME`, `LANG`, in
+//! POSIX precedence), which terminals set on Linux and macOS alike. Anything not
+//! known to prefer a 12 hour clock, or no locale at all, gets 24 hours.
+
+use crate::config::TimeFormat;
+use crate::models::TimePoint;
+use std::sync::OnceLock;
+
+/// Language and territory pairs whose everyday clock is 12 hours
+const TWELVE_HOUR_LOCALES: &[(&str, &str)] = &[
+    ("en", "US"),
+    ("en", "CA"),
+    ("en", "AU"),
+    ("en", "NZ"),
+    ("en", "PH"),
+    ("en", "IN"),
+    ("es", "US"),
+    ("hi", "IN"),
+    ("ar", "EG"),
+    ("ar", "SA"),
+    ("ko", "KR"),
+];
+
+/// Clock style of the system locale, read once per run; 24 hours when unknown
+pub fn detect_time_format() -> TimeFormat {
+    static DETECTED: OnceLock<TimeFormat> = OnceLock::new();
+    *DETECTED.get_or_init(|| {
+        ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| time_format_for_locale(&locale))
+            .unwrap_or_default()
+    })
+}
+
+/// Clock style of a locale name like `en_US.UTF-8`, `de_DE@euro` or `en-GB`
+///
+/// None for `C`, `POSIX` and names without a territory, which say nothing about it.
+pub fn time_format_for_locale(locale: &str) -> Option<TimeFormat> {
+    let name = locale.split(['.', '@']).next()?;
+    let (language, territory) = name.split_once(['_', '-'])?;
+    if language.is_empty() || territory.is_empty() {
+        return None;
+    }
+    let twelve = TWELVE_HOUR_LOCALES.iter().any(|(lang, land)| {
+        language.eq_ignore_ascii_case(lang) && territory.eq_ignore_ascii_case(land)
+    });
+    Some(if twelve {
+        TimeFormat::H12
+    } else {
+        TimeFormat::H24
+    })
+}
+
+/// "09:05" or "9:05 AM"
+pub fn format_time(time: TimePoint, format: TimeFormat) -> String {
+    match format {
+        TimeFormat::H24 => time.to_string(),
+        TimeFormat::H12 => {
+            let suffix = if time.hour < 12 { "AM" } else { "PM" };
+            let hour = match time.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            format!("{}:{:02} {}", hour, time.minute, suffix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locales_map_to_their_clock() {
+        for locale in ["en_US.UTF-8", "en_US", "en-US", "en_CA.utf8", "ko_KR.UTF-8"] {
+            assert_eq!(
+                time_format_for_locale(locale),
+                Some(TimeFormat::H12),
+                "{}",
+                locale
+            );
+        }
+        for locale in ["en_GB.UTF-8", "de_DE@euro", "fr_CA.UTF-8", "ja_JP.UTF-8"] {
+            assert_eq!(
+                time_format_for_locale(locale),
+                Some(TimeFormat::H24),
+                "{}",
+                locale
+            );
+        }
+    }
+
+    #[test]
+    fn test_locales_without_a_territory_say_nothing() {
+        for locale in ["C", "POSIX", "C.UTF-8", "en", "", "_US"] {
+            assert_eq!(time_format_for_locale(locale), None, "{}", locale);
+        }
+    }
+
+    #[test]
+    fn test_format_time_in_both_clocks() {
+        let at = |h, m| TimePoint::new(h, m).unwrap();
+        assert_eq!(format_time(at(9, 5), TimeFormat::H24), "09:05");
+        assert_eq!(format_time(at(9, 5), TimeFormat::H12), "9:05 AM");
+        assert_eq!(format_time(at(0, 30), TimeFormat::H12), "12:30 AM");
+        assert_eq!(format_time(at(12, 0), TimeFormat::H12), "12:00 PM");
+        assert_eq!(format_time(at(23, 59), TimeFormat::H12), "11:59 PM");
+    }
+}
@@ -0,0 +1,240 @@
+//! Dates in the configured language and format, and the calendar grid's week layout
+//!
+//! A small built-in table of month and weekday names per `Locale`, and a subset of
+//! strftime: `%Y %y %m %d %e %B %b %A %a %%`. Anything else is copied as written.
+
+use crate::config::{Locale, WeekStart};
+use time::{Date, Month, Weekday};
+
+/// Full month names, January first
+fn month_names(locale: Locale) -> [&'static str; 12] {
+    match locale {
+        Locale::En => [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        Locale::De => [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        Locale::Fr => [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        Locale::Es => [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+    }
+}
+
+/// Abbreviated month names, January first
+fn short_month_names(locale: Locale) -> [&'static str; 12] {
+    match locale {
+        Locale::En => [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        Locale::De => [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        Locale::Fr => [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        Locale::Es => [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+    }
+}
+
+/// Full weekday names, Monday first
+fn weekday_names(locale: Locale) -> [&'static str; 7] {
+    match locale {
+        Locale::En => [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        Locale::De => [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        Locale::Fr => [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        Locale::Es => [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+    }
+}
+
+/// Abbreviated weekday names, Monday first
+fn short_weekday_names(locale: Locale) -> [&'static str; 7] {
+    match locale {
+        Locale::En => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        Locale::De => ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        Locale::Fr => ["lun", "mar", "mer", "jeu", "ven", "sam", "dim"],
+        Locale::Es => ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+    }
+}
+
+pub fn month_name(month: Month, locale: Locale) -> &'static str {
+    month_names(locale)[month as usize - 1]
+}
+
+pub fn short_weekday_name(weekday: Weekday, locale: Locale) -> &'static str {
+    short_weekday_names(locale)[weekday.number_days_from_monday() as usize]
+}
+
+/// `date` rendered with `pattern`'s tokens
+pub fn format_date(date: Date, pattern: &str, locale: Locale) -> String {
+    let month = date.month() as usize - 1;
+    let weekday = date.weekday().number_days_from_monday() as usize;
+
+    let mut out = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(&date.year().to_string()),
+            Some('y') => out.push_str(&format!("{:02}", date.year().rem_euclid(100))),
+            Some('m') => out.push_str(&format!("{:02}", date.month() as u8)),
+            Some('d') => out.push_str(&format!("{:02}", date.day())),
+            Some('e') => out.push_str(&date.day().to_string()),
+            Some('B') => out.push_str(month_names(locale)[month]),
+            Some('b') => out.push_str(short_month_names(locale)[month]),
+            Some('A') => out.push_str(weekday_names(locale)[weekday]),
+            Some('a') => out.push_str(short_weekday_names(locale)[weekday]),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+/// The calendar's columns, left to right
+pub fn week_columns(week_start: WeekStart) -> [Weekday; 7] {
+    let mut columns = [week_start.weekday(); 7];
+    for i in 1..7 {
+        columns[i] = columns[i - 1].next();
+    }
+    columns
+}
+
+/// Empty cells before day 1 in a calendar grid starting weeks on `week_start`
+pub fn calendar_offset(first_of_month: Weekday, week_start: WeekStart) -> u8 {
+    let days_from_start = first_of_month.number_days_from_monday() as i8
+        - week_start.weekday().number_days_from_monday() as i8;
+    days_from_start.rem_euclid(7) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_format_date_tokens() {
+        let day = date!(2025 - 03 - 02);
+        assert_eq!(format_date(day, "%Y-%m-%d", Locale::En), "2025-03-02");
+        assert_eq!(format_date(day, "%d.%m.%y", Locale::De), "02.03.25");
+        assert_eq!(format_date(day, "%a %e %b", Locale::En), "Sun 2 Mar");
+        assert_eq!(
+            format_date(day, "%A, %e. %B %Y", Locale::De),
+            "Sonntag, 2. März 2025"
+        );
+        assert_eq!(format_date(day, "%A %e %B", Locale::Fr), "dimanche 2 mars");
+        assert_eq!(format_date(day, "%a %e %b", Locale::Es), "dom 2 mar");
+        // Literal percent, unknown tokens and a trailing % are kept as written
+        assert_eq!(format_date(day, "100%% %Q %", Locale::En), "100% %Q %");
+    }
+
+    #[test]
+    fn test_week_columns() {
+        assert_eq!(week_columns(WeekStart::Monday)[0], Weekday::Monday);
+        assert_eq!(week_columns(WeekStart::Monday)[6], Weekday::Sunday);
+        assert_eq!(week_columns(WeekStart::Sunday)[0], Weekday::Sunday);
+        assert_eq!(week_columns(WeekStart::Sunday)[6], Weekday::Saturday);
+    }
+
+    #[test]
+    fn test_calendar_offset_for_month_starting_on_sunday() {
+        // June 2025 starts on a Sunday
+        let first = date!(2025 - 06 - 01).weekday();
+        assert_eq!(first, Weekday::Sunday);
+        assert_eq!(calendar_offset(first, WeekStart::Monday), 6);
+        assert_eq!(calendar_offset(first, WeekStart::Sunday), 0);
+
+        // November 2025 starts on a Saturday
+        let first = date!(2025 - 11 - 01).weekday();
+        assert_eq!(calendar_offset(first, WeekStart::Monday), 5);
+        assert_eq!(calendar_offset(first, WeekStart::Sunday), 6);
+
+        // A Monday start needs no padding only with Monday-first weeks
+        let first = date!(2025 - 09 - 01).weekday();
+        assert_eq!(calendar_offset(first, WeekStart::Monday), 0);
+        assert_eq!(calendar_offset(first, WeekStart::Sunday), 1);
+    }
+}
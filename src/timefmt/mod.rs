@@ -0,0 +1,236 @@
+//! Shared time helpers: timer elapsed time, duration, date and clock formatting, calendar math
+//!
+//! Used by the CLI, the TUI and the timer alike. Nothing here touches storage, so
+//! the render path can call it with its own injected clock.
+
+mod clock;
+mod dates;
+
+pub use clock::{detect_time_format, format_time};
+pub use dates::{calendar_offset, format_date, month_name, short_weekday_name, week_columns};
+
+use crate::timer::{TimerState, TimerStatus};
+use std::time::Duration;
+use time::{Month, OffsetDateTime};
+
+/// Current local time, falling back to UTC if the local offset can't be determined
+pub fn now_local() -> OffsetDateTime {
+    OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc())
+}
+
+/// Time a timer has been running at `now`, minus paused time
+///
+/// A paused timer stops counting at the moment it was paused.
+pub fn elapsed(timer: &TimerState, now: OffsetDateTime) -> Duration {
+    let end_point = if timer.status == TimerStatus::Paused {
+        timer.paused_at.unwrap_or(now)
+    } else {
+        now
+    };
+
+    let elapsed = end_point - timer.start_time;
+    let paused = Duration::from_secs(timer.paused_duration_secs.max(0) as u64);
+
+    // A start time in the future (clock skew) counts as nothing elapsed
+    let elapsed: Duration = elapsed.try_into().unwrap_or(Duration::ZERO);
+    elapsed.saturating_sub(paused)
+}
+
+/// Stopwatch style: "MM:SS" under an hour, "H:MM:SS" above
+pub fn format_duration_clock(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+/// Minute precision for an ambient clock: "HH:MM", seconds dropped
+pub fn format_duration_minutes(duration: Duration) -> String {
+    let total_minutes = duration.as_secs() / 60;
+    format!("{:02}:{:02}", total_minutes / 60, total_minutes % 60)
+}
+
+/// Human style with seconds: "2m 05s" under an hour, "1h 01m 01s" above
+pub fn format_duration_human(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else {
+        format!("{}m {:02}s", minutes, seconds)
+    }
+}
+
+/// Minute totals as shown in tables and reports: "Xh YYm"
+pub fn format_minutes(minutes: u32) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Number of days in `month` of `year`
+pub fn days_in_month(month: Month, year: i32) -> u8 {
+    match month {
+        Month::January
+        | Month::March
+        | Month::May
+        | Month::July
+        | Month::August
+        | Month::October
+        | Month::December => 31,
+        Month::April | Month::June | Month::September | Month::November => 30,
+        Month::February => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+    }
+}
+
+/// Gregorian leap year: every fourth year, except centuries not divisible by 400
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn timer_started_at(start: OffsetDateTime) -> TimerState {
+        TimerState {
+            id: None,
+            task_name: "Task".to_string(),
+            description: None,
+            start_time: start,
+            end_time: None,
+            date: start.date(),
+            status: TimerStatus::Running,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: start,
+            source_record_id: None,
+            source_record_date: None,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_elapsed_running() {
+        let timer = timer_started_at(datetime!(2025-11-06 09:00 UTC));
+        let now = datetime!(2025-11-06 10:30:15 UTC);
+        assert_eq!(elapsed(&timer, now), Duration::from_secs(5415));
+    }
+
+    #[test]
+    fn test_elapsed_excludes_paused_time() {
+        let mut timer = timer_started_at(datetime!(2025-11-06 09:00 UTC));
+        timer.paused_duration_secs = 600;
+        let now = datetime!(2025-11-06 10:00 UTC);
+        assert_eq!(elapsed(&timer, now), Duration::from_secs(3000));
+    }
+
+    #[test]
+    fn test_elapsed_paused_stops_at_pause() {
+        let mut timer = timer_started_at(datetime!(2025-11-06 09:00 UTC));
+        timer.status = TimerStatus::Paused;
+        timer.paused_at = Some(datetime!(2025-11-06 09:20 UTC));
+        let now = datetime!(2025-11-06 12:00 UTC);
+        assert_eq!(elapsed(&timer, now), Duration::from_secs(20 * 60));
+    }
+
+    #[test]
+    fn test_elapsed_future_start_is_zero() {
+        let timer = timer_started_at(datetime!(2025-11-06 09:00 UTC));
+        let now = datetime!(2025-11-06 08:00 UTC);
+        assert_eq!(elapsed(&timer, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_elapsed_across_days() {
+        let timer = timer_started_at(datetime!(2025-11-06 09:00 UTC));
+        let now = datetime!(2025-11-08 10:00 UTC);
+        assert_eq!(elapsed(&timer, now), Duration::from_secs(49 * 3600));
+    }
+
+    #[test]
+    fn test_format_duration_clock() {
+        assert_eq!(format_duration_clock(Duration::ZERO), "00:00");
+        assert_eq!(format_duration_clock(Duration::from_secs(125)), "02:05");
+        assert_eq!(format_duration_clock(Duration::from_secs(3661)), "1:01:01");
+        assert_eq!(
+            format_duration_clock(Duration::from_secs(27 * 3600 + 5)),
+            "27:00:05"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_minutes() {
+        assert_eq!(format_duration_minutes(Duration::ZERO), "00:00");
+        // Seconds are dropped, not rounded
+        assert_eq!(format_duration_minutes(Duration::from_secs(59)), "00:00");
+        assert_eq!(format_duration_minutes(Duration::from_secs(125)), "00:02");
+        assert_eq!(
+            format_duration_minutes(Duration::from_secs(83 * 60 + 59)),
+            "01:23"
+        );
+        assert_eq!(
+            format_duration_minutes(Duration::from_secs(27 * 3600 + 5 * 60)),
+            "27:05"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_human() {
+        assert_eq!(format_duration_human(Duration::ZERO), "0m 00s");
+        assert_eq!(format_duration_human(Duration::from_secs(45)), "0m 45s");
+        assert_eq!(format_duration_human(Duration::from_secs(125)), "2m 05s");
+        assert_eq!(
+            format_duration_human(Duration::from_secs(3661)),
+            "1h 01m 01s"
+        );
+        assert_eq!(
+            format_duration_human(Duration::from_secs(30 * 3600 + 60)),
+            "30h 01m 00s"
+        );
+    }
+
+    #[test]
+    fn test_format_minutes() {
+        assert_eq!(format_minutes(0), "0h 00m");
+        assert_eq!(format_minutes(75), "1h 15m");
+        assert_eq!(format_minutes(25 * 60 + 9), "25h 09m");
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(Month::January, 2025), 31);
+        assert_eq!(days_in_month(Month::April, 2025), 30);
+        assert_eq!(days_in_month(Month::February, 2025), 28);
+        assert_eq!(days_in_month(Month::February, 2024), 29);
+        assert_eq!(days_in_month(Month::February, 1900), 28);
+        assert_eq!(days_in_month(Month::February, 2000), 29);
+    }
+
+    #[test]
+    fn test_is_leap_year() {
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(2023));
+        // Centuries are leap years only when divisible by 400
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2100));
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(2400));
+    }
+}
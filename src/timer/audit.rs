@@ -0,0 +1,288 @@
+//! Compare a day's records against the timer sessions that wrote them
+//!
+//! Every stopped session is journaled with the record it created or extended.
+//! Records whose times were hand-edited afterwards by more than a tolerance are
+//! reported, so a messy day can be reconciled against what the timer measured.
+
+use crate::models::{TimePoint, WorkRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use time::Date;
+
+/// One stopped timer session, as written to the session journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionLogEntry {
+    /// Day file the session's record was written to
+    pub date: Date,
+    /// Record the session created or extended
+    pub record_id: u32,
+    /// Wall-clock start of the session
+    pub start: TimePoint,
+    /// Wall-clock end of the session
+    pub end: TimePoint,
+    /// The session extended an existing record (only its end was set by the timer)
+    #[serde(default)]
+    pub extended: bool,
+    /// How the session went, for focus statistics
+    #[serde(flatten)]
+    pub focus: SessionFocus,
+}
+
+/// Pause bookkeeping for one session
+///
+/// Journals written before these were tracked read back as all defaults, with
+/// `active_secs` left empty so readers fall back to the wall-clock span.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionFocus {
+    /// Seconds the timer was running, pauses excluded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_secs: Option<u64>,
+    /// Times the session was paused
+    #[serde(default)]
+    pub pauses: u32,
+    /// Seconds spent paused
+    #[serde(default)]
+    pub paused_secs: u64,
+}
+
+/// Which end of a record disagrees with its session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftEdge {
+    Start,
+    End,
+}
+
+/// A disagreement between the records and the session journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftFinding {
+    /// A record's start or end differs from the timer's by `minutes`
+    Edited {
+        record_id: u32,
+        edge: DriftEdge,
+        recorded: TimePoint,
+        timed: TimePoint,
+        minutes: u32,
+    },
+    /// A session's record no longer exists
+    RecordDeleted {
+        record_id: u32,
+        session: SessionLogEntry,
+    },
+}
+
+impl fmt::Display for DriftFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriftFinding::Edited {
+                record_id,
+                edge,
+                recorded,
+                timed,
+                minutes,
+            } => {
+                let edge = match edge {
+                    DriftEdge::Start => "start",
+                    DriftEdge::End => "end",
+                };
+                write!(
+                    f,
+                    "record {} differs from timer session by {}m ({} {}, timer {})",
+                    record_id, minutes, edge, recorded, timed
+                )
+            }
+            DriftFinding::RecordDeleted { record_id, session } => write!(
+                f,
+                "record {} was deleted (timer session {}-{})",
+                record_id, session.start, session.end
+            ),
+        }
+    }
+}
+
+/// Compare records against the sessions that produced them
+///
+/// Records never touched by a timer are not reported. When several sessions
+/// wrote the same record, its start is checked against the first session that
+/// created it and its end against the last one.
+pub fn find_drift(
+    records: &[&WorkRecord],
+    sessions: &[SessionLogEntry],
+    tolerance_minutes: u32,
+) -> Vec<DriftFinding> {
+    let mut by_record: HashMap<u32, Vec<&SessionLogEntry>> = HashMap::new();
+    for session in sessions {
+        by_record
+            .entry(session.record_id)
+            .or_default()
+            .push(session);
+    }
+
+    let mut record_ids: Vec<u32> = by_record.keys().copied().collect();
+    record_ids.sort();
+
+    let mut findings = Vec::new();
+    for record_id in record_ids {
+        let sessions = &by_record[&record_id];
+        let Some(record) = records.iter().find(|record| record.id == record_id) else {
+            let last = sessions[sessions.len() - 1];
+            findings.push(DriftFinding::RecordDeleted {
+                record_id,
+                session: last.clone(),
+            });
+            continue;
+        };
+
+        if let Some(created) = sessions.iter().find(|session| !session.extended) {
+            push_if_drifted(
+                &mut findings,
+                record_id,
+                DriftEdge::Start,
+                record.start,
+                created.start,
+                tolerance_minutes,
+            );
+        }
+        let last = sessions[sessions.len() - 1];
+        push_if_drifted(
+            &mut findings,
+            record_id,
+            DriftEdge::End,
+            record.end,
+            last.end,
+            tolerance_minutes,
+        );
+    }
+    findings
+}
+
+fn push_if_drifted(
+    findings: &mut Vec<DriftFinding>,
+    record_id: u32,
+    edge: DriftEdge,
+    recorded: TimePoint,
+    timed: TimePoint,
+    tolerance_minutes: u32,
+) {
+    let minutes = recorded
+        .to_minutes_since_midnight()
+        .abs_diff(timed.to_minutes_since_midnight());
+    if minutes > tolerance_minutes {
+        findings.push(DriftFinding::Edited {
+            record_id,
+            edge,
+            recorded,
+            timed,
+            minutes,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn t(hour: u8, minute: u8) -> TimePoint {
+        TimePoint::new(hour, minute).unwrap()
+    }
+
+    fn record(id: u32, start: TimePoint, end: TimePoint) -> WorkRecord {
+        WorkRecord::new(id, format!("Task {}", id), start, end)
+    }
+
+    fn session(record_id: u32, start: TimePoint, end: TimePoint) -> SessionLogEntry {
+        SessionLogEntry {
+            date: date!(2025 - 11 - 06),
+            record_id,
+            start,
+            end,
+            extended: false,
+            focus: SessionFocus::default(),
+        }
+    }
+
+    #[test]
+    fn test_untouched_record_has_no_findings() {
+        let r = record(1, t(9, 0), t(10, 0));
+        let sessions = vec![session(1, t(9, 0), t(10, 2))];
+        assert!(find_drift(&[&r], &sessions, 5).is_empty());
+    }
+
+    #[test]
+    fn test_edited_start() {
+        let r = record(7, t(8, 35), t(10, 0));
+        let sessions = vec![session(7, t(9, 0), t(10, 0))];
+
+        let findings = find_drift(&[&r], &sessions, 5);
+        assert_eq!(
+            findings,
+            vec![DriftFinding::Edited {
+                record_id: 7,
+                edge: DriftEdge::Start,
+                recorded: t(8, 35),
+                timed: t(9, 0),
+                minutes: 25,
+            }]
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            "record 7 differs from timer session by 25m (start 08:35, timer 09:00)"
+        );
+    }
+
+    #[test]
+    fn test_edited_end_uses_last_session() {
+        let r = record(3, t(9, 0), t(12, 0));
+        let mut extension = session(3, t(11, 0), t(11, 30));
+        extension.extended = true;
+        let sessions = vec![session(3, t(9, 0), t(10, 0)), extension];
+
+        let findings = find_drift(&[&r], &sessions, 5);
+        assert_eq!(findings.len(), 1);
+        assert!(matches!(
+            findings[0],
+            DriftFinding::Edited {
+                edge: DriftEdge::End,
+                minutes: 30,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_extended_record_start_is_not_compared() {
+        // The timer only set the end of a record that was created by hand
+        let r = record(2, t(8, 0), t(11, 0));
+        let mut extension = session(2, t(10, 0), t(11, 0));
+        extension.extended = true;
+        assert!(find_drift(&[&r], &[extension], 5).is_empty());
+    }
+
+    #[test]
+    fn test_deleted_session_record() {
+        let sessions = vec![session(4, t(13, 0), t(14, 0))];
+        let findings = find_drift(&[], &sessions, 5);
+        assert_eq!(
+            findings,
+            vec![DriftFinding::RecordDeleted {
+                record_id: 4,
+                session: sessions[0].clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_never_timed_records_are_ignored() {
+        let manual = record(5, t(9, 0), t(17, 0));
+        assert!(find_drift(&[&manual], &[], 0).is_empty());
+    }
+
+    #[test]
+    fn test_drift_within_tolerance_is_ignored() {
+        let r = record(1, t(9, 5), t(10, 0));
+        let sessions = vec![session(1, t(9, 0), t(10, 0))];
+        assert!(find_drift(&[&r], &sessions, 5).is_empty());
+        assert_eq!(find_drift(&[&r], &sessions, 4).len(), 1);
+    }
+}
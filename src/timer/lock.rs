@@ -0,0 +1,206 @@
+//! Pause the timer while the session is locked or the machine sleeps (`[timer] pause_on_lock`)
+//!
+//! Lock and sleep events come from logind over the system bus, which needs the
+//! `logind` feature on Linux; elsewhere `listen_for_lock` yields nothing. Only a timer that
+//! was running when the user went away is paused, and it is resumed only if it
+//! is still the one paused when they are back.
+//!
+//! Events are stamped when they arrive, since the TUI may only get to them after
+//! the machine wakes up; the pause then starts when the user went away.
+
+use super::TimerStatus;
+use std::sync::mpsc;
+use time::OffsetDateTime;
+
+/// A change reported by the session manager
+// Only the logind listener sends these outside of tests
+#[cfg_attr(not(all(feature = "logind", target_os = "linux")), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockEvent {
+    Locked,
+    Unlocked,
+    Sleeping,
+    Woke,
+}
+
+/// What to do with the timer after an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockAction {
+    Pause,
+    Resume,
+}
+
+/// Tracks lock and sleep separately, so waking up on a locked screen stays paused
+#[derive(Debug, Clone, Default)]
+pub struct LockPause {
+    locked: bool,
+    asleep: bool,
+    /// The timer was paused by us rather than by the user
+    paused_timer: bool,
+}
+
+impl LockPause {
+    /// Update for `event`, given the status of the timer (if any) at that moment
+    pub fn handle(&mut self, event: LockEvent, timer: Option<TimerStatus>) -> Option<LockAction> {
+        let was_away = self.is_away();
+        match event {
+            LockEvent::Locked => self.locked = true,
+            LockEvent::Unlocked => self.locked = false,
+            LockEvent::Sleeping => self.asleep = true,
+            LockEvent::Woke => self.asleep = false,
+        }
+
+        match (was_away, self.is_away()) {
+            (false, true) if timer == Some(TimerStatus::Running) => {
+                self.paused_timer = true;
+                Some(LockAction::Pause)
+            }
+            (true, false) if self.paused_timer => {
+                self.paused_timer = false;
+                // Stopped or resumed by hand meanwhile: leave it alone
+                (timer == Some(TimerStatus::Paused)).then_some(LockAction::Resume)
+            }
+            _ => None,
+        }
+    }
+
+    /// The timer is paused because of a lock or sleep
+    pub fn paused_timer(&self) -> bool {
+        self.paused_timer
+    }
+
+    /// Forget an automatic pause, e.g. once the user resumes or stops the timer
+    pub fn release(&mut self) {
+        self.paused_timer = false;
+    }
+
+    fn is_away(&self) -> bool {
+        self.locked || self.asleep
+    }
+}
+
+/// Start listening for lock and sleep events; None where they aren't available
+#[cfg(all(feature = "logind", target_os = "linux"))]
+pub fn listen_for_lock() -> Option<mpsc::Receiver<(LockEvent, OffsetDateTime)>> {
+    logind::listen().ok()
+}
+
+/// Start listening for lock and sleep events; None where they aren't available
+#[cfg(not(all(feature = "logind", target_os = "linux")))]
+pub fn listen_for_lock() -> Option<mpsc::Receiver<(LockEvent, OffsetDateTime)>> {
+    None
+}
+
+#[cfg(all(feature = "logind", target_os = "linux"))]
+mod logind {
+    use super::LockEvent;
+    use crate::timefmt::now_local;
+    use std::sync::mpsc;
+    use time::OffsetDateTime;
+    use zbus::blocking::{Connection, Proxy};
+    use zbus::zvariant::OwnedObjectPath;
+
+    const LOGIN1: &str = "org.freedesktop.login1";
+
+    /// One thread per signal, all feeding the same channel
+    pub(super) fn listen() -> zbus::Result<mpsc::Receiver<(LockEvent, OffsetDateTime)>> {
+        let connection = Connection::system()?;
+        let manager = Proxy::new(
+            &connection,
+            LOGIN1,
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )?;
+        let (sender, receiver) = mpsc::channel();
+
+        let sleep = manager.receive_signal("PrepareForSleep")?;
+        let sleep_sender = sender.clone();
+        std::thread::spawn(move || {
+            for message in sleep {
+                let Ok(going) = message.body().deserialize::<bool>() else {
+                    continue;
+                };
+                let event = if going {
+                    LockEvent::Sleeping
+                } else {
+                    LockEvent::Woke
+                };
+                if sleep_sender.send((event, now_local())).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // Without a logind session (e.g. over ssh) only sleep is reported
+        let session_path: Option<OwnedObjectPath> =
+            manager.call("GetSessionByPID", &(std::process::id())).ok();
+        if let Some(path) = session_path {
+            let session = Proxy::new(&connection, LOGIN1, path, "org.freedesktop.login1.Session")?;
+            for (signal, event) in [("Lock", LockEvent::Locked), ("Unlock", LockEvent::Unlocked)] {
+                let messages = session.receive_signal(signal)?;
+                let sender = sender.clone();
+                std::thread::spawn(move || {
+                    for _ in messages {
+                        if sender.send((event, now_local())).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        }
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUNNING: Option<TimerStatus> = Some(TimerStatus::Running);
+    const PAUSED: Option<TimerStatus> = Some(TimerStatus::Paused);
+
+    #[test]
+    fn test_lock_pauses_and_unlock_resumes() {
+        let mut lock = LockPause::default();
+        assert_eq!(
+            lock.handle(LockEvent::Locked, RUNNING),
+            Some(LockAction::Pause)
+        );
+        assert!(lock.paused_timer());
+        assert_eq!(
+            lock.handle(LockEvent::Unlocked, PAUSED),
+            Some(LockAction::Resume)
+        );
+        assert!(!lock.paused_timer());
+    }
+
+    #[test]
+    fn test_waking_on_a_locked_screen_stays_paused() {
+        let mut lock = LockPause::default();
+        assert_eq!(
+            lock.handle(LockEvent::Locked, RUNNING),
+            Some(LockAction::Pause)
+        );
+        assert_eq!(lock.handle(LockEvent::Sleeping, PAUSED), None);
+        assert_eq!(lock.handle(LockEvent::Woke, PAUSED), None);
+        assert_eq!(
+            lock.handle(LockEvent::Unlocked, PAUSED),
+            Some(LockAction::Resume)
+        );
+    }
+
+    #[test]
+    fn test_timers_paused_or_stopped_by_hand_are_left_alone() {
+        let mut lock = LockPause::default();
+        // Already paused when the screen locked
+        assert_eq!(lock.handle(LockEvent::Locked, PAUSED), None);
+        assert_eq!(lock.handle(LockEvent::Unlocked, PAUSED), None);
+        // No timer at all
+        assert_eq!(lock.handle(LockEvent::Sleeping, None), None);
+        assert_eq!(lock.handle(LockEvent::Woke, None), None);
+        // Stopped while away
+        lock.handle(LockEvent::Locked, RUNNING);
+        assert_eq!(lock.handle(LockEvent::Unlocked, None), None);
+        assert!(!lock.paused_timer());
+    }
+}
@@ -4,12 +4,19 @@
 //! Timers can be started, paused, resumed, and stopped, with automatic conversion
 //! to WorkRecord upon completion.
 
+mod audit;
+mod lock;
+
+pub use audit::{SessionFocus, SessionLogEntry, find_drift};
+pub use lock::{LockAction, LockEvent, LockPause, listen_for_lock};
+
 use crate::models::{TimePoint, WorkRecord};
 use crate::storage::Storage;
+use crate::timefmt;
 use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
 use std::time::Duration as StdDuration;
-use time::{Date, OffsetDateTime};
+use time::{Date, OffsetDateTime, Time};
 
 /// Timer status enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -50,6 +57,10 @@ pub struct TimerState {
     /// Total duration in seconds when paused (cumulative)
     pub paused_duration_secs: i64,
 
+    /// Times the session has been paused
+    #[serde(default)]
+    pub pause_count: u32,
+
     /// When timer was last paused (to track current pause duration)
     pub paused_at: Option<OffsetDateTime>,
 
@@ -68,6 +79,52 @@ pub struct TimerState {
     /// If present, we'll update the record in this date's file instead of the timer start date
     #[serde(default)]
     pub source_record_date: Option<Date>,
+
+    /// Notes jotted during the session, added to the record's description on stop
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<(OffsetDateTime, String)>,
+}
+
+/// What stopping a timer did to its day file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopAction {
+    /// No (or a missing) source record: a new record was added
+    Created,
+    /// The source record's end moved from `previous_end`
+    Updated { previous_end: TimePoint },
+}
+
+/// A stopped session and the record it was written to
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoppedTimer {
+    /// The session alone, from its start to the stop
+    pub session: WorkRecord,
+    /// The record as saved in the day file
+    pub record: WorkRecord,
+    /// Day file the record is in
+    pub date: Date,
+    /// When the session started, with its UTC offset
+    pub started_at: OffsetDateTime,
+    pub action: StopAction,
+}
+
+impl StoppedTimer {
+    /// "updated #12 Existing Task → end 16:42, +1h 05m" or "created #31 New Task 15:37–16:42"
+    pub fn summary(&self) -> String {
+        match self.action {
+            StopAction::Created => format!(
+                "created #{} {} {}–{}",
+                self.record.id, self.record.name, self.record.start, self.record.end
+            ),
+            StopAction::Updated { .. } => format!(
+                "updated #{} {} → end {}, +{}",
+                self.record.id,
+                self.record.name,
+                self.record.end,
+                timefmt::format_minutes(self.session.total_minutes)
+            ),
+        }
+    }
 }
 
 /// Timer manager for controlling timer operations
@@ -76,19 +133,33 @@ pub struct TimerState {
 /// query their current status. Manages persistence through the StorageManager layer.
 pub struct TimerManager {
     storage: Storage,
+    create_record_on_start: bool,
 }
 
 impl TimerManager {
     /// Create a new timer manager with low-level Storage
     /// For internal use - external callers should use storage::StorageManager instead
     pub fn new(storage: Storage) -> Self {
-        TimerManager { storage }
+        TimerManager {
+            storage,
+            create_record_on_start: false,
+        }
+    }
+
+    /// Add an in-progress record to the day when a timer without a source record starts
+    pub fn create_record_on_start(mut self, enabled: bool) -> Self {
+        self.create_record_on_start = enabled;
+        self
     }
 
     /// Start a new timer
     ///
+    /// With `create_record_on_start`, a timer without a source record also adds a
+    /// zero-length `in_progress` record to today's file and becomes linked to it.
+    ///
     /// # Errors
-    /// Returns an error if a timer is already running
+    /// Returns an error if a timer is already running, including one started by
+    /// another process between our check and write
     pub fn start(
         &self,
         task_name: String,
@@ -96,14 +167,26 @@ impl TimerManager {
         source_record_id: Option<u32>,
         source_record_date: Option<Date>,
     ) -> Result<TimerState> {
-        // Check if timer already running
-        if (self.storage.load_active_timer()?).is_some() {
-            return Err(anyhow!("A timer is already running"));
-        }
-
         let now = OffsetDateTime::now_local()
             .context("Failed to get local time. System clock may not be configured correctly.")?;
-        let timer = TimerState {
+        self.start_at(
+            task_name,
+            description,
+            source_record_id,
+            source_record_date,
+            now,
+        )
+    }
+
+    fn start_at(
+        &self,
+        task_name: String,
+        description: Option<String>,
+        source_record_id: Option<u32>,
+        source_record_date: Option<Date>,
+        now: OffsetDateTime,
+    ) -> Result<TimerState> {
+        let mut timer = TimerState {
             id: None,
             task_name,
             description,
@@ -112,23 +195,127 @@ impl TimerManager {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id,
             source_record_date,
+            notes: Vec::new(),
         };
+        self.claim(&timer)?;
+
+        if source_record_id.is_none() && self.create_record_on_start {
+            let created =
+                self.create_in_progress_record(&timer.task_name, timer.description.as_deref(), now);
+            match created {
+                Ok(id) => {
+                    timer.source_record_id = Some(id);
+                    timer.source_record_date = Some(now.date());
+                    self.storage.save_active_timer(&timer)?;
+                }
+                Err(e) => {
+                    self.storage.clear_active_timer()?;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(timer)
+    }
 
-        self.storage.save_active_timer(&timer)?;
+    /// Start a timer on today's `record` as if it had run since the record ended
+    ///
+    /// For work that went on after the record without a timer: the gap since its end
+    /// counts as worked time, so the elapsed time includes it and stopping extends
+    /// the record over it.
+    ///
+    /// # Errors
+    /// Returns an error if the record isn't from today, ends later than now or is
+    /// already being timed, or if a timer is already running
+    pub fn backfill(&self, record: &WorkRecord, date: Date) -> Result<TimerState> {
+        let now = OffsetDateTime::now_local()
+            .context("Failed to get local time. System clock may not be configured correctly.")?;
+        self.backfill_at(record, date, now)
+    }
+
+    fn backfill_at(
+        &self,
+        record: &WorkRecord,
+        date: Date,
+        now: OffsetDateTime,
+    ) -> Result<TimerState> {
+        if record.in_progress {
+            return Err(anyhow!("Record #{} is already being timed", record.id));
+        }
+        if date != now.date() {
+            return Err(anyhow!("Only today's records can be backfilled"));
+        }
+        let end = Time::from_hms(record.end.hour, record.end.minute, 0)
+            .context("Invalid record end time")?;
+        let start_time = now.replace_time(end);
+        if start_time > now {
+            return Err(anyhow!("Record #{} ends later than now", record.id));
+        }
+
+        let description = (!record.description.is_empty()).then(|| record.description.clone());
+        let timer = TimerState {
+            id: None,
+            task_name: record.name.clone(),
+            description,
+            start_time,
+            end_time: None,
+            date,
+            status: TimerStatus::Running,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: now,
+            updated_at: now,
+            source_record_id: Some(record.id),
+            source_record_date: Some(date),
+            notes: Vec::new(),
+        };
+        self.claim(&timer)?;
         Ok(timer)
     }
 
+    /// Write `timer` as the running one, unless another timer is already running
+    ///
+    /// Claiming the timer file is the "already running" check, so two racing
+    /// starts can't both succeed.
+    fn claim(&self, timer: &TimerState) -> Result<()> {
+        if !self.storage.create_active_timer(timer)? {
+            return Err(anyhow!("A timer is already running"));
+        }
+        Ok(())
+    }
+
+    /// Add a record starting and ending at `now`, marked in progress, and return its id
+    fn create_in_progress_record(
+        &self,
+        task_name: &str,
+        description: Option<&str>,
+        now: OffsetDateTime,
+    ) -> Result<u32> {
+        let mut day_data = self.storage.load(&now.date())?;
+        let start = TimePoint::new(now.hour(), now.minute())
+            .map_err(|e| anyhow!(e))
+            .context("Failed to create TimePoint for timer start time")?;
+        let mut record = WorkRecord::new(day_data.next_id(), task_name.to_string(), start, start);
+        record.description = description.unwrap_or_default().to_string();
+        record.in_progress = true;
+        let id = record.id;
+        day_data.add_record(record);
+        self.storage.save(&day_data)?;
+        Ok(id)
+    }
+
     /// Stop the active timer and convert it to a WorkRecord
     ///
     /// # Errors
     /// Returns an error if no timer is running
-    pub fn stop(&self) -> Result<WorkRecord> {
-        let mut timer = self
+    pub fn stop(&self) -> Result<StoppedTimer> {
+        let timer = self
             .storage
             .load_active_timer()?
             .ok_or_else(|| anyhow!("No timer is currently running"))?;
@@ -136,6 +323,128 @@ impl TimerManager {
         let now = OffsetDateTime::now_local()
             .context("Failed to get local time. System clock may not be configured correctly.")?;
 
+        self.stop_at(timer, now)
+    }
+
+    /// Stop the running timer, if any, and start a new unlinked one on `task_name`
+    ///
+    /// The name is checked before anything is stopped, so a rejected switch leaves
+    /// the running session untouched. Returns the stopped session's record, if there
+    /// was one, and the new timer.
+    ///
+    /// # Errors
+    /// Returns an error if the task name is empty
+    pub fn switch(
+        &self,
+        task_name: String,
+        description: Option<String>,
+    ) -> Result<(Option<WorkRecord>, TimerState)> {
+        let now = OffsetDateTime::now_local()
+            .context("Failed to get local time. System clock may not be configured correctly.")?;
+        self.switch_at(task_name, description, now)
+    }
+
+    fn switch_at(
+        &self,
+        task_name: String,
+        description: Option<String>,
+        now: OffsetDateTime,
+    ) -> Result<(Option<WorkRecord>, TimerState)> {
+        let task_name = task_name.trim().to_string();
+        if task_name.is_empty() {
+            return Err(anyhow!("Task name cannot be empty"));
+        }
+
+        let Some(running) = self.storage.load_active_timer()? else {
+            let timer = self.start_at(task_name, description, None, None, now)?;
+            return Ok((None, timer));
+        };
+
+        // Keep what stopping overwrites, so a failed start can put the old session back
+        let target_date = running
+            .source_record_date
+            .unwrap_or_else(|| running.start_time.date());
+        let day_before = self.storage.load(&target_date)?;
+        let log_len = self.storage.session_log_len()?;
+
+        let stopped = self.stop_at(running.clone(), now)?;
+        match self.start_at(task_name, description, None, None, now) {
+            Ok(timer) => Ok((Some(stopped.session), timer)),
+            Err(e) => {
+                // If another process claimed the timer meanwhile, the old session
+                // stays stopped as recorded rather than overwriting that timer
+                if self.storage.create_active_timer(&running)? {
+                    self.storage.save(&day_before)?;
+                    self.storage.truncate_session_log(log_len)?;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Stop the active timer if `now` is past its auto-stop deadline (see `auto_stop_due`)
+    ///
+    /// The timer is ended at `cutoff` on its start day, not at `now`, so the record
+    /// lands in that day's file. With `restart` set, a timer left running overnight
+    /// is followed by a fresh one for the same task; one stopped on its own day is
+    /// not. Returns the record of the stopped timer, or None if nothing was overdue.
+    pub fn auto_stop_if_overdue(
+        &self,
+        cutoff: TimePoint,
+        restart: bool,
+        now: OffsetDateTime,
+    ) -> Result<Option<WorkRecord>> {
+        let timer = match self.storage.load_active_timer()? {
+            Some(timer) => timer,
+            None => return Ok(None),
+        };
+
+        if !auto_stop_due(&timer, cutoff, now) {
+            return Ok(None);
+        }
+
+        let overnight = now.to_offset(timer.start_time.offset()).date() > timer.start_time.date();
+        let task_name = timer.task_name.clone();
+        let description = timer.description.clone();
+        let end = auto_stop_time(&timer, cutoff);
+        let record = self.stop_at(timer, end)?.session;
+
+        if restart && overnight {
+            self.start(task_name, description, None, None)?;
+        }
+
+        Ok(Some(record))
+    }
+
+    /// End a session still running from an earlier day at the midnight after it
+    /// started, and continue the same task from that midnight
+    ///
+    /// The first part goes to the start day's file, the rest becomes a new session
+    /// on the next day. A session several days old moves one day per call. Paused
+    /// sessions are left alone. Returns None when there was nothing to split.
+    pub fn split_at_midnight(
+        &self,
+        now: OffsetDateTime,
+    ) -> Result<Option<(StoppedTimer, TimerState)>> {
+        let Some(timer) = self.storage.load_active_timer()? else {
+            return Ok(None);
+        };
+        if timer.status != TimerStatus::Running {
+            return Ok(None);
+        }
+        let Some(midnight) = midnight_after_start(&timer, now) else {
+            return Ok(None);
+        };
+
+        let task_name = timer.task_name.clone();
+        let description = timer.description.clone();
+        let stopped = self.stop_at(timer, midnight)?;
+        let continued = self.start_at(task_name, description, None, None, midnight)?;
+        Ok(Some((stopped, continued)))
+    }
+
+    /// Stop `timer` at `now` and write its record to the right day file
+    fn stop_at(&self, mut timer: TimerState, now: OffsetDateTime) -> Result<StoppedTimer> {
         // Determine which date's data file to load:
         // - If timer has source_record_date, use that (record is from a specific day's view)
         // - Otherwise use timer.start_time.date() (creating new record on timer's start date)
@@ -143,6 +452,15 @@ impl TimerManager {
             .source_record_date
             .unwrap_or_else(|| timer.start_time.date());
 
+        // Measure before the status changes, so a paused timer's open pause counts
+        let active_secs = timefmt::elapsed(&timer, now).as_secs();
+        let wall_secs = u64::try_from((now - timer.start_time).whole_seconds()).unwrap_or(0);
+        let focus = SessionFocus {
+            active_secs: Some(active_secs),
+            pauses: timer.pause_count,
+            paused_secs: wall_secs.saturating_sub(active_secs),
+        };
+
         timer.end_time = Some(now);
         timer.status = TimerStatus::Stopped;
         timer.updated_at = now;
@@ -150,38 +468,65 @@ impl TimerManager {
         // Load the day's data file
         let mut day_data = self.storage.load(&target_date)?;
 
+        let end_timepoint = TimePoint::new(now.hour(), now.minute())
+            .map_err(|e| anyhow!(e))
+            .context("Failed to create TimePoint for timer end time")?;
+
         // If timer was started from an existing record, update that record's end time
         // Otherwise, create a new work record
-        if let Some(source_id) = timer.source_record_id {
-            // Find and update the existing record
-            if let Some(record) = day_data.work_records.get_mut(&source_id) {
-                // Update the end time to now
-                let end_timepoint = TimePoint::new(now.hour(), now.minute())
-                    .map_err(|e| anyhow!(e))
-                    .context("Failed to create TimePoint for timer end time")?;
-                record.end = end_timepoint;
-                record.update_duration();
-            } else {
-                // Source record not found, create new one instead
-                let mut work_record = self.to_work_record(timer.clone())?;
-                // Assign proper ID from day_data instead of using placeholder
-                work_record.id = day_data.next_id();
-                day_data.add_record(work_record);
-            }
+        let existing = timer
+            .source_record_id
+            .and_then(|source_id| day_data.work_records.get_mut(&source_id));
+        let (session, action) = if let Some(record) = existing {
+            let action = StopAction::Updated {
+                previous_end: record.end,
+            };
+            // A record created on start holds only this session, so it isn't an extension
+            let extended = !record.in_progress;
+            record.in_progress = false;
+            // Update the end time to now
+            record.end = end_timepoint;
+            record.description = with_notes(&record.description, timer.start_time, &timer.notes);
+            // Use the offsets at both ends of the session so DST changes are accounted for
+            record.update_duration_with_offsets(timer.start_time.offset(), now.offset());
+            let session = SessionLogEntry {
+                date: target_date,
+                record_id: record.id,
+                start: TimePoint::new(timer.start_time.hour(), timer.start_time.minute())
+                    .map_err(|e| anyhow!(e))?,
+                end: end_timepoint,
+                extended,
+                focus,
+            };
+            (session, action)
         } else {
-            // No source record, create a new work record
+            // No (or a missing) source record, create a new work record
             let mut work_record = self.to_work_record(timer.clone())?;
             // Assign proper ID from day_data instead of using placeholder
             work_record.id = day_data.next_id();
+            let session = SessionLogEntry {
+                date: target_date,
+                record_id: work_record.id,
+                start: work_record.start,
+                end: work_record.end,
+                extended: false,
+                focus,
+            };
             day_data.add_record(work_record);
-        }
+            (session, StopAction::Created)
+        };
 
         self.storage.save(&day_data)?;
+        self.storage.append_session_log(&session)?;
         self.storage.clear_active_timer()?;
 
-        // Return a work record for the stopped timer (for display purposes)
-        let work_record = self.to_work_record(timer)?;
-        Ok(work_record)
+        Ok(StoppedTimer {
+            record: day_data.work_records[&session.record_id].clone(),
+            started_at: timer.start_time,
+            session: self.to_work_record(timer)?,
+            date: target_date,
+            action,
+        })
     }
 
     /// Pause the active timer
@@ -189,6 +534,15 @@ impl TimerManager {
     /// # Errors
     /// Returns an error if timer is not running
     pub fn pause(&self) -> Result<TimerState> {
+        let now = OffsetDateTime::now_local()
+            .context("Failed to get local time. System clock may not be configured correctly.")?;
+        self.pause_at(now)
+    }
+
+    /// Pause the active timer as of `at`, e.g. when the machine went to sleep
+    ///
+    /// `at` is kept between the session's start and now.
+    pub fn pause_at(&self, at: OffsetDateTime) -> Result<TimerState> {
         let mut timer = self
             .storage
             .load_active_timer()?
@@ -204,7 +558,8 @@ impl TimerManager {
 
         let now = OffsetDateTime::now_local()
             .context("Failed to get local time. System clock may not be configured correctly.")?;
-        timer.paused_at = Some(now);
+        timer.paused_at = Some(at.min(now).max(timer.start_time));
+        timer.pause_count += 1;
         timer.status = TimerStatus::Paused;
         timer.updated_at = now;
 
@@ -243,6 +598,32 @@ impl TimerManager {
         Ok(timer)
     }
 
+    /// Add a timestamped note to the running or paused session
+    ///
+    /// # Errors
+    /// Returns an error if no timer is running or the note is empty
+    pub fn add_note(&self, text: &str) -> Result<TimerState> {
+        let now = OffsetDateTime::now_local()
+            .context("Failed to get local time. System clock may not be configured correctly.")?;
+        self.add_note_at(text, now)
+    }
+
+    fn add_note_at(&self, text: &str, now: OffsetDateTime) -> Result<TimerState> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(anyhow!("Note cannot be empty"));
+        }
+        let mut timer = self
+            .storage
+            .load_active_timer()?
+            .ok_or_else(|| anyhow!("No timer is currently running"))?;
+
+        timer.notes.push((now, text.to_string()));
+        timer.updated_at = now;
+        self.storage.save_active_timer(&timer)?;
+        Ok(timer)
+    }
+
     /// Get the current timer status
     ///
     /// Returns None if no timer is running
@@ -254,26 +635,7 @@ impl TimerManager {
     ///
     /// Returns the time since start_time, minus any paused durations.
     pub fn get_elapsed_duration(&self, timer: &TimerState) -> StdDuration {
-        let end_point = if timer.status == TimerStatus::Paused {
-            // If paused, use when it was paused
-            timer.paused_at.unwrap_or_else(|| {
-                OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc())
-            })
-        } else {
-            // If running, use now
-            OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc())
-        };
-
-        let elapsed = end_point - timer.start_time;
-        let paused_duration_std = StdDuration::from_secs(timer.paused_duration_secs as u64);
-
-        // Convert time::Duration to std::Duration for arithmetic
-        let elapsed_std = StdDuration::from_secs(elapsed.whole_seconds() as u64)
-            + StdDuration::from_nanos(elapsed.subsec_nanoseconds() as u64);
-
-        elapsed_std
-            .checked_sub(paused_duration_std)
-            .unwrap_or(StdDuration::ZERO)
+        timefmt::elapsed(timer, timefmt::now_local())
     }
 
     /// Convert a stopped timer to a WorkRecord
@@ -302,15 +664,77 @@ impl TimerManager {
             start_timepoint,
             end_timepoint,
         );
+        // Real elapsed time across a DST change differs from the wall-clock span
+        record.update_duration_with_offsets(start_time.offset(), end_time.offset());
 
-        if let Some(description) = timer.description {
-            record.description = description;
+        if let Some(description) = &timer.description {
+            record.description = description.clone();
         }
+        record.description = with_notes(&record.description, start_time, &timer.notes);
 
         Ok(record)
     }
 }
 
+/// `description` followed by a line per session note, "- [+0:42] found root cause"
+///
+/// Offsets count wall-clock time from the session's start, pauses included.
+pub fn with_notes(
+    description: &str,
+    start: OffsetDateTime,
+    notes: &[(OffsetDateTime, String)],
+) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if !description.is_empty() {
+        lines.push(description.to_string());
+    }
+    for (at, text) in notes {
+        let minutes = (*at - start).whole_minutes().max(0);
+        lines.push(format!(
+            "- [+{}:{:02}] {}",
+            minutes / 60,
+            minutes % 60,
+            text
+        ));
+    }
+    lines.join("\n")
+}
+
+/// The midnight ending the day `timer` started on, once `now` has passed it
+pub fn midnight_after_start(timer: &TimerState, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    let next_day = timer.start_time.date().next_day()?;
+    let midnight = timer
+        .start_time
+        .replace_date(next_day)
+        .replace_time(Time::MIDNIGHT);
+    (now >= midnight).then_some(midnight)
+}
+
+/// Whether `timer` should be auto-stopped at `now` under an `auto_stop_at` of `cutoff`
+///
+/// A timer started before the cutoff is due as soon as the cutoff passes on its
+/// start day. One started at or after it isn't stopped right away, only once
+/// its start day is over.
+pub fn auto_stop_due(timer: &TimerState, cutoff: TimePoint, now: OffsetDateTime) -> bool {
+    now >= auto_stop_time(timer, cutoff)
+}
+
+/// When a timer left running overnight should be considered stopped
+///
+/// This is `cutoff` on the timer's start day, or the midnight ending that day for
+/// a timer started at or after the cutoff.
+pub fn auto_stop_time(timer: &TimerState, cutoff: TimePoint) -> OffsetDateTime {
+    let cutoff_time = Time::from_hms(cutoff.hour, cutoff.minute, 0).unwrap_or(Time::MIDNIGHT);
+    let end = timer.start_time.replace_time(cutoff_time);
+    if end > timer.start_time {
+        return end;
+    }
+    timer
+        .start_time
+        .replace_time(Time::MIDNIGHT)
+        .saturating_add(time::Duration::DAY)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,11 +758,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id: None,
             source_record_date: None,
+            notes: Vec::new(),
         };
 
         assert_eq!(timer.task_name, "Test Task");
@@ -358,11 +784,13 @@ mod tests {
             date: now.date(),
             status: TimerStatus::Running,
             paused_duration_secs: 0,
+            pause_count: 0,
             paused_at: None,
             created_at: now,
             updated_at: now,
             source_record_id: None,
             source_record_date: None,
+            notes: Vec::new(),
         };
 
         let json = serde_json::to_string(&timer).unwrap();
@@ -386,6 +814,94 @@ mod tests {
         assert_eq!(timer.paused_duration_secs, 0);
     }
 
+    #[test]
+    fn test_switch_stops_running_and_starts_new() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let first = manager
+            .start("Review".to_string(), None, None, None)
+            .unwrap();
+        let (stopped, timer) = manager
+            .switch("Deploy".to_string(), Some("v2".to_string()))
+            .unwrap();
+
+        assert_eq!(stopped.unwrap().name, "Review");
+        assert_eq!(timer.task_name, "Deploy");
+        assert_eq!(timer.source_record_id, None);
+
+        // Exactly one stopped record and one running timer
+        let day = storage.load(&first.start_time.date()).unwrap();
+        assert_eq!(day.work_records.len(), 1);
+        assert_eq!(day.work_records.values().next().unwrap().name, "Review");
+        let active = storage.load_active_timer().unwrap().unwrap();
+        assert_eq!(active.task_name, "Deploy");
+        assert_eq!(active.description.as_deref(), Some("v2"));
+    }
+
+    #[test]
+    fn test_switch_that_fails_to_start_keeps_running_timer() {
+        use time::macros::datetime;
+        let (storage, temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone()).create_record_on_start(true);
+
+        let running = overnight_timer(datetime!(2025-11-04 22:00 +0));
+        storage.save_active_timer(&running).unwrap();
+        // The old session stops into its start day; the new one can't add its record
+        std::fs::write(temp.path().join("2025-11-05.json"), "not json").unwrap();
+
+        let result = manager.switch_at("Deploy".to_string(), None, datetime!(2025-11-05 09:00 +0));
+        assert!(result.is_err());
+
+        let active = storage.load_active_timer().unwrap().unwrap();
+        assert_eq!(active.task_name, "Forgotten Task");
+        assert_eq!(active.start_time, running.start_time);
+        assert!(
+            storage
+                .load(&datetime!(2025-11-04 22:00 +0).date())
+                .unwrap()
+                .work_records
+                .is_empty()
+        );
+        assert!(
+            storage
+                .load_session_log(&datetime!(2025-11-04 22:00 +0).date())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_switch_without_running_timer_only_starts() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let (stopped, timer) = manager.switch("Deploy".to_string(), None).unwrap();
+        assert!(stopped.is_none());
+        assert!(
+            storage
+                .load(&timer.start_time.date())
+                .unwrap()
+                .work_records
+                .is_empty()
+        );
+        assert!(storage.load_active_timer().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_switch_with_empty_name_keeps_running_timer() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        manager
+            .start("Review".to_string(), None, None, None)
+            .unwrap();
+        assert!(manager.switch("  ".to_string(), None).is_err());
+
+        let active = storage.load_active_timer().unwrap().unwrap();
+        assert_eq!(active.task_name, "Review");
+    }
+
     #[test]
     fn test_cannot_start_when_already_running() {
         let (storage, _temp) = create_test_storage();
@@ -401,6 +917,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_racing_starts_leave_exactly_one_timer() {
+        let (storage, temp) = create_test_storage();
+        let starts = 16;
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(starts));
+
+        let handles: Vec<_> = (0..starts)
+            .map(|i| {
+                let manager = TimerManager::new(storage.clone());
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    manager.start(format!("Task {}", i), None, None, None)
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let winners: Vec<&TimerState> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(winners.len(), 1);
+        for err in results.iter().filter_map(|r| r.as_ref().err()) {
+            assert_eq!(err.to_string(), "A timer is already running");
+        }
+        let timer_files = std::fs::read_dir(temp.path())
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name() == "running_timer.json")
+            .count();
+        assert_eq!(timer_files, 1);
+        let stored = storage.load_active_timer().unwrap().unwrap();
+        assert_eq!(stored.task_name, winners[0].task_name);
+    }
+
+    #[test]
+    fn test_failed_record_creation_releases_the_timer() {
+        let (storage, temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone()).create_record_on_start(true);
+        // A day file that can't be parsed makes creating the in-progress record fail
+        let today = OffsetDateTime::now_local().unwrap().date();
+        std::fs::write(temp.path().join(format!("{}.json", today)), "not json").unwrap();
+
+        assert!(manager.start("Task".to_string(), None, None, None).is_err());
+        assert!(storage.load_active_timer().unwrap().is_none());
+    }
+
     #[test]
     fn test_pause_running_timer() {
         let (storage, _temp) = create_test_storage();
@@ -415,6 +975,27 @@ mod tests {
         assert!(timer.paused_at.is_some());
     }
 
+    #[test]
+    fn test_pause_at_counts_from_the_given_instant() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let mut timer = manager.start("Work".to_string(), None, None, None).unwrap();
+        let now = OffsetDateTime::now_local().unwrap();
+        timer.start_time = now - time::Duration::hours(1);
+        storage.save_active_timer(&timer).unwrap();
+
+        let away = now - time::Duration::minutes(20);
+        let paused = manager.pause_at(away).unwrap();
+        assert_eq!(paused.paused_at, Some(away));
+        let resumed = manager.resume().unwrap();
+        assert!(resumed.paused_duration_secs >= 20 * 60);
+
+        // Never before the session started
+        let paused = manager.pause_at(now - time::Duration::hours(2)).unwrap();
+        assert_eq!(paused.paused_at, Some(timer.start_time));
+    }
+
     #[test]
     fn test_cannot_pause_paused_timer() {
         let (storage, _temp) = create_test_storage();
@@ -511,7 +1092,7 @@ mod tests {
         let result = manager.stop();
 
         assert!(result.is_ok());
-        let work_record = result.unwrap();
+        let work_record = result.unwrap().session;
         assert_eq!(work_record.name, "Work");
 
         // Timer should be cleared
@@ -519,6 +1100,312 @@ mod tests {
         assert!(timer_status.is_none());
     }
 
+    #[test]
+    fn test_stop_journals_session_for_audit() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let started = manager.start("Work".to_string(), None, None, None).unwrap();
+        manager.stop().unwrap();
+
+        let date = started.start_time.date();
+        let sessions = storage.load_session_log(&date).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert!(!sessions[0].extended);
+
+        let day = storage.load(&date).unwrap();
+        let records = day.get_sorted_records();
+        assert_eq!(records[0].id, sessions[0].record_id);
+        assert!(find_drift(&records, &sessions, 0).is_empty());
+    }
+
+    #[test]
+    fn test_stop_journals_pauses() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let started = manager.start("Work".to_string(), None, None, None).unwrap();
+        manager.pause().unwrap();
+        manager.resume().unwrap();
+        let paused = manager.pause().unwrap();
+        assert_eq!(paused.pause_count, 2);
+        // Stopped while paused, so the open pause counts too
+        manager.stop().unwrap();
+
+        let sessions = storage
+            .load_session_log(&started.start_time.date())
+            .unwrap();
+        assert_eq!(sessions[0].focus.pauses, 2);
+        assert_eq!(sessions[0].focus.active_secs, Some(0));
+    }
+
+    #[test]
+    fn test_start_creates_in_progress_record() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone()).create_record_on_start(true);
+
+        let timer = manager
+            .start("Work".to_string(), Some("API".to_string()), None, None)
+            .unwrap();
+
+        let date = timer.start_time.date();
+        assert_eq!(timer.source_record_date, Some(date));
+        let day = storage.load(&date).unwrap();
+        let record = &day.work_records[&timer.source_record_id.unwrap()];
+        assert_eq!(record.name, "Work");
+        assert_eq!(record.description, "API");
+        assert!(record.in_progress);
+        assert_eq!(record.start, record.end);
+        assert_eq!(record.total_minutes, 0);
+    }
+
+    #[test]
+    fn test_start_from_source_record_creates_nothing() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone()).create_record_on_start(true);
+
+        let timer = manager
+            .start("Work".to_string(), None, Some(7), None)
+            .unwrap();
+        assert_eq!(timer.source_record_id, Some(7));
+        assert!(
+            storage
+                .load(&timer.start_time.date())
+                .unwrap()
+                .work_records
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_notes_survive_pause_and_resume() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage);
+        assert!(manager.add_note("too early").is_err());
+
+        manager.start("Work".to_string(), None, None, None).unwrap();
+        manager.add_note("found root cause").unwrap();
+        manager.pause().unwrap();
+        manager.add_note("  waiting on CI  ").unwrap();
+        manager.resume().unwrap();
+        assert!(manager.add_note("   ").is_err());
+
+        let notes: Vec<String> = manager
+            .status()
+            .unwrap()
+            .unwrap()
+            .notes
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(notes, ["found root cause", "waiting on CI"]);
+    }
+
+    #[test]
+    fn test_stop_adds_notes_to_the_description() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let timer = manager
+            .start(
+                "Debugging".to_string(),
+                Some("Login bug".to_string()),
+                None,
+                None,
+            )
+            .unwrap();
+        let at = |minutes| timer.start_time + time::Duration::minutes(minutes);
+        manager.add_note_at("found root cause", at(42)).unwrap();
+        let timer = manager.add_note_at("waiting on CI", at(75)).unwrap();
+        let stopped = manager.stop_at(timer, at(90)).unwrap();
+
+        let expected = "Login bug\n- [+0:42] found root cause\n- [+1:15] waiting on CI";
+        assert_eq!(stopped.record.description, expected);
+        assert_eq!(stopped.session.description, expected);
+        // A continued record keeps its description and gains the new notes
+        let timer = manager
+            .start(
+                "Debugging".to_string(),
+                None,
+                Some(stopped.record.id),
+                Some(stopped.date),
+            )
+            .unwrap();
+        let timer = manager
+            .add_note_at("fixed", timer.start_time + time::Duration::minutes(5))
+            .unwrap();
+        let end = timer.start_time + time::Duration::minutes(10);
+        let continued = manager.stop_at(timer, end).unwrap();
+        assert_eq!(
+            continued.record.description,
+            format!("{}\n- [+0:05] fixed", expected)
+        );
+        assert_eq!(
+            with_notes("", at(0), &[(at(61), "x".to_string())]),
+            "- [+1:01] x"
+        );
+    }
+
+    #[test]
+    fn test_stop_reports_created_record() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+
+        let timer = manager
+            .start("New Task".to_string(), None, None, None)
+            .unwrap();
+        let end = timer.start_time + time::Duration::minutes(65);
+        let stopped = manager.stop_at(timer.clone(), end).unwrap();
+
+        assert_eq!(stopped.action, StopAction::Created);
+        assert_eq!(stopped.date, timer.start_time.date());
+        let saved = &storage.load(&stopped.date).unwrap().work_records[&stopped.record.id];
+        assert_eq!(&stopped.record, saved);
+        assert_eq!(
+            stopped.summary(),
+            format!(
+                "created #{} New Task {}–{}",
+                saved.id, saved.start, saved.end
+            )
+        );
+    }
+
+    #[test]
+    fn test_stop_reports_updated_source_record() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+        let today = OffsetDateTime::now_local().unwrap().date();
+        let mut day = crate::models::DayData::new(today);
+        day.add_record(WorkRecord::new(
+            12,
+            "Existing Task".to_string(),
+            TimePoint::new(0, 0).unwrap(),
+            TimePoint::new(0, 30).unwrap(),
+        ));
+        storage.save(&day).unwrap();
+
+        let timer = manager
+            .start("Existing Task".to_string(), None, Some(12), Some(today))
+            .unwrap();
+        let end = timer.start_time + time::Duration::minutes(65);
+        let stopped = manager.stop_at(timer, end).unwrap();
+
+        assert_eq!(
+            stopped.action,
+            StopAction::Updated {
+                previous_end: TimePoint::new(0, 30).unwrap()
+            }
+        );
+        assert_eq!(stopped.record.id, 12);
+        assert_eq!(stopped.date, today);
+        assert_eq!(
+            stopped.summary(),
+            format!(
+                "updated #12 Existing Task → end {}, +1h 05m",
+                stopped.record.end
+            )
+        );
+    }
+
+    #[test]
+    fn test_backfilled_session_counts_the_gap() {
+        use time::macros::{date, datetime};
+
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+        let day = date!(2025 - 11 - 06);
+        let mut day_data = crate::models::DayData::new(day);
+        let record = WorkRecord::new(
+            3,
+            "Feature dev".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 0).unwrap(),
+        );
+        day_data.add_record(record.clone());
+        storage.save(&day_data).unwrap();
+
+        // Remembered at 10:40 that the work never stopped
+        let timer = manager
+            .backfill_at(&record, day, datetime!(2025-11-06 10:40 +1))
+            .unwrap();
+        assert_eq!(timer.start_time, datetime!(2025-11-06 10:00 +1));
+        assert_eq!(timer.source_record_id, Some(3));
+        assert_eq!(
+            timefmt::elapsed(&timer, datetime!(2025-11-06 10:40 +1)),
+            StdDuration::from_secs(40 * 60)
+        );
+
+        let stopped = manager
+            .stop_at(timer, datetime!(2025-11-06 11:00 +1))
+            .unwrap();
+        assert_eq!(stopped.session.total_minutes, 60);
+        assert_eq!(stopped.record.start, TimePoint::new(9, 0).unwrap());
+        assert_eq!(stopped.record.end, TimePoint::new(11, 0).unwrap());
+        assert_eq!(stopped.record.total_minutes, 120);
+    }
+
+    #[test]
+    fn test_backfill_needs_a_finished_record_from_today() {
+        use time::macros::{date, datetime};
+
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage);
+        let now = datetime!(2025-11-06 10:40 +1);
+        let mut record = WorkRecord::new(
+            3,
+            "Feature dev".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(11, 0).unwrap(),
+        );
+
+        let later = manager.backfill_at(&record, date!(2025 - 11 - 06), now);
+        assert_eq!(
+            later.unwrap_err().to_string(),
+            "Record #3 ends later than now"
+        );
+        let yesterday = manager.backfill_at(&record, date!(2025 - 11 - 05), now);
+        assert_eq!(
+            yesterday.unwrap_err().to_string(),
+            "Only today's records can be backfilled"
+        );
+        record.end = TimePoint::new(9, 0).unwrap();
+        record.in_progress = true;
+        let running = manager.backfill_at(&record, date!(2025 - 11 - 06), now);
+        assert_eq!(
+            running.unwrap_err().to_string(),
+            "Record #3 is already being timed"
+        );
+    }
+
+    #[test]
+    fn test_stop_finishes_record_created_on_start() {
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone()).create_record_on_start(true);
+
+        let timer = manager.start("Work".to_string(), None, None, None).unwrap();
+        let id = timer.source_record_id.unwrap();
+        let end = timer.start_time + time::Duration::minutes(90);
+        manager.stop_at(timer.clone(), end).unwrap();
+
+        let date = timer.start_time.date();
+        let day = storage.load(&date).unwrap();
+        assert_eq!(day.work_records.len(), 1);
+        let record = &day.work_records[&id];
+        assert!(!record.in_progress);
+        assert_eq!(
+            record.end,
+            TimePoint::new(end.hour(), end.minute()).unwrap()
+        );
+        assert_eq!(record.total_minutes, 90);
+
+        // The session made the whole record, so it isn't logged as an extension
+        let sessions = storage.load_session_log(&date).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].record_id, id);
+        assert!(!sessions[0].extended);
+        assert!(storage.load_active_timer().unwrap().is_none());
+    }
+
     #[test]
     fn test_cannot_stop_without_running_timer() {
         let (storage, _temp) = create_test_storage();
@@ -539,7 +1426,7 @@ mod tests {
             None,
             None,
         );
-        let work_record = manager.stop().unwrap();
+        let work_record = manager.stop().unwrap().session;
 
         assert_eq!(work_record.name, "Work");
         assert_eq!(work_record.description, "Important task");
@@ -571,7 +1458,7 @@ mod tests {
         assert_eq!(resumed_again.status, TimerStatus::Running);
 
         // Stop
-        let work_record = manager.stop().unwrap();
+        let work_record = manager.stop().unwrap().session;
         assert_eq!(work_record.name, "Task");
 
         // Verify timer is cleared
@@ -606,6 +1493,68 @@ mod tests {
         assert!(elapsed.as_secs() < 2);
     }
 
+    #[test]
+    fn test_to_work_record_across_spring_forward() {
+        use time::macros::datetime;
+
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage);
+        let start = datetime!(2025-03-30 01:30 +01:00);
+        let end = datetime!(2025-03-30 03:30 +02:00);
+        let timer = TimerState {
+            id: None,
+            task_name: "Night deploy".to_string(),
+            description: None,
+            start_time: start,
+            end_time: Some(end),
+            date: start.date(),
+            status: TimerStatus::Stopped,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: end,
+            source_record_id: None,
+            source_record_date: None,
+            notes: Vec::new(),
+        };
+
+        let record = manager.to_work_record(timer).unwrap();
+        assert_eq!(record.start, TimePoint::new(1, 30).unwrap());
+        assert_eq!(record.end, TimePoint::new(3, 30).unwrap());
+        assert_eq!(record.total_minutes, 60);
+    }
+
+    #[test]
+    fn test_to_work_record_across_fall_back() {
+        use time::macros::datetime;
+
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage);
+        let start = datetime!(2025-10-26 01:30 +02:00);
+        let end = datetime!(2025-10-26 02:30 +01:00);
+        let timer = TimerState {
+            id: None,
+            task_name: "Night deploy".to_string(),
+            description: None,
+            start_time: start,
+            end_time: Some(end),
+            date: start.date(),
+            status: TimerStatus::Stopped,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: end,
+            source_record_id: None,
+            source_record_date: None,
+            notes: Vec::new(),
+        };
+
+        let record = manager.to_work_record(timer).unwrap();
+        assert_eq!(record.total_minutes, 120);
+    }
+
     #[test]
     fn test_stop_updates_existing_record() {
         use crate::models::DayData;
@@ -664,4 +1613,298 @@ mod tests {
             updated_record.end.minute
         );
     }
+
+    fn overnight_timer(start: OffsetDateTime) -> TimerState {
+        TimerState {
+            id: None,
+            task_name: "Forgotten Task".to_string(),
+            description: Some("Left running".to_string()),
+            start_time: start,
+            end_time: None,
+            date: start.date(),
+            status: TimerStatus::Running,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: start,
+            source_record_id: None,
+            source_record_date: None,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_auto_stop_time_uses_cutoff_on_start_day() {
+        use time::macros::datetime;
+        let timer = overnight_timer(datetime!(2025-11-05 09:15 +1));
+        let end = auto_stop_time(&timer, TimePoint::new(23, 59).unwrap());
+        assert_eq!(end, datetime!(2025-11-05 23:59 +1));
+    }
+
+    #[test]
+    fn test_auto_stop_time_after_the_cutoff_is_the_end_of_the_start_day() {
+        use time::macros::datetime;
+        let timer = overnight_timer(datetime!(2025-11-05 20:00 UTC));
+        let end = auto_stop_time(&timer, TimePoint::new(18, 0).unwrap());
+        assert_eq!(end, datetime!(2025-11-06 00:00 UTC));
+    }
+
+    #[test]
+    fn test_auto_stop_due_when_the_cutoff_passes_on_the_start_day() {
+        use time::macros::datetime;
+        let timer = overnight_timer(datetime!(2025-11-05 09:00 +1));
+        let cutoff = TimePoint::new(19, 0).unwrap();
+        assert!(!auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-05 18:59 +1)
+        ));
+        assert!(auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-05 19:00 +1)
+        ));
+        // The same instant in another offset is still before the cutoff
+        assert!(!auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-05 17:30 UTC)
+        ));
+        assert!(auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-06 08:00 +1)
+        ));
+    }
+
+    #[test]
+    fn test_auto_stop_not_due_for_timer_started_after_the_cutoff() {
+        use time::macros::datetime;
+        let timer = overnight_timer(datetime!(2025-11-05 19:30 UTC));
+        let cutoff = TimePoint::new(19, 0).unwrap();
+        assert!(!auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-05 19:31 UTC)
+        ));
+        assert!(!auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-05 23:59 UTC)
+        ));
+        assert!(auto_stop_due(
+            &timer,
+            cutoff,
+            datetime!(2025-11-06 00:00 UTC)
+        ));
+
+        let at_cutoff = overnight_timer(datetime!(2025-11-05 19:00 UTC));
+        assert!(!auto_stop_due(
+            &at_cutoff,
+            cutoff,
+            datetime!(2025-11-05 19:05 UTC)
+        ));
+    }
+
+    #[test]
+    fn test_auto_stop_on_the_start_day_ends_at_the_cutoff_without_restart() {
+        use time::macros::{date, datetime};
+        let (storage, _temp) = create_test_storage();
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-05 09:00 UTC)))
+            .unwrap();
+        let manager = TimerManager::new(storage.clone());
+        let cutoff = TimePoint::new(19, 0).unwrap();
+
+        let early = datetime!(2025-11-05 18:45 UTC);
+        assert!(
+            manager
+                .auto_stop_if_overdue(cutoff, true, early)
+                .unwrap()
+                .is_none()
+        );
+
+        let record = manager
+            .auto_stop_if_overdue(cutoff, true, datetime!(2025-11-05 19:07:30 UTC))
+            .unwrap()
+            .expect("timer past the cutoff should be stopped");
+        assert_eq!(record.end, cutoff);
+        assert_eq!(record.total_minutes, 10 * 60);
+        let day = storage.load(&date!(2025 - 11 - 05)).unwrap();
+        assert_eq!(day.work_records.len(), 1);
+        // Restarting would only run into the same cutoff again
+        assert!(manager.status().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_midnight_after_start() {
+        use time::macros::datetime;
+        let timer = overnight_timer(datetime!(2025-11-05 22:15 +1));
+        assert_eq!(
+            midnight_after_start(&timer, datetime!(2025-11-05 23:59 +1)),
+            None
+        );
+        assert_eq!(
+            midnight_after_start(&timer, datetime!(2025-11-06 00:00 +1)),
+            Some(datetime!(2025-11-06 00:00 +1))
+        );
+        // The same instant seen in UTC is still past the timer's local midnight
+        assert_eq!(
+            midnight_after_start(&timer, datetime!(2025-11-05 23:30 UTC)),
+            Some(datetime!(2025-11-06 00:00 +1))
+        );
+        // Days later, the split still happens at the first midnight
+        assert_eq!(
+            midnight_after_start(&timer, datetime!(2025-11-08 10:00 +1)),
+            Some(datetime!(2025-11-06 00:00 +1))
+        );
+    }
+
+    #[test]
+    fn test_split_at_midnight_continues_on_the_new_day() {
+        use time::macros::{date, datetime};
+        let (storage, _temp) = create_test_storage();
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-05 22:15 UTC)))
+            .unwrap();
+        let manager = TimerManager::new(storage.clone());
+
+        let now = datetime!(2025-11-06 00:00:30 UTC);
+        let (stopped, continued) = manager.split_at_midnight(now).unwrap().unwrap();
+        assert_eq!(stopped.date, date!(2025 - 11 - 05));
+        assert_eq!(stopped.session.start, TimePoint::new(22, 15).unwrap());
+        assert_eq!(stopped.session.end, TimePoint::new(0, 0).unwrap());
+        assert_eq!(stopped.session.total_minutes, 105);
+        assert_eq!(continued.task_name, "Forgotten Task");
+        assert_eq!(continued.description.as_deref(), Some("Left running"));
+        assert_eq!(continued.start_time, datetime!(2025-11-06 00:00 UTC));
+        assert_eq!(continued.date, date!(2025 - 11 - 06));
+
+        let old_day = storage.load(&date!(2025 - 11 - 05)).unwrap();
+        assert_eq!(old_day.work_records.len(), 1);
+        assert_eq!(manager.status().unwrap(), Some(continued));
+
+        // Nothing more to split until the next midnight
+        assert!(manager.split_at_midnight(now).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_split_at_midnight_leaves_paused_and_current_sessions() {
+        use time::macros::datetime;
+        let (storage, _temp) = create_test_storage();
+        let manager = TimerManager::new(storage.clone());
+        let now = datetime!(2025-11-06 00:05 UTC);
+        assert!(manager.split_at_midnight(now).unwrap().is_none());
+
+        let mut paused = overnight_timer(datetime!(2025-11-05 22:15 UTC));
+        paused.status = TimerStatus::Paused;
+        paused.paused_at = Some(datetime!(2025-11-05 23:00 UTC));
+        storage.save_active_timer(&paused).unwrap();
+        assert!(manager.split_at_midnight(now).unwrap().is_none());
+
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-06 00:01 UTC)))
+            .unwrap();
+        assert!(manager.split_at_midnight(now).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_auto_stop_ignores_timer_started_today() {
+        use time::macros::datetime;
+        let (storage, _temp) = create_test_storage();
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-05 09:00 UTC)))
+            .unwrap();
+        let manager = TimerManager::new(storage);
+
+        let result = manager
+            .auto_stop_if_overdue(
+                TimePoint::new(23, 59).unwrap(),
+                false,
+                datetime!(2025-11-05 22:00 UTC),
+            )
+            .unwrap();
+        assert!(result.is_none());
+        assert!(manager.status().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_auto_stop_logs_record_to_start_day() {
+        use time::macros::{date, datetime};
+        let (storage, _temp) = create_test_storage();
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-05 09:00 UTC)))
+            .unwrap();
+        let manager = TimerManager::new(storage.clone());
+
+        let record = manager
+            .auto_stop_if_overdue(
+                TimePoint::new(23, 59).unwrap(),
+                false,
+                datetime!(2025-11-06 08:30 UTC),
+            )
+            .unwrap()
+            .expect("overnight timer should be stopped");
+        assert_eq!(record.end, TimePoint::new(23, 59).unwrap());
+        assert_eq!(record.total_minutes, 14 * 60 + 59);
+
+        let start_day = storage.load(&date!(2025 - 11 - 05)).unwrap();
+        assert_eq!(start_day.work_records.len(), 1);
+        let saved = start_day.work_records.values().next().unwrap();
+        assert_eq!(saved.name, "Forgotten Task");
+        assert_eq!(saved.description, "Left running");
+
+        let next_day = storage.load(&date!(2025 - 11 - 06)).unwrap();
+        assert!(next_day.work_records.is_empty());
+        assert!(manager.status().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_auto_stop_after_the_cutoff_ends_at_midnight() {
+        use time::macros::{date, datetime};
+        let (storage, _temp) = create_test_storage();
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-05 20:00 UTC)))
+            .unwrap();
+        let manager = TimerManager::new(storage.clone());
+
+        let record = manager
+            .auto_stop_if_overdue(
+                TimePoint::new(19, 0).unwrap(),
+                false,
+                datetime!(2025-11-06 08:30 UTC),
+            )
+            .unwrap()
+            .expect("overnight timer should be stopped");
+        assert_eq!(record.start, TimePoint::new(20, 0).unwrap());
+        assert_eq!(record.end, TimePoint::new(0, 0).unwrap());
+        assert_eq!(record.total_minutes, 4 * 60);
+        let start_day = storage.load(&date!(2025 - 11 - 05)).unwrap();
+        assert_eq!(start_day.work_records.len(), 1);
+    }
+
+    #[test]
+    fn test_auto_stop_with_restart_starts_fresh_timer() {
+        use time::macros::datetime;
+        let (storage, _temp) = create_test_storage();
+        storage
+            .save_active_timer(&overnight_timer(datetime!(2025-11-05 09:00 UTC)))
+            .unwrap();
+        let manager = TimerManager::new(storage);
+
+        manager
+            .auto_stop_if_overdue(
+                TimePoint::new(23, 59).unwrap(),
+                true,
+                datetime!(2025-11-06 08:30 UTC),
+            )
+            .unwrap()
+            .expect("overnight timer should be stopped");
+
+        let restarted = manager.status().unwrap().expect("timer should restart");
+        assert_eq!(restarted.task_name, "Forgotten Task");
+        assert_eq!(restarted.status, TimerStatus::Running);
+        assert_ne!(restarted.start_time, datetime!(2025-11-05 09:00 UTC));
+    }
 }
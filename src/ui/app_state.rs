@@ -1,8 +1,18 @@
+use super::bell::{self, TimerEvent};
+use super::field_changes::{FieldChange, FieldChanges, RecordField};
 use super::history::History;
-use crate::config::{Config, Theme};
-use crate::models::{DayData, WorkRecord};
-use crate::timer::TimerState;
-use time::Date;
+use super::keymap::KeyMap;
+use super::record_filter::RecordFilter;
+use crate::budget::{BudgetPeriod, BudgetStatus, BudgetTracker, RunningTime, period_statuses};
+use crate::config::{Config, Theme, TotalsWindow};
+use crate::models::{
+    DayData, PlanAudit, RecordConflict, RecordTemplate, Resolution, TimePoint, WorkRecord, shift,
+};
+use crate::profile::Profile;
+use crate::report::{FocusStats, WeekGrid, WindowTotals, week_containing};
+use crate::timer::{LockAction, LockEvent, LockPause, TimerState, TimerStatus};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use time::{Date, OffsetDateTime};
 
 pub enum AppMode {
     Browse,
@@ -11,6 +21,46 @@ pub enum AppMode {
     CommandPalette,
     Calendar,
     TaskPicker,
+    GotoId,
+    LinkPrompt,
+    AnnotationPrompt,
+    EstimatePrompt,
+    AdjustmentPrompt,
+    Fields,
+    ConfirmClear,
+    ConfirmCascade,
+    MergeConflict,
+    RevertField,
+    Summary,
+    SessionNotePrompt,
+    JoinPrompt,
+    ShiftPrompt,
+    RecentDays,
+    WeekGrid,
+    TemplatePicker,
+    TemplateNamePrompt,
+}
+
+/// A day with records in the recent days list (`G`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecentDay {
+    pub date: Date,
+    pub records: usize,
+    pub worked_minutes: u32,
+}
+
+/// A start waiting on `[edit] cascade = "ask"`: record `id` would start at `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingCascade {
+    pub id: u32,
+    pub start: TimePoint,
+}
+
+/// A URL shown to the user instead of (or after) opening it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlNotice {
+    pub message: String,
+    pub url: String,
 }
 
 pub enum EditField {
@@ -20,13 +70,39 @@ pub enum EditField {
     Description,
 }
 
+/// Source of "now" for everything the UI derives from the wall clock
+///
+/// The TUI always uses `System`; `Fixed` pins the time so rendered frames are deterministic.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Clock {
+    #[default]
+    System,
+    #[allow(dead_code)]
+    Fixed(OffsetDateTime),
+}
+
+impl Clock {
+    /// Current local time (falls back to UTC if the local offset can't be determined)
+    pub fn now(&self) -> OffsetDateTime {
+        match self {
+            Clock::System => crate::timefmt::now_local(),
+            Clock::Fixed(now) => *now,
+        }
+    }
+
+    /// Current local date
+    pub fn today(&self) -> Date {
+        self.now().date()
+    }
+}
+
 pub struct Command {
     pub key: &'static str,
     pub description: &'static str,
     pub action: CommandAction,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandAction {
     MoveUp,
     MoveDown,
@@ -43,12 +119,49 @@ pub enum CommandAction {
     Redo,
     Save,
     StartTimer,
+    ResumeLastTask,
+    BackfillTimer,
+    SwitchSession,
+    NoteSession,
+    RecentDays,
     PauseTimer,
+    GotoId,
+    GotoTimerRecord,
+    SetLink,
+    Annotate,
+    SetEstimate,
+    SetAdjustment,
+    EditFields,
+    RevertField,
+    ClearDay,
+    ShiftDay,
+    SaveTemplate,
+    InsertTemplate,
+    ToggleRelativeTimes,
+    MarkPlanned,
+    TogglePlanAudit,
+    CycleTotalsWindow,
+    ToggleProject,
+    FocusSummary,
+    ToggleFlag,
+    ToggleBillable,
+    NextFlag,
+    PreviousFlag,
+    JumpLongest,
+    JumpShortest,
     Quit,
 }
 
+/// Redraws the record a stopped session was written to stays highlighted (~2 s idle)
+const STOPPED_FLASH_FRAMES: u64 = 4;
+
+/// Days offered by the recent days list
+const RECENT_DAYS: usize = 14;
+
 pub struct AppState {
     pub day_data: DayData,
+    /// The viewed day as last loaded or saved, to tell whether it has unsaved edits
+    saved_day: DayData,
     pub current_date: Date,
     pub mode: AppMode,
     pub selected_index: usize,
@@ -66,16 +179,109 @@ pub struct AppState {
     pub calendar_view_month: time::Month,
     pub calendar_view_year: i32,
     pub config: Config,
+    /// Browse keys after `[keys]` overrides
+    pub keymap: KeyMap,
     pub theme: Theme,
     pub last_error_message: Option<String>,
+    /// One-line notice shown in the footer until the next key press
+    pub status_message: Option<String>,
+    /// Why the last save failed; shown in the footer until a save succeeds
+    pub save_error: Option<String>,
+    /// Records changed both here and on disk, settled one at a time in MergeConflict mode
+    pub merge_conflicts: Vec<RecordConflict>,
+    /// URL shown in a modal when it was copied or couldn't be opened
+    pub url_notice: Option<UrlNotice>,
+    /// Custom field values being edited in Fields mode, in `[fields]` order
+    pub field_drafts: Vec<(String, String)>,
+    pub field_selected: usize,
     pub task_picker_selected: usize,
     pub active_timer: Option<TimerState>,
     pub last_file_modified: Option<std::time::SystemTime>,
+    pub clock: Clock,
+    /// Redraw counter driving the timer bar spinner
+    pub frame: u64,
+    /// Record just written by stopping a session, highlighted until the given frame
+    pub stopped_flash: Option<(u32, u64)>,
+    /// Lock and sleep events, when `[timer] pause_on_lock` could subscribe to them
+    pub lock_events: Option<std::sync::mpsc::Receiver<(LockEvent, OffsetDateTime)>>,
+    pub lock_pause: LockPause,
+    pub profile: Profile,
+    /// Launched with `--safe`: config and custom themes were not loaded
+    pub safe_mode: bool,
+    pub relative_times: bool,
+    /// Compare records with the day's plan in a Plan column (`O`)
+    pub show_plan_audit: bool,
+    /// Task picker confirms by creating a new record instead of renaming the selected one
+    pub task_picker_create: bool,
+    /// Task picker confirms by switching the session to the chosen task (`w`)
+    pub task_picker_switch: bool,
+    /// Projects collapsed to a single line in the summary (kept across days for the session)
+    pub collapsed_projects: HashSet<String>,
+    /// Summary row highlighted while the summary has focus (`f`)
+    pub summary_selected: usize,
+    /// Records table narrowed to a summary row; cleared with Esc or a day change
+    pub record_filter: Option<RecordFilter>,
+    /// Ids of the viewed day's records flagged with `m` (see `[ui] persist_flags`)
+    pub flagged: BTreeSet<u32>,
+    /// Cumulative status of every configured budget (see `refresh_budgets`)
+    pub budget_statuses: Vec<BudgetStatus>,
+    budget_tracker: BudgetTracker,
+    /// Period summed in the header (`Tab`), see `refresh_window_totals`
+    pub totals_window: TotalsWindow,
+    /// Worked minutes over `totals_window`, None for today or until computed
+    pub window_worked_minutes: Option<u32>,
+    window_totals: WindowTotals,
+    /// Today's timer session statistics, see `refresh_focus_stats`
+    pub focus_today: Option<FocusStats>,
+    focus_loaded_for: Option<Date>,
+    ticket_cache: crate::integrations::TicketCache,
+    /// Record to select once the day being navigated to has loaded
+    pending_record_id: Option<u32>,
+    /// Record whose upcoming-start reminder already rang, so it rings once
+    upcoming_alerted: Option<(Date, u32)>,
+    /// A timer change asked for the bell, see `take_bell`
+    bell_pending: bool,
     history: History,
+    /// Recent edits per record, offered by `H` (Revert field)
+    field_changes: FieldChanges,
+    /// Highlighted change in the Revert field list
+    pub revert_selected: usize,
+    /// Next record's new start, asked about in ConfirmCascade mode
+    pub pending_cascade: Option<PendingCascade>,
+    /// Latest days with records, newest first, while the recent days list is open
+    pub recent_days: Vec<RecentDay>,
+    /// Highlighted entry of `recent_days`
+    pub recent_days_selected: usize,
+    /// Records the join prompt (`J` in visual mode) will merge
+    pub join_ids: Vec<u32>,
+    /// Ends `n` made up for new records, by day and id; a record still ending
+    /// there has no real end yet (`[defaults] close_previous`)
+    placeholder_ends: HashMap<(Date, u32), TimePoint>,
+    /// Saved record templates, while the template picker or name prompt is open
+    pub templates: Vec<RecordTemplate>,
+    /// Highlighted entry of the filtered templates
+    pub template_selected: usize,
+    /// Template being renamed in the name prompt, None when saving a new one
+    pub template_renaming: Option<String>,
+    /// Tasks × days totals of the calendar's week, while open (`w` in the calendar)
+    pub week_grid: Option<WeekGrid>,
+    /// First task row shown in the week grid
+    pub week_grid_scroll: usize,
+    /// `[[task_budgets]]` of the grid's week and of the selected date's month
+    pub period_budgets: Vec<(BudgetPeriod, BudgetStatus)>,
+    /// Local date at the last midnight check, None before the first tick
+    seen_day: Option<Date>,
 }
 
 impl AppState {
+    /// Create the app state, loading the user's config from disk
+    #[allow(dead_code)]
     pub fn new(day_data: DayData) -> Self {
+        Self::with_config(day_data, Config::load().unwrap_or_default())
+    }
+
+    /// Create the app state with an explicit config (no disk access)
+    pub fn with_config(day_data: DayData, config: Config) -> Self {
         let current_date = day_data.date;
         let available_commands = vec![
             Command {
@@ -153,11 +359,156 @@ impl AppState {
                 description: "Start/Stop session (toggle)",
                 action: CommandAction::StartTimer,
             },
+            Command {
+                key: "w",
+                description: "Switch to task… (stop session, start another)",
+                action: CommandAction::SwitchSession,
+            },
+            Command {
+                key: "J",
+                description: "Add note to running session…",
+                action: CommandAction::NoteSession,
+            },
+            Command {
+                key: "A",
+                description: "Start session for today's most recent task",
+                action: CommandAction::ResumeLastTask,
+            },
+            Command {
+                key: "B",
+                description: "Start session counting the time since selected record ended",
+                action: CommandAction::BackfillTimer,
+            },
             Command {
                 key: "P",
                 description: "Pause/Resume active session",
                 action: CommandAction::PauseTimer,
             },
+            Command {
+                key: "g",
+                description: "Go to record by ID",
+                action: CommandAction::GotoId,
+            },
+            Command {
+                key: "g a",
+                description: "Go to the active session's record",
+                action: CommandAction::GotoTimerRecord,
+            },
+            Command {
+                key: "G",
+                description: "Go to a recent day with records…",
+                action: CommandAction::RecentDays,
+            },
+            Command {
+                key: "U",
+                description: "Set link for record",
+                action: CommandAction::SetLink,
+            },
+            Command {
+                key: "N",
+                description: "Annotate task in summary…",
+                action: CommandAction::Annotate,
+            },
+            Command {
+                key: "E",
+                description: "Set estimate for record…",
+                action: CommandAction::SetEstimate,
+            },
+            Command {
+                key: "K",
+                description: "Adjust time of record…",
+                action: CommandAction::SetAdjustment,
+            },
+            Command {
+                key: "F",
+                description: "Edit custom fields…",
+                action: CommandAction::EditFields,
+            },
+            Command {
+                key: "H",
+                description: "Revert a field to an earlier value…",
+                action: CommandAction::RevertField,
+            },
+            Command {
+                key: "X",
+                description: "Clear all records on this day…",
+                action: CommandAction::ClearDay,
+            },
+            Command {
+                key: "D",
+                description: "Shift entire day earlier/later…",
+                action: CommandAction::ShiftDay,
+            },
+            Command {
+                key: "Y",
+                description: "Save selected record as template…",
+                action: CommandAction::SaveTemplate,
+            },
+            Command {
+                key: "I",
+                description: "Insert template…",
+                action: CommandAction::InsertTemplate,
+            },
+            Command {
+                key: "R",
+                description: "Toggle relative start/end times",
+                action: CommandAction::ToggleRelativeTimes,
+            },
+            Command {
+                key: "M",
+                description: "Mark day as planned",
+                action: CommandAction::MarkPlanned,
+            },
+            Command {
+                key: "O",
+                description: "Toggle planned vs actual audit",
+                action: CommandAction::TogglePlanAudit,
+            },
+            Command {
+                key: "Tab",
+                description: "Header totals: today / last 7 days / last 30 days",
+                action: CommandAction::CycleTotalsWindow,
+            },
+            Command {
+                key: "z",
+                description: "Collapse/expand selected record's project in summary",
+                action: CommandAction::ToggleProject,
+            },
+            Command {
+                key: "f",
+                description: "Focus summary; Enter shows only that row's records",
+                action: CommandAction::FocusSummary,
+            },
+            Command {
+                key: "m",
+                description: "Flag/unflag selected record for attention",
+                action: CommandAction::ToggleFlag,
+            },
+            Command {
+                key: "$",
+                description: "Mark selected record(s) billable/non-billable",
+                action: CommandAction::ToggleBillable,
+            },
+            Command {
+                key: "}",
+                description: "Jump to next flagged record",
+                action: CommandAction::NextFlag,
+            },
+            Command {
+                key: "{",
+                description: "Jump to previous flagged record",
+                action: CommandAction::PreviousFlag,
+            },
+            Command {
+                key: ")",
+                description: "Jump to the day's longest record",
+                action: CommandAction::JumpLongest,
+            },
+            Command {
+                key: "(",
+                description: "Jump to the day's shortest record",
+                action: CommandAction::JumpShortest,
+            },
             Command {
                 key: "q",
                 description: "Quit application",
@@ -165,14 +516,24 @@ impl AppState {
             },
         ];
 
-        let config = Config::load().unwrap_or_default();
         let theme = config.get_theme();
+        let (keymap, key_errors) = KeyMap::from_config(&config.keys, &config.quick_tasks);
+        let relative_times = config.ui.relative_times;
+        let flagged = match config.ui.persist_flags {
+            true => day_data.flagged.clone(),
+            false => BTreeSet::new(),
+        };
+        let totals_window = config.ui.header_totals;
+        let saved_day = day_data.clone();
+        let mut day_data = day_data;
+        day_data.apply_offset_changes(config.time.active_offset_changes());
 
         AppState {
             calendar_selected_date: current_date,
             calendar_view_month: current_date.month(),
             calendar_view_year: current_date.year(),
             day_data,
+            saved_day,
             current_date,
             mode: AppMode::Browse,
             selected_index: 0,
@@ -187,12 +548,59 @@ impl AppState {
             available_commands,
             date_changed: false,
             config,
+            keymap,
             theme,
-            last_error_message: None,
+            last_error_message: (!key_errors.is_empty()).then(|| key_errors.join("; ")),
+            status_message: None,
+            save_error: None,
+            merge_conflicts: Vec::new(),
+            url_notice: None,
+            field_drafts: Vec::new(),
+            field_selected: 0,
             task_picker_selected: 0,
             active_timer: None,
             last_file_modified: None,
+            clock: Clock::System,
+            frame: 0,
+            stopped_flash: None,
+            lock_events: None,
+            lock_pause: LockPause::default(),
+            profile: Profile::default(),
+            safe_mode: false,
+            relative_times,
+            show_plan_audit: false,
+            task_picker_create: false,
+            task_picker_switch: false,
+            collapsed_projects: HashSet::new(),
+            summary_selected: 0,
+            record_filter: None,
+            flagged,
+            budget_statuses: Vec::new(),
+            budget_tracker: BudgetTracker::new(),
+            totals_window,
+            window_worked_minutes: None,
+            window_totals: WindowTotals::new(),
+            focus_today: None,
+            focus_loaded_for: None,
+            ticket_cache: crate::integrations::TicketCache::new(),
+            pending_record_id: None,
+            upcoming_alerted: None,
+            bell_pending: false,
             history: History::new(),
+            field_changes: FieldChanges::new(),
+            revert_selected: 0,
+            pending_cascade: None,
+            recent_days: Vec::new(),
+            recent_days_selected: 0,
+            join_ids: Vec::new(),
+            placeholder_ends: HashMap::new(),
+            templates: Vec::new(),
+            template_selected: 0,
+            template_renaming: None,
+            week_grid: None,
+            week_grid_scroll: 0,
+            period_budgets: Vec::new(),
+            seen_day: None,
         }
     }
 
@@ -202,8 +610,12 @@ impl AppState {
     }
 
     pub fn move_selection_up(&mut self) {
-        if self.selected_index > 0 {
-            self.selected_index -= 1;
+        let records = self.day_data.get_sorted_records();
+        if let Some(index) = (0..self.selected_index)
+            .rev()
+            .find(|&i| self.is_shown(records[i]))
+        {
+            self.selected_index = index;
         }
         if matches!(self.mode, AppMode::Visual) {
             self.visual_end = self.selected_index;
@@ -211,9 +623,11 @@ impl AppState {
     }
 
     pub fn move_selection_down(&mut self) {
-        let record_count = self.day_data.work_records.len();
-        if self.selected_index < record_count.saturating_sub(1) {
-            self.selected_index += 1;
+        let records = self.day_data.get_sorted_records();
+        if let Some(index) =
+            (self.selected_index + 1..records.len()).find(|&i| self.is_shown(records[i]))
+        {
+            self.selected_index = index;
         }
         if matches!(self.mode, AppMode::Visual) {
             self.visual_end = self.selected_index;
@@ -221,12 +635,16 @@ impl AppState {
     }
 
     pub fn enter_edit_mode(&mut self) {
+        if self.day_data.work_records.is_empty() {
+            self.open_task_picker_for_new_record();
+            return;
+        }
         if let Some(record) = self.get_selected_record() {
             let input_value = match self.edit_field {
                 EditField::Name => record.name.clone(),
                 EditField::Start => record.start.to_string(),
                 EditField::End => record.end.to_string(),
-                EditField::Description => record.description.clone(),
+                EditField::Description => self.description_input(record),
             };
             self.mode = AppMode::Edit;
             self.input_buffer = input_value;
@@ -234,7 +652,29 @@ impl AppState {
         }
     }
 
+    /// What editing `record`'s description starts from
+    ///
+    /// An empty description of a record with a ticket gets the filled
+    /// `[defaults] description_template`, if one is set.
+    fn description_input(&self, record: &WorkRecord) -> String {
+        if !record.description.is_empty() {
+            return record.description.clone();
+        }
+        self.config
+            .defaults
+            .description_template
+            .as_deref()
+            .and_then(|template| {
+                crate::integrations::fill_description_template(template, &record.name)
+            })
+            .unwrap_or_default()
+    }
+
     pub fn change_task_name(&mut self) {
+        if self.day_data.work_records.is_empty() {
+            self.open_task_picker_for_new_record();
+            return;
+        }
         if matches!(self.edit_field, EditField::Name) && self.get_selected_record().is_some() {
             // Check if there are any existing tasks to pick from
             let task_names = self.get_unique_task_names();
@@ -252,8 +692,20 @@ impl AppState {
         }
     }
 
+    /// Open the task picker to name the day's first record
+    pub fn open_task_picker_for_new_record(&mut self) {
+        self.input_buffer.clear();
+        self.task_picker_selected = 0;
+        self.task_picker_create = true;
+        self.mode = AppMode::TaskPicker;
+    }
+
     pub fn exit_edit_mode(&mut self) {
-        self.mode = AppMode::Browse;
+        // A committed end may have left a cascade to ask about
+        self.mode = match self.pending_cascade {
+            Some(_) => AppMode::ConfirmCascade,
+            None => AppMode::Browse,
+        };
         self.input_buffer.clear();
         self.edit_field = EditField::Name;
         self.time_cursor = 0;
@@ -273,7 +725,7 @@ impl AppState {
                     EditField::End
                 }
                 EditField::End => {
-                    self.input_buffer = record.description.clone();
+                    self.input_buffer = self.description_input(record);
                     self.time_cursor = 0;
                     EditField::Description
                 }
@@ -312,8 +764,12 @@ impl AppState {
 
                 self.time_cursor += 1;
 
-                if self.time_cursor >= positions.len() && self.save_current_field().is_ok() {
-                    self.exit_edit_mode();
+                if self.time_cursor >= positions.len() {
+                    // An out-of-range time ("25:00") stays open to be corrected
+                    match self.save_current_field() {
+                        Ok(()) => self.exit_edit_mode(),
+                        Err(e) => self.last_error_message = Some(e),
+                    }
                 }
             }
         }
@@ -333,41 +789,131 @@ impl AppState {
     }
 
     fn save_current_field(&mut self) -> Result<(), String> {
-        let records = self.day_data.get_sorted_records();
-        if let Some(&record) = records.get(self.selected_index) {
-            let id = record.id;
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return Ok(());
+        };
+        let field = match self.edit_field {
+            EditField::Name => RecordField::Name,
+            EditField::Start => RecordField::Start,
+            EditField::End => RecordField::End,
+            EditField::Description => RecordField::Description,
+        };
+        let value = self.input_buffer.clone();
+        self.set_record_field(id, field, &value)
+    }
 
-            if let Some(record_mut) = self.day_data.work_records.get_mut(&id) {
-                match self.edit_field {
-                    EditField::Name => {
-                        if self.input_buffer.trim().is_empty() {
-                            return Err("Name cannot be empty".to_string());
-                        }
-                        record_mut.name = self.input_buffer.trim().to_string();
-                    }
-                    EditField::Start => {
-                        record_mut.start = self
-                            .input_buffer
-                            .parse()
-                            .map_err(|_| "Invalid start time format (use HH:MM)".to_string())?;
-                        record_mut.update_duration();
-                    }
-                    EditField::End => {
-                        record_mut.end = self
-                            .input_buffer
-                            .parse()
-                            .map_err(|_| "Invalid end time format (use HH:MM)".to_string())?;
-                        record_mut.update_duration();
-                    }
-                    EditField::Description => {
-                        record_mut.description = self.input_buffer.trim().to_string();
-                    }
+    /// Set one field of record `id` from text, remembering the edit for `H`
+    fn set_record_field(&mut self, id: u32, field: RecordField, value: &str) -> Result<(), String> {
+        let date = self.day_data.date;
+        let changes = self.config.time.active_offset_changes();
+        let Some(record) = self.day_data.work_records.get_mut(&id) else {
+            return Ok(());
+        };
+        let mut moved_end = None;
+        let old = match field {
+            RecordField::Name => {
+                if value.trim().is_empty() {
+                    return Err("Name cannot be empty".to_string());
                 }
+                std::mem::replace(&mut record.name, value.trim().to_string())
             }
+            RecordField::Start => {
+                let start: TimePoint = value.parse()?;
+                let old = std::mem::replace(&mut record.start, start);
+                record.update_duration_on(date, changes);
+                old.to_string()
+            }
+            RecordField::End => {
+                let end: TimePoint = value.parse()?;
+                let old = std::mem::replace(&mut record.end, end);
+                // An end set by hand finishes a record a timer left behind
+                record.in_progress = false;
+                record.update_duration_on(date, changes);
+                moved_end = Some((old, end));
+                old.to_string()
+            }
+            RecordField::Description => {
+                std::mem::replace(&mut record.description, value.trim().to_string())
+            }
+        };
+        let new = field.value_of(record);
+        self.note_field_change(id, field, old, new);
+        if let Some((old_end, new_end)) = moved_end {
+            self.cascade_end(id, old_end, new_end);
         }
         Ok(())
     }
 
+    /// Carry an end moved from `old_end` over to the record that started there
+    ///
+    /// Applies `[edit] cascade`: nothing, the move, or a question first. Only the
+    /// record right after `id` moves; any gap stops the cascade. Part of the same
+    /// undo step as the end edit itself.
+    fn cascade_end(&mut self, id: u32, old_end: TimePoint, new_end: TimePoint) {
+        use crate::config::Cascade;
+
+        if old_end == new_end {
+            return;
+        }
+        let Some(next) = self.day_data.contiguous_after(id, old_end) else {
+            return;
+        };
+        let pending = PendingCascade {
+            id: next,
+            start: new_end,
+        };
+        match self.config.edit.cascade {
+            Cascade::Off => {}
+            Cascade::Contiguous => self.apply_cascade(pending),
+            Cascade::Ask => {
+                self.pending_cascade = Some(pending);
+                self.mode = AppMode::ConfirmCascade;
+            }
+        }
+    }
+
+    /// Move the next record's start, clamping it to that record's end
+    fn apply_cascade(&mut self, cascade: PendingCascade) {
+        let date = self.day_data.date;
+        let changes = self.config.time.active_offset_changes();
+        let Some(record) = self.day_data.work_records.get_mut(&cascade.id) else {
+            return;
+        };
+        let clamped = cascade.start > record.end && record.start <= record.end;
+        let start = if clamped { record.end } else { cascade.start };
+        let old = std::mem::replace(&mut record.start, start);
+        record.update_duration_on(date, changes);
+        if clamped {
+            self.status_message = Some(format!(
+                "{} would end before it starts; it now starts at its end, {}",
+                record.name, record.end
+            ));
+        }
+        let new = start.to_string();
+        self.note_field_change(cascade.id, RecordField::Start, old.to_string(), new);
+    }
+
+    /// Answer to the cascade question: move the next record too, or leave it
+    pub fn confirm_cascade(&mut self, apply: bool) {
+        if let Some(cascade) = self.pending_cascade.take()
+            && apply
+        {
+            self.apply_cascade(cascade);
+        }
+        self.mode = AppMode::Browse;
+    }
+
+    /// Remember an edit of record `id` on the viewed day
+    fn note_field_change(&mut self, id: u32, field: RecordField, old: String, new: String) {
+        let change = FieldChange {
+            field,
+            old,
+            new,
+            at: self.clock.now(),
+        };
+        self.field_changes.push(self.day_data.date, id, change);
+    }
+
     pub fn save_edit(&mut self) -> Result<(), String> {
         self.save_snapshot();
         self.save_current_field()?;
@@ -375,19 +921,122 @@ impl AppState {
         Ok(())
     }
 
+    /// Handle text pasted into the terminal (bracketed paste arrives as one string)
+    ///
+    /// A time range ("09:15 - 10:45", "9.15-10.45", "9:15 to 10:45") pasted in Browse mode
+    /// or on a Start/End field sets both times of the selected record in one step.
+    /// Returns true when the day data changed and needs saving.
+    pub fn handle_paste(&mut self, text: &str) -> Result<bool, String> {
+        use crate::models::TimePoint;
+
+        let on_time_field = matches!(self.edit_field, EditField::Start | EditField::End);
+        match self.mode {
+            AppMode::Browse => {
+                let (start, end) = TimePoint::parse_range(text)
+                    .ok_or_else(|| format!("Pasted text is not a time range: {}", text.trim()))?;
+                self.set_selected_range(start, end)?;
+                Ok(true)
+            }
+            AppMode::Edit if on_time_field => {
+                if let Some((start, end)) = TimePoint::parse_range(text) {
+                    self.set_selected_range(start, end)?;
+                    return Ok(true);
+                }
+                // A single time replaces the field being edited
+                let time = TimePoint::parse(text)
+                    .map_err(|_| format!("Pasted text is not a time: {}", text.trim()))?;
+                self.input_buffer = time.to_string();
+                self.save_edit()?;
+                Ok(true)
+            }
+            AppMode::Edit => {
+                self.input_buffer.push_str(&text.replace(['\r', '\n'], " "));
+                Ok(false)
+            }
+            AppMode::TaskPicker => {
+                self.input_buffer.push_str(text.trim());
+                Ok(false)
+            }
+            AppMode::CommandPalette => {
+                self.command_palette_input.push_str(text.trim());
+                Ok(false)
+            }
+            AppMode::LinkPrompt
+            | AppMode::AnnotationPrompt
+            | AppMode::SessionNotePrompt
+            | AppMode::JoinPrompt
+            | AppMode::ShiftPrompt
+            | AppMode::TemplateNamePrompt => {
+                self.input_buffer.push_str(text.trim());
+                Ok(false)
+            }
+            AppMode::TemplatePicker => {
+                self.input_buffer.push_str(text.trim());
+                self.template_selected = 0;
+                Ok(false)
+            }
+            AppMode::EstimatePrompt => {
+                text.trim()
+                    .chars()
+                    .for_each(|c| self.handle_estimate_prompt_char(c));
+                Ok(false)
+            }
+            AppMode::Fields => {
+                text.trim().chars().for_each(|c| self.handle_fields_char(c));
+                Ok(false)
+            }
+            AppMode::GotoId => {
+                text.trim()
+                    .chars()
+                    .for_each(|c| self.handle_goto_id_char(c));
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Set start and end of the selected record together (one undo step)
+    pub fn set_selected_range(
+        &mut self,
+        start: crate::models::TimePoint,
+        end: crate::models::TimePoint,
+    ) -> Result<(), String> {
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return Err("No record selected".to_string());
+        };
+
+        self.save_snapshot();
+        let date = self.day_data.date;
+        if let Some(record) = self.day_data.work_records.get_mut(&id) {
+            record.start = start;
+            record.end = end;
+            record.update_duration_on(date, self.config.time.active_offset_changes());
+        }
+        if matches!(self.mode, AppMode::Edit) {
+            self.exit_edit_mode();
+        }
+        Ok(())
+    }
+
     pub fn add_new_record(&mut self) {
+        self.add_new_record_named("New Task");
+    }
+
+    /// Add an hour-long record with the given name
+    ///
+    /// It starts where `[defaults] new_record_start` says, or at 09:00 on an empty day.
+    pub fn add_new_record_named(&mut self, name: &str) {
         use crate::models::{TimePoint, WorkRecord};
 
         self.save_snapshot();
 
         let id = self.day_data.next_id();
 
-        let (default_start, default_end) = if let Some(current_record) = self.get_selected_record()
-        {
-            let start_minutes = current_record.end.to_minutes_since_midnight();
+        let (default_start, default_end) = if let Some(start) = self.new_record_start() {
+            let start_minutes = start.to_minutes_since_midnight();
             let end_minutes = (start_minutes + 60).min(24 * 60 - 1);
             (
-                current_record.end,
+                start,
                 TimePoint::from_minutes_since_midnight(end_minutes).unwrap(),
             )
         } else {
@@ -397,14 +1046,70 @@ impl AppState {
             )
         };
 
-        let record = WorkRecord::new(id, "New Task".to_string(), default_start, default_end);
+        if self.config.defaults.close_previous {
+            self.close_previous_record(default_start);
+        }
+        let record = WorkRecord::new(id, name.to_string(), default_start, default_end);
 
         self.day_data.add_record(record);
+        self.placeholder_ends
+            .insert((self.day_data.date, id), default_end);
 
         let records = self.day_data.get_sorted_records();
         self.selected_index = records.iter().position(|r| r.id == id).unwrap_or(0);
     }
 
+    /// End the record that started last before `start` at `start`
+    ///
+    /// Only an end `n` filled in and nobody changed since is moved; a real end,
+    /// or one a running session owns, is left alone.
+    fn close_previous_record(&mut self, start: TimePoint) {
+        let date = self.day_data.date;
+        let Some(previous) = self
+            .day_data
+            .work_records
+            .values()
+            .filter(|record| record.start < start)
+            .max_by_key(|record| (record.start, record.id))
+        else {
+            return;
+        };
+        let id = previous.id;
+        if previous.in_progress
+            || previous.end == start
+            || self.placeholder_ends.get(&(date, id)) != Some(&previous.end)
+        {
+            return;
+        }
+
+        self.placeholder_ends.remove(&(date, id));
+        let changes = self.config.time.active_offset_changes();
+        if let Some(record) = self.day_data.work_records.get_mut(&id) {
+            record.end = start;
+            record.update_duration_on(date, changes);
+            self.status_message = Some(format!("Ended '{}' at {}", record.name, start));
+        }
+    }
+
+    /// Start for a new record under the configured strategy, `None` to use 09:00
+    fn new_record_start(&self) -> Option<TimePoint> {
+        use crate::config::NewRecordStart;
+
+        let after_selected = || self.get_selected_record().map(|record| record.end);
+        match self.config.defaults.new_record_start {
+            NewRecordStart::AfterSelected => after_selected(),
+            NewRecordStart::Now => {
+                let now = self.clock.now();
+                if now.date() == self.day_data.date {
+                    TimePoint::new(now.hour(), now.minute()).ok()
+                } else {
+                    after_selected()
+                }
+            }
+            NewRecordStart::AfterLast => self.day_data.work_records.values().map(|r| r.end).max(),
+        }
+    }
+
     pub fn add_break(&mut self) {
         use crate::models::{TimePoint, WorkRecord};
 
@@ -475,28 +1180,78 @@ impl AppState {
         let now = OffsetDateTime::now_utc().to_offset(local_offset);
         let current_time = format!("{:02}:{:02}", now.hour(), now.minute());
 
-        let records = self.day_data.get_sorted_records();
-        if let Some(&record) = records.get(self.selected_index) {
-            let id = record.id;
-
-            if let Some(record_mut) = self.day_data.work_records.get_mut(&id) {
-                match self.edit_field {
-                    EditField::Start => {
-                        if let Ok(time_point) = current_time.parse() {
-                            record_mut.start = time_point;
-                            record_mut.update_duration();
-                        }
-                    }
-                    EditField::End => {
-                        if let Ok(time_point) = current_time.parse() {
-                            record_mut.end = time_point;
-                            record_mut.update_duration();
-                        }
-                    }
-                    _ => {}
-                }
-            }
+        let field = match self.edit_field {
+            EditField::Start => RecordField::Start,
+            EditField::End => RecordField::End,
+            _ => return,
+        };
+        if let Some(id) = self.get_selected_record().map(|record| record.id) {
+            // A well-formed time always parses
+            let _ = self.set_record_field(id, field, &current_time);
+        }
+    }
+
+    /// Recent edits of the selected record, newest first
+    pub fn selected_record_changes(&self) -> Vec<&FieldChange> {
+        match self.get_selected_record() {
+            Some(record) => self.field_changes.for_record(self.day_data.date, record.id),
+            None => Vec::new(),
+        }
+    }
+
+    /// List the selected record's recent edits to revert one (`H`)
+    pub fn open_revert_field(&mut self) {
+        if self.selected_record_changes().is_empty() {
+            self.status_message = Some("No recent edits to this record".to_string());
+            return;
+        }
+        self.revert_selected = 0;
+        self.mode = AppMode::RevertField;
+    }
+
+    pub fn close_revert_field(&mut self) {
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn move_revert_selection_up(&mut self) {
+        self.revert_selected = self.revert_selected.saturating_sub(1);
+    }
+
+    pub fn move_revert_selection_down(&mut self) {
+        let count = self.selected_record_changes().len();
+        if self.revert_selected + 1 < count {
+            self.revert_selected += 1;
+        }
+    }
+
+    /// Put the highlighted edit's old value back, as one undoable edit
+    ///
+    /// Other fields, and later edits of other records, are left as they are.
+    pub fn confirm_revert_field(&mut self) -> Result<(), String> {
+        self.mode = AppMode::Browse;
+        let Some(change) = self
+            .selected_record_changes()
+            .get(self.revert_selected)
+            .map(|change| (*change).clone())
+        else {
+            return Err("No edit selected".to_string());
+        };
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return Err("No record selected".to_string());
+        };
+
+        self.save_snapshot();
+        self.set_record_field(id, change.field, &change.old)?;
+        // Start and End edits can move the record in the sorted table
+        if let Some(index) = self.index_of_record_id(id) {
+            self.selected_index = index;
         }
+        self.status_message = Some(format!(
+            "Reverted {} to {}",
+            change.field.label(),
+            change.old
+        ));
+        Ok(())
     }
 
     pub fn enter_visual_mode(&mut self) {
@@ -513,24 +1268,30 @@ impl AppState {
         let start = self.visual_start.min(self.visual_end);
         let end = self.visual_start.max(self.visual_end);
 
-        index >= start && index <= end
+        index >= start
+            && index <= end
+            && self
+                .day_data
+                .get_sorted_records()
+                .get(index)
+                .is_some_and(|record| self.is_shown(record))
     }
 
-    pub fn delete_visual_selection(&mut self) {
-        self.save_snapshot();
-
-        let records = self.day_data.get_sorted_records();
-        let start = self.visual_start.min(self.visual_end);
-        let end = self.visual_start.max(self.visual_end);
-
-        let ids_to_delete: Vec<u32> = records
+    /// Ids of the records in the visual selection, in table order
+    fn visual_selection_ids(&self) -> Vec<u32> {
+        self.day_data
+            .get_sorted_records()
             .iter()
             .enumerate()
-            .filter(|(i, _)| *i >= start && *i <= end)
+            .filter(|(i, _)| self.is_in_visual_selection(*i))
             .map(|(_, record)| record.id)
-            .collect();
+            .collect()
+    }
+
+    pub fn delete_visual_selection(&mut self) {
+        self.save_snapshot();
 
-        for id in ids_to_delete {
+        for id in self.visual_selection_ids() {
             self.day_data.remove_record(id);
         }
 
@@ -541,45 +1302,155 @@ impl AppState {
         self.exit_visual_mode();
     }
 
-    fn save_snapshot(&mut self) {
-        self.history.push(self.day_data.clone());
+    /// Ask for the name of the record the visual selection is joined into
+    ///
+    /// The selection is checked first, so a gap is reported before any typing.
+    pub fn open_join_prompt(&mut self) {
+        let ids = self.visual_selection_ids();
+        if let Err(e) = self.day_data.merged_span(&ids) {
+            self.last_error_message = Some(e);
+            return;
+        }
+        // The selection is in start order, so the first record is the earliest
+        self.input_buffer = ids
+            .first()
+            .and_then(|id| self.day_data.work_records.get(id))
+            .map(|record| record.name.clone())
+            .unwrap_or_default();
+        self.join_ids = ids;
+        self.mode = AppMode::JoinPrompt;
     }
 
-    pub fn undo(&mut self) {
-        if let Some(previous_state) = self.history.undo(self.day_data.clone()) {
-            self.day_data = previous_state;
+    pub fn close_join_prompt(&mut self) {
+        self.input_buffer.clear();
+        self.join_ids.clear();
+        self.mode = AppMode::Browse;
+    }
 
-            if self.selected_index >= self.day_data.work_records.len() {
-                self.selected_index = self.day_data.work_records.len().saturating_sub(1);
-            }
-        }
+    pub fn handle_join_prompt_char(&mut self, c: char) {
+        self.input_buffer.push(c);
     }
 
-    pub fn redo(&mut self) {
-        if let Some(next_state) = self.history.redo(self.day_data.clone()) {
-            self.day_data = next_state;
+    pub fn handle_join_prompt_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
 
-            if self.selected_index >= self.day_data.work_records.len() {
-                self.selected_index = self.day_data.work_records.len().saturating_sub(1);
+    /// Merge the selected records into one under the typed name (one undo step)
+    pub fn confirm_join_prompt(&mut self) {
+        let name = self.input_buffer.clone();
+        let ids = std::mem::take(&mut self.join_ids);
+        self.close_join_prompt();
+
+        let mut joined = self.day_data.clone();
+        match joined.merge_records(&ids, &name) {
+            Ok(id) => {
+                self.save_snapshot();
+                self.day_data = joined;
+                if let Some(index) = self.index_of_record_id(id) {
+                    self.selected_index = index;
+                }
+                self.status_message = Some(format!("Joined {} records into #{}", ids.len(), id));
             }
+            Err(e) => self.last_error_message = Some(e),
         }
     }
 
-    pub fn open_command_palette(&mut self) {
-        self.mode = AppMode::CommandPalette;
-        self.command_palette_input.clear();
-        self.command_palette_selected = 0;
+    /// Ask for the offset every record of the day is moved by
+    pub fn open_shift_prompt(&mut self) {
+        if self.day_data.work_records.is_empty() {
+            self.status_message = Some("No records to shift".to_string());
+            return;
+        }
+        self.input_buffer.clear();
+        self.mode = AppMode::ShiftPrompt;
     }
 
-    pub fn close_command_palette(&mut self) {
+    pub fn close_shift_prompt(&mut self) {
+        self.input_buffer.clear();
         self.mode = AppMode::Browse;
-        self.command_palette_input.clear();
-        self.command_palette_selected = 0;
     }
 
-    pub fn handle_command_palette_char(&mut self, c: char) {
-        self.command_palette_input.push(c);
-        self.command_palette_selected = 0;
+    pub fn handle_shift_prompt_char(&mut self, c: char) {
+        self.input_buffer.push(c);
+    }
+
+    pub fn handle_shift_prompt_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Move the whole day by the typed offset (one undo step)
+    ///
+    /// A shift that would squeeze a record to nothing is refused here; the CLI's
+    /// `--force` is the way to truncate.
+    pub fn confirm_shift_prompt(&mut self) {
+        let input = self.input_buffer.clone();
+        self.close_shift_prompt();
+
+        let minutes = match shift::parse_offset(&input) {
+            Ok(minutes) => minutes,
+            Err(e) => {
+                self.last_error_message = Some(e);
+                return;
+            }
+        };
+        let mut shifted = self.day_data.clone();
+        match shift::shift_day(&mut shifted, minutes, false) {
+            Ok(report) => {
+                self.save_snapshot();
+                self.day_data = shifted;
+                let mut message = format!(
+                    "Shifted {} records by {}",
+                    self.day_data.work_records.len(),
+                    shift::format_offset(minutes)
+                );
+                if let Some(summary) = report.summary() {
+                    message.push_str(&format!("; {}", summary));
+                }
+                self.status_message = Some(message);
+            }
+            Err(e) => self.last_error_message = Some(e),
+        }
+    }
+
+    fn save_snapshot(&mut self) {
+        self.history.push(self.day_data.clone());
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(previous_state) = self.history.undo(self.day_data.clone()) {
+            self.day_data = previous_state;
+
+            if self.selected_index >= self.day_data.work_records.len() {
+                self.selected_index = self.day_data.work_records.len().saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(next_state) = self.history.redo(self.day_data.clone()) {
+            self.day_data = next_state;
+
+            if self.selected_index >= self.day_data.work_records.len() {
+                self.selected_index = self.day_data.work_records.len().saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn open_command_palette(&mut self) {
+        self.mode = AppMode::CommandPalette;
+        self.command_palette_input.clear();
+        self.command_palette_selected = 0;
+    }
+
+    pub fn close_command_palette(&mut self) {
+        self.mode = AppMode::Browse;
+        self.command_palette_input.clear();
+        self.command_palette_selected = 0;
+    }
+
+    pub fn handle_command_palette_char(&mut self, c: char) {
+        self.command_palette_input.push(c);
+        self.command_palette_selected = 0;
     }
 
     pub fn handle_command_palette_backspace(&mut self) {
@@ -620,17 +1491,32 @@ impl AppState {
             .iter()
             .enumerate()
             .filter_map(|(i, cmd)| {
-                let search_text = format!("{} {}", cmd.key, cmd.description);
+                let search_text = format!("{} {}", self.command_key(cmd), cmd.description);
                 matcher
                     .fuzzy_match(&search_text, query)
                     .map(|score| (i, score, cmd))
             })
             .collect();
 
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|r| std::cmp::Reverse(r.1));
         results
     }
 
+    /// Key shown for a palette command, following `[keys]` overrides
+    pub fn command_key(&self, cmd: &Command) -> String {
+        self.keymap.label(cmd.action, cmd.key)
+    }
+
+    /// Key label and name of each quick task with a usable key, for the palette
+    pub fn quick_task_hints(&self) -> Vec<(String, &str)> {
+        self.config
+            .quick_tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, task)| Some((self.keymap.quick_task_label(i)?, task.name.as_str())))
+            .collect()
+    }
+
     pub fn execute_selected_command(&mut self) -> Option<CommandAction> {
         let filtered = self.get_filtered_commands();
         let action = filtered
@@ -655,10 +1541,53 @@ impl AppState {
     }
 
     pub fn load_new_day_data(&mut self, new_day_data: DayData) {
-        self.day_data = new_day_data;
-        self.selected_index = 0;
+        self.set_day_from_disk(new_day_data);
+        self.selected_index = self
+            .pending_record_id
+            .take()
+            .and_then(|id| self.index_of_record_id(id))
+            .unwrap_or(0);
         self.history = History::new();
+        self.record_filter = None;
+        self.flagged = match self.config.ui.persist_flags {
+            true => self.day_data.flagged.clone(),
+            false => BTreeSet::new(),
+        };
         self.date_changed = false;
+        self.flag_orphaned_records();
+    }
+
+    /// Show the day as just read from disk, keeping selection and undo history
+    ///
+    /// The disk version becomes the base later edits are compared and merged against.
+    fn set_day_from_disk(&mut self, day: DayData) {
+        self.saved_day = day.clone();
+        self.day_data = day;
+        self.day_data
+            .apply_offset_changes(self.config.time.active_offset_changes());
+    }
+
+    /// Warn in the status line about in-progress records no running timer belongs to
+    pub fn flag_orphaned_records(&mut self) {
+        use crate::validation::find_orphaned_in_progress;
+
+        let found = find_orphaned_in_progress(
+            &self.day_data.get_sorted_records(),
+            self.active_timer.as_ref(),
+            self.day_data.date,
+        );
+        if found.is_empty() {
+            return;
+        }
+        let listed = found
+            .iter()
+            .map(|id| format!("#{}", id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.status_message = Some(format!(
+            "⚠ Unfinished record with no running timer: {} (set its end time)",
+            listed
+        ));
     }
 
     pub fn open_calendar(&mut self) {
@@ -737,7 +1666,7 @@ impl AppState {
             let day = self
                 .calendar_selected_date
                 .day()
-                .min(days_in_month(new_month, new_year));
+                .min(crate::timefmt::days_in_month(new_month, new_year));
             self.calendar_selected_date =
                 time::Date::from_calendar_date(new_year, new_month, day).unwrap();
         }
@@ -772,7 +1701,7 @@ impl AppState {
             let day = self
                 .calendar_selected_date
                 .day()
-                .min(days_in_month(new_month, new_year));
+                .min(crate::timefmt::days_in_month(new_month, new_year));
             self.calendar_selected_date =
                 time::Date::from_calendar_date(new_year, new_month, day).unwrap();
         }
@@ -784,333 +1713,4850 @@ impl AppState {
         self.close_calendar();
     }
 
-    pub fn open_ticket_in_browser(&mut self) {
-        use crate::integrations::{build_url, detect_tracker, extract_ticket_from_name};
-
-        if let Some(record) = self.get_selected_record() {
-            if let Some(ticket_id) = extract_ticket_from_name(&record.name) {
-                if let Some(tracker_name) = detect_tracker(&ticket_id, &self.config) {
-                    match build_url(&ticket_id, &tracker_name, &self.config, false) {
-                        Ok(url) => {
-                            if let Err(e) = open_url_in_browser(&url) {
-                                self.last_error_message =
-                                    Some(format!("Failed to open browser: {}", e));
-                            }
-                        }
-                        Err(e) => {
-                            self.last_error_message = Some(format!("Failed to build URL: {}", e));
-                        }
-                    }
-                } else {
-                    self.last_error_message =
-                        Some("Could not detect tracker for ticket".to_string());
-                }
-            } else {
-                self.last_error_message = Some("No ticket found in task name".to_string());
-            }
+    /// Totals per task and day for the week holding the calendar's selected day
+    ///
+    /// The day on screen is taken from memory, the others from their files.
+    pub fn open_week_grid(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let week_start = self.config.ui.week_start.weekday();
+        let first = week_containing(self.calendar_selected_date, week_start);
+        let days: [DayData; 7] = self
+            .days_between(storage, first, first + time::Duration::days(6))?
+            .try_into()
+            .expect("seven days");
+
+        let budgets = &self.config.task_budgets;
+        let mut period_budgets: Vec<(BudgetPeriod, BudgetStatus)> =
+            period_statuses(budgets, BudgetPeriod::Week, &days)
+                .into_iter()
+                .map(|status| (BudgetPeriod::Week, status))
+                .collect();
+        if budgets.iter().any(|budget| budget.monthly_hours.is_some()) {
+            let (first, last) = BudgetPeriod::Month.range(self.calendar_selected_date, week_start);
+            let month = self.days_between(storage, first, last)?;
+            period_budgets.extend(
+                period_statuses(budgets, BudgetPeriod::Month, &month)
+                    .into_iter()
+                    .map(|status| (BudgetPeriod::Month, status)),
+            );
         }
-    }
 
-    pub fn open_worklog_in_browser(&mut self) {
-        use crate::integrations::{build_url, detect_tracker, extract_ticket_from_name};
+        self.week_grid = Some(WeekGrid::build(&days, self.config.ui.group_by));
+        self.period_budgets = period_budgets;
+        self.week_grid_scroll = 0;
+        self.mode = AppMode::WeekGrid;
+        Ok(())
+    }
 
-        if let Some(record) = self.get_selected_record() {
-            if let Some(ticket_id) = extract_ticket_from_name(&record.name) {
-                if let Some(tracker_name) = detect_tracker(&ticket_id, &self.config) {
-                    match build_url(&ticket_id, &tracker_name, &self.config, true) {
-                        Ok(url) => {
-                            if let Err(e) = open_url_in_browser(&url) {
-                                self.last_error_message =
-                                    Some(format!("Failed to open browser: {}", e));
-                            }
-                        }
-                        Err(e) => {
-                            self.last_error_message = Some(format!("Failed to build URL: {}", e));
-                        }
-                    }
-                } else {
-                    self.last_error_message =
-                        Some("Could not detect tracker for ticket".to_string());
-                }
-            } else {
-                self.last_error_message = Some("No ticket found in task name".to_string());
-            }
+    /// Stored days from `first` to `last`, with the viewed day taken from memory
+    fn days_between(
+        &self,
+        storage: &crate::storage::StorageManager,
+        first: Date,
+        last: Date,
+    ) -> Result<Vec<DayData>, String> {
+        let mut days = storage
+            .load_days_between(first, last)
+            .map_err(|e| e.to_string())?;
+        if let Some(day) = days.iter_mut().find(|day| day.date == self.day_data.date) {
+            *day = self.day_data.clone();
         }
+        Ok(days)
     }
 
-    pub fn clear_error(&mut self) {
-        self.last_error_message = None;
+    /// Back to the calendar the grid was opened from
+    pub fn close_week_grid(&mut self) {
+        self.week_grid = None;
+        self.period_budgets.clear();
+        self.mode = AppMode::Calendar;
     }
 
-    pub fn close_task_picker(&mut self) {
-        // Cancel and return to Browse mode
-        self.input_buffer.clear();
-        self.mode = AppMode::Browse;
+    pub fn scroll_week_grid_up(&mut self) {
+        self.week_grid_scroll = self.week_grid_scroll.saturating_sub(1);
     }
 
-    pub fn get_unique_task_names(&self) -> Vec<String> {
-        use std::collections::HashSet;
-
-        let mut seen = HashSet::new();
-        let mut task_names = Vec::new();
-
-        for record in self.day_data.work_records.values() {
-            let name = record.name.trim().to_string();
-            if !name.is_empty() && seen.insert(name.clone()) {
-                task_names.push(name);
-            }
+    pub fn scroll_week_grid_down(&mut self) {
+        let rows = self.week_grid.as_ref().map_or(0, |grid| grid.rows.len());
+        if self.week_grid_scroll + 1 < rows {
+            self.week_grid_scroll += 1;
         }
-
-        task_names.sort();
-        task_names
     }
 
-    pub fn get_filtered_task_names(&self) -> Vec<String> {
-        let all_tasks = self.get_unique_task_names();
-        let filter = self.input_buffer.to_lowercase();
-
-        if filter.is_empty() {
-            return all_tasks;
+    /// List the latest days with records up to today, to jump to one (`G`)
+    pub fn open_recent_days(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let days = storage
+            .recent_days(self.clock.today(), RECENT_DAYS)
+            .map_err(|e| e.to_string())?;
+        if days.is_empty() {
+            self.status_message = Some("No days with records yet".to_string());
+            return Ok(());
         }
+        self.recent_days = days
+            .iter()
+            .map(|day| RecentDay {
+                date: day.date,
+                records: day.work_records.len(),
+                worked_minutes: day.breakdown().worked_minutes,
+            })
+            .collect();
+        self.recent_days_selected = self
+            .recent_days
+            .iter()
+            .position(|day| day.date == self.current_date)
+            .unwrap_or(0);
+        self.mode = AppMode::RecentDays;
+        Ok(())
+    }
 
-        all_tasks
-            .into_iter()
-            .filter(|task| task.to_lowercase().contains(&filter))
-            .collect()
+    pub fn close_recent_days(&mut self) {
+        self.recent_days.clear();
+        self.mode = AppMode::Browse;
     }
 
-    pub fn move_task_picker_up(&mut self) {
-        if self.task_picker_selected > 0 {
-            self.task_picker_selected -= 1;
-        }
+    pub fn move_recent_days_up(&mut self) {
+        self.recent_days_selected = self.recent_days_selected.saturating_sub(1);
     }
 
-    pub fn move_task_picker_down(&mut self, task_count: usize) {
-        if self.task_picker_selected < task_count.saturating_sub(1) {
-            self.task_picker_selected += 1;
+    pub fn move_recent_days_down(&mut self) {
+        if self.recent_days_selected + 1 < self.recent_days.len() {
+            self.recent_days_selected += 1;
         }
     }
 
-    pub fn select_task_from_picker(&mut self) {
-        let filtered_tasks = self.get_filtered_task_names();
-
-        if filtered_tasks.is_empty() {
-            // No matches - use the typed input as-is (creating new task)
-            // input_buffer already contains the typed text
-        } else if let Some(selected_name) = filtered_tasks.get(self.task_picker_selected) {
-            // Select from filtered list
-            self.input_buffer = selected_name.clone();
+    /// Go to the highlighted day
+    pub fn confirm_recent_day(&mut self) {
+        if let Some(day) = self.recent_days.get(self.recent_days_selected) {
+            self.current_date = day.date;
+            self.date_changed = true;
         }
+        self.close_recent_days();
+    }
 
-        // Save the task name and return to Browse mode
-        if let Some(record) = self.get_selected_record() {
-            let record_id = record.id;
-            let new_name = self.input_buffer.trim().to_string();
-
-            self.save_snapshot();
-            if let Some(work_record) = self.day_data.work_records.get_mut(&record_id) {
-                work_record.name = new_name;
-            }
-        }
+    /// Ask for a name to save the selected record as a template under (`Y`)
+    pub fn open_save_template(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            self.status_message = Some("No record to save as a template".to_string());
+            return;
+        };
+        self.input_buffer = record.name.clone();
+        self.template_renaming = None;
+        self.mode = AppMode::TemplateNamePrompt;
+    }
 
+    /// Leave the name prompt: back to the picker after a rename, else to the table
+    pub fn close_template_name_prompt(&mut self) {
         self.input_buffer.clear();
-        self.mode = AppMode::Browse;
+        if self.template_renaming.take().is_some() {
+            self.mode = AppMode::TemplatePicker;
+        } else {
+            self.mode = AppMode::Browse;
+        }
     }
 
-    pub fn handle_task_picker_char(&mut self, c: char) {
+    pub fn handle_template_name_char(&mut self, c: char) {
         self.input_buffer.push(c);
-        // Reset selection when typing
-        self.task_picker_selected = 0;
     }
 
-    pub fn handle_task_picker_backspace(&mut self) {
+    pub fn handle_template_name_backspace(&mut self) {
         self.input_buffer.pop();
-        // Reset selection when deleting
-        self.task_picker_selected = 0;
     }
 
-    /// Start a new timer with the current selected task
-    pub fn start_timer_for_selected(
+    /// Save the selected record as a template, or rename the one being renamed
+    ///
+    /// Saving under a name already taken replaces that template; renaming onto
+    /// another template's name is refused.
+    pub fn confirm_template_name(
         &mut self,
         storage: &crate::storage::StorageManager,
     ) -> Result<(), String> {
-        if let Some(record) = self.get_selected_record() {
-            match storage.start_timer(
-                record.name.clone(),
-                Some(record.description.clone()),
-                Some(record.id),
-                Some(self.current_date),
-            ) {
-                Ok(timer) => {
-                    self.active_timer = Some(timer);
-                    Ok(())
-                }
-                Err(e) => Err(e.to_string()),
-            }
-        } else {
-            Err("No record selected".to_string())
+        let name = self.input_buffer.trim().to_string();
+        if name.is_empty() {
+            return Err("Template name can't be empty".to_string());
         }
-    }
+        let mut templates = storage.load_templates().map_err(|e| e.to_string())?;
 
-    /// Stop the active timer and convert to work record
-    pub fn stop_active_timer(
-        &mut self,
-        storage: &mut crate::storage::StorageManager,
-    ) -> Result<(), String> {
-        if self.active_timer.is_some() {
-            match storage.stop_timer() {
-                Ok(_work_record) => {
-                    self.active_timer = None;
-                    // Reload day data to reflect the new work record
-                    match storage.load_with_tracking(self.current_date) {
-                        Ok(new_day_data) => {
-                            self.day_data = new_day_data;
-                            self.selected_index = 0;
-                            Ok(())
-                        }
-                        Err(e) => Err(format!("Failed to reload day data: {}", e)),
-                    }
-                }
-                Err(e) => Err(e.to_string()),
+        let message = if let Some(old) = self.template_renaming.clone() {
+            if name != old && templates.iter().any(|template| template.name == name) {
+                return Err(format!("A template named '{}' already exists", name));
             }
+            if let Some(template) = templates.iter_mut().find(|template| template.name == old) {
+                template.name = name.clone();
+            }
+            format!("Renamed template '{}' to '{}'", old, name)
         } else {
-            Err("No active timer".to_string())
-        }
-    }
-
-    /// Pause the active timer
-    pub fn pause_active_timer(
-        &mut self,
-        storage: &crate::storage::StorageManager,
-    ) -> Result<(), String> {
-        if self.active_timer.is_some() {
-            match storage.pause_timer() {
-                Ok(paused_timer) => {
-                    self.active_timer = Some(paused_timer);
-                    Ok(())
+            let Some(record) = self.get_selected_record() else {
+                return Err("No record selected".to_string());
+            };
+            let template = RecordTemplate::from_record(&name, record);
+            match templates.iter_mut().find(|template| template.name == name) {
+                Some(existing) => {
+                    *existing = template;
+                    format!("Updated template '{}'", name)
+                }
+                None => {
+                    templates.push(template);
+                    format!("Saved template '{}'", name)
                 }
-                Err(e) => Err(e.to_string()),
             }
+        };
+
+        storage
+            .save_templates(&templates)
+            .map_err(|e| e.to_string())?;
+        self.templates = templates;
+        self.status_message = Some(message);
+        self.close_template_name_prompt();
+        if matches!(self.mode, AppMode::TemplatePicker) {
+            self.template_selected = self
+                .filtered_templates()
+                .iter()
+                .position(|template| template.name == name)
+                .unwrap_or(0);
         } else {
-            Err("No active timer".to_string())
+            self.templates.clear();
         }
+        Ok(())
     }
 
-    /// Resume a paused timer
-    pub fn resume_active_timer(
+    /// List the saved templates to insert one (`I`)
+    pub fn open_template_picker(
         &mut self,
         storage: &crate::storage::StorageManager,
     ) -> Result<(), String> {
-        if self.active_timer.is_some() {
-            match storage.resume_timer() {
-                Ok(resumed_timer) => {
-                    self.active_timer = Some(resumed_timer);
-                    Ok(())
-                }
-                Err(e) => Err(e.to_string()),
-            }
-        } else {
-            Err("No active timer".to_string())
+        let templates = storage.load_templates().map_err(|e| e.to_string())?;
+        if templates.is_empty() {
+            self.status_message = Some("No templates yet; save a record as one with Y".to_string());
+            return Ok(());
         }
+        self.templates = templates;
+        self.template_selected = 0;
+        self.input_buffer.clear();
+        self.mode = AppMode::TemplatePicker;
+        Ok(())
     }
 
-    /// Get current status of active timer or None if no timer running
-    pub fn get_timer_status(&self) -> Option<&TimerState> {
-        self.active_timer.as_ref()
+    pub fn close_template_picker(&mut self) {
+        self.templates.clear();
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
     }
 
-    /// Check if the data file has been modified externally and reload if needed
-    /// Returns true if the file was reloaded
-    pub fn check_and_reload_if_modified(
-        &mut self,
-        storage: &mut crate::storage::StorageManager,
-    ) -> bool {
-        let mut changed = false;
-
-        // Check if day data file has been modified
-        if let Ok(Some(new_data)) = storage.check_and_reload(self.current_date) {
-            self.day_data = new_data;
-            self.last_file_modified = storage.get_last_modified(&self.current_date);
+    /// Templates matching the typed filter, best match first
+    pub fn filtered_templates(&self) -> Vec<&RecordTemplate> {
+        use fuzzy_matcher::FuzzyMatcher;
+        use fuzzy_matcher::skim::SkimMatcherV2;
 
-            // Adjust selected_index if it's now out of bounds
-            let record_count = self.day_data.work_records.len();
-            if self.selected_index >= record_count && record_count > 0 {
-                self.selected_index = record_count - 1;
+        let query = self.input_buffer.as_str();
+        if query.is_empty() {
+            return self.templates.iter().collect();
+        }
+        let matcher = SkimMatcherV2::default();
+        let mut matches: Vec<(i64, &RecordTemplate)> = self
+            .templates
+            .iter()
+            .filter_map(|template| {
+                let text = format!("{} {}", template.name, template.task);
+                matcher
+                    .fuzzy_match(&text, query)
+                    .map(|score| (score, template))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, template)| template).collect()
+    }
+
+    fn selected_template(&self) -> Option<&RecordTemplate> {
+        self.filtered_templates()
+            .get(self.template_selected)
+            .copied()
+    }
+
+    pub fn handle_template_picker_char(&mut self, c: char) {
+        self.input_buffer.push(c);
+        self.template_selected = 0;
+    }
+
+    pub fn handle_template_picker_backspace(&mut self) {
+        self.input_buffer.pop();
+        self.template_selected = 0;
+    }
+
+    pub fn move_template_picker_up(&mut self) {
+        self.template_selected = self.template_selected.saturating_sub(1);
+    }
+
+    pub fn move_template_picker_down(&mut self) {
+        if self.template_selected + 1 < self.filtered_templates().len() {
+            self.template_selected += 1;
+        }
+    }
+
+    /// Add a record from the highlighted template (one undo step)
+    ///
+    /// It starts where `n` would put a new record, after the selected one by
+    /// default, and lasts the template's duration.
+    pub fn insert_selected_template(&mut self) {
+        let Some(template) = self.selected_template().cloned() else {
+            return;
+        };
+        let start = self
+            .new_record_start()
+            .unwrap_or_else(|| TimePoint::new(9, 0).unwrap());
+
+        self.save_snapshot();
+        let id = self.day_data.next_id();
+        self.day_data.add_record(template.instantiate(id, start));
+        self.selected_index = self
+            .day_data
+            .get_sorted_records()
+            .iter()
+            .position(|record| record.id == id)
+            .unwrap_or(0);
+        self.close_template_picker();
+        self.status_message = Some(format!("Inserted template '{}'", template.name));
+    }
+
+    /// Rename the highlighted template in the name prompt
+    pub fn rename_selected_template(&mut self) {
+        let Some(name) = self
+            .selected_template()
+            .map(|template| template.name.clone())
+        else {
+            return;
+        };
+        self.input_buffer = name.clone();
+        self.template_renaming = Some(name);
+        self.mode = AppMode::TemplateNamePrompt;
+    }
+
+    /// Delete the highlighted template; the picker closes once none are left
+    pub fn delete_selected_template(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let Some(name) = self
+            .selected_template()
+            .map(|template| template.name.clone())
+        else {
+            return Ok(());
+        };
+        let mut templates = storage.load_templates().map_err(|e| e.to_string())?;
+        templates.retain(|template| template.name != name);
+        storage
+            .save_templates(&templates)
+            .map_err(|e| e.to_string())?;
+        self.templates = templates;
+        self.status_message = Some(format!("Deleted template '{}'", name));
+        if self.templates.is_empty() {
+            self.close_template_picker();
+        } else {
+            let count = self.filtered_templates().len();
+            self.template_selected = self.template_selected.min(count.saturating_sub(1));
+        }
+        Ok(())
+    }
+
+    pub fn open_ticket_in_browser(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        match self.ticket_url_for(record) {
+            Ok(url) => self.open_url(&url),
+            Err(e) => self.last_error_message = Some(e),
+        }
+    }
+
+    /// Whether a tracker is set up; cached until the integrations config changes
+    pub fn has_integrations(&self) -> bool {
+        self.ticket_cache.has_integrations(&self.config)
+    }
+
+    /// Ticket in the record's name and the tracker it routes to, when integrations are set up
+    pub fn resolved_ticket(
+        &self,
+        record: &WorkRecord,
+    ) -> Option<crate::integrations::ResolvedTicket> {
+        if !self.has_integrations() {
+            return None;
+        }
+        self.ticket_cache
+            .resolve(record.id, &record.name, &self.config)
+    }
+
+    /// URL opened by `T`: the record's own link, else the URL of its detected ticket
+    pub fn ticket_url_for(&self, record: &WorkRecord) -> Result<String, String> {
+        use crate::integrations::{extract_ticket_from_name, ticket_url};
+
+        if let Some(url) = &record.url {
+            return Ok(url.clone());
+        }
+
+        let ticket_id = extract_ticket_from_name(&record.name)
+            .ok_or_else(|| "No ticket found in task name".to_string())?;
+        ticket_url(&ticket_id, &self.config, false).map_err(|e| e.to_string())
+    }
+
+    /// Open a URL per `[integrations] open_mode`, showing it when it can't be opened
+    pub fn open_url(&mut self, url: &str) {
+        use crate::integrations::{
+            OpenOutcome, copy_to_clipboard, open_url_in_browser, open_with_fallback,
+        };
+
+        let mode = self.config.integrations.open_mode;
+        self.url_notice =
+            match open_with_fallback(url, mode, open_url_in_browser, copy_to_clipboard) {
+                OpenOutcome::Opened => None,
+                OpenOutcome::Copied => Some(UrlNotice {
+                    message: "Copied to clipboard".to_string(),
+                    url: url.to_string(),
+                }),
+                OpenOutcome::Shown { reason } => Some(UrlNotice {
+                    message: reason.unwrap_or_else(|| "Select the URL to copy it".to_string()),
+                    url: url.to_string(),
+                }),
+            };
+    }
+
+    /// Whether `T` has something to open without an integration configured
+    pub fn selected_record_has_url(&self) -> bool {
+        self.get_selected_record()
+            .is_some_and(|record| record.url.is_some())
+    }
+
+    /// Open the link prompt, prefilled with the selected record's current link
+    pub fn open_link_prompt(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        self.input_buffer = record.url.clone().unwrap_or_default();
+        self.mode = AppMode::LinkPrompt;
+    }
+
+    pub fn close_link_prompt(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn handle_link_prompt_char(&mut self, c: char) {
+        if !c.is_whitespace() {
+            self.input_buffer.push(c);
+        }
+    }
+
+    pub fn handle_link_prompt_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Save the typed link on the selected record (an empty prompt removes it)
+    pub fn confirm_link_prompt(&mut self) {
+        let input = self.input_buffer.trim().to_string();
+        self.close_link_prompt();
+
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return;
+        };
+        let url = (!input.is_empty()).then_some(input);
+        if self.day_data.work_records.get(&id).map(|r| &r.url) == Some(&url) {
+            return;
+        }
+
+        self.save_snapshot();
+        if let Some(record) = self.day_data.work_records.get_mut(&id) {
+            record.url = url;
+        }
+    }
+
+    /// Open the prompt for the note on the selected record's task group
+    pub fn open_annotation_prompt(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        self.input_buffer = self
+            .day_data
+            .annotation(&record.name)
+            .unwrap_or_default()
+            .to_string();
+        self.mode = AppMode::AnnotationPrompt;
+    }
+
+    pub fn close_annotation_prompt(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn handle_annotation_prompt_char(&mut self, c: char) {
+        self.input_buffer.push(c);
+    }
+
+    pub fn handle_annotation_prompt_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Pin the typed note to the selected record's task (an empty prompt removes it)
+    pub fn confirm_annotation_prompt(&mut self) {
+        let note = self.input_buffer.trim().to_string();
+        self.close_annotation_prompt();
+
+        let Some(task) = self.get_selected_record().map(|record| record.name.clone()) else {
+            return;
+        };
+        if self.day_data.annotation(&task).unwrap_or_default() == note {
+            return;
+        }
+
+        self.save_snapshot();
+        self.day_data.set_annotation(&task, &note);
+    }
+
+    /// Open the estimate prompt, prefilled with the selected record's estimate in minutes
+    pub fn open_estimate_prompt(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        self.input_buffer = record
+            .estimate_minutes
+            .map(|minutes| minutes.to_string())
+            .unwrap_or_default();
+        self.mode = AppMode::EstimatePrompt;
+    }
+
+    pub fn close_estimate_prompt(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn handle_estimate_prompt_char(&mut self, c: char) {
+        if c.is_ascii_digit() || c == ':' {
+            self.input_buffer.push(c);
+        }
+    }
+
+    pub fn handle_estimate_prompt_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Save the typed estimate ("90" minutes or "1:30") on the selected record; empty removes it
+    pub fn confirm_estimate_prompt(&mut self) {
+        let input = self.input_buffer.trim().to_string();
+        self.close_estimate_prompt();
+
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return;
+        };
+        let estimate = match parse_estimate(&input) {
+            Ok(estimate) => estimate,
+            Err(e) => {
+                self.last_error_message = Some(e);
+                return;
             }
+        };
+        if self
+            .day_data
+            .work_records
+            .get(&id)
+            .map(|r| r.estimate_minutes)
+            == Some(estimate)
+        {
+            return;
+        }
 
-            changed = true;
+        self.save_snapshot();
+        if let Some(record) = self.day_data.work_records.get_mut(&id) {
+            record.estimate_minutes = estimate;
+        }
+    }
+
+    /// Open the adjustment prompt, prefilled with the selected record's signed adjustment
+    pub fn open_adjustment_prompt(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        self.input_buffer = record
+            .adjustment_minutes
+            .filter(|&minutes| minutes != 0)
+            .map(|minutes| format!("{:+}", minutes))
+            .unwrap_or_default();
+        self.mode = AppMode::AdjustmentPrompt;
+    }
+
+    pub fn close_adjustment_prompt(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn handle_adjustment_prompt_char(&mut self, c: char) {
+        if c.is_ascii_digit() || matches!(c, ':' | '+' | '-' | 'h' | 'm' | ' ') {
+            self.input_buffer.push(c);
         }
+    }
 
-        // Check if active timer has been modified externally (e.g., started/stopped from CLI)
-        if let Ok(Some(timer)) = storage.load_active_timer() {
-            // Timer exists - update if different from current state
-            if self.active_timer.is_none() || self.active_timer.as_ref() != Some(&timer) {
-                self.active_timer = Some(timer);
-                changed = true;
+    pub fn handle_adjustment_prompt_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Save the typed adjustment ("-30" or "+1:30") on the selected record; empty removes it
+    pub fn confirm_adjustment_prompt(&mut self) {
+        let input = self.input_buffer.trim().to_string();
+        self.close_adjustment_prompt();
+
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return;
+        };
+        let adjustment = match parse_adjustment(&input) {
+            Ok(adjustment) => adjustment,
+            Err(e) => {
+                self.last_error_message = Some(e);
+                return;
             }
-        } else if self.active_timer.is_some() {
-            // Timer was cleared externally
-            self.active_timer = None;
-            changed = true;
+        };
+        if self
+            .day_data
+            .work_records
+            .get(&id)
+            .map(|r| r.adjustment_minutes.filter(|&minutes| minutes != 0))
+            == Some(adjustment)
+        {
+            return;
         }
 
-        changed
+        self.save_snapshot();
+        if let Some(record) = self.day_data.work_records.get_mut(&id) {
+            record.adjustment_minutes = adjustment;
+        }
     }
-}
 
-/// Open a URL in the default browser using platform-specific commands.
-///
-/// On Windows, special care is taken to handle URLs with query parameters
-/// containing `&` characters. The `start` command requires an empty string
-/// as the window title argument before the URL, otherwise `&` is interpreted
-/// as a command separator by cmd.exe.
-fn open_url_in_browser(url: &str) -> std::io::Result<()> {
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open").arg(url).spawn()?;
-    }
-
-    #[cfg(target_os = "windows")]
-    {
-        // Windows cmd.exe treats & as a command separator.
-        // We use raw_arg to pass the complete command string with proper quoting.
-        // Format: cmd /C start "" "url" - empty quotes for title, quoted URL.
-        use std::os::windows::process::CommandExt;
-        std::process::Command::new("cmd")
-            .raw_arg(format!("/C start \"\" \"{}\"", url))
-            .spawn()?;
-    }
-
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    {
-        // Linux/Unix
-        std::process::Command::new("xdg-open").arg(url).spawn()?;
-    }
-
-    Ok(())
-}
+    /// Ask before removing every record on the viewed day
+    pub fn open_clear_day(&mut self) {
+        if self.day_data.work_records.is_empty() {
+            self.last_error_message = Some("No records to clear on this day".to_string());
+            return;
+        }
+        self.mode = AppMode::ConfirmClear;
+    }
+
+    pub fn close_clear_day(&mut self) {
+        self.mode = AppMode::Browse;
+    }
+
+    /// Remove every record on the viewed day; `u` brings them back
+    ///
+    /// The running session is left alone, even when it is linked to one of them.
+    pub fn confirm_clear_day(&mut self) {
+        self.save_snapshot();
+        let count = self.day_data.work_records.len();
+        self.day_data.clear_records();
+        self.selected_index = 0;
+        self.mode = AppMode::Browse;
+        self.status_message = Some(format!(
+            "Cleared {} record(s) on {}, press u to undo",
+            count, self.current_date
+        ));
+    }
+
+    /// Open the custom field editor for the selected record
+    pub fn open_fields_editor(&mut self) {
+        if self.config.fields.is_empty() {
+            self.last_error_message = Some("No custom fields declared in [fields]".to_string());
+            return;
+        }
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        self.field_drafts = self
+            .config
+            .fields
+            .keys()
+            .map(|name| {
+                let value = record.extra.get(name).cloned().unwrap_or_default();
+                (name.clone(), value)
+            })
+            .collect();
+        self.field_selected = 0;
+        self.mode = AppMode::Fields;
+    }
+
+    pub fn close_fields_editor(&mut self) {
+        self.field_drafts.clear();
+        self.field_selected = 0;
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn move_field_selection_up(&mut self) {
+        self.field_selected = self.field_selected.saturating_sub(1);
+    }
+
+    pub fn move_field_selection_down(&mut self) {
+        if self.field_selected + 1 < self.field_drafts.len() {
+            self.field_selected += 1;
+        }
+    }
+
+    fn selected_field_def(&self) -> Option<&crate::config::FieldDef> {
+        let (name, _) = self.field_drafts.get(self.field_selected)?;
+        self.config.fields.get(name)
+    }
+
+    /// Type into the selected field; choice fields are changed with `cycle_field_choice`
+    pub fn handle_fields_char(&mut self, c: char) {
+        if !matches!(
+            self.selected_field_def(),
+            Some(crate::config::FieldDef::Text)
+        ) {
+            return;
+        }
+        if let Some((_, value)) = self.field_drafts.get_mut(self.field_selected) {
+            value.push(c);
+        }
+    }
+
+    pub fn handle_fields_backspace(&mut self) {
+        if !matches!(
+            self.selected_field_def(),
+            Some(crate::config::FieldDef::Text)
+        ) {
+            return;
+        }
+        if let Some((_, value)) = self.field_drafts.get_mut(self.field_selected) {
+            value.pop();
+        }
+    }
+
+    /// Step the selected choice field through its options, with "unset" between the ends
+    pub fn cycle_field_choice(&mut self, forward: bool) {
+        let Some(crate::config::FieldDef::Choice { options }) = self.selected_field_def() else {
+            return;
+        };
+        let mut values = vec![String::new()];
+        values.extend(options.iter().cloned());
+        let Some((_, value)) = self.field_drafts.get(self.field_selected) else {
+            return;
+        };
+
+        let current = values.iter().position(|v| v == value).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % values.len()
+        } else {
+            (current + values.len() - 1) % values.len()
+        };
+        let next_value = values[next].clone();
+        self.field_drafts[self.field_selected].1 = next_value;
+    }
+
+    /// Validate the drafts and store them on the selected record (one undo step)
+    ///
+    /// Empty values remove the field; keys not declared in `[fields]` are left alone.
+    pub fn confirm_fields_editor(&mut self) -> Result<(), String> {
+        for (name, value) in &self.field_drafts {
+            if let Some(def) = self.config.fields.get(name) {
+                def.check(value.trim())
+                    .map_err(|e| format!("{}: {}", name, e))?;
+            }
+        }
+
+        let drafts = std::mem::take(&mut self.field_drafts);
+        self.close_fields_editor();
 
-fn days_in_month(month: time::Month, year: i32) -> u8 {
-    use time::Month;
-    match month {
-        Month::January
-        | Month::March
-        | Month::May
-        | Month::July
-        | Month::August
-        | Month::October
-        | Month::December => 31,
-        Month::April | Month::June | Month::September | Month::November => 30,
-        Month::February => {
-            if is_leap_year(year) {
-                29
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return Ok(());
+        };
+        let Some(record) = self.day_data.work_records.get(&id) else {
+            return Ok(());
+        };
+        let mut extra = record.extra.clone();
+        for (name, value) in drafts {
+            let value = value.trim().to_string();
+            if value.is_empty() {
+                extra.remove(&name);
             } else {
-                28
+                extra.insert(name, value);
             }
         }
+        if extra == record.extra {
+            return Ok(());
+        }
+
+        self.save_snapshot();
+        if let Some(record) = self.day_data.work_records.get_mut(&id) {
+            record.extra = extra;
+        }
+        Ok(())
+    }
+
+    pub fn open_worklog_in_browser(&mut self) {
+        use crate::integrations::{extract_ticket_from_name, ticket_url};
+
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        let Some(ticket_id) = extract_ticket_from_name(&record.name) else {
+            self.last_error_message = Some("No ticket found in task name".to_string());
+            return;
+        };
+        match ticket_url(&ticket_id, &self.config, true) {
+            Ok(url) => self.open_url(&url),
+            Err(e) => self.last_error_message = Some(e.to_string()),
+        }
+    }
+
+    pub fn clear_error(&mut self) {
+        self.last_error_message = None;
+        self.status_message = None;
+    }
+
+    pub fn close_task_picker(&mut self) {
+        // Cancel and return to Browse mode
+        self.input_buffer.clear();
+        self.task_picker_create = false;
+        self.task_picker_switch = false;
+        self.mode = AppMode::Browse;
+    }
+
+    /// Open the task picker to switch the session to another task
+    ///
+    /// Nothing is stopped until a task is confirmed, so cancelling the picker leaves
+    /// the running session exactly as it was.
+    /// Ask for a note to add to the running session
+    pub fn open_session_note_prompt(&mut self) {
+        if self.active_timer.is_none() {
+            self.status_message = Some("No active session to add a note to".to_string());
+            return;
+        }
+        self.input_buffer.clear();
+        self.mode = AppMode::SessionNotePrompt;
+    }
+
+    pub fn close_session_note_prompt(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn handle_session_note_char(&mut self, c: char) {
+        self.input_buffer.push(c);
+    }
+
+    pub fn handle_session_note_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Add the typed note to the session; it lands in the record's description on stop
+    pub fn confirm_session_note(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let note = self.input_buffer.trim().to_string();
+        self.close_session_note_prompt();
+        if note.is_empty() {
+            return Ok(());
+        }
+
+        let timer = storage.add_timer_note(&note).map_err(|e| e.to_string())?;
+        self.status_message = Some(match timer.notes.len() {
+            1 => "Note added to session".to_string(),
+            count => format!("Note added to session ({} notes)", count),
+        });
+        self.active_timer = Some(timer);
+        Ok(())
     }
-}
 
-fn is_leap_year(year: i32) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+    pub fn open_switch_session(&mut self) {
+        self.input_buffer.clear();
+        self.task_picker_selected = 0;
+        self.task_picker_switch = true;
+        self.mode = AppMode::TaskPicker;
+    }
+
+    /// Name the picker resolves to: the highlighted match, or the typed text if none match
+    fn picked_task_name(&self) -> String {
+        let filtered_tasks = self.get_filtered_task_names();
+        match filtered_tasks.get(self.task_picker_selected) {
+            Some(name) => name.clone(),
+            None => self.input_buffer.trim().to_string(),
+        }
+    }
+
+    /// Stop the running session (if any) and start a new unlinked one on the picked task
+    pub fn confirm_switch_session(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let name = self.picked_task_name();
+        self.close_task_picker();
+        if name.is_empty() {
+            return Err("No task name given".to_string());
+        }
+        self.switch_session_to(name, None, storage)
+    }
+
+    /// Start the `[[quick_tasks]]` entry at `index`, stopping any other session
+    pub fn start_quick_task(
+        &mut self,
+        index: usize,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let Some(task) = self.config.quick_tasks.get(index).cloned() else {
+            return Err(format!("No quick task #{}", index + 1));
+        };
+        if self
+            .active_timer
+            .as_ref()
+            .is_some_and(|timer| timer.task_name == task.name)
+        {
+            self.status_message = Some(format!("{} is already running", task.name));
+            return Ok(());
+        }
+        self.switch_session_to(task.name.clone(), task.description, storage)?;
+        self.status_message = Some(format!("Session started: {}", task.name));
+        Ok(())
+    }
+
+    fn switch_session_to(
+        &mut self,
+        name: String,
+        description: Option<String>,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        if self.active_timer.is_none() && self.insert_gap_break() {
+            self.save_day(storage);
+        }
+        let (stopped, timer) = storage
+            .switch_timer(name, description)
+            .map_err(|e| e.to_string())?;
+        let created = timer.source_record_id.is_some();
+        self.active_timer = Some(timer);
+        self.timer_event(TimerEvent::Started);
+        if stopped.is_some() || created {
+            // The stopped session's record, or one created for the new session, may
+            // belong to the day on screen
+            self.invalidate_cached_totals();
+            let day_data = storage
+                .load_with_tracking(self.current_date)
+                .map_err(|e| format!("Failed to reload day data: {}", e))?;
+            self.set_day_from_disk(day_data);
+            self.last_file_modified = storage.get_last_modified(&self.current_date);
+            let record_count = self.day_data.work_records.len();
+            if self.selected_index >= record_count && record_count > 0 {
+                self.selected_index = record_count - 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill the gap since today's last record with a break (`[timer] fill_gaps_with_break`)
+    ///
+    /// Undoable as one step. Returns true when a break was added.
+    fn insert_gap_break(&mut self) -> bool {
+        let Some((min, max)) = self.config.timer.break_gap_bounds() else {
+            return false;
+        };
+        let now = self.clock.now();
+        if now.date() != self.day_data.date {
+            return false;
+        }
+        let Ok(now) = TimePoint::new(now.hour(), now.minute()) else {
+            return false;
+        };
+        if self.day_data.gap_before(now, min, max).is_none() {
+            return false;
+        }
+        self.save_snapshot();
+        self.day_data.fill_gap_with_break(now, min, max).is_some()
+    }
+
+    pub fn get_unique_task_names(&self) -> Vec<String> {
+        use std::collections::HashSet;
+
+        let mut seen = HashSet::new();
+        let mut task_names = Vec::new();
+
+        for record in self.day_data.work_records.values() {
+            let name = record.name.trim().to_string();
+            if !name.is_empty() && seen.insert(name.clone()) {
+                task_names.push(name);
+            }
+        }
+
+        task_names.sort();
+        task_names
+    }
+
+    pub fn get_filtered_task_names(&self) -> Vec<String> {
+        let all_tasks = self.get_unique_task_names();
+        let filter = self.input_buffer.to_lowercase();
+
+        if filter.is_empty() {
+            return all_tasks;
+        }
+
+        all_tasks
+            .into_iter()
+            .filter(|task| task.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    pub fn move_task_picker_up(&mut self) {
+        if self.task_picker_selected > 0 {
+            self.task_picker_selected -= 1;
+        }
+    }
+
+    pub fn move_task_picker_down(&mut self, task_count: usize) {
+        if self.task_picker_selected < task_count.saturating_sub(1) {
+            self.task_picker_selected += 1;
+        }
+    }
+
+    pub fn select_task_from_picker(&mut self) {
+        // The highlighted match, or the typed text as a new task name
+        self.input_buffer = self.picked_task_name();
+
+        // In create mode the chosen name goes on a new record
+        if self.task_picker_create {
+            let new_name = self.input_buffer.trim().to_string();
+            if !new_name.is_empty() {
+                self.add_new_record_named(&new_name);
+            }
+            self.close_task_picker();
+            return;
+        }
+
+        // Save the task name and return to Browse mode
+        if let Some(record) = self.get_selected_record() {
+            let record_id = record.id;
+            let new_name = self.input_buffer.trim().to_string();
+
+            self.save_snapshot();
+            // A blank pick leaves the name as it was
+            let _ = self.set_record_field(record_id, RecordField::Name, &new_name);
+        }
+
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn handle_task_picker_char(&mut self, c: char) {
+        self.input_buffer.push(c);
+        // Reset selection when typing
+        self.task_picker_selected = 0;
+    }
+
+    pub fn handle_task_picker_backspace(&mut self) {
+        self.input_buffer.pop();
+        // Reset selection when deleting
+        self.task_picker_selected = 0;
+    }
+
+    /// Switch the Start/End columns between clock times and times relative to now
+    pub fn toggle_relative_times(&mut self) {
+        self.relative_times = !self.relative_times;
+    }
+
+    /// Keep the day's records as its plan, to audit what actually happens against
+    pub fn mark_day_planned(&mut self) {
+        if self.day_data.work_records.is_empty() {
+            self.last_error_message = Some("No records to plan on this day".to_string());
+            return;
+        }
+        self.save_snapshot();
+        self.day_data.mark_planned();
+        self.status_message = Some(format!(
+            "Marked {} record(s) as the day's plan (O compares)",
+            self.day_data.planned.len()
+        ));
+    }
+
+    /// Show or hide the planned vs actual audit; needs a day marked as planned
+    pub fn toggle_plan_audit(&mut self) {
+        if !self.show_plan_audit && self.day_data.planned.is_empty() {
+            self.last_error_message =
+                Some("No plan for this day (mark it as planned with M)".to_string());
+            return;
+        }
+        self.show_plan_audit = !self.show_plan_audit;
+    }
+
+    /// The viewed day compared with its plan, while the audit is shown
+    pub fn plan_audit(&self) -> Option<PlanAudit> {
+        if !self.show_plan_audit {
+            return None;
+        }
+        self.day_data.plan_audit()
+    }
+
+    /// Collapse or expand the selected record's project in the summary panel
+    pub fn toggle_selected_project_collapsed(&mut self) {
+        let Some(record) = self.get_selected_record() else {
+            return;
+        };
+        let label = record
+            .project
+            .clone()
+            .unwrap_or_else(|| crate::models::NO_PROJECT_LABEL.to_string());
+
+        if !self.collapsed_projects.remove(&label) {
+            self.collapsed_projects.insert(label);
+        }
+    }
+
+    pub fn is_project_collapsed(&self, label: &str) -> bool {
+        self.collapsed_projects.contains(label)
+    }
+
+    /// Move focus to the summary panel to pick a row to filter the records by
+    pub fn focus_summary(&mut self) {
+        let rows = crate::ui::view::summary_lines(self).len();
+        if rows == 0 {
+            self.last_error_message = Some("No summary rows to filter by".to_string());
+            return;
+        }
+        self.summary_selected = self.summary_selected.min(rows - 1);
+        self.mode = AppMode::Summary;
+    }
+
+    pub fn close_summary(&mut self) {
+        self.mode = AppMode::Browse;
+    }
+
+    pub fn move_summary_selection_up(&mut self) {
+        self.summary_selected = self.summary_selected.saturating_sub(1);
+    }
+
+    pub fn move_summary_selection_down(&mut self) {
+        let rows = crate::ui::view::summary_lines(self).len();
+        if self.summary_selected + 1 < rows {
+            self.summary_selected += 1;
+        }
+    }
+
+    /// Show only the highlighted summary row's records and go back to the table
+    pub fn apply_summary_filter(&mut self) {
+        let filter = crate::ui::view::summary_lines(self)
+            .get(self.summary_selected)
+            .map(|line| RecordFilter::from_summary_line(line, self.config.ui.group_by));
+        self.mode = AppMode::Browse;
+        let Some(filter) = filter else {
+            return;
+        };
+        if let Some(index) = self
+            .day_data
+            .get_sorted_records()
+            .iter()
+            .position(|record| filter.matches(record))
+        {
+            self.selected_index = index;
+        }
+        self.record_filter = Some(filter);
+    }
+
+    /// Show every record again
+    pub fn clear_record_filter(&mut self) {
+        self.record_filter = None;
+    }
+
+    /// Whether the records table shows `record` under the current filter
+    pub fn is_shown(&self, record: &WorkRecord) -> bool {
+        self.record_filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(record))
+    }
+
+    /// Minutes of the records the filter shows, for the header's "(filtered)" total
+    pub fn filtered_minutes(&self) -> Option<u32> {
+        let filter = self.record_filter.as_ref()?;
+        Some(
+            self.day_data
+                .work_records
+                .values()
+                .filter(|record| filter.matches(record))
+                .map(|record| record.total_minutes)
+                .sum(),
+        )
+    }
+
+    /// Flag or unflag the selected record; with `[ui] persist_flags` the day file keeps it
+    pub fn toggle_flag(&mut self) {
+        let Some(id) = self.get_selected_record().map(|record| record.id) else {
+            return;
+        };
+        if !self.flagged.remove(&id) {
+            self.flagged.insert(id);
+        }
+        if self.config.ui.persist_flags {
+            self.day_data.flagged = self.flagged.clone();
+        }
+    }
+
+    /// Whether records without a `$` mark count as billable (`[defaults] billable`)
+    pub fn billable_default(&self) -> bool {
+        self.config.defaults.billable
+    }
+
+    /// Mark the selected record, or every record of a visual selection, billable or not
+    ///
+    /// A selection with any billable record becomes non-billable, otherwise billable.
+    pub fn toggle_billable(&mut self) {
+        let ids: Vec<u32> = if matches!(self.mode, AppMode::Visual) {
+            self.visual_selection_ids()
+        } else {
+            self.get_selected_record()
+                .map(|record| record.id)
+                .into_iter()
+                .collect()
+        };
+        if ids.is_empty() {
+            return;
+        }
+
+        self.save_snapshot();
+        let default = self.billable_default();
+        let billable = !ids
+            .iter()
+            .filter_map(|id| self.day_data.work_records.get(id))
+            .any(|record| record.is_billable(default));
+        for id in &ids {
+            if let Some(record) = self.day_data.work_records.get_mut(id) {
+                record.billable = Some(billable);
+            }
+        }
+        self.status_message = Some(format!(
+            "Marked {} record(s) {}",
+            ids.len(),
+            if billable { "billable" } else { "non-billable" }
+        ));
+        if matches!(self.mode, AppMode::Visual) {
+            self.exit_visual_mode();
+        }
+    }
+
+    pub fn is_flagged(&self, id: u32) -> bool {
+        self.flagged.contains(&id)
+    }
+
+    /// What `record` still lacks under `[validation]` (description, ticket, duration)
+    pub fn record_issues(&self, record: &WorkRecord) -> Vec<crate::validation::RecordIssue> {
+        crate::validation::record_issues(record, self.config.required_fields())
+    }
+
+    /// Select the next (or previous) flagged record, wrapping around the day
+    pub fn jump_to_flag(&mut self, forward: bool) {
+        let flagged: Vec<usize> = self
+            .day_data
+            .get_sorted_records()
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| self.is_flagged(record.id))
+            .map(|(index, _)| index)
+            .collect();
+        let current = self.selected_index;
+        let target = match forward {
+            true => flagged
+                .iter()
+                .find(|&&index| index > current)
+                .or(flagged.first()),
+            false => flagged
+                .iter()
+                .rev()
+                .find(|&&index| index < current)
+                .or(flagged.last()),
+        };
+        match target {
+            Some(&index) => self.selected_index = index,
+            None => {
+                self.last_error_message =
+                    Some("No flagged records on this day (flag one with m)".to_string())
+            }
+        }
+    }
+
+    /// Select the day's longest record, or its shortest; nothing on an empty day
+    pub fn jump_to_longest(&mut self, longest: bool) {
+        let id = match longest {
+            true => self.day_data.longest_record(),
+            false => self.day_data.shortest_record(),
+        };
+        if let Some(index) = id.and_then(|id| self.index_of_record_id(id)) {
+            self.selected_index = index;
+        }
+    }
+
+    /// Open the prompt for jumping to a record by ID
+    pub fn open_goto_id(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::GotoId;
+    }
+
+    pub fn close_goto_id(&mut self) {
+        self.input_buffer.clear();
+        self.mode = AppMode::Browse;
+    }
+
+    /// Only digits are accepted in the ID prompt
+    pub fn handle_goto_id_char(&mut self, c: char) {
+        if c.is_ascii_digit() {
+            self.input_buffer.push(c);
+        }
+    }
+
+    pub fn handle_goto_id_backspace(&mut self) {
+        self.input_buffer.pop();
+    }
+
+    /// Position of the record with `id` in the sorted (displayed) list
+    pub fn index_of_record_id(&self, id: u32) -> Option<usize> {
+        self.day_data
+            .get_sorted_records()
+            .iter()
+            .position(|record| record.id == id)
+    }
+
+    /// Select the record whose ID was typed into the prompt
+    pub fn confirm_goto_id(&mut self) -> Result<(), String> {
+        let input = self.input_buffer.trim().to_string();
+        self.close_goto_id();
+
+        let id: u32 = input
+            .parse()
+            .map_err(|_| format!("Invalid record ID: '{}'", input))?;
+        match self.index_of_record_id(id) {
+            Some(index) => {
+                self.selected_index = index;
+                Ok(())
+            }
+            None => Err(format!("No record with ID {} on {}", id, self.current_date)),
+        }
+    }
+
+    /// The day the active timer belongs to
+    ///
+    /// That's its record's day when it has one, else the day it was started, so a
+    /// session running past midnight still belongs to the day it began.
+    pub fn timer_day(&self) -> Option<Date> {
+        let timer = self.active_timer.as_ref()?;
+        Some(timer.source_record_date.unwrap_or(timer.date))
+    }
+
+    /// Whether the active timer belongs to a day other than the one on screen
+    pub fn timer_on_other_day(&self) -> bool {
+        self.timer_day()
+            .is_some_and(|date| date != self.current_date)
+    }
+
+    /// Refuse timer start/stop while viewing a day the running session doesn't belong to
+    pub fn check_timer_day(&self) -> Result<(), String> {
+        match self.timer_day() {
+            Some(date) if date != self.current_date => Err(format!(
+                "Session belongs to {}, press g a to go there",
+                date
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The active timer's record as (date, id), if the timer is attached to one
+    pub fn timer_source_record(&self) -> Option<(Date, u32)> {
+        let date = self.timer_day()?;
+        let id = self.active_timer.as_ref()?.source_record_id?;
+        Some((date, id))
+    }
+
+    /// Position of the active timer's record in the displayed list, if it's on this day
+    pub fn timer_source_index(&self) -> Option<usize> {
+        let (date, id) = self.timer_source_record()?;
+        if date != self.current_date {
+            return None;
+        }
+        self.index_of_record_id(id)
+    }
+
+    /// Select the record the active timer is attached to, switching day if needed
+    pub fn goto_timer_record(&mut self) -> Result<(), String> {
+        if matches!(self.mode, AppMode::GotoId) {
+            self.close_goto_id();
+        }
+        if self.active_timer.is_none() {
+            return Err("No session is running".to_string());
+        }
+        let (date, id) = self
+            .timer_source_record()
+            .ok_or("The running session isn't attached to a record")?;
+
+        if date != self.current_date {
+            self.current_date = date;
+            self.pending_record_id = Some(id);
+            self.date_changed = true;
+            return Ok(());
+        }
+        match self.timer_source_index() {
+            Some(index) => {
+                self.selected_index = index;
+                Ok(())
+            }
+            None => Err(format!("No record with ID {} on {}", id, self.current_date)),
+        }
+    }
+
+    /// Start a new timer with the current selected task
+    pub fn start_timer_for_selected(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        if let Some(record) = self.get_selected_record() {
+            // Same check as `session start`, instead of timing a blank task
+            if record.name.trim().is_empty() {
+                return Err("Task name cannot be empty".to_string());
+            }
+            match storage.start_timer(
+                record.name.clone(),
+                Some(record.description.clone()),
+                Some(record.id),
+                Some(self.current_date),
+            ) {
+                Ok(timer) => {
+                    self.active_timer = Some(timer);
+                    self.timer_event(TimerEvent::Started);
+                    Ok(())
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        } else {
+            Err("No record selected".to_string())
+        }
+    }
+
+    /// Start a timer on the selected record, backdated to the record's end
+    ///
+    /// For having kept working without starting the timer: stopping it extends the
+    /// record over the whole gap.
+    pub fn backfill_timer_for_selected(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let Some(record) = self.get_selected_record() else {
+            return Err("No record selected".to_string());
+        };
+        let timer = storage
+            .backfill_timer(record, self.current_date)
+            .map_err(|e| e.to_string())?;
+        self.status_message = Some(format!(
+            "Session started on {}, counting from {}",
+            timer.task_name, record.end
+        ));
+        self.active_timer = Some(timer);
+        self.timer_event(TimerEvent::Started);
+        Ok(())
+    }
+
+    /// Start a timer for the task of today's most recently ended record
+    ///
+    /// Ignores the selection, so coming back from a break is one key press.
+    pub fn start_timer_for_last_task(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        if self.current_date != self.clock.today() {
+            return Err("Go to today to resume the last task".to_string());
+        }
+        let Some(record) = self.day_data.most_recent_record() else {
+            return Err("No task worked on today".to_string());
+        };
+        let (name, description, id) = (record.name.clone(), record.description.clone(), record.id);
+
+        match storage.start_timer(name, Some(description), Some(id), Some(self.current_date)) {
+            Ok(timer) => {
+                self.active_timer = Some(timer);
+                self.timer_event(TimerEvent::Started);
+                if let Some(index) = self.index_of_record_id(id) {
+                    self.selected_index = index;
+                }
+                Ok(())
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Stop the active timer and convert to work record
+    pub fn stop_active_timer(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        if self.active_timer.is_some() {
+            // The end the timer is about to move, to offer it in Revert field
+            let source_end = self
+                .timer_source_record()
+                .and_then(|(_, id)| self.day_data.work_records.get(&id))
+                .map(|record| (record.id, record.end.to_string()));
+            match storage.stop_timer() {
+                Ok(stopped) => {
+                    self.active_timer = None;
+                    self.lock_pause.release();
+                    self.timer_event(TimerEvent::Stopped);
+                    // The record may have been written to another day
+                    self.invalidate_cached_totals();
+                    let mut summary = format!("Session stopped: {}", stopped.summary());
+                    if stopped.date != self.current_date {
+                        summary.push_str(&format!(" on {}", stopped.date));
+                    }
+                    if let Some(worklog) = crate::integrations::worklog::auto_worklog(
+                        &stopped,
+                        &self.config,
+                        &crate::integrations::worklog::CurlClient,
+                    ) {
+                        summary.push_str(&format!("; {}", worklog));
+                    }
+                    self.status_message = Some(summary);
+                    // Reload day data to reflect the new work record
+                    match storage.load_with_tracking(self.current_date) {
+                        Ok(new_day_data) => {
+                            self.set_day_from_disk(new_day_data);
+                            if stopped.date == self.current_date {
+                                self.select_stopped_record(stopped.record.id);
+                            }
+                            if let Some((id, old)) = source_end {
+                                let new = self.day_data.work_records.get(&id);
+                                if let Some(new) = new.map(|record| record.end.to_string()) {
+                                    self.note_field_change(id, RecordField::End, old, new);
+                                }
+                            }
+                            Ok(())
+                        }
+                        Err(e) => Err(format!("Failed to reload day data: {}", e)),
+                    }
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        } else {
+            Err("No active timer".to_string())
+        }
+    }
+
+    /// Select the record a stopped session was written to and flash it for a few redraws
+    fn select_stopped_record(&mut self, id: u32) {
+        self.selected_index = self.index_of_record_id(id).unwrap_or(0);
+        self.stopped_flash = Some((id, self.frame + STOPPED_FLASH_FRAMES));
+    }
+
+    /// Whether `id` is the record a just-stopped session was written to
+    pub fn is_flashing(&self, id: u32) -> bool {
+        self.stopped_flash
+            .is_some_and(|(flashed, until)| flashed == id && self.frame < until)
+    }
+
+    /// Auto-stop a timer past its `[timer] auto_stop_at` deadline, if one is set
+    ///
+    /// The record ends at the configured time rather than now, and the footer says
+    /// so. Runs on every tick, including the first one after startup. Returns true
+    /// when a timer was stopped.
+    pub fn auto_stop_overdue_timer(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<bool, String> {
+        let Some(cutoff) = self.config.timer.auto_stop_cutoff() else {
+            return Ok(false);
+        };
+        if self.active_timer.is_none() {
+            return Ok(false);
+        }
+
+        let restart = self.config.timer.restart_after_auto_stop;
+        match storage.auto_stop_timer(cutoff, restart, self.clock.now()) {
+            Ok(Some(record)) => {
+                self.timer_event(TimerEvent::AutoStopped);
+                self.reload_after_timer_write(storage)?;
+                self.status_message = Some(format!(
+                    "⏹ Session '{}' auto-stopped at {} ([timer] auto_stop_at)",
+                    record.name, record.end
+                ));
+                Ok(true)
+            }
+            Ok(None) => Ok(false),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Pick up a timer and records written behind the app's back by the timer manager
+    fn reload_after_timer_write(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        self.invalidate_cached_totals();
+        self.active_timer = storage.load_active_timer().map_err(|e| e.to_string())?;
+        // The record may belong to the day on screen
+        match storage.load_with_tracking(self.current_date) {
+            Ok(new_day_data) => {
+                self.set_day_from_disk(new_day_data);
+                self.last_file_modified = storage.get_last_modified(&self.current_date);
+                let record_count = self.day_data.work_records.len();
+                if self.selected_index >= record_count && record_count > 0 {
+                    self.selected_index = record_count - 1;
+                }
+                Ok(())
+            }
+            Err(e) => Err(format!("Failed to reload day data: {}", e)),
+        }
+    }
+
+    /// Act on the local date having changed since the last tick (`[timer] on_midnight`)
+    ///
+    /// `follow` moves the view from yesterday to today; `split` also ends a running
+    /// session at midnight and continues it on the new day. Returns true when the
+    /// date rolled over.
+    pub fn handle_midnight(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+    ) -> Result<bool, String> {
+        use crate::config::MidnightRollover;
+
+        let today = self.clock.today();
+        let Some(previous) = self.seen_day.replace(today) else {
+            return Ok(false);
+        };
+        // A clock set back isn't a new day
+        if today <= previous {
+            return Ok(false);
+        }
+        let rollover = self.config.timer.on_midnight;
+        if rollover == MidnightRollover::Stay {
+            return Ok(true);
+        }
+
+        let mut split = false;
+        if rollover == MidnightRollover::Split && self.active_timer.is_some() {
+            if self.is_dirty() {
+                self.save_day(storage);
+            }
+            // A session several days old is split once per midnight
+            while storage
+                .split_timer_at_midnight(self.clock.now())
+                .map_err(|e| format!("Failed to split the session: {}", e))?
+                .is_some()
+            {
+                split = true;
+            }
+            if split {
+                self.reload_after_timer_write(storage)?;
+            }
+        }
+
+        if self.current_date == previous {
+            self.current_date = today;
+            self.date_changed = true;
+        }
+        self.status_message = Some(if split {
+            format!("Session split at midnight, continuing on {}", today)
+        } else {
+            format!("New day: {}", today)
+        });
+        Ok(true)
+    }
+
+    /// Pause the active timer
+    pub fn pause_active_timer(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        self.pause_timer(storage, TimerEvent::Paused, None)
+    }
+
+    /// Pause the timer now, or as of `at`
+    fn pause_timer(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+        event: TimerEvent,
+        at: Option<OffsetDateTime>,
+    ) -> Result<(), String> {
+        if self.active_timer.is_some() {
+            let paused = match at {
+                Some(at) => storage.pause_timer_at(at),
+                None => storage.pause_timer(),
+            };
+            match paused {
+                Ok(paused_timer) => {
+                    self.active_timer = Some(paused_timer);
+                    self.timer_event(event);
+                    Ok(())
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        } else {
+            Err("No active timer".to_string())
+        }
+    }
+
+    /// Resume a paused timer
+    pub fn resume_active_timer(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        self.resume_timer(storage, TimerEvent::Resumed)
+    }
+
+    fn resume_timer(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+        event: TimerEvent,
+    ) -> Result<(), String> {
+        if self.active_timer.is_some() {
+            match storage.resume_timer() {
+                Ok(resumed_timer) => {
+                    self.active_timer = Some(resumed_timer);
+                    self.lock_pause.release();
+                    self.timer_event(event);
+                    Ok(())
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        } else {
+            Err("No active timer".to_string())
+        }
+    }
+
+    /// Pause or resume the timer for lock and sleep events received since the last tick
+    pub fn poll_lock_events(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let events: Vec<(LockEvent, OffsetDateTime)> = match &self.lock_events {
+            Some(receiver) => receiver.try_iter().collect(),
+            None => return Ok(()),
+        };
+        for (event, at) in events {
+            self.handle_lock_event(event, at, storage)?;
+        }
+        Ok(())
+    }
+
+    /// Apply one lock or sleep event, received at `at`, to the active timer
+    ///
+    /// A pause starts at `at`, so time spent asleep before the event was handled
+    /// isn't counted as worked.
+    pub fn handle_lock_event(
+        &mut self,
+        event: LockEvent,
+        at: OffsetDateTime,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let status = self.active_timer.as_ref().map(|timer| timer.status);
+        match self.lock_pause.handle(event, status) {
+            Some(LockAction::Pause) => {
+                self.pause_timer(storage, TimerEvent::Away, Some(at))?;
+                self.status_message = Some("Timer paused while away".to_string());
+            }
+            Some(LockAction::Resume) => {
+                self.resume_timer(storage, TimerEvent::Away)?;
+                self.status_message = Some("Welcome back, timer resumed".to_string());
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// The timer is paused only because the session is locked or asleep
+    pub fn paused_while_away(&self) -> bool {
+        self.lock_pause.paused_timer()
+            && self
+                .active_timer
+                .as_ref()
+                .is_some_and(|timer| timer.status == TimerStatus::Paused)
+    }
+
+    /// Get current status of active timer or None if no timer running
+    pub fn get_timer_status(&self) -> Option<&TimerState> {
+        self.active_timer.as_ref()
+    }
+
+    /// Check if the data file has been modified externally and reload if needed
+    /// Returns true if the file was reloaded
+    pub fn check_and_reload_if_modified(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+    ) -> bool {
+        let mut changed = false;
+        let old_timer = self.active_timer.clone();
+        let mut reloaded_day = None;
+        let mut merged = false;
+
+        // Unsaved edits are merged with the disk, which needs the Browse screen for
+        // conflicts; a change made meanwhile is picked up once back there
+        let dirty = self.is_dirty();
+        let can_reload = !dirty || matches!(self.mode, AppMode::Browse);
+
+        // Check if day data file has been modified
+        if let Some(new_data) = can_reload
+            .then(|| storage.check_and_reload(self.current_date))
+            .and_then(|result| result.ok().flatten())
+        {
+            if dirty {
+                self.merge_from_disk(new_data, storage);
+                merged = true;
+            } else {
+                let old_day = std::mem::replace(&mut self.saved_day, new_data.clone());
+                reloaded_day = Some(old_day);
+                self.day_data = new_data;
+                self.day_data
+                    .apply_offset_changes(self.config.time.active_offset_changes());
+                self.last_file_modified = storage.get_last_modified(&self.current_date);
+            }
+            self.invalidate_cached_totals();
+
+            // Adjust selected_index if it's now out of bounds
+            let record_count = self.day_data.work_records.len();
+            if self.selected_index >= record_count && record_count > 0 {
+                self.selected_index = record_count - 1;
+            }
+
+            changed = true;
+        }
+
+        // Check if active timer has been modified externally (e.g., started/stopped from CLI)
+        if let Ok(Some(timer)) = storage.load_active_timer() {
+            // Timer exists - update if different from current state
+            if self.active_timer.is_none() || self.active_timer.as_ref() != Some(&timer) {
+                self.active_timer = Some(timer);
+                changed = true;
+            }
+        } else if self.active_timer.is_some() {
+            // Timer was cleared externally
+            self.active_timer = None;
+            self.invalidate_cached_totals();
+            changed = true;
+        }
+
+        if changed {
+            self.flag_orphaned_records();
+            // A merge already says what happened
+            let days = reloaded_day.as_ref().map(|old| (old, &self.saved_day));
+            if !merged
+                && let Some(notice) = super::reload_diff::describe_reload(
+                    days,
+                    old_timer.as_ref(),
+                    self.active_timer.as_ref(),
+                )
+            {
+                self.status_message = Some(notice);
+            }
+        }
+        changed
+    }
+
+    /// Merge the viewed day's unsaved edits with `disk`, written meanwhile by someone else
+    ///
+    /// Changes made on one side only are combined and saved right away. Records
+    /// changed on both sides are listed in `merge_conflicts` and asked about one at
+    /// a time; until then the day holds their disk version.
+    fn merge_from_disk(&mut self, disk: DayData, storage: &mut crate::storage::StorageManager) {
+        let merge = crate::models::merge_days(&self.saved_day, &self.day_data, &disk);
+        self.saved_day = disk;
+        self.day_data = merge.merged;
+        self.day_data
+            .apply_offset_changes(self.config.time.active_offset_changes());
+        self.last_file_modified = storage.get_last_modified(&self.current_date);
+        if merge.conflicts.is_empty() {
+            self.save_day(storage);
+            self.status_message = Some("Merged changes from disk with your edits".to_string());
+        } else {
+            self.merge_conflicts = merge.conflicts;
+            self.mode = AppMode::MergeConflict;
+        }
+    }
+
+    /// Settle the first merge conflict; back to Browse once none are left
+    pub fn resolve_merge_conflict(&mut self, resolution: Resolution) {
+        if self.merge_conflicts.is_empty() {
+            self.mode = AppMode::Browse;
+            return;
+        }
+        let conflict = self.merge_conflicts.remove(0);
+        conflict.resolve(&mut self.day_data, resolution);
+        if self.merge_conflicts.is_empty() {
+            self.mode = AppMode::Browse;
+            self.status_message = Some("Merged changes from disk with your edits".to_string());
+        }
+        let record_count = self.day_data.work_records.len();
+        if self.selected_index >= record_count && record_count > 0 {
+            self.selected_index = record_count - 1;
+        }
+    }
+
+    /// Recompute `[budgets]` statuses, including the day on screen and the running timer
+    ///
+    /// Totals from other days are cached and only reloaded after the date changes or
+    /// the cache is invalidated (timer stopped, external file change).
+    pub fn refresh_budgets(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let running = self.active_timer.as_ref().map(|timer| RunningTime {
+            task_name: &timer.task_name,
+            elapsed_minutes: (crate::timefmt::elapsed(timer, self.clock.now()).as_secs() / 60)
+                as u32,
+            source_record_id: timer
+                .source_record_id
+                .filter(|_| timer.source_record_date == Some(self.current_date)),
+        });
+
+        match self
+            .budget_tracker
+            .statuses(&self.config.budgets, &self.day_data, running, || {
+                storage.load_all_days()
+            }) {
+            Ok(statuses) => {
+                self.budget_statuses = statuses;
+                Ok(())
+            }
+            Err(e) => Err(format!("Failed to compute budgets: {}", e)),
+        }
+    }
+
+    /// Recompute the header's multi-day total when it shows more than today
+    ///
+    /// Other days come from a cache that is rebuilt when the window, the date or
+    /// the viewed day changes, or after `invalidate_cached_totals`.
+    pub fn refresh_window_totals(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        if self.totals_window == TotalsWindow::Today {
+            self.window_worked_minutes = None;
+            return Ok(());
+        }
+        let today = self.clock.today();
+        match self
+            .window_totals
+            .worked_minutes(self.totals_window, today, &self.day_data, |date| {
+                storage.load_day(date)
+            }) {
+            Ok(minutes) => {
+                self.window_worked_minutes = Some(minutes);
+                Ok(())
+            }
+            Err(e) => Err(format!("Failed to compute totals: {}", e)),
+        }
+    }
+
+    /// Load today's journaled sessions for the header while today is on screen
+    ///
+    /// The journal is read once per day and again after a stopped timer.
+    pub fn refresh_focus_stats(
+        &mut self,
+        storage: &crate::storage::StorageManager,
+    ) -> Result<(), String> {
+        let today = self.clock.today();
+        if self.current_date != today {
+            self.focus_today = None;
+            return Ok(());
+        }
+        if self.focus_loaded_for == Some(today) {
+            return Ok(());
+        }
+        self.focus_loaded_for = Some(today);
+        match storage.load_session_log(today) {
+            Ok(sessions) => {
+                self.focus_today = Some(FocusStats::from_sessions(1, &sessions));
+                Ok(())
+            }
+            Err(e) => {
+                self.focus_today = None;
+                Err(format!("Failed to read the session log: {}", e))
+            }
+        }
+    }
+
+    /// Show today, the last 7 days or the last 30 days in the header, in turn
+    pub fn cycle_totals_window(&mut self) {
+        self.totals_window = self.totals_window.next();
+        self.window_worked_minutes = None;
+    }
+
+    /// Drop cached totals of other days (after a stopped timer or external change)
+    fn invalidate_cached_totals(&mut self) {
+        self.budget_tracker.invalidate();
+        self.window_totals.invalidate();
+        self.focus_loaded_for = None;
+    }
+
+    /// Save the viewed day after applying `[validation] clamp_future_end`
+    ///
+    /// A failed save (read-only or full disk) pops up an error and leaves
+    /// `save_error` set, so the footer keeps warning that edits are only in memory
+    /// until a later save goes through.
+    pub fn save_day(&mut self, storage: &mut crate::storage::StorageManager) {
+        self.apply_future_end_rule();
+        match storage.save(&self.day_data) {
+            Ok(()) => {
+                self.save_error = None;
+                self.saved_day = self.day_data.clone();
+                self.last_file_modified = storage.get_last_modified(&self.current_date);
+            }
+            Err(e) => {
+                let message = format!("Changes are not being saved: {:#}", e);
+                self.last_error_message = Some(message.clone());
+                self.save_error = Some(message);
+            }
+        }
+    }
+
+    /// Whether the viewed day differs from what was last loaded or saved
+    ///
+    /// Offset changes applied on load count as edits, so they still get written.
+    pub fn is_dirty(&self) -> bool {
+        self.day_data != self.saved_day
+    }
+
+    /// Leave the viewed day for `current_date` after `[`/`]`, the calendar or goto
+    ///
+    /// The day being left is saved only when it has unsaved edits; if that save fails
+    /// the app stays on it. `next_step` is then asked for further day steps (-1 or +1)
+    /// until it returns `None`, so holding `[` or `]` loads only the day it stops on.
+    pub fn change_day(
+        &mut self,
+        storage: &mut crate::storage::StorageManager,
+        mut next_step: impl FnMut() -> anyhow::Result<Option<i64>>,
+    ) -> anyhow::Result<()> {
+        if self.is_dirty() {
+            self.save_day(storage);
+            if self.save_error.is_some() {
+                // Stay on the unsaved day instead of dropping its edits
+                self.current_date = self.day_data.date;
+                self.date_changed = false;
+                return Ok(());
+            }
+        }
+        while let Some(days) = next_step()? {
+            self.current_date = self.current_date.saturating_add(time::Duration::days(days));
+        }
+        let new_day_data = storage.load_with_tracking(self.current_date)?;
+        self.load_new_day_data(new_day_data);
+        self.last_file_modified = storage.get_last_modified(&self.current_date);
+        Ok(())
+    }
+
+    /// Apply `[validation] clamp_future_end` to today's records before they are saved
+    ///
+    /// Started records ending more than the grace period after now are listed in the
+    /// status line (`warn`) or cut back to end now (`clamp`, undoable). Other days are
+    /// never touched.
+    pub fn apply_future_end_rule(&mut self) {
+        use crate::config::FutureEndRule;
+        use crate::validation::{describe_future_ends, find_future_ends};
+
+        let validation = &self.config.validation;
+        let rule = validation.clamp_future_end;
+        if rule == FutureEndRule::Off {
+            return;
+        }
+        let now = self.clock.now();
+        if now.date() != self.day_data.date {
+            return;
+        }
+        let Ok(now) = TimePoint::new(now.hour(), now.minute()) else {
+            return;
+        };
+
+        let found = find_future_ends(
+            &self.day_data.get_sorted_records(),
+            now,
+            validation.future_end_grace(),
+        );
+        if found.is_empty() {
+            return;
+        }
+
+        let listed = describe_future_ends(&found);
+        if rule == FutureEndRule::Warn {
+            self.status_message = Some(format!("⚠ End time in the future: {}", listed));
+            return;
+        }
+
+        self.save_snapshot();
+        let changes = self.config.time.active_offset_changes().to_vec();
+        for future in &found {
+            if let Some(record) = self.day_data.work_records.get_mut(&future.record_id) {
+                record.end = now;
+                record.update_duration_on(self.day_data.date, &changes);
+            }
+        }
+        self.status_message = Some(format!("Ended at {} (was in the future): {}", now, listed));
+    }
+
+    /// Next record on the viewed day that hasn't started yet, with minutes until it does
+    ///
+    /// Only looks at today, and only when `[upcoming] enabled` is set.
+    pub fn next_upcoming(&self) -> Option<(&WorkRecord, u32)> {
+        let upcoming = &self.config.upcoming;
+        if !upcoming.enabled {
+            return None;
+        }
+        let now = self.clock.now();
+        if now.date() != self.current_date {
+            return None;
+        }
+        let now = TimePoint::new(now.hour(), now.minute()).ok()?;
+        crate::ui::upcoming::next_upcoming(
+            &self.day_data.get_sorted_records(),
+            now,
+            upcoming.include_breaks,
+        )
+    }
+
+    /// Whether the reminder for the next upcoming record should ring now
+    ///
+    /// True once per record, when it is `[upcoming] alert_lead_minutes` or less away.
+    pub fn take_upcoming_alert(&mut self) -> bool {
+        let Some(lead) = self.config.upcoming.alert_lead_minutes else {
+            return false;
+        };
+        let Some((record, minutes)) = self.next_upcoming() else {
+            return false;
+        };
+        let key = (self.current_date, record.id);
+        if !crate::ui::upcoming::alert_due(minutes, lead) || self.upcoming_alerted == Some(key) {
+            return false;
+        }
+        self.upcoming_alerted = Some(key);
+        true
+    }
+
+    /// Ask for the bell if `[timer] bell` covers `event`
+    fn timer_event(&mut self, event: TimerEvent) {
+        self.bell_pending |= bell::rings(&self.config.timer, event);
+    }
+
+    /// Whether a timer change since the last call should ring the bell
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// Budget status covering a task name, using the most specific matching key
+    pub fn budget_status_for(&self, name: &str) -> Option<&BudgetStatus> {
+        let key = crate::budget::budget_key_for(&self.config.budgets, name)?;
+        self.budget_statuses.iter().find(|status| status.key == key)
+    }
+}
+
+/// An estimate as typed: whole minutes ("90") or hours and minutes ("1:30"); empty is none
+fn parse_estimate(input: &str) -> Result<Option<u32>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let minutes = crate::models::parse_minutes(input)
+        .ok_or_else(|| format!("Invalid estimate '{}' (use minutes or H:MM)", input))?;
+    Ok((minutes > 0).then_some(minutes))
+}
+
+/// An adjustment as typed: signed minutes ("-30") or hours and minutes ("+1:30", "-1h");
+/// empty or zero is none
+fn parse_adjustment(input: &str) -> Result<Option<i32>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let (sign, rest) = match input.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, input.strip_prefix('+').unwrap_or(input)),
+    };
+    let minutes = crate::models::parse_minutes(rest.trim())
+        .and_then(|minutes| i32::try_from(minutes).ok())
+        .ok_or_else(|| format!("Invalid adjustment '{}' (use e.g. -30 or +1:30)", input))?;
+    Ok((minutes != 0).then_some(sign * minutes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::view::SummaryLine;
+    use ratatui::style::Color;
+    use time::macros::date;
+
+    fn custom_theme_config() -> Config {
+        let toml_str = r##"
+[theme]
+active = "mine"
+
+[theme.custom.mine]
+active_border = "#ff0000"
+inactive_border = "gray"
+searching_border = "yellow"
+selected_bg = "#102030"
+selected_inactive_bg = "black"
+visual_bg = "blue"
+timer_active_bg = "green"
+row_alternate_bg = "black"
+edit_bg = "blue"
+focus_bg = "cyan"
+primary_text = "white"
+secondary_text = "gray"
+highlight_text = "cyan"
+success = "green"
+warning = "yellow"
+error = "red"
+info = "cyan"
+timer_text = "yellow"
+badge = "magenta"
+"##;
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_with_config_uses_injected_theme() {
+        let app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), custom_theme_config());
+
+        assert_eq!(app.config.theme.active, "mine");
+        assert!(matches!(app.theme.active_border, Color::Rgb(255, 0, 0)));
+        assert!(matches!(app.theme.selected_bg, Color::Rgb(16, 32, 48)));
+    }
+
+    #[test]
+    fn test_with_config_default_theme() {
+        let app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+
+        assert_eq!(app.config.theme.active, "default");
+        let expected = Theme::default_theme();
+        assert_eq!(
+            format!("{:?}", app.theme.active_border),
+            format!("{:?}", expected.active_border)
+        );
+    }
+
+    #[test]
+    fn test_with_config_keeps_day_data() {
+        let day = DayData::new(date!(2025 - 11 - 06));
+        let app = AppState::with_config(day, Config::default());
+
+        assert_eq!(app.current_date, date!(2025 - 11 - 06));
+        assert_eq!(app.calendar_selected_date, app.current_date);
+        assert!(app.day_data.work_records.is_empty());
+    }
+
+    fn app_with_records() -> AppState {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        // Ids deliberately out of start-time order
+        for (id, hour) in [(7, 13), (2, 9), (5, 11)] {
+            day.add_record(WorkRecord::new(
+                id,
+                format!("Task {}", id),
+                crate::models::TimePoint::new(hour, 0).unwrap(),
+                crate::models::TimePoint::new(hour + 1, 0).unwrap(),
+            ));
+        }
+        AppState::with_config(day, Config::default())
+    }
+
+    #[test]
+    fn test_jumping_between_flags_wraps_around() {
+        // Sorted: Task 2 (9:00), Task 5 (11:00), Task 7 (13:00)
+        let mut app = app_with_records();
+        app.selected_index = 0;
+        app.toggle_flag();
+        app.selected_index = 2;
+        app.toggle_flag();
+        assert!(app.is_flagged(2) && app.is_flagged(7));
+
+        app.selected_index = 1;
+        app.jump_to_flag(true);
+        assert_eq!(app.selected_index, 2);
+        app.jump_to_flag(true);
+        assert_eq!(app.selected_index, 0);
+
+        app.jump_to_flag(false);
+        assert_eq!(app.selected_index, 2);
+        app.jump_to_flag(false);
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[test]
+    fn test_jump_to_longest_and_shortest_record() {
+        let mut app = app_with_records();
+        // All three last an hour, so the earliest start wins either way
+        app.selected_index = 2;
+        app.jump_to_longest(true);
+        assert_eq!(app.get_selected_record().unwrap().id, 2);
+        app.selected_index = 2;
+        app.jump_to_longest(false);
+        assert_eq!(app.get_selected_record().unwrap().id, 2);
+
+        let record = app.day_data.work_records.get_mut(&5).unwrap();
+        record.end = TimePoint::new(11, 30).unwrap();
+        record.update_duration();
+        let record = app.day_data.work_records.get_mut(&7).unwrap();
+        record.end = TimePoint::new(15, 0).unwrap();
+        record.update_duration();
+        app.jump_to_longest(true);
+        assert_eq!(app.get_selected_record().unwrap().id, 7);
+        app.jump_to_longest(false);
+        assert_eq!(app.get_selected_record().unwrap().id, 5);
+
+        let mut empty = AppState::new(DayData::new(date!(2025 - 11 - 06)));
+        empty.jump_to_longest(true);
+        assert_eq!(empty.selected_index, 0);
+        assert!(empty.last_error_message.is_none());
+    }
+
+    #[test]
+    fn test_jumping_without_flags_reports_it() {
+        let mut app = app_with_records();
+        app.selected_index = 1;
+        app.toggle_flag();
+        app.toggle_flag();
+        app.jump_to_flag(true);
+        assert_eq!(app.selected_index, 1);
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("No flagged records on this day (flag one with m)")
+        );
+    }
+
+    #[test]
+    fn test_billable_toggle_on_a_record_and_a_selection() {
+        let mut app = app_with_records();
+        // Sorted: Task 2, Task 5, Task 7
+        app.selected_index = 1;
+        app.toggle_billable();
+        assert_eq!(app.day_data.work_records[&5].billable, Some(false));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Marked 1 record(s) non-billable")
+        );
+
+        // A selection holding a billable record all becomes non-billable
+        app.selected_index = 0;
+        app.enter_visual_mode();
+        app.move_selection_down();
+        app.toggle_billable();
+        assert!(matches!(app.mode, AppMode::Browse));
+        let default = app.billable_default();
+        assert!(!app.day_data.work_records[&2].is_billable(default));
+        assert!(!app.day_data.work_records[&5].is_billable(default));
+        assert!(app.day_data.work_records[&7].is_billable(default));
+
+        // ...and back again once none is
+        app.selected_index = 0;
+        app.enter_visual_mode();
+        app.move_selection_down();
+        app.toggle_billable();
+        assert!(app.day_data.work_records[&2].is_billable(default));
+        assert!(app.day_data.work_records[&5].is_billable(default));
+
+        app.undo();
+        assert!(!app.day_data.work_records[&2].is_billable(default));
+        app.undo();
+        app.undo();
+        assert_eq!(app.day_data.work_records[&5].billable, None);
+    }
+
+    #[test]
+    fn test_flags_are_session_only_unless_persisted() {
+        let mut app = app_with_records();
+        app.toggle_flag();
+        assert!(app.day_data.flagged.is_empty());
+        app.load_new_day_data(app.day_data.clone());
+        assert!(app.flagged.is_empty());
+
+        let mut config = Config::default();
+        config.ui.persist_flags = true;
+        let mut app = AppState::with_config(app_with_records().day_data, config);
+        app.toggle_flag();
+        let flagged_id = app.get_selected_record().unwrap().id;
+        assert!(app.day_data.flagged.contains(&flagged_id));
+        app.load_new_day_data(app.day_data.clone());
+        assert!(app.is_flagged(flagged_id));
+    }
+
+    #[test]
+    fn test_key_help_follows_remapped_keys() {
+        let mut config = Config::default();
+        config.keys.insert("quit".to_string(), "Ctrl+c".to_string());
+        let app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), config);
+
+        let (help, _) = crate::ui::view::mode_hint(&app);
+        assert!(help.contains("Ctrl+C: Quit"), "{}", help);
+        assert!(!help.contains("q: Quit"), "{}", help);
+        assert!(help.contains("n: New"), "{}", help);
+
+        let quit = app
+            .available_commands
+            .iter()
+            .find(|cmd| cmd.action == CommandAction::Quit)
+            .unwrap();
+        assert_eq!(app.command_key(quit), "Ctrl+C");
+        assert!(app.last_error_message.is_none());
+    }
+
+    #[test]
+    fn test_bad_key_overrides_are_reported() {
+        let mut config = Config::default();
+        config.keys.insert("fly".to_string(), "f".to_string());
+        let app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), config);
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("Unknown action 'fly' in [keys]")
+        );
+    }
+
+    #[test]
+    fn test_clear_day_empties_the_day_and_undo_restores_it() {
+        let mut app = app_with_records();
+        app.selected_index = 2;
+        app.open_clear_day();
+        assert!(matches!(app.mode, AppMode::ConfirmClear));
+
+        app.confirm_clear_day();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(app.day_data.work_records.is_empty());
+        assert_eq!(app.day_data.date, date!(2025 - 11 - 06));
+        assert_eq!(app.selected_index, 0);
+        // A record added afterwards doesn't reuse a cleared id
+        assert_eq!(app.day_data.last_id, 7);
+
+        app.undo();
+        let mut ids: Vec<u32> = app.day_data.work_records.keys().copied().collect();
+        ids.sort();
+        assert_eq!(ids, vec![2, 5, 7]);
+    }
+
+    /// Standup 09:00-09:15, Review 09:15-10:00, Coding 10:00-11:00, then Lunch after a gap
+    fn back_to_back(cascade: crate::config::Cascade) -> AppState {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        for (id, name, start, end) in [
+            (1, "Standup", (9, 0), (9, 15)),
+            (2, "Review", (9, 15), (10, 0)),
+            (3, "Coding", (10, 0), (11, 0)),
+            (4, "Lunch", (11, 30), (12, 0)),
+        ] {
+            day.add_record(WorkRecord::new(
+                id,
+                name.to_string(),
+                TimePoint::new(start.0, start.1).unwrap(),
+                TimePoint::new(end.0, end.1).unwrap(),
+            ));
+        }
+        let mut config = Config::default();
+        config.edit.cascade = cascade;
+        AppState::with_config(day, config)
+    }
+
+    /// Edit the end of record `id` to `end` the way Enter commits it
+    fn edit_end(app: &mut AppState, id: u32, end: &str) {
+        app.selected_index = app.index_of_record_id(id).unwrap();
+        app.edit_field = EditField::End;
+        app.enter_edit_mode();
+        app.input_buffer = end.to_string();
+        app.save_edit().unwrap();
+    }
+
+    fn times(app: &AppState, id: u32) -> (String, String, u32) {
+        let record = &app.day_data.work_records[&id];
+        (
+            record.start.to_string(),
+            record.end.to_string(),
+            record.total_minutes,
+        )
+    }
+
+    #[test]
+    fn test_cascade_moves_only_the_next_contiguous_record() {
+        let mut app = back_to_back(crate::config::Cascade::Contiguous);
+        edit_end(&mut app, 1, "09:25");
+        assert_eq!(times(&app, 1), ("09:00".into(), "09:25".into(), 25));
+        assert_eq!(times(&app, 2), ("09:25".into(), "10:00".into(), 35));
+        // Review's end didn't move, so Coding stays put
+        assert_eq!(times(&app, 3), ("10:00".into(), "11:00".into(), 60));
+        assert!(matches!(app.mode, AppMode::Browse));
+
+        // Ending earlier pulls the next start back too
+        edit_end(&mut app, 2, "09:50");
+        assert_eq!(times(&app, 3), ("09:50".into(), "11:00".into(), 70));
+    }
+
+    #[test]
+    fn test_cascade_stops_at_a_gap() {
+        let mut app = back_to_back(crate::config::Cascade::Contiguous);
+        edit_end(&mut app, 3, "11:10");
+        assert_eq!(times(&app, 4), ("11:30".into(), "12:00".into(), 30));
+    }
+
+    #[test]
+    fn test_cascade_clamps_instead_of_going_negative() {
+        let mut app = back_to_back(crate::config::Cascade::Contiguous);
+        edit_end(&mut app, 1, "10:20");
+        assert_eq!(times(&app, 2), ("10:00".into(), "10:00".into(), 0));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Review would end before it starts; it now starts at its end, 10:00")
+        );
+    }
+
+    #[test]
+    fn test_cascade_is_undone_with_the_edit() {
+        let mut app = back_to_back(crate::config::Cascade::Contiguous);
+        edit_end(&mut app, 1, "09:25");
+        app.undo();
+        assert_eq!(times(&app, 1), ("09:00".into(), "09:15".into(), 15));
+        assert_eq!(times(&app, 2), ("09:15".into(), "10:00".into(), 45));
+    }
+
+    #[test]
+    fn test_cascade_off_leaves_the_next_record() {
+        let mut app = back_to_back(crate::config::Cascade::Off);
+        edit_end(&mut app, 1, "09:25");
+        assert_eq!(times(&app, 2), ("09:15".into(), "10:00".into(), 45));
+        assert!(app.pending_cascade.is_none());
+    }
+
+    #[test]
+    fn test_cascade_ask_waits_for_an_answer() {
+        let mut app = back_to_back(crate::config::Cascade::Ask);
+        edit_end(&mut app, 1, "09:25");
+        assert!(matches!(app.mode, AppMode::ConfirmCascade));
+        assert_eq!(times(&app, 2), ("09:15".into(), "10:00".into(), 45));
+        assert_eq!(
+            crate::ui::view::cascade_question(&app).as_deref(),
+            Some("Also move Review to start at 09:25?")
+        );
+
+        app.confirm_cascade(true);
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(times(&app, 2), ("09:25".into(), "10:00".into(), 35));
+        // Still one undo step
+        app.undo();
+        assert_eq!(times(&app, 1), ("09:00".into(), "09:15".into(), 15));
+        assert_eq!(times(&app, 2), ("09:15".into(), "10:00".into(), 45));
+
+        edit_end(&mut app, 1, "09:25");
+        app.confirm_cascade(false);
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(app.pending_cascade.is_none());
+        assert_eq!(times(&app, 2), ("09:15".into(), "10:00".into(), 45));
+    }
+
+    #[test]
+    fn test_clear_day_cancel_keeps_records() {
+        let mut app = app_with_records();
+        app.open_clear_day();
+        app.close_clear_day();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.day_data.work_records.len(), 3);
+    }
+
+    #[test]
+    fn test_clear_day_on_empty_day_does_not_ask() {
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+        app.open_clear_day();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(app.last_error_message.is_some());
+    }
+
+    /// Add a record with `strategy` on `app_with_records`, first record selected, at 15:20
+    fn new_record_with(strategy: crate::config::NewRecordStart, today: bool) -> WorkRecord {
+        use time::macros::datetime;
+
+        let mut app = app_with_records();
+        app.config.defaults.new_record_start = strategy;
+        app.clock = Clock::Fixed(if today {
+            datetime!(2025-11-06 15:20 UTC)
+        } else {
+            datetime!(2025-11-08 15:20 UTC)
+        });
+        app.selected_index = 0;
+        app.add_new_record();
+        app.get_selected_record().unwrap().clone()
+    }
+
+    #[test]
+    fn test_new_record_starts_after_selected_by_default() {
+        use crate::config::NewRecordStart;
+
+        let record = new_record_with(NewRecordStart::AfterSelected, true);
+        assert_eq!(record.start, TimePoint::new(10, 0).unwrap());
+        assert_eq!(record.end, TimePoint::new(11, 0).unwrap());
+    }
+
+    #[test]
+    fn test_new_record_starts_now_when_viewing_today() {
+        use crate::config::NewRecordStart;
+
+        let record = new_record_with(NewRecordStart::Now, true);
+        assert_eq!(record.start, TimePoint::new(15, 20).unwrap());
+        assert_eq!(record.end, TimePoint::new(16, 20).unwrap());
+
+        // On another day "now" means nothing, so it falls back to the selected record
+        let record = new_record_with(NewRecordStart::Now, false);
+        assert_eq!(record.start, TimePoint::new(10, 0).unwrap());
+    }
+
+    #[test]
+    fn test_new_record_starts_after_last_record() {
+        use crate::config::NewRecordStart;
+
+        let record = new_record_with(NewRecordStart::AfterLast, true);
+        assert_eq!(record.start, TimePoint::new(14, 0).unwrap());
+        assert_eq!(record.end, TimePoint::new(15, 0).unwrap());
+    }
+
+    /// An empty day with `[defaults] close_previous` and records starting now, at 09:00
+    fn closing_app() -> AppState {
+        use time::macros::datetime;
+
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+        app.config.defaults.new_record_start = crate::config::NewRecordStart::Now;
+        app.config.defaults.close_previous = true;
+        app.clock = Clock::Fixed(datetime!(2025-11-06 09:00 UTC));
+        app
+    }
+
+    #[test]
+    fn test_new_record_closes_a_placeholder_end() {
+        use time::macros::datetime;
+
+        let mut app = closing_app();
+        app.add_new_record();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 09:40 UTC));
+        app.add_new_record_named("Review");
+
+        let first = &app.day_data.work_records[&1];
+        assert_eq!(first.end, TimePoint::new(9, 40).unwrap());
+        assert_eq!(first.total_minutes, 40);
+        let second = &app.day_data.work_records[&2];
+        assert_eq!(second.start, TimePoint::new(9, 40).unwrap());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Ended 'New Task' at 09:40")
+        );
+
+        // A later record stretches the placeholder too; one undo restores both
+        app.clock = Clock::Fixed(datetime!(2025-11-06 11:15 UTC));
+        app.add_new_record();
+        assert_eq!(
+            app.day_data.work_records[&2].end,
+            TimePoint::new(11, 15).unwrap()
+        );
+        app.undo();
+        assert_eq!(app.day_data.work_records.len(), 2);
+        assert_eq!(
+            app.day_data.work_records[&2].end,
+            TimePoint::new(10, 40).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_record_keeps_an_end_that_was_set() {
+        use time::macros::datetime;
+
+        let mut app = closing_app();
+        app.add_new_record();
+        app.set_selected_range(
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(9, 30).unwrap(),
+        )
+        .unwrap();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 09:45 UTC));
+        app.add_new_record();
+        assert_eq!(
+            app.day_data.work_records[&1].end,
+            TimePoint::new(9, 30).unwrap()
+        );
+        assert_eq!(app.status_message, None);
+
+        // Records that didn't come from `n` have real ends as well
+        let mut app = app_with_records();
+        app.config.defaults.close_previous = true;
+        app.config.defaults.new_record_start = crate::config::NewRecordStart::Now;
+        app.clock = Clock::Fixed(datetime!(2025-11-06 13:30 UTC));
+        app.add_new_record();
+        assert_eq!(
+            app.day_data.work_records[&7].end,
+            TimePoint::new(14, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_record_leaves_placeholders_without_close_previous() {
+        use time::macros::datetime;
+
+        let mut app = closing_app();
+        app.config.defaults.close_previous = false;
+        app.add_new_record();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 09:40 UTC));
+        app.add_new_record();
+        assert_eq!(
+            app.day_data.work_records[&1].end,
+            TimePoint::new(10, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_record_on_empty_day_starts_at_nine() {
+        use crate::config::NewRecordStart;
+
+        for strategy in [NewRecordStart::AfterSelected, NewRecordStart::AfterLast] {
+            let mut app =
+                AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+            app.config.defaults.new_record_start = strategy;
+            app.add_new_record();
+            let record = app.get_selected_record().unwrap();
+            assert_eq!(record.start, TimePoint::new(9, 0).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_index_of_record_id_follows_display_order() {
+        let app = app_with_records();
+        assert_eq!(app.index_of_record_id(2), Some(0));
+        assert_eq!(app.index_of_record_id(5), Some(1));
+        assert_eq!(app.index_of_record_id(7), Some(2));
+        assert_eq!(app.index_of_record_id(3), None);
+    }
+
+    #[test]
+    fn test_goto_id_selects_matching_record() {
+        let mut app = app_with_records();
+        app.open_goto_id();
+        app.handle_goto_id_char('7');
+        assert!(app.confirm_goto_id().is_ok());
+        assert_eq!(app.selected_index, 2);
+        assert!(matches!(app.mode, AppMode::Browse));
+    }
+
+    #[test]
+    fn test_goto_id_unknown_id_errors() {
+        let mut app = app_with_records();
+        app.selected_index = 1;
+        app.open_goto_id();
+        app.handle_goto_id_char('4');
+        app.handle_goto_id_char('2');
+
+        let err = app.confirm_goto_id().unwrap_err();
+        assert!(err.contains("42"), "{}", err);
+        assert_eq!(app.selected_index, 1);
+    }
+
+    #[test]
+    fn test_goto_id_ignores_non_digits() {
+        let mut app = app_with_records();
+        app.open_goto_id();
+        app.handle_goto_id_char('x');
+        app.handle_goto_id_char('5');
+        app.handle_goto_id_char('-');
+        assert_eq!(app.input_buffer, "5");
+
+        app.handle_goto_id_backspace();
+        assert!(app.confirm_goto_id().is_err());
+    }
+
+    #[test]
+    fn test_change_task_name_on_empty_day_creates_named_record() {
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+
+        app.change_task_name();
+        assert!(matches!(app.mode, AppMode::TaskPicker));
+        assert!(app.task_picker_create);
+
+        for c in "Planning".chars() {
+            app.handle_task_picker_char(c);
+        }
+        app.select_task_from_picker();
+
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(!app.task_picker_create);
+        assert_eq!(app.day_data.work_records.len(), 1);
+        let record = app.get_selected_record().unwrap();
+        assert_eq!(record.name, "Planning");
+        assert_eq!(record.start.to_string(), "09:00");
+    }
+
+    #[test]
+    fn test_enter_edit_mode_on_empty_day_opens_create_picker() {
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+        app.enter_edit_mode();
+        assert!(matches!(app.mode, AppMode::TaskPicker));
+        assert!(app.task_picker_create);
+    }
+
+    #[test]
+    fn test_create_picker_cancel_or_blank_adds_nothing() {
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+
+        app.change_task_name();
+        app.close_task_picker();
+        assert!(!app.task_picker_create);
+
+        app.change_task_name();
+        app.handle_task_picker_char(' ');
+        app.select_task_from_picker();
+        assert!(app.day_data.work_records.is_empty());
+    }
+
+    #[test]
+    fn test_change_task_name_with_records_renames() {
+        let mut app = app_with_records();
+        app.change_task_name();
+        assert!(!app.task_picker_create);
+        for c in "Renamed".chars() {
+            app.handle_task_picker_char(c);
+        }
+        app.select_task_from_picker();
+
+        assert_eq!(app.day_data.work_records.len(), 3);
+        assert_eq!(app.get_selected_record().unwrap().name, "Renamed");
+    }
+
+    /// Pick the summary row for `name` and filter the table by it
+    fn filter_by_summary_row(app: &mut AppState, name: &str) {
+        app.focus_summary();
+        assert!(matches!(app.mode, AppMode::Summary));
+        app.summary_selected = crate::ui::view::summary_lines(app)
+            .iter()
+            .position(|line| matches!(line, SummaryLine::Task { name: n, .. } if n == name))
+            .unwrap();
+        app.apply_summary_filter();
+    }
+
+    #[test]
+    fn test_summary_row_filters_the_records_table() {
+        let mut app = app_with_records();
+        app.day_data.work_records.get_mut(&7).unwrap().name = "Task 2".to_string();
+        filter_by_summary_row(&mut app, "Task 2");
+
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.record_filter.as_ref().unwrap().label(), "Task 2");
+        // Sorted: 2 (9:00), 5 (11:00), 7 (13:00); only 2 and 7 are shown
+        assert_eq!(app.get_selected_record().unwrap().id, 2);
+        app.move_selection_down();
+        assert_eq!(app.get_selected_record().unwrap().id, 7);
+        app.move_selection_up();
+        assert_eq!(app.get_selected_record().unwrap().id, 2);
+        assert_eq!(app.filtered_minutes(), Some(120));
+    }
+
+    #[test]
+    fn test_clearing_the_filter_restores_every_record() {
+        let mut app = app_with_records();
+        filter_by_summary_row(&mut app, "Task 7");
+        assert_eq!(app.get_selected_record().unwrap().id, 7);
+        app.move_selection_up();
+        assert_eq!(app.get_selected_record().unwrap().id, 7);
+
+        app.clear_record_filter();
+        assert!(app.record_filter.is_none());
+        assert_eq!(app.filtered_minutes(), None);
+        app.move_selection_up();
+        assert_eq!(app.get_selected_record().unwrap().id, 5);
+    }
+
+    #[test]
+    fn test_visual_selection_skips_filtered_out_records() {
+        let mut app = app_with_records();
+        app.day_data.work_records.get_mut(&7).unwrap().name = "Task 2".to_string();
+        filter_by_summary_row(&mut app, "Task 2");
+        app.enter_visual_mode();
+        app.move_selection_down();
+        app.delete_visual_selection();
+
+        let left: Vec<u32> = app.day_data.work_records.keys().copied().collect();
+        assert_eq!(left, vec![5]);
+    }
+
+    #[test]
+    fn test_app_opens_on_the_loaded_day_with_the_calendar_up() {
+        // As `work-tuimer --date 2025-11-03 --calendar` starts the TUI
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 03)), Config::default());
+        app.open_calendar();
+
+        assert_eq!(app.current_date, date!(2025 - 11 - 03));
+        assert!(matches!(app.mode, AppMode::Calendar));
+        assert_eq!(app.calendar_selected_date, date!(2025 - 11 - 03));
+    }
+
+    #[test]
+    fn test_toggle_project_collapse_survives_day_change() {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        let mut record = WorkRecord::new(
+            1,
+            "API".to_string(),
+            crate::models::TimePoint::new(9, 0).unwrap(),
+            crate::models::TimePoint::new(10, 0).unwrap(),
+        );
+        record.project = Some("Acme".to_string());
+        day.add_record(record);
+        let mut app = AppState::with_config(day, Config::default());
+
+        app.toggle_selected_project_collapsed();
+        assert!(app.is_project_collapsed("Acme"));
+
+        app.load_new_day_data(DayData::new(date!(2025 - 11 - 07)));
+        assert!(app.is_project_collapsed("Acme"));
+
+        app.load_new_day_data(app_with_records().day_data);
+        app.toggle_selected_project_collapsed();
+        assert!(app.is_project_collapsed(crate::models::NO_PROJECT_LABEL));
+        assert!(app.is_project_collapsed("Acme"));
+    }
+
+    #[test]
+    fn test_paste_range_sets_both_fields_in_one_undo_step() {
+        use crate::models::TimePoint;
+
+        let mut app = app_with_records();
+        app.selected_index = 1; // record 5, 11:00-12:00
+        app.edit_field = EditField::Start;
+        app.enter_edit_mode();
+
+        assert_eq!(app.handle_paste("9.15 – 10.45"), Ok(true));
+        let record = app.get_selected_record().unwrap();
+        assert_eq!(record.start, TimePoint::new(9, 15).unwrap());
+        assert_eq!(record.end, TimePoint::new(10, 45).unwrap());
+        assert_eq!(record.total_minutes, 90);
+        assert!(matches!(app.mode, AppMode::Browse));
+
+        app.undo();
+        let record = &app.day_data.work_records[&5];
+        assert_eq!(record.start, TimePoint::new(11, 0).unwrap());
+        assert_eq!(record.end, TimePoint::new(12, 0).unwrap());
+    }
+
+    #[test]
+    fn test_paste_single_time_on_time_field() {
+        let mut app = app_with_records();
+        app.edit_field = EditField::End;
+        app.enter_edit_mode();
+
+        assert_eq!(app.handle_paste("10.30"), Ok(true));
+        assert_eq!(app.day_data.work_records[&2].end.to_string(), "10:30");
+        assert!(matches!(app.mode, AppMode::Browse));
+
+        app.edit_field = EditField::Start;
+        app.enter_edit_mode();
+        assert!(app.handle_paste("soon").is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_time_is_explained_in_edit_mode() {
+        let mut app = app_with_records();
+        app.edit_field = EditField::End;
+        app.enter_edit_mode();
+        for c in "2500".chars() {
+            app.handle_char_input(c);
+        }
+        // The last digit couldn't save the field, so editing goes on
+        assert!(matches!(app.mode, AppMode::Edit));
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("Invalid time '25:00': hour must be 0-23")
+        );
+        assert_eq!(
+            app.save_edit(),
+            Err("Invalid time '25:00': hour must be 0-23".to_string())
+        );
+        assert_eq!(app.day_data.work_records[&2].end.to_string(), "10:00");
+
+        // A pasted time may be written in any accepted form
+        assert_eq!(app.handle_paste("1045 "), Ok(true));
+        assert_eq!(app.day_data.work_records[&2].end.to_string(), "10:45");
+    }
+
+    #[test]
+    fn test_paste_text_into_name_field_is_not_parsed() {
+        let mut app = app_with_records();
+        app.enter_edit_mode();
+        app.input_buffer.clear();
+
+        assert_eq!(app.handle_paste("09:15 - 10:45"), Ok(false));
+        assert_eq!(app.input_buffer, "09:15 - 10:45");
+        assert_eq!(app.day_data.work_records[&2].start.to_string(), "09:00");
+    }
+
+    #[test]
+    fn test_paste_in_browse_mode_requires_range() {
+        let mut app = app_with_records();
+        assert!(app.handle_paste("standup notes").is_err());
+        assert_eq!(app.handle_paste("8:00 to 9:30"), Ok(true));
+        assert_eq!(app.day_data.work_records[&2].total_minutes, 90);
+    }
+
+    fn tracker_config() -> Config {
+        toml::from_str(
+            r#"
+[integrations.trackers.jira]
+enabled = true
+base_url = "https://example.atlassian.net"
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+worklog_url = "{base_url}/browse/{ticket}?focusedWorklogId=-1"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_explicit_url_wins_over_detected_ticket() {
+        let app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), tracker_config());
+        let mut record = WorkRecord::new(
+            1,
+            "PROJ-42 Review".to_string(),
+            crate::models::TimePoint::new(9, 0).unwrap(),
+            crate::models::TimePoint::new(10, 0).unwrap(),
+        );
+
+        assert_eq!(
+            app.ticket_url_for(&record).unwrap(),
+            "https://example.atlassian.net/browse/PROJ-42"
+        );
+
+        record.url = Some("https://github.com/org/repo/pull/7".to_string());
+        assert_eq!(
+            app.ticket_url_for(&record).unwrap(),
+            "https://github.com/org/repo/pull/7"
+        );
+
+        // A link works without any ticket in the name or tracker configured
+        let app = app_with_records();
+        record.name = "Design review".to_string();
+        assert_eq!(
+            app.ticket_url_for(&record).unwrap(),
+            "https://github.com/org/repo/pull/7"
+        );
+        record.url = None;
+        assert_eq!(
+            app.ticket_url_for(&record).unwrap_err(),
+            "No ticket found in task name"
+        );
+    }
+
+    fn app_with_fields() -> AppState {
+        use crate::config::FieldDef;
+
+        let mut app = app_with_records();
+        app.config.fields.insert(
+            "cost_center".to_string(),
+            FieldDef::Choice {
+                options: vec!["R&D".to_string(), "Ops".to_string()],
+            },
+        );
+        app.config
+            .fields
+            .insert("po_number".to_string(), FieldDef::Text);
+        app
+    }
+
+    #[test]
+    fn test_fields_editor_requires_declared_fields() {
+        let mut app = app_with_records();
+        app.open_fields_editor();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("No custom fields declared in [fields]")
+        );
+    }
+
+    #[test]
+    fn test_fields_editor_sets_text_and_choice() {
+        let mut app = app_with_fields();
+        app.open_fields_editor();
+        assert!(matches!(app.mode, AppMode::Fields));
+        assert_eq!(app.field_drafts.len(), 2);
+
+        // Choice fields ignore typing and cycle through their options
+        app.handle_fields_char('x');
+        app.cycle_field_choice(true);
+        app.cycle_field_choice(true);
+        assert_eq!(app.field_drafts[0].1, "Ops");
+        app.cycle_field_choice(true);
+        assert_eq!(app.field_drafts[0].1, "");
+        app.cycle_field_choice(false);
+        assert_eq!(app.field_drafts[0].1, "Ops");
+
+        app.move_field_selection_down();
+        for c in "PO-12".chars() {
+            app.handle_fields_char(c);
+        }
+        app.confirm_fields_editor().unwrap();
+        assert!(matches!(app.mode, AppMode::Browse));
+
+        let record = app.get_selected_record().unwrap();
+        assert_eq!(record.extra.get("cost_center").unwrap(), "Ops");
+        assert_eq!(record.extra.get("po_number").unwrap(), "PO-12");
+
+        app.undo();
+        assert!(app.get_selected_record().unwrap().extra.is_empty());
+    }
+
+    #[test]
+    fn test_fields_editor_rejects_value_outside_choices() {
+        let mut app = app_with_fields();
+        app.open_fields_editor();
+        app.field_drafts[0].1 = "Sales".to_string();
+
+        assert_eq!(
+            app.confirm_fields_editor().unwrap_err(),
+            "cost_center: 'Sales' is not one of: R&D, Ops"
+        );
+        // Stays open so the value can be fixed
+        assert!(matches!(app.mode, AppMode::Fields));
+        assert!(app.get_selected_record().unwrap().extra.is_empty());
+    }
+
+    #[test]
+    fn test_fields_editor_keeps_undeclared_keys() {
+        let mut app = app_with_fields();
+        let id = app.get_selected_record().unwrap().id;
+        let record = app.day_data.work_records.get_mut(&id).unwrap();
+        record
+            .extra
+            .insert("legacy_code".to_string(), "L-1".to_string());
+        record
+            .extra
+            .insert("po_number".to_string(), "PO-1".to_string());
+
+        app.open_fields_editor();
+        assert_eq!(app.field_drafts[1].1, "PO-1");
+        app.move_field_selection_down();
+        app.field_drafts[1].1.clear();
+        app.confirm_fields_editor().unwrap();
+
+        let extra = &app.get_selected_record().unwrap().extra;
+        assert_eq!(extra.get("legacy_code").unwrap(), "L-1");
+        assert!(!extra.contains_key("po_number"));
+    }
+
+    #[test]
+    fn test_switch_session_cancel_leaves_everything_as_it_was() {
+        let mut app = app_with_records();
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), Some(5)));
+        let records_before = app.day_data.work_records.len();
+
+        app.open_switch_session();
+        assert!(matches!(app.mode, AppMode::TaskPicker));
+        for c in "Deploy".chars() {
+            app.handle_task_picker_char(c);
+        }
+        app.close_task_picker();
+
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(!app.task_picker_switch);
+        let timer = app.active_timer.as_ref().unwrap();
+        assert_eq!(timer.task_name, "Task 5");
+        assert_eq!(timer.source_record_id, Some(5));
+        assert_eq!(app.day_data.work_records.len(), records_before);
+        assert!(app.input_buffer.is_empty());
+    }
+
+    #[test]
+    fn test_picked_task_name_prefers_match_then_typed_text() {
+        let mut app = app_with_records();
+        app.open_switch_session();
+        for c in "task 7".chars() {
+            app.handle_task_picker_char(c);
+        }
+        assert_eq!(app.picked_task_name(), "Task 7");
+
+        for c in " deploy ".chars() {
+            app.handle_task_picker_char(c);
+        }
+        assert_eq!(app.picked_task_name(), "task 7 deploy");
+    }
+
+    #[test]
+    fn test_failed_save_is_surfaced_until_a_save_succeeds() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        let mut storage = crate::storage::StorageManager::new_with_dir(data_dir.clone()).unwrap();
+        let mut app = app_with_records();
+
+        app.save_day(&mut storage);
+        assert!(app.save_error.is_none());
+
+        // Make the data directory unwritable. Permission bits don't stop root, so
+        // replace it with a plain file instead.
+        std::fs::remove_dir_all(&data_dir).unwrap();
+        std::fs::write(&data_dir, "").unwrap();
+        app.add_new_record();
+        app.save_day(&mut storage);
+        let error = app.last_error_message.clone().unwrap();
+        assert!(
+            error.starts_with("Changes are not being saved"),
+            "{}",
+            error
+        );
+        assert_eq!(app.save_error.as_ref(), Some(&error));
+
+        // The popup goes with the next key; the warning stays
+        app.clear_error();
+        assert!(app.last_error_message.is_none());
+        assert!(app.save_error.is_some());
+
+        std::fs::remove_file(&data_dir).unwrap();
+        std::fs::create_dir(&data_dir).unwrap();
+        app.save_day(&mut storage);
+        assert!(app.save_error.is_none());
+        assert_eq!(
+            storage
+                .load_with_tracking(app.current_date)
+                .unwrap()
+                .work_records
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_week_grid_from_the_calendar() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut monday = DayData::new(date!(2025 - 11 - 03));
+        monday.add_record(WorkRecord::new(
+            1,
+            "Task 5".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(11, 0).unwrap(),
+        ));
+        storage.save(&monday).unwrap();
+
+        // The viewed Thursday is unsaved, so it must come from memory
+        let mut app = app_with_records();
+        app.open_calendar();
+        app.open_week_grid(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::WeekGrid));
+        let grid = app.week_grid.as_ref().unwrap();
+        assert_eq!(grid.dates[0], date!(2025 - 11 - 03));
+        assert_eq!(grid.rows[0].key, "Task 5");
+        assert_eq!(grid.rows[0].minutes, [120, 0, 0, 60, 0, 0, 0]);
+        assert_eq!(grid.grand_total(), 300);
+
+        app.scroll_week_grid_down();
+        app.scroll_week_grid_down();
+        app.scroll_week_grid_down();
+        assert_eq!(app.week_grid_scroll, 2);
+        app.close_week_grid();
+        assert!(matches!(app.mode, AppMode::Calendar));
+        assert!(app.week_grid.is_none());
+    }
+
+    #[test]
+    fn test_budgets_count_every_record_of_a_capped_day() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf())
+                .unwrap()
+                .max_records(5);
+        let mut large = DayData::new(date!(2025 - 11 - 03));
+        for id in 1..=12 {
+            large.add_record(WorkRecord::new(
+                id,
+                "ACME-7 import".to_string(),
+                TimePoint::new(9, 0).unwrap(),
+                TimePoint::new(9, 10).unwrap(),
+            ));
+        }
+        storage.save(&large).unwrap();
+        // The day on screen is capped
+        let shown = storage.load_with_tracking(large.date).unwrap();
+        assert_eq!(shown.work_records.len(), 5);
+
+        let mut app = app_with_records();
+        app.config.budgets.insert("ACME-7".to_string(), 600);
+        app.refresh_budgets(&storage).unwrap();
+        assert_eq!(app.budget_statuses[0].spent_minutes, 120);
+    }
+
+    #[test]
+    fn test_week_grid_checks_task_budgets() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        // Outside the week but inside the month
+        let mut earlier = DayData::new(date!(2025 - 11 - 01));
+        earlier.add_record(WorkRecord::new(
+            1,
+            "Task 5".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(12, 0).unwrap(),
+        ));
+        storage.save(&earlier).unwrap();
+
+        let mut app = app_with_records();
+        app.config.task_budgets = vec![
+            crate::config::TaskBudget {
+                task: Some("task 5".to_string()),
+                weekly_hours: Some(0.5),
+                monthly_hours: Some(8.0),
+                ..Default::default()
+            },
+            crate::config::TaskBudget {
+                task: Some("Task 7".to_string()),
+                weekly_hours: Some(2.0),
+                ..Default::default()
+            },
+        ];
+        app.open_calendar();
+        app.open_week_grid(&storage).unwrap();
+
+        let budgets: Vec<_> = app
+            .period_budgets
+            .iter()
+            .map(|(period, status)| (*period, status.key.as_str(), status.spent_minutes))
+            .collect();
+        assert_eq!(
+            budgets,
+            [
+                (BudgetPeriod::Week, "task 5", 60),
+                (BudgetPeriod::Week, "Task 7", 60),
+                (BudgetPeriod::Month, "task 5", 240),
+            ]
+        );
+        assert_eq!(
+            app.period_budgets[0].1.level(),
+            crate::budget::BudgetLevel::Exceeded
+        );
+        assert_eq!(
+            app.period_budgets[2].1.level(),
+            crate::budget::BudgetLevel::Ok
+        );
+
+        app.close_week_grid();
+        assert!(app.period_budgets.is_empty());
+    }
+
+    #[test]
+    fn test_recent_days_jump_to_a_day_with_records() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.open_recent_days(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No days with records yet")
+        );
+
+        app.save_day(&mut storage);
+        let mut earlier = DayData::new(date!(2025 - 10 - 31));
+        earlier.add_record(WorkRecord::new(
+            1,
+            "Planning".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 30).unwrap(),
+        ));
+        storage.save(&earlier).unwrap();
+
+        // The viewed day is highlighted to start with
+        app.open_recent_days(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::RecentDays));
+        let dates: Vec<Date> = app.recent_days.iter().map(|day| day.date).collect();
+        assert_eq!(dates, [date!(2025 - 11 - 06), date!(2025 - 10 - 31)]);
+        assert_eq!(app.recent_days[1].records, 1);
+        assert_eq!(app.recent_days[1].worked_minutes, 90);
+        assert_eq!(app.recent_days_selected, 0);
+
+        app.move_recent_days_down();
+        app.move_recent_days_down();
+        app.confirm_recent_day();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.current_date, date!(2025 - 10 - 31));
+        assert!(app.date_changed);
+        assert!(app.recent_days.is_empty());
+    }
+
+    #[test]
+    fn test_template_saved_and_inserted_after_selected_record() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.open_template_picker(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::Browse));
+
+        // Sorted: Task 2 (9:00), Task 5 (11:00), Task 7 (13:00)
+        let task = app.day_data.work_records.get_mut(&2).unwrap();
+        task.end = TimePoint::new(9, 45).unwrap();
+        task.description = "Daily sync".to_string();
+        app.selected_index = 0;
+        app.open_save_template();
+        assert!(matches!(app.mode, AppMode::TemplateNamePrompt));
+        assert_eq!(app.input_buffer, "Task 2");
+        app.input_buffer = "Standup".to_string();
+        app.confirm_template_name(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Saved template 'Standup'")
+        );
+
+        app.selected_index = 1;
+        app.open_template_picker(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::TemplatePicker));
+        app.insert_selected_template();
+        assert!(matches!(app.mode, AppMode::Browse));
+
+        // Next free id, right after Task 5, for the template's 45 minutes
+        let inserted = &app.day_data.work_records[&8];
+        assert_eq!(inserted.name, "Task 2");
+        assert_eq!(inserted.start, TimePoint::new(12, 0).unwrap());
+        assert_eq!(inserted.end, TimePoint::new(12, 45).unwrap());
+        assert_eq!(inserted.description, "Daily sync");
+        assert_eq!(app.get_selected_record().unwrap().id, 8);
+
+        app.undo();
+        assert!(!app.day_data.work_records.contains_key(&8));
+    }
+
+    #[test]
+    fn test_templates_filtered_renamed_and_deleted() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        for (index, name) in ["Standup", "Code review"].into_iter().enumerate() {
+            app.selected_index = index;
+            app.open_save_template();
+            app.input_buffer = name.to_string();
+            app.confirm_template_name(&storage).unwrap();
+        }
+
+        app.open_template_picker(&storage).unwrap();
+        "rev"
+            .chars()
+            .for_each(|c| app.handle_template_picker_char(c));
+        let names: Vec<&str> = app
+            .filtered_templates()
+            .iter()
+            .map(|template| template.name.as_str())
+            .collect();
+        assert_eq!(names, ["Code review"]);
+
+        // Renaming onto another template's name is refused
+        app.rename_selected_template();
+        assert!(matches!(app.mode, AppMode::TemplateNamePrompt));
+        app.input_buffer = "Standup".to_string();
+        assert_eq!(
+            app.confirm_template_name(&storage),
+            Err("A template named 'Standup' already exists".to_string())
+        );
+        app.input_buffer = "Review".to_string();
+        app.confirm_template_name(&storage).unwrap();
+        assert!(matches!(app.mode, AppMode::TemplatePicker));
+        let stored: Vec<String> = storage
+            .load_templates()
+            .unwrap()
+            .into_iter()
+            .map(|template| template.name)
+            .collect();
+        assert_eq!(stored, ["Standup", "Review"]);
+
+        app.delete_selected_template(&storage).unwrap();
+        app.delete_selected_template(&storage).unwrap();
+        assert!(storage.load_templates().unwrap().is_empty());
+        assert!(matches!(app.mode, AppMode::Browse));
+    }
+
+    #[test]
+    fn test_session_notes_end_up_in_the_description() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.save_day(&mut storage);
+
+        app.open_session_note_prompt();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No active session to add a note to")
+        );
+
+        app.selected_index = 2;
+        app.start_timer_for_selected(&storage).unwrap();
+        for note in ["found root cause", "waiting on CI"] {
+            app.open_session_note_prompt();
+            assert!(matches!(app.mode, AppMode::SessionNotePrompt));
+            note.chars().for_each(|c| app.handle_session_note_char(c));
+            app.confirm_session_note(&storage).unwrap();
+        }
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Note added to session (2 notes)")
+        );
+        assert_eq!(app.active_timer.as_ref().unwrap().notes.len(), 2);
+
+        app.stop_active_timer(&mut storage).unwrap();
+        let description = &app.day_data.work_records[&7].description;
+        assert!(
+            description.starts_with("- [+0:00] found root cause\n- [+0:00] waiting on CI"),
+            "{}",
+            description
+        );
+    }
+
+    #[test]
+    fn test_stopping_a_session_selects_and_flashes_its_record() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.save_day(&mut storage);
+        // Sorted: Task 2, Task 5, Task 7
+        app.selected_index = 2;
+        app.start_timer_for_selected(&storage).unwrap();
+
+        app.stop_active_timer(&mut storage).unwrap();
+        assert_eq!(app.get_selected_record().unwrap().id, 7);
+        // The reloaded day is the new base, with nothing left to save
+        assert!(!app.is_dirty());
+        assert!(app.is_flashing(7));
+        assert!(!app.is_flashing(5));
+        let status = app.status_message.clone().unwrap();
+        assert!(
+            status.starts_with("Session stopped: updated #7 Task 7 → end "),
+            "{}",
+            status
+        );
+
+        app.frame += STOPPED_FLASH_FRAMES;
+        assert!(!app.is_flashing(7));
+    }
+
+    #[test]
+    fn test_bell_on_timer_changes_but_not_lock_pauses() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.save_day(&mut storage);
+        app.start_timer_for_selected(&storage).unwrap();
+        assert!(!app.take_bell());
+
+        app.config.timer.bell = true;
+        app.pause_active_timer(&storage).unwrap();
+        assert!(app.take_bell());
+        assert!(!app.take_bell());
+        app.resume_active_timer(&storage).unwrap();
+        assert!(app.take_bell());
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        app.lock_events = Some(receiver);
+        sender
+            .send((LockEvent::Locked, crate::timefmt::now_local()))
+            .unwrap();
+        app.poll_lock_events(&storage).unwrap();
+        assert!(app.paused_while_away());
+        assert!(!app.take_bell());
+
+        app.stop_active_timer(&mut storage).unwrap();
+        assert!(app.take_bell());
+    }
+
+    #[test]
+    fn test_lock_events_pause_and_resume_the_timer() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.save_day(&mut storage);
+        app.start_timer_for_selected(&storage).unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        app.lock_events = Some(receiver);
+
+        // Handled a while after it arrived: the pause starts when it arrived
+        let locked_at = crate::timefmt::now_local();
+        sender.send((LockEvent::Locked, locked_at)).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        app.poll_lock_events(&storage).unwrap();
+        assert_eq!(
+            app.active_timer.as_ref().unwrap().status,
+            TimerStatus::Paused
+        );
+        assert_eq!(
+            app.active_timer.as_ref().unwrap().paused_at,
+            Some(locked_at)
+        );
+        assert!(app.paused_while_away());
+        assert_eq!(
+            storage.load_active_timer().unwrap().unwrap().status,
+            TimerStatus::Paused
+        );
+
+        sender
+            .send((LockEvent::Unlocked, crate::timefmt::now_local()))
+            .unwrap();
+        app.poll_lock_events(&storage).unwrap();
+        assert_eq!(
+            app.active_timer.as_ref().unwrap().status,
+            TimerStatus::Running
+        );
+        assert!(!app.paused_while_away());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Welcome back, timer resumed")
+        );
+    }
+
+    #[test]
+    fn test_is_dirty_tracks_edits_since_last_load_or_save() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        assert!(!app.is_dirty());
+
+        app.add_new_record();
+        assert!(app.is_dirty());
+        app.save_day(&mut storage);
+        assert!(!app.is_dirty());
+
+        app.undo();
+        assert!(app.is_dirty());
+        app.redo();
+        assert!(!app.is_dirty());
+
+        app.load_new_day_data(DayData::new(date!(2025 - 11 - 07)));
+        assert!(!app.is_dirty());
+    }
+
+    #[test]
+    fn test_change_day_saves_only_a_dirty_day() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        let first_day = app.current_date;
+
+        app.navigate_to_next_day();
+        app.change_day(&mut storage, || Ok(None)).unwrap();
+        assert_eq!(app.day_data.date, date!(2025 - 11 - 07));
+        assert!(!app.date_changed);
+        // Nothing was edited, so the day was left without writing it
+        assert!(storage.get_last_modified(&first_day).is_none());
+        assert!(
+            storage
+                .load_with_tracking(first_day)
+                .unwrap()
+                .work_records
+                .is_empty()
+        );
+
+        app.add_new_record();
+        app.navigate_to_previous_day();
+        app.change_day(&mut storage, || Ok(None)).unwrap();
+        assert_eq!(
+            storage
+                .load_with_tracking(date!(2025 - 11 - 07))
+                .unwrap()
+                .work_records
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_rapid_day_navigation_loads_only_the_last_day() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        for day in 7..=10 {
+            let date = Date::from_calendar_date(2025, time::Month::November, day).unwrap();
+            let mut data = DayData::new(date);
+            data.add_record(WorkRecord::new(
+                1,
+                format!("Day {}", day),
+                crate::models::TimePoint::new(9, 0).unwrap(),
+                crate::models::TimePoint::new(10, 0).unwrap(),
+            ));
+            storage.save(&data).unwrap();
+        }
+        // Saving tracks the files; start from a storage that has loaded nothing
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+
+        // ] pressed, then held: three more arrive before input settles
+        app.navigate_to_next_day();
+        let mut held = vec![Some(1), Some(1), Some(-1), Some(1), None].into_iter();
+        app.change_day(&mut storage, || Ok(held.next().flatten()))
+            .unwrap();
+
+        assert_eq!(app.current_date, date!(2025 - 11 - 09));
+        assert_eq!(app.day_data.date, date!(2025 - 11 - 09));
+        assert_eq!(
+            app.get_selected_record().map(|r| r.name.as_str()),
+            Some("Day 9")
+        );
+        assert!(storage.get_last_modified(&date!(2025 - 11 - 09)).is_some());
+        for skipped in [date!(2025 - 11 - 07), date!(2025 - 11 - 08)] {
+            assert!(storage.get_last_modified(&skipped).is_none());
+        }
+    }
+
+    /// App with records 2, 5 and 7 saved in a temp dir
+    fn saved_app() -> (tempfile::TempDir, crate::storage::StorageManager, AppState) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.save_day(&mut storage);
+        (temp_dir, storage, app)
+    }
+
+    /// Edit the saved day the way a CLI command would, behind the app's back
+    fn edit_on_disk(dir: &std::path::Path, edit: impl FnOnce(&mut DayData)) {
+        // Make sure the modification time moves on
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let mut external = crate::storage::StorageManager::new_with_dir(dir.to_path_buf()).unwrap();
+        let mut day = external.load_with_tracking(date!(2025 - 11 - 06)).unwrap();
+        edit(&mut day);
+        external.save(&day).unwrap();
+    }
+
+    #[test]
+    fn test_external_change_replaces_a_clean_day() {
+        let (temp_dir, mut storage, mut app) = saved_app();
+        edit_on_disk(temp_dir.path(), |day| {
+            day.remove_record(7);
+        });
+
+        assert!(app.check_and_reload_if_modified(&mut storage));
+        assert_eq!(app.day_data.work_records.len(), 2);
+        assert!(!app.is_dirty());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Reloaded from disk: -1 record: 'Task 7'")
+        );
+    }
+
+    #[test]
+    fn test_external_timer_start_is_announced() {
+        let (temp_dir, mut storage, mut app) = saved_app();
+        let external =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        external
+            .start_timer("PROJ-2 fix".to_string(), None, None, None)
+            .unwrap();
+
+        assert!(app.check_and_reload_if_modified(&mut storage));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Reloaded from disk: timer started externally on 'PROJ-2 fix'")
+        );
+    }
+
+    #[test]
+    fn test_external_change_merges_with_unsaved_edits() {
+        let (temp_dir, mut storage, mut app) = saved_app();
+        app.day_data.work_records.get_mut(&2).unwrap().description = "mine".to_string();
+        edit_on_disk(temp_dir.path(), |day| {
+            day.work_records.get_mut(&7).unwrap().description = "disk".to_string();
+        });
+
+        assert!(app.check_and_reload_if_modified(&mut storage));
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.day_data.work_records[&2].description, "mine");
+        assert_eq!(app.day_data.work_records[&7].description, "disk");
+        // The merge is written straight away
+        assert!(!app.is_dirty());
+        let on_disk = storage.load_with_tracking(app.current_date).unwrap();
+        assert_eq!(on_disk, app.day_data);
+        assert!(app.status_message.unwrap().starts_with("Merged"));
+    }
+
+    #[test]
+    fn test_conflicting_external_change_asks_for_each_record() {
+        let (temp_dir, mut storage, mut app) = saved_app();
+        app.day_data.work_records.get_mut(&2).unwrap().description = "mine".to_string();
+        app.day_data.work_records.get_mut(&5).unwrap().description = "mine".to_string();
+        edit_on_disk(temp_dir.path(), |day| {
+            day.work_records.get_mut(&2).unwrap().description = "disk".to_string();
+            day.remove_record(5);
+        });
+
+        app.check_and_reload_if_modified(&mut storage);
+        assert!(matches!(app.mode, AppMode::MergeConflict));
+        assert_eq!(app.merge_conflicts.len(), 2);
+        assert_eq!(app.day_data.work_records[&2].description, "disk");
+
+        app.resolve_merge_conflict(Resolution::KeepMine);
+        assert_eq!(app.day_data.work_records[&2].description, "mine");
+        assert!(matches!(app.mode, AppMode::MergeConflict));
+
+        app.resolve_merge_conflict(Resolution::KeepBoth);
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.day_data.work_records[&5].description, "mine");
+        assert!(app.is_dirty());
+    }
+
+    #[test]
+    fn test_external_change_waits_while_a_dirty_day_is_being_edited() {
+        let (temp_dir, mut storage, mut app) = saved_app();
+        app.day_data.work_records.get_mut(&2).unwrap().description = "mine".to_string();
+        app.enter_edit_mode();
+        edit_on_disk(temp_dir.path(), |day| {
+            day.remove_record(7);
+        });
+
+        app.check_and_reload_if_modified(&mut storage);
+        assert_eq!(app.day_data.work_records.len(), 3);
+
+        app.exit_edit_mode();
+        app.check_and_reload_if_modified(&mut storage);
+        assert_eq!(app.day_data.work_records.len(), 2);
+        assert_eq!(app.day_data.work_records[&2].description, "mine");
+    }
+
+    #[test]
+    fn test_confirm_switch_session_rejects_empty_name() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+        app.open_switch_session();
+
+        assert_eq!(
+            app.confirm_switch_session(&mut storage).unwrap_err(),
+            "No task name given"
+        );
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(storage.load_active_timer().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_start_timer_rejects_blank_task_name() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.day_data.work_records.get_mut(&2).unwrap().name = "   ".to_string();
+
+        assert_eq!(
+            app.start_timer_for_selected(&storage).unwrap_err(),
+            "Task name cannot be empty"
+        );
+        assert!(app.active_timer.is_none());
+        assert!(storage.load_active_timer().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_switching_sessions_leaves_the_reloaded_day_clean() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.config.quick_tasks =
+            toml::from_str::<Config>("[[quick_tasks]]\nkey = \"1\"\nname = \"Email\"\n")
+                .unwrap()
+                .quick_tasks;
+        app.save_day(&mut storage);
+        app.start_timer_for_selected(&storage).unwrap();
+
+        app.start_quick_task(0, &mut storage).unwrap();
+        assert_eq!(
+            app.day_data,
+            storage.load_with_tracking(app.current_date).unwrap()
+        );
+        assert!(!app.is_dirty());
+    }
+
+    #[test]
+    fn test_quick_task_switches_the_session_to_its_task() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let config: Config = toml::from_str(
+            r#"
+[[quick_tasks]]
+key = "1"
+name = "Standup"
+description = "Daily sync"
+
+[[quick_tasks]]
+key = "2"
+name = "Email"
+"#,
+        )
+        .unwrap();
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), config);
+
+        let crate::ui::keymap::Resolved::QuickTask(index) =
+            app.keymap.resolve("1".parse().unwrap())
+        else {
+            panic!("1 isn't bound to a quick task");
+        };
+        app.start_quick_task(index, &mut storage).unwrap();
+        let timer = storage.load_active_timer().unwrap().unwrap();
+        assert_eq!(timer.task_name, "Standup");
+        assert_eq!(timer.description.as_deref(), Some("Daily sync"));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Session started: Standup")
+        );
+
+        // Pressing it again keeps the running session
+        app.start_quick_task(index, &mut storage).unwrap();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Standup is already running")
+        );
+        assert_eq!(
+            storage.load_active_timer().unwrap().unwrap().start_time,
+            timer.start_time
+        );
+
+        // Another quick task replaces it
+        app.start_quick_task(1, &mut storage).unwrap();
+        assert_eq!(app.active_timer.as_ref().unwrap().task_name, "Email");
+        assert_eq!(
+            app.start_quick_task(2, &mut storage).unwrap_err(),
+            "No quick task #3"
+        );
+    }
+
+    #[test]
+    fn test_annotation_prompt_notes_the_selected_task() {
+        let mut app = app_with_records();
+        app.open_annotation_prompt();
+        assert!(matches!(app.mode, AppMode::AnnotationPrompt));
+        for c in "over estimate".chars() {
+            app.handle_annotation_prompt_char(c);
+        }
+        app.confirm_annotation_prompt();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.day_data.annotation("Task 2"), Some("over estimate"));
+
+        // Prefilled with the current note; emptying it removes the note
+        app.open_annotation_prompt();
+        assert_eq!(app.input_buffer, "over estimate");
+        app.input_buffer.clear();
+        app.confirm_annotation_prompt();
+        assert_eq!(app.day_data.annotation("Task 2"), None);
+
+        app.undo();
+        assert_eq!(app.day_data.annotation("Task 2"), Some("over estimate"));
+    }
+
+    #[test]
+    fn test_estimate_prompt_sets_and_clears_estimate() {
+        let mut app = app_with_records();
+        app.open_estimate_prompt();
+        assert!(matches!(app.mode, AppMode::EstimatePrompt));
+        for c in "1:3x0".chars() {
+            app.handle_estimate_prompt_char(c);
+        }
+        app.confirm_estimate_prompt();
+        assert!(matches!(app.mode, AppMode::Browse));
+        let estimate = |app: &AppState| app.get_selected_record().unwrap().estimate_minutes;
+        assert_eq!(estimate(&app), Some(90));
+
+        // Prefilled in minutes; a bad value is reported and changes nothing
+        app.open_estimate_prompt();
+        assert_eq!(app.input_buffer, "90");
+        app.input_buffer = "1:75".to_string();
+        app.confirm_estimate_prompt();
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("Invalid estimate '1:75' (use minutes or H:MM)")
+        );
+        assert_eq!(estimate(&app), Some(90));
+
+        // Emptying it removes the estimate
+        app.open_estimate_prompt();
+        app.input_buffer.clear();
+        app.confirm_estimate_prompt();
+        assert_eq!(estimate(&app), None);
+
+        app.undo();
+        assert_eq!(estimate(&app), Some(90));
+    }
+
+    #[test]
+    fn test_parse_estimate() {
+        assert_eq!(parse_estimate(""), Ok(None));
+        assert_eq!(parse_estimate("45"), Ok(Some(45)));
+        assert_eq!(parse_estimate("2:05"), Ok(Some(125)));
+        assert_eq!(parse_estimate("0"), Ok(None));
+        assert!(parse_estimate(":").is_err());
+        assert!(parse_estimate("1:2:3").is_err());
+        // Too long to count in minutes
+        assert!(parse_estimate("71582789:00").is_err());
+        assert!(parse_estimate("99999999999").is_err());
+    }
+
+    #[test]
+    fn test_adjustment_prompt_sets_and_clears_adjustment() {
+        let mut app = app_with_records();
+        let total = app.day_data.total_minutes();
+        app.open_adjustment_prompt();
+        assert!(matches!(app.mode, AppMode::AdjustmentPrompt));
+        for c in "-3x0".chars() {
+            app.handle_adjustment_prompt_char(c);
+        }
+        app.confirm_adjustment_prompt();
+        assert!(matches!(app.mode, AppMode::Browse));
+        let adjustment = |app: &AppState| app.get_selected_record().unwrap().adjustment_minutes;
+        assert_eq!(adjustment(&app), Some(-30));
+        assert_eq!(app.day_data.total_minutes(), total - 30);
+
+        // Prefilled with its sign; a bad value is reported and changes nothing
+        app.open_adjustment_prompt();
+        assert_eq!(app.input_buffer, "-30");
+        app.input_buffer = "+1:75".to_string();
+        app.confirm_adjustment_prompt();
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("Invalid adjustment '+1:75' (use e.g. -30 or +1:30)")
+        );
+        assert_eq!(adjustment(&app), Some(-30));
+
+        // Emptying it removes the adjustment
+        app.open_adjustment_prompt();
+        app.input_buffer.clear();
+        app.confirm_adjustment_prompt();
+        assert_eq!(adjustment(&app), None);
+
+        app.undo();
+        assert_eq!(adjustment(&app), Some(-30));
+    }
+
+    #[test]
+    fn test_parse_adjustment() {
+        assert_eq!(parse_adjustment(""), Ok(None));
+        assert_eq!(parse_adjustment("45"), Ok(Some(45)));
+        assert_eq!(parse_adjustment("+1:30"), Ok(Some(90)));
+        assert_eq!(parse_adjustment("-1h 15m"), Ok(Some(-75)));
+        assert_eq!(parse_adjustment("-0"), Ok(None));
+        assert!(parse_adjustment("-").is_err());
+        assert!(parse_adjustment("--30").is_err());
+        // Too long to count in minutes
+        assert!(parse_adjustment("-3000000000").is_err());
+    }
+
+    #[test]
+    fn test_plan_audit_needs_a_plan_and_can_be_undone() {
+        let mut app = app_with_records();
+        app.toggle_plan_audit();
+        assert!(!app.show_plan_audit);
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("No plan for this day (mark it as planned with M)")
+        );
+
+        app.mark_day_planned();
+        assert_eq!(app.day_data.planned.len(), 3);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Marked 3 record(s) as the day's plan (O compares)")
+        );
+        assert!(app.plan_audit().is_none());
+
+        app.toggle_plan_audit();
+        app.day_data.remove_record(5);
+        let audit = app.plan_audit().unwrap();
+        assert_eq!(audit.describe(2), "on plan");
+        assert_eq!(audit.missed.len(), 1);
+
+        app.undo();
+        assert!(app.day_data.planned.is_empty());
+    }
+
+    #[test]
+    fn test_link_prompt_sets_and_clears_url() {
+        let mut app = app_with_records();
+        app.open_link_prompt();
+        assert!(matches!(app.mode, AppMode::LinkPrompt));
+        for c in "https://docs.example.com/x".chars() {
+            app.handle_link_prompt_char(c);
+        }
+        app.confirm_link_prompt();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert!(app.selected_record_has_url());
+
+        // Prefilled with the current link; emptying it removes the link
+        app.open_link_prompt();
+        assert_eq!(app.input_buffer, "https://docs.example.com/x");
+        app.input_buffer.clear();
+        app.confirm_link_prompt();
+        assert!(!app.selected_record_has_url());
+
+        app.undo();
+        assert!(app.selected_record_has_url());
+    }
+
+    #[test]
+    fn test_next_upcoming_only_when_enabled_and_today() {
+        use time::macros::datetime;
+
+        let mut app = app_with_records();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 10:37 UTC));
+        assert!(app.next_upcoming().is_none());
+
+        app.config.upcoming.enabled = true;
+        let (record, minutes) = app.next_upcoming().unwrap();
+        assert_eq!(record.id, 5);
+        assert_eq!(minutes, 23);
+
+        // Another day's plan has no countdown
+        app.clock = Clock::Fixed(datetime!(2025-11-05 10:37 UTC));
+        assert!(app.next_upcoming().is_none());
+    }
+
+    #[test]
+    fn test_upcoming_alert_rings_once_per_record() {
+        use time::macros::datetime;
+
+        let mut app = app_with_records();
+        app.config.upcoming.enabled = true;
+        app.config.upcoming.alert_lead_minutes = Some(10);
+
+        app.clock = Clock::Fixed(datetime!(2025-11-06 10:45 UTC));
+        assert!(!app.take_upcoming_alert());
+
+        app.clock = Clock::Fixed(datetime!(2025-11-06 10:50 UTC));
+        assert!(app.take_upcoming_alert());
+        app.clock = Clock::Fixed(datetime!(2025-11-06 10:55 UTC));
+        assert!(!app.take_upcoming_alert());
+
+        // The next record gets its own reminder
+        app.clock = Clock::Fixed(datetime!(2025-11-06 12:52 UTC));
+        assert!(app.take_upcoming_alert());
+    }
+
+    fn timer_on(date: Date, source_record_id: Option<u32>) -> TimerState {
+        use time::macros::datetime;
+
+        let start = datetime!(2025-11-06 09:00 UTC);
+        TimerState {
+            id: None,
+            task_name: "Task 5".to_string(),
+            description: None,
+            start_time: start,
+            end_time: None,
+            date,
+            status: crate::timer::TimerStatus::Running,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: start,
+            source_record_id,
+            source_record_date: Some(date),
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_auto_stop_at_ends_the_session_at_the_configured_time() {
+        use time::macros::datetime;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.save_day(&mut storage);
+        let timer = timer_on(date!(2025 - 11 - 06), None);
+        storage.save_active_timer(&timer).unwrap();
+        app.active_timer = Some(timer);
+
+        app.clock = Clock::Fixed(datetime!(2025-11-06 19:30 UTC));
+        assert_eq!(
+            app.auto_stop_overdue_timer(&mut storage),
+            Ok(false),
+            "off by default"
+        );
+
+        app.config.timer.auto_stop_at = Some("19:00".to_string());
+        app.clock = Clock::Fixed(datetime!(2025-11-06 18:59 UTC));
+        assert_eq!(app.auto_stop_overdue_timer(&mut storage), Ok(false));
+        assert!(app.active_timer.is_some());
+
+        app.clock = Clock::Fixed(datetime!(2025-11-06 19:04 UTC));
+        assert_eq!(app.auto_stop_overdue_timer(&mut storage), Ok(true));
+        assert!(app.active_timer.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("⏹ Session 'Task 5' auto-stopped at 19:00 ([timer] auto_stop_at)")
+        );
+        let logged = app
+            .day_data
+            .work_records
+            .values()
+            .find(|record| record.start == TimePoint::new(9, 0).unwrap() && record.id > 7)
+            .expect("the session's record");
+        assert_eq!(logged.end, TimePoint::new(19, 0).unwrap());
+        assert!(!app.is_dirty());
+    }
+
+    #[test]
+    fn test_midnight_rollover_detection() {
+        use crate::config::MidnightRollover;
+        use time::macros::datetime;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 23:59 UTC));
+
+        // The first tick only notes the date
+        assert_eq!(app.handle_midnight(&mut storage), Ok(false));
+        assert_eq!(app.handle_midnight(&mut storage), Ok(false));
+
+        // `stay` (the default) notices but leaves the view alone
+        app.clock = Clock::Fixed(datetime!(2025-11-07 00:00 UTC));
+        assert_eq!(app.handle_midnight(&mut storage), Ok(true));
+        assert_eq!(app.current_date, date!(2025 - 11 - 06));
+        assert!(!app.date_changed);
+
+        // `follow` moves on from yesterday, once
+        app.config.timer.on_midnight = MidnightRollover::Follow;
+        app.clock = Clock::Fixed(datetime!(2025-11-08 00:01 UTC));
+        app.current_date = date!(2025 - 11 - 07);
+        assert_eq!(app.handle_midnight(&mut storage), Ok(true));
+        assert_eq!(app.current_date, date!(2025 - 11 - 08));
+        assert!(app.date_changed);
+        assert_eq!(app.handle_midnight(&mut storage), Ok(false));
+
+        // ...but not from another day the user is looking at
+        app.date_changed = false;
+        app.current_date = date!(2025 - 10 - 01);
+        app.clock = Clock::Fixed(datetime!(2025-11-09 00:01 UTC));
+        assert_eq!(app.handle_midnight(&mut storage), Ok(true));
+        assert_eq!(app.current_date, date!(2025 - 10 - 01));
+
+        // A clock set back is no rollover
+        app.clock = Clock::Fixed(datetime!(2025-11-08 12:00 UTC));
+        assert_eq!(app.handle_midnight(&mut storage), Ok(false));
+    }
+
+    #[test]
+    fn test_midnight_split_moves_the_session_to_the_new_day() {
+        use crate::config::MidnightRollover;
+        use time::macros::datetime;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        app.config.timer.on_midnight = MidnightRollover::Split;
+        app.save_day(&mut storage);
+        let mut timer = timer_on(date!(2025 - 11 - 06), None);
+        timer.source_record_date = None;
+        timer.start_time = datetime!(2025-11-06 22:30 UTC);
+        storage.save_active_timer(&timer).unwrap();
+        app.active_timer = Some(timer);
+
+        app.clock = Clock::Fixed(datetime!(2025-11-06 23:59 UTC));
+        app.handle_midnight(&mut storage).unwrap();
+        app.clock = Clock::Fixed(datetime!(2025-11-07 00:00:20 UTC));
+        assert_eq!(app.handle_midnight(&mut storage), Ok(true));
+
+        // 22:30–00:00 landed on the old day, which was on screen and reloaded
+        assert_eq!(app.day_data.work_records.len(), 4);
+        let stored = storage.load_day(date!(2025 - 11 - 06)).unwrap();
+        assert!(stored.work_records.values().any(|record| {
+            record.name == "Task 5"
+                && record.start == TimePoint::new(22, 30).unwrap()
+                && record.total_minutes == 90
+        }));
+        let timer = app.active_timer.as_ref().unwrap();
+        assert_eq!(timer.start_time, datetime!(2025-11-07 00:00 UTC));
+        assert_eq!(app.timer_day(), Some(date!(2025 - 11 - 07)));
+        assert_eq!(app.current_date, date!(2025 - 11 - 07));
+        assert!(app.date_changed);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Session split at midnight, continuing on 2025-11-07")
+        );
+    }
+
+    #[test]
+    fn test_timer_source_index_resolves_from_source_record_id() {
+        let mut app = app_with_records();
+        assert_eq!(app.timer_source_index(), None);
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), Some(5)));
+        assert_eq!(app.timer_source_index(), Some(1));
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), Some(7)));
+        assert_eq!(app.timer_source_index(), Some(2));
+
+        // Same id on another day is a different record
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 05), Some(7)));
+        assert_eq!(app.timer_source_index(), None);
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), None));
+        assert_eq!(app.timer_source_index(), None);
+    }
+
+    /// Fields and old/new values of the selected record's remembered edits, newest first
+    fn remembered(app: &AppState) -> Vec<(RecordField, String, String)> {
+        app.selected_record_changes()
+            .iter()
+            .map(|change| (change.field, change.old.clone(), change.new.clone()))
+            .collect()
+    }
+
+    fn t(hour: u8, minute: u8) -> TimePoint {
+        TimePoint::new(hour, minute).unwrap()
+    }
+
+    #[test]
+    fn test_description_template_prefills_empty_descriptions_with_a_ticket() {
+        let mut app = app_with_records();
+        app.config.defaults.description_template = Some("{ticket}: ".to_string());
+        app.day_data.work_records.get_mut(&2).unwrap().name = "PROJ-12 Review".to_string();
+        app.selected_index = app.index_of_record_id(2).unwrap();
+        app.edit_field = EditField::Description;
+        app.enter_edit_mode();
+        assert_eq!(app.input_buffer, "PROJ-12: ");
+
+        // Reached by Tab from the end field too
+        app.edit_field = EditField::End;
+        app.next_field();
+        assert_eq!(app.input_buffer, "PROJ-12: ");
+        app.exit_edit_mode();
+
+        // A description already written is left as it is
+        app.day_data.work_records.get_mut(&2).unwrap().description = "done".to_string();
+        app.edit_field = EditField::Description;
+        app.enter_edit_mode();
+        assert_eq!(app.input_buffer, "done");
+        app.exit_edit_mode();
+
+        // No ticket in the name, nothing to fill in
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        app.edit_field = EditField::Description;
+        app.enter_edit_mode();
+        assert_eq!(app.input_buffer, "");
+    }
+
+    #[test]
+    fn test_saved_edit_is_remembered() {
+        let mut app = app_with_records();
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        app.edit_field = EditField::Start;
+        app.input_buffer = "11:15".to_string();
+        app.save_edit().unwrap();
+
+        assert_eq!(
+            remembered(&app),
+            [(RecordField::Start, "11:00".to_string(), "11:15".to_string())]
+        );
+
+        // Saving an unchanged field is not an edit
+        app.edit_field = EditField::Description;
+        app.input_buffer = String::new();
+        app.save_edit().unwrap();
+        assert_eq!(remembered(&app).len(), 1);
+    }
+
+    #[test]
+    fn test_time_set_to_now_is_remembered() {
+        let mut app = app_with_records();
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        // An end that can't be the current minute, so setting it to now changes it
+        let now = crate::timefmt::now_local();
+        let old = t((now.hour() + 12) % 24, now.minute());
+        app.day_data.work_records.get_mut(&5).unwrap().end = old;
+
+        app.edit_field = EditField::End;
+        app.set_current_time_on_field();
+        let new = app.day_data.work_records[&5].end;
+        assert_eq!(
+            remembered(&app),
+            [(RecordField::End, old.to_string(), new.to_string())]
+        );
+    }
+
+    #[test]
+    fn test_picker_rename_is_remembered() {
+        let mut app = app_with_records();
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        app.change_task_name();
+        assert!(matches!(app.mode, AppMode::TaskPicker));
+        for c in "Deploy".chars() {
+            app.handle_task_picker_char(c);
+        }
+        app.select_task_from_picker();
+
+        assert_eq!(
+            remembered(&app),
+            [(
+                RecordField::Name,
+                "Task 5".to_string(),
+                "Deploy".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_timer_end_update_is_remembered() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut app = app_with_records();
+        let now = crate::timefmt::now_local();
+        let old = t((now.hour() + 12) % 24, now.minute());
+        app.day_data.work_records.get_mut(&5).unwrap().end = old;
+        storage.save(&app.day_data).unwrap();
+
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        app.start_timer_for_selected(&storage).unwrap();
+        app.stop_active_timer(&mut storage).unwrap();
+
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        let changes = remembered(&app);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, RecordField::End);
+        assert_eq!(changes[0].1, old.to_string());
+        assert_eq!(changes[0].2, app.day_data.work_records[&5].end.to_string());
+    }
+
+    #[test]
+    fn test_revert_field_applies_the_inverse_only() {
+        let mut app = app_with_records();
+        app.selected_index = app.index_of_record_id(2).unwrap();
+        app.edit_field = EditField::Start;
+        app.input_buffer = "09:30".to_string();
+        app.save_edit().unwrap();
+        app.edit_field = EditField::End;
+        app.input_buffer = "10:15".to_string();
+        app.save_edit().unwrap();
+
+        // Newest first: the End edit, then the Start edit
+        app.open_revert_field();
+        assert!(matches!(app.mode, AppMode::RevertField));
+        app.move_revert_selection_down();
+        app.confirm_revert_field().unwrap();
+
+        assert!(matches!(app.mode, AppMode::Browse));
+        let record = &app.day_data.work_records[&2];
+        assert_eq!((record.start, record.end), (t(9, 0), t(10, 15)));
+        assert_eq!(record.total_minutes, 75);
+        // The revert is itself an edit
+        assert_eq!(
+            remembered(&app)[0],
+            (RecordField::Start, "09:30".to_string(), "09:00".to_string())
+        );
+
+        // and one undo step
+        app.undo();
+        assert_eq!(app.day_data.work_records[&2].start, t(9, 30));
+    }
+
+    #[test]
+    fn test_revert_field_needs_an_edit() {
+        let mut app = app_with_records();
+        app.open_revert_field();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No recent edits to this record")
+        );
+    }
+
+    #[test]
+    fn test_loading_day_flags_in_progress_record_without_timer() {
+        let mut app = app_with_records();
+        let mut day = app.day_data.clone();
+        day.work_records.get_mut(&5).unwrap().in_progress = true;
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), Some(5)));
+        app.load_new_day_data(day.clone());
+        assert_eq!(app.status_message, None);
+
+        app.active_timer = None;
+        app.load_new_day_data(day);
+        let status = app.status_message.clone().unwrap();
+        assert!(status.contains("#5"), "{}", status);
+
+        // Setting its end by hand finishes it
+        app.selected_index = app.index_of_record_id(5).unwrap();
+        app.edit_field = EditField::End;
+        app.input_buffer = "12:30".to_string();
+        app.save_edit().unwrap();
+        assert!(!app.day_data.work_records[&5].in_progress);
+    }
+
+    #[test]
+    fn test_header_totals_window_cycles_and_sums_stored_days() {
+        use time::macros::datetime;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let mut earlier = DayData::new(date!(2025 - 11 - 01));
+        earlier.add_record(WorkRecord::new(
+            1,
+            "Coding".to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(12, 0).unwrap(),
+        ));
+        storage.save(&earlier).unwrap();
+
+        let mut app = app_with_records();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 18:00 UTC));
+        app.refresh_window_totals(&storage).unwrap();
+        assert_eq!(app.window_worked_minutes, None);
+
+        app.cycle_totals_window();
+        assert_eq!(app.totals_window, TotalsWindow::Week);
+        app.refresh_window_totals(&storage).unwrap();
+        // 3h on screen plus 3h stored five days earlier
+        assert_eq!(app.window_worked_minutes, Some(6 * 60));
+
+        app.cycle_totals_window();
+        app.cycle_totals_window();
+        assert_eq!(app.totals_window, TotalsWindow::Today);
+        app.refresh_window_totals(&storage).unwrap();
+        assert_eq!(app.window_worked_minutes, None);
+    }
+
+    #[test]
+    fn test_focus_stats_load_for_today_only() {
+        use crate::timer::{SessionFocus, SessionLogEntry};
+        use time::macros::datetime;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        let storage_dir =
+            crate::storage::Storage::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+        storage_dir
+            .append_session_log(&SessionLogEntry {
+                date: date!(2025 - 11 - 06),
+                record_id: 7,
+                start: TimePoint::new(7, 0).unwrap(),
+                end: TimePoint::new(8, 0).unwrap(),
+                extended: false,
+                focus: SessionFocus {
+                    active_secs: Some(55 * 60),
+                    pauses: 2,
+                    paused_secs: 300,
+                },
+            })
+            .unwrap();
+
+        let mut app = app_with_records();
+        app.config.ui.show_day_breakdown = true;
+        app.clock = Clock::Fixed(datetime!(2025-11-06 18:00 UTC));
+        app.refresh_focus_stats(&storage).unwrap();
+        assert_eq!(app.focus_today.as_ref().map(FocusStats::sessions), Some(1));
+        assert!(crate::ui::view::day_totals_text(&app).ends_with(" · focus: 1 session, 2 pauses"));
+
+        app.clock = Clock::Fixed(datetime!(2025-11-07 09:00 UTC));
+        app.refresh_focus_stats(&storage).unwrap();
+        assert_eq!(app.focus_today, None);
+    }
+
+    #[test]
+    fn test_join_visual_selection() {
+        let mut app = app_with_records();
+        app.day_data.work_records.get_mut(&2).unwrap().end = TimePoint::new(11, 0).unwrap();
+        app.day_data.work_records.get_mut(&2).unwrap().description = "setup".to_string();
+        app.day_data.work_records.get_mut(&5).unwrap().description = "deploy".to_string();
+        app.selected_index = 0;
+        app.enter_visual_mode();
+        app.move_selection_down();
+        app.open_join_prompt();
+        assert!(matches!(app.mode, AppMode::JoinPrompt));
+        assert_eq!(app.input_buffer, "Task 2");
+
+        app.input_buffer = "Release".to_string();
+        app.confirm_join_prompt();
+        assert!(matches!(app.mode, AppMode::Browse));
+        let record = app.get_selected_record().unwrap();
+        assert_eq!((record.id, record.name.as_str()), (2, "Release"));
+        assert_eq!(record.start.to_string(), "09:00");
+        assert_eq!(record.end.to_string(), "12:00");
+        assert_eq!(record.description, "setup\ndeploy");
+        assert_eq!(app.day_data.work_records.len(), 2);
+
+        app.undo();
+        assert_eq!(app.day_data.work_records.len(), 3);
+    }
+
+    #[test]
+    fn test_join_refuses_a_gap_before_prompting() {
+        let mut app = app_with_records();
+        app.selected_index = 1;
+        app.enter_visual_mode();
+        app.move_selection_down();
+        app.open_join_prompt();
+        assert!(matches!(app.mode, AppMode::Visual));
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("Records must be contiguous, nothing between 12:00 and 13:00")
+        );
+    }
+
+    #[test]
+    fn test_shift_day_moves_every_record_in_one_undo_step() {
+        let mut app = app_with_records();
+        app.open_shift_prompt();
+        assert!(matches!(app.mode, AppMode::ShiftPrompt));
+        "-30m".chars().for_each(|c| app.handle_shift_prompt_char(c));
+        app.confirm_shift_prompt();
+
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Shifted 3 records by -30m")
+        );
+        let starts: Vec<String> = app
+            .day_data
+            .get_sorted_records()
+            .iter()
+            .map(|record| record.start.to_string())
+            .collect();
+        assert_eq!(starts, ["08:30", "10:30", "12:30"]);
+
+        app.undo();
+        assert_eq!(app.day_data.work_records[&2].start.to_string(), "09:00");
+    }
+
+    #[test]
+    fn test_shift_day_refuses_squeezing_and_bad_offsets() {
+        let mut app = app_with_records();
+        app.open_shift_prompt();
+        app.input_buffer = "+11h".to_string();
+        app.confirm_shift_prompt();
+        assert_eq!(
+            app.last_error_message.as_deref(),
+            Some("Shifting by +11h would leave #7 empty at the edge of the day")
+        );
+        assert_eq!(app.day_data.work_records[&7].start.to_string(), "13:00");
+
+        app.open_shift_prompt();
+        app.input_buffer = "soon".to_string();
+        app.confirm_shift_prompt();
+        assert!(
+            app.last_error_message
+                .as_deref()
+                .is_some_and(|e| e.starts_with("Invalid offset 'soon'"))
+        );
+    }
+
+    #[test]
+    fn test_gap_break_is_one_undo_step() {
+        use time::macros::datetime;
+
+        let mut app = app_with_records();
+        app.clock = Clock::Fixed(datetime!(2025-11-06 14:30 UTC));
+        assert!(!app.insert_gap_break(), "off by default");
+
+        app.config.timer.fill_gaps_with_break = true;
+        assert!(app.insert_gap_break());
+        let record = app.day_data.get_sorted_records().last().copied().unwrap();
+        assert!(record.is_break());
+        assert_eq!(record.start.to_string(), "14:00");
+        assert_eq!(record.end.to_string(), "14:30");
+
+        app.undo();
+        assert_eq!(app.day_data.work_records.len(), 3);
+
+        // Only today's timeline gets a break
+        app.clock = Clock::Fixed(datetime!(2025-11-07 14:30 UTC));
+        assert!(!app.insert_gap_break());
+    }
+
+    #[test]
+    fn test_start_timer_for_last_task_needs_today() {
+        use time::macros::datetime;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage =
+            crate::storage::StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut app = app_with_records();
+        app.clock = Clock::Fixed(datetime!(2025-11-07 09:00 UTC));
+        assert_eq!(
+            app.start_timer_for_last_task(&storage).unwrap_err(),
+            "Go to today to resume the last task"
+        );
+
+        let mut app = AppState::with_config(DayData::new(date!(2025 - 11 - 06)), Config::default());
+        app.clock = Clock::Fixed(datetime!(2025-11-06 09:00 UTC));
+        assert_eq!(
+            app.start_timer_for_last_task(&storage).unwrap_err(),
+            "No task worked on today"
+        );
+        assert!(app.active_timer.is_none());
+    }
+
+    #[test]
+    fn test_timer_day_linked_to_record() {
+        let mut app = app_with_records();
+        assert_eq!(app.timer_day(), None);
+        assert!(!app.timer_on_other_day());
+        assert!(app.check_timer_day().is_ok());
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), Some(5)));
+        assert_eq!(app.timer_day(), Some(date!(2025 - 11 - 06)));
+        assert!(!app.timer_on_other_day());
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 05), Some(5)));
+        assert!(app.timer_on_other_day());
+        assert_eq!(
+            app.check_timer_day().unwrap_err(),
+            "Session belongs to 2025-11-05, press g a to go there"
+        );
+    }
+
+    #[test]
+    fn test_timer_day_unlinked_uses_start_date() {
+        let mut app = app_with_records();
+        let mut timer = timer_on(date!(2025 - 11 - 05), None);
+        timer.source_record_date = None;
+        app.active_timer = Some(timer);
+
+        assert_eq!(app.timer_day(), Some(date!(2025 - 11 - 05)));
+        assert!(app.timer_on_other_day());
+        assert_eq!(app.timer_source_record(), None);
+    }
+
+    #[test]
+    fn test_timer_day_across_midnight_stays_on_start_day() {
+        use time::macros::datetime;
+
+        // Started yesterday evening, still running after midnight
+        let mut app = app_with_records();
+        let mut timer = timer_on(date!(2025 - 11 - 05), None);
+        timer.source_record_date = None;
+        timer.start_time = datetime!(2025-11-05 23:00 UTC);
+        app.active_timer = Some(timer);
+        app.clock = Clock::Fixed(datetime!(2025-11-06 01:00 UTC));
+
+        assert_eq!(app.current_date, app.clock.today());
+        assert!(app.timer_on_other_day());
+
+        app.current_date = date!(2025 - 11 - 05);
+        assert!(!app.timer_on_other_day());
+        assert!(app.check_timer_day().is_ok());
+    }
+
+    #[test]
+    fn test_goto_timer_record_selects_it() {
+        let mut app = app_with_records();
+        assert_eq!(
+            app.goto_timer_record().unwrap_err(),
+            "No session is running"
+        );
+
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 06), Some(7)));
+        app.open_goto_id();
+        app.goto_timer_record().unwrap();
+        assert!(matches!(app.mode, AppMode::Browse));
+        assert_eq!(app.selected_index, 2);
+    }
+
+    #[test]
+    fn test_goto_timer_record_on_another_day() {
+        let mut app = app_with_records();
+        app.active_timer = Some(timer_on(date!(2025 - 11 - 05), Some(3)));
+        app.goto_timer_record().unwrap();
+        assert!(app.date_changed);
+        assert_eq!(app.current_date, date!(2025 - 11 - 05));
+
+        let mut previous_day = DayData::new(date!(2025 - 11 - 05));
+        for (id, hour) in [(1, 8), (3, 10)] {
+            previous_day.add_record(WorkRecord::new(
+                id,
+                format!("Task {}", id),
+                TimePoint::new(hour, 0).unwrap(),
+                TimePoint::new(hour + 1, 0).unwrap(),
+            ));
+        }
+        app.load_new_day_data(previous_day);
+        assert_eq!(app.selected_index, 1);
+    }
+
+    fn app_with_placeholder_end() -> AppState {
+        use time::macros::datetime;
+
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        for (id, start, end) in [
+            (1, (9, 0), (10, 0)),
+            (2, (13, 0), (18, 0)),
+            (3, (16, 0), (17, 0)),
+        ] {
+            day.add_record(WorkRecord::new(
+                id,
+                format!("Task {}", id),
+                TimePoint::new(start.0, start.1).unwrap(),
+                TimePoint::new(end.0, end.1).unwrap(),
+            ));
+        }
+        let mut app = AppState::with_config(day, Config::default());
+        app.clock = Clock::Fixed(datetime!(2025-11-06 14:30 UTC));
+        app
+    }
+
+    #[test]
+    fn test_future_end_rule_off_by_default() {
+        let mut app = app_with_placeholder_end();
+        app.apply_future_end_rule();
+        assert!(app.status_message.is_none());
+        assert_eq!(
+            app.day_data.work_records[&2].end,
+            TimePoint::new(18, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_future_end_rule_warns() {
+        let mut app = app_with_placeholder_end();
+        app.config.validation.clamp_future_end = crate::config::FutureEndRule::Warn;
+        app.apply_future_end_rule();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("⚠ End time in the future: #2 (18:00)")
+        );
+        assert_eq!(
+            app.day_data.work_records[&2].end,
+            TimePoint::new(18, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_future_end_rule_clamps_and_undoes() {
+        let mut app = app_with_placeholder_end();
+        app.config.validation.clamp_future_end = crate::config::FutureEndRule::Clamp;
+        app.apply_future_end_rule();
+
+        let clamped = &app.day_data.work_records[&2];
+        assert_eq!(clamped.end, TimePoint::new(14, 30).unwrap());
+        assert_eq!(clamped.total_minutes, 90);
+        // The planned record is left alone
+        assert_eq!(
+            app.day_data.work_records[&3].end,
+            TimePoint::new(17, 0).unwrap()
+        );
+        assert!(
+            app.status_message
+                .as_deref()
+                .unwrap()
+                .contains("#2 (18:00)")
+        );
+
+        app.undo();
+        assert_eq!(
+            app.day_data.work_records[&2].end,
+            TimePoint::new(18, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_future_end_rule_skips_other_days() {
+        use time::macros::datetime;
+
+        let mut app = app_with_placeholder_end();
+        app.config.validation.clamp_future_end = crate::config::FutureEndRule::Clamp;
+        app.clock = Clock::Fixed(datetime!(2025-11-07 08:00 UTC));
+        app.apply_future_end_rule();
+        assert_eq!(
+            app.day_data.work_records[&2].end,
+            TimePoint::new(18, 0).unwrap()
+        );
+        assert!(app.status_message.is_none());
+    }
 }
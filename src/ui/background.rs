@@ -0,0 +1,201 @@
+//! Terminal background detection for `[theme] auto_detect`
+//!
+//! The terminal is asked for its background color with the OSC 11 query and
+//! answers with an escape sequence like `ESC ] 11 ; rgb:ffff/ffff/ffff BEL`. The
+//! query goes to the controlling tty in raw mode, before the alternate screen is
+//! entered, and waits a short while at most. A terminal that stays silent (or
+//! anything else going wrong) just means no answer, and the configured theme is
+//! used as is.
+//!
+//! The query is followed by a DA1 request (`ESC [ c`), which every terminal
+//! answers, in order. Reading up to that answer consumes the OSC 11 one too, so a
+//! slow reply can't reach the TUI later as keystrokes.
+
+use crate::config::Background;
+use std::time::Duration;
+
+/// How long to wait for the terminal's answer
+pub const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Background color from an OSC 11 answer, as 8-bit red, green and blue
+///
+/// Accepts the `rgb:R/G/B` form with 1 to 4 hex digits per component, which
+/// nearly every terminal sends, and `#RRGGBB`. The answer may end with BEL or
+/// ST (`ESC \`), and anything before the sequence is skipped.
+pub fn parse_osc11(response: &[u8]) -> Option<(u8, u8, u8)> {
+    let text = std::str::from_utf8(response).ok()?;
+    let start = text.find("\x1b]11;")? + "\x1b]11;".len();
+    let rest = &text[start..];
+    let end = rest.find(['\x07', '\x1b'])?;
+    let color = &rest[..end];
+
+    if let Some(components) = color.strip_prefix("rgb:") {
+        let mut parts = components.split('/').map(scale_component);
+        let rgb = (parts.next()??, parts.next()??, parts.next()??);
+        return parts.next().is_none().then_some(rgb);
+    }
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    Some((
+        scale_component(&hex[0..2])?,
+        scale_component(&hex[2..4])?,
+        scale_component(&hex[4..6])?,
+    ))
+}
+
+/// Whether `response` holds the answer to DA1, `ESC [ ? 6 4 ; 1 ; 2 c` or similar
+pub fn has_da1_answer(response: &[u8]) -> bool {
+    let mut rest = response;
+    while let Some(start) = rest.windows(3).position(|w| w == b"\x1b[?") {
+        let params = &rest[start + 3..];
+        let end = params
+            .iter()
+            .position(|b| !(b.is_ascii_digit() || *b == b';'));
+        if end.is_some_and(|end| params[end] == b'c') {
+            return true;
+        }
+        rest = params;
+    }
+    false
+}
+
+/// Scale a 1 to 4 digit hex component to 0..=255
+fn scale_component(hex: &str) -> Option<u8> {
+    if hex.is_empty() || hex.len() > 4 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    let max = (1u32 << (4 * hex.len())) - 1;
+    Some((value * 255 / max) as u8)
+}
+
+/// Light or dark, by perceived brightness of `rgb`
+pub fn classify((r, g, b): (u8, u8, u8)) -> Background {
+    let luma = 299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b);
+    if luma > 128 * 1000 {
+        Background::Light
+    } else {
+        Background::Dark
+    }
+}
+
+/// Ask the terminal for its background; raw mode must be on
+///
+/// None when there is no tty, the terminal doesn't answer within `timeout`, or
+/// the answer can't be read.
+#[cfg(unix)]
+pub fn detect(timeout: Duration) -> Option<Background> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::fd::AsRawFd;
+    use std::time::Instant;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    tty.write_all(b"\x1b]11;?\x07\x1b[c").ok()?;
+    tty.flush().ok()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut response = Vec::new();
+    // The answers are a few dozen bytes; more than this is not them
+    while response.len() < 512 {
+        let left = deadline.saturating_duration_since(Instant::now());
+        let mut fds = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `fds` is a single valid pollfd for an open descriptor
+        let ready = unsafe { libc::poll(&mut fds, 1, left.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            return None;
+        }
+        let mut buf = [0u8; 64];
+        let read = tty.read(&mut buf).ok()?;
+        if read == 0 {
+            return None;
+        }
+        response.extend_from_slice(&buf[..read]);
+        // Answers come in order, so once DA1 is in, OSC 11 is in or never will be
+        if has_da1_answer(&response) {
+            return parse_osc11(&response).map(classify);
+        }
+    }
+    None
+}
+
+/// Ask the terminal for its background (not supported on this platform)
+#[cfg(not(unix))]
+pub fn detect(_timeout: Duration) -> Option<Background> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rgb_form_with_bel() {
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:ffff/ffff/ffff\x07"),
+            Some((255, 255, 255))
+        );
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:1919/1919/2323\x07"),
+            Some((25, 25, 35))
+        );
+    }
+
+    #[test]
+    fn test_parse_rgb_form_with_st_and_short_components() {
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:fd/f6/e3\x1b\\"),
+            Some((253, 246, 227))
+        );
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:f/0/8\x07"), Some((255, 0, 136)));
+    }
+
+    #[test]
+    fn test_parse_hex_form_after_other_input() {
+        assert_eq!(parse_osc11(b"x\x1b]11;#282828\x07"), Some((40, 40, 40)));
+    }
+
+    #[test]
+    fn test_da1_answer_ends_the_read() {
+        let both = b"\x1b]11;rgb:0000/0000/0000\x07\x1b[?64;1;2;6;22c";
+        assert!(has_da1_answer(both));
+        assert_eq!(parse_osc11(both), Some((0, 0, 0)));
+        // A terminal without OSC 11 answers DA1 alone
+        assert!(has_da1_answer(b"\x1b[?1;2c"));
+        assert!(!has_da1_answer(b"\x1b]11;rgb:0000/0000/0000\x07"));
+        assert!(!has_da1_answer(b"\x1b[?64;1"));
+        assert!(!has_da1_answer(b"\x1b[?1;2x\x1b[?"));
+    }
+
+    #[test]
+    fn test_parse_rejects_partial_or_malformed_answers() {
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:ffff/ffff/ff"), None);
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:ffff/ffff\x07"), None);
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:ffff/ffff/ffff/ffff\x07"), None);
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:fffff/0/0\x07"), None);
+        assert_eq!(parse_osc11(b"\x1b]11;#fff\x07"), None);
+        assert_eq!(parse_osc11("\x1b]11;#ééé\x07".as_bytes()), None);
+        assert_eq!(parse_osc11(b"\x1b]10;rgb:ffff/ffff/ffff\x07"), None);
+        assert_eq!(parse_osc11(b""), None);
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify((255, 255, 255)), Background::Light);
+        assert_eq!(classify((253, 246, 227)), Background::Light);
+        assert_eq!(classify((25, 25, 35)), Background::Dark);
+        assert_eq!(classify((40, 40, 40)), Background::Dark);
+        // Saturated blue is dark despite its high blue component
+        assert_eq!(classify((0, 0, 255)), Background::Dark);
+    }
+}
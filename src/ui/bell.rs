@@ -0,0 +1,77 @@
+//! Audible feedback on timer changes (`[timer] bell`)
+//!
+//! The bell is the terminal's own BEL character, written between frames. It
+//! doesn't move the cursor or print anything, so the alternate screen is left
+//! as drawn; terminals that are muted or set to a visual bell handle it their way.
+
+use crate::config::TimerConfig;
+use std::io::{self, Write};
+
+/// A change to the running timer made from the TUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerEvent {
+    Started,
+    Stopped,
+    Paused,
+    Resumed,
+    /// Stopped by `[timer] auto_stop_at`
+    AutoStopped,
+    /// Paused or resumed because the session was locked or woke up
+    Away,
+}
+
+/// Whether `event` rings the bell under `config`
+///
+/// Pauses for a locked screen are left silent, since nobody is there to hear
+/// them and the bell would greet you on return.
+pub fn rings(config: &TimerConfig, event: TimerEvent) -> bool {
+    config.bell && event != TimerEvent::Away
+}
+
+/// Write the BEL character to `out`
+pub fn ring(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(b"\x07")?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [TimerEvent; 6] = [
+        TimerEvent::Started,
+        TimerEvent::Stopped,
+        TimerEvent::Paused,
+        TimerEvent::Resumed,
+        TimerEvent::AutoStopped,
+        TimerEvent::Away,
+    ];
+
+    #[test]
+    fn test_off_by_default() {
+        let config = TimerConfig::default();
+        assert!(ALL.iter().all(|&event| !rings(&config, event)));
+    }
+
+    #[test]
+    fn test_bell_rings_for_timer_changes_but_not_lock_pauses() {
+        let config = TimerConfig {
+            bell: true,
+            ..TimerConfig::default()
+        };
+        for event in ALL {
+            assert_eq!(
+                rings(&config, event),
+                event != TimerEvent::Away,
+                "{event:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ring_writes_bel_only() {
+        let mut out = Vec::new();
+        ring(&mut out).unwrap();
+        assert_eq!(out, b"\x07");
+    }
+}
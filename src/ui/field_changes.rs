@@ -0,0 +1,137 @@
+//! Recent field edits per record, for reverting one field without a full-day undo
+//!
+//! Kept in memory for the session only. Each record remembers its last
+//! `MAX_CHANGES_PER_RECORD` edits, newest last.
+
+use crate::models::WorkRecord;
+use std::collections::{HashMap, VecDeque};
+use time::{Date, OffsetDateTime};
+
+const MAX_CHANGES_PER_RECORD: usize = 10;
+
+/// A record field whose edits are remembered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordField {
+    Name,
+    Start,
+    End,
+    Description,
+}
+
+impl RecordField {
+    pub fn label(self) -> &'static str {
+        match self {
+            RecordField::Name => "Name",
+            RecordField::Start => "Start",
+            RecordField::End => "End",
+            RecordField::Description => "Description",
+        }
+    }
+
+    /// The field's current value in `record`, as text
+    pub fn value_of(self, record: &WorkRecord) -> String {
+        match self {
+            RecordField::Name => record.name.clone(),
+            RecordField::Start => record.start.to_string(),
+            RecordField::End => record.end.to_string(),
+            RecordField::Description => record.description.clone(),
+        }
+    }
+}
+
+/// One edit: the field's value before and after, as shown in the table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: RecordField,
+    pub old: String,
+    pub new: String,
+    pub at: OffsetDateTime,
+}
+
+#[derive(Debug, Default)]
+pub struct FieldChanges {
+    records: HashMap<(Date, u32), VecDeque<FieldChange>>,
+}
+
+impl FieldChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember `change` to record `id` on `date`; edits that change nothing are skipped
+    pub fn push(&mut self, date: Date, id: u32, change: FieldChange) {
+        if change.old == change.new {
+            return;
+        }
+        let changes = self.records.entry((date, id)).or_default();
+        if changes.len() >= MAX_CHANGES_PER_RECORD {
+            changes.pop_front();
+        }
+        changes.push_back(change);
+    }
+
+    /// Changes to record `id` on `date`, newest first
+    pub fn for_record(&self, date: Date, id: u32) -> Vec<&FieldChange> {
+        self.records
+            .get(&(date, id))
+            .map(|changes| changes.iter().rev().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    fn change(field: RecordField, old: &str, new: &str) -> FieldChange {
+        FieldChange {
+            field,
+            old: old.to_string(),
+            new: new.to_string(),
+            at: datetime!(2025-11-06 14:00 UTC),
+        }
+    }
+
+    #[test]
+    fn test_changes_are_listed_newest_first_per_record() {
+        let day = date!(2025 - 11 - 06);
+        let mut changes = FieldChanges::new();
+        changes.push(day, 1, change(RecordField::Start, "09:00", "09:15"));
+        changes.push(day, 2, change(RecordField::Name, "Review", "Code review"));
+        changes.push(day, 1, change(RecordField::End, "10:00", "10:30"));
+
+        let fields: Vec<RecordField> = changes
+            .for_record(day, 1)
+            .iter()
+            .map(|change| change.field)
+            .collect();
+        assert_eq!(fields, [RecordField::End, RecordField::Start]);
+        assert_eq!(changes.for_record(day, 2).len(), 1);
+        // The same id on another day is another record
+        assert!(changes.for_record(date!(2025 - 11 - 07), 1).is_empty());
+    }
+
+    #[test]
+    fn test_no_op_edits_are_skipped() {
+        let day = date!(2025 - 11 - 06);
+        let mut changes = FieldChanges::new();
+        changes.push(day, 1, change(RecordField::Start, "09:00", "09:00"));
+        assert!(changes.for_record(day, 1).is_empty());
+    }
+
+    #[test]
+    fn test_only_the_last_changes_are_kept() {
+        let day = date!(2025 - 11 - 06);
+        let mut changes = FieldChanges::new();
+        for minute in 0..=MAX_CHANGES_PER_RECORD {
+            let new = format!("09:{:02}", minute + 1);
+            changes.push(day, 1, change(RecordField::Start, "09:00", &new));
+        }
+
+        let kept = changes.for_record(day, 1);
+        assert_eq!(kept.len(), MAX_CHANGES_PER_RECORD);
+        assert_eq!(kept[0].new, format!("09:{:02}", MAX_CHANGES_PER_RECORD + 1));
+        assert_eq!(kept.last().unwrap().new, "09:02");
+    }
+}
@@ -0,0 +1,282 @@
+//! Footer key hints that follow what can be done right now
+//!
+//! Browse and visual mode offer many keys, most of which do nothing in a given
+//! moment (`S` on an empty day, `J` with one record selected). Their hints are
+//! picked here from the mode and a few facts about the app, most useful first,
+//! with the palette (`?`) behind them for the rest. Prompts keep their fixed
+//! hints in `view::mode_hint`.
+
+use crate::timer::TimerStatus;
+use crate::ui::AppMode;
+use crate::ui::app_state::CommandAction;
+use crate::ui::text_width::{display_width, truncate_to_width};
+
+/// Hints shown in browse mode at most, Quit and the palette included
+pub const MAX_HINTS: usize = 12;
+
+const SEPARATOR: &str = " | ";
+
+/// What the hints depend on besides the mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HintContext {
+    /// The viewed day has records
+    pub has_records: bool,
+    /// State of the session timer, None when none is running or paused
+    pub timer: Option<TimerStatus>,
+    /// A ticket tracker is configured, so `T` and `L` work
+    pub integrations: bool,
+    /// Records in the visual selection
+    pub selected: usize,
+}
+
+/// One key hint; `action` is set when the key can be remapped in `[keys]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub action: Option<CommandAction>,
+    pub key: &'static str,
+    pub label: &'static str,
+}
+
+impl Hint {
+    fn fixed(key: &'static str, label: &'static str) -> Self {
+        Self {
+            action: None,
+            key,
+            label,
+        }
+    }
+
+    fn remappable(action: CommandAction, key: &'static str, label: &'static str) -> Self {
+        Self {
+            action: Some(action),
+            key,
+            label,
+        }
+    }
+}
+
+/// Hints for `mode`, most useful first; None for modes with fixed hints
+pub fn hints(mode: &AppMode, context: HintContext) -> Option<Vec<Hint>> {
+    match mode {
+        AppMode::Browse => Some(browse(context)),
+        AppMode::Visual => Some(visual(context)),
+        _ => None,
+    }
+}
+
+fn browse(context: HintContext) -> Vec<Hint> {
+    let mut hints = Vec::new();
+    if context.has_records {
+        hints.push(Hint::fixed("↑/↓", "Row"));
+        hints.push(Hint::remappable(CommandAction::Edit, "Enter", "Edit"));
+    }
+    hints.push(Hint::remappable(CommandAction::New, "n", "New"));
+    match context.timer {
+        Some(TimerStatus::Running) => {
+            hints.push(Hint::remappable(
+                CommandAction::StartTimer,
+                "S",
+                "Stop session",
+            ));
+            hints.push(Hint::remappable(CommandAction::PauseTimer, "P", "Pause"));
+        }
+        Some(TimerStatus::Paused) => {
+            hints.push(Hint::remappable(
+                CommandAction::StartTimer,
+                "S",
+                "Stop session",
+            ));
+            hints.push(Hint::remappable(CommandAction::PauseTimer, "P", "Resume"));
+        }
+        Some(TimerStatus::Stopped) | None if context.has_records => {
+            hints.push(Hint::remappable(
+                CommandAction::StartTimer,
+                "S",
+                "Start session",
+            ));
+        }
+        _ => {}
+    }
+    if context.has_records {
+        hints.push(Hint::remappable(CommandAction::Delete, "d", "Delete"));
+        hints.push(Hint::remappable(CommandAction::Visual, "v", "Visual"));
+    }
+    hints.push(Hint::fixed("[/]", "Day"));
+    hints.push(Hint::fixed("C", "Calendar"));
+    if context.has_records {
+        if context.integrations {
+            hints.push(Hint::fixed("T", "Ticket"));
+            hints.push(Hint::fixed("L", "Worklog"));
+        }
+        hints.push(Hint::remappable(CommandAction::Change, "c", "Change"));
+        hints.push(Hint::remappable(CommandAction::Break, "b", "Break"));
+        hints.push(Hint::remappable(CommandAction::SetNow, "t", "Now"));
+        hints.push(Hint::fixed("←/→", "Field"));
+        hints.push(Hint::remappable(CommandAction::GotoId, "g", "Go to ID"));
+    }
+
+    hints.truncate(MAX_HINTS - 2);
+    hints.push(Hint::remappable(CommandAction::Quit, "q", "Quit"));
+    hints.push(Hint::fixed("?", "more"));
+    hints
+}
+
+fn visual(context: HintContext) -> Vec<Hint> {
+    let mut hints = vec![
+        Hint::fixed("↑/↓", "Extend selection"),
+        Hint::fixed("d", "Delete"),
+    ];
+    if context.selected >= 2 {
+        hints.push(Hint::fixed("J", "Join"));
+    }
+    hints.push(Hint::fixed("$", "Billable"));
+    hints.push(Hint::fixed("Esc", "Exit visual"));
+    hints
+}
+
+/// Fit a " | "-separated hint line into `width` columns by leaving hints out
+///
+/// The last two hints (Quit and the palette, or a prompt's Enter and Esc) stay
+/// the longest; hints before them go from the end. Hints are never cut in the
+/// middle unless even the last one alone is too wide.
+pub fn fit(help: &str, width: usize) -> String {
+    let mut hints: Vec<&str> = help.split(SEPARATOR).collect();
+    while display_width(&hints.join(SEPARATOR)) > width && hints.len() > 1 {
+        let drop = hints.len().saturating_sub(3);
+        hints.remove(drop);
+    }
+    truncate_to_width(&hints.join(SEPARATOR), width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(hints: &[Hint]) -> Vec<String> {
+        hints
+            .iter()
+            .map(|hint| format!("{}: {}", hint.key, hint.label))
+            .collect()
+    }
+
+    fn browse_with(context: HintContext) -> Vec<String> {
+        keys(&hints(&AppMode::Browse, context).unwrap())
+    }
+
+    #[test]
+    fn test_empty_day_offers_only_what_works() {
+        let hints = browse_with(HintContext::default());
+        assert_eq!(
+            hints,
+            ["n: New", "[/]: Day", "C: Calendar", "q: Quit", "?: more"]
+        );
+    }
+
+    #[test]
+    fn test_records_without_timer_offer_start() {
+        let hints = browse_with(HintContext {
+            has_records: true,
+            ..HintContext::default()
+        });
+        assert_eq!(hints.len(), MAX_HINTS);
+        assert_eq!(
+            hints[..5],
+            [
+                "↑/↓: Row",
+                "Enter: Edit",
+                "n: New",
+                "S: Start session",
+                "d: Delete"
+            ]
+        );
+        assert_eq!(hints[MAX_HINTS - 2..], ["q: Quit", "?: more"]);
+    }
+
+    #[test]
+    fn test_running_timer_offers_stop_and_pause() {
+        let running = browse_with(HintContext {
+            has_records: true,
+            timer: Some(TimerStatus::Running),
+            ..HintContext::default()
+        });
+        assert!(running.contains(&"S: Stop session".to_string()));
+        assert!(running.contains(&"P: Pause".to_string()));
+        assert!(!running.contains(&"S: Start session".to_string()));
+
+        // A session can run while an empty day is viewed
+        let paused = browse_with(HintContext {
+            timer: Some(TimerStatus::Paused),
+            ..HintContext::default()
+        });
+        assert_eq!(paused[1..3], ["S: Stop session", "P: Resume"]);
+    }
+
+    #[test]
+    fn test_tracker_keys_only_with_integrations() {
+        let context = HintContext {
+            has_records: true,
+            integrations: true,
+            ..HintContext::default()
+        };
+        let hints = browse_with(context);
+        assert_eq!(hints[8..10], ["T: Ticket", "L: Worklog"]);
+        assert!(!browse_with(HintContext::default()).contains(&"T: Ticket".to_string()));
+        let without = browse_with(HintContext {
+            has_records: true,
+            ..HintContext::default()
+        });
+        assert!(!without.contains(&"T: Ticket".to_string()));
+    }
+
+    #[test]
+    fn test_visual_mode_offers_join_for_two_or_more() {
+        let one = keys(
+            &hints(
+                &AppMode::Visual,
+                HintContext {
+                    has_records: true,
+                    selected: 1,
+                    ..HintContext::default()
+                },
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            one,
+            [
+                "↑/↓: Extend selection",
+                "d: Delete",
+                "$: Billable",
+                "Esc: Exit visual"
+            ]
+        );
+
+        let two = keys(
+            &hints(
+                &AppMode::Visual,
+                HintContext {
+                    has_records: true,
+                    selected: 2,
+                    ..HintContext::default()
+                },
+            )
+            .unwrap(),
+        );
+        assert!(two.contains(&"J: Join".to_string()));
+    }
+
+    #[test]
+    fn test_prompts_keep_fixed_hints() {
+        assert_eq!(hints(&AppMode::Edit, HintContext::default()), None);
+    }
+
+    #[test]
+    fn test_fit_leaves_out_hints_before_the_last_two() {
+        let help = "n: New | [/]: Day | C: Calendar | q: Quit | ?: more";
+        assert_eq!(fit(help, 80), help);
+        assert_eq!(fit(help, 40), "n: New | [/]: Day | q: Quit | ?: more");
+        assert_eq!(fit(help, 25), "q: Quit | ?: more");
+        assert_eq!(fit(help, 10), "?: more");
+        assert_eq!(fit(help, 5), "?: m…");
+    }
+}
@@ -21,8 +21,14 @@ impl History {
         self.redo_stack.clear();
     }
 
+    /// Restore the previous snapshot
+    ///
+    /// Record ids are never rolled back: the restored `last_id` is the high-water mark
+    /// of both states, so an id handed out before the undo (and possibly seen by the CLI)
+    /// is not reused by the next add.
     pub fn undo(&mut self, current_state: DayData) -> Option<DayData> {
-        if let Some(previous_state) = self.undo_stack.pop() {
+        if let Some(mut previous_state) = self.undo_stack.pop() {
+            previous_state.last_id = previous_state.last_id.max(current_state.last_id);
             self.redo_stack.push(current_state);
             Some(previous_state)
         } else {
@@ -30,8 +36,10 @@ impl History {
         }
     }
 
+    /// Re-apply an undone snapshot, keeping ids monotonic like `undo`
     pub fn redo(&mut self, current_state: DayData) -> Option<DayData> {
-        if let Some(next_state) = self.redo_stack.pop() {
+        if let Some(mut next_state) = self.redo_stack.pop() {
+            next_state.last_id = next_state.last_id.max(current_state.last_id);
             self.undo_stack.push(current_state);
             Some(next_state)
         } else {
@@ -329,4 +337,46 @@ mod tests {
         assert_eq!(history.undo_stack.len(), 4);
         assert_eq!(history.redo_stack.len(), 1);
     }
+
+    #[test]
+    fn test_add_undo_add_produces_distinct_ids() {
+        let mut history = History::new();
+        let mut day = create_day_with_record(1, "Task1");
+
+        // Add a record
+        history.push(day.clone());
+        let first_id = day.next_id();
+        day.add_record(WorkRecord::new(
+            first_id,
+            "Added".to_string(),
+            TimePoint::new(10, 0).unwrap(),
+            TimePoint::new(11, 0).unwrap(),
+        ));
+
+        // Undo it, then add again
+        let mut day = history.undo(day).unwrap();
+        assert!(!day.work_records.contains_key(&first_id));
+        history.push(day.clone());
+        let second_id = day.next_id();
+
+        assert_ne!(first_id, second_id);
+        assert!(second_id > first_id);
+    }
+
+    #[test]
+    fn test_redo_keeps_high_water_mark() {
+        let mut history = History::new();
+        let mut day = create_day_with_record(1, "Task1");
+
+        history.push(day.clone());
+        day.next_id();
+        let day = history.undo(day).unwrap();
+        assert_eq!(day.last_id, 2);
+
+        // Hand out another id after the undo, then redo
+        let mut day = day;
+        day.next_id();
+        let day = history.redo(day).unwrap();
+        assert_eq!(day.last_id, 3);
+    }
 }
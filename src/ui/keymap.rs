@@ -0,0 +1,537 @@
+//! Browse-mode key bindings: the built-in keys, `[keys]` overrides, and their labels
+//!
+//! `main.rs` turns each key press into a `KeyBinding` and asks the map what it
+//! does. The footer and the command palette take their key labels from here too,
+//! so help text follows whatever the user remapped.
+//!
+//! An override replaces the action's built-in keys rather than adding to them.
+//! `[[quick_tasks]]` keys are resolved here too, after the overrides.
+
+use super::app_state::CommandAction;
+use crate::config::QuickTask;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A key without modifiers other than Ctrl; Shift is part of the character ("S")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Tab,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A key press as written in `[keys]`: "q", "Ctrl+c", "Enter", "Tab"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub ctrl: bool,
+    pub key: Key,
+}
+
+impl KeyBinding {
+    pub const fn plain(key: Key) -> Self {
+        KeyBinding { ctrl: false, key }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::plain(Key::Char(c))
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Unknown key '{}'", s);
+        let (ctrl, name) = match s.split_once('+') {
+            Some((modifier, name)) if modifier.eq_ignore_ascii_case("ctrl") && !name.is_empty() => {
+                (true, name)
+            }
+            Some(_) if s != "+" => return Err(invalid()),
+            _ => (false, s),
+        };
+
+        let mut chars = name.chars();
+        let key = match (chars.next(), chars.next()) {
+            // Ctrl combinations are case-insensitive, like the terminal reports them
+            (Some(c), None) if ctrl => Key::Char(c.to_ascii_lowercase()),
+            (Some(c), None) if !c.is_whitespace() => Key::Char(c),
+            _ => match name.to_ascii_lowercase().as_str() {
+                "enter" => Key::Enter,
+                "esc" => Key::Esc,
+                "tab" => Key::Tab,
+                "backspace" => Key::Backspace,
+                "space" => Key::Char(' '),
+                "up" => Key::Up,
+                "down" => Key::Down,
+                "left" => Key::Left,
+                "right" => Key::Right,
+                _ => return Err(invalid()),
+            },
+        };
+        Ok(KeyBinding { ctrl, key })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        match self.key {
+            Key::Char(' ') => write!(f, "Space"),
+            Key::Char(c) if self.ctrl => write!(f, "{}", c.to_ascii_uppercase()),
+            Key::Char(c) => write!(f, "{}", c),
+            Key::Enter => write!(f, "Enter"),
+            Key::Esc => write!(f, "Esc"),
+            Key::Tab => write!(f, "Tab"),
+            Key::Backspace => write!(f, "Backspace"),
+            Key::Up => write!(f, "↑"),
+            Key::Down => write!(f, "↓"),
+            Key::Left => write!(f, "←"),
+            Key::Right => write!(f, "→"),
+        }
+    }
+}
+
+/// Name of each remappable action in `[keys]`, with its built-in keys
+const ACTIONS: &[(&str, CommandAction, &[KeyBinding])] = &[
+    (
+        "move_up",
+        CommandAction::MoveUp,
+        &[KeyBinding::plain(Key::Up), KeyBinding::char('k')],
+    ),
+    (
+        "move_down",
+        CommandAction::MoveDown,
+        &[KeyBinding::plain(Key::Down), KeyBinding::char('j')],
+    ),
+    (
+        "move_left",
+        CommandAction::MoveLeft,
+        &[KeyBinding::plain(Key::Left), KeyBinding::char('h')],
+    ),
+    (
+        "move_right",
+        CommandAction::MoveRight,
+        &[KeyBinding::plain(Key::Right), KeyBinding::char('l')],
+    ),
+    (
+        "edit",
+        CommandAction::Edit,
+        &[KeyBinding::plain(Key::Enter), KeyBinding::char('i')],
+    ),
+    ("change", CommandAction::Change, &[KeyBinding::char('c')]),
+    ("new", CommandAction::New, &[KeyBinding::char('n')]),
+    ("break", CommandAction::Break, &[KeyBinding::char('b')]),
+    ("delete", CommandAction::Delete, &[KeyBinding::char('d')]),
+    ("visual", CommandAction::Visual, &[KeyBinding::char('v')]),
+    ("set_now", CommandAction::SetNow, &[KeyBinding::char('t')]),
+    ("undo", CommandAction::Undo, &[KeyBinding::char('u')]),
+    ("redo", CommandAction::Redo, &[KeyBinding::char('r')]),
+    ("save", CommandAction::Save, &[KeyBinding::char('s')]),
+    (
+        "session",
+        CommandAction::StartTimer,
+        &[KeyBinding::char('S')],
+    ),
+    (
+        "switch_session",
+        CommandAction::SwitchSession,
+        &[KeyBinding::char('w')],
+    ),
+    (
+        "note_session",
+        CommandAction::NoteSession,
+        &[KeyBinding::char('J')],
+    ),
+    (
+        "resume_last_task",
+        CommandAction::ResumeLastTask,
+        &[KeyBinding::char('A')],
+    ),
+    (
+        "backfill",
+        CommandAction::BackfillTimer,
+        &[KeyBinding::char('B')],
+    ),
+    ("pause", CommandAction::PauseTimer, &[KeyBinding::char('P')]),
+    ("goto_id", CommandAction::GotoId, &[KeyBinding::char('g')]),
+    (
+        "recent_days",
+        CommandAction::RecentDays,
+        &[KeyBinding::char('G')],
+    ),
+    ("set_link", CommandAction::SetLink, &[KeyBinding::char('U')]),
+    (
+        "annotate",
+        CommandAction::Annotate,
+        &[KeyBinding::char('N')],
+    ),
+    (
+        "set_estimate",
+        CommandAction::SetEstimate,
+        &[KeyBinding::char('E')],
+    ),
+    (
+        "set_adjustment",
+        CommandAction::SetAdjustment,
+        &[KeyBinding::char('K')],
+    ),
+    (
+        "edit_fields",
+        CommandAction::EditFields,
+        &[KeyBinding::char('F')],
+    ),
+    (
+        "revert_field",
+        CommandAction::RevertField,
+        &[KeyBinding::char('H')],
+    ),
+    (
+        "clear_day",
+        CommandAction::ClearDay,
+        &[KeyBinding::char('X')],
+    ),
+    (
+        "shift_day",
+        CommandAction::ShiftDay,
+        &[KeyBinding::char('D')],
+    ),
+    (
+        "save_template",
+        CommandAction::SaveTemplate,
+        &[KeyBinding::char('Y')],
+    ),
+    (
+        "insert_template",
+        CommandAction::InsertTemplate,
+        &[KeyBinding::char('I')],
+    ),
+    (
+        "relative_times",
+        CommandAction::ToggleRelativeTimes,
+        &[KeyBinding::char('R')],
+    ),
+    (
+        "mark_planned",
+        CommandAction::MarkPlanned,
+        &[KeyBinding::char('M')],
+    ),
+    (
+        "plan_audit",
+        CommandAction::TogglePlanAudit,
+        &[KeyBinding::char('O')],
+    ),
+    (
+        "totals_window",
+        CommandAction::CycleTotalsWindow,
+        &[KeyBinding::plain(Key::Tab)],
+    ),
+    (
+        "toggle_project",
+        CommandAction::ToggleProject,
+        &[KeyBinding::char('z')],
+    ),
+    (
+        "focus_summary",
+        CommandAction::FocusSummary,
+        &[KeyBinding::char('f')],
+    ),
+    ("flag", CommandAction::ToggleFlag, &[KeyBinding::char('m')]),
+    (
+        "billable",
+        CommandAction::ToggleBillable,
+        &[KeyBinding::char('$')],
+    ),
+    (
+        "next_flag",
+        CommandAction::NextFlag,
+        &[KeyBinding::char('}')],
+    ),
+    (
+        "previous_flag",
+        CommandAction::PreviousFlag,
+        &[KeyBinding::char('{')],
+    ),
+    (
+        "longest",
+        CommandAction::JumpLongest,
+        &[KeyBinding::char(')')],
+    ),
+    (
+        "shortest",
+        CommandAction::JumpShortest,
+        &[KeyBinding::char('(')],
+    ),
+    ("quit", CommandAction::Quit, &[KeyBinding::char('q')]),
+];
+
+/// What a key press does in Browse mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolved {
+    /// Bound by `[keys]`
+    Action(CommandAction),
+    /// Starts the `[[quick_tasks]]` entry at this index
+    QuickTask(usize),
+    /// A built-in key whose action was moved elsewhere; does nothing
+    Unbound,
+    /// Not affected by `[keys]`; handled as built in
+    BuiltIn,
+}
+
+/// Key bindings in effect: the built-in ones with `[keys]` overrides applied
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    overrides: Vec<(CommandAction, KeyBinding)>,
+    quick_tasks: Vec<(usize, KeyBinding)>,
+}
+
+impl KeyMap {
+    /// Apply `[keys]` entries (`quit = "Ctrl+c"`) and quick task keys
+    ///
+    /// Bad entries are skipped and described, as are quick tasks whose key is
+    /// already taken by an action (built in or from `[keys]`) or an earlier quick task.
+    pub fn from_config(
+        keys: &BTreeMap<String, String>,
+        quick_tasks: &[QuickTask],
+    ) -> (Self, Vec<String>) {
+        let mut map = KeyMap::default();
+        let mut errors = Vec::new();
+        for (name, key) in keys {
+            let Some(&(_, action, _)) = ACTIONS.iter().find(|(known, _, _)| known == name) else {
+                errors.push(format!("Unknown action '{}' in [keys]", name));
+                continue;
+            };
+            match key.parse() {
+                Ok(binding) => map.overrides.push((action, binding)),
+                Err(e) => errors.push(format!("{} in [keys] {}", e, name)),
+            }
+        }
+        for (index, task) in quick_tasks.iter().enumerate() {
+            let binding = match task.key.parse::<KeyBinding>() {
+                Ok(binding) => binding,
+                Err(e) => {
+                    errors.push(format!("{} for quick task '{}'", e, task.name));
+                    continue;
+                }
+            };
+            if let Some(action) = map.bound_action(binding) {
+                errors.push(format!(
+                    "Key '{}' for quick task '{}' is already bound to {}",
+                    binding, task.name, action
+                ));
+                continue;
+            }
+            if map.quick_tasks.iter().any(|(_, b)| *b == binding) {
+                errors.push(format!(
+                    "Key '{}' for quick task '{}' is already bound",
+                    binding, task.name
+                ));
+                continue;
+            }
+            map.quick_tasks.push((index, binding));
+        }
+        (map, errors)
+    }
+
+    /// What `binding` does, given the overrides
+    pub fn resolve(&self, binding: KeyBinding) -> Resolved {
+        if let Some(&(action, _)) = self.overrides.iter().find(|(_, b)| *b == binding) {
+            return Resolved::Action(action);
+        }
+        if let Some(&(index, _)) = self.quick_tasks.iter().find(|(_, b)| *b == binding) {
+            return Resolved::QuickTask(index);
+        }
+        let moved = ACTIONS.iter().any(|(_, action, defaults)| {
+            defaults.contains(&binding) && self.override_for(*action).is_some()
+        });
+        if moved {
+            Resolved::Unbound
+        } else {
+            Resolved::BuiltIn
+        }
+    }
+
+    /// Key label for `action`: its override, else `default` as shown in the built-in help
+    pub fn label(&self, action: CommandAction, default: &str) -> String {
+        match self.override_for(action) {
+            Some(binding) => binding.to_string(),
+            None => default.to_string(),
+        }
+    }
+
+    /// Key label for the quick task at `index`, if it has a usable key
+    pub fn quick_task_label(&self, index: usize) -> Option<String> {
+        self.quick_tasks
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, binding)| binding.to_string())
+    }
+
+    /// Name of the action `binding` triggers, its override or else its built-in keys
+    fn bound_action(&self, binding: KeyBinding) -> Option<&'static str> {
+        ACTIONS
+            .iter()
+            .find(|(_, action, defaults)| match self.override_for(*action) {
+                Some(bound) => bound == binding,
+                None => defaults.contains(&binding),
+            })
+            .map(|(name, _, _)| *name)
+    }
+
+    fn override_for(&self, action: CommandAction) -> Option<KeyBinding> {
+        self.overrides
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, binding)| *binding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, key)| (name.to_string(), key.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_and_label_bindings() {
+        let parse = |s: &str| s.parse::<KeyBinding>();
+        assert_eq!(parse("q"), Ok(KeyBinding::char('q')));
+        assert_eq!(parse("Q"), Ok(KeyBinding::char('Q')));
+        assert_eq!(parse("+"), Ok(KeyBinding::char('+')));
+        assert_eq!(
+            parse("ctrl+C"),
+            Ok(KeyBinding {
+                ctrl: true,
+                key: Key::Char('c')
+            })
+        );
+        assert_eq!(parse("Tab"), Ok(KeyBinding::plain(Key::Tab)));
+        assert_eq!(parse("Alt+x"), Err("Unknown key 'Alt+x'".to_string()));
+        assert_eq!(parse("Home"), Err("Unknown key 'Home'".to_string()));
+
+        assert_eq!(parse("Ctrl+c").unwrap().to_string(), "Ctrl+C");
+        assert_eq!(parse("space").unwrap().to_string(), "Space");
+    }
+
+    #[test]
+    fn test_override_replaces_built_in_key() {
+        let (map, errors) = KeyMap::from_config(&keys(&[("quit", "Ctrl+c")]), &[]);
+        assert!(errors.is_empty());
+
+        let ctrl_c = "Ctrl+c".parse().unwrap();
+        assert_eq!(map.resolve(ctrl_c), Resolved::Action(CommandAction::Quit));
+        assert_eq!(map.resolve(KeyBinding::char('q')), Resolved::Unbound);
+        assert_eq!(map.resolve(KeyBinding::char('n')), Resolved::BuiltIn);
+
+        assert_eq!(map.label(CommandAction::Quit, "q"), "Ctrl+C");
+        assert_eq!(map.label(CommandAction::New, "n"), "n");
+    }
+
+    #[test]
+    fn test_bad_entries_are_reported_and_skipped() {
+        let (map, errors) = KeyMap::from_config(
+            &keys(&[("fly", "f"), ("quit", "Hyper+q"), ("new", "a")]),
+            &[],
+        );
+        assert_eq!(
+            errors,
+            [
+                "Unknown action 'fly' in [keys]",
+                "Unknown key 'Hyper+q' in [keys] quit",
+            ]
+        );
+        assert_eq!(
+            map.resolve(KeyBinding::char('a')),
+            Resolved::Action(CommandAction::New)
+        );
+        assert_eq!(map.resolve(KeyBinding::char('q')), Resolved::BuiltIn);
+    }
+
+    fn quick(key: &str, name: &str) -> QuickTask {
+        QuickTask {
+            key: key.to_string(),
+            name: name.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_quick_task_keys_resolve_to_their_task() {
+        let tasks = [
+            quick("1", "Standup"),
+            quick("2", "Email"),
+            quick("x", "Review"),
+        ];
+        let (map, errors) = KeyMap::from_config(&BTreeMap::new(), &tasks);
+        assert!(errors.is_empty());
+
+        assert_eq!(map.resolve(KeyBinding::char('1')), Resolved::QuickTask(0));
+        assert_eq!(map.resolve(KeyBinding::char('2')), Resolved::QuickTask(1));
+        assert_eq!(map.resolve(KeyBinding::char('x')), Resolved::QuickTask(2));
+        assert_eq!(map.resolve(KeyBinding::char('3')), Resolved::BuiltIn);
+        assert_eq!(map.quick_task_label(1).as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_quick_task_key_conflicts_are_reported() {
+        let tasks = [
+            quick("1", "Standup"),
+            quick("1", "Email"),
+            quick("Ctrl+c", "Review"),
+            quick("Hyper+x", "Lunch"),
+        ];
+        let (map, errors) = KeyMap::from_config(&keys(&[("quit", "Ctrl+c")]), &tasks);
+        assert_eq!(
+            errors,
+            [
+                "Key '1' for quick task 'Email' is already bound",
+                "Key 'Ctrl+C' for quick task 'Review' is already bound to quit",
+                "Unknown key 'Hyper+x' for quick task 'Lunch'",
+            ]
+        );
+        assert_eq!(map.resolve(KeyBinding::char('1')), Resolved::QuickTask(0));
+        assert_eq!(
+            map.resolve("Ctrl+c".parse().unwrap()),
+            Resolved::Action(CommandAction::Quit)
+        );
+        assert_eq!(map.quick_task_label(2), None);
+    }
+
+    #[test]
+    fn test_quick_task_keys_cannot_shadow_built_in_keys() {
+        let tasks = [
+            quick("q", "Lunch"),
+            quick("j", "Standup"),
+            quick("s", "Review"),
+            quick("x", "Email"),
+        ];
+        // "s" is free once save moves to Ctrl+s; "x" is taken by undo's new key
+        let (map, errors) =
+            KeyMap::from_config(&keys(&[("save", "Ctrl+s"), ("undo", "x")]), &tasks);
+        assert_eq!(
+            errors,
+            [
+                "Key 'q' for quick task 'Lunch' is already bound to quit",
+                "Key 'j' for quick task 'Standup' is already bound to move_down",
+                "Key 'x' for quick task 'Email' is already bound to undo",
+            ]
+        );
+        assert_eq!(map.resolve(KeyBinding::char('q')), Resolved::BuiltIn);
+        assert_eq!(map.resolve(KeyBinding::char('j')), Resolved::BuiltIn);
+        assert_eq!(map.resolve(KeyBinding::char('s')), Resolved::QuickTask(2));
+        assert_eq!(map.quick_task_label(0), None);
+    }
+}
@@ -1,5 +1,20 @@
 pub mod app_state;
+pub mod background;
+pub mod bell;
+pub mod field_changes;
+pub mod hints;
 pub mod history;
+pub mod keymap;
+pub mod plain;
+pub mod record_filter;
+pub mod reload_diff;
 pub mod render;
+pub mod render_meta;
+pub mod spinner;
+pub mod text_width;
+pub mod upcoming;
+pub mod view;
 
+#[allow(unused_imports)]
+pub use app_state::Clock;
 pub use app_state::{AppMode, AppState, EditField};
@@ -0,0 +1,644 @@
+//! Plain line renderer for screen readers (`[ui] screen_reader` or `--plain`)
+//!
+//! No borders, icons or colour: the screen is a list of sentences read top to
+//! bottom. Mode and status come first, modals become a prompt section above the
+//! records, and the focused line is marked with `>`.
+
+use crate::config::FieldDef;
+use crate::models::{WorkRecord, humanize_minutes};
+use crate::timefmt::format_minutes;
+use crate::timer::TimerStatus;
+use crate::ui::view::{self, SummaryLine, TimerLocation};
+use crate::ui::{AppMode, AppState, EditField};
+use ratatui::{Frame, text::Line, widgets::Paragraph};
+use unicode_width::UnicodeWidthStr;
+
+/// Lines of the screen, with the one holding focus kept in view
+struct Screen {
+    lines: Vec<String>,
+    focus: Option<usize>,
+}
+
+impl Screen {
+    fn push(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    /// Push a list entry, `>` when it has focus
+    fn push_item(&mut self, focused: bool, text: impl AsRef<str>) {
+        if focused {
+            self.focus = Some(self.lines.len());
+        }
+        let marker = if focused { ">" } else { " " };
+        self.lines.push(format!("{} {}", marker, text.as_ref()));
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &AppState) {
+    let area = frame.size();
+    let screen = screen(app);
+
+    let mut rows = Vec::new();
+    let mut focus_row = 0;
+    for (i, line) in screen.lines.iter().enumerate() {
+        if screen.focus == Some(i) {
+            focus_row = rows.len();
+        }
+        rows.extend(wrap(line, area.width as usize));
+    }
+
+    // Scroll only as far as needed to show the focused line
+    let height = area.height as usize;
+    let offset = (focus_row + 1).saturating_sub(height);
+    let lines: Vec<Line> = rows.into_iter().skip(offset).map(Line::from).collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn screen(app: &AppState) -> Screen {
+    let mut screen = Screen {
+        lines: Vec::new(),
+        focus: None,
+    };
+
+    announce(&mut screen, app);
+    prompt(&mut screen, app);
+    records(&mut screen, app);
+    summary(&mut screen, app);
+
+    screen.push("");
+    if let Some(link) = view::selected_link(app) {
+        screen.push(format!("T opens {}", link));
+    }
+    let (help, _) = view::mode_hint(app);
+    screen.push(format!("Keys: {}", plain_keys(&help)));
+    screen
+}
+
+/// Mode, notices and session state, in the order they matter
+fn announce(screen: &mut Screen, app: &AppState) {
+    let (_, label) = view::mode_hint(app);
+    let mut title = format!("WorkTimer {}", app.config.ui.format_date(app.current_date));
+    if !app.profile.is_default() {
+        title.push_str(&format!(", profile {}", app.profile.name()));
+    }
+    title.push_str(&format!(". {} mode.", sentence_case(label)));
+    if app.safe_mode {
+        title.push_str(" Safe mode.");
+    }
+    screen.push(title);
+
+    if let Some(error) = &app.last_error_message {
+        screen.push(format!(
+            "Error: {} Press any key to dismiss.",
+            sentence(error)
+        ));
+    }
+    if let Some(notice) = &app.url_notice {
+        screen.push(format!(
+            "Link: {} {} Press any key to close.",
+            sentence(&notice.message),
+            notice.url
+        ));
+    }
+    if let Some(message) = &app.status_message {
+        screen.push(format!("Status: {}", sentence(message)));
+    }
+    if let Some(error) = &app.save_error {
+        screen.push(format!("Warning: {}", sentence(error)));
+    }
+
+    if app.day_data.is_total_clamped() {
+        screen.push("Warning: adjustments exceed tracked time.");
+    }
+    screen.push(format!(
+        "{}.",
+        view::day_totals_text(app).replace(" |", ",")
+    ));
+    if let Some((record, minutes)) = app.next_upcoming() {
+        screen.push(format!(
+            "Next: {} in {}.",
+            record.name,
+            humanize_minutes(minutes)
+        ));
+    }
+
+    if let Some(timer) = &app.active_timer {
+        let state = match timer.status {
+            TimerStatus::Running => "running",
+            TimerStatus::Paused if app.paused_while_away() => "paused while away",
+            TimerStatus::Paused => "paused",
+            TimerStatus::Stopped => "stopped",
+        };
+        let mut line = format!(
+            "Session {}: {}, {} elapsed",
+            state,
+            timer.task_name,
+            view::timer_elapsed_text(app, timer)
+        );
+        match view::timer_location(app) {
+            Some(TimerLocation::Here(id)) => line.push_str(&format!(", record {}", id)),
+            Some(TimerLocation::OtherDay(date, Some(id))) => {
+                line.push_str(&format!(", record {} on {}", id, date));
+            }
+            Some(TimerLocation::OtherDay(date, None)) => line.push_str(&format!(", on {}", date)),
+            None => {}
+        }
+        match timer.notes.len() {
+            0 => {}
+            1 => line.push_str(", 1 note"),
+            count => line.push_str(&format!(", {} notes", count)),
+        }
+        line.push('.');
+        if let Some((_, notice)) = app
+            .budget_status_for(&timer.task_name)
+            .and_then(view::budget_notice)
+        {
+            line.push_str(&format!(" Budget: {}.", notice));
+        }
+        screen.push(line);
+    }
+}
+
+/// The open modal or edit, as a prompt and its options
+fn prompt(screen: &mut Screen, app: &AppState) {
+    match app.mode {
+        AppMode::Browse | AppMode::Visual | AppMode::Summary => {}
+        AppMode::Edit => {
+            screen.push("");
+            let field = match app.edit_field {
+                EditField::Name => "name",
+                EditField::Start => "start time",
+                EditField::End => "end time",
+                EditField::Description => "description",
+            };
+            let mut line = format!("Editing {}: {}", field, typed(&app.input_buffer));
+            if matches!(app.edit_field, EditField::Start | EditField::End) {
+                line.push_str(&format!(", digit {} of 4", app.time_cursor.min(3) + 1));
+            }
+            screen.push_item(true, line);
+        }
+        AppMode::CommandPalette => {
+            let commands = app.get_filtered_commands();
+            screen.push("");
+            screen.push(format!(
+                "Search commands: {}",
+                typed(&app.command_palette_input)
+            ));
+            let quick_tasks = app.quick_task_hints();
+            if !quick_tasks.is_empty() {
+                let keys: Vec<String> = quick_tasks
+                    .iter()
+                    .map(|(key, name)| format!("{}: {}", plain_keys(key), name))
+                    .collect();
+                screen.push(format!("Quick tasks: {}.", keys.join(", ")));
+            }
+            screen.push(format!("{} commands found.", commands.len()));
+            for (i, (_, _, command)) in commands.iter().enumerate() {
+                screen.push_item(
+                    i == app.command_palette_selected,
+                    format!(
+                        "{}: {}",
+                        plain_keys(&app.command_key(command)),
+                        command.description
+                    ),
+                );
+            }
+        }
+        AppMode::Calendar => {
+            let date = app.calendar_selected_date;
+            let ui = &app.config.ui;
+            let mut line = format!(
+                "Choose date: {}, {}",
+                ui.format_date(date),
+                crate::timefmt::format_date(date, "%A", ui.locale)
+            );
+            if date == app.clock.today() {
+                line.push_str(", today");
+            }
+            if app.timer_day() == Some(date) {
+                line.push_str(", session day");
+            }
+            screen.push("");
+            screen.push_item(true, line);
+        }
+        AppMode::TaskPicker => {
+            let tasks = app.get_filtered_task_names();
+            screen.push("");
+            screen.push(format!("{}.", view::task_picker_header(app)));
+            screen.push(format!("Task name: {}", typed(&app.input_buffer)));
+            if tasks.is_empty() && !app.input_buffer.is_empty() {
+                screen.push_item(true, format!("Create new task: {}", app.input_buffer));
+            } else if tasks.is_empty() {
+                screen.push("No existing tasks. Type to create a new one.");
+            }
+            for (i, name) in tasks.iter().enumerate() {
+                screen.push_item(i == app.task_picker_selected, name);
+            }
+        }
+        AppMode::ConfirmClear => {
+            screen.push("");
+            screen.push_item(
+                true,
+                format!("{} Press y to clear.", view::clear_day_question(app)),
+            );
+        }
+        AppMode::ConfirmCascade => {
+            if let Some(question) = view::cascade_question(app) {
+                screen.push("");
+                screen.push_item(true, format!("{} Press y to move it.", question));
+            }
+        }
+        AppMode::MergeConflict => {
+            if let Some((question, mine, disk)) = view::merge_conflict_text(app) {
+                screen.push("");
+                screen.push_item(true, format!("{}.", question));
+                screen.push(format!("Mine: {}.", mine));
+                screen.push(format!("Disk: {}.", disk));
+            }
+        }
+        AppMode::GotoId => {
+            screen.push("");
+            screen.push_item(
+                true,
+                format!("Go to record ID: {}", typed(&app.input_buffer)),
+            );
+        }
+        AppMode::LinkPrompt => {
+            screen.push("");
+            screen.push_item(true, format!("Record link: {}", typed(&app.input_buffer)));
+        }
+        AppMode::AnnotationPrompt => {
+            screen.push("");
+            screen.push_item(true, format!("Task note: {}", typed(&app.input_buffer)));
+        }
+        AppMode::SessionNotePrompt => {
+            screen.push("");
+            screen.push_item(true, format!("Session note: {}", typed(&app.input_buffer)));
+        }
+        AppMode::JoinPrompt => {
+            screen.push("");
+            screen.push_item(
+                true,
+                format!(
+                    "Join {} records as: {}",
+                    app.join_ids.len(),
+                    typed(&app.input_buffer)
+                ),
+            );
+        }
+        AppMode::TemplatePicker => {
+            screen.push("");
+            screen.push(format!("Filter templates: {}", typed(&app.input_buffer)));
+            for (i, template) in app.filtered_templates().iter().enumerate() {
+                screen.push_item(
+                    i == app.template_selected,
+                    format!("{}: {}", template.name, view::template_detail(template)),
+                );
+            }
+        }
+        AppMode::TemplateNamePrompt => {
+            screen.push("");
+            let label = match &app.template_renaming {
+                Some(old) => format!("Rename template {} to", old),
+                None => "Save as template named".to_string(),
+            };
+            screen.push_item(true, format!("{}: {}", label, typed(&app.input_buffer)));
+        }
+        AppMode::ShiftPrompt => {
+            screen.push("");
+            screen.push_item(
+                true,
+                format!("Shift whole day by: {}", typed(&app.input_buffer)),
+            );
+        }
+        AppMode::EstimatePrompt => {
+            screen.push("");
+            screen.push_item(
+                true,
+                format!("Estimate in minutes: {}", typed(&app.input_buffer)),
+            );
+        }
+        AppMode::AdjustmentPrompt => {
+            screen.push("");
+            screen.push_item(
+                true,
+                format!("Adjustment in minutes: {}", typed(&app.input_buffer)),
+            );
+        }
+        AppMode::RecentDays => {
+            screen.push("");
+            screen.push("Go to a recent day:");
+            for (i, day) in app.recent_days.iter().enumerate() {
+                screen.push_item(
+                    i == app.recent_days_selected,
+                    view::recent_day_line(app, day),
+                );
+            }
+        }
+        AppMode::WeekGrid => {
+            if let Some(grid) = &app.week_grid {
+                screen.push("");
+                screen.push("Week by task:");
+                for line in grid.lines(app.config.ui.locale) {
+                    screen.push(line);
+                }
+                if !app.period_budgets.is_empty() {
+                    screen.push("Task budgets:");
+                    for (period, status) in &app.period_budgets {
+                        screen.push(view::period_budget_line(app, *period, status));
+                    }
+                }
+            }
+        }
+        AppMode::RevertField => {
+            screen.push("");
+            screen.push("Revert an edit of this record:");
+            for (i, change) in app.selected_record_changes().iter().enumerate() {
+                let line = format!(
+                    "{} changed from {} to {} at {:02}:{:02}",
+                    change.field.label(),
+                    typed(&change.old),
+                    typed(&change.new),
+                    change.at.hour(),
+                    change.at.minute()
+                );
+                screen.push_item(i == app.revert_selected, line);
+            }
+        }
+        AppMode::Fields => {
+            screen.push("");
+            screen.push("Custom fields:");
+            for (i, (name, value)) in app.field_drafts.iter().enumerate() {
+                let value = if value.is_empty() { "unset" } else { value };
+                let line = match app.config.fields.get(name) {
+                    Some(FieldDef::Choice { .. }) => format!("{}: {} (choice)", name, value),
+                    _ => format!("{}: {}", name, value),
+                };
+                screen.push_item(i == app.field_selected, line);
+            }
+        }
+    }
+}
+
+fn records(screen: &mut Screen, app: &AppState) {
+    let records = app.day_data.get_sorted_records();
+    screen.push("");
+    if records.is_empty() {
+        screen
+            .push("No records yet. Press n to add a record, or c or Enter to name the first one.");
+        return;
+    }
+
+    match &app.record_filter {
+        Some(filter) => {
+            let shown = records.iter().filter(|record| app.is_shown(record)).count();
+            screen.push(format!(
+                "Records: {} of {}, filtered: {}.",
+                shown,
+                records.len(),
+                filter.label()
+            ));
+        }
+        None => screen.push(format!("Records: {}.", records.len())),
+    }
+    if let Some(note) = view::truncated_note(app) {
+        screen.push(format!(
+            "Day file too large ([storage] max_records): {}.",
+            note
+        ));
+    }
+    // A prompt or the summary owns focus while it is open
+    let list_focus = screen.focus.is_none() && !matches!(app.mode, AppMode::Summary);
+    let audit = app.plan_audit();
+    for (i, record) in records.iter().enumerate() {
+        if !app.is_shown(record) {
+            continue;
+        }
+        let mut line = record_line(app, record);
+        if app.timer_source_record() == Some((app.current_date, record.id)) {
+            line.push_str(" (session)");
+        }
+        if app.is_flagged(record.id) {
+            line.push_str(" (flagged)");
+        }
+        let issues = app.record_issues(record);
+        if !issues.is_empty() {
+            line.push_str(&format!(
+                " (missing: {})",
+                crate::validation::describe_issues(&issues)
+            ));
+        }
+        if !record.is_billable(app.billable_default()) {
+            line.push_str(" (non-billable)");
+        }
+        if matches!(app.mode, AppMode::Visual) && app.is_in_visual_selection(i) {
+            line.push_str(" (marked)");
+        }
+        if let Some(audit) = &audit {
+            line.push_str(&format!(" (plan: {})", audit.describe(record.id)));
+        }
+        screen.push_item(list_focus && i == app.selected_index, line);
+    }
+
+    if let Some(audit) = audit {
+        for planned in &audit.missed {
+            screen.push(format!(
+                "Missed: {}-{}  {}",
+                planned.start, planned.end, planned.name
+            ));
+        }
+    }
+}
+
+/// "HH:MM-HH:MM  duration  name — description"
+fn record_line(app: &AppState, record: &WorkRecord) -> String {
+    let (start, end) = view::record_times(app, record);
+    let mut line = String::new();
+    if app.config.ui.show_ids {
+        line.push_str(&format!("#{}  ", record.id));
+    }
+    line.push_str(&format!(
+        "{}-{}  {}  {}",
+        start,
+        end,
+        view::duration_text(record),
+        record.name
+    ));
+    if !record.description.is_empty() {
+        line.push_str(&format!(" — {}", record.description));
+    }
+    line
+}
+
+fn summary(screen: &mut Screen, app: &AppState) {
+    let lines = view::summary_lines(app);
+    if lines.is_empty() {
+        return;
+    }
+
+    screen.push("");
+    screen.push("Summary:");
+    let focused = matches!(app.mode, AppMode::Summary);
+    for (i, line) in lines.into_iter().enumerate() {
+        let focused = focused && i == app.summary_selected;
+        match line {
+            SummaryLine::Project {
+                label,
+                minutes,
+                billable,
+                collapsed,
+            } => {
+                let folded = if collapsed { ", collapsed" } else { "" };
+                screen.push_item(
+                    focused,
+                    format!(
+                        "Project {}: {}{}{}",
+                        label,
+                        format_minutes(minutes),
+                        billable_text(billable),
+                        folded
+                    ),
+                );
+            }
+            SummaryLine::Task {
+                name,
+                minutes,
+                billable,
+                nested,
+                budget,
+                annotation,
+            } => {
+                let indent = if nested { "  " } else { "" };
+                let mut text = format!(
+                    "{}{}: {}{}",
+                    indent,
+                    name,
+                    format_minutes(minutes),
+                    billable_text(billable)
+                );
+                if let Some(status) = budget {
+                    text.push_str(&format!(", {}% of budget", status.percent()));
+                }
+                if let Some(note) = annotation {
+                    text.push_str(&format!(", note: {}", note));
+                }
+                screen.push_item(focused, text);
+            }
+        }
+    }
+    if let Some(hours) = hours_worked(app) {
+        screen.push(hours);
+    }
+}
+
+/// "Hours worked: 9:00 60m, 10:00 30m." with `[ui] heat_strip`, in place of the strip
+fn hours_worked(app: &AppState) -> Option<String> {
+    if !app.config.ui.heat_strip {
+        return None;
+    }
+    let hours: Vec<String> = crate::report::day_hours(&app.day_data)
+        .iter()
+        .enumerate()
+        .filter(|(_, minutes)| **minutes > 0)
+        .map(|(hour, minutes)| format!("{}:00 {}m", hour, minutes))
+        .collect();
+    (!hours.is_empty()).then(|| format!("Hours worked: {}.", hours.join(", ")))
+}
+
+/// ", billable 1h 30m" when the day's totals are split
+fn billable_text(billable: Option<u32>) -> String {
+    billable
+        .map(|minutes| format!(", billable {}", format_minutes(minutes)))
+        .unwrap_or_default()
+}
+
+/// Key hints with arrow glyphs spelled out
+fn plain_keys(help: &str) -> String {
+    help.replace('↑', "Up")
+        .replace('↓', "Down")
+        .replace('←', "Left")
+        .replace('→', "Right")
+        .replace(" | ", ", ")
+}
+
+/// Prompt input, read out as "empty" rather than as nothing
+fn typed(input: &str) -> &str {
+    if input.is_empty() { "empty" } else { input }
+}
+
+/// "COMMAND PALETTE" -> "Command palette"
+fn sentence_case(label: &str) -> String {
+    let lower = label.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => lower,
+    }
+}
+
+/// End a message with a full stop unless it already has one
+fn sentence(message: &str) -> String {
+    let message = message.trim_end();
+    if message.ends_with(['.', '!', '?']) {
+        message.to_string()
+    } else {
+        format!("{}.", message)
+    }
+}
+
+/// Word-wrap a line to `width` columns; words longer than a row are split
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    for (i, word) in line.split(' ').enumerate() {
+        if i > 0 {
+            if !word.is_empty() && row.width() + 1 + word.width() > width {
+                rows.push(std::mem::take(&mut row));
+            } else {
+                row.push(' ');
+            }
+        }
+        for c in word.chars() {
+            let c_width = unicode_width::UnicodeWidthChar::width(c).unwrap_or(0);
+            if row.width() + c_width > width {
+                rows.push(std::mem::take(&mut row));
+            }
+            row.push(c);
+        }
+    }
+    rows.push(row);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_breaks_between_words() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("  indented item", 40), vec!["  indented item"]);
+    }
+
+    #[test]
+    fn test_wrap_splits_long_words() {
+        assert_eq!(wrap("abcdefgh", 3), vec!["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn test_plain_keys_spells_out_arrows() {
+        assert_eq!(
+            plain_keys("↑/↓: Row | ←/→: Field | q: Quit"),
+            "Up/Down: Row, Left/Right: Field, q: Quit"
+        );
+    }
+
+    #[test]
+    fn test_sentence_helpers() {
+        assert_eq!(sentence_case("COMMAND PALETTE"), "Command palette");
+        assert_eq!(sentence("Saved"), "Saved.");
+        assert_eq!(sentence("Saved!"), "Saved!");
+    }
+}
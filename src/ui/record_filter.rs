@@ -0,0 +1,107 @@
+//! Records table narrowed to one summary row (`f`, then Enter on the row)
+//!
+//! The filter is derived from the summary line it was picked on, so it follows
+//! the summary's grouping: a task name, a ticket id or a project.
+
+use crate::models::{GroupBy, NO_PROJECT_LABEL, WorkRecord};
+use crate::ui::view::SummaryLine;
+
+/// Which records the table shows while a summary row is picked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordFilter {
+    /// Records whose `group_key(by)` is `key`
+    Group { by: GroupBy, key: String },
+    /// Records of a project, by its summary label
+    Project(String),
+}
+
+impl RecordFilter {
+    /// Filter for the records behind a summary line grouped by `by`
+    pub fn from_summary_line(line: &SummaryLine, by: GroupBy) -> Self {
+        match line {
+            SummaryLine::Project { label, .. } => RecordFilter::Project(label.clone()),
+            SummaryLine::Task { name, .. } => RecordFilter::Group {
+                by,
+                key: name.clone(),
+            },
+        }
+    }
+
+    pub fn matches(&self, record: &WorkRecord) -> bool {
+        match self {
+            RecordFilter::Group { by, key } => record.group_key(*by) == *key,
+            RecordFilter::Project(label) => {
+                record.project.as_deref().unwrap_or(NO_PROJECT_LABEL) == label
+            }
+        }
+    }
+
+    /// Shown after "filtered: " in the records title
+    pub fn label(&self) -> &str {
+        match self {
+            RecordFilter::Group { key, .. } => key,
+            RecordFilter::Project(label) => label,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimePoint;
+
+    fn record(name: &str, project: Option<&str>) -> WorkRecord {
+        let mut record = WorkRecord::new(
+            1,
+            name.to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 0).unwrap(),
+        );
+        record.project = project.map(str::to_string);
+        record
+    }
+
+    fn task(name: &str) -> SummaryLine<'static> {
+        SummaryLine::Task {
+            name: name.to_string(),
+            minutes: 60,
+            billable: None,
+            nested: false,
+            budget: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn test_name_row_matches_the_exact_name() {
+        let filter = RecordFilter::from_summary_line(&task("ACME-12 fix"), GroupBy::Name);
+        assert_eq!(filter.label(), "ACME-12 fix");
+        assert!(filter.matches(&record("ACME-12 fix", None)));
+        assert!(!filter.matches(&record("ACME-12 fix bug", None)));
+    }
+
+    #[test]
+    fn test_ticket_row_matches_every_record_of_the_ticket() {
+        let filter = RecordFilter::from_summary_line(&task("ACME-12"), GroupBy::Ticket);
+        assert_eq!(filter.label(), "ACME-12");
+        assert!(filter.matches(&record("ACME-12 fix", None)));
+        assert!(filter.matches(&record("ACME-12 fix bug", None)));
+        assert!(!filter.matches(&record("ACME-13 fix", None)));
+        // Records without a ticket are grouped under their name
+        let standup = RecordFilter::from_summary_line(&task("Standup"), GroupBy::Ticket);
+        assert!(standup.matches(&record("Standup", None)));
+    }
+
+    #[test]
+    fn test_project_row_matches_the_project() {
+        let line = SummaryLine::Project {
+            label: NO_PROJECT_LABEL.to_string(),
+            minutes: 60,
+            billable: None,
+            collapsed: false,
+        };
+        let filter = RecordFilter::from_summary_line(&line, GroupBy::Name);
+        assert!(filter.matches(&record("Standup", None)));
+        assert!(!filter.matches(&record("Standup", Some("Acme"))));
+    }
+}
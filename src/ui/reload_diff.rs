@@ -0,0 +1,205 @@
+//! What changed when the day or the timer is reloaded after an outside edit
+//!
+//! `check_and_reload_if_modified` compares the day and timer it had with the ones
+//! on disk and shows the result as a status notice, so a CLI edit doesn't just
+//! silently change the table.
+
+use crate::models::DayData;
+use crate::timer::{TimerState, TimerStatus};
+
+/// Names listed per kind of change before the rest are counted
+const MAX_NAMES: usize = 2;
+
+/// One notice line for the changes between `old` and `new`, None if nothing changed
+///
+/// Either day may be missing when only the timer was reloaded.
+pub fn describe_reload(
+    days: Option<(&DayData, &DayData)>,
+    old_timer: Option<&TimerState>,
+    new_timer: Option<&TimerState>,
+) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some((old, new)) = days {
+        let mut added: Vec<&str> = Vec::new();
+        let mut changed: Vec<&str> = Vec::new();
+        for record in new.get_sorted_records() {
+            match old.work_records.get(&record.id) {
+                None => added.push(&record.name),
+                Some(before) if before != record => changed.push(&record.name),
+                Some(_) => {}
+            }
+        }
+        let removed: Vec<&str> = old
+            .get_sorted_records()
+            .into_iter()
+            .filter(|record| !new.work_records.contains_key(&record.id))
+            .map(|record| record.name.as_str())
+            .collect();
+
+        for (sign, names) in [("+", &added), ("-", &removed), ("~", &changed)] {
+            if !names.is_empty() {
+                parts.push(format!("{}{}", sign, records(names)));
+            }
+        }
+    }
+
+    if let Some(timer) = timer_change(old_timer, new_timer) {
+        parts.push(timer);
+    }
+
+    (!parts.is_empty()).then(|| format!("Reloaded from disk: {}", parts.join(", ")))
+}
+
+/// "1 record: 'A'", "3 records: 'A', 'B' and 1 more"
+fn records(names: &[&str]) -> String {
+    let quoted: Vec<String> = names
+        .iter()
+        .take(MAX_NAMES)
+        .map(|name| format!("'{}'", name))
+        .collect();
+    let mut text = format!(
+        "{} record{}: {}",
+        names.len(),
+        if names.len() == 1 { "" } else { "s" },
+        quoted.join(", ")
+    );
+    if names.len() > MAX_NAMES {
+        text.push_str(&format!(" and {} more", names.len() - MAX_NAMES));
+    }
+    text
+}
+
+fn timer_change(old: Option<&TimerState>, new: Option<&TimerState>) -> Option<String> {
+    match (old, new) {
+        (None, Some(timer)) => Some(format!("timer started externally on '{}'", timer.task_name)),
+        (Some(_), None) => Some("timer stopped externally".to_string()),
+        (Some(old), Some(new)) if old.start_time != new.start_time => {
+            Some(format!("timer switched externally to '{}'", new.task_name))
+        }
+        (Some(old), Some(new)) if old.status != new.status => match new.status {
+            TimerStatus::Paused => Some("timer paused externally".to_string()),
+            _ => Some("timer resumed externally".to_string()),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TimePoint, WorkRecord};
+    use time::macros::{date, datetime};
+
+    fn day(records: &[(u32, &str)]) -> DayData {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        for &(id, name) in records {
+            day.add_record(WorkRecord::new(
+                id,
+                name.to_string(),
+                TimePoint::new(8 + id as u8, 0).unwrap(),
+                TimePoint::new(9 + id as u8, 0).unwrap(),
+            ));
+        }
+        day
+    }
+
+    fn timer(task: &str) -> TimerState {
+        let start = datetime!(2025-11-06 15:45 UTC);
+        TimerState {
+            id: None,
+            task_name: task.to_string(),
+            description: None,
+            start_time: start,
+            end_time: None,
+            date: start.date(),
+            status: TimerStatus::Running,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: start,
+            source_record_id: None,
+            source_record_date: None,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_added_removed_and_modified_records() {
+        let old = day(&[(1, "Standup"), (2, "Review")]);
+        let mut new = day(&[(1, "Standup"), (3, "PROJ-2 fix")]);
+        new.work_records.get_mut(&1).unwrap().description = "Daily".to_string();
+
+        assert_eq!(
+            describe_reload(Some((&old, &new)), None, None).as_deref(),
+            Some(
+                "Reloaded from disk: +1 record: 'PROJ-2 fix', -1 record: 'Review', ~1 record: 'Standup'"
+            )
+        );
+    }
+
+    #[test]
+    fn test_long_lists_are_counted() {
+        let old = day(&[]);
+        let new = day(&[(1, "A"), (2, "B"), (3, "C"), (4, "D")]);
+        assert_eq!(
+            describe_reload(Some((&old, &new)), None, None).as_deref(),
+            Some("Reloaded from disk: +4 records: 'A', 'B' and 2 more")
+        );
+    }
+
+    #[test]
+    fn test_unchanged_reload_says_nothing() {
+        let old = day(&[(1, "Standup")]);
+        let running = timer("Standup");
+        assert_eq!(
+            describe_reload(Some((&old, &old.clone())), Some(&running), Some(&running)),
+            None
+        );
+        assert_eq!(describe_reload(None, None, None), None);
+    }
+
+    #[test]
+    fn test_timer_started_stopped_switched_and_paused() {
+        let running = timer("PROJ-2 fix");
+        assert_eq!(
+            describe_reload(None, None, Some(&running)).as_deref(),
+            Some("Reloaded from disk: timer started externally on 'PROJ-2 fix'")
+        );
+        assert_eq!(
+            describe_reload(None, Some(&running), None).as_deref(),
+            Some("Reloaded from disk: timer stopped externally")
+        );
+
+        let mut other = timer("Review");
+        other.start_time = datetime!(2025-11-06 16:00 UTC);
+        assert_eq!(
+            describe_reload(None, Some(&running), Some(&other)).as_deref(),
+            Some("Reloaded from disk: timer switched externally to 'Review'")
+        );
+
+        let mut paused = running.clone();
+        paused.status = TimerStatus::Paused;
+        assert_eq!(
+            describe_reload(None, Some(&running), Some(&paused)).as_deref(),
+            Some("Reloaded from disk: timer paused externally")
+        );
+        assert_eq!(
+            describe_reload(None, Some(&paused), Some(&running)).as_deref(),
+            Some("Reloaded from disk: timer resumed externally")
+        );
+    }
+
+    #[test]
+    fn test_record_and_timer_changes_combine() {
+        let old = day(&[]);
+        let new = day(&[(1, "PROJ-2 fix")]);
+        let running = timer("PROJ-2 fix");
+        assert_eq!(
+            describe_reload(Some((&old, &new)), None, Some(&running)).as_deref(),
+            Some(
+                "Reloaded from disk: +1 record: 'PROJ-2 fix', timer started externally on 'PROJ-2 fix'"
+            )
+        );
+    }
+}
@@ -1,38 +1,25 @@
-use crate::timer::{TimerState, TimerStatus};
-use crate::ui::AppState;
+use crate::budget::BudgetLevel;
+use crate::models::EstimateStatus;
+use crate::timefmt::{
+    calendar_offset, days_in_month, format_minutes, month_name, short_weekday_name, week_columns,
+};
+use crate::ui::render_meta::RenderMeta;
+use crate::ui::text_width::truncate_to_width;
+use crate::ui::view::{self, SummaryLine, TimerLocation};
+use crate::ui::{AppState, EditField};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     widgets::{Block, BorderType, Borders, Cell, Padding, Paragraph, Row, Table, TableState},
 };
-use std::time::Duration as StdDuration;
-use time::OffsetDateTime;
-
-/// Calculate elapsed duration for a timer (extracted from TimerManager to avoid storage dependency)
-fn calculate_timer_elapsed(timer: &TimerState) -> StdDuration {
-    let end_point = if timer.status == TimerStatus::Paused {
-        // If paused, use when it was paused
-        timer.paused_at.unwrap_or_else(|| {
-            OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc())
-        })
-    } else {
-        // If running, use now
-        OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc())
-    };
-
-    let elapsed = end_point - timer.start_time;
-    let paused_duration_std = StdDuration::from_secs(timer.paused_duration_secs as u64);
-
-    // Convert time::Duration to std::Duration for arithmetic
-    let elapsed_std = StdDuration::from_secs(elapsed.whole_seconds() as u64)
-        + StdDuration::from_nanos(elapsed.subsec_nanoseconds() as u64);
-
-    // Subtract paused time
-    elapsed_std.saturating_sub(paused_duration_std)
-}
 
 pub fn render(frame: &mut Frame, app: &AppState) {
+    if app.config.ui.screen_reader {
+        super::plain::render(frame, app);
+        return;
+    }
+
     // Layout changes if timer is active: add timer bar at top
     let main_constraints = if app.active_timer.is_some() {
         vec![
@@ -115,23 +102,109 @@ pub fn render(frame: &mut Frame, app: &AppState) {
         render_task_picker(frame, app);
     }
 
+    // Render goto-id prompt if active
+    if matches!(app.mode, crate::ui::AppMode::GotoId) {
+        render_goto_id(frame, app);
+    }
+
+    // Render clear-day confirmation if active
+    if matches!(app.mode, crate::ui::AppMode::ConfirmClear) {
+        render_clear_day(frame, app);
+    }
+
+    // Render cascade question if active
+    if matches!(app.mode, crate::ui::AppMode::ConfirmCascade) {
+        render_confirm_cascade(frame, app);
+    }
+
+    // Render merge conflict prompt if active
+    if matches!(app.mode, crate::ui::AppMode::MergeConflict) {
+        render_merge_conflict(frame, app);
+    }
+
+    // Render link prompt if active
+    if matches!(app.mode, crate::ui::AppMode::LinkPrompt) {
+        render_link_prompt(frame, app);
+    }
+
+    // Render task note prompt (or the join and shift prompts, which look the same) if active
+    if matches!(
+        app.mode,
+        crate::ui::AppMode::AnnotationPrompt
+            | crate::ui::AppMode::SessionNotePrompt
+            | crate::ui::AppMode::JoinPrompt
+            | crate::ui::AppMode::ShiftPrompt
+            | crate::ui::AppMode::TemplateNamePrompt
+    ) {
+        render_annotation_prompt(frame, app);
+    }
+
+    // Render the template picker if active
+    if matches!(app.mode, crate::ui::AppMode::TemplatePicker) {
+        render_template_picker(frame, app);
+    }
+
+    // Render estimate prompt if active
+    if matches!(app.mode, crate::ui::AppMode::EstimatePrompt) {
+        render_estimate_prompt(frame, app);
+    }
+
+    // Render adjustment prompt if active
+    if matches!(app.mode, crate::ui::AppMode::AdjustmentPrompt) {
+        render_adjustment_prompt(frame, app);
+    }
+
+    // Render custom field editor if active
+    if matches!(app.mode, crate::ui::AppMode::Fields) {
+        render_fields_editor(frame, app);
+    }
+
+    // Render the recent days list if active
+    if matches!(app.mode, crate::ui::AppMode::RecentDays) {
+        render_recent_days(frame, app);
+    }
+
+    // Render the week grid if active
+    if matches!(app.mode, crate::ui::AppMode::WeekGrid) {
+        render_week_grid(frame, app);
+    }
+
+    // Render the record's recent edits if reverting one
+    if matches!(app.mode, crate::ui::AppMode::RevertField) {
+        render_revert_field(frame, app);
+    }
+
+    // Render URL notice if a link was copied or couldn't be opened
+    if app.url_notice.is_some() {
+        render_url_notice(frame, app);
+    }
+
     // Render error modal if there's an error
     if app.last_error_message.is_some() {
         render_error_modal(frame, app);
     }
 }
 
+/// Icon shown before a task name, guessed from the name
+fn task_icon(name: &str) -> &'static str {
+    let name = name.to_lowercase();
+    if name.contains("break") {
+        "☕"
+    } else if name.contains("meeting") {
+        "👥"
+    } else if name.contains("code") || name.contains("dev") {
+        "💻"
+    } else {
+        "📋"
+    }
+}
+
 fn render_header(frame: &mut Frame, area: Rect, app: &AppState) {
-    let date_str = format!("{}", app.current_date);
+    use crate::models::humanize_minutes;
+    use crate::ui::upcoming::WARNING_MINUTES;
+    use ratatui::text::{Line, Span};
 
-    let total_minutes: u32 = app
-        .day_data
-        .work_records
-        .values()
-        .map(|r| r.total_minutes)
-        .sum();
-    let total_hours = total_minutes / 60;
-    let total_mins = total_minutes % 60;
+    let date_str = app.config.ui.format_date(app.current_date);
 
     // Create a more visual header with sections
     let chunks = Layout::default()
@@ -139,7 +212,18 @@ fn render_header(frame: &mut Frame, area: Rect, app: &AppState) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
-    let title_text = format!("⏱  WorkTimer - {} [←prev] [next→]", date_str);
+    let mut title_text = if app.profile.is_default() {
+        format!("⏱  WorkTimer - {} [←prev] [next→]", date_str)
+    } else {
+        format!(
+            "⏱  WorkTimer [{}] - {} [←prev] [next→]",
+            app.profile.name(),
+            date_str
+        )
+    };
+    if app.safe_mode {
+        title_text.push_str(" SAFE MODE");
+    }
     let title = Paragraph::new(title_text)
         .style(
             Style::default()
@@ -154,13 +238,38 @@ fn render_header(frame: &mut Frame, area: Rect, app: &AppState) {
                 .border_style(Style::default().fg(app.theme.active_border)),
         );
 
-    let total_text = format!("Total: {}h {:02}m", total_hours, total_mins);
-    let total = Paragraph::new(total_text)
-        .style(
+    let totals = view::day_totals_text(app);
+    let total_text = if app.day_data.is_total_clamped() {
+        format!("⚠ Adjustments exceed tracked time | {}", totals)
+    } else {
+        totals
+    };
+    let mut spans = Vec::new();
+    if let Some((record, minutes)) = app.next_upcoming() {
+        let style = if minutes <= WARNING_MINUTES {
             Style::default()
-                .fg(app.theme.success)
-                .add_modifier(Modifier::BOLD),
-        )
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.secondary_text)
+        };
+        spans.push(Span::styled(
+            format!(
+                "next: {} {} in {} | ",
+                task_icon(&record.name),
+                truncate_to_width(&record.name, 20),
+                humanize_minutes(minutes)
+            ),
+            style,
+        ));
+    }
+    spans.push(Span::styled(
+        total_text,
+        Style::default()
+            .fg(app.theme.success)
+            .add_modifier(Modifier::BOLD),
+    ));
+    let total = Paragraph::new(Line::from(spans))
         .alignment(Alignment::Right)
         .block(
             Block::default()
@@ -176,14 +285,47 @@ fn render_header(frame: &mut Frame, area: Rect, app: &AppState) {
 fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
     let records = app.day_data.get_sorted_records();
 
+    if records.is_empty() {
+        render_records_empty_state(frame, area, app);
+        return;
+    }
+    let show_ids = app.config.ui.show_ids;
+
+    // "12h 30m ago" is wider than "HH:MM"
+    let time_width = if app.relative_times { 12 } else { 10 };
+    let mut widths = vec![
+        Constraint::Percentage(25),
+        Constraint::Length(time_width),
+        Constraint::Length(time_width),
+        Constraint::Length(12),
+        Constraint::Percentage(30),
+    ];
+    if show_ids {
+        widths.insert(0, Constraint::Length(5));
+    }
+    // The plan audit takes the Description column's place while it's shown
+    let audit = app.plan_audit();
+    let name_column = usize::from(show_ids);
+    let name_width = table_column_widths(&widths, area.width.saturating_sub(2))[name_column];
+
     // Calculate how many rows can fit in the visible area
     // Account for: borders (2) + header (2) + margin (1) = 5 lines
     let available_height = area.height.saturating_sub(5) as usize;
 
+    // Records hidden by a summary filter take no rows; `i` stays the day index
+    let shown: Vec<(usize, &crate::models::WorkRecord)> = records
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, record)| app.is_shown(record))
+        .collect();
+    let selected_row = shown.iter().position(|(i, _)| *i == app.selected_index);
+
     // Calculate scroll offset to keep selected item visible
-    let scroll_offset = if records.len() > available_height {
-        if app.selected_index >= available_height {
-            app.selected_index.saturating_sub(available_height - 1)
+    let selected = selected_row.unwrap_or(0);
+    let scroll_offset = if shown.len() > available_height {
+        if selected >= available_height {
+            selected.saturating_sub(available_height - 1)
         } else {
             0
         }
@@ -191,10 +333,15 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
         0
     };
 
-    let rows: Vec<Row> = records
+    // Rows are built for the visible window only (borders and header take 4 lines),
+    // so a huge day renders as fast as a small one
+    let visible_rows = area.height.saturating_sub(4) as usize;
+    let mut rows: Vec<Row> = shown
         .iter()
         .enumerate()
-        .map(|(i, record)| {
+        .skip(scroll_offset)
+        .take(visible_rows)
+        .map(|(row, &(i, record))| {
             let is_selected = i == app.selected_index;
             let is_editing = matches!(app.mode, crate::ui::AppMode::Edit) && is_selected;
             let is_in_visual =
@@ -202,10 +349,9 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
 
             // Check if this record has an active timer running
             // Compare by source_record_id to highlight only the specific record, not all with same name
-            let has_active_timer = app
-                .active_timer
-                .as_ref()
-                .is_some_and(|timer| timer.source_record_id == Some(record.id));
+            let has_active_timer = app.timer_source_record() == Some((app.current_date, record.id));
+            // A just-stopped session's record keeps the timer highlight for a moment
+            let just_stopped = app.is_flashing(record.id);
 
             // Enhanced styling with more vibrant colors
             let style = if is_in_visual {
@@ -213,7 +359,7 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
                     .bg(app.theme.visual_bg)
                     .fg(app.theme.primary_text)
                     .add_modifier(Modifier::BOLD)
-            } else if has_active_timer {
+            } else if has_active_timer || just_stopped {
                 // Highlight record with active timer in green/gold
                 Style::default()
                     .bg(app.theme.timer_active_bg)
@@ -224,151 +370,40 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
                     .bg(app.theme.selected_bg)
                     .fg(app.theme.highlight_text)
                     .add_modifier(Modifier::BOLD)
-            } else if i % 2 == 0 {
+            } else if row % 2 == 0 {
                 Style::default().bg(app.theme.row_alternate_bg)
             } else {
                 Style::default()
             };
 
-            // Add icon/emoji based on task type, with timer indicator if active
-            let icon = if has_active_timer {
-                "⏱ " // Timer icon for active timers
-            } else if record.name.to_lowercase().contains("break") {
-                "☕"
-            } else if record.name.to_lowercase().contains("meeting") {
-                "👥"
-            } else if record.name.to_lowercase().contains("code")
-                || record.name.to_lowercase().contains("dev")
-            {
-                "💻"
-            } else {
-                "📋"
-            };
+            let meta = RenderMeta::build(app, record, has_active_timer);
+            let edit_field = is_editing.then_some(&app.edit_field);
+            let name_display = name_cell_text(app, record, &meta, edit_field);
 
-            // Determine display text and styles for each field
-            let (name_display, start_display, end_display, description_display) = if is_editing {
-                match app.edit_field {
-                    crate::ui::EditField::Name => {
-                        // Add cursor indicator to show user is in edit mode
-                        let text_with_cursor = format!("{}▏", app.input_buffer);
-
-                        // Extract and display ticket badge if present and config exists
-                        let display = if app.config.has_integrations() {
-                            if crate::integrations::extract_ticket_from_name(&app.input_buffer)
-                                .is_some()
-                            {
-                                format!("🎫 {} {}", icon, text_with_cursor)
-                            } else {
-                                format!("{} {}", icon, text_with_cursor)
-                            }
-                        } else {
-                            format!("{} {}", icon, text_with_cursor)
-                        };
-                        (
-                            display,
-                            record.start.to_string(),
-                            record.end.to_string(),
-                            record.description.clone(),
-                        )
-                    }
-                    crate::ui::EditField::Description => {
-                        // Add cursor indicator to show user is in edit mode
-                        let description_with_cursor = format!("{}▏", app.input_buffer);
-
-                        // Extract and display ticket badge if present and config exists
-                        let display = if app.config.has_integrations() {
-                            if crate::integrations::extract_ticket_from_name(&record.name).is_some()
-                            {
-                                format!("🎫 {} {}", icon, record.name)
-                            } else {
-                                format!("{} {}", icon, record.name)
-                            }
-                        } else {
-                            format!("{} {}", icon, record.name)
-                        };
-                        (
-                            display,
-                            record.start.to_string(),
-                            record.end.to_string(),
-                            description_with_cursor,
-                        )
-                    }
-                    crate::ui::EditField::Start | crate::ui::EditField::End => {
-                        // Add cursor position indicator for time fields
-                        let time_str = &app.input_buffer;
-                        let positions = [0, 1, 3, 4];
-                        let cursor_pos = if app.time_cursor < positions.len() {
-                            positions[app.time_cursor]
-                        } else {
-                            positions[positions.len() - 1]
-                        };
-
-                        let mut display = String::new();
-                        for (i, ch) in time_str.chars().enumerate() {
-                            if i == cursor_pos {
-                                display.push('[');
-                                display.push(ch);
-                                display.push(']');
-                            } else {
-                                display.push(ch);
-                            }
-                        }
-
-                        // Extract and display ticket badge if present and config exists
-                        let name_with_badge = if app.config.has_integrations() {
-                            if crate::integrations::extract_ticket_from_name(&record.name).is_some()
-                            {
-                                format!("🎫 {} {}", icon, record.name)
-                            } else {
-                                format!("{} {}", icon, record.name)
-                            }
-                        } else {
-                            format!("{} {}", icon, record.name)
-                        };
-
-                        match app.edit_field {
-                            crate::ui::EditField::Start => (
-                                name_with_badge,
-                                display,
-                                record.end.to_string(),
-                                record.description.clone(),
-                            ),
-                            crate::ui::EditField::End => (
-                                name_with_badge,
-                                record.start.to_string(),
-                                display,
-                                record.description.clone(),
-                            ),
-                            _ => unreachable!(),
-                        }
-                    }
-                }
+            // Editing always shows HH:MM
+            let (mut start_display, mut end_display) = if is_editing {
+                (record.start.to_string(), record.end.to_string())
             } else {
-                // Extract and display ticket badge if present and config exists (non-editing mode)
-                let name_with_badge = if app.config.has_integrations() {
-                    if crate::integrations::extract_ticket_from_name(&record.name).is_some() {
-                        format!("🎫 {} {}", icon, record.name)
-                    } else {
-                        format!("{} {}", icon, record.name)
-                    }
-                } else {
-                    format!("{} {}", icon, record.name)
-                };
-                (
-                    name_with_badge,
-                    record.start.to_string(),
-                    record.end.to_string(),
-                    record.description.clone(),
-                )
+                view::record_times(app, record)
             };
+            let mut description_display = record.description.clone();
+            match edit_field {
+                Some(EditField::Start) => start_display = time_with_cursor(app),
+                Some(EditField::End) => end_display = time_with_cursor(app),
+                Some(EditField::Description) => {
+                    // Add cursor indicator to show user is in edit mode
+                    description_display = format!("{}▏", app.input_buffer);
+                }
+                Some(EditField::Name) | None => {}
+            }
 
             // Apply styles based on focus and edit state
-            let name_style = if is_editing && matches!(app.edit_field, crate::ui::EditField::Name) {
+            let name_style = if is_editing && matches!(app.edit_field, EditField::Name) {
                 Style::default()
                     .bg(app.theme.edit_bg)
                     .fg(app.theme.primary_text)
                     .add_modifier(Modifier::BOLD)
-            } else if is_selected && matches!(app.edit_field, crate::ui::EditField::Name) {
+            } else if is_selected && matches!(app.edit_field, EditField::Name) {
                 Style::default()
                     .bg(app.theme.focus_bg)
                     .fg(app.theme.primary_text)
@@ -377,13 +412,12 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
                 Style::default()
             };
 
-            let start_style = if is_editing && matches!(app.edit_field, crate::ui::EditField::Start)
-            {
+            let start_style = if is_editing && matches!(app.edit_field, EditField::Start) {
                 Style::default()
                     .bg(app.theme.edit_bg)
                     .fg(app.theme.primary_text)
                     .add_modifier(Modifier::BOLD)
-            } else if is_selected && matches!(app.edit_field, crate::ui::EditField::Start) {
+            } else if is_selected && matches!(app.edit_field, EditField::Start) {
                 Style::default()
                     .bg(app.theme.focus_bg)
                     .fg(app.theme.primary_text)
@@ -392,12 +426,12 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
                 Style::default().fg(app.theme.success)
             };
 
-            let end_style = if is_editing && matches!(app.edit_field, crate::ui::EditField::End) {
+            let end_style = if is_editing && matches!(app.edit_field, EditField::End) {
                 Style::default()
                     .bg(app.theme.edit_bg)
                     .fg(app.theme.primary_text)
                     .add_modifier(Modifier::BOLD)
-            } else if is_selected && matches!(app.edit_field, crate::ui::EditField::End) {
+            } else if is_selected && matches!(app.edit_field, EditField::End) {
                 Style::default()
                     .bg(app.theme.focus_bg)
                     .fg(app.theme.primary_text)
@@ -406,79 +440,203 @@ fn render_records(frame: &mut Frame, area: Rect, app: &AppState) {
                 Style::default().fg(app.theme.error)
             };
 
-            let description_style = if is_editing
-                && matches!(app.edit_field, crate::ui::EditField::Description)
-            {
-                Style::default()
-                    .bg(app.theme.edit_bg)
-                    .fg(app.theme.primary_text)
-                    .add_modifier(Modifier::BOLD)
-            } else if is_selected && matches!(app.edit_field, crate::ui::EditField::Description) {
-                Style::default()
-                    .bg(app.theme.focus_bg)
-                    .fg(app.theme.primary_text)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(app.theme.primary_text)
-            };
+            let description_style =
+                if is_editing && matches!(app.edit_field, EditField::Description) {
+                    Style::default()
+                        .bg(app.theme.edit_bg)
+                        .fg(app.theme.primary_text)
+                        .add_modifier(Modifier::BOLD)
+                } else if is_selected && matches!(app.edit_field, EditField::Description) {
+                    Style::default()
+                        .bg(app.theme.focus_bg)
+                        .fg(app.theme.primary_text)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(app.theme.primary_text)
+                };
 
-            Row::new(vec![
-                Cell::from(name_display).style(name_style),
+            let mut cells = vec![
+                name_cell(
+                    truncate_to_width(&name_display, name_width as usize),
+                    &meta,
+                    edit_field.is_some(),
+                )
+                .style(name_style),
                 Cell::from(start_display).style(start_style),
                 Cell::from(end_display).style(end_style),
-                Cell::from(record.format_duration()).style(Style::default().fg(app.theme.badge)),
+                duration_cell(app, record),
                 Cell::from(description_display).style(description_style),
-            ])
-            .style(style)
+            ];
+            if show_ids {
+                cells.insert(
+                    0,
+                    Cell::from(record.id.to_string())
+                        .style(Style::default().fg(app.theme.secondary_text)),
+                );
+            }
+            // A description being edited stays visible
+            if let Some(audit) = audit.as_ref().filter(|_| !is_editing) {
+                let color = match audit.matched.get(&record.id) {
+                    None => app.theme.warning,
+                    Some(delta) if delta.is_on_plan() => app.theme.success,
+                    Some(_) => app.theme.info,
+                };
+                let plan = Cell::from(audit.describe(record.id)).style(Style::default().fg(color));
+                *cells.last_mut().unwrap() = plan;
+            }
+
+            Row::new(cells).style(style)
         })
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Percentage(25),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(12),
-            Constraint::Percentage(30),
-        ],
-    )
-    .header(
-        Row::new(vec![
-            Cell::from("📝 Task Name"),
-            Cell::from("🕐 Start"),
-            Cell::from("🕐 End"),
-            Cell::from("⏱  Duration"),
-            Cell::from("📄 Description"),
-        ])
-        .style(
+    // Planned records nothing matched, after the real ones so selection is unaffected
+    let at_end = scroll_offset + rows.len() >= shown.len();
+    if let Some(audit) = audit.as_ref().filter(|_| at_end) {
+        for planned in &audit.missed {
+            let mut cells = vec![
+                Cell::from(truncate_to_width(
+                    &format!("✗  {}", planned.name),
+                    name_width as usize,
+                )),
+                Cell::from(planned.start.to_string()),
+                Cell::from(planned.end.to_string()),
+                Cell::from(planned.format_duration()),
+                Cell::from("missed").style(Style::default().fg(app.theme.error)),
+            ];
+            if show_ids {
+                cells.insert(0, Cell::from(""));
+            }
+            rows.push(
+                Row::new(cells).style(
+                    Style::default()
+                        .fg(app.theme.secondary_text)
+                        .add_modifier(Modifier::DIM),
+                ),
+            );
+        }
+    }
+
+    let mut header_cells = vec![
+        Cell::from("📝 Task Name"),
+        Cell::from("🕐 Start"),
+        Cell::from("🕐 End"),
+        Cell::from("⏱  Duration"),
+        Cell::from("📄 Description"),
+    ];
+    if show_ids {
+        header_cells.insert(0, Cell::from("#"));
+    }
+    if audit.is_some() {
+        *header_cells.last_mut().unwrap() = Cell::from("🎯 Plan");
+    }
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.active_border))
+        .title(records_title(app))
+        .title_style(
             Style::default()
-                .fg(app.theme.warning)
+                .fg(app.theme.highlight_text)
                 .add_modifier(Modifier::BOLD),
+        );
+    // The selected name in full when `[ui] max_name_chars` cuts it in its row
+    if let Some(name) = view::selected_full_name(app) {
+        use ratatui::text::Span;
+        use ratatui::widgets::block::{Position, Title};
+        block = block.title(
+            Title::from(Span::styled(
+                format!(" {} ", name),
+                Style::default().fg(app.theme.secondary_text),
+            ))
+            .position(Position::Bottom),
+        );
+    }
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(header_cells)
+                .style(
+                    Style::default()
+                        .fg(app.theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .bottom_margin(1),
         )
-        .bottom_margin(1),
-    )
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(app.theme.active_border))
-            .title("📊 Work Records")
-            .title_style(
-                Style::default()
-                    .fg(app.theme.highlight_text)
-                    .add_modifier(Modifier::BOLD),
-            ),
-    );
+        .block(block);
 
     // Use stateful rendering to handle scrolling
     let mut table_state = TableState::default()
-        .with_selected(Some(app.selected_index))
-        .with_offset(scroll_offset);
+        .with_selected(selected_row.map(|row| row.saturating_sub(scroll_offset)));
 
     frame.render_stateful_widget(table, area, &mut table_state);
 }
 
+/// "📊 Work Records", naming the summary row the table is filtered by and a
+/// day loaded only in part
+fn records_title(app: &AppState) -> String {
+    let notes: Vec<String> = app
+        .record_filter
+        .as_ref()
+        .map(|filter| format!("filtered: {}", filter.label()))
+        .into_iter()
+        .chain(view::truncated_note(app))
+        .collect();
+    if notes.is_empty() {
+        "📊 Work Records".to_string()
+    } else {
+        format!("📊 Work Records ({})", notes.join("; "))
+    }
+}
+
+/// Column widths a `Table` lays out for `widths` inside `inner_width` columns
+fn table_column_widths(widths: &[Constraint], inner_width: u16) -> Vec<u16> {
+    use ratatui::layout::Flex;
+
+    // Same split as Table: no highlight symbol, one column of spacing
+    Layout::horizontal(widths.iter().copied())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(Rect::new(0, 0, inner_width, 1))
+        .iter()
+        .map(|column| column.width)
+        .collect()
+}
+
+/// Hint shown in place of the records table on a day without records
+fn render_records_empty_state(frame: &mut Frame, area: Rect, app: &AppState) {
+    use ratatui::text::Line;
+
+    let lines = vec![
+        Line::from(""),
+        Line::from("No records yet").style(
+            Style::default()
+                .fg(app.theme.highlight_text)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::from(""),
+        Line::from("Press n to add a record, or c / Enter to name the first one")
+            .style(Style::default().fg(app.theme.secondary_text)),
+    ];
+
+    let hint = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.active_border))
+                .title("📊 Work Records")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.highlight_text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+
+    frame.render_widget(hint, area);
+}
+
 fn render_grouped_totals(frame: &mut Frame, area: Rect, app: &AppState) {
     let grouped = app.day_data.get_grouped_totals();
 
@@ -502,117 +660,326 @@ fn render_grouped_totals(frame: &mut Frame, area: Rect, app: &AppState) {
         return;
     }
 
-    let rows: Vec<Row> = grouped
-        .iter()
-        .map(|(name, minutes)| {
-            let hours = minutes / 60;
-            let mins = minutes % 60;
-
-            // Choose icon based on task type
-            let icon = if name.to_lowercase().contains("break") {
-                "☕"
-            } else if name.to_lowercase().contains("meeting") {
-                "👥"
-            } else if name.to_lowercase().contains("code") || name.to_lowercase().contains("dev") {
-                "💻"
-            } else {
-                "📋"
-            };
-
-            Row::new(vec![
-                Cell::from(format!("{} {}", icon, name)),
-                Cell::from(format!("{}h {:02}m", hours, mins)).style(
+    // With focus (`f`) the picked row is highlighted and the border lights up
+    let focused = matches!(app.mode, crate::ui::AppMode::Summary);
+    let rows: Vec<Row> = view::summary_lines(app)
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let row = summary_row(app, line);
+            if focused && i == app.summary_selected {
+                row.style(
                     Style::default()
-                        .fg(app.theme.badge)
+                        .bg(app.theme.selected_bg)
+                        .fg(app.theme.highlight_text)
                         .add_modifier(Modifier::BOLD),
-                ),
-            ])
+                )
+            } else {
+                row
+            }
         })
         .collect();
+    let border = if focused {
+        app.theme.active_border
+    } else {
+        app.theme.warning
+    };
 
-    let table = Table::new(
-        rows,
-        [Constraint::Percentage(65), Constraint::Percentage(35)],
-    )
-    .header(
-        Row::new(vec![Cell::from("Task"), Cell::from("Total")])
-            .style(
-                Style::default()
-                    .fg(app.theme.warning)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .bottom_margin(1),
-    )
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(app.theme.warning))
-            .title("📈 Summary")
-            .title_style(
-                Style::default()
-                    .fg(app.theme.warning)
-                    .add_modifier(Modifier::BOLD),
-            ),
-    );
+    // A billable column once any record on the day is non-billable
+    let (widths, header) = if view::billable_total(app).is_some() {
+        (
+            vec![
+                Constraint::Percentage(46),
+                Constraint::Percentage(27),
+                Constraint::Percentage(27),
+            ],
+            vec!["Task", "Total", "Billable"],
+        )
+    } else {
+        (
+            vec![Constraint::Percentage(65), Constraint::Percentage(35)],
+            vec!["Task", "Total"],
+        )
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(border))
+        .title("📈 Summary")
+        .title_style(
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        );
+    // Worked minutes per hour along the bottom border
+    if let Some(strip) = view::heat_strip(app) {
+        use ratatui::text::Span;
+        use ratatui::widgets::block::{Position, Title};
+
+        block = block.title(
+            Title::from(Span::styled(strip, Style::default().fg(app.theme.success)))
+                .position(Position::Bottom)
+                .alignment(Alignment::Center),
+        );
+    }
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(header)
+                .style(
+                    Style::default()
+                        .fg(app.theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .bottom_margin(1),
+        )
+        .block(block);
 
     frame.render_widget(table, area);
 }
 
-fn render_footer(frame: &mut Frame, area: Rect, app: &AppState) {
-    // Build help text for Browse mode conditionally
-    let browse_help = if app.config.has_integrations() {
-        "↑/↓: Row | ←/→: Field | [/]: Day | C: Calendar | Enter: Edit | c: Change | n: New | b: Break | d: Delete | v: Visual | t: Now | T: Ticket | L: Worklog | S: Session Start/Stop | P: Pause | ?: Help | q: Quit"
-    } else {
-        "↑/↓: Row | ←/→: Field | [/]: Day | C: Calendar | Enter: Edit | c: Change | n: New | b: Break | d: Delete | v: Visual | t: Now | S: Session Start/Stop | P: Pause | ?: Help | q: Quit"
-    };
+/// Name column text; while the row is edited its markers and link badge make way,
+/// and a name being typed gets the badge its ticket would have
+fn name_cell_text(
+    app: &AppState,
+    record: &crate::models::WorkRecord,
+    meta: &RenderMeta,
+    editing: Option<&EditField>,
+) -> String {
+    match editing {
+        None => meta.label.clone(),
+        Some(EditField::Name) => {
+            let typed_ticket = crate::integrations::extract_ticket_from_name(&app.input_buffer)
+                .filter(|_| app.has_integrations());
+            let badge = match typed_ticket {
+                Some(ticket) => {
+                    format!(
+                        "{} ",
+                        crate::integrations::ticket_badge(&ticket, &app.config).icon
+                    )
+                }
+                None => String::new(),
+            };
+            format!("{}{} {}▏", badge, meta.icon, app.input_buffer)
+        }
+        Some(_) => format!("{}{} {}", meta.ticket_badge, meta.icon, record.name),
+    }
+}
 
-    let (help_text, mode_color, mode_label) = match app.mode {
-        crate::ui::AppMode::Browse => (browse_help, app.theme.info, "BROWSE"),
-        crate::ui::AppMode::Edit => (
-            "Tab: Next field | Enter: Save | Esc: Cancel",
-            app.theme.warning,
-            "EDIT",
-        ),
-        crate::ui::AppMode::Visual => (
-            "↑/↓: Extend selection | d: Delete | Esc: Exit visual",
-            app.theme.badge,
-            "VISUAL",
-        ),
-        crate::ui::AppMode::CommandPalette => (
-            "↑/↓: Navigate | Enter: Execute | Esc: Cancel",
-            app.theme.success,
-            "COMMAND PALETTE",
-        ),
-        crate::ui::AppMode::Calendar => (
-            "hjkl/arrows: Navigate | </>: Month | Enter: Select | Esc: Cancel",
-            app.theme.badge,
-            "CALENDAR",
-        ),
-        crate::ui::AppMode::TaskPicker => (
-            "Type: Filter/Create | ↑/↓: Navigate | Enter: Select | Esc: Cancel",
-            app.theme.info,
-            "TASK PICKER",
-        ),
+/// Name cell with the ticket badge in its tracker's `badge_color`, if it has one
+///
+/// A badge cut off by the column width, or a row being edited, is drawn plain.
+fn name_cell<'a>(text: String, meta: &RenderMeta, editing: bool) -> Cell<'a> {
+    use ratatui::text::{Line, Span};
+
+    let badge = meta.ticket_badge.as_str();
+    let found = (!badge.is_empty() && !editing)
+        .then(|| text.find(badge))
+        .flatten();
+    let (Some(color), Some(at)) = (meta.badge_color, found) else {
+        return Cell::from(text);
     };
+    let (before, rest) = text.split_at(at);
+    let (badge, after) = rest.split_at(badge.len());
+    Cell::from(Line::from(vec![
+        Span::raw(before.to_string()),
+        Span::styled(badge.to_string(), Style::default().fg(color)),
+        Span::raw(after.to_string()),
+    ]))
+}
 
-    let footer = Paragraph::new(help_text)
-        .style(Style::default().fg(app.theme.secondary_text))
-        .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(mode_color))
-                .title(format!("⌨  {} MODE", mode_label))
-                .title_style(Style::default().fg(mode_color).add_modifier(Modifier::BOLD))
-                .padding(Padding::horizontal(1)),
-        );
-
-    frame.render_widget(footer, area);
+/// Time being edited with the digit under the cursor in brackets: "[0]9:00"
+fn time_with_cursor(app: &AppState) -> String {
+    let positions = [0, 1, 3, 4];
+    let cursor_pos = positions[app.time_cursor.min(positions.len() - 1)];
+
+    let mut display = String::new();
+    for (i, ch) in app.input_buffer.chars().enumerate() {
+        if i == cursor_pos {
+            display.push('[');
+            display.push(ch);
+            display.push(']');
+        } else {
+            display.push(ch);
+        }
+    }
+    display
 }
 
-fn render_command_palette(frame: &mut Frame, app: &AppState) {
+/// Summary row: projects bold with a fold marker, tasks with an icon and budget use
+fn summary_row<'a>(app: &AppState, line: SummaryLine) -> Row<'a> {
+    use ratatui::text::{Line, Span};
+
+    match line {
+        SummaryLine::Project {
+            label,
+            minutes,
+            billable,
+            collapsed,
+        } => {
+            let marker = if collapsed { "▸" } else { "▾" };
+            let bold = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+            let mut cells = vec![
+                Cell::from(format!("{} {}", marker, label)).style(bold(app.theme.highlight_text)),
+                Cell::from(format_minutes(minutes)).style(bold(app.theme.success)),
+            ];
+            if let Some(billable) = billable {
+                cells.push(Cell::from(format_minutes(billable)).style(bold(app.theme.success)));
+            }
+            Row::new(cells)
+        }
+        SummaryLine::Task {
+            name,
+            minutes,
+            billable,
+            nested,
+            budget,
+            annotation,
+        } => {
+            let mut total = vec![Span::styled(
+                format_minutes(minutes),
+                Style::default()
+                    .fg(app.theme.badge)
+                    .add_modifier(Modifier::BOLD),
+            )];
+            if let Some(status) = budget {
+                total.push(Span::styled(
+                    format!(" {}%", status.percent()),
+                    budget_style(app, status.level()),
+                ));
+            }
+            if let Some(note) = annotation {
+                total.push(Span::styled(
+                    format!(" {}", note),
+                    Style::default()
+                        .fg(app.theme.secondary_text)
+                        .add_modifier(Modifier::DIM),
+                ));
+            }
+
+            let indent = if nested { "  " } else { "" };
+            let mut cells = vec![
+                Cell::from(format!("{}{} {}", indent, task_icon(&name), name)),
+                Cell::from(Line::from(total)),
+            ];
+            if let Some(billable) = billable {
+                cells.push(
+                    Cell::from(format_minutes(billable))
+                        .style(Style::default().fg(app.theme.badge)),
+                );
+            }
+            Row::new(cells)
+        }
+    }
+}
+
+/// Duration column; non-billable records are dimmed and marked "nb"
+fn duration_cell<'a>(app: &AppState, record: &crate::models::WorkRecord) -> Cell<'a> {
+    let style = estimate_style(app, record.estimate_status());
+    if record.is_billable(app.billable_default()) {
+        Cell::from(view::duration_text(record)).style(style)
+    } else {
+        Cell::from(format!("{} nb", view::duration_text(record)))
+            .style(style.add_modifier(Modifier::DIM))
+    }
+}
+
+/// Colour for the duration column: over the estimate in red, within it in green
+fn estimate_style(app: &AppState, status: Option<EstimateStatus>) -> Style {
+    match status {
+        None => Style::default().fg(app.theme.badge),
+        Some(EstimateStatus::Within) => Style::default().fg(app.theme.success),
+        Some(EstimateStatus::Over) => Style::default()
+            .fg(app.theme.error)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+/// Colour for a budget percentage: plain below the warning threshold
+fn budget_style(app: &AppState, level: BudgetLevel) -> Style {
+    match level {
+        BudgetLevel::Ok => Style::default().fg(app.theme.secondary_text),
+        BudgetLevel::Warning => Style::default()
+            .fg(app.theme.warning)
+            .add_modifier(Modifier::BOLD),
+        BudgetLevel::Exceeded => Style::default()
+            .fg(app.theme.error)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+fn render_footer(frame: &mut Frame, area: Rect, app: &AppState) {
+    let (help_text, mode_label) = view::mode_hint(app);
+    let mode_color = match app.mode {
+        crate::ui::AppMode::Browse
+        | crate::ui::AppMode::TaskPicker
+        | crate::ui::AppMode::GotoId
+        | crate::ui::AppMode::LinkPrompt
+        | crate::ui::AppMode::AnnotationPrompt
+        | crate::ui::AppMode::SessionNotePrompt
+        | crate::ui::AppMode::JoinPrompt
+        | crate::ui::AppMode::ShiftPrompt
+        | crate::ui::AppMode::EstimatePrompt
+        | crate::ui::AppMode::AdjustmentPrompt
+        | crate::ui::AppMode::Fields
+        | crate::ui::AppMode::RevertField
+        | crate::ui::AppMode::RecentDays
+        | crate::ui::AppMode::WeekGrid
+        | crate::ui::AppMode::TemplatePicker
+        | crate::ui::AppMode::TemplateNamePrompt => app.theme.info,
+        crate::ui::AppMode::Edit => app.theme.warning,
+        crate::ui::AppMode::Visual | crate::ui::AppMode::Calendar | crate::ui::AppMode::Summary => {
+            app.theme.badge
+        }
+        crate::ui::AppMode::CommandPalette | crate::ui::AppMode::ConfirmCascade => {
+            app.theme.success
+        }
+        crate::ui::AppMode::ConfirmClear | crate::ui::AppMode::MergeConflict => app.theme.error,
+    };
+
+    // A status notice replaces the key hints until the next key press; a failed
+    // save replaces them until a save succeeds
+    let save_warning = app.save_error.as_ref().map(|e| format!("⚠ {}", e));
+    let (footer_text, footer_color) = match (&app.status_message, &save_warning) {
+        (Some(message), _) => (message.as_str(), app.theme.warning),
+        (None, Some(warning)) => (warning.as_str(), app.theme.error),
+        (None, None) => (help_text.as_str(), app.theme.secondary_text),
+    };
+    // Hints that don't fit are left out rather than wrapped or cut mid-word
+    let fitted;
+    let footer_text = if app.status_message.is_none() && save_warning.is_none() {
+        fitted = crate::ui::hints::fit(footer_text, area.width.saturating_sub(4) as usize);
+        fitted.as_str()
+    } else {
+        footer_text
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(mode_color))
+        .title(format!("⌨  {} MODE", mode_label))
+        .title_style(Style::default().fg(mode_color).add_modifier(Modifier::BOLD))
+        .padding(Padding::horizontal(1));
+    // Where T goes, so a wrong browse_url template shows before opening anything
+    if let Some(link) = view::selected_link(app) {
+        use ratatui::{text::Span, widgets::block::Title};
+        block = block.title(
+            Title::from(Span::styled(
+                format!(" {} ", link),
+                Style::default().fg(app.theme.secondary_text),
+            ))
+            .alignment(Alignment::Right),
+        );
+    }
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(footer_color))
+        .alignment(Alignment::Center)
+        .block(block);
+
+    frame.render_widget(footer, area);
+}
+
+fn render_command_palette(frame: &mut Frame, app: &AppState) {
+    use ratatui::text::{Line, Span};
     use ratatui::widgets::Clear;
 
     // Create a centered modal
@@ -636,10 +1003,16 @@ fn render_command_palette(frame: &mut Frame, app: &AppState) {
     let bg_block = Block::default().style(Style::default().bg(app.theme.row_alternate_bg));
     frame.render_widget(bg_block, modal_area);
 
-    // Split modal into input and results
+    // Split modal into input, results and the quick task keys if any are set
+    let quick_tasks = app.quick_task_hints();
+    let quick_height = if quick_tasks.is_empty() { 0 } else { 3 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(5)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(quick_height),
+        ])
         .split(modal_area);
 
     // Render search input
@@ -693,7 +1066,7 @@ fn render_command_palette(frame: &mut Frame, app: &AppState) {
                 Style::default().bg(app.theme.row_alternate_bg)
             };
 
-            let key_display = format!("  {}  ", cmd.key);
+            let key_display = format!("  {}  ", app.command_key(cmd));
             let score_display = if *score > 0 {
                 format!(" ({})", score)
             } else {
@@ -736,11 +1109,42 @@ fn render_command_palette(frame: &mut Frame, app: &AppState) {
     );
 
     frame.render_widget(results_table, chunks[1]);
+
+    if !quick_tasks.is_empty() {
+        let mut spans = Vec::new();
+        for (i, (key, name)) in quick_tasks.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                key.clone(),
+                Style::default()
+                    .fg(app.theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::styled(
+                format!(" {}", name),
+                Style::default().fg(app.theme.primary_text),
+            ));
+        }
+        let quick = Paragraph::new(Line::from(spans)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.active_border))
+                .title("⚡ Quick Tasks")
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
+        );
+        frame.render_widget(quick, chunks[2]);
+    }
 }
 
 fn render_calendar(frame: &mut Frame, app: &AppState) {
     use ratatui::widgets::Clear;
-    use time::{Date, Month, Weekday};
+    use time::{Date, Weekday};
+
+    let today = app.clock.today();
+    let timer_day = app.timer_day();
 
     // Create a centered modal
     let area = frame.size();
@@ -773,24 +1177,11 @@ fn render_calendar(frame: &mut Frame, app: &AppState) {
         .split(modal_area);
 
     // Render month/year header
-    let month_name = match app.calendar_view_month {
-        Month::January => "January",
-        Month::February => "February",
-        Month::March => "March",
-        Month::April => "April",
-        Month::May => "May",
-        Month::June => "June",
-        Month::July => "July",
-        Month::August => "August",
-        Month::September => "September",
-        Month::October => "October",
-        Month::November => "November",
-        Month::December => "December",
-    };
-
+    let ui = &app.config.ui;
     let header_text = format!(
         "📅  {} {}  [< prev] [next >]",
-        month_name, app.calendar_view_year
+        month_name(app.calendar_view_month, ui.locale),
+        app.calendar_view_year
     );
     let header = Paragraph::new(header_text)
         .style(
@@ -813,121 +1204,29 @@ fn render_calendar(frame: &mut Frame, app: &AppState) {
     let first_day =
         Date::from_calendar_date(app.calendar_view_year, app.calendar_view_month, 1).unwrap();
 
-    let days_in_month = get_days_in_month(app.calendar_view_month, app.calendar_view_year);
-    let first_weekday = first_day.weekday();
-
-    // Calculate starting offset (0 = Monday, 6 = Sunday)
-    let offset = match first_weekday {
-        Weekday::Monday => 0,
-        Weekday::Tuesday => 1,
-        Weekday::Wednesday => 2,
-        Weekday::Thursday => 3,
-        Weekday::Friday => 4,
-        Weekday::Saturday => 5,
-        Weekday::Sunday => 6,
-    };
+    let days_in_month = days_in_month(app.calendar_view_month, app.calendar_view_year);
+    let offset = calendar_offset(first_day.weekday(), ui.week_start);
 
     // Create calendar rows
     let mut rows = Vec::new();
 
-    // Header row with weekday names
-    rows.push(Row::new(vec![
-        Cell::from("Mon").style(
-            Style::default()
-                .fg(app.theme.warning)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Cell::from("Tue").style(
-            Style::default()
-                .fg(app.theme.warning)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Cell::from("Wed").style(
-            Style::default()
-                .fg(app.theme.warning)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Cell::from("Thu").style(
-            Style::default()
-                .fg(app.theme.warning)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Cell::from("Fri").style(
-            Style::default()
-                .fg(app.theme.warning)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Cell::from("Sat").style(
-            Style::default()
-                .fg(app.theme.info)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Cell::from("Sun").style(
-            Style::default()
-                .fg(app.theme.info)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
+    // Header row with weekday names, weekends highlighted wherever they fall
+    rows.push(Row::new(week_columns(ui.week_start).map(|weekday| {
+        let color = match weekday {
+            Weekday::Saturday | Weekday::Sunday => app.theme.info,
+            _ => app.theme.warning,
+        };
+        Cell::from(short_weekday_name(weekday, ui.locale))
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+    })));
 
-    // Calendar days
-    let mut current_day = 1;
+    // Calendar days, padded with empty cells before day 1 and after the last day
+    let slots = (offset + days_in_month).div_ceil(7) * 7;
     let mut week_row = Vec::new();
-
-    // Fill in the offset days with empty cells
-    for _ in 0..offset {
-        week_row.push(Cell::from("  "));
-    }
-
-    // Fill in the actual days
-    for day_of_week in offset..7 {
-        if current_day <= days_in_month {
-            let date = Date::from_calendar_date(
-                app.calendar_view_year,
-                app.calendar_view_month,
-                current_day,
-            )
-            .unwrap();
-
-            let is_selected = date == app.calendar_selected_date;
-            let is_today = date == time::OffsetDateTime::now_utc().date();
-            let is_current_view = date == app.current_date;
-
-            let day_str = format!("{:2}", current_day);
-
-            let style = if is_selected {
-                Style::default()
-                    .bg(app.theme.visual_bg)
-                    .fg(app.theme.primary_text)
-                    .add_modifier(Modifier::BOLD)
-            } else if is_current_view {
-                Style::default()
-                    .bg(app.theme.selected_bg)
-                    .fg(app.theme.primary_text)
-                    .add_modifier(Modifier::BOLD)
-            } else if is_today {
-                Style::default()
-                    .fg(app.theme.success)
-                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
-            } else {
-                Style::default().fg(app.theme.primary_text)
-            };
-
-            week_row.push(Cell::from(day_str).style(style));
-            current_day += 1;
-        } else {
-            week_row.push(Cell::from("  "));
-        }
-
-        if day_of_week == 6 {
-            rows.push(Row::new(week_row.clone()));
-            week_row.clear();
-        }
-    }
-
-    // Continue filling remaining weeks
-    while current_day <= days_in_month {
-        for _ in 0..7 {
-            if current_day <= days_in_month {
+    for slot in 0..slots {
+        let day = slot.checked_sub(offset).map(|index| index + 1);
+        match day.filter(|&day| day <= days_in_month) {
+            Some(current_day) => {
                 let date = Date::from_calendar_date(
                     app.calendar_view_year,
                     app.calendar_view_month,
@@ -936,10 +1235,10 @@ fn render_calendar(frame: &mut Frame, app: &AppState) {
                 .unwrap();
 
                 let is_selected = date == app.calendar_selected_date;
-                let is_today = date == time::OffsetDateTime::now_utc().date();
+                let is_today = date == today;
                 let is_current_view = date == app.current_date;
 
-                let day_str = format!("{:2}", current_day);
+                let day_str = calendar_day_label(current_day, timer_day == Some(date));
 
                 let style = if is_selected {
                     Style::default()
@@ -960,13 +1259,13 @@ fn render_calendar(frame: &mut Frame, app: &AppState) {
                 };
 
                 week_row.push(Cell::from(day_str).style(style));
-                current_day += 1;
-            } else {
-                week_row.push(Cell::from("  "));
             }
+            None => week_row.push(Cell::from("  ")),
+        }
+
+        if slot % 7 == 6 {
+            rows.push(Row::new(std::mem::take(&mut week_row)));
         }
-        rows.push(Row::new(week_row.clone()));
-        week_row.clear();
     }
 
     let calendar_table = Table::new(
@@ -998,29 +1297,64 @@ fn render_calendar(frame: &mut Frame, app: &AppState) {
     frame.render_widget(calendar_table, chunks[1]);
 }
 
-fn get_days_in_month(month: time::Month, year: i32) -> u8 {
-    use time::Month;
-    match month {
-        Month::January
-        | Month::March
-        | Month::May
-        | Month::July
-        | Month::August
-        | Month::October
-        | Month::December => 31,
-        Month::April | Month::June | Month::September | Month::November => 30,
-        Month::February => {
-            if is_leap_year_render(year) {
-                29
-            } else {
-                28
-            }
-        }
+/// Day number for a calendar cell, marked when the running session belongs to it
+fn calendar_day_label(day: u8, is_timer_day: bool) -> String {
+    if is_timer_day {
+        format!("{:2}▶", day)
+    } else {
+        format!("{:2}", day)
     }
 }
 
-fn is_leap_year_render(year: i32) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+fn render_url_notice(frame: &mut Frame, app: &AppState) {
+    use ratatui::text::Line;
+    use ratatui::widgets::{Clear, Wrap};
+
+    let Some(notice) = &app.url_notice else {
+        return;
+    };
+
+    let area = frame.size();
+    let width = area.width.min(90);
+    let height = 7;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    // The URL gets its own line(s) so it can be selected with the mouse
+    let lines = vec![
+        Line::from(notice.message.clone()).style(Style::default().fg(app.theme.secondary_text)),
+        Line::from(""),
+        Line::from(notice.url.clone()).style(
+            Style::default()
+                .fg(app.theme.primary_text)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.info))
+            .title("🔗 Link (press any key to close)")
+            .title_style(
+                Style::default()
+                    .fg(app.theme.info)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(app.theme.row_alternate_bg)),
+    );
+
+    frame.render_widget(paragraph, modal_area);
 }
 
 fn render_error_modal(frame: &mut Frame, app: &AppState) {
@@ -1092,16 +1426,13 @@ fn render_error_modal(frame: &mut Frame, app: &AppState) {
     frame.render_widget(help, chunks[1]);
 }
 
-fn render_task_picker(frame: &mut Frame, app: &AppState) {
+fn render_clear_day(frame: &mut Frame, app: &AppState) {
+    use ratatui::text::Line;
     use ratatui::widgets::Clear;
 
-    let filtered_tasks = app.get_filtered_task_names();
-    let all_tasks = app.get_unique_task_names();
-
-    // Create a smaller centered modal (mini-picker style)
     let area = frame.size();
     let width = area.width.min(60);
-    let height = (filtered_tasks.len() as u16 + 8).clamp(12, 20); // Ensure minimum height for visibility
+    let height = 5;
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
 
@@ -1112,90 +1443,764 @@ fn render_task_picker(frame: &mut Frame, app: &AppState) {
         height,
     };
 
-    // Clear the background
     frame.render_widget(Clear, modal_area);
 
-    // Add a background block for the entire modal
-    let bg_block = Block::default().style(Style::default().bg(app.theme.selected_inactive_bg));
-    frame.render_widget(bg_block, modal_area);
-
-    // Split modal into header, input, and list
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Length(4), // Input field (increased for better visibility)
-            Constraint::Min(5),    // List
-        ])
-        .split(modal_area);
-
-    // Render header with help text
-    let header_text = if app.input_buffer.is_empty() {
-        "Select existing task or type new name"
-    } else {
-        "Type to filter, or create new task"
-    };
-
-    let header = Paragraph::new(header_text)
-        .style(Style::default().fg(app.theme.primary_text))
+    let lines = vec![
+        Line::from(view::clear_day_question(app))
+            .style(Style::default().fg(app.theme.primary_text)),
+        Line::from(""),
+        Line::from("y: Clear | any other key: Cancel")
+            .style(Style::default().fg(app.theme.secondary_text)),
+    ];
+    let prompt = Paragraph::new(lines)
         .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true })
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(app.theme.info))
-                .title("📋 Task Picker")
+                .border_style(Style::default().fg(app.theme.error))
+                .title("🗑  Clear day")
                 .title_style(
                     Style::default()
-                        .fg(app.theme.info)
+                        .fg(app.theme.error)
                         .add_modifier(Modifier::BOLD),
                 )
-                .style(Style::default().bg(app.theme.selected_inactive_bg)),
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
         );
 
-    frame.render_widget(header, chunks[0]);
+    frame.render_widget(prompt, modal_area);
+}
 
-    // Render input field
-    let input_display = if app.input_buffer.is_empty() {
-        "Start typing...".to_string()
-    } else {
-        app.input_buffer.clone()
+fn render_confirm_cascade(frame: &mut Frame, app: &AppState) {
+    use ratatui::text::Line;
+    use ratatui::widgets::Clear;
+
+    let Some(question) = view::cascade_question(app) else {
+        return;
     };
+    let area = frame.size();
+    let width = area.width.min(60);
+    let height = 5;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
 
-    let input = Paragraph::new(input_display)
-        .style(if app.input_buffer.is_empty() {
-            Style::default().fg(app.theme.secondary_text)
-        } else {
-            Style::default()
-                .fg(app.theme.primary_text)
-                .add_modifier(Modifier::BOLD)
-        })
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let lines = vec![
+        Line::from(question).style(Style::default().fg(app.theme.primary_text)),
+        Line::from(""),
+        Line::from("y: Move it too | any other key: Leave it")
+            .style(Style::default().fg(app.theme.secondary_text)),
+    ];
+    let prompt = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true })
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(app.theme.warning))
-                .title("Filter / New Task")
-                .title_style(Style::default().fg(app.theme.warning))
-                .style(Style::default().bg(app.theme.selected_inactive_bg))
-                .padding(ratatui::widgets::Padding::horizontal(1)),
+                .border_style(Style::default().fg(app.theme.success))
+                .title("⏩ Next record")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.success)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
         );
 
-    frame.render_widget(input, chunks[1]);
+    frame.render_widget(prompt, modal_area);
+}
 
-    // Render task list
-    if all_tasks.is_empty() {
-        let empty_msg = Paragraph::new("No existing tasks. Type to create new one.")
-            .style(Style::default().fg(app.theme.secondary_text))
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(app.theme.info))
-                    .style(Style::default().bg(app.theme.selected_inactive_bg)),
-            );
-        frame.render_widget(empty_msg, chunks[2]);
+fn render_merge_conflict(frame: &mut Frame, app: &AppState) {
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::Clear;
+
+    let Some((question, mine, disk)) = view::merge_conflict_text(app) else {
+        return;
+    };
+
+    let area = frame.size();
+    let width = area.width.min(70);
+    let height = 8;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let label = Style::default()
+        .fg(app.theme.secondary_text)
+        .add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.primary_text);
+    let lines = vec![
+        Line::from(question).style(text),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Mine: ", label),
+            Span::styled(mine, text),
+        ]),
+        Line::from(vec![
+            Span::styled("Disk: ", label),
+            Span::styled(disk, text),
+        ]),
+        Line::from(""),
+        Line::from("m: Keep mine | d: Take disk | b: Keep both")
+            .style(Style::default().fg(app.theme.secondary_text)),
+    ];
+    let prompt = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.error))
+            .title("⚠  Changed on disk")
+            .title_style(
+                Style::default()
+                    .fg(app.theme.error)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(app.theme.row_alternate_bg)),
+    );
+
+    frame.render_widget(prompt, modal_area);
+}
+
+fn render_goto_id(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    // Small centered prompt
+    let area = frame.size();
+    let width = area.width.min(40);
+    let height = 3;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let prompt = Paragraph::new(format!("# {}▏", app.input_buffer))
+        .style(Style::default().fg(app.theme.primary_text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.info))
+                .title("🔎 Go to record ID")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
+        );
+
+    frame.render_widget(prompt, modal_area);
+}
+
+fn render_link_prompt(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    // Wide enough for typical URLs, centered
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(80);
+    let height = 3;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    // Keep the end of long URLs (where the cursor is) visible
+    let visible = width.saturating_sub(4) as usize;
+    let chars: Vec<char> = app.input_buffer.chars().collect();
+    let shown: String = chars[chars.len().saturating_sub(visible)..]
+        .iter()
+        .collect();
+
+    let prompt = Paragraph::new(format!("{}▏", shown))
+        .style(Style::default().fg(app.theme.primary_text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.info))
+                .title("🔗 Record link")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
+        );
+
+    frame.render_widget(prompt, modal_area);
+}
+
+fn render_annotation_prompt(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(60);
+    let height = 3;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    // Keep the end of the note (where the cursor is) visible
+    let visible = width.saturating_sub(4) as usize;
+    let chars: Vec<char> = app.input_buffer.chars().collect();
+    let shown: String = chars[chars.len().saturating_sub(visible)..]
+        .iter()
+        .collect();
+    // A session note is for the running task, a summary note for the selected one
+    let title = match (&app.mode, &app.active_timer) {
+        (crate::ui::AppMode::SessionNotePrompt, Some(timer)) => {
+            format!("📝 Session note for {}", timer.task_name)
+        }
+        (crate::ui::AppMode::JoinPrompt, _) => {
+            format!("⛓ Join {} records as", app.join_ids.len())
+        }
+        (crate::ui::AppMode::ShiftPrompt, _) => format!(
+            "⇅ Shift all {} records by (+1h, -30m)",
+            app.day_data.work_records.len()
+        ),
+        (crate::ui::AppMode::TemplateNamePrompt, _) => match &app.template_renaming {
+            Some(old) => format!("🧩 Rename template {}", old),
+            None => "🧩 Save as template".to_string(),
+        },
+        _ => format!(
+            "📝 Note for {}",
+            app.get_selected_record()
+                .map(|record| record.name.as_str())
+                .unwrap_or_default()
+        ),
+    };
+
+    let prompt = Paragraph::new(format!("{}▏", shown))
+        .style(Style::default().fg(app.theme.primary_text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.info))
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
+        );
+
+    frame.render_widget(prompt, modal_area);
+}
+
+fn render_estimate_prompt(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(40);
+    let height = 3;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let prompt = Paragraph::new(format!("{}▏", app.input_buffer))
+        .style(Style::default().fg(app.theme.primary_text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.info))
+                .title("⏳ Estimate (minutes or H:MM)")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
+        );
+
+    frame.render_widget(prompt, modal_area);
+}
+
+fn render_adjustment_prompt(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(40);
+    let height = 3;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let prompt = Paragraph::new(format!("{}▏", app.input_buffer))
+        .style(Style::default().fg(app.theme.primary_text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.info))
+                .title("± Adjustment (e.g. -30 or +1:30)")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.row_alternate_bg)),
+        );
+
+    frame.render_widget(prompt, modal_area);
+}
+
+fn render_fields_editor(frame: &mut Frame, app: &AppState) {
+    use crate::config::FieldDef;
+    use ratatui::widgets::Clear;
+
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(60);
+    let height = (app.field_drafts.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let name_width = app
+        .field_drafts
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let rows: Vec<Row> = app
+        .field_drafts
+        .iter()
+        .enumerate()
+        .map(|(i, (name, value))| {
+            let is_selected = i == app.field_selected;
+            let shown = match app.config.fields.get(name) {
+                Some(FieldDef::Choice { .. }) if value.is_empty() => "◀ (unset) ▶".to_string(),
+                Some(FieldDef::Choice { .. }) => format!("◀ {} ▶", value),
+                _ if is_selected => format!("{}▏", value),
+                _ => value.clone(),
+            };
+            let style = if is_selected {
+                Style::default()
+                    .bg(app.theme.selected_bg)
+                    .fg(app.theme.primary_text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.primary_text)
+            };
+            Row::new(vec![
+                Cell::from(format!("{:width$}", name, width = name_width)),
+                Cell::from(shown),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(name_width as u16 + 1),
+            Constraint::Min(10),
+        ],
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.info))
+            .title("🏷  Custom fields")
+            .title_style(
+                Style::default()
+                    .fg(app.theme.info)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(app.theme.row_alternate_bg)),
+    );
+
+    frame.render_widget(table, modal_area);
+}
+
+fn render_revert_field(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let changes = app.selected_record_changes();
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(70);
+    let height = (changes.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let rows: Vec<Row> = changes
+        .iter()
+        .enumerate()
+        .map(|(i, change)| {
+            let style = if i == app.revert_selected {
+                Style::default()
+                    .bg(app.theme.selected_bg)
+                    .fg(app.theme.primary_text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.primary_text)
+            };
+            Row::new(vec![Cell::from(view::field_change_line(change))]).style(style)
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Min(10)]).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.info))
+            .title("↶ Revert field")
+            .title_style(
+                Style::default()
+                    .fg(app.theme.info)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(app.theme.row_alternate_bg)),
+    );
+
+    frame.render_widget(table, modal_area);
+}
+
+fn render_recent_days(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(50);
+    let height = (app.recent_days.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let rows: Vec<Row> = app
+        .recent_days
+        .iter()
+        .enumerate()
+        .map(|(i, day)| {
+            let style = if i == app.recent_days_selected {
+                Style::default()
+                    .bg(app.theme.selected_bg)
+                    .fg(app.theme.primary_text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.primary_text)
+            };
+            Row::new(vec![Cell::from(view::recent_day_line(app, day))]).style(style)
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Min(10)]).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.info))
+            .title("📅 Recent days")
+            .title_style(
+                Style::default()
+                    .fg(app.theme.info)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(app.theme.row_alternate_bg)),
+    );
+
+    frame.render_widget(table, modal_area);
+}
+
+fn render_week_grid(frame: &mut Frame, app: &AppState) {
+    use crate::report::{CELL_WIDTH, cell_text};
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::Clear;
+
+    let Some(grid) = &app.week_grid else {
+        return;
+    };
+
+    // Task column, then seven days and the total, one space apart
+    let cell_width = CELL_WIDTH as u16;
+    let cells_width = 8 * (cell_width + 1);
+    let area = frame.size();
+    let width = area.width.saturating_sub(4).min(cells_width + 30);
+    let name_width = width.saturating_sub(cells_width + 2) as usize;
+    // Task budgets go under the table, set off by a blank line
+    let budget_lines = match app.period_budgets.len() {
+        0 => 0,
+        count => count as u16 + 1,
+    };
+    // Header and totals rows, plus borders
+    let height = (grid.rows.len().max(1) as u16 + 4 + budget_lines).min(area.height);
+    let visible = height.saturating_sub(4 + budget_lines) as usize;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let text_style = Style::default().fg(app.theme.primary_text);
+    let bold = text_style.add_modifier(Modifier::BOLD);
+    let cells_row = |name: &str, minutes: &[u32], style: Style| {
+        let mut cells = vec![Cell::from(truncate_to_width(name, name_width))];
+        cells.extend(minutes.iter().map(|&minutes| {
+            Cell::from(format!(
+                "{:>width$}",
+                cell_text(minutes),
+                width = CELL_WIDTH
+            ))
+        }));
+        Row::new(cells).style(style)
+    };
+
+    let mut header = vec![Cell::from("Task")];
+    header.extend(
+        grid.day_labels(app.config.ui.locale)
+            .into_iter()
+            .chain(["Total".to_string()])
+            .map(|label| Cell::from(format!("{:>width$}", label, width = CELL_WIDTH))),
+    );
+
+    let scroll = app
+        .week_grid_scroll
+        .min(grid.rows.len().saturating_sub(visible));
+    let mut rows: Vec<Row> = grid
+        .rows
+        .iter()
+        .skip(scroll)
+        .take(visible)
+        .map(|row| {
+            let mut minutes = row.minutes.to_vec();
+            minutes.push(row.total());
+            cells_row(&row.key, &minutes, text_style)
+        })
+        .collect();
+    if grid.rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from("No work recorded")]).style(text_style));
+    }
+    let mut totals = grid.column_totals().to_vec();
+    totals.push(grid.grand_total());
+    rows.push(cells_row("Total", &totals, bold));
+
+    let mut widths = vec![Constraint::Min(4)];
+    widths.extend([Constraint::Length(cell_width); 8]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.info))
+        .title(format!(
+            "🗓 Week of {}",
+            app.config.ui.format_date(grid.dates[0])
+        ))
+        .title_style(
+            Style::default()
+                .fg(app.theme.info)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(Style::default().bg(app.theme.row_alternate_bg));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(budget_lines)])
+        .split(inner);
+    let table = Table::new(rows, widths).header(Row::new(header).style(bold));
+    frame.render_widget(table, chunks[0]);
+
+    if budget_lines > 0 {
+        let lines: Vec<Line> = std::iter::once(Line::from(""))
+            .chain(app.period_budgets.iter().map(|(period, status)| {
+                Line::from(Span::styled(
+                    view::period_budget_line(app, *period, status),
+                    budget_style(app, status.level()),
+                ))
+            }))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+    }
+}
+
+fn render_task_picker(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let filtered_tasks = app.get_filtered_task_names();
+    let all_tasks = app.get_unique_task_names();
+
+    // Create a smaller centered modal (mini-picker style)
+    let area = frame.size();
+    let width = area.width.min(60);
+    let height = (filtered_tasks.len() as u16 + 8).clamp(12, 20); // Ensure minimum height for visibility
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+
+    let modal_area = Rect {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    // Clear the background
+    frame.render_widget(Clear, modal_area);
+
+    // Add a background block for the entire modal
+    let bg_block = Block::default().style(Style::default().bg(app.theme.selected_inactive_bg));
+    frame.render_widget(bg_block, modal_area);
+
+    // Split modal into header, input, and list
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(4), // Input field (increased for better visibility)
+            Constraint::Min(5),    // List
+        ])
+        .split(modal_area);
+
+    // Render header with help text
+    let header_text = view::task_picker_header(app);
+
+    let header = Paragraph::new(header_text)
+        .style(Style::default().fg(app.theme.primary_text))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.info))
+                .title("📋 Task Picker")
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .style(Style::default().bg(app.theme.selected_inactive_bg)),
+        );
+
+    frame.render_widget(header, chunks[0]);
+
+    // Render input field
+    let input_display = if app.input_buffer.is_empty() {
+        "Start typing...".to_string()
+    } else {
+        app.input_buffer.clone()
+    };
+
+    let input = Paragraph::new(input_display)
+        .style(if app.input_buffer.is_empty() {
+            Style::default().fg(app.theme.secondary_text)
+        } else {
+            Style::default()
+                .fg(app.theme.primary_text)
+                .add_modifier(Modifier::BOLD)
+        })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(app.theme.warning))
+                .title("Filter / New Task")
+                .title_style(Style::default().fg(app.theme.warning))
+                .style(Style::default().bg(app.theme.selected_inactive_bg))
+                .padding(ratatui::widgets::Padding::horizontal(1)),
+        );
+
+    frame.render_widget(input, chunks[1]);
+
+    // Render task list
+    if all_tasks.is_empty() {
+        let empty_msg = Paragraph::new("No existing tasks. Type to create new one.")
+            .style(Style::default().fg(app.theme.secondary_text))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(app.theme.info))
+                    .style(Style::default().bg(app.theme.selected_inactive_bg)),
+            );
+        frame.render_widget(empty_msg, chunks[2]);
     } else if filtered_tasks.is_empty() && !app.input_buffer.is_empty() {
         let new_task_msg =
             Paragraph::new(format!("Press Enter to create: \"{}\"", app.input_buffer))
@@ -1236,17 +2241,7 @@ fn render_task_picker(frame: &mut Frame, app: &AppState) {
                 };
 
                 // Add icon based on task type
-                let icon = if name.to_lowercase().contains("break") {
-                    "☕"
-                } else if name.to_lowercase().contains("meeting") {
-                    "👥"
-                } else if name.to_lowercase().contains("code")
-                    || name.to_lowercase().contains("dev")
-                {
-                    "💻"
-                } else {
-                    "📋"
-                };
+                let icon = task_icon(name);
 
                 let display_name = format!("{} {}", icon, name);
 
@@ -1281,44 +2276,162 @@ fn render_task_picker(frame: &mut Frame, app: &AppState) {
     }
 }
 
+fn render_template_picker(frame: &mut Frame, app: &AppState) {
+    use ratatui::widgets::Clear;
+
+    let templates = app.filtered_templates();
+
+    let area = frame.size();
+    let width = area.width.min(60);
+    let height = (templates.len() as u16 + 8).clamp(12, 20);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+    let bg_block = Block::default().style(Style::default().bg(app.theme.selected_inactive_bg));
+    frame.render_widget(bg_block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(5)])
+        .split(modal_area);
+
+    let (input_display, input_style) = if app.input_buffer.is_empty() {
+        (
+            "Type to filter...".to_string(),
+            Style::default().fg(app.theme.secondary_text),
+        )
+    } else {
+        (
+            app.input_buffer.clone(),
+            Style::default()
+                .fg(app.theme.primary_text)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let input = Paragraph::new(input_display).style(input_style).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.warning))
+            .title("🧩 Insert Template")
+            .title_style(Style::default().fg(app.theme.warning))
+            .style(Style::default().bg(app.theme.selected_inactive_bg))
+            .padding(ratatui::widgets::Padding::horizontal(1)),
+    );
+    frame.render_widget(input, chunks[0]);
+
+    let rows: Vec<Row> = templates
+        .iter()
+        .enumerate()
+        .map(|(i, template)| {
+            let style = if i == app.template_selected {
+                Style::default()
+                    .bg(app.theme.selected_bg)
+                    .fg(app.theme.primary_text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().bg(app.theme.selected_inactive_bg)
+            };
+            Row::new(vec![
+                Cell::from(template.name.clone())
+                    .style(Style::default().fg(app.theme.primary_text)),
+                Cell::from(view::template_detail(template))
+                    .style(Style::default().fg(app.theme.secondary_text)),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let title = if app.input_buffer.is_empty() {
+        format!("Templates ({})", templates.len())
+    } else {
+        format!("Filtered ({}/{})", templates.len(), app.templates.len())
+    };
+    let table = Table::new(
+        rows,
+        [Constraint::Percentage(45), Constraint::Percentage(55)],
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.info))
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(app.theme.info)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(app.theme.selected_inactive_bg)),
+    );
+    frame.render_widget(table, chunks[1]);
+}
+
 /// Render timer bar showing active timer status at the top of the screen
 fn render_timer_bar(frame: &mut Frame, area: Rect, app: &AppState) {
     use crate::timer::TimerStatus;
 
     if let Some(timer) = &app.active_timer {
-        // Calculate elapsed time directly without needing storage
-        let elapsed = calculate_timer_elapsed(timer);
-
-        // Format elapsed time
-        let secs = elapsed.as_secs();
-        let hours = secs / 3600;
-        let mins = (secs % 3600) / 60;
-        let seconds = secs % 60;
-
         let status_icon = match timer.status {
             TimerStatus::Running => "▶",
             TimerStatus::Paused => "⏸",
             TimerStatus::Stopped => "⏹",
         };
 
-        let timer_text = if hours > 0 {
-            format!(
-                "{} {} - {}:{}:{}",
-                status_icon, timer.task_name, hours, mins, seconds
-            )
-        } else {
-            format!(
-                "{} {} - {:02}:{:02}",
-                status_icon, timer.task_name, mins, seconds
-            )
-        };
+        let elapsed_text = view::timer_elapsed_text(app, timer);
+        let mut timer_text = format!("{} {} - {}", status_icon, timer.task_name, elapsed_text);
+        // Heartbeat so a running session visibly ticks; still while paused
+        if app.config.ui.timer_spinner && timer.status == TimerStatus::Running {
+            timer_text.push(' ');
+            timer_text.push_str(super::spinner::spinner_frame(app.frame));
+        }
+        if app.paused_while_away() {
+            timer_text.push_str(" (away)");
+        }
+        if !timer.notes.is_empty() {
+            timer_text.push_str(&format!(" 📝{}", timer.notes.len()));
+        }
+        // Which record the session will update; `g a` jumps to it
+        match view::timer_location(app) {
+            Some(TimerLocation::Here(id)) => timer_text.push_str(&format!(" | record #{}", id)),
+            Some(TimerLocation::OtherDay(date, Some(id))) => {
+                timer_text.push_str(&format!(" | timer → {} #{}", date, id));
+            }
+            Some(TimerLocation::OtherDay(date, None)) => {
+                timer_text.push_str(&format!(" | timer → {}", date));
+            }
+            None => {}
+        }
 
-        let timer_color = match timer.status {
+        let mut timer_color = match timer.status {
             TimerStatus::Running => app.theme.success,
             TimerStatus::Paused => app.theme.warning,
             TimerStatus::Stopped => app.theme.error,
         };
 
+        // Budget banner for the tracked ticket
+        if let Some((level, notice)) = app
+            .budget_status_for(&timer.task_name)
+            .and_then(view::budget_notice)
+        {
+            match level {
+                BudgetLevel::Ok => {}
+                BudgetLevel::Warning => {
+                    timer_text.push_str(&format!(" | ⚠ {}", notice));
+                    timer_color = app.theme.warning;
+                }
+                BudgetLevel::Exceeded => {
+                    timer_text.push_str(&format!(" | ⛔ {}", notice));
+                    timer_color = app.theme.error;
+                }
+            }
+        }
+
         let timer_paragraph = Paragraph::new(timer_text)
             .style(
                 Style::default()
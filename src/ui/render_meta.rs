@@ -0,0 +1,182 @@
+//! Per-record decoration for the records table: icon, ticket badge and markers
+//!
+//! Built once per row and shared by the browse and editing branches of
+//! `render_records`. Ticket lookups go through `AppState::resolved_ticket`, whose
+//! cache only reruns the patterns after a rename or a change to `[integrations]`.
+
+use crate::integrations::tracker_badge;
+use crate::models::WorkRecord;
+use crate::ui::AppState;
+use crate::ui::text_width::truncate_to_width;
+use ratatui::style::Color;
+
+/// How a record's name cell is decorated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderMeta {
+    /// Task icon; a stopwatch while the row's session is running
+    pub icon: &'static str,
+    /// "🎫jira " for a detected ticket, with the tracker's `badge_icon` in place
+    /// of 🎫; empty without one or without integrations
+    pub ticket_badge: String,
+    /// The tracker's `badge_color`, for drawing `ticket_badge`
+    pub badge_color: Option<Color>,
+    /// The name as shown outside of editing: markers, link or ticket badge and icon,
+    /// with the name cut to `[ui] max_name_chars`
+    pub label: String,
+}
+
+impl RenderMeta {
+    pub fn build(app: &AppState, record: &WorkRecord, timing: bool) -> Self {
+        let icon = record_icon(record, timing);
+        let (ticket_badge, badge_color) = match app.resolved_ticket(record) {
+            Some(resolved) => {
+                let badge = tracker_badge(resolved.tracker.as_deref(), &app.config);
+                let tracker = resolved.tracker.unwrap_or_default();
+                (format!("{}{} ", badge.icon, tracker), badge.color)
+            }
+            None => (String::new(), None),
+        };
+
+        // An explicit link wins over the detected ticket
+        let badge = if record.url.is_some() {
+            "🔗 "
+        } else {
+            ticket_badge.as_str()
+        };
+        let name = match app.config.ui.max_name_chars {
+            0 => record.name.clone(),
+            max => truncate_to_width(&record.name, max),
+        };
+        let mut label = format!("{}{} {}", badge, icon, name);
+        if !app.record_issues(record).is_empty() {
+            label.insert_str(0, "⚠ ");
+        }
+        if app.is_flagged(record.id) {
+            label.insert_str(0, "⚑ ");
+        }
+
+        RenderMeta {
+            icon,
+            ticket_badge,
+            badge_color,
+            label,
+        }
+    }
+}
+
+/// Icon guessed from the kind of record and its name
+fn record_icon(record: &WorkRecord, timing: bool) -> &'static str {
+    let name = record.name.to_lowercase();
+    if timing {
+        "⏱ "
+    } else if record.is_adjustment() {
+        "±"
+    } else if record.is_break() {
+        "☕"
+    } else if name.contains("meeting") {
+        "👥"
+    } else if name.contains("code") || name.contains("dev") {
+        "💻"
+    } else {
+        "📋"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::{DayData, TimePoint};
+    use time::macros::date;
+
+    fn app_with(name: &str, config: Config) -> AppState {
+        let mut day = DayData::new(date!(2025 - 11 - 06));
+        day.add_record(WorkRecord::new(
+            1,
+            name.to_string(),
+            TimePoint::new(9, 0).unwrap(),
+            TimePoint::new(10, 0).unwrap(),
+        ));
+        AppState::with_config(day, config)
+    }
+
+    fn jira() -> Config {
+        toml::from_str(
+            r#"
+[integrations.trackers.jira]
+enabled = true
+base_url = "https://test.atlassian.net"
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn meta(app: &AppState, timing: bool) -> RenderMeta {
+        RenderMeta::build(app, &app.day_data.work_records[&1], timing)
+    }
+
+    #[test]
+    fn test_ticket_badge_names_the_tracker() {
+        let app = app_with("PROJ-7 Code review", jira());
+        let meta = meta(&app, false);
+        assert_eq!(meta.icon, "💻");
+        assert_eq!(meta.ticket_badge, "🎫jira ");
+        assert_eq!(meta.label, "🎫jira 💻 PROJ-7 Code review");
+    }
+
+    #[test]
+    fn test_ticket_badge_uses_the_trackers_icon_and_color() {
+        let mut config = jira();
+        let tracker = config.integrations.trackers.get_mut("jira").unwrap();
+        tracker.badge_icon = Some("◆".to_string());
+        tracker.badge_color = Some("blue".to_string());
+        let app = app_with("PROJ-7 Code review", config);
+        let custom = meta(&app, false);
+        assert_eq!(custom.ticket_badge, "◆jira ");
+        assert_eq!(custom.badge_color, Some(Color::Blue));
+        assert_eq!(custom.label, "◆jira 💻 PROJ-7 Code review");
+
+        let plain = app_with("PROJ-7 Code review", jira());
+        assert_eq!(meta(&plain, false).badge_color, None);
+    }
+
+    #[test]
+    fn test_no_badge_without_a_ticket_or_integrations() {
+        let app = app_with("Team meeting", jira());
+        assert_eq!(meta(&app, false).label, "👥 Team meeting");
+
+        let app = app_with("PROJ-7 Code review", Config::default());
+        let meta = meta(&app, false);
+        assert_eq!(meta.ticket_badge, "");
+        assert_eq!(meta.label, "💻 PROJ-7 Code review");
+    }
+
+    #[test]
+    fn test_link_flag_and_timer_decorate_the_label() {
+        let mut app = app_with("PROJ-7 Code review", jira());
+        app.day_data.work_records.get_mut(&1).unwrap().url = Some("https://x.test".to_string());
+        app.toggle_flag();
+        let meta = meta(&app, true);
+        assert_eq!(meta.icon, "⏱ ");
+        // The ticket badge is kept for editing, where the link isn't shown
+        assert_eq!(meta.ticket_badge, "🎫jira ");
+        assert_eq!(meta.label, "⚑ 🔗 ⏱  PROJ-7 Code review");
+    }
+
+    #[test]
+    fn test_name_cut_to_max_name_chars() {
+        let mut app = app_with("PROJ-7 Code review", jira());
+        app.config.ui.max_name_chars = 10;
+        assert_eq!(meta(&app, false).label, "🎫jira 💻 PROJ-7 Co…");
+        // Emoji count as two columns and combined letters stay whole
+        app.day_data.work_records.get_mut(&1).unwrap().name = "Cafe\u{301} ☕☕ chat".to_string();
+        assert_eq!(meta(&app, false).label, "📋 Cafe\u{301} ☕☕…");
+        // Names that fit, or no limit, are left alone
+        app.config.ui.max_name_chars = 0;
+        assert_eq!(meta(&app, false).label, "📋 Cafe\u{301} ☕☕ chat");
+        // The stored name is untouched
+        assert_eq!(app.day_data.work_records[&1].name, "Cafe\u{301} ☕☕ chat");
+    }
+}
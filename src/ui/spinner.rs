@@ -0,0 +1,31 @@
+//! Heartbeat glyph for the timer bar
+//!
+//! The frame counter is bumped once per redraw, so a running session visibly ticks
+//! even when nothing else on screen changes.
+
+/// Braille spinner, one glyph per redraw
+pub const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Glyph to show on redraw number `frame`
+pub fn spinner_frame(frame: u64) -> &'static str {
+    FRAMES[(frame % FRAMES.len() as u64) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spinner_frame_cycles() {
+        assert_eq!(spinner_frame(0), "⠋");
+        assert_eq!(spinner_frame(1), "⠙");
+        assert_eq!(spinner_frame(9), "⠏");
+        assert_eq!(spinner_frame(10), "⠋");
+        assert_eq!(spinner_frame(23), "⠸");
+    }
+
+    #[test]
+    fn test_spinner_frame_survives_large_counters() {
+        assert_eq!(spinner_frame(u64::MAX), FRAMES[(u64::MAX % 10) as usize]);
+    }
+}
@@ -0,0 +1,126 @@
+//! Display width of cell text, for fitting task names into table columns
+//!
+//! Terminals disagree on how wide many emoji are (`⏱` is one column in some,
+//! two in others). Widths here count every emoji as two columns, so text
+//! fitted with them never overflows its cell whichever way the terminal draws
+//! the icons. Truncation works on grapheme clusters, so a multi-codepoint
+//! emoji or a letter with combining marks is never split.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "…";
+
+/// Columns `text` may occupy, counting emoji as two columns wide
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Cut `text` to at most `max_width` columns, ending with `…` when shortened
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let budget = max_width - ELLIPSIS.width();
+    let mut width = 0;
+    let mut truncated = String::new();
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = grapheme_width(grapheme);
+        if width + grapheme_width > budget {
+            break;
+        }
+        width += grapheme_width;
+        truncated.push_str(grapheme);
+    }
+    truncated.push_str(ELLIPSIS);
+    truncated
+}
+
+fn grapheme_width(grapheme: &str) -> usize {
+    if is_emoji(grapheme) {
+        2
+    } else {
+        grapheme.width()
+    }
+}
+
+/// Emoji presentation requested (VS16) or a code point from the emoji blocks
+fn is_emoji(grapheme: &str) -> bool {
+    grapheme.chars().any(|c| {
+        matches!(c as u32,
+            0xFE0F
+            | 0x2300..=0x23FF   // Misc Technical (⏱, ⌛)
+            | 0x2600..=0x27BF   // Misc Symbols, Dingbats (☕, ✅)
+            | 0x2B00..=0x2BFF   // Misc Symbols and Arrows (⭐)
+            | 0x1F000..=0x1FAFF // Pictographs, emoticons, transport, supplemental
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_width() {
+        assert_eq!(display_width("Code review"), 11);
+        assert_eq!(display_width(""), 0);
+    }
+
+    #[test]
+    fn test_emoji_count_as_two_columns() {
+        assert_eq!(display_width("📋 Task"), 7);
+        assert_eq!(display_width("☕ Break"), 8);
+        // Narrow in unicode-width, but many terminals draw it wide
+        assert_eq!(display_width("⏱ x"), 4);
+        assert_eq!(display_width("🎫 💻 PROJ-1"), 12);
+    }
+
+    #[test]
+    fn test_cjk_and_combining_marks() {
+        assert_eq!(display_width("会議"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn test_truncate_leaves_short_text_alone() {
+        assert_eq!(truncate_to_width("📋 Task", 7), "📋 Task");
+        assert_eq!(truncate_to_width("📋 Task", 20), "📋 Task");
+    }
+
+    #[test]
+    fn test_truncate_adds_ellipsis_within_width() {
+        let truncated = truncate_to_width("📋 Feature development", 10);
+        assert_eq!(truncated, "📋 Featur…");
+        assert_eq!(display_width(&truncated), 10);
+    }
+
+    #[test]
+    fn test_truncate_never_splits_an_emoji() {
+        // The emoji does not fit next to the ellipsis, so it is dropped whole
+        assert_eq!(truncate_to_width("a📋bc", 3), "a…");
+        assert_eq!(truncate_to_width("📋📋", 3), "📋…");
+    }
+
+    #[test]
+    fn test_truncate_keeps_grapheme_clusters_whole() {
+        // Family emoji joined with ZWJ and a flag made of two regional indicators
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let text = format!("{}{}🇵🇱 team", family, family);
+        let truncated = truncate_to_width(&text, 4);
+        assert_eq!(truncated, format!("{}…", family));
+
+        let accented = "e\u{301}e\u{301}e\u{301}";
+        assert_eq!(truncate_to_width(accented, 2), "e\u{301}…");
+    }
+
+    #[test]
+    fn test_truncate_to_zero() {
+        assert_eq!(truncate_to_width("Task", 0), "");
+        assert_eq!(truncate_to_width("Task", 1), "…");
+    }
+}
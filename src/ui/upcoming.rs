@@ -0,0 +1,123 @@
+//! Countdown to the next record that hasn't started yet
+//!
+//! Meetings entered ahead of time are records with a start later than now. These
+//! helpers pick the next one and decide when its reminder is due; both are pure
+//! over (records, now) so the TUI can call them on every tick.
+
+use crate::models::{TimePoint, WorkRecord};
+
+/// Minutes before a start at which the countdown switches to a warning style
+pub const WARNING_MINUTES: u32 = 5;
+
+/// The record starting soonest after `now`, with the minutes until it starts
+///
+/// Records starting at `now` or earlier have already begun and are skipped, as are
+/// adjustments and, unless `include_breaks`, breaks. Ties go to the lower id.
+pub fn next_upcoming<'a>(
+    records: &[&'a WorkRecord],
+    now: TimePoint,
+    include_breaks: bool,
+) -> Option<(&'a WorkRecord, u32)> {
+    let now_minutes = now.to_minutes_since_midnight();
+    records
+        .iter()
+        .copied()
+        .filter(|record| record.start.to_minutes_since_midnight() > now_minutes)
+        .filter(|record| !record.is_adjustment())
+        .filter(|record| include_breaks || !record.is_break())
+        .min_by_key(|record| (record.start, record.id))
+        .map(|record| {
+            (
+                record,
+                record.start.to_minutes_since_midnight() - now_minutes,
+            )
+        })
+}
+
+/// Whether a reminder `lead_minutes` ahead of a start `minutes_until` away is due
+pub fn alert_due(minutes_until: u32, lead_minutes: u32) -> bool {
+    minutes_until <= lead_minutes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(hour: u8, minute: u8) -> TimePoint {
+        TimePoint::new(hour, minute).unwrap()
+    }
+
+    fn record(id: u32, name: &str, start: TimePoint) -> WorkRecord {
+        let end =
+            TimePoint::from_minutes_since_midnight(start.to_minutes_since_midnight() + 30).unwrap();
+        WorkRecord::new(id, name.to_string(), start, end)
+    }
+
+    #[test]
+    fn test_picks_the_soonest_future_record() {
+        let standup = record(1, "Standup", t(9, 30));
+        let sync = record(2, "Team sync", t(14, 0));
+        let review = record(3, "Review", t(11, 0));
+
+        let (next, minutes) = next_upcoming(&[&standup, &sync, &review], t(10, 37), false).unwrap();
+        assert_eq!(next.id, 3);
+        assert_eq!(minutes, 23);
+    }
+
+    #[test]
+    fn test_nothing_upcoming() {
+        let standup = record(1, "Standup", t(9, 30));
+        assert!(next_upcoming(&[&standup], t(16, 0), false).is_none());
+        assert!(next_upcoming(&[], t(16, 0), false).is_none());
+    }
+
+    #[test]
+    fn test_just_started_record_is_not_upcoming() {
+        let started = record(1, "Standup", t(9, 30));
+        let later = record(2, "Review", t(9, 31));
+
+        let (next, minutes) = next_upcoming(&[&started, &later], t(9, 30), false).unwrap();
+        assert_eq!(next.id, 2);
+        assert_eq!(minutes, 1);
+
+        assert!(next_upcoming(&[&started], t(9, 30), false).is_none());
+    }
+
+    #[test]
+    fn test_tie_goes_to_lower_id() {
+        let second = record(7, "Client call", t(13, 0));
+        let first = record(4, "Team sync", t(13, 0));
+
+        let (next, _) = next_upcoming(&[&second, &first], t(12, 0), false).unwrap();
+        assert_eq!(next.id, 4);
+    }
+
+    #[test]
+    fn test_breaks_excluded_unless_configured() {
+        let lunch = record(1, "Lunch break", t(12, 0));
+        let sync = record(2, "Team sync", t(14, 0));
+
+        let (next, _) = next_upcoming(&[&lunch, &sync], t(11, 0), false).unwrap();
+        assert_eq!(next.id, 2);
+
+        let (next, minutes) = next_upcoming(&[&lunch, &sync], t(11, 0), true).unwrap();
+        assert_eq!(next.id, 1);
+        assert_eq!(minutes, 60);
+    }
+
+    #[test]
+    fn test_adjustments_are_skipped() {
+        let mut credit = WorkRecord::new(1, "Correction".to_string(), t(15, 0), t(15, 0));
+        credit.adjustment_minutes = Some(-30);
+        assert!(next_upcoming(&[&credit], t(11, 0), true).is_none());
+    }
+
+    #[test]
+    fn test_alert_due_at_lead_time() {
+        assert!(!alert_due(11, 10));
+        assert!(alert_due(10, 10));
+        assert!(alert_due(1, 10));
+        assert!(alert_due(0, 0));
+        assert!(!alert_due(1, 0));
+    }
+}
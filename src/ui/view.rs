@@ -0,0 +1,530 @@
+//! Display data shared by the table renderer and the plain line renderer
+//!
+//! Everything here is text and numbers only. Each renderer decides how to
+//! decorate it: borders, icons and colours in `render`, bare lines in `plain`.
+
+use crate::budget::{BudgetLevel, BudgetPeriod, BudgetStatus};
+use crate::config::TimerPrecision;
+use crate::models::{GroupBy, PlanAudit, RecordTemplate, TimePoint, WorkRecord};
+use crate::timefmt::{self, format_minutes};
+use crate::timer::{TimerState, TimerStatus};
+use crate::ui::app_state::RecentDay;
+use crate::ui::field_changes::FieldChange;
+use crate::ui::hints::{self, HintContext};
+use crate::ui::text_width::display_width;
+use crate::ui::{AppMode, AppState};
+use time::Date;
+
+/// Day totals as shown in the header, without the clamped-adjustments warning
+///
+/// With the plan audit shown, planned and actual totals follow.
+pub fn day_totals_text(app: &AppState) -> String {
+    let totals = day_totals(app);
+    match app.plan_audit() {
+        Some(audit) => format!("{} | {}", totals, plan_totals_text(&audit)),
+        None => totals,
+    }
+}
+
+/// "Planned: 6h 00m, actual: 6h 30m, 1 missed"
+pub fn plan_totals_text(audit: &PlanAudit) -> String {
+    let mut text = format!(
+        "Planned: {}, actual: {}",
+        format_minutes(audit.planned_minutes),
+        format_minutes(audit.actual_minutes)
+    );
+    if !audit.missed.is_empty() {
+        text.push_str(&format!(", {} missed", audit.missed.len()));
+    }
+    text
+}
+
+fn day_totals(app: &AppState) -> String {
+    if let Some(minutes) = app
+        .filtered_minutes()
+        .filter(|_| app.config.ui.filtered_totals)
+    {
+        format!("Total: {} (filtered)", format_minutes(minutes))
+    } else if let Some(minutes) = app.window_worked_minutes {
+        format!(
+            "{} worked: {}",
+            app.totals_window.label(),
+            format_minutes(minutes)
+        )
+    } else {
+        let mut text = if app.config.ui.show_day_breakdown {
+            let breakdown = app.day_data.breakdown();
+            format!(
+                "Worked: {} | Breaks: {} | Span: {}",
+                format_minutes(breakdown.worked_minutes),
+                format_minutes(breakdown.break_minutes),
+                format_minutes(breakdown.span_minutes)
+            )
+        } else {
+            format!("Total: {}", format_minutes(app.day_data.total_minutes()))
+        };
+        if let Some(minutes) = billable_total(app) {
+            text.push_str(&format!(" · billable: {}", format_minutes(minutes)));
+        }
+        if let Some(focus) = app
+            .focus_today
+            .as_ref()
+            .filter(|focus| app.config.ui.show_day_breakdown && focus.sessions() > 0)
+        {
+            text.push_str(&format!(" · focus: {}", focus.summary()));
+        }
+        text
+    }
+}
+
+/// Billable part of the day's total, once a record on the day is non-billable
+pub fn billable_total(app: &AppState) -> Option<u32> {
+    let default = app.billable_default();
+    app.day_data
+        .has_non_billable(default)
+        .then(|| app.day_data.billable_minutes(default))
+}
+
+/// Duration column text: "2h 00m", or actual against estimate in minutes ("120/90m")
+pub fn duration_text(record: &WorkRecord) -> String {
+    match record.estimate_minutes {
+        Some(estimate) => format!("{}/{}m", record.effective_minutes(), estimate),
+        None => record.format_duration(),
+    }
+}
+
+/// Start and end of a record, relative to now when relative times are on
+///
+/// Relative display only applies to today's records, so both fall back to HH:MM
+/// together. Clock times are snapped to `[ui] snap_display_minutes`, for display
+/// only, and follow `[ui] time_format`.
+pub fn record_times(app: &AppState, record: &WorkRecord) -> (String, String) {
+    let now = app.clock.now();
+    let relative = |time: TimePoint| {
+        if app.relative_times {
+            time.relative_to(app.current_date, now)
+        } else {
+            None
+        }
+    };
+    match (relative(record.start), relative(record.end)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => {
+            let step = app.config.ui.snap_display_minutes;
+            let clock = app.config.ui.clock();
+            (
+                timefmt::format_time(record.start.snapped(step), clock),
+                timefmt::format_time(record.end.snapped(step), clock),
+            )
+        }
+    }
+}
+
+/// Elapsed session time at the configured precision
+pub fn timer_elapsed_text(app: &AppState, timer: &TimerState) -> String {
+    let elapsed = timefmt::elapsed(timer, app.clock.now());
+    match app.config.ui.timer_precision {
+        TimerPrecision::Seconds => timefmt::format_duration_clock(elapsed),
+        TimerPrecision::Minutes => timefmt::format_duration_minutes(elapsed),
+    }
+}
+
+/// Where `T` takes the selected record, e.g. "jira: https://…", shown while browsing
+///
+/// A ticket no tracker matches says so, which is how a routing mistake shows up
+/// before anything is opened.
+pub fn selected_link(app: &AppState) -> Option<String> {
+    if !matches!(app.mode, AppMode::Browse) {
+        return None;
+    }
+    let record = app.get_selected_record()?;
+    if let Some(url) = &record.url {
+        return Some(format!("link: {}", url));
+    }
+    let resolved = app.resolved_ticket(record)?;
+    Some(match (resolved.tracker, resolved.browse_url) {
+        (Some(tracker), Some(url)) => format!("{}: {}", tracker, url),
+        (Some(tracker), None) => format!("{}: {} has no URL", resolved.ticket, tracker),
+        (None, _) => format!("{}: no tracker matches", resolved.ticket),
+    })
+}
+
+/// The selected record's whole name while `[ui] max_name_chars` cuts it in the table
+pub fn selected_full_name(app: &AppState) -> Option<String> {
+    let max = app.config.ui.max_name_chars;
+    if max == 0 || !matches!(app.mode, AppMode::Browse) {
+        return None;
+    }
+    let record = app.get_selected_record()?;
+    (display_width(&record.name) > max).then(|| record.name.clone())
+}
+
+/// "Thu 2025-11-06 · 4 records · 5h 15m" for the recent days list
+pub fn recent_day_line(app: &AppState, day: &RecentDay) -> String {
+    let records = match day.records {
+        1 => "1 record".to_string(),
+        count => format!("{} records", count),
+    };
+    format!(
+        "{} {} · {} · {}",
+        timefmt::short_weekday_name(day.date.weekday(), app.config.ui.locale),
+        app.config.ui.format_date(day.date),
+        records,
+        format_minutes(day.worked_minutes)
+    )
+}
+
+/// "5000 of 7200 loaded, not saved" for a day past `[storage] max_records`
+pub fn truncated_note(app: &AppState) -> Option<String> {
+    app.day_data.truncated.map(|truncated| {
+        format!(
+            "{} of {} loaded, not saved",
+            truncated.kept, truncated.total
+        )
+    })
+}
+
+/// "PROJ-12 Review · 1h 30m" for the template picker
+pub fn template_detail(template: &RecordTemplate) -> String {
+    format!(
+        "{} · {}",
+        template.task,
+        format_minutes(template.duration_minutes)
+    )
+}
+
+/// Which record the running session will update, seen from the viewed day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerLocation {
+    /// A record on the day being viewed
+    Here(u32),
+    /// Another day, with the linked record if there is one
+    OtherDay(Date, Option<u32>),
+}
+
+pub fn timer_location(app: &AppState) -> Option<TimerLocation> {
+    let timer = app.active_timer.as_ref()?;
+    match (app.timer_day(), timer.source_record_id) {
+        (Some(date), id) if app.timer_on_other_day() => Some(TimerLocation::OtherDay(date, id)),
+        (_, Some(id)) => Some(TimerLocation::Here(id)),
+        _ => None,
+    }
+}
+
+/// Budget warning for the session's task, `None` while it is comfortably under
+pub fn budget_notice(status: &BudgetStatus) -> Option<(BudgetLevel, String)> {
+    match status.level() {
+        BudgetLevel::Ok => None,
+        BudgetLevel::Warning => Some((
+            BudgetLevel::Warning,
+            format!("{} at {}% of budget", status.key, status.percent()),
+        )),
+        BudgetLevel::Exceeded => Some((
+            BudgetLevel::Exceeded,
+            format!(
+                "{} over budget ({} / {})",
+                status.key,
+                format_minutes(status.spent_minutes),
+                format_minutes(status.budget_minutes)
+            ),
+        )),
+    }
+}
+
+/// A `[[task_budgets]]` line under the week grid, flagged once at 80% and when over
+pub fn period_budget_line(app: &AppState, period: BudgetPeriod, status: &BudgetStatus) -> String {
+    let flag = match status.level() {
+        BudgetLevel::Ok => " ",
+        BudgetLevel::Warning => "⚠",
+        BudgetLevel::Exceeded => "⛔",
+    };
+    let span = match period {
+        BudgetPeriod::Week => "this week".to_string(),
+        BudgetPeriod::Month => format!(
+            "in {}",
+            timefmt::month_name(app.calendar_selected_date.month(), app.config.ui.locale)
+        ),
+    };
+    format!(
+        "{} {}: {} of {} {}",
+        flag,
+        status.key,
+        format_minutes(status.spent_minutes),
+        format_minutes(status.budget_minutes),
+        span
+    )
+}
+
+/// Key hints and mode name for the footer
+pub fn mode_hint(app: &AppState) -> (String, &'static str) {
+    let (help, label) = match app.mode {
+        AppMode::Browse | AppMode::Visual => {
+            let label = match app.mode {
+                AppMode::Browse => "BROWSE",
+                _ => "VISUAL",
+            };
+            return (context_hint(app).unwrap_or_default(), label);
+        }
+        AppMode::Edit => ("Tab: Next field | Enter: Save | Esc: Cancel", "EDIT"),
+        AppMode::CommandPalette => (
+            "↑/↓: Navigate | Enter: Execute | Esc: Cancel",
+            "COMMAND PALETTE",
+        ),
+        AppMode::Calendar => (
+            "hjkl/arrows: Navigate | </>: Month | w: Week grid | Enter: Select | Esc: Cancel",
+            "CALENDAR",
+        ),
+        AppMode::TaskPicker => (
+            "Type: Filter/Create | ↑/↓: Navigate | Enter: Select | Esc: Cancel",
+            "TASK PICKER",
+        ),
+        AppMode::GotoId => (
+            "0-9: Record ID | a: Session record | Enter: Go | Esc: Cancel",
+            "GO TO ID",
+        ),
+        AppMode::LinkPrompt => (
+            "Type: URL | Enter: Save (empty clears) | Esc: Cancel",
+            "LINK",
+        ),
+        AppMode::AnnotationPrompt => (
+            "Type: Note | Enter: Save (empty clears) | Esc: Cancel",
+            "NOTE",
+        ),
+        AppMode::SessionNotePrompt => (
+            "Type: Note | Enter: Add to session | Esc: Cancel",
+            "SESSION NOTE",
+        ),
+        AppMode::JoinPrompt => (
+            "Type: Name of the joined record | Enter: Join | Esc: Cancel",
+            "JOIN",
+        ),
+        AppMode::ShiftPrompt => (
+            "Type: Offset like +1h, -30m or 90 | Enter: Shift day | Esc: Cancel",
+            "SHIFT",
+        ),
+        AppMode::EstimatePrompt => (
+            "Type: Minutes or H:MM | Enter: Save (empty clears) | Esc: Cancel",
+            "ESTIMATE",
+        ),
+        AppMode::AdjustmentPrompt => (
+            "Type: Minutes like -30 or +1:30 | Enter: Save (empty clears) | Esc: Cancel",
+            "ADJUST",
+        ),
+        AppMode::Fields => (
+            "↑/↓: Field | Type: Text | ←/→: Choice | Enter: Save | Esc: Cancel",
+            "FIELDS",
+        ),
+        AppMode::ConfirmClear => ("y: Clear all | Any other key: Cancel", "CLEAR DAY"),
+        AppMode::ConfirmCascade => ("y: Move it too | Any other key: Leave it", "CASCADE"),
+        AppMode::MergeConflict => ("m: Keep mine | d: Take disk | b: Keep both", "MERGE"),
+        AppMode::RevertField => (
+            "↑/↓: Edit | Enter: Revert to old value | Esc: Cancel",
+            "REVERT",
+        ),
+        AppMode::RecentDays => ("↑/↓: Day | Enter: Go to day | Esc: Cancel", "RECENT DAYS"),
+        AppMode::WeekGrid => ("↑/↓: Scroll | Esc: Back to calendar", "WEEK GRID"),
+        AppMode::TemplatePicker => (
+            "Type: Filter | ↑/↓: Navigate | Enter: Insert | Ctrl+R: Rename | Ctrl+D: Delete | Esc: Cancel",
+            "TEMPLATES",
+        ),
+        AppMode::TemplateNamePrompt => (
+            "Type: Template name | Enter: Save | Esc: Cancel",
+            "TEMPLATE",
+        ),
+        AppMode::Summary => (
+            "↑/↓: Row | Enter: Show only its records | Esc: Back",
+            "SUMMARY",
+        ),
+    };
+    (help.to_string(), label)
+}
+
+/// Context hints for the footer, with any `[keys]` overrides in place of the built-in keys
+fn context_hint(app: &AppState) -> Option<String> {
+    let context = HintContext {
+        has_records: !app.day_data.work_records.is_empty(),
+        timer: app
+            .active_timer
+            .as_ref()
+            .map(|timer| timer.status)
+            .filter(|status| *status != TimerStatus::Stopped),
+        integrations: app.has_integrations(),
+        selected: (0..app.day_data.work_records.len())
+            .filter(|&index| app.is_in_visual_selection(index))
+            .count(),
+    };
+    let hints = hints::hints(&app.mode, context)?;
+    let hints: Vec<String> = hints
+        .into_iter()
+        .map(|hint| {
+            let key = match hint.action {
+                Some(action) => app.keymap.label(action, hint.key),
+                None => hint.key.to_string(),
+            };
+            format!("{}: {}", key, hint.label)
+        })
+        .collect();
+    Some(hints.join(" | "))
+}
+
+/// "End: 10:30 → 11:45 (14:02)" for the Revert field list
+pub fn field_change_line(change: &FieldChange) -> String {
+    let shown = |value: &str| {
+        if value.is_empty() {
+            "(empty)".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    format!(
+        "{}: {} → {} ({:02}:{:02})",
+        change.field.label(),
+        shown(&change.old),
+        shown(&change.new),
+        change.at.hour(),
+        change.at.minute()
+    )
+}
+
+/// Question asked before clearing the viewed day
+pub fn clear_day_question(app: &AppState) -> String {
+    format!(
+        "Delete all {} record(s) on {}? Undo with u.",
+        app.day_data.work_records.len(),
+        app.current_date
+    )
+}
+
+/// Question asked before moving the next record's start along with an edited end
+pub fn cascade_question(app: &AppState) -> Option<String> {
+    let cascade = app.pending_cascade?;
+    let record = app.day_data.work_records.get(&cascade.id)?;
+    Some(format!(
+        "Also move {} to start at {}?",
+        record.name, cascade.start
+    ))
+}
+
+/// The first merge conflict: what happened, then this side's and the disk's record
+pub fn merge_conflict_text(app: &AppState) -> Option<(String, String, String)> {
+    let conflict = app.merge_conflicts.first()?;
+    let side = |record: Option<&WorkRecord>| match record {
+        Some(record) if record.description.is_empty() => {
+            format!("{} {}-{}", record.name, record.start, record.end)
+        }
+        Some(record) => format!(
+            "{} {}-{} ({})",
+            record.name, record.start, record.end, record.description
+        ),
+        None => "deleted".to_string(),
+    };
+    Some((
+        format!(
+            "Record {} changed here and on disk ({} left)",
+            conflict.id,
+            app.merge_conflicts.len()
+        ),
+        side(conflict.mine.as_ref()),
+        side(conflict.disk.as_ref()),
+    ))
+}
+
+/// What Enter in the task picker will do
+pub fn task_picker_header(app: &AppState) -> &'static str {
+    if app.task_picker_create {
+        "Name the first record for this day"
+    } else if app.task_picker_switch {
+        "Switch the session to this task"
+    } else if app.input_buffer.is_empty() {
+        "Select existing task or type new name"
+    } else {
+        "Type to filter, or create new task"
+    }
+}
+
+/// "0 ·········██▄·█████······ 23": the day's worked minutes per hour, with `[ui] heat_strip`
+pub fn heat_strip(app: &AppState) -> Option<String> {
+    app.config.ui.heat_strip.then(|| {
+        format!(
+            "0 {} 23",
+            crate::report::strip(&crate::report::day_hours(&app.day_data))
+        )
+    })
+}
+
+/// One line of the summary panel
+#[derive(Debug, Clone, PartialEq)]
+pub enum SummaryLine<'a> {
+    Project {
+        label: String,
+        minutes: u32,
+        /// Billable part of `minutes`, once a record on the day is non-billable
+        billable: Option<u32>,
+        collapsed: bool,
+    },
+    Task {
+        name: String,
+        minutes: u32,
+        billable: Option<u32>,
+        /// Listed under a project rather than on its own
+        nested: bool,
+        budget: Option<&'a BudgetStatus>,
+        /// Note pinned to the task for the day
+        annotation: Option<&'a str>,
+    },
+}
+
+/// Summary lines for the day, grouped by project when any record has one
+///
+/// Collapsed projects contribute their total line only. With `[ui] group_by` set to
+/// `ticket` or `project` the lines are flat totals per group key instead.
+pub fn summary_lines(app: &AppState) -> Vec<SummaryLine<'_>> {
+    let group_by = app.config.ui.group_by;
+    let default = app.billable_default();
+    let split = app.day_data.has_non_billable(default);
+    let billable = |filter: &dyn Fn(&WorkRecord) -> bool| {
+        split.then(|| app.day_data.billable_minutes_where(default, filter))
+    };
+    let task_line =
+        |(name, minutes): (String, u32), project: Option<&Option<String>>| SummaryLine::Task {
+            billable: billable(&|record| {
+                record.group_key(group_by) == name
+                    && project.is_none_or(|project| record.project == *project)
+            }),
+            budget: app.budget_status_for(&name),
+            annotation: app.day_data.annotation(&name),
+            name,
+            minutes,
+            nested: project.is_some(),
+        };
+
+    if group_by != GroupBy::Name || !app.day_data.has_projects() {
+        return app
+            .day_data
+            .get_grouped_totals_by(group_by)
+            .into_iter()
+            .map(|task| task_line(task, None))
+            .collect();
+    }
+
+    let mut lines = Vec::new();
+    for project in app.day_data.get_project_totals() {
+        let collapsed = app.is_project_collapsed(project.label());
+        lines.push(SummaryLine::Project {
+            label: project.label().to_string(),
+            minutes: project.total_minutes,
+            billable: billable(&|record| record.project == project.project),
+            collapsed,
+        });
+        if !collapsed {
+            lines.extend(
+                project
+                    .tasks
+                    .iter()
+                    .cloned()
+                    .map(|task| task_line(task, Some(&project.project))),
+            );
+        }
+    }
+    lines
+}
@@ -0,0 +1,299 @@
+//! Save-time checks on a day's records
+//!
+//! Rules here look at records as they are about to be written and report the
+//! ones that look like data-entry mistakes. Callers decide whether to only warn
+//! or to fix them, according to `[validation]` in the config.
+
+use crate::integrations::extract_ticket_from_name;
+use crate::models::{TimePoint, WorkRecord};
+use crate::timer::TimerState;
+use time::Date;
+
+/// A started record whose end is still ahead of now (usually a placeholder)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FutureEnd {
+    pub record_id: u32,
+    pub end: TimePoint,
+}
+
+/// Records that have started but end more than `grace_minutes` after `now`
+///
+/// Records that haven't started yet are planned (a meeting entered in advance)
+/// and are left alone, as are records running past midnight.
+pub fn find_future_ends(
+    records: &[&WorkRecord],
+    now: TimePoint,
+    grace_minutes: u32,
+) -> Vec<FutureEnd> {
+    let now_minutes = now.to_minutes_since_midnight();
+    let mut found: Vec<FutureEnd> = records
+        .iter()
+        .filter(|record| record.start <= now && record.start <= record.end)
+        .filter(|record| record.end.to_minutes_since_midnight() > now_minutes + grace_minutes)
+        .map(|record| FutureEnd {
+            record_id: record.id,
+            end: record.end,
+        })
+        .collect();
+    found.sort_by_key(|future| future.record_id);
+    found
+}
+
+/// "#3 (18:00), #5 (17:30)" for a status line
+pub fn describe_future_ends(found: &[FutureEnd]) -> String {
+    found
+        .iter()
+        .map(|future| format!("#{} ({})", future.record_id, future.end))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Something a record lacks before it is ready for a timesheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordIssue {
+    NoDescription,
+    NoTicket,
+    ZeroDuration,
+}
+
+impl RecordIssue {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            RecordIssue::NoDescription => "no description",
+            RecordIssue::NoTicket => "no ticket",
+            RecordIssue::ZeroDuration => "zero duration",
+        }
+    }
+}
+
+/// Which of the `RecordIssue` checks apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequiredFields {
+    pub description: bool,
+    pub ticket: bool,
+    pub duration: bool,
+}
+
+/// What `record` is missing under `required`
+///
+/// Breaks need neither a description nor a ticket. Adjustments and records still
+/// in progress are allowed to be zero-length.
+pub fn record_issues(record: &WorkRecord, required: RequiredFields) -> Vec<RecordIssue> {
+    let mut issues = Vec::new();
+    let is_work = !record.is_break() && !record.is_adjustment();
+    if required.description && is_work && record.description.trim().is_empty() {
+        issues.push(RecordIssue::NoDescription);
+    }
+    if required.ticket && is_work && extract_ticket_from_name(&record.name).is_none() {
+        issues.push(RecordIssue::NoTicket);
+    }
+    if required.duration
+        && record.total_minutes == 0
+        && !record.in_progress
+        && !record.is_adjustment()
+    {
+        issues.push(RecordIssue::ZeroDuration);
+    }
+    issues
+}
+
+/// "no description, no ticket"
+pub fn describe_issues(issues: &[RecordIssue]) -> String {
+    issues
+        .iter()
+        .map(RecordIssue::describe)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// In-progress records on `date` that no running timer is linked to
+///
+/// A timer with `[timer] create_record_on_start` adds its record up front; one that
+/// was lost (crash, cleared timer file) leaves the record behind with no end.
+pub fn find_orphaned_in_progress(
+    records: &[&WorkRecord],
+    timer: Option<&TimerState>,
+    date: Date,
+) -> Vec<u32> {
+    let linked = timer
+        .filter(|timer| timer.source_record_date.unwrap_or(timer.date) == date)
+        .and_then(|timer| timer.source_record_id);
+    let mut found: Vec<u32> = records
+        .iter()
+        .filter(|record| record.in_progress && Some(record.id) != linked)
+        .map(|record| record.id)
+        .collect();
+    found.sort_unstable();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::TimerStatus;
+    use time::OffsetDateTime;
+    use time::macros::{date, datetime};
+
+    fn t(hour: u8, minute: u8) -> TimePoint {
+        TimePoint::new(hour, minute).unwrap()
+    }
+
+    fn record(id: u32, start: TimePoint, end: TimePoint) -> WorkRecord {
+        WorkRecord::new(id, format!("Task {}", id), start, end)
+    }
+
+    const ALL_REQUIRED: RequiredFields = RequiredFields {
+        description: true,
+        ticket: true,
+        duration: true,
+    };
+
+    #[test]
+    fn test_complete_record_has_no_issues() {
+        let mut r = record(1, t(9, 0), t(10, 0));
+        r.name = "PROJ-12 Review".to_string();
+        r.description = "Auth changes".to_string();
+        assert!(record_issues(&r, ALL_REQUIRED).is_empty());
+    }
+
+    #[test]
+    fn test_each_missing_field_is_reported() {
+        let mut r = record(1, t(9, 0), t(9, 0));
+        r.description = "  ".to_string();
+        assert_eq!(
+            record_issues(&r, ALL_REQUIRED),
+            [
+                RecordIssue::NoDescription,
+                RecordIssue::NoTicket,
+                RecordIssue::ZeroDuration
+            ]
+        );
+        assert_eq!(
+            describe_issues(&record_issues(&r, ALL_REQUIRED)),
+            "no description, no ticket, zero duration"
+        );
+
+        // Only the configured checks apply
+        let only_duration = RequiredFields {
+            description: false,
+            ticket: false,
+            duration: true,
+        };
+        assert_eq!(
+            record_issues(&r, only_duration),
+            [RecordIssue::ZeroDuration]
+        );
+        r.end = t(9, 30);
+        r.update_duration();
+        assert!(record_issues(&r, only_duration).is_empty());
+    }
+
+    #[test]
+    fn test_breaks_adjustments_and_running_records_are_exempt() {
+        let mut lunch = record(1, t(12, 0), t(12, 30));
+        lunch.name = "Break".to_string();
+        assert!(record_issues(&lunch, ALL_REQUIRED).is_empty());
+
+        let mut running = record(2, t(13, 0), t(13, 0));
+        running.name = "PROJ-1 Build".to_string();
+        running.description = "CI".to_string();
+        running.in_progress = true;
+        assert!(record_issues(&running, ALL_REQUIRED).is_empty());
+
+        let mut adjustment = record(3, t(17, 0), t(17, 0));
+        adjustment.adjustment_minutes = Some(-15);
+        assert!(record_issues(&adjustment, ALL_REQUIRED).is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_end_is_found() {
+        let r = record(1, t(13, 0), t(18, 0));
+        assert_eq!(
+            find_future_ends(&[&r], t(14, 30), 5),
+            vec![FutureEnd {
+                record_id: 1,
+                end: t(18, 0)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_grace_period() {
+        let r = record(1, t(13, 0), t(14, 35));
+        assert!(find_future_ends(&[&r], t(14, 30), 5).is_empty());
+        assert_eq!(find_future_ends(&[&r], t(14, 30), 4).len(), 1);
+
+        // Ending exactly now is never in the future
+        let r = record(2, t(13, 0), t(14, 30));
+        assert!(find_future_ends(&[&r], t(14, 30), 0).is_empty());
+    }
+
+    #[test]
+    fn test_multi_record_day() {
+        let done = record(1, t(9, 0), t(10, 0));
+        let placeholder = record(4, t(13, 0), t(18, 0));
+        let running_long = record(2, t(14, 0), t(16, 0));
+        let planned = record(3, t(16, 0), t(17, 0));
+        let records = [&done, &placeholder, &running_long, &planned];
+
+        let found = find_future_ends(&records, t(14, 30), 5);
+        let ids: Vec<u32> = found.iter().map(|future| future.record_id).collect();
+        assert_eq!(ids, vec![2, 4]);
+        assert_eq!(describe_future_ends(&found), "#2 (16:00), #4 (18:00)");
+    }
+
+    #[test]
+    fn test_planned_and_overnight_records_are_ignored() {
+        let planned = record(1, t(15, 0), t(16, 0));
+        let overnight = record(2, t(22, 0), t(1, 0));
+        assert!(find_future_ends(&[&planned, &overnight], t(14, 30), 0).is_empty());
+        assert!(find_future_ends(&[&overnight], t(23, 0), 0).is_empty());
+    }
+
+    fn running_timer(source_record_id: u32, source_record_date: Date) -> TimerState {
+        let start: OffsetDateTime = datetime!(2025-11-06 09:00 UTC);
+        TimerState {
+            id: None,
+            task_name: "Task".to_string(),
+            description: None,
+            start_time: start,
+            end_time: None,
+            date: start.date(),
+            status: TimerStatus::Running,
+            paused_duration_secs: 0,
+            pause_count: 0,
+            paused_at: None,
+            created_at: start,
+            updated_at: start,
+            source_record_id: Some(source_record_id),
+            source_record_date: Some(source_record_date),
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_in_progress_record_without_timer_is_orphaned() {
+        let day = date!(2025 - 11 - 06);
+        let done = record(1, t(8, 0), t(9, 0));
+        let mut running = record(2, t(9, 0), t(9, 0));
+        running.in_progress = true;
+        let mut abandoned = record(3, t(7, 0), t(7, 0));
+        abandoned.in_progress = true;
+        let records = [&done, &running, &abandoned];
+
+        assert_eq!(find_orphaned_in_progress(&records, None, day), vec![2, 3]);
+
+        let timer = running_timer(2, day);
+        assert_eq!(
+            find_orphaned_in_progress(&records, Some(&timer), day),
+            vec![3]
+        );
+
+        // The same id on another day's file isn't this record
+        let elsewhere = running_timer(2, date!(2025 - 11 - 05));
+        assert_eq!(
+            find_orphaned_in_progress(&records, Some(&elsewhere), day),
+            vec![2, 3]
+        );
+    }
+}
@@ -0,0 +1,1196 @@
+//! Headless rendering tests
+//!
+//! Every screen is rendered into ratatui's `TestBackend` and compared, as plain text,
+//! against a golden file in `tests/snapshots/`. Styles are not part of the contract,
+//! only the characters that end up on screen.
+//!
+//! When a layout change is intentional, regenerate the golden files with:
+//! `UPDATE_SNAPSHOTS=1 cargo test --test render_snapshots`
+
+use ratatui::{Terminal, backend::TestBackend};
+use std::fs;
+use std::path::PathBuf;
+use time::macros::{date, datetime};
+use work_tuimer::config::{Config, Locale, TaskBudget, TimeFormat, TotalsWindow, WeekStart};
+use work_tuimer::models::{DayData, GroupBy, RecordConflict, TimePoint, Truncated, WorkRecord};
+use work_tuimer::profile::Profile;
+use work_tuimer::storage::StorageManager;
+use work_tuimer::timer::{TimerState, TimerStatus};
+use work_tuimer::ui::{AppState, Clock, render};
+
+const NARROW: (u16, u16) = (80, 24);
+const WIDE: (u16, u16) = (120, 40);
+
+fn fixture_day() -> DayData {
+    let mut day = DayData::new(date!(2025 - 11 - 06));
+    let records = [
+        (
+            1,
+            "PROJ-101 Code review",
+            (9, 0),
+            (10, 30),
+            "Reviewed auth changes",
+        ),
+        (2, "Team meeting", (10, 30), (11, 0), ""),
+        (3, "Break", (12, 0), (12, 30), ""),
+        (4, "Feature dev", (12, 30), (15, 45), "Calendar modal"),
+    ];
+    for (id, name, (sh, sm), (eh, em), description) in records {
+        let mut record = WorkRecord::new(
+            id,
+            name.to_string(),
+            TimePoint::new(sh, sm).unwrap(),
+            TimePoint::new(eh, em).unwrap(),
+        );
+        record.description = description.to_string();
+        day.add_record(record);
+    }
+    day
+}
+
+/// Build an AppState with the built-in default config and a pinned clock
+fn fixture_app(day: DayData) -> AppState {
+    let mut app = AppState::with_config(day, Config::default());
+    app.clock = Clock::Fixed(datetime!(2025-11-06 16:00 UTC));
+    // Not whatever clock the machine's locale prefers
+    app.config.ui.time_format = Some(TimeFormat::H24);
+    app
+}
+
+fn running_timer() -> TimerState {
+    let start = datetime!(2025-11-06 15:45 UTC);
+    TimerState {
+        id: None,
+        task_name: "Feature dev".to_string(),
+        description: None,
+        start_time: start,
+        end_time: None,
+        date: start.date(),
+        status: TimerStatus::Running,
+        paused_duration_secs: 0,
+        pause_count: 0,
+        paused_at: None,
+        created_at: start,
+        updated_at: start,
+        source_record_id: Some(4),
+        source_record_date: Some(start.date()),
+        notes: Vec::new(),
+    }
+}
+
+/// Render a single frame and return the buffer as one string per terminal row
+fn render_lines(app: &AppState, (width, height): (u16, u16)) -> Vec<String> {
+    let backend = TestBackend::new(width, height);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal.draw(|f| render::render(f, app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            let mut line = String::new();
+            for x in 0..buffer.area.width {
+                line.push_str(buffer.get(x, y).symbol());
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+fn assert_snapshot(name: &str, lines: &[String]) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+    let actual = lines.join("\n") + "\n";
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {:?}; run with UPDATE_SNAPSHOTS=1 to create it",
+            path
+        )
+    });
+    assert_eq!(
+        expected, actual,
+        "Snapshot '{}' changed; run with UPDATE_SNAPSHOTS=1 if this is intended",
+        name
+    );
+}
+
+#[test]
+fn snapshot_main_screen() {
+    let app = fixture_app(fixture_day());
+    assert_snapshot("main_80x24", &render_lines(&app, NARROW));
+    assert_snapshot("main_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_main_screen_with_active_timer() {
+    let mut app = fixture_app(fixture_day());
+    app.active_timer = Some(running_timer());
+    assert_snapshot("timer_80x24", &render_lines(&app, NARROW));
+    assert_snapshot("timer_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_empty_day() {
+    let app = fixture_app(DayData::new(date!(2025 - 11 - 06)));
+    assert_snapshot("empty_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_fields_editor() {
+    use work_tuimer::config::FieldDef;
+
+    let mut app = fixture_app(fixture_day());
+    app.config.fields.insert(
+        "cost_center".to_string(),
+        FieldDef::Choice {
+            options: vec!["R&D".to_string(), "Ops".to_string()],
+        },
+    );
+    app.config
+        .fields
+        .insert("po_number".to_string(), FieldDef::Text);
+    app.open_fields_editor();
+    app.cycle_field_choice(true);
+    assert_snapshot("fields_editor_80x24", &render_lines(&app, NARROW));
+}
+
+#[test]
+fn snapshot_calendar_modal() {
+    let mut app = fixture_app(fixture_day());
+    app.open_calendar();
+    assert_snapshot("calendar_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_calendar_modal_sunday_start_in_german() {
+    let mut app = fixture_app(fixture_day());
+    app.config.ui.locale = Locale::De;
+    app.config.ui.week_start = WeekStart::Sunday;
+    app.config.ui.date_format = "%A, %e. %B %Y".to_string();
+    app.open_calendar();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("Donnerstag, 6. November 2025"),
+        "{}",
+        lines[1]
+    );
+    assert_snapshot("calendar_sunday_de_120x40", &lines);
+}
+
+#[test]
+fn duration_is_coloured_by_estimate() {
+    let mut day = fixture_day();
+    // Code review took 90 minutes, Feature dev 195
+    day.work_records.get_mut(&1).unwrap().estimate_minutes = Some(120);
+    day.work_records.get_mut(&4).unwrap().estimate_minutes = Some(180);
+    let app = fixture_app(day);
+    let theme = app.theme.clone();
+
+    let backend = TestBackend::new(WIDE.0, WIDE.1);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal.draw(|f| render::render(f, &app)).unwrap();
+    let buffer = terminal.backend().buffer();
+
+    // Colour of the first cell where `text` starts
+    let fg_of = |text: &str| {
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                let rest: String = (x..buffer.area.width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect();
+                if rest.starts_with(text) {
+                    return buffer.get(x, y).fg;
+                }
+            }
+        }
+        panic!("{} not rendered", text);
+    };
+
+    assert_eq!(fg_of("90/120m"), theme.success);
+    assert_eq!(fg_of("195/180m"), theme.error);
+    // Records without an estimate keep the plain duration
+    assert_eq!(fg_of("0h 30m"), theme.badge);
+}
+
+#[test]
+fn snapshot_flagged_records() {
+    let mut app = fixture_app(fixture_day());
+    app.selected_index = 1;
+    app.toggle_flag();
+    app.selected_index = 3;
+    app.toggle_flag();
+    app.jump_to_flag(true);
+    assert_eq!(app.selected_index, 1);
+
+    let lines = render_lines(&app, WIDE);
+    let flagged = lines.iter().filter(|line| line.contains('⚑')).count();
+    assert_eq!(flagged, 2, "{}", lines.join("\n"));
+    assert_snapshot("flagged_records_120x40", &lines);
+}
+
+#[test]
+fn snapshot_records_missing_fields() {
+    let mut app = fixture_app(fixture_day());
+    app.config.validation.require_description = true;
+
+    let lines = render_lines(&app, WIDE);
+    // Team meeting has no description; the break is exempt
+    let warned: Vec<&String> = lines.iter().filter(|line| line.contains('⚠')).collect();
+    assert_eq!(warned.len(), 1, "{}", lines.join("\n"));
+    assert!(warned[0].contains("Team meeting"));
+    assert_snapshot("missing_fields_120x40", &lines);
+}
+
+#[test]
+fn snapshot_non_billable_split() {
+    let mut app = fixture_app(fixture_day());
+    app.selected_index = 1;
+    app.toggle_billable();
+
+    let lines = render_lines(&app, WIDE);
+    let text = lines.join("\n");
+    assert!(text.contains("0h 30m nb"), "{}", text);
+    assert!(
+        text.contains("Total: 5h 45m · billable: 5h 15m"),
+        "{}",
+        text
+    );
+    assert!(text.contains("Billable"), "{}", text);
+    assert_snapshot("non_billable_120x40", &lines);
+}
+
+#[test]
+fn plain_non_billable_split() {
+    let mut app = plain_app(fixture_day());
+    app.selected_index = 1;
+    app.toggle_billable();
+
+    let lines = render_lines(&app, NARROW);
+    assert_no_decoration(&lines);
+    assert!(lines.contains(&"> 10:30-11:00  0h 30m  Team meeting (non-billable)".to_string()));
+    assert!(lines.contains(&"  Team meeting: 0h 30m, billable 0h 00m".to_string()));
+}
+
+#[test]
+fn snapshot_plan_audit() {
+    let mut day = fixture_day();
+    day.mark_planned();
+    // The meeting started late and ran over, the break was skipped, support came up
+    let meeting = day.work_records.get_mut(&2).unwrap();
+    meeting.start = TimePoint::new(10, 45).unwrap();
+    meeting.end = TimePoint::new(11, 30).unwrap();
+    meeting.update_duration();
+    day.remove_record(3);
+    day.add_record(WorkRecord::new(
+        5,
+        "Support".to_string(),
+        TimePoint::new(16, 0).unwrap(),
+        TimePoint::new(16, 30).unwrap(),
+    ));
+    let mut app = fixture_app(day);
+    app.toggle_plan_audit();
+
+    let lines = render_lines(&app, WIDE);
+    let text = lines.join("\n");
+    assert!(text.contains("+15m late, ran 15m over"), "{}", text);
+    assert!(text.contains("unplanned"), "{}", text);
+    assert!(text.contains("missed"), "{}", text);
+    assert!(
+        text.contains("Planned: 5h 45m, actual: 6h 00m, 1 missed"),
+        "{}",
+        text
+    );
+    assert_snapshot("plan_audit_120x40", &lines);
+}
+
+#[test]
+fn snapshot_command_palette() {
+    let mut app = fixture_app(fixture_day());
+    app.open_command_palette();
+    assert_snapshot("command_palette_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_command_palette_quick_tasks() {
+    let config: Config = toml::from_str(
+        r#"
+[[quick_tasks]]
+key = "1"
+name = "Standup"
+
+[[quick_tasks]]
+key = "2"
+name = "Email"
+"#,
+    )
+    .unwrap();
+    let mut app = AppState::with_config(fixture_day(), config);
+    app.clock = Clock::Fixed(datetime!(2025-11-06 16:00 UTC));
+    app.config.ui.time_format = Some(TimeFormat::H24);
+    app.open_command_palette();
+    let lines = render_lines(&app, WIDE);
+    assert!(lines.iter().any(|line| line.contains("1 Standup  2 Email")));
+    assert_snapshot("command_palette_quick_tasks_120x40", &lines);
+
+    app.config.ui.screen_reader = true;
+    let lines = render_lines(&app, NARROW);
+    assert!(lines.contains(&"Quick tasks: 1: Standup, 2: Email.".to_string()));
+}
+
+#[test]
+fn snapshot_task_picker() {
+    let mut app = fixture_app(fixture_day());
+    app.change_task_name();
+    assert_snapshot("task_picker_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_error_modal() {
+    let mut app = fixture_app(fixture_day());
+    app.last_error_message = Some("Failed to save: disk full".to_string());
+    assert_snapshot("error_80x24", &render_lines(&app, NARROW));
+}
+
+#[test]
+fn snapshot_main_screen_with_ids() {
+    let mut app = fixture_app(fixture_day());
+    app.config.ui.show_ids = true;
+    assert_snapshot("ids_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn snapshot_goto_id_prompt() {
+    let mut app = fixture_app(fixture_day());
+    app.config.ui.show_ids = true;
+    app.open_goto_id();
+    app.handle_goto_id_char('3');
+    assert_snapshot("goto_id_80x24", &render_lines(&app, NARROW));
+}
+
+#[test]
+fn snapshot_recent_days() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut storage = StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+    storage.save(&fixture_day()).unwrap();
+    let mut friday = DayData::new(date!(2025 - 10 - 31));
+    friday.add_record(WorkRecord::new(
+        1,
+        "Planning".to_string(),
+        TimePoint::new(9, 0).unwrap(),
+        TimePoint::new(10, 30).unwrap(),
+    ));
+    storage.save(&friday).unwrap();
+
+    let mut app = fixture_app(fixture_day());
+    app.open_recent_days(&storage).unwrap();
+    let lines = render_lines(&app, NARROW);
+    assert!(lines.iter().any(|l| l.contains("1 record · 1h 30m")));
+    assert_snapshot("recent_days_80x24", &lines);
+}
+
+#[test]
+fn snapshot_week_grid() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut storage = StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+    let mut monday = DayData::new(date!(2025 - 11 - 03));
+    monday.add_record(WorkRecord::new(
+        1,
+        "Planning".to_string(),
+        TimePoint::new(9, 0).unwrap(),
+        TimePoint::new(10, 30).unwrap(),
+    ));
+    storage.save(&monday).unwrap();
+
+    let mut app = fixture_app(fixture_day());
+    app.open_calendar();
+    app.open_week_grid(&storage).unwrap();
+    let lines = render_lines(&app, WIDE);
+    assert!(lines.iter().any(|l| l.contains("Mon 03")));
+    assert!(lines.iter().any(|l| l.contains("Planning")));
+    assert_snapshot("week_grid_120x40", &lines);
+}
+
+#[test]
+fn snapshot_week_grid_task_budgets() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut storage = StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+    let mut monday = DayData::new(date!(2025 - 11 - 03));
+    monday.add_record(WorkRecord::new(
+        1,
+        "Planning".to_string(),
+        TimePoint::new(9, 0).unwrap(),
+        TimePoint::new(10, 30).unwrap(),
+    ));
+    storage.save(&monday).unwrap();
+
+    let mut app = fixture_app(fixture_day());
+    app.config.task_budgets = vec![TaskBudget {
+        task: Some("Planning".to_string()),
+        weekly_hours: Some(1.0),
+        monthly_hours: Some(10.0),
+        ..TaskBudget::default()
+    }];
+    app.open_calendar();
+    app.open_week_grid(&storage).unwrap();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("Planning: 1h 30m of 1h 00m this week"))
+    );
+    assert_snapshot("week_grid_budgets_120x40", &lines);
+}
+
+/// Record 2 edited here while it was deleted on disk
+fn merge_conflict_app(mut app: AppState) -> AppState {
+    let mut mine = app.day_data.work_records[&2].clone();
+    mine.description = "Planning".to_string();
+    app.merge_conflicts = vec![RecordConflict {
+        id: 2,
+        mine: Some(mine),
+        disk: None,
+    }];
+    app.mode = work_tuimer::ui::AppMode::MergeConflict;
+    app
+}
+
+#[test]
+fn snapshot_merge_conflict() {
+    let app = merge_conflict_app(fixture_app(fixture_day()));
+    assert_snapshot("merge_conflict_80x24", &render_lines(&app, NARROW));
+}
+
+#[test]
+fn snapshot_relative_times() {
+    let mut app = fixture_app(fixture_day());
+    app.toggle_relative_times();
+    assert_snapshot("relative_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn relative_times_fall_back_to_absolute_on_other_days() {
+    let mut day = fixture_day();
+    day.date = date!(2025 - 11 - 05);
+    let mut app = fixture_app(day);
+    app.current_date = date!(2025 - 11 - 05);
+    app.toggle_relative_times();
+
+    let lines = render_lines(&app, WIDE);
+    assert!(lines.iter().any(|l| l.contains("09:00")));
+    assert!(!lines.iter().any(|l| l.contains(" ago")));
+}
+
+#[test]
+fn snapped_display_times_leave_records_exact() {
+    let mut day = fixture_day();
+    day.work_records.get_mut(&1).unwrap().start = TimePoint::new(9, 3).unwrap();
+    day.work_records.get_mut(&1).unwrap().end = TimePoint::new(10, 32).unwrap();
+    let mut app = fixture_app(day);
+    app.config.ui.snap_display_minutes = 5;
+
+    let lines = render_lines(&app, WIDE);
+    let row = lines.iter().find(|l| l.contains("Code rev")).unwrap();
+    assert!(row.contains("09:05") && row.contains("10:30"), "{}", row);
+    assert_eq!(
+        app.day_data.work_records[&1].start,
+        TimePoint::new(9, 3).unwrap()
+    );
+}
+
+#[test]
+fn snapshot_heat_strip() {
+    let mut app = fixture_app(fixture_day());
+    app.config.ui.heat_strip = true;
+    let lines = render_lines(&app, WIDE);
+    // 9:00-11:00 and 12:30-15:45 worked, the 12:00 break left out
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("0 ·········██·▄██▆········ 23"))
+    );
+    assert_snapshot("heat_strip_120x40", &lines);
+}
+
+#[test]
+fn snapshot_long_names_capped() {
+    let mut day = fixture_day();
+    day.work_records.get_mut(&1).unwrap().name =
+        "PROJ-101 Code review of the authentication rewrite and its migration plan".to_string();
+    let mut app = fixture_app(day);
+    app.config.ui.max_name_chars = 12;
+    let lines = render_lines(&app, WIDE);
+    assert!(lines.iter().any(|l| l.contains("PROJ-101 Co…  ")));
+    // The selected record's whole name sits under the table
+    assert!(lines.iter().any(|l| l.contains("and its migration plan ─")));
+    assert_snapshot("long_names_capped_120x40", &lines);
+}
+
+#[test]
+fn twelve_hour_clock_times() {
+    let mut app = fixture_app(fixture_day());
+    app.config.ui.time_format = Some(TimeFormat::H12);
+    let lines = render_lines(&app, WIDE);
+    let row = lines.iter().find(|l| l.contains("Calendar modal")).unwrap();
+    assert!(
+        row.contains("12:30 PM") && row.contains("3:45 PM"),
+        "{}",
+        row
+    );
+}
+
+#[test]
+fn snapshot_summary_grouped_by_ticket() {
+    let mut day = fixture_day();
+    day.work_records.get_mut(&4).unwrap().name = "PROJ-101 Feature dev".to_string();
+    let mut app = fixture_app(day);
+    app.config.ui.group_by = GroupBy::Ticket;
+    let lines = render_lines(&app, WIDE);
+    // 1h 30m of code review and 3h 15m of feature dev on the same ticket
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("PROJ-101") && l.contains("4h 45m"))
+    );
+    assert_snapshot("summary_by_ticket_120x40", &lines);
+}
+
+#[test]
+fn snapshot_records_filtered_by_summary_row() {
+    let mut day = fixture_day();
+    day.work_records.get_mut(&4).unwrap().name = "PROJ-101 Feature dev".to_string();
+    let mut app = fixture_app(day);
+    app.config.ui.group_by = GroupBy::Ticket;
+    app.config.ui.filtered_totals = true;
+    app.focus_summary();
+    assert_snapshot("summary_focused_120x40", &render_lines(&app, WIDE));
+
+    // The first summary row is PROJ-101
+    app.apply_summary_filter();
+    let lines = render_lines(&app, WIDE);
+    assert!(lines.iter().any(|l| l.contains("filtered: PROJ-101")));
+    assert!(lines.iter().any(|l| l.contains("4h 45m (filtered)")));
+    assert!(
+        !lines
+            .iter()
+            .any(|l| l.contains("Team meeting") && l.contains("10:30"))
+    );
+    assert_snapshot("records_filtered_120x40", &lines);
+}
+
+#[test]
+fn snapshot_project_summary_collapsed() {
+    let mut day = fixture_day();
+    for id in [1, 4] {
+        day.work_records.get_mut(&id).unwrap().project = Some("Acme".to_string());
+    }
+    let mut app = fixture_app(day);
+    assert_snapshot("projects_120x40", &render_lines(&app, WIDE));
+
+    // Selected row is record 1, which belongs to Acme
+    app.toggle_selected_project_collapsed();
+    assert_snapshot("projects_collapsed_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn timer_bar_uses_injected_clock() {
+    let mut app = fixture_app(fixture_day());
+    app.active_timer = Some(running_timer());
+    let lines = render_lines(&app, WIDE);
+
+    // Started 15:45, clock pinned at 16:00
+    assert!(lines[1].contains("Feature dev - 15:00"), "{}", lines[1]);
+}
+
+#[test]
+fn timer_bar_minutes_precision_drops_seconds() {
+    use work_tuimer::config::TimerPrecision;
+
+    let mut app = fixture_app(fixture_day());
+    app.config.ui.timer_precision = TimerPrecision::Minutes;
+    app.config.ui.timer_spinner = false;
+    app.active_timer = Some(running_timer());
+    let lines = render_lines(&app, WIDE);
+
+    // Started 15:45, clock pinned at 16:00
+    assert!(lines[1].contains("Feature dev - 00:15 |"), "{}", lines[1]);
+}
+
+#[test]
+fn timer_bar_spinner_ticks_only_while_running() {
+    let mut app = fixture_app(fixture_day());
+    app.active_timer = Some(running_timer());
+    app.frame = 3;
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("15:00 ⠸"), "{}", lines[1]);
+
+    app.config.ui.timer_spinner = false;
+    let lines = render_lines(&app, WIDE);
+    assert!(!lines[1].contains("⠸"), "{}", lines[1]);
+
+    app.config.ui.timer_spinner = true;
+    let mut timer = running_timer();
+    timer.status = TimerStatus::Paused;
+    timer.paused_at = Some(datetime!(2025-11-06 16:00 UTC));
+    app.active_timer = Some(timer);
+    let lines = render_lines(&app, WIDE);
+    assert!(!lines[1].contains("⠸"), "{}", lines[1]);
+}
+
+#[test]
+fn timer_bar_points_to_timer_day_when_viewing_another() {
+    let mut app = fixture_app(fixture_day());
+    app.active_timer = Some(running_timer());
+    app.current_date = date!(2025 - 11 - 07);
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("timer → 2025-11-06 #4"), "{}", lines[1]);
+}
+
+#[test]
+fn calendar_marks_timer_day() {
+    let mut app = fixture_app(fixture_day());
+    app.active_timer = Some(running_timer());
+    app.open_calendar();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines.iter().any(|l| l.contains(" 6▶")),
+        "{}",
+        lines.join("\n")
+    );
+}
+
+#[test]
+fn header_shows_current_date_at_all_sizes() {
+    let app = fixture_app(fixture_day());
+    for size in [NARROW, WIDE] {
+        let lines = render_lines(&app, size);
+        assert!(lines[1].contains("2025-11-06"), "{:?}: {}", size, lines[1]);
+    }
+}
+
+#[test]
+fn header_moves_below_timer_bar_when_active() {
+    let mut app = fixture_app(fixture_day());
+    let lines = render_lines(&app, WIDE);
+    assert!(!lines.iter().any(|l| l.contains("Feature dev - ")));
+
+    app.active_timer = Some(running_timer());
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("Feature dev"), "{}", lines[1]);
+    assert!(lines[4].contains("2025-11-06"), "{}", lines[4]);
+}
+
+#[test]
+fn footer_shows_current_mode() {
+    let mut app = fixture_app(fixture_day());
+    for size in [NARROW, WIDE] {
+        let lines = render_lines(&app, size);
+        let footer = &lines[size.1 as usize - 3];
+        assert!(footer.contains("BROWSE MODE"), "{:?}: {}", size, footer);
+    }
+
+    app.enter_visual_mode();
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[WIDE.1 as usize - 3].contains("VISUAL MODE"));
+}
+
+#[test]
+fn rendered_rows_fit_terminal_width() {
+    let app = fixture_app(fixture_day());
+    let wide = render_lines(&app, WIDE);
+    let narrow = render_lines(&app, NARROW);
+
+    assert!(wide.iter().any(|l| l.contains("Reviewed auth changes")));
+    assert!(
+        narrow
+            .iter()
+            .all(|l| l.chars().count() <= NARROW.0 as usize)
+    );
+}
+
+#[test]
+fn header_shows_named_profile() {
+    let mut app = fixture_app(fixture_day());
+    app.profile = Profile::named("acme").unwrap();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("WorkTimer [acme] - 2025-11-06"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn header_shows_safe_mode() {
+    let mut app = fixture_app(fixture_day());
+    app.safe_mode = true;
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("SAFE MODE"), "{}", lines[1]);
+}
+
+#[test]
+fn header_shows_day_breakdown_when_enabled() {
+    let mut config = Config::default();
+    config.ui.show_day_breakdown = true;
+    let mut app = AppState::with_config(fixture_day(), config);
+    app.clock = Clock::Fixed(datetime!(2025-11-06 16:00 UTC));
+
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("Worked: 5h 15m | Breaks: 0h 30m | Span: 6h 45m"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn header_names_the_totals_window() {
+    let mut app = fixture_app(fixture_day());
+    app.totals_window = TotalsWindow::Week;
+    app.window_worked_minutes = Some(32 * 60 + 10);
+
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("Last 7 days worked: 32h 10m"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn header_counts_down_to_next_record() {
+    let mut config = Config::default();
+    config.upcoming.enabled = true;
+    let mut app = AppState::with_config(fixture_day(), config);
+
+    app.clock = Clock::Fixed(datetime!(2025-11-06 10:07 UTC));
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("Team meeting in 23m | Total: 5h 45m"),
+        "{}",
+        lines[1]
+    );
+
+    // Breaks are skipped by default
+    app.clock = Clock::Fixed(datetime!(2025-11-06 11:30 UTC));
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("Feature dev in 1h"), "{}", lines[1]);
+
+    app.clock = Clock::Fixed(datetime!(2025-11-06 16:00 UTC));
+    let lines = render_lines(&app, WIDE);
+    assert!(!lines[1].contains("next:"), "{}", lines[1]);
+}
+
+#[test]
+fn footer_shows_status_message() {
+    let mut app = fixture_app(fixture_day());
+    app.status_message = Some("⚠ End time in the future: #4 (18:00)".to_string());
+    let lines = render_lines(&app, NARROW);
+    let footer = &lines[lines.len() - 2];
+    assert!(
+        footer.contains("End time in the future: #4 (18:00)"),
+        "{}",
+        footer
+    );
+}
+
+#[test]
+fn adjustments_are_labelled_and_clamped_total_warns() {
+    let mut day = fixture_day();
+    let mut credit = WorkRecord::new(
+        5,
+        "Billing correction".to_string(),
+        TimePoint::new(16, 0).unwrap(),
+        TimePoint::new(16, 0).unwrap(),
+    );
+    credit.adjustment_minutes = Some(-30);
+    day.add_record(credit.clone());
+
+    let app = fixture_app(day.clone());
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("Total: 5h 15m"), "{}", lines[1]);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("± Billing") && l.contains("-0h 30m"))
+    );
+
+    credit.adjustment_minutes = Some(-24 * 60);
+    day.add_record(credit);
+    let app = fixture_app(day);
+    let lines = render_lines(&app, WIDE);
+    assert!(lines[1].contains("⚠"), "{}", lines[1]);
+    assert!(lines[1].contains("Total: 0h 00m"), "{}", lines[1]);
+}
+
+#[test]
+fn budget_percent_and_timer_banner() {
+    use work_tuimer::storage::StorageManager;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut storage = StorageManager::new_with_dir(temp_dir.path().to_path_buf()).unwrap();
+    let mut earlier = DayData::new(date!(2025 - 11 - 05));
+    earlier.add_record(WorkRecord::new(
+        1,
+        "PROJ-101 Code review".to_string(),
+        TimePoint::new(9, 0).unwrap(),
+        TimePoint::new(12, 0).unwrap(),
+    ));
+    storage.save(&earlier).unwrap();
+
+    // 3h yesterday + 1h30m today = 270m of 300m
+    let mut app = fixture_app(fixture_day());
+    app.config.budgets.insert("PROJ-".to_string(), 300);
+    app.refresh_budgets(&storage).unwrap();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("PROJ-101") && l.contains("1h 30m 90%")),
+        "{}",
+        lines.join("\n")
+    );
+
+    let mut timer = running_timer();
+    timer.task_name = "PROJ-101 Code review".to_string();
+    timer.source_record_id = None;
+    app.active_timer = Some(timer);
+    app.refresh_budgets(&storage).unwrap();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("⚠ PROJ- at 95% of budget"),
+        "{}",
+        lines[1]
+    );
+
+    app.config.budgets.insert("PROJ-".to_string(), 240);
+    app.refresh_budgets(&storage).unwrap();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines[1].contains("PROJ- over budget (4h 45m / 4h 00m)"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn records_with_links_show_indicator() {
+    let mut day = fixture_day();
+    let mut record = day.work_records[&2].clone();
+    record.url = Some("https://docs.example.com/agenda".to_string());
+    day.add_record(record);
+
+    let app = fixture_app(day);
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("🔗") && l.contains("Team meeting")),
+        "{}",
+        lines.join("\n")
+    );
+    assert_eq!(lines.iter().filter(|l| l.contains("🔗")).count(), 1);
+}
+
+/// Config routing PROJ tickets to Jira; OPS tickets match no tracker
+fn jira_config() -> Config {
+    toml::from_str(
+        r#"
+[ui]
+time_format = "24h"
+
+[integrations.trackers.jira]
+enabled = true
+base_url = "https://test.atlassian.net"
+ticket_patterns = ["^PROJ-\\d+$"]
+browse_url = "{base_url}/browse/{ticket}"
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn ticket_badge_names_tracker_and_footer_shows_url() {
+    let mut app = fixture_app(fixture_day());
+    app.config = jira_config();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("🎫 jira") && l.contains("PROJ-101")),
+        "{}",
+        lines.join("\n")
+    );
+    assert_eq!(lines.iter().filter(|l| l.contains("🎫")).count(), 1);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("jira: https://test.atlassian.net/browse/PROJ-101")),
+        "{}",
+        lines.join("\n")
+    );
+
+    // A ticket no tracker matches is called out instead of a URL
+    app.day_data.work_records.get_mut(&1).unwrap().name = "OPS-7 Code review".to_string();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("OPS-7: no tracker matches"))
+    );
+    assert!(!lines.iter().any(|l| l.contains("🎫 jira")));
+
+    app.config.ui.screen_reader = true;
+    app.day_data.work_records.get_mut(&1).unwrap().name = "PROJ-101 Code review".to_string();
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines.contains(&"T opens jira: https://test.atlassian.net/browse/PROJ-101".to_string())
+    );
+}
+
+#[test]
+fn snapshot_name_badges_in_browse_and_edit() {
+    let mut app = fixture_app(fixture_day());
+    app.config = jira_config();
+    app.config.validation.require_description = true;
+    app.day_data.work_records.get_mut(&4).unwrap().url =
+        Some("https://example.com/pr/9".to_string());
+    app.selected_index = 3;
+    app.toggle_flag();
+    app.selected_index = 0;
+    assert_snapshot("badges_browse_120x40", &render_lines(&app, WIDE));
+
+    // Typing a name shows the badge its ticket would get
+    app.enter_edit_mode();
+    app.input_buffer = "PROJ-102 Pairing".to_string();
+    assert_snapshot("badges_edit_name_120x40", &render_lines(&app, WIDE));
+
+    app.next_field();
+    assert_snapshot("badges_edit_start_120x40", &render_lines(&app, WIDE));
+    app.next_field();
+    app.next_field();
+    assert_snapshot("badges_edit_description_120x40", &render_lines(&app, WIDE));
+
+    // Markers and the link badge make way while a row is edited
+    app.exit_edit_mode();
+    app.selected_index = 3;
+    app.next_field();
+    app.enter_edit_mode();
+    assert_snapshot("badges_edit_linked_120x40", &render_lines(&app, WIDE));
+}
+
+#[test]
+fn url_notice_shows_full_url() {
+    use work_tuimer::ui::app_state::UrlNotice;
+
+    let mut app = fixture_app(fixture_day());
+    app.url_notice = Some(UrlNotice {
+        message: "Could not open browser (not found); URL copied to clipboard".to_string(),
+        url: "https://test.atlassian.net/browse/PROJ-101".to_string(),
+    });
+    let lines = render_lines(&app, NARROW);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("https://test.atlassian.net/browse/PROJ-101")),
+        "{}",
+        lines.join("\n")
+    );
+    assert!(lines.iter().any(|l| l.contains("URL copied to clipboard")));
+}
+
+#[test]
+fn revert_field_lists_recent_edits() {
+    use work_tuimer::ui::EditField;
+
+    let mut app = fixture_app(fixture_day());
+    app.edit_field = EditField::Start;
+    app.input_buffer = "09:15".to_string();
+    app.save_edit().unwrap();
+    app.open_revert_field();
+
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines.iter().any(|l| l.contains("Start: 09:00 → 09:15 (")),
+        "{}",
+        lines.join("\n")
+    );
+
+    app.config.ui.screen_reader = true;
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("Start changed from 09:00 to 09:15 at ")),
+        "{}",
+        lines.join("\n")
+    );
+}
+
+#[test]
+fn summary_shows_task_annotations() {
+    let mut day = fixture_day();
+    day.set_annotation("Team meeting", "ran long");
+
+    let app = fixture_app(day.clone());
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("Team meeting") && l.contains("0h 30m ran")),
+        "{}",
+        lines.join("\n")
+    );
+
+    let app = plain_app(day);
+    let lines = render_lines(&app, WIDE);
+    assert!(lines.contains(&"  Team meeting: 0h 30m, note: ran long".to_string()));
+}
+
+/// Fail if the plain renderer drew anything a screen reader would spell out
+fn assert_no_decoration(lines: &[String]) {
+    for line in lines {
+        for c in line.chars() {
+            let decorative = ('\u{2500}'..='\u{259F}').contains(&c) // box drawing, blocks
+                || ('\u{2190}'..='\u{21FF}').contains(&c) // arrows
+                || ('\u{2300}'..='\u{23FF}').contains(&c) // ⏱ ⏸
+                || ('\u{25A0}'..='\u{27BF}').contains(&c) // shapes, ▶ ☕ ⚠ ⛔
+                || ('\u{2800}'..='\u{28FF}').contains(&c) // spinner
+                || c >= '\u{1F000}'; // emoji
+            assert!(!decorative, "{:?} in plain line {:?}", c, line);
+        }
+    }
+}
+
+fn plain_app(day: DayData) -> AppState {
+    let mut app = fixture_app(day);
+    app.config.ui.screen_reader = true;
+    app
+}
+
+#[test]
+fn snapshot_plain_main_screen() {
+    let mut app = plain_app(fixture_day());
+    app.active_timer = Some(running_timer());
+    app.selected_index = 1;
+    let lines = render_lines(&app, NARROW);
+    assert_no_decoration(&lines);
+    assert!(lines.contains(&"> 10:30-11:00  0h 30m  Team meeting".to_string()));
+    assert!(lines.contains(
+        &"  09:00-10:30  1h 30m  PROJ-101 Code review — Reviewed auth changes".to_string()
+    ));
+    assert_snapshot("plain_timer_80x24", &lines);
+}
+
+#[test]
+fn snapshot_plain_command_palette() {
+    let mut app = plain_app(fixture_day());
+    app.open_command_palette();
+    let lines = render_lines(&app, NARROW);
+    assert_no_decoration(&lines);
+    assert_eq!(lines[0], "WorkTimer 2025-11-06. Command palette mode.");
+    assert_snapshot("plain_command_palette_80x24", &lines);
+}
+
+#[test]
+fn plain_modals_are_prompts() {
+    let mut app = plain_app(fixture_day());
+    app.last_error_message = Some("Failed to save: disk full".to_string());
+    let lines = render_lines(&app, WIDE);
+    assert_no_decoration(&lines);
+    assert_eq!(
+        lines[1],
+        "Error: Failed to save: disk full. Press any key to dismiss."
+    );
+
+    let mut app = plain_app(fixture_day());
+    app.open_calendar();
+    let lines = render_lines(&app, WIDE);
+    assert_no_decoration(&lines);
+    assert!(lines.contains(&"> Choose date: 2025-11-06, Thursday, today".to_string()));
+    // The prompt has focus, so no record is marked
+    assert_eq!(lines.iter().filter(|l| l.starts_with('>')).count(), 1);
+
+    let mut app = plain_app(fixture_day());
+    app.change_task_name();
+    let lines = render_lines(&app, WIDE);
+    assert_no_decoration(&lines);
+    assert!(lines.contains(&"Task name: empty".to_string()));
+}
+
+#[test]
+fn plain_merge_conflict_names_both_sides() {
+    let app = merge_conflict_app(plain_app(fixture_day()));
+    let lines = render_lines(&app, WIDE);
+    assert_no_decoration(&lines);
+    assert!(lines.contains(&"> Record 2 changed here and on disk (1 left).".to_string()));
+    assert!(lines.contains(&"Mine: Team meeting 10:30-11:00 (Planning).".to_string()));
+    assert!(lines.contains(&"Disk: deleted.".to_string()));
+}
+
+#[test]
+fn snapshot_large_day_scrolled_and_truncated() {
+    let mut day = DayData::new(date!(2025 - 11 - 06));
+    for id in 1..=40u32 {
+        let minutes = 8 * 60 + id * 15;
+        day.add_record(WorkRecord::new(
+            id,
+            format!("Import {}", id),
+            TimePoint::from_minutes_since_midnight(minutes).unwrap(),
+            TimePoint::from_minutes_since_midnight(minutes + 15).unwrap(),
+        ));
+    }
+    day.truncated = Some(Truncated {
+        kept: 40,
+        total: 7200,
+    });
+    let mut app = fixture_app(day);
+    app.selected_index = 33;
+    assert_snapshot("large_day_truncated_80x24", &render_lines(&app, NARROW));
+}
+
+#[test]
+fn plain_mode_scrolls_to_the_selected_record() {
+    let mut day = DayData::new(date!(2025 - 11 - 06));
+    for id in 1..=30u32 {
+        let hour = (id / 2) as u8;
+        let minute = if id % 2 == 0 { 0 } else { 30 };
+        day.add_record(WorkRecord::new(
+            id,
+            format!("Task {}", id),
+            TimePoint::new(hour, minute).unwrap(),
+            TimePoint::new(hour, minute + 15).unwrap(),
+        ));
+    }
+    let mut app = plain_app(day);
+    app.selected_index = 29;
+    let lines = render_lines(&app, NARROW);
+    assert_no_decoration(&lines);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.starts_with("> ") && l.ends_with("Task 30"))
+    );
+}
+
+#[test]
+fn save_error_replaces_key_hints() {
+    let mut app = fixture_app(fixture_day());
+    app.save_error = Some("Changes are not being saved: disk full".to_string());
+    let lines = render_lines(&app, WIDE);
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains("⚠ Changes are not being saved: disk full")),
+        "{}",
+        lines.join("\n")
+    );
+    assert!(!lines.iter().any(|l| l.contains("q: Quit")));
+}
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use time::OffsetDateTime;
+use time::macros::datetime;
+use work_tuimer::config::Config;
+use work_tuimer::models::{DayData, TimePoint, WorkRecord};
+use work_tuimer::serve::respond;
+use work_tuimer::storage::{Storage, StorageManager};
+
+const STATUS: &str = "GET /status HTTP/1.1\r\nHost: localhost:4877\r\n\r\n";
+const TODAY: &str = "GET /today HTTP/1.1\r\nHost: localhost:4877\r\n\r\n";
+
+fn body(request: &str, storage: &Storage, now: OffsetDateTime) -> Value {
+    let response = respond(request, storage, &Config::default(), now);
+    assert_eq!(response.status, 200, "{}", response.body);
+    serde_json::from_str(&response.body).unwrap()
+}
+
+#[test]
+fn test_status_reports_the_running_session() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let manager = StorageManager::new_with_dir(temp_dir.path().to_path_buf())?;
+    let storage = Storage::new_with_dir(temp_dir.path().to_path_buf())?;
+
+    assert_eq!(
+        body(STATUS, &storage, OffsetDateTime::now_utc()),
+        json!({ "active": false })
+    );
+
+    let timer = manager.start_timer("PROJ-42 Code review".to_string(), None, None, None)?;
+    let now = timer.start_time + time::Duration::seconds(90);
+    assert_eq!(
+        body(STATUS, &storage, now),
+        json!({
+            "active": true,
+            "task": "PROJ-42 Code review",
+            "ticket": "PROJ-42",
+            "status": "running",
+            "elapsed_seconds": 90,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_today_reports_the_day_breakdown() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let storage = Storage::new_with_dir(temp_dir.path().to_path_buf())?;
+    let now = datetime!(2025-11-06 16:00 UTC);
+
+    let mut day = DayData::new(now.date());
+    for (id, name, start, end) in [(1, "Code review", 9, 11), (2, "Break", 11, 12)] {
+        day.add_record(WorkRecord::new(
+            id,
+            name.to_string(),
+            TimePoint::new(start, 0).unwrap(),
+            TimePoint::new(end, 0).unwrap(),
+        ));
+    }
+    storage.save(&day)?;
+
+    assert_eq!(
+        body(TODAY, &storage, now),
+        json!({
+            "date": "2025-11-06",
+            "worked_minutes": 120,
+            "break_minutes": 60,
+            "span_minutes": 180,
+            "billable_minutes": 180,
+            "records": 2,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_responses_are_http() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let storage = Storage::new_with_dir(temp_dir.path().to_path_buf())?;
+
+    let response = respond(
+        "GET /missing HTTP/1.1\r\n\r\n",
+        &storage,
+        &Config::default(),
+        OffsetDateTime::now_utc(),
+    );
+    assert_eq!(
+        response.to_http(),
+        "HTTP/1.1 404 Not Found\r\nContent-Type: application/json\r\nContent-Length: 38\r\nConnection: close\r\n\r\n{\"error\":\"No such endpoint: /missing\"}"
+    );
+    Ok(())
+}
@@ -40,7 +40,7 @@ fn test_timer_lifecycle_start_stop() -> Result<()> {
     thread::sleep(Duration::from_millis(100));
 
     // Stop the timer
-    let record = manager.stop_timer()?;
+    let record = manager.stop_timer()?.session;
 
     assert_eq!(record.name, "Integration Test Task");
     assert_eq!(record.description, "Testing timer lifecycle");
@@ -91,7 +91,7 @@ fn test_timer_pause_resume() -> Result<()> {
     );
 
     // Stop and verify
-    let record = manager.stop_timer()?;
+    let record = manager.stop_timer()?.session;
     assert_eq!(record.name, "Pausable Task");
 
     Ok(())
@@ -123,7 +123,7 @@ fn test_timer_with_source_record() -> Result<()> {
 
     // Stop and verify the link is preserved
     thread::sleep(Duration::from_millis(50));
-    let record = manager.stop_timer()?;
+    let record = manager.stop_timer()?.session;
 
     assert_eq!(record.name, "Original Task");
     assert_eq!(record.description, "Continuing work");
@@ -145,7 +145,7 @@ fn test_end_to_end_workflow_timer_to_saved_record() -> Result<()> {
     thread::sleep(Duration::from_millis(100));
 
     // 3. Stop timer - this automatically saves the record to day data
-    let record = manager.stop_timer()?;
+    let record = manager.stop_timer()?.session;
     assert_eq!(record.name, "Full Workflow Task");
 
     // 4. Load day data with tracking to verify the record was saved
@@ -353,3 +353,41 @@ fn test_load_with_tracking_updates_internal_state() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_unknown_fields_survive_load_and_save() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut manager = StorageManager::new_with_dir(temp_dir.path().to_path_buf())?;
+    let today = OffsetDateTime::now_utc().date();
+    let path = temp_dir.path().join(format!("{}.json", today));
+
+    // A day written by a newer version, with fields this one doesn't know
+    let mut day = DayData::new(today);
+    day.add_record(create_test_record(1, "Code review", 9, 10));
+    day.add_record(create_test_record(2, "Standup", 10, 11));
+    let mut json = serde_json::to_value(&day)?;
+    json["day_notes"] = "from the future".into();
+    json["work_records"]["1"]["tags"] = serde_json::json!(["review"]);
+    json["work_records"]["1"]["mood"] = serde_json::json!({"score": 4});
+    json["work_records"]["2"]["notes"] = "short one".into();
+    std::fs::write(&path, json.to_string())?;
+
+    // Edit in place, replace a record with a fresh one, and continue one with the timer
+    let mut day = manager.load_with_tracking(today)?;
+    day.work_records.get_mut(&1).unwrap().description = "edited".to_string();
+    manager.save(&day)?;
+    manager.update_record(today, create_test_record(2, "Standup", 10, 12))?;
+    manager.start_timer("Code review".to_string(), None, Some(1), Some(today))?;
+    manager.stop_timer()?;
+
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(saved["day_notes"], "from the future");
+    let records = &saved["work_records"];
+    assert_eq!(records["1"]["description"], "edited");
+    assert_eq!(records["1"]["tags"], serde_json::json!(["review"]));
+    assert_eq!(records["1"]["mood"]["score"], 4);
+    assert_eq!(records["2"]["total_minutes"], 120);
+    assert_eq!(records["2"]["notes"], "short one");
+
+    Ok(())
+}